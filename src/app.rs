@@ -1,9 +1,14 @@
 use crate::input::CommandRegistry;
-use crate::models::{Project, ProjectType};
+use crate::models::change::{TaskChange, diff_project_tasks};
+use crate::models::{Project, ProjectType, Task};
 use crate::ui::dialogs::DialogType;
+use crate::ui::image::{ImagePlacement, ImageProtocol};
 use crate::ui::layout::{Direction, SplitNode};
+use crate::ui::text_field::TextField;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::time::Instant;
 
 /// 调试日志辅助函数
@@ -35,29 +40,54 @@ pub enum NotificationLevel {
     Error,
 }
 
+/// 尚未解决的保存失败，`:write` 据此重试或再次提示
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveError {
+    /// 写入 config.toml 失败；内存中的配置仍是修改后的，可以重试
+    Config(String),
+    /// 后台保存界面状态失败；可以重新提交
+    State(String),
+    /// 任务或项目文件写入失败；修改已回滚，只能提示
+    Files(String),
+}
+
+/// 未绑定按键提示的显示时长
+const KEY_FLASH_DURATION: std::time::Duration = std::time::Duration::from_millis(1200);
+
 /// 通知消息
 #[derive(Debug, Clone)]
 pub struct Notification {
     pub message: String,
     pub level: NotificationLevel,
     pub created_at: Instant,
+    /// 通知显示期间按 `u` 可以撤销的移动（任务完成提示）
+    pub undo: Option<UndoMove>,
 }
 
 impl Notification {
-    /// 检查通知是否已过期（3秒后自动消失）
+    /// 检查通知是否已过期（3秒后自动消失，可撤销的完成提示保留 5 秒）
     pub fn is_expired(&self) -> bool {
-        self.created_at.elapsed().as_secs() >= 3
+        let seconds = if self.undo.is_some() { 5 } else { 3 };
+        self.created_at.elapsed().as_secs() >= seconds
     }
 }
 
+/// 可撤销的任务移动：把任务恢复成移动前的样子
+#[derive(Debug, Clone)]
+pub struct UndoMove {
+    pub project_id: String,
+    /// 移动前的任务（包括进入新状态时自动化规则改掉的字段）
+    pub task: Task,
+}
+
 /// 应用模式
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum Mode {
     /// 正常模式 - 导航和查看
     Normal,
-    // /// 命令模式 - 输入命令 (已注释)
-    // Command,
+    /// 命令模式 - 输入命令
+    Command,
     /// 任务选择模式
     TaskSelect,
     /// 对话框模式
@@ -74,6 +104,14 @@ pub enum Mode {
     StatusSelect,
     /// 标记管理模式 - X 快捷
     MarkSelect,
+    /// 快速添加模式 - 在当前列底部连续输入任务标题
+    QuickAdd,
+    /// 标签概览 - :tags
+    TagOverview,
+    /// 发送任务到其他面板 - Space t >
+    SendToPane,
+    /// 就地编辑列标题中的状态显示名 - 列标题上按 r / Space s e
+    StatusRename,
 }
 
 /// 空格菜单状态
@@ -89,6 +127,8 @@ pub enum MenuState {
     Task,
     /// 状态管理子菜单
     Status,
+    /// 卡片颜色子菜单（任务子菜单中按 c 进入）
+    Color,
 }
 
 /// 应用状态
@@ -105,14 +145,54 @@ pub struct App {
     pub key_buffer: Vec<char>,
     /// 按键缓冲区开始等待的时间（用于区分单键和多键序列）
     pub key_buffer_started_at: Option<std::time::Instant>,
+    /// 未绑定按键的短暂提示（内容, 出现时间）
+    pub key_flash: Option<(String, Instant)>,
     /// 每个面板选中的任务索引
     pub selected_task_index: HashMap<usize, usize>,
-    /// 已标记的任务（项目名、任务 ID）
+    /// 已标记的任务（项目 ID、任务 ID）
     pub marked_tasks: HashSet<(String, u32)>,
+    /// 在看板中展开显示正文的任务（项目 ID、任务 ID）
+    pub expanded_tasks: HashSet<(String, u32)>,
     /// 每个面板选中的列 (0=todo, 1=doing, 2=done)
     pub selected_column: HashMap<usize, usize>,
+    /// 聚焦单列显示的面板（临时状态，不保存）
+    pub zoomed_pane: Option<usize>,
+    /// 每个面板的优先级筛选（临时状态，不保存）
+    pub priority_filters: HashMap<usize, PriorityFilter>,
+    /// 每个面板的标签筛选（临时状态，不保存）
+    pub tag_filters: HashMap<usize, String>,
+    /// 每个面板的列宽策略（保存到状态文件）
+    pub column_layouts: HashMap<usize, ColumnLayout>,
+    /// 锁定为只读的面板（保存到状态文件），其中的项目不能被修改或更换
+    pub read_only_panes: HashSet<usize>,
+    /// 最近一次检测到的外部修改（:revert-external 可撤销一次）
+    pub external_changes: Vec<TaskChange>,
+    /// 各项目最近一次从磁盘加载的时间（项目 ID → 时间），显示在面板底边
+    pub loaded_at: HashMap<String, chrono::DateTime<chrono::Local>>,
+    /// 磁盘 IO 工作线程：后台重新加载项目、保存界面状态
+    pub io: crate::worker::IoWorker,
+    /// 会话中的状态自动保存
+    pub autosave: crate::state::Autosave,
+    /// 正在后台执行的耗时操作，执行期间显示进度对话框
+    pub job: Option<crate::job::Job>,
+    /// 进出文本输入时自动切换输入法
+    pub ime: crate::input::ime::ImeState,
+    /// 专注模式（:focus）：隐藏完成列，其他面板折叠为窄条（临时状态，不保存）
+    pub focus_mode: bool,
+    /// 屏幕共享模式（:redact）：隐藏所有面板的任务标题和正文（临时状态，不保存）
+    pub redact: bool,
+    /// `:diff-panes` 找出的在另一个面板中没有对应任务的卡片：(项目 ID, 任务 ID)
+    pub pane_diff: HashSet<(String, u32)>,
+    /// 本次会话中最近创建或编辑的任务：项目 ID -> 任务 ID（`:last` / `g;` 跳回）
+    pub last_edited: HashMap<String, u32>,
+    /// 最近一次按键的时间（无操作隐藏看板用）
+    pub last_input_at: Instant,
+    /// 看板已隐藏（无操作超时或 `:lock`），按任意键恢复
+    pub screen_locked: bool,
+    /// Space w g 抓取的面板，等待方向键与相邻面板交换项目
+    pub grabbed_pane: Option<usize>,
     /// 命令输入缓冲
-    pub command_input: String,
+    pub command_input: TextField,
     /// 命令补全选中的索引
     #[allow(dead_code)]
     pub completion_selected_index: Option<usize>,
@@ -133,28 +213,157 @@ pub struct App {
     pub is_new_task_file: bool,
     /// 待预览的文件路径（用于外部预览工具调用）
     pub pending_preview_file: Option<String>,
+    /// 待打开 shell 的目录（:shell，由主循环暂停界面后启动）
+    pub pending_shell_dir: Option<PathBuf>,
+    /// 预览模式的标题（任务预览 / 变更日志）
+    pub preview_title: String,
+    /// 预览标题栏中的附加信息（任务的自定义字段）
+    pub preview_header: String,
     /// 预览模式的内容
     pub preview_content: String,
     /// 预览模式的滚动位置
     pub preview_scroll: u16,
+    /// 预览中选中的链接序号（Tab 切换，Enter 打开）
+    pub preview_link: Option<usize>,
+    /// 列底部快速添加的输入框（`o`）
+    pub quick_add_input: TextField,
+    /// 就地编辑状态显示名的输入框，显示在聚焦列的标题处
+    pub status_rename_input: TextField,
+    /// 列标题处于选中状态的面板（在第一个任务上按 k 进入，j 回到任务）
+    pub column_header_focus: Option<usize>,
+    /// 预览内容中相对图片路径的基准目录（任务文件所在目录）
+    pub preview_base_dir: Option<PathBuf>,
+    /// 启动时检测到的终端图形协议（未启用内联图片时为 None）
+    pub image_protocol: Option<ImageProtocol>,
+    /// 本帧预览中需要绘制的图片
+    pub preview_images: Vec<ImagePlacement>,
+    /// 终端上当前已绘制的图片，位置不变时不重复发送
+    pub shown_images: Vec<ImagePlacement>,
     /// 命令注册表
     pub command_registry: CommandRegistry,
     /// 应用配置
     pub config: crate::config::Config,
     /// 是否显示首次运行欢迎对话框
     pub show_welcome_dialog: bool,
+    /// 是否显示键位速查浮层（Ctrl+/，临时状态）
+    pub show_cheat_sheet: bool,
     /// 最大化前的窗口布局（用于恢复）
     pub saved_layout: Option<SplitNode>,
+    /// 主页布局（`:home-set` 保存，`:home` / `Space w 0` 恢复）
+    pub home_layout: Option<crate::state::HomeLayout>,
+    /// 项目选择器中折叠的分组
+    pub collapsed_project_groups: std::collections::BTreeSet<String>,
+    /// 本次启动前运行的旧版本（升级后才有，`:changelog` 显示此后的发布说明）
+    pub upgraded_from: Option<String>,
     /// 通知消息
     pub notification: Option<Notification>,
+    /// 最近一次尚未解决的保存失败
+    pub last_save_error: Option<SaveError>,
     /// 最后一次列宽调整的时间（用于控制百分比显示）
     pub last_column_resize_time: Option<std::time::Instant>,
-    /// 每个面板的任务列表滚动状态（pane_id -> ListState）
-    pub list_states: HashMap<usize, ratatui::widgets::ListState>,
+    /// 每列任务列表的滚动位置（(pane_id, 列) -> 第一个可见任务）
+    pub list_offsets: HashMap<(usize, usize), usize>,
+    /// 每个面板每列上次选中的任务 (面板, 列) -> (任务 ID, 索引)，h/l 回到该列时恢复
+    pub column_selections: HashMap<(usize, usize), (Option<u32>, usize)>,
+    /// 上一帧构建的任务卡片，任务没有变化时复用
+    pub card_cache: crate::ui::virtual_list::CardCache,
     /// 搜索状态
     pub search_state: Option<SearchState>,
     /// 状态选择状态 (s 快捷)
     pub status_select_state: Option<StatusSelectState>,
+    /// 标签概览（:tags）
+    pub tag_overview: Option<crate::ui::tag_overview::TagOverview>,
+    /// 发送任务到其他面板（Space t >）
+    pub send_to_pane: Option<crate::ui::send_to_pane::SendToPane>,
+    /// 交互式教程（:tutor）
+    pub tutor: Option<crate::ui::tutor::Tutor>,
+    /// 拼写检查的词表（开启 spell_check 后首次显示标题输入框或预览时加载）
+    pub spell_checker: Option<crate::ui::spell::SpellChecker>,
+}
+
+/// 面板的优先级筛选（1/2/3 切换）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriorityFilter {
+    /// 显示全部任务
+    #[default]
+    All,
+    /// 只显示中、高优先级
+    Medium,
+    /// 只显示高优先级
+    High,
+}
+
+impl PriorityFilter {
+    /// 任务是否满足筛选条件（未设置优先级按最低处理）
+    pub fn matches(self, task: &Task) -> bool {
+        match self {
+            PriorityFilter::All => true,
+            PriorityFilter::Medium => matches!(task.priority.as_deref(), Some("high" | "medium")),
+            PriorityFilter::High => task.priority.as_deref() == Some("high"),
+        }
+    }
+
+    /// 列标题中显示的筛选标记
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            PriorityFilter::All => None,
+            PriorityFilter::Medium => Some("≥中"),
+            PriorityFilter::High => Some("高"),
+        }
+    }
+}
+
+/// 面板的任务筛选：优先级筛选和标签筛选同时生效
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFilter {
+    pub priority: PriorityFilter,
+    /// 只显示带此标签的任务（`#1`..`#9` 选择选中任务的标签）
+    pub tag: Option<String>,
+}
+
+impl TaskFilter {
+    /// 任务是否满足全部筛选条件
+    pub fn matches(&self, task: &Task) -> bool {
+        self.priority.matches(task) && self.tag.as_ref().is_none_or(|tag| task.tags.contains(tag))
+    }
+
+    /// 是否有筛选隐藏了部分任务
+    pub fn is_active(&self) -> bool {
+        self.priority != PriorityFilter::All || self.tag.is_some()
+    }
+
+    /// 列标题中显示的筛选标记，如 `高 #backend`
+    pub fn label(&self) -> Option<String> {
+        let parts: Vec<String> = self
+            .priority
+            .label()
+            .map(str::to_string)
+            .into_iter()
+            .chain(self.tag.as_ref().map(|tag| format!("#{}", tag)))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+/// 面板的列宽策略（:column-layout）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnLayout {
+    /// 等宽（或使用手动调整的列宽）
+    #[default]
+    Equal,
+    /// 按列中可见任务数分配宽度（有上下限）
+    Auto,
+}
+
+impl ColumnLayout {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "equal" => Some(ColumnLayout::Equal),
+            "auto" => Some(ColumnLayout::Auto),
+            _ => None,
+        }
+    }
 }
 
 /// 搜索状态
@@ -186,16 +395,25 @@ impl App {
     pub fn new() -> Result<Self> {
         // 检查首次运行并加载配置
         let (config, is_first_run) = crate::config::check_first_run()?;
+        crate::ui::theme::init(config.palette);
+        let safe_mode = crate::safe_mode::is_enabled();
+        crate::safe_mode::log(format!("配置已加载: {:?}", config));
 
-        // 加载所有项目
-        let projects = crate::fs::load_all_projects()?;
+        // 先只读取项目配置，任务在恢复布局后交给 IO 线程加载
+        let projects = crate::fs::load_all_project_headers_with_config(&config)?;
+        crate::safe_mode::log(format!("已找到 {} 个项目", projects.len()));
 
-        // 创建初始分屏树，如果有项目则自动加载第一个
+        // 注册配置中的自定义命令别名
+        let mut command_registry = CommandRegistry::new();
+        let skipped_aliases = command_registry.register_user_aliases(&config.aliases);
+
+        // 创建初始分屏树，如果有项目则自动加载第一个（安全模式保持空面板）
         let mut split_tree = SplitNode::new_leaf(0);
         if !projects.is_empty()
+            && !safe_mode
             && let Some(SplitNode::Leaf { project_id, .. }) = split_tree.find_pane_mut(0)
         {
-            *project_id = Some(projects[0].name.clone());
+            *project_id = Some(projects[0].id.clone());
         }
 
         let mut app = Self {
@@ -205,10 +423,34 @@ impl App {
             mode: Mode::Normal,
             key_buffer: Vec::new(),
             key_buffer_started_at: None,
+            key_flash: None,
             selected_task_index: HashMap::new(),
             marked_tasks: HashSet::new(),
+            expanded_tasks: HashSet::new(),
             selected_column: HashMap::new(),
-            command_input: String::new(),
+            zoomed_pane: None,
+            focus_mode: false,
+            redact: false,
+            pane_diff: HashSet::new(),
+            last_edited: HashMap::new(),
+            last_input_at: Instant::now(),
+            screen_locked: false,
+            priority_filters: HashMap::new(),
+            tag_filters: HashMap::new(),
+            column_layouts: HashMap::new(),
+            read_only_panes: HashSet::new(),
+            external_changes: Vec::new(),
+            loaded_at: HashMap::new(),
+            io: crate::worker::IoWorker::spawn(),
+            autosave: Default::default(),
+            ime: crate::input::ime::ImeState::new(
+                (!safe_mode)
+                    .then(|| crate::input::ime::ImeCommands::from_config(&config))
+                    .flatten(),
+            ),
+            job: None,
+            grabbed_pane: None,
+            command_input: TextField::default(),
             completion_selected_index: None,
             next_pane_id: 1,
             should_quit: false,
@@ -218,17 +460,39 @@ impl App {
             pending_editor_file: None,
             is_new_task_file: false,
             pending_preview_file: None,
+            pending_shell_dir: None,
+            preview_title: String::new(),
+            preview_header: String::new(),
             preview_content: String::new(),
             preview_scroll: 0,
-            command_registry: CommandRegistry::new(),
+            preview_link: None,
+            quick_add_input: TextField::default(),
+            status_rename_input: TextField::default(),
+            column_header_focus: None,
+            preview_base_dir: None,
+            image_protocol: crate::ui::image::detect(config.inline_images && !safe_mode),
+            preview_images: Vec::new(),
+            shown_images: Vec::new(),
+            command_registry,
             config,
             show_welcome_dialog: is_first_run,
+            show_cheat_sheet: false,
             saved_layout: None,
+            home_layout: None,
+            upgraded_from: None,
+            collapsed_project_groups: Default::default(),
             notification: None,
+            last_save_error: None,
             last_column_resize_time: None,
-            list_states: HashMap::new(),
+            list_offsets: HashMap::new(),
+            column_selections: HashMap::new(),
+            card_cache: Default::default(),
             search_state: None,
             status_select_state: None,
+            tag_overview: None,
+            send_to_pane: None,
+            tutor: None,
+            spell_checker: None,
         };
 
         // 调试：记录初始状态
@@ -240,7 +504,12 @@ impl App {
         ));
 
         // 尝试加载保存的状态
-        if let Ok(state) = crate::state::load_state() {
+        if safe_mode {
+            crate::safe_mode::log("跳过 state.toml".to_string());
+        } else if let Ok(state) = crate::state::load_state() {
+            app.upgraded_from = state.last_run_version.clone().filter(|last| {
+                crate::changelog::is_newer(crate::changelog::current_version(), last)
+            });
             crate::state::apply_state(&mut app, state);
             log_debug(format!(
                 "加载状态后: focused_pane={}, next_pane_id={}, pane_ids={:?}",
@@ -250,22 +519,146 @@ impl App {
             ));
         }
 
+        app.load_initial_tasks();
+        app.autosave.mark_saved(&crate::state::extract_state(&app));
+
+        // 外部工具在使用时才报错会覆盖在恢复的界面上，启动时先提示
+        let tool_warnings = crate::config::tool_warnings(&app.config);
+        if !tool_warnings.is_empty() {
+            app.show_notification(
+                format!(
+                    "{}，可用 :set editor <命令> / :set viewer <命令> 或 :config-detect 修正",
+                    tool_warnings.join("；")
+                ),
+                NotificationLevel::Warning,
+            );
+        }
+
+        if !skipped_aliases.is_empty() {
+            app.show_notification(
+                format!(
+                    "忽略无效或与内置命令冲突的别名: {}",
+                    skipped_aliases.join(", ")
+                ),
+                NotificationLevel::Warning,
+            );
+        }
+
+        if let Some(path) = app.get_focused_project().map(|p| p.path.clone()) {
+            app.check_project_migration(&path);
+        }
+
+        // 升级后首次启动：显示新功能说明（迁移对话框等优先）
+        if app.upgraded_from.is_some() && app.mode == Mode::Normal {
+            app.show_changelog(false);
+        }
+
+        if safe_mode {
+            app.show_notification(
+                format!(
+                    "安全模式：未恢复布局，不保存状态，自动化已停用，日志写入 {}",
+                    crate::safe_mode::LOG_FILE
+                ),
+                NotificationLevel::Warning,
+            );
+        }
+
         Ok(app)
     }
 
+    /// 在预览弹窗中显示更新日志：升级后显示旧版本之后的发布说明，否则显示当前版本的；
+    /// `all` 显示完整日志
+    pub fn show_changelog(&mut self, all: bool) {
+        let version = crate::changelog::current_version();
+        let (title, content) = if all {
+            ("更新日志".to_string(), crate::changelog::full())
+        } else {
+            let title = match &self.upgraded_from {
+                Some(from) => format!("新功能：{} → {}", from, version),
+                None => format!("新功能：{}", version),
+            };
+            (
+                title,
+                crate::changelog::whats_new(self.upgraded_from.as_deref()),
+            )
+        };
+        if content.is_empty() {
+            self.show_notification(
+                format!("更新日志中没有 {} 的说明", version),
+                NotificationLevel::Info,
+            );
+            return;
+        }
+        self.preview_title = title;
+        self.preview_header =
+            "Esc 关闭 · :changelog 再次打开 · :changelog all 完整日志".to_string();
+        self.preview_base_dir = None;
+        self.preview_content = content;
+        self.preview_scroll = 0;
+        self.mode = Mode::Preview;
+    }
+
     /// 处理键盘输入
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> bool {
         use crate::input::handle_key_input;
+        self.last_input_at = Instant::now();
+        // 隐藏看板时按键只用来恢复显示，不执行任何操作
+        if self.screen_locked {
+            self.screen_locked = false;
+            return true;
+        }
         handle_key_input(self, key)
     }
 
+    /// 记录刚创建或编辑的任务
+    pub fn remember_edited(&mut self, project_id: &str, task_id: u32) {
+        self.last_edited.insert(project_id.to_string(), task_id);
+    }
+
+    /// 聚焦项目中最近创建或编辑的任务
+    ///
+    /// 优先使用本次会话的记录；没有记录（或任务已删除）时取文件修改时间最新的任务
+    pub fn last_edited_task(&self) -> Option<&crate::models::Task> {
+        let project = self.get_focused_project()?;
+        self.last_edited
+            .get(&project.id)
+            .and_then(|id| project.tasks.iter().find(|task| task.id == *id))
+            .or_else(|| {
+                project
+                    .tasks
+                    .iter()
+                    .filter_map(|task| Some((crate::fs::task::task_modified_at(task)?, task)))
+                    .max_by_key(|(modified, _)| *modified)
+                    .map(|(_, task)| task)
+            })
+    }
+
+    /// 无按键操作超过 `idle_lock_minutes` 分钟时隐藏看板（事件循环每次轮询时调用）
+    pub fn check_idle_lock(&mut self) {
+        let minutes = self.config.idle_lock_minutes;
+        if minutes > 0
+            && !self.screen_locked
+            && self.last_input_at.elapsed() >= std::time::Duration::from_secs(minutes * 60)
+        {
+            self.lock_screen();
+        }
+    }
+
+    /// 隐藏看板，清除正在输入的按键序列
+    pub fn lock_screen(&mut self) {
+        self.screen_locked = true;
+        self.key_buffer.clear();
+        self.key_buffer_started_at = None;
+        self.show_cheat_sheet = false;
+    }
+
     /// 获取当前聚焦面板显示的项目
     pub fn get_focused_project(&self) -> Option<&Project> {
         if let Some(SplitNode::Leaf { project_id, .. }) =
             self.split_tree.find_pane(self.focused_pane)
             && let Some(pid) = project_id
         {
-            return self.projects.iter().find(|p| &p.name == pid);
+            return self.projects.iter().find(|p| &p.id == pid);
         }
         None
     }
@@ -277,17 +670,13 @@ impl App {
             self.split_tree.find_pane(self.focused_pane)
             && let Some(pid) = project_id.clone()
         {
-            return self.projects.iter_mut().find(|p| p.name == pid);
+            return self.projects.iter_mut().find(|p| p.id == pid);
         }
         None
     }
 
-    pub fn open_project(
-        &mut self,
-        project_name: String,
-        project_path: std::path::PathBuf,
-        project_type: ProjectType,
-    ) {
+    /// 在当前面板打开指定路径的项目（按路径定位，同名项目不会混淆）
+    pub fn open_project(&mut self, project_path: std::path::PathBuf, project_type: ProjectType) {
         // 重新从文件系统加载项目数据，确保获取最新的任务列表
         if let Ok(updated_project) = crate::fs::load_project_with_type(&project_path, project_type)
         {
@@ -299,93 +688,342 @@ impl App {
                 self.projects.push(updated_project);
             }
         }
-        self.prioritize_project_path_for_name(&project_name, &project_path);
 
-        self.set_focused_project_id(project_name);
+        let Some(project_id) = self
+            .projects
+            .iter()
+            .find(|p| p.path == project_path)
+            .map(|p| p.id.clone())
+        else {
+            return;
+        };
+        self.set_focused_project_id(project_id);
+        self.check_project_migration(&project_path);
     }
 
-    /// 设置当前聚焦面板的项目
-    pub fn set_focused_project(&mut self, project_name: String) {
-        if let Some(project) = self.projects.iter().find(|p| p.name == project_name) {
-            self.open_project(
-                project.name.clone(),
-                project.path.clone(),
-                project.project_type,
+    /// 从项目列表打开项目
+    ///
+    /// 开启 `reuse_open_pane` 且项目已在其他面板显示时切换到那个面板；
+    /// `allow_duplicate` 为 true 时仍在当前面板再打开一份。
+    pub fn open_or_focus_project(
+        &mut self,
+        project_path: std::path::PathBuf,
+        project_type: ProjectType,
+        allow_duplicate: bool,
+    ) {
+        if self.config.reuse_open_pane
+            && !allow_duplicate
+            && let Some(pane_id) = self.pane_showing_project(&project_path)
+        {
+            if self.saved_layout.is_some() {
+                self.focus_pane_while_maximized(pane_id);
+            } else {
+                self.focused_pane = pane_id;
+            }
+            self.show_notification(
+                "项目已在其他面板打开，已切换过去（Alt+Enter 打开副本）".to_string(),
+                NotificationLevel::Info,
             );
             return;
         }
+        self.open_project(project_path, project_type);
+    }
+
+    /// 正在显示该项目的其他面板（最大化时在保存的原布局中查找）
+    fn pane_showing_project(&self, project_path: &std::path::Path) -> Option<usize> {
+        let id = &self.projects.iter().find(|p| p.path == project_path)?.id;
+        let layout = self.saved_layout.as_ref().unwrap_or(&self.split_tree);
+        layout.collect_pane_ids().into_iter().find(|&pane_id| {
+            pane_id != self.focused_pane
+                && matches!(
+                    layout.find_pane(pane_id),
+                    Some(SplitNode::Leaf { project_id: Some(pid), .. }) if pid == id
+                )
+        })
+    }
+
+    /// 打开旧格式项目时请求确认迁移；只需写入版本号的项目直接升级
+    pub fn check_project_migration(&mut self, project_path: &std::path::Path) {
+        use crate::fs::migration;
+
+        let Ok(plan) = migration::plan_migration(project_path) else {
+            return;
+        };
+        if plan.is_up_to_date() {
+            return;
+        }
+        if !plan.needs_confirmation() {
+            let _ = migration::migrate_project(project_path);
+            return;
+        }
+        if self.dialog.is_some() {
+            return;
+        }
 
-        // 向后兼容：旧调用只给全局项目名时，尝试从全局目录加载。
-        let project_path = crate::fs::get_projects_dir().join(&project_name);
-        self.open_project(project_name, project_path, ProjectType::Global);
+        const MAX_SHOWN: usize = 5;
+        let mut message = format!(
+            "项目使用旧的文件格式（版本 {} → {}），确认后先备份到 {}/，再执行：",
+            plan.from_version,
+            migration::CURRENT_FORMAT_VERSION,
+            migration::BACKUP_DIR
+        );
+        for change in plan.changes.iter().take(MAX_SHOWN) {
+            message.push_str(&format!("\n• {}", change));
+        }
+        if plan.changes.len() > MAX_SHOWN {
+            message.push_str(&format!("\n… 共 {} 项", plan.changes.len()));
+        }
+
+        self.mode = Mode::Dialog;
+        self.dialog = Some(crate::ui::dialogs::DialogType::Confirm {
+            title: "升级项目格式".to_string(),
+            message,
+            yes_selected: false,
+            action: crate::ui::dialogs::ConfirmAction::MigrateProject,
+        });
     }
 
-    fn set_focused_project_id(&mut self, project_name: String) {
+    fn set_focused_project_id(&mut self, id: String) {
+        self.focus_preferred_pane(&id);
         // 设置当前面板的项目
         if let Some(SplitNode::Leaf { project_id, .. }) =
             self.split_tree.find_pane_mut(self.focused_pane)
         {
-            *project_id = Some(project_name.clone());
-            self.sync_focused_project_to_saved_layout(Some(project_name));
+            *project_id = Some(id.clone());
+            self.sync_focused_project_to_saved_layout(Some(id));
             // 重置选中索引到 0
             self.selected_task_index.insert(self.focused_pane, 0);
             self.selected_column.insert(self.focused_pane, 0);
+            let pane = self.focused_pane;
+            self.column_selections
+                .retain(|(pane_id, _), _| *pane_id != pane);
 
             // 保存状态
-            let state = crate::state::extract_state(self);
-            let _ = crate::state::save_state(&state);
+            self.save_state();
         }
     }
 
-    fn prioritize_project_path_for_name(
-        &mut self,
-        project_name: &str,
-        project_path: &std::path::Path,
-    ) {
-        let Some(selected_index) = self
-            .projects
-            .iter()
-            .position(|project| project.path == project_path)
-        else {
+    /// 项目设置了偏好的面板位置（`pane = "right"`）时，先把焦点移到该方向最边上的面板
+    ///
+    /// 只有一个面板、最大化显示或目标面板只读时保持当前面板。
+    fn focus_preferred_pane(&mut self, id: &str) {
+        if self.saved_layout.is_some() {
             return;
-        };
-        let Some(first_name_index) = self
+        }
+        let Some(direction) = self
             .projects
             .iter()
-            .position(|project| project.name == project_name)
+            .find(|p| p.id == id)
+            .and_then(|p| p.pane.as_deref())
+            .and_then(Direction::from_name)
         else {
             return;
         };
-
-        if first_name_index == selected_index {
-            return;
+        let target = self.split_tree.edge_pane(self.focused_pane, direction);
+        if !self.is_pane_read_only(target) {
+            self.focused_pane = target;
         }
-
-        let project = self.projects.remove(selected_index);
-        self.projects.insert(first_name_index, project);
     }
 
     /// 重新加载当前聚焦面板的项目（用于外部编辑器保存后刷新）
     pub fn reload_current_project(&mut self) -> Result<()> {
-        if let Some(SplitNode::Leaf { project_id, .. }) =
-            self.split_tree.find_pane(self.focused_pane)
-            && let Some(pid) = project_id
+        if let Some(SplitNode::Leaf {
+            project_id: Some(pid),
+            ..
+        }) = self.split_tree.find_pane(self.focused_pane)
         {
-            // 从项目列表中找到项目路径和类型
-            if let Some(project) = self.projects.iter().find(|p| &p.name == pid) {
-                let project_path = project.path.clone();
-                let project_type = project.project_type;
-
-                // 重新加载项目
-                if let Ok(updated_project) =
-                    crate::fs::load_project_with_type(&project_path, project_type)
-                    && let Some(project) = self.projects.iter_mut().find(|p| &p.name == pid)
-                {
-                    *project = updated_project;
+            let pid = pid.clone();
+            self.reload_project(&pid);
+        }
+        Ok(())
+    }
+
+    /// 重新加载指定项目（例如任务被发送到其他面板的项目后）
+    pub fn reload_project(&mut self, pid: &str) {
+        // 从项目列表中找到项目路径和类型
+        let Some(project) = self.projects.iter().find(|p| p.id == pid) else {
+            return;
+        };
+        let project_path = project.path.clone();
+        let project_type = project.project_type;
+
+        // 重新加载项目
+        if let Ok(updated_project) = crate::fs::load_project_with_type(&project_path, project_type)
+            && let Some(project) = self.projects.iter_mut().find(|p| p.id == pid)
+        {
+            *project = updated_project;
+            self.loaded_at.insert(pid.to_string(), chrono::Local::now());
+            self.io.forget(pid);
+        }
+    }
+
+    /// 在后台重新加载所有项目，完成后由 `poll_job` 替换项目列表
+    pub fn start_reload_all(&mut self) {
+        let config = self.config.clone();
+        self.start_job(crate::job::JobKind::ReloadAll, 0, move |_| {
+            match crate::fs::load_all_projects_with_config(&config) {
+                Ok(projects) => crate::job::JobResult::Reloaded(projects),
+                Err(e) => crate::job::JobResult::done(
+                    format!("重新加载失败: {}", e),
+                    NotificationLevel::Error,
+                ),
+            }
+        });
+    }
+
+    /// 在 IO 线程中重新加载当前项目（`:reload`、外部编辑器或 shell 返回后），
+    /// 结果由 `poll_io` 应用并记录外部修改
+    pub fn request_reload_current(&mut self) {
+        if let Some(project) = self.get_focused_project().cloned() {
+            self.io.load_project(&project);
+        }
+    }
+
+    /// 主循环每帧调用：应用 IO 线程已完成的项目加载，显示后台保存的错误
+    pub fn poll_io(&mut self) {
+        while let Some(loaded) = self.io.poll() {
+            match loaded.result {
+                Ok(project) => {
+                    let Some(slot) = self.projects.iter_mut().find(|p| p.id == loaded.id) else {
+                        continue;
+                    };
+                    let before = std::mem::replace(slot, project);
+                    self.loaded_at.insert(loaded.id, chrono::Local::now());
+                    if !loaded.initial {
+                        self.track_external_changes(std::slice::from_ref(&before));
+                    }
+                }
+                Err(e) => {
+                    self.show_notification(
+                        format!("重新加载失败: {}", e),
+                        NotificationLevel::Error,
+                    );
                 }
             }
         }
-        Ok(())
+        while let Some(error) = self.io.poll_error() {
+            self.last_save_error = Some(SaveError::State(error.clone()));
+            self.show_notification(error, NotificationLevel::Error);
+        }
+    }
+
+    /// 在 IO 线程中保存界面状态
+    pub fn save_state(&mut self) {
+        let state = crate::state::extract_state(self);
+        self.autosave.mark_saved(&state);
+        self.io.save_state(state);
+    }
+
+    /// 布局或选中项变化后限速保存界面状态（主循环每轮调用）
+    pub fn autosave_state(&mut self) {
+        let mut autosave = std::mem::take(&mut self.autosave);
+        let state = autosave.poll(Instant::now(), || crate::state::extract_state(self));
+        self.autosave = autosave;
+        if let Some(state) = state {
+            self.io.save_state(state);
+        }
+    }
+
+    /// 在后台执行耗时操作；已有后台任务时拒绝
+    pub fn start_job(
+        &mut self,
+        kind: crate::job::JobKind,
+        total: usize,
+        work: impl FnOnce(&crate::job::JobHandle) -> crate::job::JobResult + Send + 'static,
+    ) {
+        if let Some(job) = &self.job {
+            let message = format!("{}尚未完成，请稍候", job.kind.title());
+            self.show_notification(message, NotificationLevel::Warning);
+            return;
+        }
+        self.job = Some(crate::job::Job::spawn(kind, total, work));
+    }
+
+    /// 主循环每帧调用：收取后台任务的进度，完成时应用结果
+    pub fn poll_job(&mut self) {
+        let Some(result) = self.job.as_mut().and_then(|job| job.poll()) else {
+            return;
+        };
+        let Some(job) = self.job.take() else {
+            return;
+        };
+        match result {
+            crate::job::JobResult::Done { message, level } => {
+                let _ = self.reload_current_project();
+                self.show_notification(message, level);
+            }
+            crate::job::JobResult::Reloaded(_) if job.is_cancelled() => {
+                self.show_notification("已取消重新加载".to_string(), NotificationLevel::Info);
+            }
+            crate::job::JobResult::Reloaded(projects) => {
+                // 重新加载所有项目（本地+全局），并记录外部修改
+                let before = std::mem::replace(&mut self.projects, projects);
+                self.mark_all_loaded();
+                self.track_external_changes(&before);
+            }
+        }
+    }
+
+    /// 启动时在 IO 线程中加载各项目的任务：面板中显示的项目排在前面
+    fn load_initial_tasks(&mut self) {
+        let shown: HashSet<String> = self
+            .split_tree
+            .collect_pane_ids()
+            .into_iter()
+            .filter_map(|id| match self.split_tree.find_pane(id) {
+                Some(SplitNode::Leaf { project_id, .. }) => project_id.clone(),
+                _ => None,
+            })
+            .collect();
+        let mut projects: Vec<&Project> = self.projects.iter().collect();
+        projects.sort_by_key(|project| !shown.contains(&project.id));
+        for project in projects {
+            self.io.load_initial(project);
+        }
+    }
+
+    /// 把所有项目的加载时间记为现在
+    pub fn mark_all_loaded(&mut self) {
+        let now = chrono::Local::now();
+        self.loaded_at = self.projects.iter().map(|p| (p.id.clone(), now)).collect();
+    }
+
+    /// 项目是否正在等待重新加载
+    pub fn is_reloading(&self, project_id: &str) -> bool {
+        self.io.is_loading(project_id)
+            || self
+                .job
+                .as_ref()
+                .is_some_and(|job| job.kind == crate::job::JobKind::ReloadAll)
+    }
+
+    /// 对比重新加载前的项目，有外部修改时保存快照并提示可撤销
+    pub fn track_external_changes(&mut self, before: &[Project]) {
+        if crate::safe_mode::is_enabled() {
+            return;
+        }
+        let changes: Vec<TaskChange> = before
+            .iter()
+            .filter_map(|old| {
+                self.projects
+                    .iter()
+                    .find(|p| p.id == old.id)
+                    .map(|new| diff_project_tasks(old, new))
+            })
+            .flatten()
+            .collect();
+        if changes.is_empty() {
+            return;
+        }
+
+        self.show_notification(
+            format!(
+                "检测到 {} 个任务被外部修改，:revert-external 可撤销",
+                changes.len()
+            ),
+            NotificationLevel::Info,
+        );
+        self.external_changes = changes;
     }
 
     /// 切换当前面板的最大化状态
@@ -395,8 +1033,7 @@ impl App {
             self.split_tree = saved;
 
             // 保存状态
-            let state = crate::state::extract_state(self);
-            let _ = crate::state::save_state(&state);
+            self.save_state();
         } else {
             // 当前不是最大化状态，保存当前布局并最大化
             // 只有在有多个面板时才需要最大化
@@ -417,13 +1054,159 @@ impl App {
                     };
 
                     // 保存状态（最大化后的状态）
-                    let state = crate::state::extract_state(self);
-                    let _ = crate::state::save_state(&state);
+                    self.save_state();
                 }
             }
         }
     }
 
+    /// 把当前布局保存为主页布局（最大化时保存最大化前的布局）
+    pub fn set_home_layout(&mut self) {
+        let split_tree = self
+            .saved_layout
+            .clone()
+            .unwrap_or_else(|| self.split_tree.clone());
+        let panes: HashSet<usize> = split_tree.collect_pane_ids().into_iter().collect();
+        let mut column_layouts = self.column_layouts.clone();
+        column_layouts.retain(|pane, _| panes.contains(pane));
+        let mut read_only_panes = self.read_only_panes.clone();
+        read_only_panes.retain(|pane| panes.contains(pane));
+        self.home_layout = Some(crate::state::HomeLayout {
+            split_tree,
+            focused_pane: self.focused_pane,
+            column_layouts,
+            read_only_panes,
+        });
+        self.save_state();
+    }
+
+    /// 恢复主页布局，丢弃会话中临时增加的分屏
+    ///
+    /// 项目已不存在的面板被移除；仍显示同一项目的面板保留选中位置。
+    /// 没有设置主页布局或其中的项目都已删除时返回 false。
+    pub fn restore_home_layout(&mut self) -> bool {
+        let Some(home) = self.home_layout.clone() else {
+            return false;
+        };
+        let projects = &self.projects;
+        let Some(tree) = home.split_tree.pruned(
+            &|id| projects.iter().any(|p| p.id == id),
+            &mut HashSet::new(),
+            &mut crate::ui::layout::PruneReport::default(),
+        ) else {
+            return false;
+        };
+
+        let current = self
+            .saved_layout
+            .take()
+            .unwrap_or_else(|| self.split_tree.clone());
+        let project_of = |layout: &SplitNode, pane: usize| match layout.find_pane(pane) {
+            Some(SplitNode::Leaf { project_id, .. }) => project_id.clone(),
+            _ => None,
+        };
+        let panes = tree.collect_pane_ids();
+        let unchanged: HashSet<usize> = panes
+            .iter()
+            .copied()
+            .filter(|&pane| {
+                current.find_pane(pane).is_some()
+                    && project_of(&current, pane) == project_of(&tree, pane)
+            })
+            .collect();
+
+        self.selected_column
+            .retain(|pane, _| unchanged.contains(pane));
+        self.selected_task_index
+            .retain(|pane, _| unchanged.contains(pane));
+        self.column_selections
+            .retain(|(pane, _), _| unchanged.contains(pane));
+        self.list_offsets
+            .retain(|(pane, _), _| unchanged.contains(pane));
+        self.priority_filters
+            .retain(|pane, _| unchanged.contains(pane));
+        self.tag_filters.retain(|pane, _| unchanged.contains(pane));
+        self.column_layouts = home.column_layouts;
+        self.read_only_panes = home.read_only_panes;
+        self.focused_pane = if panes.contains(&home.focused_pane) {
+            home.focused_pane
+        } else {
+            panes[0]
+        };
+        if let Some(&max_id) = panes.iter().max() {
+            self.next_pane_id = self.next_pane_id.max(max_id + 1);
+        }
+        self.split_tree = tree;
+        self.zoomed_pane = None;
+        self.grabbed_pane = None;
+        self.column_header_focus = None;
+        self.save_state();
+        true
+    }
+
+    /// 与指定方向的相邻面板交换项目（布局不变），焦点跟随项目移动。
+    /// 最大化时只有一个面板可见，不支持交换。
+    pub fn swap_pane_project(&mut self, direction: Direction) -> bool {
+        if self.saved_layout.is_some() {
+            return false;
+        }
+        let Some(target) = self
+            .split_tree
+            .find_adjacent_pane(self.focused_pane, direction)
+        else {
+            return false;
+        };
+
+        let project_of = |tree: &SplitNode, id: usize| match tree.find_pane(id) {
+            Some(SplitNode::Leaf { project_id, .. }) => project_id.clone(),
+            _ => None,
+        };
+        let source_project = project_of(&self.split_tree, self.focused_pane);
+        let target_project = project_of(&self.split_tree, target);
+
+        for (pane_id, project) in [
+            (self.focused_pane, target_project),
+            (target, source_project),
+        ] {
+            if let Some(SplitNode::Leaf { project_id, .. }) = self.split_tree.find_pane_mut(pane_id)
+            {
+                *project_id = project;
+            }
+        }
+
+        // 选中位置跟随项目
+        let source = self.focused_pane;
+        for map in [&mut self.selected_column, &mut self.selected_task_index] {
+            let a = map.remove(&source);
+            let b = map.remove(&target);
+            if let Some(value) = b {
+                map.insert(source, value);
+            }
+            if let Some(value) = a {
+                map.insert(target, value);
+            }
+        }
+        let a = self.priority_filters.remove(&source);
+        let b = self.priority_filters.remove(&target);
+        if let Some(filter) = b {
+            self.priority_filters.insert(source, filter);
+        }
+        if let Some(filter) = a {
+            self.priority_filters.insert(target, filter);
+        }
+        let a = self.tag_filters.remove(&source);
+        let b = self.tag_filters.remove(&target);
+        if let Some(tag) = b {
+            self.tag_filters.insert(source, tag);
+        }
+        if let Some(tag) = a {
+            self.tag_filters.insert(target, tag);
+        }
+
+        self.focused_pane = target;
+        true
+    }
+
     /// 最大化状态下切换到原布局中的指定面板，保持全屏显示。
     pub fn focus_pane_while_maximized(&mut self, pane_id: usize) -> bool {
         let project_id = self
@@ -493,15 +1276,15 @@ impl App {
         }
     }
 
-    fn sync_focused_project_to_saved_layout(&mut self, project_name: Option<String>) {
-        self.set_saved_layout_project(self.focused_pane, project_name);
+    fn sync_focused_project_to_saved_layout(&mut self, id: Option<String>) {
+        self.set_saved_layout_project(self.focused_pane, id);
     }
 
-    fn set_saved_layout_project(&mut self, pane_id: usize, project_name: Option<String>) {
+    fn set_saved_layout_project(&mut self, pane_id: usize, id: Option<String>) {
         if let Some(saved_layout) = &mut self.saved_layout
             && let Some(SplitNode::Leaf { project_id, .. }) = saved_layout.find_pane_mut(pane_id)
         {
-            *project_id = project_name;
+            *project_id = id;
         }
     }
 
@@ -511,9 +1294,28 @@ impl App {
             message,
             level,
             created_at: Instant::now(),
+            undo: None,
+        });
+    }
+
+    /// 任务移到完成列后的提示（`✓ 标题 → 完成`），显示期间可以按 `u` 撤销
+    pub fn show_completion(&mut self, message: String, undo: UndoMove) {
+        self.notification = Some(Notification {
+            message,
+            level: NotificationLevel::Success,
+            created_at: Instant::now(),
+            undo: Some(undo),
         });
     }
 
+    /// 仍在显示的完成提示对应的撤销操作
+    pub fn pending_undo(&self) -> Option<&UndoMove> {
+        self.notification
+            .as_ref()
+            .filter(|notification| !notification.is_expired())
+            .and_then(|notification| notification.undo.as_ref())
+    }
+
     /// 清除已过期的通知
     pub fn clear_expired_notification(&mut self) {
         if let Some(ref notification) = self.notification
@@ -521,6 +1323,43 @@ impl App {
         {
             self.notification = None;
         }
+        if self
+            .key_flash
+            .as_ref()
+            .is_some_and(|(_, shown_at)| shown_at.elapsed() >= KEY_FLASH_DURATION)
+        {
+            self.key_flash = None;
+        }
+    }
+
+    /// 正在输入的按键序列，如空格菜单的 `␣w`、普通模式的 `m`
+    pub fn pending_key_label(&self) -> Option<String> {
+        let menu_key = match (self.mode, self.menu_state) {
+            (Mode::SpaceMenu, Some(MenuState::Main) | None) => "",
+            (Mode::SpaceMenu, Some(MenuState::Project)) => "p",
+            (Mode::SpaceMenu, Some(MenuState::Window)) => "w",
+            (Mode::SpaceMenu, Some(MenuState::Task)) => "t",
+            (Mode::SpaceMenu, Some(MenuState::Status)) => "s",
+            (Mode::SpaceMenu, Some(MenuState::Color)) => "tc",
+            _ if self.key_buffer.is_empty() => return None,
+            _ => return Some(self.key_buffer.iter().collect()),
+        };
+        Some(format!("␣{}", menu_key))
+    }
+
+    /// 新任务的初始状态和优先级：配置了 `default_status` 时使用它，否则使用聚焦面板的当前列
+    pub fn new_task_target(&self, project_path: &std::path::Path) -> (String, Option<String>) {
+        let defaults = crate::fs::project::NewTaskDefaults::load(project_path, &self.config);
+        let status = defaults.status.unwrap_or_else(|| {
+            let column = self
+                .selected_column
+                .get(&self.focused_pane)
+                .copied()
+                .unwrap_or(0);
+            self.get_status_name_by_column(column)
+                .unwrap_or_else(|| "todo".to_string())
+        });
+        (status, defaults.priority)
     }
 
     /// 根据列索引获取状态名称
@@ -531,16 +1370,303 @@ impl App {
             .map(|s| s.name.clone())
     }
 
+    /// 获取面板的优先级筛选
+    pub fn priority_filter(&self, pane_id: usize) -> PriorityFilter {
+        self.priority_filters
+            .get(&pane_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// 获取面板的全部筛选条件（优先级和标签）
+    pub fn task_filter(&self, pane_id: usize) -> TaskFilter {
+        TaskFilter {
+            priority: self.priority_filter(pane_id),
+            tag: self.tag_filters.get(&pane_id).cloned(),
+        }
+    }
+
+    /// 终端窗口标题：`hxk: 项目 (todo 5 / doing 2)`，聚焦面板有优先级筛选时附加筛选标记
+    pub fn window_title(&self) -> String {
+        let Some(project) = self.get_focused_project().filter(|_| !self.screen_locked) else {
+            return "hxk".to_string();
+        };
+        let counts = project
+            .statuses
+            .iter()
+            .map(|status| {
+                let count = project
+                    .tasks
+                    .iter()
+                    .filter(|t| t.status == status.name)
+                    .count();
+                format!("{} {}", status.name, count)
+            })
+            .collect::<Vec<_>>()
+            .join(" / ");
+        let filter = self
+            .task_filter(self.focused_pane)
+            .label()
+            .map(|label| format!(" [{}]", label))
+            .unwrap_or_default();
+        format!("hxk: {} ({}){}", project.name, counts, filter)
+    }
+
+    /// 面板是否锁定为只读
+    pub fn is_pane_read_only(&self, pane_id: usize) -> bool {
+        self.read_only_panes.contains(&pane_id)
+    }
+
+    /// 获取面板的列宽策略
+    pub fn column_layout(&self, pane_id: usize) -> ColumnLayout {
+        self.column_layouts
+            .get(&pane_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// 面板中某一状态列可见的任务（应用该面板的优先级和标签筛选）
+    pub fn column_tasks<'a>(
+        &self,
+        pane_id: usize,
+        project: &'a Project,
+        status: &str,
+    ) -> Vec<&'a Task> {
+        let filter = self.task_filter(pane_id);
+        project
+            .tasks
+            .iter()
+            .filter(|t| t.status == status && filter.matches(t))
+            .collect()
+    }
+
     /// 获取当前项目的状态列数
     pub fn get_status_count(&self) -> usize {
         self.get_focused_project()
             .map(|p| p.statuses.len())
             .unwrap_or(3)
     }
+
+    /// 专注模式下是否隐藏面板的某一列
+    ///
+    /// 面板当前选中的列始终显示，这样把任务移入完成列或搜索跳转过去时仍能看到它。
+    pub fn is_column_hidden(&self, pane_id: usize, project: &Project, column: usize) -> bool {
+        self.focus_mode
+            && project.is_done_column(column)
+            && self.selected_column.get(&pane_id).copied().unwrap_or(0) != column
+    }
+
+    /// 完成列卡片数超过 `collapse_done_after` 时是否折叠为窄列
+    ///
+    /// 聚焦面板选中完成列时展开，因此左右移动到该列即可查看其中的任务。
+    pub fn is_column_collapsed(&self, pane_id: usize, project: &Project, column: usize) -> bool {
+        let threshold = self.config.collapse_done_after;
+        if threshold == 0 || !project.is_done_column(column) {
+            return false;
+        }
+        let expanded = pane_id == self.focused_pane
+            && self.selected_column.get(&pane_id).copied().unwrap_or(0) == column;
+        let status = &project.statuses[column].name;
+        !expanded && project.tasks.iter().filter(|t| &t.status == status).count() > threshold
+    }
+
+    /// 当前面板左侧/右侧最近的可见列，没有时返回 None
+    pub fn adjacent_visible_column(&self, forward: bool) -> Option<usize> {
+        let project = self.get_focused_project()?;
+        let current = self
+            .selected_column
+            .get(&self.focused_pane)
+            .copied()
+            .unwrap_or(0);
+        let visible = |&column: &usize| !self.is_column_hidden(self.focused_pane, project, column);
+        if forward {
+            (current + 1..project.statuses.len()).find(visible)
+        } else {
+            (0..current).rev().find(visible)
+        }
+    }
+
+    /// 聚焦面板切换到另一列（h/l）：记住离开的列中选中的任务，回到某列时恢复该列上次选中的任务
+    ///
+    /// 记住的任务已不在该列时（被移走或筛选掉）按原来的索引恢复。
+    pub fn switch_column(&mut self, column: usize) {
+        let pane = self.focused_pane;
+        let current = self.selected_column.get(&pane).copied().unwrap_or(0);
+        let index = self.selected_task_index.get(&pane).copied().unwrap_or(0);
+        let Some(project) = self.get_focused_project() else {
+            return;
+        };
+        let task_ids = |column: usize| -> Vec<u32> {
+            project
+                .statuses
+                .get(column)
+                .map(|status| {
+                    self.column_tasks(pane, project, &status.name)
+                        .iter()
+                        .map(|t| t.id)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let leaving = task_ids(current).get(index).copied();
+        let restored = match self.column_selections.get(&(pane, column)) {
+            Some(&(task_id, remembered)) => {
+                let tasks = task_ids(column);
+                task_id
+                    .and_then(|id| tasks.iter().position(|&t| t == id))
+                    .unwrap_or_else(|| remembered.min(tasks.len().saturating_sub(1)))
+            }
+            None => 0,
+        };
+
+        self.column_selections
+            .insert((pane, current), (leaving, index));
+        self.selected_column.insert(pane, column);
+        self.selected_task_index.insert(pane, restored);
+    }
+
+    /// 按需加载拼写检查的词表，并切换到聚焦项目的自定义词典；关闭 spell_check 时释放词表
+    pub fn prepare_spell_checker(&mut self) {
+        if !self.config.spell_check {
+            self.spell_checker = None;
+            return;
+        }
+        let Some(project_path) = self.get_focused_project().map(|p| p.path.clone()) else {
+            return;
+        };
+        match &mut self.spell_checker {
+            Some(checker) => checker.set_project(&project_path),
+            None => {
+                self.spell_checker = crate::ui::spell::SpellChecker::load(
+                    self.config.spell_wordlist.as_deref(),
+                    &project_path,
+                )
+            }
+        }
+    }
+
+    /// 开始交互式教程：生成练习项目并在当前面板打开；教程进行中时退出教程
+    pub fn toggle_tutor(&mut self) {
+        if self.tutor.is_some() {
+            self.stop_tutor();
+            return;
+        }
+
+        let dir = crate::ui::tutor::practice_dir();
+        let _ = std::fs::remove_dir_all(&dir);
+        let project = crate::ui::tutor::create_practice_project(&dir)
+            .and_then(|_| crate::fs::load_project_with_type(&dir, ProjectType::Global));
+        let project = match project {
+            Ok(project) => project,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&dir);
+                self.show_notification(
+                    format!("无法创建练习项目: {}", e),
+                    NotificationLevel::Error,
+                );
+                return;
+            }
+        };
+
+        let previous_project = match self.split_tree.find_pane(self.focused_pane) {
+            Some(SplitNode::Leaf { project_id, .. }) => project_id.clone(),
+            _ => None,
+        };
+        self.tutor = Some(crate::ui::tutor::Tutor {
+            project_id: project.id.clone(),
+            project_path: dir,
+            pane_id: self.focused_pane,
+            previous_project,
+            panes_at_start: self.split_tree.collect_pane_ids().len(),
+            step: 0,
+        });
+        let id = project.id.clone();
+        self.projects.push(project);
+        self.set_focused_project_id(id);
+        self.mode = Mode::Normal;
+    }
+
+    /// 退出教程：恢复面板原来的项目并删除练习项目
+    fn stop_tutor(&mut self) {
+        let Some(tutor) = self.tutor.take() else {
+            return;
+        };
+        for pane_id in self.split_tree.collect_pane_ids() {
+            if let Some(SplitNode::Leaf { project_id, .. }) = self.split_tree.find_pane_mut(pane_id)
+                && project_id.as_deref() == Some(tutor.project_id.as_str())
+            {
+                *project_id = if pane_id == tutor.pane_id {
+                    tutor.previous_project.clone()
+                } else {
+                    None
+                };
+                self.selected_task_index.insert(pane_id, 0);
+                self.selected_column.insert(pane_id, 0);
+            }
+        }
+        if let Some(SplitNode::Leaf { project_id, .. }) =
+            self.split_tree.find_pane(self.focused_pane)
+        {
+            let id = project_id.clone();
+            self.sync_focused_project_to_saved_layout(id);
+        }
+        self.projects.retain(|p| p.id != tutor.project_id);
+        let _ = std::fs::remove_dir_all(&tutor.project_path);
+        self.save_state();
+        self.show_notification("已退出教程".to_string(), NotificationLevel::Info);
+    }
+
+    /// 检查教程当前步骤是否已完成，完成后进入下一步
+    pub fn advance_tutor(&mut self) {
+        let Some(tutor) = &self.tutor else {
+            return;
+        };
+        let step = crate::ui::tutor::next_step(self, tutor);
+        if step == tutor.step {
+            return;
+        }
+        if let Some(tutor) = &mut self.tutor {
+            tutor.step = step;
+            if tutor.is_finished() {
+                self.show_notification(
+                    "教程完成！输入 :tutor 退出".to_string(),
+                    NotificationLevel::Success,
+                );
+            }
+        }
+    }
+
+    /// 切换专注模式；进入时若选中的是完成列则移到最近的其他列
+    pub fn toggle_focus_mode(&mut self) {
+        self.focus_mode = !self.focus_mode;
+        if !self.focus_mode {
+            return;
+        }
+        let Some(project) = self.get_focused_project() else {
+            return;
+        };
+        let current = self
+            .selected_column
+            .get(&self.focused_pane)
+            .copied()
+            .unwrap_or(0);
+        if !project.is_done_column(current) {
+            return;
+        }
+        let target = (0..current)
+            .rev()
+            .chain(current + 1..project.statuses.len())
+            .find(|&column| !project.is_done_column(column));
+        if let Some(column) = target {
+            self.selected_column.insert(self.focused_pane, column);
+            self.selected_task_index.insert(self.focused_pane, 0);
+        }
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::config::Config;
     use crate::models::{Project, ProjectType};
@@ -548,14 +1674,17 @@ mod tests {
     use std::path::PathBuf;
 
     fn test_project(name: &str) -> Project {
-        Project::new(
-            name.to_string(),
-            PathBuf::from("/tmp").join(name),
-            ProjectType::Global,
-        )
+        Project {
+            id: name.to_string(),
+            ..Project::new(
+                name.to_string(),
+                PathBuf::from("/tmp").join(name),
+                ProjectType::Global,
+            )
+        }
     }
 
-    fn test_app() -> App {
+    pub(crate) fn test_app() -> App {
         App {
             projects: vec![
                 test_project("alpha"),
@@ -570,10 +1699,30 @@ mod tests {
             mode: Mode::Normal,
             key_buffer: Vec::new(),
             key_buffer_started_at: None,
+            key_flash: None,
             selected_task_index: HashMap::new(),
             marked_tasks: HashSet::new(),
+            expanded_tasks: HashSet::new(),
             selected_column: HashMap::new(),
-            command_input: String::new(),
+            zoomed_pane: None,
+            focus_mode: false,
+            redact: false,
+            pane_diff: HashSet::new(),
+            last_edited: HashMap::new(),
+            last_input_at: Instant::now(),
+            screen_locked: false,
+            priority_filters: HashMap::new(),
+            tag_filters: HashMap::new(),
+            column_layouts: HashMap::new(),
+            read_only_panes: HashSet::new(),
+            external_changes: Vec::new(),
+            loaded_at: HashMap::new(),
+            io: crate::worker::IoWorker::spawn(),
+            autosave: Default::default(),
+            ime: Default::default(),
+            job: None,
+            grabbed_pane: None,
+            command_input: TextField::default(),
             completion_selected_index: None,
             next_pane_id: 3,
             should_quit: false,
@@ -583,21 +1732,43 @@ mod tests {
             pending_editor_file: None,
             is_new_task_file: false,
             pending_preview_file: None,
+            pending_shell_dir: None,
+            preview_title: String::new(),
+            preview_header: String::new(),
             preview_content: String::new(),
             preview_scroll: 0,
+            preview_link: None,
+            quick_add_input: TextField::default(),
+            status_rename_input: TextField::default(),
+            column_header_focus: None,
+            preview_base_dir: None,
+            image_protocol: None,
+            preview_images: Vec::new(),
+            shown_images: Vec::new(),
             command_registry: CommandRegistry::new(),
             config: Config::default(),
             show_welcome_dialog: false,
+            show_cheat_sheet: false,
             saved_layout: None,
+            home_layout: None,
+            upgraded_from: None,
+            collapsed_project_groups: Default::default(),
             notification: None,
+            last_save_error: None,
             last_column_resize_time: None,
-            list_states: HashMap::new(),
+            list_offsets: HashMap::new(),
+            column_selections: HashMap::new(),
+            card_cache: Default::default(),
             search_state: None,
             status_select_state: None,
+            tag_overview: None,
+            send_to_pane: None,
+            tutor: None,
+            spell_checker: None,
         }
     }
 
-    fn three_pane_layout() -> SplitNode {
+    pub(crate) fn three_pane_layout() -> SplitNode {
         SplitNode::Horizontal {
             left: Box::new(SplitNode::Leaf {
                 id: 0,
@@ -632,6 +1803,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn opening_a_shown_project_focuses_its_pane_when_reuse_is_enabled() {
+        let mut app = test_app();
+        app.split_tree = three_pane_layout();
+        app.config.reuse_open_pane = true;
+
+        app.open_or_focus_project(PathBuf::from("/tmp/gamma"), ProjectType::Global, false);
+
+        assert_eq!(app.focused_pane, 2);
+        assert_eq!(focused_project_id(&app), Some("gamma".to_string()));
+        assert_eq!(app.pane_showing_project(&PathBuf::from("/tmp/gamma")), None);
+        assert_eq!(
+            app.pane_showing_project(&PathBuf::from("/tmp/alpha")),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn opening_a_project_with_a_preferred_pane_moves_to_that_side() {
+        let mut app = test_app();
+        app.split_tree = three_pane_layout();
+        app.projects[2].pane = Some("right".to_string());
+        app.projects[1].pane = Some("bottom".to_string());
+
+        app.open_project(PathBuf::from("/tmp/gamma"), ProjectType::Global);
+        assert_eq!(app.focused_pane, 1);
+        assert_eq!(focused_project_id(&app), Some("gamma".to_string()));
+
+        app.open_project(PathBuf::from("/tmp/beta"), ProjectType::Global);
+        assert_eq!(app.focused_pane, 2);
+        assert_eq!(focused_project_id(&app), Some("beta".to_string()));
+
+        // 目标面板只读时留在当前面板
+        app.focused_pane = 0;
+        app.read_only_panes.insert(2);
+        app.open_project(PathBuf::from("/tmp/beta"), ProjectType::Global);
+        assert_eq!(app.focused_pane, 0);
+    }
+
+    #[test]
+    fn idle_lock_blanks_board_and_swallows_the_waking_key() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let mut app = test_app();
+        app.check_idle_lock();
+        assert!(!app.screen_locked, "未设置 idle_lock_minutes 时不隐藏");
+
+        app.config.idle_lock_minutes = 5;
+        app.last_input_at = Instant::now() - std::time::Duration::from_secs(4 * 60);
+        app.check_idle_lock();
+        assert!(!app.screen_locked);
+        app.last_input_at = Instant::now() - std::time::Duration::from_secs(5 * 60);
+        app.check_idle_lock();
+        assert!(app.screen_locked);
+        assert_eq!(app.window_title(), "hxk");
+
+        assert!(app.handle_key(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE)));
+        assert!(!app.screen_locked);
+        assert_eq!(app.mode, Mode::Normal);
+        app.check_idle_lock();
+        assert!(!app.screen_locked);
+    }
+
+    #[test]
+    fn completion_notice_carries_undo_until_replaced() {
+        let mut app = test_app();
+        let undo = UndoMove {
+            project_id: "alpha".to_string(),
+            task: Task::new(3, "Fix login bug".to_string(), "doing".to_string()),
+        };
+        let pending = |app: &App| app.pending_undo().map(|undo| undo.task.id);
+        app.show_completion("✓ Fix login bug → Done".to_string(), undo.clone());
+        assert_eq!(pending(&app), Some(3));
+
+        app.notification.as_mut().unwrap().created_at -= std::time::Duration::from_secs(4);
+        assert_eq!(pending(&app), Some(3));
+        app.notification.as_mut().unwrap().created_at -= std::time::Duration::from_secs(1);
+        assert_eq!(pending(&app), None);
+
+        app.show_completion("✓".to_string(), undo);
+        app.show_notification("其他消息".to_string(), NotificationLevel::Info);
+        assert_eq!(pending(&app), None);
+    }
+
+    #[test]
+    fn unbound_keys_flash_with_the_pending_sequence() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let mut app = test_app();
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE));
+        assert_eq!(app.pending_key_label().as_deref(), Some("␣w"));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('%'), KeyModifiers::NONE));
+        assert_eq!(
+            app.key_flash.as_ref().map(|(message, _)| message.as_str()),
+            Some("␣w% 未绑定")
+        );
+        assert_eq!(app.mode, Mode::SpaceMenu);
+    }
+
     #[test]
     fn focus_next_pane_while_maximized_keeps_fullscreen() {
         let mut app = test_app();
@@ -675,6 +1946,26 @@ mod tests {
         assert_eq!(app.split_tree.collect_pane_ids(), vec![0]);
     }
 
+    #[test]
+    fn swap_pane_project_swaps_with_adjacent_pane_and_follows_focus() {
+        let mut app = test_app();
+        app.split_tree = three_pane_layout();
+        app.selected_column.insert(0, 2);
+
+        assert!(app.swap_pane_project(Direction::Right));
+
+        assert_eq!(app.focused_pane, 1);
+        assert_eq!(focused_project_id(&app), Some("alpha".to_string()));
+        assert_eq!(app.selected_column.get(&1), Some(&2));
+        match app.split_tree.find_pane(0) {
+            Some(SplitNode::Leaf { project_id, .. }) => {
+                assert_eq!(project_id.as_deref(), Some("beta"))
+            }
+            _ => panic!("pane 0 should be a leaf"),
+        }
+        assert!(!app.swap_pane_project(Direction::Right));
+    }
+
     #[test]
     fn sync_focused_project_updates_saved_layout_while_maximized() {
         let mut app = test_app();
@@ -692,4 +1983,217 @@ mod tests {
         assert_eq!(saved_project_id(&app, 2), Some("gamma".to_string()));
         assert!(app.saved_layout.is_some());
     }
+
+    #[test]
+    fn priority_filter_applies_per_pane() {
+        let mut app = test_app();
+        let mut project = test_project("alpha");
+        for (id, priority) in [
+            (1, Some("high")),
+            (2, Some("medium")),
+            (3, Some("low")),
+            (4, None),
+        ] {
+            let mut task = Task::new(id, format!("task {}", id), "todo".to_string());
+            task.priority = priority.map(str::to_string);
+            project.tasks.push(task);
+        }
+
+        let ids = |app: &App, pane: usize| -> Vec<u32> {
+            app.column_tasks(pane, &project, "todo")
+                .iter()
+                .map(|t| t.id)
+                .collect()
+        };
+        assert_eq!(ids(&app, 0), vec![1, 2, 3, 4]);
+
+        app.priority_filters.insert(0, PriorityFilter::Medium);
+        assert_eq!(ids(&app, 0), vec![1, 2]);
+        app.priority_filters.insert(0, PriorityFilter::High);
+        assert_eq!(ids(&app, 0), vec![1]);
+        // 其他面板不受影响
+        assert_eq!(ids(&app, 1), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn tag_filter_combines_with_priority_filter() {
+        let mut app = test_app();
+        let mut project = test_project("alpha");
+        for (id, priority, tags) in [
+            (1, "high", vec!["api"]),
+            (2, "low", vec!["api", "ui"]),
+            (3, "high", vec!["ui"]),
+        ] {
+            let mut task = Task::new(id, format!("task {}", id), "todo".to_string());
+            task.priority = Some(priority.to_string());
+            task.tags = tags.into_iter().map(str::to_string).collect();
+            project.tasks.push(task);
+        }
+        let ids = |app: &App| -> Vec<u32> {
+            app.column_tasks(0, &project, "todo")
+                .iter()
+                .map(|t| t.id)
+                .collect()
+        };
+
+        app.tag_filters.insert(0, "api".to_string());
+        assert_eq!(ids(&app), vec![1, 2]);
+        app.priority_filters.insert(0, PriorityFilter::High);
+        assert_eq!(ids(&app), vec![1]);
+        assert_eq!(app.task_filter(0).label().as_deref(), Some("高 #api"));
+        assert!(!app.task_filter(1).is_active());
+    }
+
+    #[test]
+    fn window_title_shows_focused_project_counts() {
+        let mut app = test_app();
+        app.projects[0].statuses = ["todo", "doing"]
+            .iter()
+            .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+            .collect();
+        for (id, status) in [(1, "todo"), (2, "todo"), (3, "doing")] {
+            app.projects[0]
+                .tasks
+                .push(Task::new(id, format!("task {}", id), status.to_string()));
+        }
+        assert_eq!(app.window_title(), "hxk: alpha (todo 2 / doing 1)");
+
+        app.priority_filters.insert(0, PriorityFilter::High);
+        assert_eq!(app.window_title(), "hxk: alpha (todo 2 / doing 1) [高]");
+    }
+
+    #[test]
+    fn focus_mode_hides_done_column_and_navigation_skips_it() {
+        let mut app = test_app();
+        app.projects[0].statuses = ["todo", "doing", "done", "blocked"]
+            .iter()
+            .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+            .collect();
+        app.selected_column.insert(0, 2);
+
+        // 进入专注模式时离开完成列
+        app.toggle_focus_mode();
+        assert_eq!(app.selected_column[&0], 1);
+        let project = app.projects[0].clone();
+        assert!(app.is_column_hidden(0, &project, 2));
+        assert!(!app.is_column_hidden(0, &project, 3));
+
+        assert_eq!(app.adjacent_visible_column(true), Some(3));
+        app.selected_column.insert(0, 3);
+        assert_eq!(app.adjacent_visible_column(false), Some(1));
+
+        app.toggle_focus_mode();
+        assert!(!app.is_column_hidden(0, &project, 2));
+        assert_eq!(app.adjacent_visible_column(false), Some(2));
+    }
+
+    #[test]
+    fn done_column_collapses_past_threshold_until_selected() {
+        let mut app = test_app();
+        app.projects[0].statuses = ["todo", "doing", "done"]
+            .iter()
+            .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+            .collect();
+        for id in 10..13 {
+            let task = Task::new(id, format!("Done {}", id), "done".to_string());
+            app.projects[0].tasks.push(task);
+        }
+        let project = app.projects[0].clone();
+
+        // 未开启时不折叠
+        assert!(!app.is_column_collapsed(0, &project, 2));
+
+        app.config.collapse_done_after = 3;
+        assert!(!app.is_column_collapsed(0, &project, 2));
+        app.config.collapse_done_after = 2;
+        assert!(app.is_column_collapsed(0, &project, 2));
+        assert!(!app.is_column_collapsed(0, &project, 1));
+
+        // 选中完成列时展开
+        app.selected_column.insert(0, 2);
+        assert!(!app.is_column_collapsed(0, &project, 2));
+    }
+
+    #[test]
+    fn switching_columns_restores_previous_selection() {
+        let mut app = test_app();
+        app.projects[0].statuses = ["todo", "doing"]
+            .iter()
+            .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+            .collect();
+        for (id, status) in [
+            (1, "todo"),
+            (2, "todo"),
+            (3, "todo"),
+            (4, "doing"),
+            (5, "doing"),
+        ] {
+            let task = Task::new(id, format!("Task {}", id), status.to_string());
+            app.projects[0].tasks.push(task);
+        }
+
+        app.selected_task_index.insert(0, 2);
+        app.switch_column(1);
+        assert_eq!(app.selected_task_index[&0], 0);
+        app.selected_task_index.insert(0, 1);
+
+        app.switch_column(0);
+        assert_eq!(app.selected_task_index[&0], 2);
+        app.switch_column(1);
+        assert_eq!(app.selected_task_index[&0], 1);
+
+        // 记住的任务离开该列后按原来的索引恢复（超出时选中最后一个）
+        app.switch_column(0);
+        app.projects[0].tasks.retain(|t| t.id != 3);
+        app.switch_column(1);
+        app.switch_column(0);
+        assert_eq!(app.selected_task_index[&0], 1);
+    }
+
+    #[test]
+    fn restoring_home_layout_drops_session_splits() {
+        let mut app = test_app();
+        app.split_tree = three_pane_layout();
+        app.focused_pane = 1;
+        app.next_pane_id = 3;
+        assert!(!app.restore_home_layout());
+        app.set_home_layout();
+
+        // 会话中临时分屏、换项目、最大化
+        app.selected_column.insert(0, 2);
+        app.selected_column.insert(2, 1);
+        if let Some(SplitNode::Leaf { project_id, .. }) = app.split_tree.find_pane_mut(2) {
+            *project_id = Some("alpha".to_string());
+        }
+        app.split_tree.find_pane_mut(0).unwrap().split_horizontal(3);
+        app.next_pane_id = 4;
+        app.focused_pane = 3;
+        app.toggle_maximize();
+
+        assert!(app.restore_home_layout());
+        assert!(app.saved_layout.is_none());
+        assert_eq!(app.split_tree.collect_pane_ids(), vec![0, 1, 2]);
+        assert_eq!(app.focused_pane, 1);
+        assert_eq!(focused_project_id(&app).as_deref(), Some("beta"));
+        // 仍显示同一项目的面板保留选中的列
+        assert_eq!(app.selected_column.get(&0), Some(&2));
+        assert_eq!(app.selected_column.get(&2), None);
+        assert_eq!(app.next_pane_id, 4);
+    }
+
+    #[test]
+    fn changelog_after_upgrade_lists_releases_since_previous_version() {
+        let mut app = test_app();
+        app.upgraded_from = Some("0.3.3".to_string());
+        app.show_changelog(false);
+        assert_eq!(app.mode, Mode::Preview);
+        assert_eq!(
+            app.preview_title,
+            format!("新功能：0.3.3 → {}", crate::changelog::current_version())
+        );
+        assert!(!app.preview_content.contains("[0.3.3]"));
+
+        app.show_changelog(true);
+        assert!(app.preview_content.contains("[0.3.3]"));
+    }
 }
@@ -0,0 +1,155 @@
+/// 版本和构建信息（`hxk --version`、`:version` 关于弹窗）
+///
+/// 用户报告问题时附上这些信息，可以直接看出安装来自哪个提交、启用了哪些功能，
+/// 以及实际使用的配置和数据目录。git 提交和构建时间由 `build.rs` 在编译时写入。
+use chrono::DateTime;
+use std::path::PathBuf;
+
+/// 编译时启用的可选功能（Cargo features）
+pub fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "clipboard") {
+        features.push("clipboard");
+    }
+    if cfg!(feature = "images") {
+        features.push("images");
+    }
+    if cfg!(feature = "spellcheck") {
+        features.push("spellcheck");
+    }
+    if cfg!(feature = "scripting") {
+        features.push("scripting");
+    }
+    features
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub name: &'static str,
+    pub version: &'static str,
+    /// 构建时的 git 提交（不在 git 仓库中构建时为 None）
+    pub git_hash: Option<&'static str>,
+    /// 构建日期（UTC）
+    pub build_date: Option<String>,
+    pub features: Vec<&'static str>,
+    pub target: String,
+    pub config_path: PathBuf,
+    pub data_dir: PathBuf,
+    pub state_path: PathBuf,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        let git_hash = Some(env!("HXK_GIT_HASH")).filter(|hash| !hash.is_empty());
+        let build_date = env!("HXK_BUILD_TIMESTAMP")
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+            .map(|date| date.format("%Y-%m-%d").to_string());
+        Self {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            git_hash,
+            build_date,
+            features: enabled_features(),
+            target: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+            config_path: crate::config::get_config_path(),
+            data_dir: crate::fs::get_data_dir(),
+            state_path: crate::state::get_state_file_path(),
+        }
+    }
+
+    /// 第一行：`helix-kanban 0.3.4 (abc123def 2026-10-15)`
+    pub fn version_line(&self) -> String {
+        let details: Vec<&str> = [self.git_hash, self.build_date.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        if details.is_empty() {
+            format!("{} {}", self.name, self.version)
+        } else {
+            format!("{} {} ({})", self.name, self.version, details.join(" "))
+        }
+    }
+
+    /// 完整的多行文本（CLI 输出和关于弹窗共用）
+    pub fn to_text(&self) -> String {
+        let features = if self.features.is_empty() {
+            "(none)".to_string()
+        } else {
+            self.features.join(", ")
+        };
+        [
+            self.version_line(),
+            format!("commit:   {}", self.git_hash.unwrap_or("unknown")),
+            format!(
+                "built:    {}",
+                self.build_date.as_deref().unwrap_or("unknown")
+            ),
+            format!("target:   {}", self.target),
+            format!("features: {}", features),
+            format!("config:   {}", self.config_path.display()),
+            format!("data:     {}", self.data_dir.display()),
+            format!("state:    {}", self.state_path.display()),
+        ]
+        .join("\n")
+    }
+
+    pub fn to_json(&self) -> String {
+        let value = serde_json::json!({
+            "name": self.name,
+            "version": self.version,
+            "git_hash": self.git_hash,
+            "build_date": self.build_date,
+            "target": self.target,
+            "features": self.features,
+            "config_path": self.config_path,
+            "data_dir": self.data_dir,
+            "state_path": self.state_path,
+        });
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> BuildInfo {
+        BuildInfo {
+            name: "helix-kanban",
+            version: "1.2.3",
+            git_hash: Some("abc123def"),
+            build_date: Some("2026-10-15".to_string()),
+            features: vec!["clipboard", "images"],
+            target: "x86_64-linux".to_string(),
+            config_path: PathBuf::from("/home/u/.kanban/config.toml"),
+            data_dir: PathBuf::from("/home/u/.kanban"),
+            state_path: PathBuf::from("/home/u/.kanban/state.toml"),
+        }
+    }
+
+    #[test]
+    fn text_lists_commit_features_and_paths() {
+        let text = info().to_text();
+        assert!(text.starts_with("helix-kanban 1.2.3 (abc123def 2026-10-15)\n"));
+        assert!(text.contains("features: clipboard, images"));
+        assert!(text.contains("config:   /home/u/.kanban/config.toml"));
+
+        let bare = BuildInfo {
+            git_hash: None,
+            build_date: None,
+            ..info()
+        };
+        assert_eq!(bare.version_line(), "helix-kanban 1.2.3");
+        assert!(bare.to_text().contains("commit:   unknown"));
+    }
+
+    #[test]
+    fn json_is_machine_readable() {
+        let value: serde_json::Value = serde_json::from_str(&info().to_json()).unwrap();
+        assert_eq!(value["git_hash"], "abc123def");
+        assert_eq!(value["features"][1], "images");
+        assert_eq!(value["data_dir"], "/home/u/.kanban");
+    }
+}
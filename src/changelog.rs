@@ -0,0 +1,115 @@
+/// 内置更新日志（升级后的"新功能"弹窗和 `:changelog`）
+///
+/// 编译时嵌入 CHANGELOG.md。状态文件记录上次运行的版本，启动时版本更新了就显示
+/// 两个版本之间的发布说明；未发布（`[Unreleased]`）的内容不显示。
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+/// 当前版本号
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// 版本号 `a` 是否比 `b` 新（无法解析时视为不新）
+pub fn is_newer(a: &str, b: &str) -> bool {
+    match (parse_version(a), parse_version(b)) {
+        (Some(a), Some(b)) => a > b,
+        _ => false,
+    }
+}
+
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// 更新日志中的一个版本：(版本号, 标题行, 内容)
+fn sections(changelog: &str) -> Vec<(&str, &str, String)> {
+    let mut sections = Vec::new();
+    let mut current: Option<(&str, &str, Vec<&str>)> = None;
+    for line in changelog.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            if let Some((version, title, body)) = current.take() {
+                sections.push((version, title, body.join("\n").trim().to_string()));
+            }
+            let version = heading
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or_default();
+            current = Some((version, heading, Vec::new()));
+        } else if let Some((_, _, body)) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    if let Some((version, title, body)) = current {
+        sections.push((version, title, body.join("\n").trim().to_string()));
+    }
+    sections
+}
+
+/// `since`（不含）到 `current`（含）之间的发布说明；`since` 为 None 时只取 `current`
+pub fn release_notes(changelog: &str, since: Option<&str>, current: &str) -> String {
+    sections(changelog)
+        .into_iter()
+        .filter(|(version, _, _)| parse_version(version).is_some())
+        .filter(|(version, _, _)| match since {
+            Some(since) => is_newer(version, since) && !is_newer(version, current),
+            None => parse_version(version) == parse_version(current),
+        })
+        .map(|(_, title, body)| format!("## {}\n\n{}", title, body))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// 本次升级的新功能说明（`upgraded_from` 为升级前的版本）
+pub fn whats_new(upgraded_from: Option<&str>) -> String {
+    release_notes(CHANGELOG, upgraded_from, current_version())
+}
+
+/// 完整的更新日志（不含文件开头的说明）
+pub fn full() -> String {
+    CHANGELOG
+        .find("\n## ")
+        .map(|start| CHANGELOG[start + 1..].to_string())
+        .unwrap_or_else(|| CHANGELOG.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# Changelog\n\n## [Unreleased]\n\n### Added\n- 未发布\n\n\
+                          ## [0.3.0] - 2026-08-01\n\n### Added\n- 三\n\n\
+                          ## [0.2.10] - 2026-07-01\n\n### Fixed\n- 二\n\n\
+                          ## [0.2.9] - 2026-06-01\n\n- 一\n";
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert!(is_newer("0.2.10", "0.2.9"));
+        assert!(is_newer("0.3.0", "0.2.26"));
+        assert!(!is_newer("0.2.9", "0.2.9"));
+        assert!(!is_newer("garbage", "0.1.0"));
+    }
+
+    #[test]
+    fn notes_cover_versions_since_last_run() {
+        let notes = release_notes(SAMPLE, Some("0.2.9"), "0.3.0");
+        assert!(notes.starts_with("## [0.3.0] - 2026-08-01\n\n### Added\n- 三"));
+        assert!(notes.contains("## [0.2.10] - 2026-07-01"));
+        assert!(!notes.contains("一"));
+        assert!(!notes.contains("未发布"));
+
+        let current_only = release_notes(SAMPLE, None, "0.2.10");
+        assert_eq!(current_only, "## [0.2.10] - 2026-07-01\n\n### Fixed\n- 二");
+    }
+
+    #[test]
+    fn embedded_changelog_has_current_version() {
+        assert!(!whats_new(None).is_empty());
+        assert!(full().starts_with("## "));
+    }
+}
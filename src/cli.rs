@@ -1,8 +1,54 @@
+use crate::error::{self, EXIT_USAGE, KanbanError};
 use crate::fs;
 use crate::models::{ProjectType, Task};
+use crate::ui::width::{pad_to_width, truncate_to_width};
 use anyhow::Result;
 use std::env;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// `--quiet` / `-q`：只输出数据，省略确认信息、表头和空结果提示
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// 提示性输出，`--quiet` 时省略
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// 从参数中移除 `--quiet` / `-q`，返回是否指定了该选项
+fn take_quiet_flag(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != "--quiet" && arg != "-q");
+    args.len() != before
+}
+
+/// 参数错误（缺少参数、未知子命令等），退出码为 EXIT_USAGE
+fn usage(msg: impl Into<String>) -> KanbanError {
+    KanbanError::Validation(msg.into())
+}
+
+fn parse_task_id(arg: &str) -> error::Result<u32> {
+    arg.parse()
+        .map_err(|_| usage(format!("Invalid task ID (must be a number): {}", arg)))
+}
+
+/// 打印错误并按错误类型设置退出码
+fn exit_with(result: error::Result<()>) -> Result<bool> {
+    if let Err(e) = result {
+        eprintln!("Error: {}", e.message());
+        std::process::exit(e.exit_code());
+    }
+    Ok(false)
+}
 
 /// 处理 CLI 命令
 /// 返回 true 表示应该继续进入 TUI，false 表示已处理完毕应该退出
@@ -11,95 +57,245 @@ pub fn handle_cli() -> Result<bool> {
     let _ = fs::ensure_global_ai_config();
     let _ = fs::ensure_global_claude_md();
 
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    QUIET.store(take_quiet_flag(&mut args), Ordering::Relaxed);
 
     // 如果没有参数，进入 TUI 模式
     if args.len() < 2 {
         return Ok(true);
     }
 
+    // `hxk --no-color` / `hxk --safe-mode`：以无颜色方案或安全模式进入 TUI 模式
+    if args[1..]
+        .iter()
+        .all(|arg| arg == "--no-color" || arg == "--safe-mode")
+    {
+        if args.iter().any(|arg| arg == "--no-color") {
+            crate::ui::theme::request_no_color();
+        }
+        if args.iter().any(|arg| arg == "--safe-mode") {
+            crate::safe_mode::enable();
+        }
+        return Ok(true);
+    }
+
+    // 变更日志中的操作者：MCP 调用视为 AI，其余为命令行
+    fs::journal::set_default_actor(if args[1] == "mcp" { "mcp" } else { "cli" });
+
     // 处理 CLI 命令
     match args[1].as_str() {
         // MCP server
         "mcp" => {
             if let Err(e) = crate::mcp::start_mcp_server() {
                 eprintln!("MCP server error: {}", e);
-                std::process::exit(1);
+                std::process::exit(error::EXIT_FAILURE);
             }
             Ok(false)
         }
         // 新的结构化命令
-        "project" => {
-            if let Err(e) = handle_project_command(&args[1..]) {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
-            Ok(false)
-        }
-        "task" => {
-            if let Err(e) = handle_task_command(&args[1..]) {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
-            Ok(false)
-        }
-        "status" => {
-            if let Err(e) = handle_status_command(&args[1..]) {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
-            Ok(false)
-        }
+        "project" => exit_with(handle_project_command(&args[1..])),
+        "task" => exit_with(handle_task_command(&args[1..])),
+        "status" => exit_with(handle_status_command(&args[1..])),
+        "link" => exit_with(handle_link_command(&args[1..])),
+        "journal" => exit_with(handle_journal_command(&args[1..])),
+        "import" => exit_with(handle_import_command(&args[1..])),
+        "export" => exit_with(handle_export_command(&args[1..])),
+        "metrics" => exit_with(handle_metrics_command(&args[1..])),
+        "board" => exit_with(handle_board_command(&args[1..])),
+        "backup" => exit_with(handle_backup_command(&args[1..])),
+        "restore" => exit_with(handle_restore_command(&args[1..])),
+        "doctor" => exit_with(handle_doctor_command(&args[1..])),
         // 向后兼容的旧命令
         "create" => {
             if args.len() < 3 {
-                eprintln!("用法: hxk create <project-name>");
-                std::process::exit(1);
+                eprintln!("用法: hxk create <project-name> [--workflow <basic|dev>]");
+                std::process::exit(EXIT_USAGE);
             }
-            cli_create(&args[2])?;
-            Ok(false)
-        }
-        "list" => {
-            cli_list()?;
+            let workflow = match parse_workflow_flag(&args[3..]) {
+                Ok(workflow) => workflow,
+                Err(e) => {
+                    eprintln!("错误: {}", e.message());
+                    std::process::exit(e.exit_code());
+                }
+            };
+            cli_create(&args[2], workflow)?;
             Ok(false)
         }
+        "list" => exit_with(cli_list(&args[2..])),
         "add" => {
             if args.len() < 3 {
                 eprintln!("用法: hxk add <task-title>");
-                std::process::exit(1);
+                std::process::exit(EXIT_USAGE);
             }
-            cli_add(&args[2..])?;
-            Ok(false)
+            exit_with(cli_add(&args[2..]))
         }
         "config" => {
             if args.len() < 3 {
-                eprintln!("用法: hxk config <show|editor|viewer>");
+                eprintln!("用法: hxk config <show|editor|viewer|detect>");
                 eprintln!("示例:");
                 eprintln!("  hxk config show");
                 eprintln!("  hxk config editor nvim");
                 eprintln!("  hxk config viewer glow");
-                std::process::exit(1);
+                eprintln!("  hxk config detect");
+                std::process::exit(EXIT_USAGE);
             }
             match args[2].as_str() {
                 "show" => crate::config::show_config()?,
+                "detect" => crate::config::detect_and_save_tools()?,
                 "editor" => {
                     if args.len() < 4 {
                         eprintln!("用法: hxk config editor <命令>");
-                        std::process::exit(1);
+                        std::process::exit(EXIT_USAGE);
                     }
                     crate::config::set_editor(args[3..].join(" "))?;
                 }
                 "viewer" => {
                     if args.len() < 4 {
                         eprintln!("用法: hxk config viewer <命令>");
-                        std::process::exit(1);
+                        std::process::exit(EXIT_USAGE);
                     }
                     crate::config::set_viewer(args[3..].join(" "))?;
                 }
+                "status-counts" => match args.get(3).map(|s| s.as_str()) {
+                    Some("on") => crate::config::set_status_counts(true)?,
+                    Some("off") => crate::config::set_status_counts(false)?,
+                    _ => {
+                        eprintln!("用法: hxk config status-counts <on|off>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "reuse-pane" => match args.get(3).map(|s| s.as_str()) {
+                    Some("on") => crate::config::set_reuse_open_pane(true)?,
+                    Some("off") => crate::config::set_reuse_open_pane(false)?,
+                    _ => {
+                        eprintln!("用法: hxk config reuse-pane <on|off>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "palette" => match args
+                    .get(3)
+                    .and_then(|name| crate::ui::theme::Palette::parse(name))
+                {
+                    Some(palette) => crate::config::set_palette(palette)?,
+                    None => {
+                        eprintln!("用法: hxk config palette <default|color-blind|no-color>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "progress" => match args
+                    .get(3)
+                    .and_then(|name| crate::models::task::ProgressMetric::parse(name))
+                {
+                    Some(progress) => crate::config::set_progress(progress)?,
+                    None => {
+                        eprintln!("用法: hxk config progress <count|estimate|priority>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "terminal-title" => match args.get(3).map(|s| s.as_str()) {
+                    Some("on") => crate::config::set_terminal_title(true)?,
+                    Some("off") => crate::config::set_terminal_title(false)?,
+                    _ => {
+                        eprintln!("用法: hxk config terminal-title <on|off>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "key-hints" => match args.get(3).map(|s| s.as_str()) {
+                    Some("on") => crate::config::set_key_hints(true)?,
+                    Some("off") => crate::config::set_key_hints(false)?,
+                    _ => {
+                        eprintln!("用法: hxk config key-hints <on|off>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "backup-retention" => match args.get(3).and_then(|n| n.parse::<usize>().ok()) {
+                    Some(retention) => crate::config::set_backup_retention(retention)?,
+                    None => {
+                        eprintln!("用法: hxk config backup-retention <数量>（0 关闭自动备份）");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "preview-size" => match args.get(3).and_then(|n| n.parse::<u16>().ok()) {
+                    Some(size) => crate::config::set_preview_size(size)?,
+                    None => {
+                        eprintln!("用法: hxk config preview-size <40-100>（100 为全屏）");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "ime-auto-switch" => match args.get(3).map(|s| s.as_str()) {
+                    Some("on") => crate::config::set_ime_auto_switch(true)?,
+                    Some("off") => crate::config::set_ime_auto_switch(false)?,
+                    _ => {
+                        eprintln!("用法: hxk config ime-auto-switch <on|off>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "collapse-done" => match args.get(3).and_then(|n| {
+                    if n == "off" {
+                        Some(0)
+                    } else {
+                        n.parse::<usize>().ok()
+                    }
+                }) {
+                    Some(threshold) => crate::config::set_collapse_done_after(threshold)?,
+                    None => {
+                        eprintln!("用法: hxk config collapse-done <卡片数|off>（0 或 off 关闭）");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "idle-lock" => match args.get(3).and_then(|n| {
+                    if n == "off" {
+                        Some(0)
+                    } else {
+                        n.parse::<u64>().ok()
+                    }
+                }) {
+                    Some(minutes) => crate::config::set_idle_lock_minutes(minutes)?,
+                    None => {
+                        eprintln!("用法: hxk config idle-lock <分钟|off>（0 或 off 关闭）");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "default-status" => match args.get(3).map(|s| s.as_str()) {
+                    Some("none") => crate::config::set_default_status(None)?,
+                    Some(status) => crate::config::set_default_status(Some(status.to_string()))?,
+                    None => {
+                        eprintln!("用法: hxk config default-status <状态|none>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "default-priority" => match args.get(3).map(|s| s.as_str()) {
+                    Some("none") => crate::config::set_default_priority(None)?,
+                    Some(priority @ ("high" | "medium" | "low")) => {
+                        crate::config::set_default_priority(Some(priority.to_string()))?
+                    }
+                    _ => {
+                        eprintln!("用法: hxk config default-priority <high|medium|low|none>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "inline-images" => match args.get(3).map(|s| s.as_str()) {
+                    Some("on") => crate::config::set_inline_images(true)?,
+                    Some("off") => crate::config::set_inline_images(false)?,
+                    _ => {
+                        eprintln!("用法: hxk config inline-images <on|off>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
+                "spell-check" => match args.get(3).map(|s| s.as_str()) {
+                    Some("on") => crate::config::set_spell_check(true)?,
+                    Some("off") => crate::config::set_spell_check(false)?,
+                    _ => {
+                        eprintln!("用法: hxk config spell-check <on|off>");
+                        std::process::exit(EXIT_USAGE);
+                    }
+                },
                 _ => {
                     eprintln!("未知的配置选项: {}", args[2]);
-                    eprintln!("可用选项: show, editor, viewer");
-                    std::process::exit(1);
+                    eprintln!(
+                        "可用选项: show, editor, viewer, detect, status-counts, reuse-pane, inline-images, palette, progress, terminal-title, key-hints, backup-retention, preview-size, ime-auto-switch, default-status, default-priority, collapse-done, idle-lock, spell-check"
+                    );
+                    std::process::exit(EXIT_USAGE);
                 }
             }
             Ok(false)
@@ -109,13 +305,13 @@ pub fn handle_cli() -> Result<bool> {
             Ok(false)
         }
         "--version" | "-V" | "-v" => {
-            print_version();
+            print_version(args.get(2).is_some_and(|arg| arg == "--json"));
             Ok(false)
         }
         _ => {
             eprintln!("未知命令: {}", args[1]);
             eprintln!("使用 'hxk --help' 查看帮助");
-            std::process::exit(1);
+            std::process::exit(EXIT_USAGE);
         }
     }
 }
@@ -124,7 +320,7 @@ pub fn handle_cli() -> Result<bool> {
 // Project Commands
 // ============================================================================
 
-fn handle_project_command(args: &[String]) -> Result<(), String> {
+fn handle_project_command(args: &[String]) -> error::Result<()> {
     if args.len() < 2 {
         print_project_usage();
         return Ok(());
@@ -134,27 +330,48 @@ fn handle_project_command(args: &[String]) -> Result<(), String> {
         "list" => project_list(),
         "info" => {
             if args.len() < 3 {
-                return Err("Missing project name\nUsage: hxk project info <name>".to_string());
+                return Err(usage(
+                    "Missing project name\nUsage: hxk project info <name>",
+                ));
             }
             project_info(&args[2])
         }
         "create" => {
             if args.len() < 3 {
-                return Err(
-                    "Missing project name\nUsage: hxk project create <name> [--local]".to_string(),
-                );
+                return Err(usage(
+                    "Missing project name\nUsage: hxk project create <name> [--local] [--workflow <basic|dev>]",
+                ));
             }
-            let is_local = args.get(3).map(|s| s.as_str()) == Some("--local");
-            project_create(&args[2], is_local)
+            let is_local = args[3..].iter().any(|s| s == "--local");
+            let workflow = parse_workflow_flag(&args[3..])?;
+            project_create(&args[2], is_local, workflow)
+        }
+        "index" => {
+            if args.len() < 3 {
+                return Err(usage(
+                    "Missing project name\nUsage: hxk project index <name> [--drop]",
+                ));
+            }
+            let drop = args.get(3).map(|s| s.as_str()) == Some("--drop");
+            project_index(&args[2], drop)
+        }
+        "migrate" => {
+            if args.len() < 3 {
+                return Err(usage(
+                    "Missing project name\nUsage: hxk project migrate <name> [--yes]",
+                ));
+            }
+            let confirmed = args[3..].iter().any(|s| s == "--yes" || s == "-y");
+            project_migrate(&args[2], confirmed)
         }
         "help" | "--help" | "-h" => {
             print_project_usage();
             Ok(())
         }
-        cmd => Err(format!(
+        cmd => Err(usage(format!(
             "Unknown project command: {}\nRun 'hxk project help' for usage",
             cmd
-        )),
+        ))),
     }
 }
 
@@ -170,25 +387,33 @@ SUBCOMMANDS:
     info <name>       Show project information
     create <name>     Create a new global project
     create <name> --local  Create a new local project
+    create <name> --workflow dev
+                      Use backlog/todo/doing/review/blocked/done instead of todo/doing/done
+    index <name>      Build (or rebuild) the task index cache for a large project
+    index <name> --drop    Remove the index cache and fall back to directory scans
+    migrate <name>    Show pending file format migrations for a project
+    migrate <name> --yes   Back up the project and apply the migrations
 
 EXAMPLES:
     hxk project list
     hxk project info myproject
     hxk project create newproject
-    hxk project create localproject --local"
+    hxk project create localproject --local
+    hxk project index bigproject
+    hxk project migrate oldproject --yes"
     );
 }
 
-fn project_list() -> Result<(), String> {
-    let projects = fs::load_all_projects().map_err(|e| e.to_string())?;
+fn project_list() -> error::Result<()> {
+    let projects = fs::load_all_projects().map_err(|e| KanbanError::io(e.to_string()))?;
 
     if projects.is_empty() {
-        println!("No projects found.");
+        say!("No projects found.");
         return Ok(());
     }
 
-    println!("TYPE  NAME                             PATH");
-    println!("----  ------------------------------  ------------------------------------");
+    say!("TYPE  NAME                             PATH");
+    say!("----  ------------------------------  ------------------------------------");
 
     for project in projects {
         let type_marker = match project.project_type {
@@ -197,18 +422,95 @@ fn project_list() -> Result<(), String> {
         };
 
         let path = project.path.to_string_lossy();
-        println!("{:<4}  {:<30}  {}", type_marker, project.name, path);
+        println!(
+            "{:<4}  {}  {}",
+            type_marker,
+            pad_to_width(&project.name, 30),
+            path
+        );
+    }
+
+    Ok(())
+}
+
+fn project_index(name: &str, drop: bool) -> error::Result<()> {
+    let project_path = find_project_path(name)?;
+
+    if drop {
+        if fs::index::drop_index(&project_path)? {
+            say!("Removed index cache for '{}'", name);
+        } else {
+            say!("Project '{}' has no index cache", name);
+        }
+        return Ok(());
     }
 
+    let stats = fs::index::build_index(&project_path)?;
+    say!(
+        "Indexed {} task(s) for '{}' ({})",
+        stats.parsed,
+        name,
+        project_path.join(fs::index::INDEX_FILE).display()
+    );
+
     Ok(())
 }
 
-fn project_info(name: &str) -> Result<(), String> {
-    let projects = fs::load_all_projects().map_err(|e| e.to_string())?;
+fn project_migrate(name: &str, confirmed: bool) -> error::Result<()> {
+    let project_path = find_project_path(name)?;
+    let plan = fs::migration::plan_migration(&project_path)?;
+
+    if plan.is_up_to_date() {
+        say!(
+            "Project '{}' is already at format version {}",
+            name,
+            plan.from_version
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Project '{}' uses format version {} (current: {})",
+        name,
+        plan.from_version,
+        fs::migration::CURRENT_FORMAT_VERSION
+    );
+    for change in &plan.changes {
+        println!("  - {}", change);
+    }
+
+    if plan.needs_confirmation() && !confirmed {
+        println!(
+            "\nRun 'hxk project migrate {} --yes' to back up and apply",
+            name
+        );
+        return Ok(());
+    }
+
+    let report = fs::migration::migrate_project(&project_path)?;
+    match report.backup {
+        Some(backup) => say!(
+            "Migrated '{}' ({} change(s)), backup: {}",
+            name,
+            report.applied,
+            backup.display()
+        ),
+        None => say!(
+            "Updated '{}' to format version {}",
+            name,
+            fs::migration::CURRENT_FORMAT_VERSION
+        ),
+    }
+
+    Ok(())
+}
+
+fn project_info(name: &str) -> error::Result<()> {
+    let projects = fs::load_all_projects().map_err(|e| KanbanError::io(e.to_string()))?;
     let project = projects
         .iter()
         .find(|p| p.name == name)
-        .ok_or_else(|| format!("Project '{}' not found", name))?;
+        .ok_or_else(|| KanbanError::NotFound(format!("Project '{}' not found", name)))?;
 
     println!("Project: {}", project.name);
     println!(
@@ -232,14 +534,30 @@ fn project_info(name: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn project_create(name: &str, is_local: bool) -> Result<(), String> {
+/// 解析 `--workflow <basic|dev>`，未指定时使用 basic
+fn parse_workflow_flag(args: &[String]) -> error::Result<fs::Workflow> {
+    match parse_flag(args, "--workflow") {
+        Some(name) => fs::Workflow::from_name(&name).ok_or_else(|| {
+            usage(format!(
+                "Unknown workflow: {}\nAvailable workflows: basic, dev",
+                name
+            ))
+        }),
+        None => Ok(fs::Workflow::Basic),
+    }
+}
+
+fn project_create(name: &str, is_local: bool, workflow: fs::Workflow) -> error::Result<()> {
     let path = if is_local {
-        fs::create_local_project(name)?
+        fs::create_local_project(name, workflow)?
     } else {
-        fs::create_project(name)?
+        fs::create_project(name, workflow)?
     };
 
-    println!(
+    if quiet() {
+        println!("{}", path.to_string_lossy());
+    }
+    say!(
         "Created {} project: {}",
         if is_local { "local" } else { "global" },
         path.to_string_lossy()
@@ -252,7 +570,7 @@ fn project_create(name: &str, is_local: bool) -> Result<(), String> {
 // Task Commands
 // ============================================================================
 
-fn handle_task_command(args: &[String]) -> Result<(), String> {
+fn handle_task_command(args: &[String]) -> error::Result<()> {
     if args.len() < 2 {
         print_task_usage();
         return Ok(());
@@ -261,43 +579,53 @@ fn handle_task_command(args: &[String]) -> Result<(), String> {
     match args[1].as_str() {
         "list" => {
             if args.len() < 3 {
-                return Err(
-                    "Missing project name\nUsage: hxk task list <project> [--status <status>]"
-                        .to_string(),
-                );
+                return Err(usage(
+                    "Missing project name\nUsage: hxk task list <project> [--status <status>] [--priority <priority>] [--tag <tag>] [--search <text>] [--sort board|id|priority|created|title]",
+                ));
             }
-            let status = parse_flag(&args[3..], "--status");
-            task_list(&args[2], status)
+            let flags = &args[3..];
+            let sort = match parse_flag(flags, "--sort") {
+                None => fs::index::TaskSort::default(),
+                Some(name) => fs::index::TaskSort::parse(&name)
+                    .ok_or_else(|| usage(format!("Unknown sort '{}'", name)))?,
+            };
+            let query = fs::index::TaskQuery {
+                status: parse_flag(flags, "--status"),
+                priority: parse_flag(flags, "--priority"),
+                tag: parse_flag(flags, "--tag"),
+                text: parse_flag(flags, "--search"),
+                sort,
+            };
+            task_list(&args[2], &query)
         }
         "show" => {
             if args.len() < 4 {
-                return Err(
-                    "Missing arguments\nUsage: hxk task show <project> <task-id>".to_string(),
-                );
+                return Err(usage(
+                    "Missing arguments\nUsage: hxk task show <project> <task-id>",
+                ));
             }
-            let task_id: u32 = args[3]
-                .parse()
-                .map_err(|_| "Invalid task ID (must be a number)".to_string())?;
+            let task_id = parse_task_id(&args[3])?;
             task_show(&args[2], task_id)
         }
         "create" => {
             if args.len() < 3 {
-                return Err("Missing project name\nUsage: hxk task create <project> --status <status> --title <title> [--content <content>]".to_string());
+                return Err(usage(
+                    "Missing project name\nUsage: hxk task create <project> [--status <status>] --title <title> [--content <content>]",
+                ));
             }
-            let status =
-                parse_flag(&args[3..], "--status").ok_or("Missing --status flag".to_string())?;
+            let status = parse_flag(&args[3..], "--status");
             let title =
-                parse_flag(&args[3..], "--title").ok_or("Missing --title flag".to_string())?;
+                parse_flag(&args[3..], "--title").ok_or_else(|| usage("Missing --title flag"))?;
             let content = parse_flag(&args[3..], "--content");
-            task_create(&args[2], &status, &title, content)
+            task_create(&args[2], status, &title, content)
         }
         "update" => {
             if args.len() < 4 {
-                return Err("Missing arguments\nUsage: hxk task update <project> <task-id> [--title <title>] [--content <content>] [--priority <priority>]".to_string());
+                return Err(usage(
+                    "Missing arguments\nUsage: hxk task update <project> <task-id> [--title <title>] [--content <content>] [--priority <priority>]",
+                ));
             }
-            let task_id: u32 = args[3]
-                .parse()
-                .map_err(|_| "Invalid task ID (must be a number)".to_string())?;
+            let task_id = parse_task_id(&args[3])?;
             let title = parse_flag(&args[4..], "--title");
             let content = parse_flag(&args[4..], "--content");
             let priority = parse_flag(&args[4..], "--priority");
@@ -305,37 +633,42 @@ fn handle_task_command(args: &[String]) -> Result<(), String> {
         }
         "move" => {
             if args.len() < 4 {
-                return Err(
-                    "Missing arguments\nUsage: hxk task move <project> <task-id> --to <status>"
-                        .to_string(),
-                );
+                return Err(usage(
+                    "Missing arguments\nUsage: hxk task move <project> <task-id> --to <status>",
+                ));
             }
-            let task_id: u32 = args[3]
-                .parse()
-                .map_err(|_| "Invalid task ID (must be a number)".to_string())?;
+            let task_id = parse_task_id(&args[3])?;
             let to_status =
-                parse_flag(&args[4..], "--to").ok_or("Missing --to flag".to_string())?;
+                parse_flag(&args[4..], "--to").ok_or_else(|| usage("Missing --to flag"))?;
             task_move(&args[2], task_id, &to_status)
         }
         "delete" => {
             if args.len() < 4 {
-                return Err(
-                    "Missing arguments\nUsage: hxk task delete <project> <task-id>".to_string(),
-                );
+                return Err(usage(
+                    "Missing arguments\nUsage: hxk task delete <project> <task-id>",
+                ));
             }
-            let task_id: u32 = args[3]
-                .parse()
-                .map_err(|_| "Invalid task ID (must be a number)".to_string())?;
+            let task_id = parse_task_id(&args[3])?;
             task_delete(&args[2], task_id)
         }
+        "renumber" => {
+            if args.len() < 3 {
+                return Err(usage(
+                    "Missing project name\nUsage: hxk task renumber <project> [--dry-run] [--yes]",
+                ));
+            }
+            let dry_run = args[3..].iter().any(|a| a == "--dry-run");
+            let assume_yes = args[3..].iter().any(|a| a == "--yes" || a == "-y");
+            task_renumber(&args[2], dry_run, assume_yes)
+        }
         "help" | "--help" | "-h" => {
             print_task_usage();
             Ok(())
         }
-        cmd => Err(format!(
+        cmd => Err(usage(format!(
             "Unknown task command: {}\nRun 'hxk task help' for usage",
             cmd
-        )),
+        ))),
     }
 }
 
@@ -347,8 +680,9 @@ USAGE:
     hxk task <SUBCOMMAND>
 
 SUBCOMMANDS:
-    list <project> [--status <status>]
-        List tasks in a project
+    list <project> [--status <status>] [--priority <priority>] [--tag <tag>] [--search <text>]
+         [--sort board|id|priority|created|title]
+        List tasks in a project (uses the index cache when built)
 
     show <project> <task-id>
         Show task details
@@ -365,6 +699,9 @@ SUBCOMMANDS:
     delete <project> <task-id>
         Delete a task
 
+    renumber <project> [--dry-run] [--yes]
+        Rewrite task ids sequentially (1..n), renaming id-based files
+
 EXAMPLES:
     hxk task list myproject
     hxk task list myproject --status todo
@@ -372,7 +709,8 @@ EXAMPLES:
     hxk task create myproject --status todo --title \"新任务\"
     hxk task update myproject 42 --priority high
     hxk task move myproject 42 --to doing
-    hxk task delete myproject 42"
+    hxk task delete myproject 42
+    hxk task renumber myproject --dry-run"
     );
 }
 
@@ -383,36 +721,47 @@ fn parse_flag(args: &[String], flag: &str) -> Option<String> {
         .cloned()
 }
 
-fn find_project_path(project_name: &str) -> Result<PathBuf, String> {
-    let projects = fs::load_all_projects().map_err(|e| e.to_string())?;
+fn find_project_path(project_name: &str) -> error::Result<PathBuf> {
+    let projects = fs::load_all_projects().map_err(|e| KanbanError::io(e.to_string()))?;
     projects
         .iter()
         .find(|p| p.name == project_name)
         .map(|p| p.path.clone())
-        .ok_or_else(|| format!("Project '{}' not found", project_name))
+        .ok_or_else(|| KanbanError::NotFound(format!("Project '{}' not found", project_name)))
 }
 
-fn task_list(project_name: &str, filter_status: Option<String>) -> Result<(), String> {
-    let project_path = find_project_path(project_name)?;
-    let project = fs::load_project(&project_path)?;
+/// 状态名不存在时的参数错误，附带可用状态列表
+fn unknown_status(status: &str, project: &crate::models::Project) -> KanbanError {
+    let names: Vec<&str> = project.statuses.iter().map(|s| s.name.as_str()).collect();
+    usage(format!(
+        "Unknown status: {}\nAvailable statuses: {}",
+        status,
+        names.join(", ")
+    ))
+}
 
-    let tasks: Vec<&Task> = if let Some(status) = filter_status {
-        project
-            .tasks
-            .iter()
-            .filter(|t| t.status == status)
-            .collect()
+/// 确认项目中存在该状态，避免在磁盘上创建孤立的状态目录
+fn check_status(project_path: &std::path::Path, status: &str) -> error::Result<()> {
+    let project = fs::load_project(project_path).map_err(KanbanError::io)?;
+    if project.statuses.iter().any(|s| s.name == status) {
+        Ok(())
     } else {
-        project.tasks.iter().collect()
-    };
+        Err(unknown_status(status, &project))
+    }
+}
+
+/// 列出任务；项目建立了索引时只读取索引，不解析任务文件
+fn task_list(project_name: &str, query: &fs::index::TaskQuery) -> error::Result<()> {
+    let project_path = find_project_path(project_name)?;
+    let tasks = fs::index::query(&project_path, query).map_err(KanbanError::io)?;
 
     if tasks.is_empty() {
-        println!("No tasks found.");
+        say!("No tasks found.");
         return Ok(());
     }
 
-    println!("ID    ORDER  TITLE                                PRIORITY  STATUS    TAGS");
-    println!("----  -----  -----------------------------------  --------  --------  --------");
+    say!("ID    ORDER  TITLE                                PRIORITY  STATUS    TAGS");
+    say!("----  -----  -----------------------------------  --------  --------  --------");
 
     for task in tasks {
         let priority = task.priority.as_deref().unwrap_or("-");
@@ -424,28 +773,28 @@ fn task_list(project_name: &str, filter_status: Option<String>) -> Result<(), St
         };
 
         println!(
-            "{:<4}  {:<5}  {:<35}  {:<8}  {:<8}  {}",
+            "{:<4}  {:<5}  {}  {:<8}  {}  {}",
             task.id,
             task.order,
-            truncate(&task.title, 35),
+            pad_to_width(&truncate_to_width(&task.title, 35), 35),
             priority,
-            truncate(&task.status, 8),
-            truncate(&tags_display, 20)
+            pad_to_width(&truncate_to_width(&task.status, 8), 8),
+            truncate_to_width(&tags_display, 20)
         );
     }
 
     Ok(())
 }
 
-fn task_show(project_name: &str, task_id: u32) -> Result<(), String> {
+fn task_show(project_name: &str, task_id: u32) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
-    let project = fs::load_project(&project_path)?;
+    let project = fs::load_project(&project_path).map_err(KanbanError::io)?;
 
     let task = project
         .tasks
         .iter()
         .find(|t| t.id == task_id)
-        .ok_or_else(|| format!("Task {} not found", task_id))?;
+        .ok_or_else(|| KanbanError::NotFound(format!("Task {} not found", task_id)))?;
 
     println!("Task #{}", task.id);
     println!("Title: {}", task.title);
@@ -470,29 +819,41 @@ fn task_show(project_name: &str, task_id: u32) -> Result<(), String> {
 
 fn task_create(
     project_name: &str,
-    status: &str,
+    status: Option<String>,
     title: &str,
     content: Option<String>,
-) -> Result<(), String> {
+) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
+    // Without --status the task lands in the configured default status (or todo)
+    let defaults = new_task_defaults(&project_path);
+    let status = status
+        .or(defaults.status)
+        .unwrap_or_else(|| "todo".to_string());
+    let status = status.as_str();
+    check_status(&project_path, status)?;
 
     // Get next task ID
-    let next_id = fs::get_next_task_id(&project_path)?;
+    let next_id = fs::get_next_task_id(&project_path).map_err(KanbanError::io)?;
 
     // Get max order for the status
-    let max_order = fs::get_max_order_in_status(&project_path, status)?;
+    let max_order = fs::get_max_order_in_status(&project_path, status).map_err(KanbanError::io)?;
     let new_order = max_order + 1000;
 
     // Create task
     let mut task = Task::new(next_id, title.to_string(), status.to_string());
     task.order = new_order;
-    task.content = content.unwrap_or_default();
+    task.priority = defaults.priority;
+    task.set_content(content.unwrap_or_default());
 
     // Save task
-    let file_path = fs::save_task(&project_path, &task)?;
+    let file_path = fs::save_task(&project_path, &task).map_err(KanbanError::io)?;
 
-    println!("Created task #{} in status '{}'", task.id, status);
-    println!("File: {}", file_path.to_string_lossy());
+    // 安静模式只输出新任务 ID，便于脚本捕获
+    if quiet() {
+        println!("{}", task.id);
+    }
+    say!("Created task #{} in status '{}'", task.id, status);
+    say!("File: {}", file_path.to_string_lossy());
 
     Ok(())
 }
@@ -503,15 +864,15 @@ fn task_update(
     title: Option<String>,
     content: Option<String>,
     priority: Option<String>,
-) -> Result<(), String> {
+) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
-    let mut project = fs::load_project(&project_path)?;
+    let mut project = fs::load_project(&project_path).map_err(KanbanError::io)?;
 
     let task = project
         .tasks
         .iter_mut()
         .find(|t| t.id == task_id)
-        .ok_or_else(|| format!("Task {} not found", task_id))?;
+        .ok_or_else(|| KanbanError::NotFound(format!("Task {} not found", task_id)))?;
 
     let mut updated = false;
 
@@ -521,11 +882,20 @@ fn task_update(
     }
 
     if let Some(new_content) = content {
-        task.content = new_content;
+        task.set_content(new_content);
         updated = true;
     }
 
     if let Some(new_priority) = priority {
+        if !matches!(
+            new_priority.as_str(),
+            "high" | "medium" | "low" | "none" | ""
+        ) {
+            return Err(usage(format!(
+                "Invalid priority: {}\nExpected one of: high, medium, low, none",
+                new_priority
+            )));
+        }
         task.priority = if new_priority == "none" || new_priority.is_empty() {
             None
         } else {
@@ -535,69 +905,678 @@ fn task_update(
     }
 
     if updated {
-        fs::save_task(&project_path, task)?;
-        println!("Updated task #{}", task_id);
+        fs::save_task(&project_path, task).map_err(KanbanError::io)?;
+        say!("Updated task #{}", task_id);
     } else {
-        println!("No changes made to task #{}", task_id);
+        say!("No changes made to task #{}", task_id);
     }
 
     Ok(())
 }
 
-fn task_move(project_name: &str, task_id: u32, new_status: &str) -> Result<(), String> {
+fn task_move(project_name: &str, task_id: u32, new_status: &str) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
-    let mut project = fs::load_project(&project_path)?;
+    let mut project = fs::load_project(&project_path).map_err(KanbanError::io)?;
+    if !project.statuses.iter().any(|s| s.name == new_status) {
+        return Err(unknown_status(new_status, &project));
+    }
 
     let task = project
         .tasks
         .iter_mut()
         .find(|t| t.id == task_id)
-        .ok_or_else(|| format!("Task {} not found", task_id))?;
+        .ok_or_else(|| KanbanError::NotFound(format!("Task {} not found", task_id)))?;
 
     let old_status = task.status.clone();
 
     // Move file and update task
-    let new_path = fs::move_task(&project_path, task, new_status)?;
+    let new_path = fs::move_task(&project_path, task, new_status).map_err(KanbanError::io)?;
     task.file_path = new_path;
 
-    println!(
+    say!(
         "Moved task #{} from '{}' to '{}'",
-        task_id, old_status, new_status
+        task_id,
+        old_status,
+        new_status
     );
 
     Ok(())
 }
 
-fn task_delete(project_name: &str, task_id: u32) -> Result<(), String> {
+fn task_delete(project_name: &str, task_id: u32) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
-    let project = fs::load_project(&project_path)?;
+    let project = fs::load_project(&project_path).map_err(KanbanError::io)?;
 
     let task = project
         .tasks
         .iter()
         .find(|t| t.id == task_id)
-        .ok_or_else(|| format!("Task {} not found", task_id))?;
+        .ok_or_else(|| KanbanError::NotFound(format!("Task {} not found", task_id)))?;
 
-    fs::delete_task(&project_path, task)?;
+    fs::delete_task(&project_path, task).map_err(KanbanError::io)?;
 
-    println!("Deleted task #{}", task_id);
+    say!("Deleted task #{}", task_id);
 
     Ok(())
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.chars().count() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", s.chars().take(max_len - 3).collect::<String>())
+fn task_renumber(project_name: &str, dry_run: bool, assume_yes: bool) -> error::Result<()> {
+    let project_path = find_project_path(project_name)?;
+    let project = fs::load_project(&project_path).map_err(KanbanError::io)?;
+
+    let plan = fs::task::plan_task_renumber(&project.tasks);
+    if plan.is_empty() {
+        say!("Task ids are already sequential.");
+        return Ok(());
+    }
+
+    for (old_id, new_id) in &plan {
+        println!("#{} -> #{}", old_id, new_id);
+    }
+
+    if dry_run {
+        say!("Dry run: {} task(s) would be renumbered.", plan.len());
+        return Ok(());
+    }
+
+    if !assume_yes {
+        print!("Renumber {} task(s)? [y/N] ", plan.len());
+        std::io::Write::flush(&mut std::io::stdout()).map_err(|e| e.to_string())?;
+        let mut answer = String::new();
+        std::io::stdin()
+            .read_line(&mut answer)
+            .map_err(|e| e.to_string())?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            say!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    fs::backup::before(&project_path, "renumber")?;
+    let applied = fs::task::renumber_tasks(&project_path)?;
+    say!("Renumbered {} task(s).", applied.len());
+
+    Ok(())
+}
+
+// ============================================================================
+// Link Commands
+// ============================================================================
+
+fn handle_link_command(args: &[String]) -> error::Result<()> {
+    let link_file = fs::project::get_link_file_path();
+
+    match args.get(1).map(|s| s.as_str()) {
+        None | Some("--list") => {
+            let linked = fs::project::read_linked_projects(&link_file);
+            if linked.is_empty() {
+                say!("No projects linked to this directory.");
+            } else {
+                for name in linked {
+                    println!("{}", name);
+                }
+            }
+            Ok(())
+        }
+        Some("help" | "--help" | "-h") => {
+            println!(
+                "Kanban Link
+
+USAGE:
+    hxk link <global-project>            Pin a global project when hxk starts in this directory
+    hxk link --remove <global-project>   Remove the link
+    hxk link --list                      Show linked projects
+
+Links are stored in ./.kanban-link (one project name per line)."
+            );
+            Ok(())
+        }
+        Some("--remove") => {
+            let name = args.get(2).ok_or_else(|| {
+                usage("Missing project name\nUsage: hxk link --remove <global-project>")
+            })?;
+            if fs::project::unlink_project(&link_file, name)? {
+                say!("Unlinked '{}' from this directory", name);
+            } else {
+                say!("'{}' is not linked to this directory", name);
+            }
+            Ok(())
+        }
+        Some(name) => {
+            let projects = fs::load_all_projects().map_err(|e| KanbanError::io(e.to_string()))?;
+            match projects.iter().find(|p| p.name == name) {
+                Some(project) if project.project_type == ProjectType::Global => {}
+                Some(_) => {
+                    return Err(usage(format!(
+                        "'{}' is a local project; only global projects can be linked",
+                        name
+                    )));
+                }
+                None => {
+                    return Err(KanbanError::NotFound(format!(
+                        "Project '{}' not found",
+                        name
+                    )));
+                }
+            }
+
+            if fs::project::link_project(&link_file, name)? {
+                say!("Linked '{}' to {}", name, link_file.display());
+            } else {
+                say!("'{}' is already linked to this directory", name);
+            }
+            Ok(())
+        }
     }
 }
 
+// ============================================================================
+// Journal Commands
+// ============================================================================
+
+fn handle_journal_command(args: &[String]) -> error::Result<()> {
+    if matches!(
+        args.get(1).map(|s| s.as_str()),
+        Some("help" | "--help" | "-h")
+    ) {
+        println!(
+            "Kanban Journal
+
+USAGE:
+    hxk journal [--project <project>] [--limit <n>]
+
+Shows the most recent task mutations (create/edit/move/delete) recorded in
+.kanban/journal.ndjson, newest first. Set HXK_ACTOR to label your own changes."
+        );
+        return Ok(());
+    }
+
+    let project = parse_flag(&args[1..], "--project");
+    let limit = match parse_flag(&args[1..], "--limit") {
+        Some(n) => n
+            .parse::<usize>()
+            .map_err(|_| usage(format!("Invalid --limit value: {}", n)))?,
+        None => 50,
+    };
+
+    let (paths, key) = match &project {
+        Some(name) => {
+            let project_path = find_project_path(name)?;
+            let config = fs::load_project_config(&project_path)?;
+            let key = fs::journal::ProjectKey {
+                id: config.id.unwrap_or_default(),
+                name: config.name,
+            };
+            let paths = fs::journal::journal_path(&project_path)
+                .into_iter()
+                .collect();
+            (paths, Some(key))
+        }
+        None => (fs::journal::default_journal_paths(), None),
+    };
+
+    let entries = fs::journal::read_recent(&paths, key.as_ref(), limit);
+    if entries.is_empty() {
+        say!("No journal entries found.");
+        return Ok(());
+    }
+
+    for entry in &entries {
+        println!("{}", fs::journal::format_entry(entry));
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Backup Commands
+// ============================================================================
+
+fn handle_backup_command(args: &[String]) -> error::Result<()> {
+    match args.get(1).map(|s| s.as_str()) {
+        Some("help" | "--help" | "-h") => {
+            print_backup_usage();
+            Ok(())
+        }
+        Some("--list") => {
+            let archives = fs::backup::list_backups(&fs::backup::backups_dir());
+            if archives.is_empty() {
+                say!("No backups found.");
+            }
+            for archive in archives {
+                println!("{}", archive.display());
+            }
+            Ok(())
+        }
+        Some(name) => backup_projects(&[find_project_path(name)?]),
+        None => {
+            let projects = fs::load_all_projects().map_err(|e| KanbanError::io(e.to_string()))?;
+            let paths: Vec<PathBuf> = projects.into_iter().map(|p| p.path).collect();
+            backup_projects(&paths)
+        }
+    }
+}
+
+fn backup_projects(paths: &[PathBuf]) -> error::Result<()> {
+    for path in paths {
+        let archive = fs::backup::create_backup(path)?;
+        println!("{}", archive.display());
+    }
+    say!("Backed up {} project(s)", paths.len());
+    Ok(())
+}
+
+fn handle_restore_command(args: &[String]) -> error::Result<()> {
+    let Some(arg) = args.get(1) else {
+        print_backup_usage();
+        return Err(usage("Missing archive\nUsage: hxk restore <archive>"));
+    };
+    if matches!(arg.as_str(), "help" | "--help" | "-h") {
+        print_backup_usage();
+        return Ok(());
+    }
+
+    let archive = fs::backup::resolve_archive(&fs::backup::backups_dir(), arg)
+        .ok_or_else(|| KanbanError::NotFound(format!("Backup not found: {}", arg)))?;
+    let target = fs::backup::restore_backup(&archive)?;
+    say!("Restored {} to {}", archive.display(), target.display());
+    Ok(())
+}
+
+fn print_backup_usage() {
+    println!(
+        "Kanban Backup
+
+USAGE:
+    hxk backup [project]      Back up one project (or every project) now
+    hxk backup --list         List backups in ~/.kanban/backups/
+    hxk restore <archive>     Restore a backup to its original location
+
+Backups are created automatically before deleting projects or statuses,
+renames and bulk operations. Each project keeps the newest 20 backups;
+change this with `hxk config backup-retention <n>` (0 disables them).
+Restoring over an existing project backs that project up first."
+    );
+}
+
+// ============================================================================
+// Doctor
+// ============================================================================
+
+fn handle_doctor_command(args: &[String]) -> error::Result<()> {
+    let paths = match args.get(1).map(|s| s.as_str()) {
+        Some("help" | "--help" | "-h") => {
+            print_doctor_usage();
+            return Ok(());
+        }
+        Some(name) => vec![find_project_path(name)?],
+        None => {
+            let mut paths = fs::list_project_dirs().map_err(|e| KanbanError::io(e.to_string()))?;
+            paths
+                .extend(fs::list_local_project_dirs().map_err(|e| KanbanError::io(e.to_string()))?);
+            paths
+        }
+    };
+
+    let mut problems = 0;
+    for path in &paths {
+        let report = match fs::project::check_status_dirs(path) {
+            Ok(report) if report.is_ok() => continue,
+            Ok(report) => report,
+            Err(e) => {
+                problems += 1;
+                println!("{}\n  cannot read .kanban.toml: {}", path.display(), e);
+                continue;
+            }
+        };
+        println!("{}", path.display());
+        for name in &report.missing {
+            println!(
+                "  missing directory for status '{}' (created on next load)",
+                name
+            );
+        }
+        for name in &report.unlisted {
+            println!(
+                "  directory '{}' is not a status in .kanban.toml (added as a column on next load; rename it to start with '.' to keep it out)",
+                name
+            );
+        }
+        problems += report.missing.len() + report.unlisted.len();
+    }
+
+    if problems > 0 {
+        return Err(KanbanError::InvalidOperation(format!(
+            "{} problem(s) found",
+            problems
+        )));
+    }
+    say!("Checked {} project(s), no problems found", paths.len());
+    Ok(())
+}
+
+fn print_doctor_usage() {
+    println!(
+        "Kanban Doctor
+
+USAGE:
+    hxk doctor [project]      Check one project (or every project)
+
+Compares each project's .kanban.toml with the directories on disk without
+changing anything:
+  - statuses whose directory is missing (hxk recreates them on load)
+  - directories that are not statuses (hxk adds them as columns on load)
+
+Exits with status 1 when a problem is found."
+    );
+}
+
+// ============================================================================
+// Import Commands
+// ============================================================================
+
+fn handle_import_command(args: &[String]) -> error::Result<()> {
+    if args.len() < 3 || matches!(args[1].as_str(), "help" | "--help" | "-h") {
+        print_import_usage();
+        return Ok(());
+    }
+
+    let format = crate::import::ImportFormat::from_name(&args[1]).ok_or_else(|| {
+        usage(format!(
+            "Unknown import format: {}\nSupported formats: org, taskwarrior",
+            args[1]
+        ))
+    })?;
+    let project_name = parse_flag(&args[3..], "--project").ok_or_else(|| {
+        usage("Missing --project flag\nUsage: hxk import <org|taskwarrior> <file> --project <project>")
+    })?;
+    let project_path = find_project_path(&project_name)?;
+
+    let tasks = crate::import::parse_file(std::path::Path::new(&args[2]), format)?;
+    if tasks.is_empty() {
+        say!("No tasks found in {}", args[2]);
+        return Ok(());
+    }
+
+    let count = crate::import::import_into_project(&project_path, &tasks)?;
+    say!("Imported {} task(s) into '{}'", count, project_name);
+
+    Ok(())
+}
+
+fn print_import_usage() {
+    println!(
+        "Kanban Import
+
+USAGE:
+    hxk import <org|taskwarrior> <file> --project <project>
+
+FORMATS:
+    org          Emacs org headings with TODO/DONE keywords ([#A] priority, :tags:)
+    taskwarrior  JSON from `task export` (pending/started/completed)
+
+Missing statuses (e.g. WAITING) are created automatically.
+
+EXAMPLES:
+    hxk import org ~/notes/todo.org --project myproject
+    task export > tasks.json && hxk import taskwarrior tasks.json --project myproject"
+    );
+}
+
+// ============================================================================
+// Export Commands
+// ============================================================================
+
+fn handle_export_command(args: &[String]) -> error::Result<()> {
+    if args
+        .iter()
+        .any(|a| a == "help" || a == "--help" || a == "-h")
+    {
+        print_export_usage();
+        return Ok(());
+    }
+
+    let format = parse_flag(&args[1..], "--format").unwrap_or_else(|| "csv".to_string());
+    if format != "csv" {
+        return Err(usage(format!(
+            "Unsupported export format: {}\nSupported formats: csv",
+            format
+        )));
+    }
+
+    let mut projects = fs::load_all_projects().map_err(|e| KanbanError::io(e.to_string()))?;
+    if let Some(name) = parse_flag(&args[1..], "--project") {
+        projects.retain(|p| p.name == name);
+        if projects.is_empty() {
+            return Err(KanbanError::NotFound(format!(
+                "Project '{}' not found",
+                name
+            )));
+        }
+    }
+
+    let csv = crate::export::projects_to_csv(&projects);
+
+    match parse_flag(&args[1..], "--output") {
+        Some(path) => {
+            std::fs::write(&path, csv).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            let task_count: usize = projects.iter().map(|p| p.tasks.len()).sum();
+            say!("Exported {} task(s) to {}", task_count, path);
+        }
+        None => print!("{}", csv),
+    }
+
+    Ok(())
+}
+
+fn handle_metrics_command(args: &[String]) -> error::Result<()> {
+    if args
+        .iter()
+        .any(|a| a == "help" || a == "--help" || a == "-h")
+    {
+        print_metrics_usage();
+        return Ok(());
+    }
+
+    let format = parse_flag(&args[1..], "--format").unwrap_or_else(|| "prometheus".to_string());
+    if format != "prometheus" && format != "json" {
+        return Err(usage(format!(
+            "Unsupported metrics format: {}\nSupported formats: prometheus, json",
+            format
+        )));
+    }
+
+    let mut projects = fs::load_all_projects().map_err(|e| KanbanError::io(e.to_string()))?;
+    if let Some(name) = parse_flag(&args[1..], "--project") {
+        projects.retain(|p| p.name == name);
+        if projects.is_empty() {
+            return Err(KanbanError::NotFound(format!(
+                "Project '{}' not found",
+                name
+            )));
+        }
+    }
+
+    // Throughput comes from the journals of the selected projects
+    let mut journal_paths: Vec<PathBuf> = Vec::new();
+    for path in projects
+        .iter()
+        .filter_map(|p| fs::journal::journal_path(&p.path))
+    {
+        if !journal_paths.contains(&path) {
+            journal_paths.push(path);
+        }
+    }
+    let journal = fs::journal::read_recent(&journal_paths, None, usize::MAX);
+
+    let now = chrono::Local::now().fixed_offset();
+    let metrics: Vec<crate::metrics::ProjectMetrics> = projects
+        .iter()
+        .map(|project| crate::metrics::collect(project, &journal, now))
+        .collect();
+    let output = match format.as_str() {
+        "json" => format!("{}\n", crate::metrics::to_json(&metrics, now)),
+        _ => crate::metrics::to_prometheus(&metrics),
+    };
+
+    match parse_flag(&args[1..], "--output") {
+        Some(path) => {
+            std::fs::write(&path, output)
+                .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            say!("Wrote metrics for {} project(s) to {}", metrics.len(), path);
+        }
+        None => print!("{}", output),
+    }
+
+    Ok(())
+}
+
+fn print_metrics_usage() {
+    println!(
+        "Kanban Metrics
+
+USAGE:
+    hxk metrics [--format prometheus|json] [--project <project>] [--output <file>]
+
+OPTIONS:
+    --format <format>    Output format (default: prometheus)
+    --project <project>  Only a single project (default: all projects)
+    --output <file>      Write to a file instead of stdout
+
+METRICS (per project):
+    hxk_tasks{{status}}    Tasks per status
+    hxk_tasks_overdue      Tasks outside the done column whose `due` date has passed
+    hxk_throughput_7d      Distinct tasks moved into the done column in the last 7 days
+                           (read from .kanban/journal.ndjson)
+
+EXAMPLES:
+    hxk metrics --output /var/lib/node_exporter/textfile/hxk.prom
+    hxk metrics --format json --project web | jq '.projects[0].overdue'"
+    );
+}
+
+fn print_export_usage() {
+    println!(
+        "Kanban Export
+
+USAGE:
+    hxk export [--format csv] [--project <project>] [--output <file>]
+
+OPTIONS:
+    --format <format>    Output format (default: csv)
+    --project <project>  Export a single project (default: all projects)
+    --output <file>      Write to a file instead of stdout
+
+COLUMNS:
+    project, id, title, status, priority, tags, created, due
+
+EXAMPLES:
+    hxk export --format csv > tasks.csv
+    hxk export --format csv --project myproject --output myproject.csv"
+    );
+}
+
+// ============================================================================
+// Board Snapshot
+// ============================================================================
+
+fn handle_board_command(args: &[String]) -> error::Result<()> {
+    if args
+        .iter()
+        .any(|a| a == "help" || a == "--help" || a == "-h")
+    {
+        print_board_usage();
+        return Ok(());
+    }
+
+    // 取第一个不是选项（也不是选项值）的参数作为项目名
+    let mut project_name = None;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--width" | "--limit" => {
+                rest.next();
+            }
+            "--color" | "--no-color" => {}
+            _ if arg.starts_with("--") => return Err(usage(format!("Unknown option: {}", arg))),
+            _ if project_name.is_none() => project_name = Some(arg.clone()),
+            _ => return Err(usage(format!("Unexpected argument: {}", arg))),
+        }
+    }
+
+    let parse_number = |flag: &str| -> error::Result<Option<usize>> {
+        parse_flag(&args[1..], flag)
+            .map(|n| {
+                n.parse::<usize>()
+                    .map_err(|_| usage(format!("Invalid {} value: {}", flag, n)))
+            })
+            .transpose()
+    };
+    let width = match parse_number("--width")? {
+        Some(width) => width,
+        None => crossterm::terminal::size()
+            .map(|(columns, _)| columns as usize)
+            .unwrap_or(80),
+    };
+    let color = if args.iter().any(|a| a == "--no-color") {
+        false
+    } else {
+        args.iter().any(|a| a == "--color")
+            || (std::io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none())
+    };
+
+    let project_path = match project_name {
+        Some(name) => find_project_path(&name)?,
+        None => fs::list_local_project_dirs()
+            .map_err(|e| KanbanError::io(e.to_string()))?
+            .first()
+            .cloned()
+            .ok_or_else(|| {
+                usage("No local project in this directory\nUsage: hxk board <project>")
+            })?,
+    };
+    let project = fs::load_project(&project_path).map_err(KanbanError::io)?;
+
+    let options = crate::export::SnapshotOptions {
+        width,
+        color,
+        limit: parse_number("--limit")?,
+    };
+    print!("{}", crate::export::board_snapshot(&project, &options));
+
+    Ok(())
+}
+
+fn print_board_usage() {
+    println!(
+        "Kanban Board Snapshot
+
+USAGE:
+    hxk board [project] [--width <n>] [--limit <n>] [--color|--no-color]
+
+Prints the board with its columns side by side and exits, without starting
+the TUI. Without a project name the local project in the current directory
+is used.
+
+OPTIONS:
+    --width <n>      Total width in columns (default: terminal width, or 80)
+    --limit <n>      Show at most n tasks per column
+    --color          Always emit ANSI colors (e.g. for `watch --color`)
+    --no-color       Never emit colors (default: colors only on a terminal,
+                     unless NO_COLOR is set)
+
+EXAMPLES:
+    hxk board myproject
+    watch --color hxk board myproject --color
+    hxk board --width 60 --limit 3 --no-color"
+    );
+}
+
 // ============================================================================
 // Status Commands
 // ============================================================================
 
-fn handle_status_command(args: &[String]) -> Result<(), String> {
+fn handle_status_command(args: &[String]) -> error::Result<()> {
     if args.len() < 2 {
         print_status_usage();
         return Ok(());
@@ -606,29 +1585,34 @@ fn handle_status_command(args: &[String]) -> Result<(), String> {
     match args[1].as_str() {
         "list" => {
             if args.len() < 3 {
-                return Err("Missing project name\nUsage: hxk status list <project>".to_string());
+                return Err(usage(
+                    "Missing project name\nUsage: hxk status list <project>",
+                ));
             }
             status_list(&args[2])
         }
         "create" => {
             if args.len() < 4 {
-                return Err("Missing arguments\nUsage: hxk status create <project> <name> [--display <display-name>]".to_string());
+                return Err(usage(
+                    "Missing arguments\nUsage: hxk status create <project> <name> [--display <display-name>]",
+                ));
             }
             let display = parse_flag(&args[4..], "--display").unwrap_or_else(|| args[3].clone());
             status_create(&args[2], &args[3], &display)
         }
         "rename" => {
             if args.len() < 5 {
-                return Err(
-                    "Missing arguments\nUsage: hxk status rename <project> <old-name> <new-name>"
-                        .to_string(),
-                );
+                return Err(usage(
+                    "Missing arguments\nUsage: hxk status rename <project> <old-name> <new-name>",
+                ));
             }
             status_rename(&args[2], &args[3], &args[4])
         }
         "delete" => {
             if args.len() < 4 {
-                return Err("Missing arguments\nUsage: hxk status delete <project> <name> [--move-to <target>]".to_string());
+                return Err(usage(
+                    "Missing arguments\nUsage: hxk status delete <project> <name> [--move-to <target>]",
+                ));
             }
             let move_to = parse_flag(&args[4..], "--move-to");
             status_delete(&args[2], &args[3], move_to.as_deref())
@@ -637,10 +1621,10 @@ fn handle_status_command(args: &[String]) -> Result<(), String> {
             print_status_usage();
             Ok(())
         }
-        cmd => Err(format!(
+        cmd => Err(usage(format!(
             "Unknown status command: {}\nRun 'hxk status help' for usage",
             cmd
-        )),
+        ))),
     }
 }
 
@@ -672,17 +1656,17 @@ EXAMPLES:
     );
 }
 
-fn status_list(project_name: &str) -> Result<(), String> {
+fn status_list(project_name: &str) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
-    let project = fs::load_project(&project_path)?;
+    let project = fs::load_project(&project_path).map_err(KanbanError::io)?;
 
     if project.statuses.is_empty() {
-        println!("No statuses found.");
+        say!("No statuses found.");
         return Ok(());
     }
 
-    println!("NAME                DISPLAY              TASKS");
-    println!("------------------  -------------------  -----");
+    say!("NAME                DISPLAY              TASKS");
+    say!("------------------  -------------------  -----");
 
     for status in &project.statuses {
         let task_count = project
@@ -692,43 +1676,47 @@ fn status_list(project_name: &str) -> Result<(), String> {
             .count();
 
         println!(
-            "{:<18}  {:<19}  {}",
-            status.name, status.display, task_count
+            "{:<18}  {}  {}",
+            status.name,
+            pad_to_width(&status.display, 19),
+            task_count
         );
     }
 
     Ok(())
 }
 
-fn status_create(project_name: &str, name: &str, display: &str) -> Result<(), String> {
+fn status_create(project_name: &str, name: &str, display: &str) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
 
     fs::status::create_status(&project_path, name, display)?;
 
-    println!("Created status '{}' with display name '{}'", name, display);
+    say!("Created status '{}' with display name '{}'", name, display);
 
     Ok(())
 }
 
-fn status_rename(project_name: &str, old_name: &str, new_name: &str) -> Result<(), String> {
+fn status_rename(project_name: &str, old_name: &str, new_name: &str) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
 
+    fs::backup::before(&project_path, "rename-status")?;
     fs::status::rename_status(&project_path, old_name, new_name, new_name)?;
 
-    println!("Renamed status from '{}' to '{}'", old_name, new_name);
+    say!("Renamed status from '{}' to '{}'", old_name, new_name);
 
     Ok(())
 }
 
-fn status_delete(project_name: &str, name: &str, move_to: Option<&str>) -> Result<(), String> {
+fn status_delete(project_name: &str, name: &str, move_to: Option<&str>) -> error::Result<()> {
     let project_path = find_project_path(project_name)?;
 
+    fs::backup::before(&project_path, "delete-status")?;
     fs::status::delete_status(&project_path, name, move_to)?;
 
     if let Some(target) = move_to {
-        println!("Deleted status '{}' and moved tasks to '{}'", name, target);
+        say!("Deleted status '{}' and moved tasks to '{}'", name, target);
     } else {
-        println!("Deleted status '{}'", name);
+        say!("Deleted status '{}'", name);
     }
 
     Ok(())
@@ -739,9 +1727,9 @@ fn status_delete(project_name: &str, name: &str, move_to: Option<&str>) -> Resul
 // ============================================================================
 
 /// 创建本地项目
-fn cli_create(name: &str) -> Result<()> {
+fn cli_create(name: &str, workflow: fs::Workflow) -> Result<()> {
     // 直接创建 .kanban 目录
-    match fs::create_local_project(name) {
+    match fs::create_local_project(name, workflow) {
         Ok(path) => {
             println!("✓ 已创建本地看板");
             println!("  项目名: {}", name);
@@ -750,77 +1738,200 @@ fn cli_create(name: &str) -> Result<()> {
         }
         Err(e) => {
             eprintln!("错误: {}", e);
-            std::process::exit(1);
+            std::process::exit(error::EXIT_FAILURE);
         }
     }
 }
 
 /// 列出所有项目
-fn cli_list() -> Result<()> {
-    println!("全局项目 (~/.kanban/projects):");
-    println!("{}", "=".repeat(40));
+/// `hxk list --sort` 的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListSort {
+    /// 按名称（忽略大小写）
+    Name,
+    /// 任务多的在前
+    Tasks,
+    /// 最近修改的在前
+    Recent,
+}
 
-    let global_projects = fs::list_project_dirs()?;
-    if global_projects.is_empty() {
-        println!("  (无)");
-    } else {
-        for (i, path) in global_projects.iter().enumerate() {
-            if let Some(name) = path.file_name() {
-                println!("  {}. [G] {}", i + 1, name.to_string_lossy());
-            }
+impl ListSort {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "name" => Some(Self::Name),
+            "tasks" => Some(Self::Tasks),
+            "recent" => Some(Self::Recent),
+            _ => None,
+        }
+    }
+}
+
+/// `hxk list` 中的一行
+struct ProjectRow {
+    project_type: ProjectType,
+    name: String,
+    tasks: usize,
+    /// 完成列中的任务数
+    done: usize,
+    /// 任务文件最后修改的时间，没有任务时为 None
+    updated: Option<std::time::SystemTime>,
+    path: PathBuf,
+}
+
+impl ProjectRow {
+    fn new(project: &crate::models::Project) -> Self {
+        let done_status = project.done_status().map(|s| s.name.as_str());
+        Self {
+            project_type: project.project_type,
+            name: project.name.clone(),
+            tasks: project.tasks.len(),
+            done: project
+                .tasks
+                .iter()
+                .filter(|t| Some(t.status.as_str()) == done_status)
+                .count(),
+            updated: project
+                .tasks
+                .iter()
+                .filter_map(fs::task::task_modified_at)
+                .max(),
+            path: project.path.clone(),
         }
     }
+}
 
-    println!("\n本地项目 (当前目录 .kanban):");
-    println!("{}", "=".repeat(40));
+/// 排序项目列表；未指定排序时保持加载顺序（全局项目在前）
+fn sort_project_rows(rows: &mut [ProjectRow], sort: Option<ListSort>) {
+    match sort {
+        None => {}
+        Some(ListSort::Name) => rows.sort_by_key(|row| row.name.to_lowercase()),
+        Some(ListSort::Tasks) => rows.sort_by_key(|row| std::cmp::Reverse(row.tasks)),
+        Some(ListSort::Recent) => rows.sort_by_key(|row| std::cmp::Reverse(row.updated)),
+    }
+}
 
-    let local_projects = fs::list_local_project_dirs()?;
-    if local_projects.is_empty() {
-        println!("  (无)");
-        println!("\n提示: 使用 'kanban create <name>' 创建本地看板");
-    } else {
-        for path in local_projects.iter() {
-            // 读取项目名称
-            if let Ok(config) = fs::load_project_config(path) {
-                println!("  1. [L] {}", config.name);
+/// 列出项目：`hxk list [--sort name|tasks|recent] [--local|--global]`
+fn cli_list(args: &[String]) -> error::Result<()> {
+    let mut sort = None;
+    let mut only = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--sort" => {
+                let name = rest
+                    .next()
+                    .ok_or_else(|| usage("Missing value for --sort (name, tasks or recent)"))?;
+                sort = Some(ListSort::parse(name).ok_or_else(|| {
+                    usage(format!(
+                        "Unknown sort: {}\nAvailable sorts: name, tasks, recent",
+                        name
+                    ))
+                })?);
+            }
+            "--local" | "--global" => {
+                let project_type = if arg == "--local" {
+                    ProjectType::Local
+                } else {
+                    ProjectType::Global
+                };
+                if only.is_some_and(|only| only != project_type) {
+                    return Err(usage("--local and --global cannot be combined"));
+                }
+                only = Some(project_type);
             }
+            _ => return Err(usage(format!("Unknown option: {}", arg))),
         }
     }
 
+    let projects = fs::load_all_projects().map_err(|e| KanbanError::io(e.to_string()))?;
+    let mut rows: Vec<ProjectRow> = projects
+        .iter()
+        .filter(|p| only.is_none_or(|only| p.project_type == only))
+        .map(ProjectRow::new)
+        .collect();
+    if rows.is_empty() {
+        say!("(无)");
+        say!("\n提示: 使用 'hxk create <name>' 创建本地看板");
+        return Ok(());
+    }
+    sort_project_rows(&mut rows, sort);
+
+    let name_width = rows
+        .iter()
+        .map(|row| crate::ui::width::display_width(&row.name))
+        .max()
+        .unwrap_or(0)
+        .clamp(4, 30);
+    say!(
+        "类型  {}  任务  完成  最近修改          路径",
+        pad_to_width("名称", name_width)
+    );
+    for row in &rows {
+        let type_marker = match row.project_type {
+            ProjectType::Global => "[G]",
+            ProjectType::Local => "[L]",
+        };
+        let updated = row
+            .updated
+            .map(|t| {
+                chrono::DateTime::<chrono::Local>::from(t)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<4}  {}  {:>4}  {:>4}  {:<16}  {}",
+            type_marker,
+            pad_to_width(&truncate_to_width(&row.name, name_width), name_width),
+            row.tasks,
+            row.done,
+            updated,
+            row.path.display()
+        );
+    }
+
     Ok(())
 }
 
+/// 项目和全局配置中的新任务默认状态、优先级
+fn new_task_defaults(project_path: &Path) -> fs::project::NewTaskDefaults {
+    let config = crate::config::load_config().unwrap_or_default();
+    fs::project::NewTaskDefaults::load(project_path, &config)
+}
+
 /// 快速添加任务
-fn cli_add(args: &[String]) -> Result<()> {
+fn cli_add(args: &[String]) -> error::Result<()> {
     let title = args.join(" ");
 
     // 查找第一个本地项目
-    let local_projects = fs::list_local_project_dirs()?;
-
-    let project_path = if let Some(first) = local_projects.first() {
-        first.clone()
-    } else {
-        eprintln!("错误: 当前目录没有本地项目");
-        eprintln!("使用 'kanban create <name>' 创建项目");
-        std::process::exit(1);
-    };
+    let local_projects =
+        fs::list_local_project_dirs().map_err(|e| KanbanError::io(e.to_string()))?;
+    let project_path = local_projects.first().cloned().ok_or_else(|| {
+        KanbanError::NotFound("当前目录没有本地项目\n使用 'hxk create <name>' 创建项目".to_string())
+    })?;
 
     // 获取下一个任务 ID
-    let next_id = fs::get_next_task_id(&project_path).map_err(|e| anyhow::anyhow!(e))?;
+    let next_id = fs::get_next_task_id(&project_path).map_err(KanbanError::io)?;
+
+    // 配置的默认状态（没有时为 todo）和默认优先级
+    let defaults = new_task_defaults(&project_path);
+    let status = defaults.status.unwrap_or_else(|| "todo".to_string());
 
-    // 获取 todo 状态的最大 order
-    let max_order =
-        fs::get_max_order_in_status(&project_path, "todo").map_err(|e| anyhow::anyhow!(e))?;
+    // 获取该状态的最大 order
+    let max_order = fs::get_max_order_in_status(&project_path, &status).map_err(KanbanError::io)?;
     let new_order = max_order + 1000;
 
     // 创建任务
-    let mut task = Task::new(next_id, title.clone(), "todo".to_string());
+    let mut task = Task::new(next_id, title.clone(), status);
     task.order = new_order;
+    task.priority = defaults.priority;
 
     // 保存任务
-    fs::save_task(&project_path, &task).map_err(|e| anyhow::anyhow!(e))?;
+    fs::save_task(&project_path, &task).map_err(KanbanError::io)?;
 
-    if let Some(project_name) = project_path.file_name() {
+    if quiet() {
+        println!("{}", task.id);
+    } else if let Some(project_name) = project_path.file_name() {
         println!("✓ 已添加任务到项目 '{}':", project_name.to_string_lossy());
         println!("  {}", title);
     }
@@ -834,8 +1945,9 @@ fn print_help() {
     println!("用法:");
     println!("  hxk                     启动 TUI 界面");
     println!("  hxk <命令> [参数]         运行 CLI 命令");
+    println!("  hxk --safe-mode         安全模式启动（单个空面板，忽略保存的状态，记录调试日志）");
     println!("  hxk --help              显示此帮助信息");
-    println!("  hxk --version           显示版本信息\n");
+    println!("  hxk --version [--json]  显示版本、构建信息（提交、日期、功能）和配置/数据路径\n");
 
     println!("MCP Server:");
     println!("  hxk mcp                              启动 MCP server (用于 AI 集成)\n");
@@ -844,17 +1956,53 @@ fn print_help() {
     println!("  hxk project list                     列出项目");
     println!("  hxk task list <project>              列出任务");
     println!("  hxk task show <project> <id>        显示任务详情");
-    println!("  hxk task create <project> --status <status> --title <title>  创建任务");
+    println!("  hxk task create <project> [--status <status>] --title <title>  创建任务");
     println!("  hxk status list <project>            列出状态列");
-    println!("  hxk status create <project> <name> [--display <name>]  创建状态\n");
+    println!("  hxk status create <project> <name> [--display <name>]  创建状态");
+    println!("  hxk export --format csv [--project <project>]  导出任务为 CSV");
+    println!("  hxk metrics [--format prometheus|json]  输出各项目的任务数、逾期数和周吞吐量");
+    println!("  hxk import <org|taskwarrior> <file> --project <project>  导入任务");
+    println!("  hxk journal [--project <project>] [--limit <n>]  查看任务变更日志");
+    println!("  hxk board [project] [--width <n>] [--limit <n>]  打印只读看板快照");
+    println!("  hxk backup [project] [--list]        备份项目到 ~/.kanban/backups/");
+    println!("  hxk restore <archive>                从备份还原项目");
+    println!("  hxk doctor [project]                 检查状态目录与配置是否一致");
+    println!("  hxk link <global-project>            在当前目录置顶显示全局项目\n");
 
     println!("简单命令（功能有限）:");
-    println!("  hxk list                              列出项目");
-    println!("  hxk create <名称>                     创建项目");
+    println!("  hxk list [--sort name|tasks|recent] [--local|--global]  列出项目（任务数、路径）");
+    println!("  hxk create <名称> [--workflow dev]    创建项目（dev: 含 backlog/review/blocked）");
     println!("  hxk add <标题>                       添加任务");
     println!("  hxk config show                      显示配置");
     println!("  hxk config editor <命令>             设置编辑器");
-    println!("  hxk config viewer <命令>             设置预览器\n");
+    println!("  hxk config viewer <命令>             设置预览器");
+    println!("  hxk config detect                    重新检测编辑器和预览器");
+    println!("  hxk config status-counts <on|off>    面板标题显示各状态任务数");
+    println!("  hxk config reuse-pane <on|off>       打开已显示的项目时切换到该面板");
+    println!("  hxk config inline-images <on|off>    任务预览中显示图片（kitty/iTerm2）");
+    println!("  hxk config spell-check <on|off>      任务标题和预览中标出拼写错误");
+    println!("  hxk config palette <名称>            配色方案: default, color-blind, no-color");
+    println!("  hxk config progress <算法>           标题完成进度: count, estimate, priority");
+    println!("  hxk config terminal-title <on|off>   终端标题显示项目和任务数");
+    println!("  hxk config key-hints <on|off>        面板底边显示常用键位提示");
+    println!("  hxk config backup-retention <n>      每个项目保留的自动备份数（0 关闭）");
+    println!("  hxk config preview-size <40-100>     任务预览弹窗宽度百分比（100 为全屏）");
+    println!("  hxk config ime-auto-switch <on|off>  进出输入框时自动切换输入法");
+    println!("  hxk config default-status <状态|none>  新任务的默认状态（none 为当前列）");
+    println!("  hxk config default-priority <优先级|none>  新任务的默认优先级");
+    println!("  hxk config collapse-done <数量|off>  完成列超过该数量时自动折叠");
+    println!("  hxk config idle-lock <分钟|off>      无操作一段时间后隐藏看板\n");
+
+    println!("全局选项:");
+    println!("  -q, --quiet            只输出结果（如新建任务的 ID），不输出提示和表头");
+    println!("  --no-color             以无颜色方案启动界面（也可设置 NO_COLOR 环境变量）\n");
+
+    println!("退出码:");
+    println!("  0  成功");
+    println!("  1  一般错误");
+    println!("  2  用法错误（参数缺失、状态或优先级无效等）");
+    println!("  3  项目或任务不存在");
+    println!("  4  文件读写失败\n");
 
     println!("详细用法:");
     println!("  hxk project --help     查看项目管理命令");
@@ -900,9 +2048,94 @@ fn print_help() {
     println!("  CLI 命令提供简洁的文本输出，易于 AI 解析和操作");
 }
 
-/// 打印版本信息
-fn print_version() {
-    const VERSION: &str = env!("CARGO_PKG_VERSION");
-    const NAME: &str = env!("CARGO_PKG_NAME");
-    println!("{} {}", NAME, VERSION);
+/// 打印版本、构建信息和使用的路径，`--json` 输出 JSON
+fn print_version(json: bool) {
+    let info = crate::build_info::BuildInfo::current();
+    if json {
+        println!("{}", info.to_json());
+    } else {
+        println!("{}", info.to_text());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_flag_is_removed_from_anywhere_in_args() {
+        let mut args: Vec<String> = ["hxk", "task", "-q", "create", "demo", "--quiet"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(take_quiet_flag(&mut args));
+        assert_eq!(args, vec!["hxk", "task", "create", "demo"]);
+        assert!(!take_quiet_flag(&mut args));
+    }
+
+    #[test]
+    fn project_rows_sort_by_name_tasks_or_recent() {
+        let now = std::time::SystemTime::now();
+        let row = |name: &str, tasks: usize, age: Option<u64>| ProjectRow {
+            project_type: ProjectType::Global,
+            name: name.to_string(),
+            tasks,
+            done: 0,
+            updated: age.map(|secs| now - std::time::Duration::from_secs(secs)),
+            path: PathBuf::from(name),
+        };
+        let mut rows = vec![
+            row("web", 3, Some(600)),
+            row("Api", 12, None),
+            row("docs", 0, Some(60)),
+        ];
+        let names = |rows: &[ProjectRow]| -> Vec<String> {
+            rows.iter().map(|row| row.name.clone()).collect()
+        };
+
+        sort_project_rows(&mut rows, None);
+        assert_eq!(names(&rows), ["web", "Api", "docs"]);
+        sort_project_rows(&mut rows, ListSort::parse("name"));
+        assert_eq!(names(&rows), ["Api", "docs", "web"]);
+        sort_project_rows(&mut rows, ListSort::parse("tasks"));
+        assert_eq!(names(&rows), ["Api", "web", "docs"]);
+        sort_project_rows(&mut rows, ListSort::parse("recent"));
+        assert_eq!(names(&rows), ["docs", "web", "Api"]);
+        assert_eq!(ListSort::parse("size"), None);
+    }
+
+    #[test]
+    fn project_row_counts_done_status_not_trailing_column() {
+        let mut project = crate::models::Project::new(
+            "web".to_string(),
+            PathBuf::from("/tmp/web"),
+            ProjectType::Global,
+        );
+        project.statuses = ["todo", "done", "blocked"]
+            .iter()
+            .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+            .collect();
+        for (id, status) in [(1, "done"), (2, "done"), (3, "blocked")] {
+            project.tasks.push(crate::models::Task::new(
+                id,
+                format!("Task {}", id),
+                status.to_string(),
+            ));
+        }
+
+        let row = ProjectRow::new(&project);
+        assert_eq!((row.done, row.tasks), (2, 3));
+    }
+
+    #[test]
+    fn errors_map_to_exit_codes() {
+        assert_eq!(parse_task_id("abc").unwrap_err().exit_code(), EXIT_USAGE);
+        assert_eq!(usage("missing").exit_code(), EXIT_USAGE);
+        assert_eq!(
+            KanbanError::NotFound("demo".into()).exit_code(),
+            error::EXIT_NOT_FOUND
+        );
+        assert_eq!(KanbanError::io("disk full").exit_code(), error::EXIT_IO);
+        assert_eq!(KanbanError::io("disk full").message(), "disk full");
+    }
 }
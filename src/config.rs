@@ -1,8 +1,9 @@
 /// 应用配置管理
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -10,25 +11,123 @@ pub struct Config {
     pub editor: String,
     /// Markdown 预览器命令
     pub markdown_viewer: String,
-    /// 隐藏的全局项目列表（软删除）
+    /// 隐藏的项目列表（软删除），记录项目 ID；旧版本记录的是项目名，仍然兼容
     #[serde(default)]
     pub hidden_projects: Vec<String>,
-    /// 每个项目的列宽配置（百分比）
+    /// 每个项目的列宽配置（百分比），按项目 ID 记录；旧版本记录的是项目名，仍然兼容
     #[serde(default)]
     pub column_widths: HashMap<String, Vec<u16>>,
-    /// 记录哪个项目的哪一列被最大化
+    /// 记录哪个项目的哪一列被最大化（键同 `column_widths`）
     #[serde(default)]
     pub maximized_column: HashMap<String, Option<usize>>,
+    /// 面板标题显示每个状态的任务数（关闭时只显示 完成/总数）
+    #[serde(default = "default_status_counts")]
+    pub status_counts: bool,
+    /// 打开已在其他面板显示的项目时切换到该面板，而不是再打开一份
+    #[serde(default)]
+    pub reuse_open_pane: bool,
+    /// 终端支持图形协议时在任务预览中显示图片（需要编译时启用 `images` feature）
+    #[serde(default)]
+    pub inline_images: bool,
+    /// 在任务标题输入框和预览中给拼写错误的英文单词加下划线（需要编译时启用 `spellcheck` feature）
+    #[serde(default)]
+    pub spell_check: bool,
+    /// 拼写检查使用的词表文件（每行一个词），未设置时使用 /usr/share/dict/words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spell_wordlist: Option<String>,
+    /// 面板底边显示当前上下文最常用的几个键位（`:hints` 切换）
+    #[serde(default)]
+    pub key_hints: bool,
+    /// 在终端窗口/标签页标题中显示聚焦项目和各状态任务数，退出时恢复原标题
+    #[serde(default)]
+    pub terminal_title: bool,
+    /// 配色方案：default / color-blind / no-color（`NO_COLOR` 和 `hxk --no-color` 优先）
+    #[serde(default)]
+    pub palette: crate::ui::theme::Palette,
+    /// 面板标题的完成进度：count（卡片数）/ estimate（估算点数加权）/ priority（优先级加权）
+    #[serde(default)]
+    pub progress: crate::models::task::ProgressMetric,
+    /// 每个项目保留的自动备份数量（删除、重命名、批量操作前备份），0 表示关闭
+    #[serde(default = "default_backup_retention")]
+    pub backup_retention: usize,
+    /// 完成列超过此数量的卡片时折叠为只显示数量的窄列（选中该列时展开），0 表示关闭
+    #[serde(default)]
+    pub collapse_done_after: usize,
+    /// 无按键操作超过此分钟数后隐藏看板，只显示应用名，按任意键恢复；0 表示关闭
+    #[serde(default)]
+    pub idle_lock_minutes: u64,
+    /// 任务预览弹窗的宽度（占终端宽度的百分比，预览中按 +/- 调整），100 为全屏
+    #[serde(default = "default_preview_size")]
+    pub preview_size: u16,
+    /// 进出输入框时自动切换输入法（普通模式切到英文，输入时恢复），默认关闭
+    #[serde(default)]
+    pub ime_auto_switch: bool,
+    /// 输出当前输入法 ID 的命令，未设置时按平台选择（macOS: im-select，Linux: fcitx5-remote -n）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ime_query_command: Option<String>,
+    /// 切换输入法的命令，`{}` 替换为输入法 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ime_switch_command: Option<String>,
+    /// 普通模式使用的英文输入法 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ime_english: Option<String>,
+    /// 新任务的默认状态（项目中没有该状态时使用当前列），项目 `.kanban.toml` 中的设置优先
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_status: Option<String>,
+    /// 新任务的默认优先级（high / medium / low），项目 `.kanban.toml` 中的设置优先
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_priority: Option<String>,
+    /// 自定义命令别名（别名 -> 命令，多条命令用 `;` 分隔）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+}
+
+fn default_status_counts() -> bool {
+    true
+}
+
+fn default_backup_retention() -> usize {
+    crate::fs::backup::DEFAULT_RETENTION
+}
+
+/// 预览弹窗宽度的可调范围和步长（百分比）
+pub const PREVIEW_SIZE_MIN: u16 = 40;
+pub const PREVIEW_SIZE_MAX: u16 = 100;
+pub const PREVIEW_SIZE_STEP: u16 = 10;
+
+fn default_preview_size() -> u16 {
+    80
 }
 
 impl Default for Config {
     fn default() -> Self {
+        let (editor, markdown_viewer) = detected_tools();
         Self {
-            editor: detect_editor(),
-            markdown_viewer: detect_markdown_viewer(),
+            editor: editor.clone(),
+            markdown_viewer: markdown_viewer.clone(),
             hidden_projects: Vec::new(),
             column_widths: HashMap::new(),
             maximized_column: HashMap::new(),
+            status_counts: default_status_counts(),
+            reuse_open_pane: false,
+            inline_images: false,
+            spell_check: false,
+            spell_wordlist: None,
+            palette: Default::default(),
+            progress: Default::default(),
+            terminal_title: false,
+            key_hints: false,
+            backup_retention: default_backup_retention(),
+            collapse_done_after: 0,
+            idle_lock_minutes: 0,
+            preview_size: default_preview_size(),
+            ime_auto_switch: false,
+            ime_query_command: None,
+            ime_switch_command: None,
+            ime_english: None,
+            default_status: None,
+            default_priority: None,
+            aliases: BTreeMap::new(),
         }
     }
 }
@@ -66,16 +165,19 @@ pub fn save_config(config: &Config) -> Result<()> {
         std::fs::create_dir_all(parent)?;
     }
 
-    let content = toml::to_string_pretty(config)?;
-    std::fs::write(config_path, content)?;
+    // 保留用户手写的注释和本版本不认识的键
+    let existing = std::fs::read_to_string(&config_path).ok();
+    let content = crate::fs::toml_preserve::to_string_preserving(config, existing.as_deref())
+        .map_err(|e| anyhow::anyhow!("Failed to serialize config: {}", e))?;
+    crate::fs::atomic::write_atomic(&config_path, content)?;
 
     Ok(())
 }
 
-/// 隐藏项目（软删除 - 只对全局项目有效）
-pub fn hide_project(config: &mut Config, project_name: &str) -> Result<()> {
-    if !config.hidden_projects.contains(&project_name.to_string()) {
-        config.hidden_projects.push(project_name.to_string());
+/// 隐藏项目（软删除），按项目 ID 记录
+pub fn hide_project(config: &mut Config, project_id: &str) -> Result<()> {
+    if !config.hidden_projects.iter().any(|p| p == project_id) {
+        config.hidden_projects.push(project_id.to_string());
         save_config(config)?;
     }
     Ok(())
@@ -83,15 +185,86 @@ pub fn hide_project(config: &mut Config, project_name: &str) -> Result<()> {
 
 /// 显示项目（取消隐藏）
 #[allow(dead_code)]
-pub fn unhide_project(config: &mut Config, project_name: &str) -> Result<()> {
-    config.hidden_projects.retain(|p| p != project_name);
+pub fn unhide_project(config: &mut Config, project: &crate::models::Project) -> Result<()> {
+    config
+        .hidden_projects
+        .retain(|p| p != &project.id && p != &project.name);
     save_config(config)?;
     Ok(())
 }
 
-/// 检查项目是否被隐藏
-pub fn is_project_hidden(config: &Config, project_name: &str) -> bool {
-    config.hidden_projects.contains(&project_name.to_string())
+/// 检查项目是否被隐藏（同时匹配项目 ID 和旧版本记录的项目名）
+pub fn is_project_hidden(config: &Config, project: &crate::models::Project) -> bool {
+    config
+        .hidden_projects
+        .iter()
+        .any(|p| p == &project.id || p == &project.name)
+}
+
+/// 项目的列宽配置（先按项目 ID，再按旧版本记录的项目名查找）
+pub fn column_widths_for<'a>(
+    config: &'a Config,
+    project: &crate::models::Project,
+) -> Option<&'a Vec<u16>> {
+    config
+        .column_widths
+        .get(&project.id)
+        .or_else(|| config.column_widths.get(&project.name))
+}
+
+/// 项目最大化的列（查找规则同 [`column_widths_for`]）
+pub fn maximized_column_for(config: &Config, project: &crate::models::Project) -> Option<usize> {
+    config
+        .maximized_column
+        .get(&project.id)
+        .or_else(|| config.maximized_column.get(&project.name))
+        .copied()
+        .flatten()
+}
+
+/// 把旧版本按项目名记录的列宽和最大化列改为按项目 ID 记录（修改这些设置前调用）
+pub fn migrate_column_layout(config: &mut Config, project: &crate::models::Project) {
+    if project.id == project.name {
+        return;
+    }
+    if let Some(widths) = config.column_widths.remove(&project.name) {
+        config
+            .column_widths
+            .entry(project.id.clone())
+            .or_insert(widths);
+    }
+    if let Some(column) = config.maximized_column.remove(&project.name) {
+        config
+            .maximized_column
+            .entry(project.id.clone())
+            .or_insert(column);
+    }
+}
+
+/// 检测到的编辑器和预览器（每个进程只检测一次）
+fn detected_tools() -> &'static (String, String) {
+    static DETECTED: OnceLock<(String, String)> = OnceLock::new();
+    DETECTED.get_or_init(|| (detect_editor(), detect_markdown_viewer()))
+}
+
+/// 重新检测编辑器和预览器（忽略缓存），返回是否有变化
+pub fn redetect_tools(config: &mut Config) -> bool {
+    let editor = detect_editor();
+    let markdown_viewer = detect_markdown_viewer();
+    let changed = config.editor != editor || config.markdown_viewer != markdown_viewer;
+    config.editor = editor;
+    config.markdown_viewer = markdown_viewer;
+    changed
+}
+
+/// 编辑器命令是否为 Helix（`hx`，部分发行版为 `helix`）
+pub fn is_helix(editor_cmd: &str) -> bool {
+    editor_cmd
+        .split_whitespace()
+        .next()
+        .and_then(|program| Path::new(program).file_name())
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| matches!(name, "hx" | "hx.exe" | "helix"))
 }
 
 /// 检测系统编辑器
@@ -106,6 +279,7 @@ fn detect_editor() -> String {
 
     // 2. 检查常见编辑器（按优先级）
     let common_editors = vec![
+        "hx", "helix", // Helix（部分发行版的命令名为 helix）
         "nvim", "vim", "nano", "emacs", "code", // VS Code
         "subl", // Sublime Text
     ];
@@ -152,13 +326,12 @@ fn detect_markdown_viewer() -> String {
                 "xdg-open", // 默认应用
             ];
 
-            for viewer in viewers {
-                if which(viewer).is_ok() {
-                    return viewer.to_string();
-                }
-            }
-
-            "xdg-open".to_string()
+            // 都没有时留空，预览时使用 $PAGER
+            viewers
+                .into_iter()
+                .find(|viewer| which(viewer).is_ok())
+                .map(str::to_string)
+                .unwrap_or_default()
         }
         _ => {
             // Windows 或其他系统
@@ -167,20 +340,119 @@ fn detect_markdown_viewer() -> String {
     }
 }
 
-/// 检查命令是否存在
-fn which(cmd: &str) -> Result<PathBuf> {
-    use std::process::Command;
+/// 预览器不可用时使用的分页器：`$PAGER`，未设置时为 `less -R`（没有 less 时为 `more`）
+pub fn pager_command() -> String {
+    pager_from(std::env::var("PAGER").ok(), which("less").is_ok())
+}
+
+fn pager_from(pager_env: Option<String>, has_less: bool) -> String {
+    match pager_env {
+        Some(pager) if !pager.trim().is_empty() => pager,
+        _ if has_less => "less -R".to_string(),
+        _ => "more".to_string(),
+    }
+}
+
+/// 用系统默认程序打开文件或目录的命令
+pub fn file_opener() -> &'static str {
+    match std::env::consts::OS {
+        "macos" => "open",
+        "windows" => "explorer",
+        _ => "xdg-open",
+    }
+}
+
+/// `:shell` 启动的 shell：`$SHELL`，Windows 上为 `%COMSPEC%`，都未设置时为 `sh` / `cmd`
+pub fn shell_command() -> String {
+    let (var, fallback) = if cfg!(windows) {
+        ("COMSPEC", "cmd")
+    } else {
+        ("SHELL", "sh")
+    };
+    std::env::var(var)
+        .ok()
+        .filter(|shell| !shell.trim().is_empty())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// 命令（可带参数）的程序是否存在；macOS 的 `open` 总是可用
+pub fn command_available(cmd: &str) -> bool {
+    match cmd.split_whitespace().next() {
+        Some("open") if cfg!(target_os = "macos") => true,
+        Some(program) => Path::new(program).is_file() || which(program).is_ok(),
+        None => false,
+    }
+}
+
+/// 启动检查：配置的编辑器或预览器不在 PATH 中时返回提示
+pub fn tool_warnings(config: &Config) -> Vec<String> {
+    tool_warnings_with(config, command_available)
+}
 
-    let output = Command::new("which").arg(cmd).output()?;
+fn tool_warnings_with(config: &Config, available: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if !available(&config.editor) {
+        warnings.push(format!("编辑器 {} 未找到", config.editor));
+    }
+    if !config.markdown_viewer.trim().is_empty() && !available(&config.markdown_viewer) {
+        warnings.push(format!(
+            "预览器 {} 未找到（将使用 {}）",
+            config.markdown_viewer,
+            pager_command()
+        ));
+    }
+    warnings
+}
 
-    if output.status.success() {
-        let path = String::from_utf8(output.stdout)?.trim().to_string();
-        Ok(PathBuf::from(path))
+/// 预览器的显示文本，未设置时说明会使用分页器
+pub fn viewer_label(viewer: &str) -> String {
+    if viewer.trim().is_empty() {
+        format!("（未设置，使用 {}）", pager_command())
     } else {
-        Err(anyhow::anyhow!("Command not found: {}", cmd))
+        viewer.to_string()
     }
 }
 
+/// 检查命令是否存在（在 PATH 中查找，不启动子进程）
+fn which(cmd: &str) -> Result<PathBuf> {
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    find_in_path(cmd, &path_var).ok_or_else(|| anyhow::anyhow!("Command not found: {}", cmd))
+}
+
+/// 在给定的 PATH 中查找可执行文件
+fn find_in_path(cmd: &str, path_var: &std::ffi::OsStr) -> Option<PathBuf> {
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string())
+            .split(';')
+            .map(|ext| ext.to_string())
+            .chain(std::iter::once(String::new()))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    std::env::split_paths(path_var).find_map(|dir| {
+        extensions.iter().find_map(|ext| {
+            let candidate = dir.join(format!("{}{}", cmd, ext));
+            is_executable(&candidate).then_some(candidate)
+        })
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
 /// 检查配置是否完整
 #[allow(dead_code)]
 pub fn is_config_complete(config: &Config) -> bool {
@@ -211,6 +483,10 @@ fn print_welcome_message(config: &Config) {
     println!("已自动检测并配置以下工具：");
     println!("  编辑器:         {}", config.editor);
     println!("  Markdown 预览: {}", config.markdown_viewer);
+    println!(
+        "  状态计数:       {}",
+        if config.status_counts { "on" } else { "off" }
+    );
     println!();
     println!("配置文件位置: {}", get_config_path().display());
     println!();
@@ -239,13 +515,323 @@ pub fn set_viewer(viewer: String) -> Result<()> {
     Ok(())
 }
 
+/// 设置面板标题是否显示每个状态的任务数
+pub fn set_status_counts(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.status_counts = enabled;
+    save_config(&config)?;
+    println!(
+        "✓ 面板标题状态计数已{}",
+        if enabled { "开启" } else { "关闭" }
+    );
+    Ok(())
+}
+
+/// 设置打开项目时是否复用已显示该项目的面板
+pub fn set_reuse_open_pane(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.reuse_open_pane = enabled;
+    save_config(&config)?;
+    println!(
+        "✓ 复用已打开项目的面板已{}",
+        if enabled { "开启" } else { "关闭" }
+    );
+    Ok(())
+}
+
+/// 设置任务预览中是否显示内联图片
+pub fn set_inline_images(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.inline_images = enabled;
+    save_config(&config)?;
+    println!("✓ 预览内联图片已{}", if enabled { "开启" } else { "关闭" });
+    if enabled && !cfg!(feature = "images") {
+        println!("  当前版本编译时未启用 images feature，预览中只显示图片路径");
+    }
+    Ok(())
+}
+
+/// 设置是否检查任务标题和预览中的拼写
+pub fn set_spell_check(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.spell_check = enabled;
+    save_config(&config)?;
+    println!("✓ 拼写检查已{}", if enabled { "开启" } else { "关闭" });
+    if enabled && !cfg!(feature = "spellcheck") {
+        println!("  当前版本编译时未启用 spellcheck feature，不会检查拼写");
+    } else if enabled
+        && crate::ui::spell::find_wordlist(config.spell_wordlist.as_deref())
+            .is_none_or(|path| !path.is_file())
+    {
+        println!("  未找到词表，请安装系统词典（/usr/share/dict/words）或设置 spell_wordlist");
+    }
+    Ok(())
+}
+
+/// 设置是否在终端标题中显示项目和任务数
+pub fn set_terminal_title(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.terminal_title = enabled;
+    save_config(&config)?;
+    println!("✓ 终端标题显示已{}", if enabled { "开启" } else { "关闭" });
+    Ok(())
+}
+
+/// 设置面板底边是否显示键位提示
+pub fn set_key_hints(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.key_hints = enabled;
+    save_config(&config)?;
+    println!("✓ 面板键位提示已{}", if enabled { "开启" } else { "关闭" });
+    Ok(())
+}
+
+/// 设置配色方案
+pub fn set_palette(palette: crate::ui::theme::Palette) -> Result<()> {
+    let mut config = load_config()?;
+    config.palette = palette;
+    save_config(&config)?;
+    println!("✓ 配色方案已设置为: {}", palette.name());
+    Ok(())
+}
+
+/// 设置面板标题的完成进度算法
+pub fn set_progress(progress: crate::models::task::ProgressMetric) -> Result<()> {
+    let mut config = load_config()?;
+    config.progress = progress;
+    save_config(&config)?;
+    println!("✓ 完成进度算法已设置为: {}", progress.name());
+    Ok(())
+}
+
+/// 设置新任务的默认状态，`None` 表示使用当前列
+pub fn set_default_status(status: Option<String>) -> Result<()> {
+    let mut config = load_config()?;
+    config.default_status = status;
+    save_config(&config)?;
+    match &config.default_status {
+        Some(status) => println!(
+            "✓ 新任务默认状态已设置为: {}（项目中没有该状态时使用当前列）",
+            status
+        ),
+        None => println!("✓ 已清除新任务默认状态，新任务放在当前列"),
+    }
+    Ok(())
+}
+
+/// 设置新任务的默认优先级，`None` 表示不设置优先级
+pub fn set_default_priority(priority: Option<String>) -> Result<()> {
+    let mut config = load_config()?;
+    config.default_priority = priority;
+    save_config(&config)?;
+    match &config.default_priority {
+        Some(priority) => println!("✓ 新任务默认优先级已设置为: {}", priority),
+        None => println!("✓ 已清除新任务默认优先级"),
+    }
+    Ok(())
+}
+
+/// 设置无操作后隐藏看板的分钟数，0 表示关闭
+pub fn set_idle_lock_minutes(minutes: u64) -> Result<()> {
+    let mut config = load_config()?;
+    config.idle_lock_minutes = minutes;
+    save_config(&config)?;
+    if minutes == 0 {
+        println!("✓ 无操作隐藏看板已关闭");
+    } else {
+        println!("✓ {} 分钟无操作后隐藏看板（按任意键恢复）", minutes);
+    }
+    Ok(())
+}
+
+/// 设置完成列自动折叠的卡片数阈值，0 表示关闭
+pub fn set_collapse_done_after(threshold: usize) -> Result<()> {
+    let mut config = load_config()?;
+    config.collapse_done_after = threshold;
+    save_config(&config)?;
+    if threshold == 0 {
+        println!("✓ 完成列自动折叠已关闭");
+    } else {
+        println!(
+            "✓ 完成列超过 {} 张卡片时自动折叠（选中该列时展开）",
+            threshold
+        );
+    }
+    Ok(())
+}
+
+/// 设置每个项目保留的自动备份数量
+pub fn set_backup_retention(retention: usize) -> Result<()> {
+    let mut config = load_config()?;
+    config.backup_retention = retention;
+    save_config(&config)?;
+    if retention == 0 {
+        println!("✓ 自动备份已关闭");
+    } else {
+        println!("✓ 每个项目保留最近 {} 个自动备份", retention);
+    }
+    Ok(())
+}
+
+/// 设置任务预览弹窗的宽度百分比
+pub fn set_preview_size(size: u16) -> Result<()> {
+    let mut config = load_config()?;
+    config.preview_size = size.clamp(PREVIEW_SIZE_MIN, PREVIEW_SIZE_MAX);
+    save_config(&config)?;
+    if config.preview_size == PREVIEW_SIZE_MAX {
+        println!("✓ 任务预览改为全屏显示");
+    } else {
+        println!("✓ 任务预览宽度已设置为 {}%", config.preview_size);
+    }
+    Ok(())
+}
+
+/// 设置是否自动切换输入法
+pub fn set_ime_auto_switch(enabled: bool) -> Result<()> {
+    let mut config = load_config()?;
+    config.ime_auto_switch = enabled;
+    save_config(&config)?;
+    if !enabled {
+        println!("✓ 输入法自动切换已关闭");
+    } else if crate::input::ime::ImeCommands::from_config(&config).is_some() {
+        println!("✓ 输入法自动切换已开启");
+    } else {
+        println!("✓ 输入法自动切换已开启，但没有找到切换工具（需要 im-select 或 fcitx5-remote，");
+        println!("  或在配置中设置 ime_query_command / ime_switch_command / ime_english）");
+    }
+    Ok(())
+}
+
+/// 重新检测编辑器和预览器并保存
+pub fn detect_and_save_tools() -> Result<()> {
+    let mut config = load_config().unwrap_or_default();
+    redetect_tools(&mut config);
+    save_config(&config)?;
+    println!("✓ 已重新检测外部工具:");
+    println!("  编辑器:         {}", config.editor);
+    println!("  Markdown 预览: {}", viewer_label(&config.markdown_viewer));
+    Ok(())
+}
+
 /// 显示当前配置
 pub fn show_config() -> Result<()> {
     let config = load_config()?;
     println!("当前配置:");
     println!("  编辑器:         {}", config.editor);
-    println!("  Markdown 预览: {}", config.markdown_viewer);
+    println!("  Markdown 预览: {}", viewer_label(&config.markdown_viewer));
     println!();
     println!("配置文件: {}", get_config_path().display());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn hidden_projects_match_id_or_legacy_name() {
+        let project = crate::models::Project {
+            id: "0123456789abcdef".to_string(),
+            ..crate::models::Project::new(
+                "work".to_string(),
+                std::path::PathBuf::from("/tmp/work"),
+                crate::models::ProjectType::Global,
+            )
+        };
+        let mut config = Config::default();
+        assert!(!is_project_hidden(&config, &project));
+
+        config.hidden_projects = vec!["work".to_string()];
+        assert!(is_project_hidden(&config, &project));
+
+        config.hidden_projects = vec!["0123456789abcdef".to_string()];
+        assert!(is_project_hidden(&config, &project));
+    }
+
+    #[test]
+    fn column_layout_follows_project_id_across_renames() {
+        let mut project = crate::models::Project {
+            id: "0123456789abcdef".to_string(),
+            ..crate::models::Project::new(
+                "work".to_string(),
+                std::path::PathBuf::from("/tmp/work"),
+                crate::models::ProjectType::Global,
+            )
+        };
+        let mut config = Config::default();
+        config
+            .column_widths
+            .insert("work".to_string(), vec![60, 40]);
+        config.maximized_column.insert("work".to_string(), Some(1));
+        assert_eq!(column_widths_for(&config, &project), Some(&vec![60, 40]));
+        assert_eq!(maximized_column_for(&config, &project), Some(1));
+
+        migrate_column_layout(&mut config, &project);
+        assert!(!config.column_widths.contains_key("work"));
+        project.name = "renamed".to_string();
+        assert_eq!(column_widths_for(&config, &project), Some(&vec![60, 40]));
+        assert_eq!(maximized_column_for(&config, &project), Some(1));
+    }
+
+    #[test]
+    fn warns_about_missing_editor_and_viewer() {
+        let config = Config {
+            editor: "hx".to_string(),
+            markdown_viewer: "glow -p".to_string(),
+            ..Config::default()
+        };
+        assert!(tool_warnings_with(&config, |_| true).is_empty());
+
+        let warnings = tool_warnings_with(&config, |cmd| cmd.starts_with("glow"));
+        assert_eq!(warnings, vec!["编辑器 hx 未找到"]);
+
+        let no_viewer = Config {
+            markdown_viewer: String::new(),
+            ..config
+        };
+        assert_eq!(tool_warnings_with(&no_viewer, |_| false).len(), 1);
+    }
+
+    #[test]
+    fn preview_size_defaults_when_missing() {
+        let saved = toml::to_string(&Config::default()).unwrap();
+        assert!(saved.contains("preview_size = 80\n"));
+        let config: Config = toml::from_str(&saved.replace("preview_size = 80\n", "")).unwrap();
+        assert_eq!(config.preview_size, 80);
+    }
+
+    #[test]
+    fn pager_prefers_env_then_less() {
+        assert_eq!(pager_from(Some("most".to_string()), true), "most");
+        assert_eq!(pager_from(Some(" ".to_string()), true), "less -R");
+        assert_eq!(pager_from(None, false), "more");
+    }
+
+    #[test]
+    fn recognizes_helix_editor_commands() {
+        assert!(is_helix("hx"));
+        assert!(is_helix("/usr/bin/helix --vsplit"));
+        assert!(!is_helix("nvim"));
+        assert!(!is_helix("hxd"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_executable_in_path_without_spawning() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+
+        // 不可执行的同名文件应被跳过
+        std::fs::write(first.path().join("fake-editor"), "").unwrap();
+        let exe = second.path().join("fake-editor");
+        std::fs::write(&exe, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&exe, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let path_var = std::env::join_paths([first.path(), second.path()]).unwrap();
+        assert_eq!(find_in_path("fake-editor", &path_var), Some(exe));
+        assert_eq!(find_in_path("missing-editor", &path_var), None);
+    }
+}
@@ -46,6 +46,49 @@ impl fmt::Display for KanbanError {
 
 impl std::error::Error for KanbanError {}
 
+/// CLI exit code for errors that fit no other category
+pub const EXIT_FAILURE: i32 = 1;
+/// CLI exit code for usage and validation errors (bad arguments, unknown status, ...)
+pub const EXIT_USAGE: i32 = 2;
+/// CLI exit code when a project, task or status does not exist
+pub const EXIT_NOT_FOUND: i32 = 3;
+/// CLI exit code for filesystem errors while reading or writing the board
+pub const EXIT_IO: i32 = 4;
+
+impl KanbanError {
+    /// Wrap a storage error reported as a string by the `fs` module
+    pub fn io(msg: impl Into<String>) -> Self {
+        KanbanError::Io(io::Error::other(msg.into()))
+    }
+
+    /// Process exit code used by the CLI, so scripts can tell failures apart
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            KanbanError::Validation(_)
+            | KanbanError::AlreadyExists(_)
+            | KanbanError::ConfigParse(_)
+            | KanbanError::TaskParse(_) => EXIT_USAGE,
+            KanbanError::NotFound(_) => EXIT_NOT_FOUND,
+            KanbanError::Io(_) => EXIT_IO,
+            KanbanError::InvalidOperation(_) | KanbanError::Serialization(_) => EXIT_FAILURE,
+        }
+    }
+
+    /// The message without the category prefix added by `Display`
+    pub fn message(&self) -> String {
+        match self {
+            KanbanError::Io(err) => err.to_string(),
+            KanbanError::ConfigParse(msg)
+            | KanbanError::TaskParse(msg)
+            | KanbanError::Validation(msg)
+            | KanbanError::NotFound(msg)
+            | KanbanError::AlreadyExists(msg)
+            | KanbanError::InvalidOperation(msg)
+            | KanbanError::Serialization(msg) => msg.clone(),
+        }
+    }
+}
+
 impl From<io::Error> for KanbanError {
     fn from(err: io::Error) -> Self {
         KanbanError::Io(err)
@@ -90,5 +133,4 @@ impl From<KanbanError> for String {
     }
 }
 
-#[allow(dead_code)]
 pub type Result<T> = std::result::Result<T, KanbanError>;
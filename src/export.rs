@@ -0,0 +1,353 @@
+/// 任务导出（供电子表格等外部工具使用）
+use crate::models::{Project, Task};
+use crate::ui::width::{display_width, pad_to_width, truncate_to_width};
+use ratatui::crossterm::style::{Color, Stylize};
+
+/// CSV 表头
+const CSV_HEADER: [&str; 8] = [
+    "project", "id", "title", "status", "priority", "tags", "created", "due",
+];
+
+/// 将项目中的所有任务导出为 CSV（每个任务一行）
+///
+/// `due` 列取自任务的 `due` 字段（`YYYY-MM-DD`），没有或无法解析时留空。
+pub fn projects_to_csv(projects: &[Project]) -> String {
+    let mut out = String::new();
+    out.push_str(&CSV_HEADER.join(","));
+    out.push_str("\r\n");
+
+    for project in projects {
+        for task in &project.tasks {
+            let row = [
+                project.name.clone(),
+                task.id.to_string(),
+                task.title.clone(),
+                task.status.clone(),
+                task.priority.clone().unwrap_or_default(),
+                task.tags.join(", "),
+                format_created(&task.created),
+                crate::metrics::due_date(task)
+                    .map(|date| date.to_string())
+                    .unwrap_or_default(),
+            ];
+            let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            out.push_str(&fields.join(","));
+            out.push_str("\r\n");
+        }
+    }
+
+    out
+}
+
+/// 按 RFC 4180 转义 CSV 字段
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 将 Unix 时间戳格式化为本地时间，非时间戳原样保留
+fn format_created(created: &str) -> String {
+    created
+        .parse::<i64>()
+        .ok()
+        .filter(|secs| *secs > 0)
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|| created.to_string())
+}
+
+/// 看板快照（`hxk board`）的输出选项
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotOptions {
+    /// 总宽度（终端列数）
+    pub width: usize,
+    /// 是否输出 ANSI 颜色
+    pub color: bool,
+    /// 每列最多显示的任务数，超出部分汇总为一行
+    pub limit: Option<usize>,
+}
+
+/// 列之间的分隔符
+const COLUMN_SEPARATOR: &str = " │ ";
+/// 每列的最小宽度
+const MIN_COLUMN_WIDTH: usize = 8;
+
+/// 将项目渲染为只读的文本看板：各状态列并排，每行一个任务
+pub fn board_snapshot(project: &Project, options: &SnapshotOptions) -> String {
+    let columns = project.statuses.len().max(1);
+    let separators = display_width(COLUMN_SEPARATOR) * (columns - 1);
+    let column_width = (options.width.saturating_sub(separators) / columns).max(MIN_COLUMN_WIDTH);
+
+    let tasks: Vec<Vec<&Task>> = project
+        .statuses
+        .iter()
+        .map(|status| {
+            let mut tasks: Vec<&Task> = project
+                .tasks
+                .iter()
+                .filter(|t| t.status == status.name)
+                .collect();
+            tasks.sort_by_key(|t| t.order);
+            tasks
+        })
+        .collect();
+
+    let paint = |text: String, color: Option<Color>, bold: bool| -> String {
+        if !options.color {
+            return text;
+        }
+        let mut styled = text.stylize();
+        if let Some(color) = color {
+            styled = styled.with(color);
+        }
+        if bold {
+            styled = styled.bold();
+        }
+        styled.to_string()
+    };
+    let join = |cells: Vec<String>| cells.join(COLUMN_SEPARATOR).trim_end().to_string();
+
+    let mut out = String::new();
+    out.push_str(&paint(
+        format!("{} · {} 个任务", project.name, project.tasks.len()),
+        None,
+        true,
+    ));
+    out.push('\n');
+
+    let headers = project
+        .statuses
+        .iter()
+        .zip(&tasks)
+        .map(|(status, tasks)| {
+            let title = truncate_to_width(
+                &format!("{} ({})", status.display, tasks.len()),
+                column_width,
+            );
+            let color = status
+                .color
+                .as_deref()
+                .and_then(|c| c.parse::<ratatui::style::Color>().ok())
+                .map(Color::from);
+            paint(pad_to_width(&title, column_width), color, true)
+        })
+        .collect();
+    out.push_str(&join(headers));
+    out.push('\n');
+    out.push_str(&vec!["─".repeat(column_width); columns].join("─┼─"));
+    out.push('\n');
+
+    let shown = |count: usize| options.limit.map_or(count, |limit| count.min(limit));
+    let rows = tasks
+        .iter()
+        .map(|tasks| {
+            let hidden = tasks.len() - shown(tasks.len());
+            shown(tasks.len()) + usize::from(hidden > 0)
+        })
+        .max()
+        .unwrap_or(0);
+
+    for row in 0..rows {
+        let cells = tasks
+            .iter()
+            .map(|tasks| {
+                let visible = shown(tasks.len());
+                if let Some(task) = tasks.get(row).filter(|_| row < visible) {
+                    task_cell(task, column_width, &paint)
+                } else if row == visible && visible < tasks.len() {
+                    let more = format!("… +{}", tasks.len() - visible);
+                    paint(
+                        pad_to_width(&more, column_width),
+                        Some(Color::DarkGrey),
+                        false,
+                    )
+                } else {
+                    " ".repeat(column_width)
+                }
+            })
+            .collect();
+        out.push_str(&join(cells));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// 一个任务单元格：`#id 标题`，有颜色时 ID 按优先级着色
+fn task_cell(
+    task: &Task,
+    width: usize,
+    paint: &dyn Fn(String, Option<Color>, bool) -> String,
+) -> String {
+    let id = format!("#{}", task.id);
+    let title_width = width.saturating_sub(display_width(&id) + 1);
+    let title = pad_to_width(&truncate_to_width(&task.title, title_width), title_width);
+    let id_color = match task.priority.as_deref() {
+        Some("high") => Color::Red,
+        Some("medium") => Color::Yellow,
+        Some("low") => Color::Green,
+        _ => Color::DarkGrey,
+    };
+    format!("{} {}", paint(id, Some(id_color), false), title)
+}
+
+/// 将一列任务格式化为 Markdown 列表（`:yank-column`），可附带任务 ID 和复选框
+///
+/// 复选框在完成列中为 `[x]`，其他列为 `[ ]`。
+pub fn column_markdown(tasks: &[&Task], with_ids: bool, checkbox: Option<bool>) -> String {
+    tasks
+        .iter()
+        .map(|task| {
+            let mut line = String::from("- ");
+            if let Some(done) = checkbox {
+                line.push_str(if done { "[x] " } else { "[ ] " });
+            }
+            if with_ids {
+                line.push_str(&format!("#{} ", task.id));
+            }
+            line.push_str(&task.title);
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectType, Status, Task};
+    use std::path::PathBuf;
+
+    fn project_with_tasks(tasks: Vec<Task>) -> Project {
+        Project {
+            id: "demo".to_string(),
+            name: "demo".to_string(),
+            path: PathBuf::from("/tmp/demo"),
+            statuses: vec![Status {
+                name: "todo".to_string(),
+                display: "Todo".to_string(),
+                color: None,
+                wip_limit: None,
+            }],
+            tasks,
+            project_type: ProjectType::Global,
+            project_order: None,
+            tags: Vec::new(),
+            fields: Vec::new(),
+            pinned: false,
+            color: None,
+            group: None,
+            pane: None,
+        }
+    }
+
+    #[test]
+    fn exports_one_row_per_task_with_header() {
+        let mut task = Task::new(7, "Write docs".to_string(), "todo".to_string());
+        task.priority = Some("high".to_string());
+        task.tags = vec!["docs".to_string(), "v1".to_string()];
+        task.created = "not-a-timestamp".to_string();
+
+        let csv = projects_to_csv(&[project_with_tasks(vec![task])]);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(
+            lines[0],
+            "project,id,title,status,priority,tags,created,due"
+        );
+        assert_eq!(
+            lines[1],
+            "demo,7,Write docs,todo,high,\"docs, v1\",not-a-timestamp,"
+        );
+    }
+
+    #[test]
+    fn fills_due_column_from_due_field() {
+        let mut text = Task::new(1, "Ship".to_string(), "todo".to_string());
+        text.extra.insert(
+            "due".to_string(),
+            toml::Value::String("2026-03-01".to_string()),
+        );
+        let mut datetime = Task::new(2, "Review".to_string(), "todo".to_string());
+        datetime.extra.insert(
+            "due".to_string(),
+            toml::Value::Datetime("2026-04-15".parse().unwrap()),
+        );
+        let mut invalid = Task::new(3, "Later".to_string(), "todo".to_string());
+        invalid
+            .extra
+            .insert("due".to_string(), toml::Value::String("soon".to_string()));
+
+        let csv = projects_to_csv(&[project_with_tasks(vec![text, datetime, invalid])]);
+        let due: Vec<&str> = csv
+            .split("\r\n")
+            .skip(1)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.rsplit(',').next().unwrap())
+            .collect();
+
+        assert_eq!(due, ["2026-03-01", "2026-04-15", ""]);
+    }
+
+    #[test]
+    fn quotes_fields_with_commas_quotes_and_newlines() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn formats_a_column_as_a_markdown_list() {
+        let first = Task::new(3, "Fix login".to_string(), "done".to_string());
+        let second = Task::new(5, "Ship it".to_string(), "done".to_string());
+        let tasks = vec![&first, &second];
+
+        assert_eq!(
+            column_markdown(&tasks, false, None),
+            "- Fix login\n- Ship it"
+        );
+        assert_eq!(
+            column_markdown(&tasks, true, Some(true)),
+            "- [x] #3 Fix login\n- [x] #5 Ship it"
+        );
+        assert_eq!(
+            column_markdown(&tasks[..1], false, Some(false)),
+            "- [ ] Fix login"
+        );
+    }
+
+    #[test]
+    fn snapshot_lays_columns_side_by_side() {
+        let mut project = project_with_tasks(
+            (1..=3)
+                .map(|id| Task::new(id, format!("task {}", id), "todo".to_string()))
+                .collect(),
+        );
+        project
+            .statuses
+            .push(Status::new("done".to_string(), "Done".to_string()));
+        project.tasks[2].status = "done".to_string();
+
+        let options = SnapshotOptions {
+            width: 30,
+            color: false,
+            limit: Some(1),
+        };
+        let snapshot = board_snapshot(&project, &options);
+        let lines: Vec<&str> = snapshot.lines().collect();
+
+        assert_eq!(lines[0], "demo · 3 个任务");
+        assert_eq!(lines[1], "Todo (2)      │ Done (1)");
+        assert_eq!(lines[3], "#1 task 1     │ #3 task 3");
+        assert_eq!(lines[4], "… +1          │");
+        assert_eq!(lines.len(), 5);
+    }
+}
@@ -0,0 +1,488 @@
+/// 破坏性操作前的自动备份
+///
+/// 删除项目、删除状态、重命名和批量操作之前，把整个项目目录打包为
+/// `~/.kanban/backups/<项目名>@<时间戳>-<操作>.tar.gz`（调用系统的 `tar`）。
+/// 每个项目只保留最近 `backup_retention` 个备份，设为 0 关闭自动备份。
+/// 归档中保存的是去掉根目录后的完整路径，`hxk restore` 据此还原到原来的位置。
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 默认每个项目保留的备份数量
+pub const DEFAULT_RETENTION: usize = 20;
+
+const ARCHIVE_EXTENSION: &str = ".tar.gz";
+
+/// 备份目录 `~/.kanban/backups`
+pub fn backups_dir() -> PathBuf {
+    super::get_data_dir().join("backups")
+}
+
+/// 破坏性操作前自动备份，返回归档路径；配置关闭自动备份时返回 None
+///
+/// 备份失败时调用方应放弃本次操作。
+pub fn before(project_path: &Path, action: &str) -> Result<Option<PathBuf>, String> {
+    let retention = retention();
+    if retention == 0 {
+        return Ok(None);
+    }
+    create_backup_in(&backups_dir(), project_path, action, retention)
+        .map(Some)
+        .map_err(|e| format!("Automatic backup failed: {}", e))
+}
+
+/// 手动备份（`hxk backup`），不受自动备份开关影响
+pub fn create_backup(project_path: &Path) -> Result<PathBuf, String> {
+    create_backup_in(&backups_dir(), project_path, "manual", retention().max(1))
+}
+
+/// 把归档还原到原来的位置（`hxk restore`）
+pub fn restore_backup(archive: &Path) -> Result<PathBuf, String> {
+    restore_backup_in(&backups_dir(), archive)
+}
+
+fn retention() -> usize {
+    crate::config::load_config()
+        .map(|config| config.backup_retention)
+        .unwrap_or(DEFAULT_RETENTION)
+}
+
+/// 在 `dir` 中创建归档并清理同一项目超出 `retention` 的旧备份
+pub fn create_backup_in(
+    dir: &Path,
+    project_path: &Path,
+    action: &str,
+    retention: usize,
+) -> Result<PathBuf, String> {
+    let project_path = project_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", project_path.display(), e))?;
+    let name = super::load_project_config(&project_path)
+        .map(|config| config.name)
+        .unwrap_or_else(|_| {
+            project_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+    let prefix = format!("{}@", sanitize(&name));
+
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    // 同一秒内多次备份时追加序号，避免覆盖
+    let mut archive = dir.join(format!(
+        "{}{}-{}{}",
+        prefix, stamp, action, ARCHIVE_EXTENSION
+    ));
+    let mut suffix = 1;
+    while archive.exists() {
+        archive = dir.join(format!(
+            "{}{}-{}-{}{}",
+            prefix, stamp, suffix, action, ARCHIVE_EXTENSION
+        ));
+        suffix += 1;
+    }
+
+    let (root, relative) = split_root(&project_path)?;
+    run_tar(
+        Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(root)
+            .arg(relative),
+    )?;
+
+    prune(dir, &prefix, retention)?;
+    Ok(archive)
+}
+
+/// 列出备份，按文件名（即项目名、时间）排序
+pub fn list_backups(dir: &Path) -> Vec<PathBuf> {
+    let mut archives: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_archive(path))
+        .collect();
+    archives.sort();
+    archives
+}
+
+/// 解析 `hxk restore` 的参数：已存在的路径直接使用，否则在备份目录中查找同名文件
+pub fn resolve_archive(dir: &Path, arg: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(arg);
+    if path.is_file() {
+        return Some(path);
+    }
+    let in_dir = dir.join(arg);
+    in_dir.is_file().then_some(in_dir)
+}
+
+/// 把归档还原到原来的位置，返回项目目录
+///
+/// 只接受 hxk 创建的归档：文件名形如 `<项目名>@<时间戳>-<操作>.tar.gz`，所有条目位于同一个
+/// 项目目录下且包含 `.kanban.toml`。归档先解压到目标旁边的临时目录，成功后再替换目标；
+/// 目标目录已存在时必须是看板项目，替换前先为它创建一个备份。
+pub fn restore_backup_in(dir: &Path, archive: &Path) -> Result<PathBuf, String> {
+    let file_name = archive
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    if !is_backup_name(file_name) {
+        return Err(format!(
+            "{} is not an hxk backup (expected <project>@<timestamp>-<action>{})",
+            archive.display(),
+            ARCHIVE_EXTENSION
+        ));
+    }
+
+    let output = Command::new("tar")
+        .arg("-tzf")
+        .arg(archive)
+        .output()
+        .map_err(|e| format!("Failed to run tar: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to read {}: {}",
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let relative = archive_project_dir(&listing)
+        .ok_or_else(|| format!("{} does not contain a single project", archive.display()))?;
+
+    // 归档中的路径相对于根目录，Windows 上取备份目录所在的盘符
+    let dir_path = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let (root, _) = split_root(&dir_path)?;
+    let target = root.join(&relative);
+    let parent = target
+        .parent()
+        .ok_or_else(|| format!("Invalid restore target: {}", target.display()))?;
+    if target.exists() && !target.join(".kanban.toml").is_file() {
+        return Err(format!(
+            "{} exists and is not a kanban project, refusing to replace it",
+            target.display()
+        ));
+    }
+
+    // 解压到同一目录下的临时目录，保证最后一步是同一文件系统内的重命名
+    fs::create_dir_all(parent)
+        .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    let staging = parent.join(format!(".hxk-restore-{}", std::process::id()));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .map_err(|e| format!("Failed to remove {}: {}", staging.display(), e))?;
+    }
+    fs::create_dir(&staging)
+        .map_err(|e| format!("Failed to create {}: {}", staging.display(), e))?;
+    let result = swap_in(dir, archive, &staging, &relative, &target);
+    let _ = fs::remove_dir_all(&staging);
+    result.map(|_| target)
+}
+
+/// 解压到 `staging`，再用解压出的项目目录替换 `target`（旧目录先备份并移到一边）
+fn swap_in(
+    dir: &Path,
+    archive: &Path,
+    staging: &Path,
+    relative: &Path,
+    target: &Path,
+) -> Result<(), String> {
+    run_tar(
+        Command::new("tar")
+            .arg("-xzf")
+            .arg(archive)
+            .arg("-C")
+            .arg(staging),
+    )?;
+    let extracted = staging.join(relative);
+    if !extracted.join(".kanban.toml").is_file() {
+        return Err(format!("{} does not contain a project", archive.display()));
+    }
+
+    if !target.exists() {
+        return fs::rename(&extracted, target)
+            .map_err(|e| format!("Failed to move project into {}: {}", target.display(), e));
+    }
+
+    create_backup_in(dir, target, "restore", retention().max(1))?;
+    let replaced = staging.join(".replaced");
+    fs::rename(target, &replaced)
+        .map_err(|e| format!("Failed to move {} aside: {}", target.display(), e))?;
+    if let Err(e) = fs::rename(&extracted, target) {
+        // 放回原来的目录，保持还原前的状态
+        let _ = fs::rename(&replaced, target);
+        return Err(format!(
+            "Failed to move project into {}: {}",
+            target.display(),
+            e
+        ));
+    }
+    Ok(())
+}
+
+/// 文件名是否形如 hxk 创建的备份：`<项目名>@<YYYYMMDD-HHMMSS>[-序号]-<操作>.tar.gz`
+fn is_backup_name(file_name: &str) -> bool {
+    let Some((name, rest)) = file_name
+        .strip_suffix(ARCHIVE_EXTENSION)
+        .and_then(|stem| stem.split_once('@'))
+    else {
+        return false;
+    };
+    let mut parts = rest.splitn(3, '-');
+    let (Some(date), Some(time), Some(action)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    let digits = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+    !name.is_empty() && digits(date, 8) && digits(time, 6) && !action.is_empty()
+}
+
+/// 归档中的项目目录：所有条目都在同一个目录下，且该目录直接包含 `.kanban.toml`
+///
+/// 目录必须是不含 `.`、`..` 的相对路径，否则返回 None。
+fn archive_project_dir(listing: &str) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let entries: Vec<&str> = listing
+        .lines()
+        .map(|line| line.trim_end_matches('/'))
+        .filter(|line| !line.is_empty())
+        .collect();
+    let prefix = Path::new(*entries.first()?);
+    if !prefix
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    if !entries
+        .iter()
+        .all(|entry| Path::new(entry).starts_with(prefix))
+    {
+        return None;
+    }
+    let config = prefix.join(".kanban.toml");
+    entries
+        .iter()
+        .any(|entry| Path::new(entry) == config)
+        .then(|| prefix.to_path_buf())
+}
+
+/// 只保留同一项目最新的 `retention` 个备份（按修改时间，同一秒内的序号不参与排序）
+fn prune(dir: &Path, prefix: &str, retention: usize) -> Result<(), String> {
+    let mut archives: Vec<(std::time::SystemTime, PathBuf)> = list_backups(dir)
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(prefix))
+        })
+        .map(|path| {
+            let modified = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            (modified, path)
+        })
+        .collect();
+    archives.sort();
+    let excess = archives.len().saturating_sub(retention);
+    for (_, old) in &archives[..excess] {
+        fs::remove_file(old).map_err(|e| format!("Failed to remove {}: {}", old.display(), e))?;
+    }
+    Ok(())
+}
+
+fn is_archive(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.ends_with(ARCHIVE_EXTENSION))
+}
+
+/// 项目名中的路径分隔符和 `@` 会破坏文件名格式，替换为 `-`
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | '@' | ':' => '-',
+            c if c.is_whitespace() => '-',
+            c => c,
+        })
+        .collect()
+}
+
+/// 拆分为 (根目录, 相对路径)
+fn split_root(path: &Path) -> Result<(&Path, &Path), String> {
+    let root = path
+        .ancestors()
+        .last()
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+    let relative = path
+        .strip_prefix(root)
+        .map_err(|_| format!("Invalid path: {}", path.display()))?;
+    Ok((root, relative))
+}
+
+fn run_tar(command: &mut Command) -> Result<(), String> {
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run tar: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project_dir(root: &Path) -> PathBuf {
+        let project = root.join("web");
+        fs::create_dir_all(project.join("todo")).unwrap();
+        fs::write(
+            project.join(".kanban.toml"),
+            "name = \"web app\"\ncreated = \"0\"\n\n[statuses]\norder = [\"todo\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n",
+        )
+        .unwrap();
+        fs::write(project.join("todo/1.md"), "# Task\n").unwrap();
+        project
+    }
+
+    #[test]
+    fn backup_and_restore_round_trip() {
+        let workspace = TempDir::new().unwrap();
+        let backups = TempDir::new().unwrap();
+        let project = project_dir(workspace.path());
+
+        let archive = create_backup_in(backups.path(), &project, "delete-project", 5).unwrap();
+        let file_name = archive.file_name().unwrap().to_str().unwrap();
+        assert!(file_name.starts_with("web-app@"));
+        assert!(file_name.ends_with("-delete-project.tar.gz"));
+
+        fs::remove_dir_all(&project).unwrap();
+        let restored = restore_backup_in(backups.path(), &archive).unwrap();
+        assert_eq!(restored, project.canonicalize().unwrap());
+        assert_eq!(
+            fs::read_to_string(project.join("todo/1.md")).unwrap(),
+            "# Task\n"
+        );
+    }
+
+    #[test]
+    fn restore_replaces_existing_project_in_place() {
+        let workspace = TempDir::new().unwrap();
+        let backups = TempDir::new().unwrap();
+        let project = project_dir(workspace.path());
+        let archive = create_backup_in(backups.path(), &project, "manual", 5).unwrap();
+
+        fs::write(project.join("todo/1.md"), "# Changed\n").unwrap();
+        fs::write(project.join("todo/2.md"), "# New\n").unwrap();
+        restore_backup_in(backups.path(), &archive).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(project.join("todo/1.md")).unwrap(),
+            "# Task\n"
+        );
+        assert!(!project.join("todo/2.md").exists());
+        // 替换前的内容另外备份了一份，临时目录已清理
+        assert_eq!(list_backups(backups.path()).len(), 2);
+        let leftovers: Vec<_> = fs::read_dir(workspace.path())
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name())
+            .collect();
+        assert_eq!(leftovers, vec![std::ffi::OsString::from("web")]);
+    }
+
+    #[test]
+    fn restore_rejects_foreign_archives() {
+        let workspace = TempDir::new().unwrap();
+        let backups = TempDir::new().unwrap();
+        let project = project_dir(workspace.path());
+        let archive = create_backup_in(backups.path(), &project, "manual", 5).unwrap();
+
+        // 不是 hxk 命名的归档
+        let renamed = backups.path().join("web.tar.gz");
+        fs::copy(&archive, &renamed).unwrap();
+        assert!(restore_backup_in(backups.path(), &renamed).is_err());
+
+        // 归档中的目录不是项目（没有 .kanban.toml）：不能借此替换任意目录
+        let plain = workspace.path().join("notes");
+        fs::create_dir_all(&plain).unwrap();
+        fs::write(plain.join("keep.txt"), "keep").unwrap();
+        let (root, relative) = split_root(&plain.canonicalize().unwrap())
+            .map(|(root, relative)| (root.to_path_buf(), relative.to_path_buf()))
+            .unwrap();
+        let foreign = backups.path().join("notes@20250101-000000-manual.tar.gz");
+        run_tar(
+            Command::new("tar")
+                .arg("-czf")
+                .arg(&foreign)
+                .arg("-C")
+                .arg(&root)
+                .arg(&relative),
+        )
+        .unwrap();
+        assert!(restore_backup_in(backups.path(), &foreign).is_err());
+        assert_eq!(fs::read_to_string(plain.join("keep.txt")).unwrap(), "keep");
+    }
+
+    #[test]
+    fn archive_must_hold_one_project_directory() {
+        assert_eq!(
+            archive_project_dir("home/me/web/\nhome/me/web/.kanban.toml\nhome/me/web/todo/1.md\n"),
+            Some(PathBuf::from("home/me/web"))
+        );
+        assert_eq!(
+            archive_project_dir("home/me/\nhome/me/web/.kanban.toml\n"),
+            None
+        );
+        assert_eq!(
+            archive_project_dir("home/me/web/\nhome/me/web/.kanban.toml\netc/passwd\n"),
+            None
+        );
+        assert_eq!(archive_project_dir("./\n./.kanban.toml\n"), None);
+        assert_eq!(archive_project_dir(""), None);
+        assert!(is_backup_name(
+            "web-app@20250101-120000-2-delete-status.tar.gz"
+        ));
+        assert!(!is_backup_name("web-app.tar.gz"));
+        assert!(!is_backup_name("web@2025-manual.tar.gz"));
+    }
+
+    #[test]
+    fn keeps_only_the_newest_backups_per_project() {
+        let workspace = TempDir::new().unwrap();
+        let backups = TempDir::new().unwrap();
+        let project = project_dir(workspace.path());
+        fs::write(
+            backups.path().join("other@20000101-000000-manual.tar.gz"),
+            "",
+        )
+        .unwrap();
+
+        for _ in 0..3 {
+            create_backup_in(backups.path(), &project, "rename", 2).unwrap();
+        }
+
+        let names: Vec<String> = list_backups(backups.path())
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names.len(), 3);
+        assert!(names[0].starts_with("other@"));
+        assert_eq!(
+            resolve_archive(backups.path(), &names[0]),
+            Some(backups.path().join(&names[0]))
+        );
+    }
+}
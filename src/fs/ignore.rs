@@ -0,0 +1,115 @@
+/// 状态目录中不当作任务加载的文件
+///
+/// 只有 `.md` 文件才会被当作任务；`.kanban.toml` 的 `ignore` 可以再排除一部分，
+/// 例如草稿或笔记。模式支持 `*` 和 `?`，不含 `/` 时匹配文件名，含 `/` 时匹配
+/// `状态目录/文件名`。编辑器交换文件等常见临时文件始终忽略。
+use std::path::Path;
+
+/// 始终忽略的文件（系统和编辑器生成的临时文件）
+const DEFAULT_IGNORES: [&str; 6] = [".DS_Store", ".*.swp", ".*.swo", "*~", ".#*", "#*#"];
+
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    patterns: Vec<String>,
+}
+
+impl IgnoreRules {
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            patterns: patterns.to_vec(),
+        }
+    }
+
+    /// 读取项目 `.kanban.toml` 中的规则，配置缺失或无法解析时只使用默认规则
+    pub fn for_project(project_path: &Path) -> Self {
+        super::project::load_project_config(project_path)
+            .map(|config| Self::new(&config.ignore))
+            .unwrap_or_default()
+    }
+
+    /// 文件是否应当作任务加载：`.md` 文件且未被忽略
+    pub fn is_task_file(&self, path: &Path) -> bool {
+        path.extension().and_then(|s| s.to_str()) == Some("md") && !self.is_ignored(path)
+    }
+
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        let status = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .unwrap_or("");
+        let relative = format!("{}/{}", status, file_name);
+
+        DEFAULT_IGNORES
+            .iter()
+            .copied()
+            .chain(self.patterns.iter().map(String::as_str))
+            .any(|pattern| {
+                if pattern.contains('/') {
+                    glob_match(pattern.trim_start_matches('/'), &relative)
+                } else {
+                    glob_match(pattern, file_name)
+                }
+            })
+    }
+}
+
+/// 简单的通配符匹配：`*` 匹配任意个字符（不跨越 `/`），`?` 匹配单个字符
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // 最近一个 `*` 的位置，以及它当前匹配到的文本位置
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == text[t] || (c == '?' && text[t] != '/') => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) if text[star_t] != '/' => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                _ => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_supports_star_and_question_mark() {
+        assert!(glob_match("*.draft.md", "12.draft.md"));
+        assert!(glob_match("note?.md", "note1.md"));
+        assert!(!glob_match("note?.md", "note12.md"));
+        assert!(glob_match("todo/*.md", "todo/3.md"));
+        assert!(!glob_match("*.md", "todo/3.md"));
+    }
+
+    #[test]
+    fn ignores_configured_patterns_and_editor_files() {
+        let rules = IgnoreRules::new(&["*.draft.md".to_string(), "doing/notes*".to_string()]);
+        assert!(rules.is_task_file(Path::new("/p/todo/3.md")));
+        assert!(!rules.is_task_file(Path::new("/p/todo/3.draft.md")));
+        assert!(!rules.is_task_file(Path::new("/p/doing/notes.md")));
+        assert!(rules.is_task_file(Path::new("/p/todo/notes.md")));
+        assert!(!rules.is_task_file(Path::new("/p/todo/.3.md.swp")));
+        assert!(!rules.is_task_file(Path::new("/p/todo/.#3.md")));
+        assert!(!rules.is_task_file(Path::new("/p/todo/cover.png")));
+    }
+}
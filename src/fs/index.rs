@@ -0,0 +1,476 @@
+/// 任务索引（可选，面向任务数量很大的项目）
+///
+/// 索引以 JSON 保存在项目目录下的 `.kanban-index.json`，只记录任务元数据（ID、排序、标题、
+/// 状态、优先级、标签、创建时间），不保存正文。每个条目同时记录文件的修改时间和大小：
+/// 使用索引前逐个读取文件信息（不读取内容），只重新解析修改时间或大小变化的文件，
+/// 因此外部编辑器原地改写任务文件也能被发现。CLI 和 MCP 的任务列出、搜索、筛选和排序
+/// 都经由 `hxk task list` / MCP `list_tasks` 调用 [`query`] 在索引上完成，
+/// 未建立索引的项目回退到目录扫描。
+use crate::models::Task;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 索引文件名
+pub const INDEX_FILE: &str = ".kanban-index.json";
+
+/// 索引格式版本，结构变化时递增以丢弃旧索引
+const INDEX_VERSION: u32 = 3;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TaskIndex {
+    version: u32,
+    /// 键为 `状态/文件名`（如 `todo/1.md`）
+    entries: HashMap<String, IndexEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    mtime_nanos: u128,
+    size: u64,
+    task: IndexedTask,
+}
+
+/// 索引中的任务元数据（不含正文）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedTask {
+    pub id: u32,
+    pub order: i32,
+    pub title: String,
+    pub status: String,
+    pub created: String,
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl From<&Task> for IndexedTask {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id,
+            order: task.order,
+            title: task.title.clone(),
+            status: task.status.clone(),
+            created: task.created.clone(),
+            priority: task.priority.clone(),
+            tags: task.tags.clone(),
+        }
+    }
+}
+
+/// 索引统计信息（用于 CLI 输出）
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct IndexStats {
+    /// 直接复用索引的任务数
+    pub cached: usize,
+    /// 重新解析的任务数
+    pub parsed: usize,
+    /// 已删除文件对应的过期条目数
+    pub removed: usize,
+}
+
+/// 列出任务时的排序方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSort {
+    /// 按状态列顺序，列内按 order（与看板一致）
+    #[default]
+    Board,
+    Id,
+    /// 高 → 中 → 低 → 未设置
+    Priority,
+    /// 最新创建的在前
+    Created,
+    Title,
+}
+
+impl TaskSort {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "board" | "order" => Some(Self::Board),
+            "id" => Some(Self::Id),
+            "priority" => Some(Self::Priority),
+            "created" => Some(Self::Created),
+            "title" => Some(Self::Title),
+            _ => None,
+        }
+    }
+}
+
+/// 列出任务的条件（`hxk task list` / MCP `list_tasks`）
+#[derive(Debug, Default, Clone)]
+pub struct TaskQuery {
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub tag: Option<String>,
+    /// 标题或标签中包含的文字（不区分大小写）
+    pub text: Option<String>,
+    pub sort: TaskSort,
+}
+
+impl TaskQuery {
+    pub fn matches(&self, task: &IndexedTask) -> bool {
+        let text = self.text.as_deref().map(str::to_lowercase);
+        self.status.as_ref().is_none_or(|s| &task.status == s)
+            && self
+                .priority
+                .as_ref()
+                .is_none_or(|p| task.priority.as_ref() == Some(p))
+            && self.tag.as_ref().is_none_or(|t| task.tags.contains(t))
+            && text.is_none_or(|text| {
+                task.title.to_lowercase().contains(&text)
+                    || task.tags.iter().any(|t| t.to_lowercase().contains(&text))
+            })
+    }
+}
+
+fn index_path(project_path: &Path) -> PathBuf {
+    project_path.join(INDEX_FILE)
+}
+
+/// 项目是否启用了索引
+pub fn is_enabled(project_path: &Path) -> bool {
+    index_path(project_path).exists()
+}
+
+fn empty_index() -> TaskIndex {
+    TaskIndex {
+        version: INDEX_VERSION,
+        ..TaskIndex::default()
+    }
+}
+
+fn load_index(project_path: &Path) -> TaskIndex {
+    fs::read_to_string(index_path(project_path))
+        .ok()
+        .and_then(|content| serde_json::from_str::<TaskIndex>(&content).ok())
+        .filter(|index| index.version == INDEX_VERSION)
+        .unwrap_or_else(empty_index)
+}
+
+fn save_index(project_path: &Path, index: &TaskIndex) -> Result<(), String> {
+    let content = serde_json::to_string(index).map_err(|e| e.to_string())?;
+    super::atomic::write_atomic(&index_path(project_path), content)
+        .map_err(|e| format!("Failed to write index: {}", e))
+}
+
+fn mtime_nanos(metadata: &fs::Metadata) -> Option<u128> {
+    Some(
+        metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_nanos(),
+    )
+}
+
+fn file_signature(path: &Path) -> Option<(u128, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((mtime_nanos(&metadata)?, metadata.len()))
+}
+
+/// 让索引与状态目录一致，返回统计信息和索引是否有变化
+///
+/// 逐个比较文件的修改时间和大小，只解析变化的文件；不再存在的文件和已删除状态的条目会被移除。
+fn refresh(
+    project_path: &Path,
+    statuses: &[(String, PathBuf)],
+    index: &mut TaskIndex,
+) -> (IndexStats, bool) {
+    let rules = super::ignore::IgnoreRules::for_project(project_path);
+    let mut stats = IndexStats::default();
+    let mut seen = HashSet::new();
+
+    for (status, status_dir) in statuses {
+        for entry in fs::read_dir(status_dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if !rules.is_task_file(&path) {
+                continue;
+            }
+            let Some((mtime_nanos, size)) = file_signature(&path) else {
+                continue;
+            };
+            let key = format!("{}/{}", status, entry.file_name().to_string_lossy());
+
+            let fresh = index
+                .entries
+                .get(&key)
+                .is_some_and(|e| e.mtime_nanos == mtime_nanos && e.size == size);
+            if fresh {
+                stats.cached += 1;
+            } else {
+                let Ok(task) = super::task::load_task_file(&path, status) else {
+                    continue;
+                };
+                stats.parsed += 1;
+                index.entries.insert(
+                    key.clone(),
+                    IndexEntry {
+                        mtime_nanos,
+                        size,
+                        task: IndexedTask::from(&task),
+                    },
+                );
+            }
+            seen.insert(key);
+        }
+    }
+
+    // 已删除的文件，以及已从配置中删除的状态
+    let before = index.entries.len();
+    index.entries.retain(|key, _| seen.contains(key));
+    stats.removed = before - index.entries.len();
+
+    let changed = stats.parsed > 0 || stats.removed > 0;
+    (stats, changed)
+}
+
+/// 按条件列出任务：启用索引时只读取索引（必要时增量更新），否则扫描整个项目
+pub fn query(project_path: &Path, query: &TaskQuery) -> Result<Vec<IndexedTask>, String> {
+    let config = super::load_project_config(project_path)?;
+    let statuses = super::project::resolve_status_dirs(project_path, &config);
+
+    let mut tasks: Vec<IndexedTask> = if is_enabled(project_path) {
+        let mut index = load_index(project_path);
+        let (_, changed) = refresh(project_path, &statuses, &mut index);
+        if changed {
+            save_index(project_path, &index)?;
+        }
+        index
+            .entries
+            .into_values()
+            .map(|entry| entry.task)
+            .collect()
+    } else {
+        super::load_project(project_path)?
+            .tasks
+            .iter()
+            .map(IndexedTask::from)
+            .collect()
+    };
+
+    tasks.retain(|task| query.matches(task));
+    sort_tasks(&mut tasks, query.sort, &statuses);
+    Ok(tasks)
+}
+
+fn sort_tasks(tasks: &mut [IndexedTask], sort: TaskSort, statuses: &[(String, PathBuf)]) {
+    let column = |task: &IndexedTask| {
+        statuses
+            .iter()
+            .position(|(status, _)| *status == task.status)
+            .unwrap_or(usize::MAX)
+    };
+    let priority = |task: &IndexedTask| match task.priority.as_deref() {
+        Some("high") => 0,
+        Some("medium") => 1,
+        Some("low") => 2,
+        _ => 3,
+    };
+    match sort {
+        TaskSort::Board => tasks.sort_by_key(|t| (column(t), t.order, t.id)),
+        TaskSort::Id => tasks.sort_by_key(|t| t.id),
+        TaskSort::Priority => tasks.sort_by_key(|t| (priority(t), column(t), t.order)),
+        TaskSort::Created => tasks.sort_by(|a, b| {
+            let created = |t: &IndexedTask| t.created.parse::<i64>().unwrap_or(0);
+            created(b).cmp(&created(a)).then(a.id.cmp(&b.id))
+        }),
+        TaskSort::Title => {
+            tasks.sort_by_key(|t| (t.title.to_lowercase(), t.id));
+        }
+    }
+}
+
+/// 为项目建立（或重建）索引，之后 `hxk task list` / MCP 列出该项目时会使用索引
+pub fn build_index(project_path: &Path) -> Result<IndexStats, String> {
+    let config = super::load_project_config(project_path)?;
+    // 丢弃旧条目，确保全部重新解析
+    let mut index = empty_index();
+    let (stats, _) = refresh(
+        project_path,
+        &super::project::resolve_status_dirs(project_path, &config),
+        &mut index,
+    );
+    save_index(project_path, &index)?;
+    Ok(stats)
+}
+
+/// 删除项目索引，恢复为目录扫描
+pub fn drop_index(project_path: &Path) -> Result<bool, String> {
+    let path = index_path(project_path);
+    if !path.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(&path).map_err(|e| format!("Failed to remove index: {}", e))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_project() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(".kanban.toml"),
+            "name = \"big\"\ncreated = \"0\"\n\n[statuses]\norder = [\"todo\", \"done\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n\n[statuses.done]\ndisplay = \"Done\"\n",
+        )
+        .unwrap();
+        for (id, status, priority, tag) in [
+            (1, "todo", None, "api"),
+            (2, "todo", Some("high"), "ui"),
+            (3, "done", Some("low"), "api"),
+        ] {
+            let mut task = Task::new(id, format!("Task {}", id), status.to_string());
+            task.order = (4 - id as i32) * 1000;
+            task.priority = priority.map(str::to_string);
+            task.tags = vec![tag.to_string()];
+            task.set_content("body text that is not indexed".to_string());
+            crate::fs::save_task(temp_dir.path(), &task).unwrap();
+        }
+        temp_dir
+    }
+
+    fn statuses(project_path: &Path) -> Vec<(String, PathBuf)> {
+        ["todo", "done"]
+            .iter()
+            .map(|status| (status.to_string(), project_path.join(status)))
+            .collect()
+    }
+
+    #[test]
+    fn only_changed_files_are_parsed_again() {
+        let temp_dir = setup_project();
+        let project_path = temp_dir.path();
+
+        let stats = build_index(project_path).unwrap();
+        assert_eq!(stats.parsed, 3);
+        assert!(is_enabled(project_path));
+        let saved = fs::read_to_string(index_path(project_path)).unwrap();
+        assert!(!saved.contains("body text"), "索引不应保存正文");
+
+        let mut index = load_index(project_path);
+        let (stats, changed) = refresh(project_path, &statuses(project_path), &mut index);
+        assert_eq!(
+            stats,
+            IndexStats {
+                cached: 3,
+                ..IndexStats::default()
+            }
+        );
+        assert!(!changed);
+
+        // 修改一个任务、删除另一个：只解析变化的文件
+        let project = crate::fs::load_project(project_path).unwrap();
+        let mut task = project.tasks.iter().find(|t| t.id == 1).unwrap().clone();
+        task.title = "Renamed with a much longer title".to_string();
+        crate::fs::save_task(project_path, &task).unwrap();
+        crate::fs::delete_task(
+            project_path,
+            project.tasks.iter().find(|t| t.id == 2).unwrap(),
+        )
+        .unwrap();
+
+        let (stats, changed) = refresh(project_path, &statuses(project_path), &mut index);
+        assert!(changed);
+        assert_eq!(stats.parsed, 1);
+        assert_eq!(stats.removed, 1);
+        assert_eq!(stats.cached, 1);
+        assert!(
+            index
+                .entries
+                .values()
+                .any(|e| e.task.title == "Renamed with a much longer title")
+        );
+    }
+
+    #[test]
+    fn in_place_edits_are_picked_up() {
+        let temp_dir = setup_project();
+        let project_path = temp_dir.path();
+        build_index(project_path).unwrap();
+
+        // 模拟外部编辑器直接改写文件内容（不经过临时文件重命名，目录修改时间不变）
+        let path = project_path.join("done/3.md");
+        let content = fs::read_to_string(&path)
+            .unwrap()
+            .replace("Task 3", "Edited in place");
+        let dir_mtime = fs::metadata(project_path.join("done"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        fs::OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, content.as_bytes()))
+            .unwrap();
+        assert_eq!(
+            fs::metadata(project_path.join("done"))
+                .unwrap()
+                .modified()
+                .unwrap(),
+            dir_mtime
+        );
+
+        let search = TaskQuery {
+            text: Some("edited in place".to_string()),
+            ..TaskQuery::default()
+        };
+        let tasks = super::query(project_path, &search).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, 3);
+    }
+
+    #[test]
+    fn query_filters_searches_and_sorts_with_or_without_index() {
+        let temp_dir = setup_project();
+        let project_path = temp_dir.path();
+        let ids = |query: &TaskQuery| -> Vec<u32> {
+            super::query(project_path, query)
+                .unwrap()
+                .iter()
+                .map(|t| t.id)
+                .collect()
+        };
+
+        for indexed in [false, true] {
+            if indexed {
+                build_index(project_path).unwrap();
+            }
+            assert_eq!(ids(&TaskQuery::default()), [2, 1, 3]);
+            let api = TaskQuery {
+                tag: Some("api".to_string()),
+                sort: TaskSort::Id,
+                ..TaskQuery::default()
+            };
+            assert_eq!(ids(&api), [1, 3]);
+            let by_priority = TaskQuery {
+                sort: TaskSort::Priority,
+                ..TaskQuery::default()
+            };
+            assert_eq!(ids(&by_priority), [2, 3, 1]);
+            let search = TaskQuery {
+                text: Some("task 3".to_string()),
+                ..TaskQuery::default()
+            };
+            assert_eq!(ids(&search), [3]);
+            let done = TaskQuery {
+                status: Some("done".to_string()),
+                ..TaskQuery::default()
+            };
+            assert_eq!(ids(&done), [3]);
+        }
+    }
+
+    #[test]
+    fn drop_index_disables_cache() {
+        let temp_dir = setup_project();
+        build_index(temp_dir.path()).unwrap();
+        assert!(drop_index(temp_dir.path()).unwrap());
+        assert!(!is_enabled(temp_dir.path()));
+        assert!(!drop_index(temp_dir.path()).unwrap());
+    }
+}
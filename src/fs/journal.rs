@@ -0,0 +1,379 @@
+/// 任务变更日志（append-only，供 AI 与人协作时审计和对账）
+///
+/// 每次创建/编辑/移动/删除/归档任务都会向 `.kanban/journal.ndjson` 追加一行 JSON。
+/// 全局项目写入 `~/.kanban/journal.ndjson`，本地项目写入仓库内的 `.kanban/journal.ndjson`。
+use crate::models::Task;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// 日志文件名
+pub const JOURNAL_FILE: &str = "journal.ndjson";
+
+/// 当前进程的默认操作者（tui / cli / mcp），可被 `HXK_ACTOR` 环境变量覆盖
+static DEFAULT_ACTOR: OnceLock<String> = OnceLock::new();
+
+/// 已加载项目的 id 和名称（项目目录 → 项目），记录日志时不必重新读取配置
+static PROJECTS: OnceLock<Mutex<HashMap<PathBuf, ProjectKey>>> = OnceLock::new();
+
+/// 变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalAction {
+    Create,
+    Edit,
+    Move,
+    Delete,
+    Archive,
+}
+
+impl JournalAction {
+    fn label(self) -> &'static str {
+        match self {
+            JournalAction::Create => "create",
+            JournalAction::Edit => "edit",
+            JournalAction::Move => "move",
+            JournalAction::Delete => "delete",
+            JournalAction::Archive => "archive",
+        }
+    }
+}
+
+/// 一条日志记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// RFC 3339 时间戳
+    pub ts: String,
+    pub actor: String,
+    pub project: String,
+    /// 项目的稳定 id（重命名后不变；旧记录没有）
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub project_id: String,
+    pub action: JournalAction,
+    pub task_id: u32,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+impl JournalEntry {
+    /// 记录是否属于该项目：有 id 时按 id 匹配，旧记录按名称匹配
+    pub fn is_for(&self, project_id: &str, project_name: &str) -> bool {
+        if self.project_id.is_empty() {
+            self.project == project_name
+        } else {
+            self.project_id == project_id
+        }
+    }
+}
+
+/// 日志中标识项目的 id 和名称
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectKey {
+    pub id: String,
+    pub name: String,
+}
+
+impl ProjectKey {
+    pub fn of(project: &crate::models::Project) -> Self {
+        Self {
+            id: project.id.clone(),
+            name: project.name.clone(),
+        }
+    }
+
+    fn matches(&self, entry: &JournalEntry) -> bool {
+        entry.is_for(&self.id, &self.name)
+    }
+}
+
+/// 登记项目的 id 和名称（加载或保存项目配置时调用），之后的日志记录直接使用
+pub fn remember_project(project_path: &Path, id: &str, name: &str) {
+    let key = ProjectKey {
+        id: id.to_string(),
+        name: name.to_string(),
+    };
+    if let Ok(mut projects) = PROJECTS.get_or_init(Default::default).lock() {
+        projects.insert(project_path.to_path_buf(), key);
+    }
+}
+
+/// 设置当前进程的默认操作者（只在启动时调用一次）
+pub fn set_default_actor(actor: &str) {
+    let _ = DEFAULT_ACTOR.set(actor.to_string());
+}
+
+fn current_actor() -> String {
+    std::env::var("HXK_ACTOR")
+        .ok()
+        .filter(|a| !a.trim().is_empty())
+        .or_else(|| DEFAULT_ACTOR.get().cloned())
+        .unwrap_or_else(|| "tui".to_string())
+}
+
+/// 查找项目所属的 `.kanban` 目录下的日志文件
+///
+/// 不在 `.kanban` 目录下的项目（例如测试用的临时目录）不记录日志。
+pub fn journal_path(project_path: &Path) -> Option<PathBuf> {
+    project_path
+        .ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == ".kanban"))
+        .map(|dir| dir.join(JOURNAL_FILE))
+}
+
+/// 项目的 id 和名称：使用已登记的，没有登记过时读取一次配置
+fn project_key(project_path: &Path) -> ProjectKey {
+    let projects = PROJECTS.get_or_init(Default::default);
+    if let Some(key) = projects
+        .lock()
+        .ok()
+        .and_then(|projects| projects.get(project_path).cloned())
+    {
+        return key;
+    }
+    match super::load_project_config(project_path) {
+        Ok(config) => {
+            let id = config.id.unwrap_or_default();
+            remember_project(project_path, &id, &config.name);
+            ProjectKey {
+                id,
+                name: config.name,
+            }
+        }
+        Err(_) => ProjectKey {
+            id: String::new(),
+            name: project_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        },
+    }
+}
+
+/// 记录一次任务变更；写日志失败不影响任务操作本身
+pub fn record(
+    project_path: &Path,
+    action: JournalAction,
+    task: &Task,
+    from: Option<&str>,
+    to: Option<&str>,
+) {
+    let Some(path) = journal_path(project_path) else {
+        return;
+    };
+
+    let project = project_key(project_path);
+    let entry = JournalEntry {
+        ts: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        actor: current_actor(),
+        project: project.name,
+        project_id: project.id,
+        action,
+        task_id: task.id,
+        title: task.title.clone(),
+        from: from.map(|s| s.to_string()),
+        to: to.map(|s| s.to_string()),
+    };
+
+    let _ = append_entry(&path, &entry);
+}
+
+fn append_entry(path: &Path, entry: &JournalEntry) -> Result<(), String> {
+    let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| e.to_string())?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())
+}
+
+/// 读取日志中最近的记录（按时间倒序），可按项目过滤
+///
+/// 传入多个日志文件时（全局 + 当前目录），合并后按时间排序。
+pub fn read_recent(
+    paths: &[PathBuf],
+    project: Option<&ProjectKey>,
+    limit: usize,
+) -> Vec<JournalEntry> {
+    let mut entries: Vec<JournalEntry> = paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<JournalEntry>(line).ok())
+                .collect::<Vec<_>>()
+        })
+        .filter(|entry| project.is_none_or(|key| key.matches(entry)))
+        .collect();
+
+    // 按解析后的时间排序（时区不同的记录也能正确比较）；稳定排序，同一秒内保持写入顺序
+    entries.sort_by_key(|entry| chrono::DateTime::parse_from_rfc3339(&entry.ts).ok());
+    entries.into_iter().rev().take(limit).collect()
+}
+
+/// 默认读取的日志文件：全局日志 + 当前目录的本地日志
+pub fn default_journal_paths() -> Vec<PathBuf> {
+    let mut paths = vec![super::get_data_dir().join(JOURNAL_FILE)];
+    let local = super::get_local_kanban_dir().join(JOURNAL_FILE);
+    if local.exists() && !paths.contains(&local) {
+        paths.push(local);
+    }
+    paths
+}
+
+/// 格式化一条记录（用于 TUI 和 CLI 展示）
+pub fn format_entry(entry: &JournalEntry) -> String {
+    let ts = chrono::DateTime::parse_from_rfc3339(&entry.ts)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| entry.ts.clone());
+
+    let mut line = format!(
+        "{}  {:<6} {:<6} {}#{} {}",
+        ts,
+        entry.actor,
+        entry.action.label(),
+        if entry.project.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", entry.project)
+        },
+        entry.task_id,
+        entry.title
+    );
+    match (&entry.from, &entry.to) {
+        (Some(from), Some(to)) => line.push_str(&format!("  ({} → {})", from, to)),
+        (None, Some(to)) => line.push_str(&format!("  ({})", to)),
+        _ => {}
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn records_only_inside_kanban_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(journal_path(temp_dir.path()), None);
+
+        let project_path = temp_dir.path().join(".kanban").join("demo");
+        fs::create_dir_all(&project_path).unwrap();
+        assert_eq!(
+            journal_path(&project_path),
+            Some(temp_dir.path().join(".kanban").join(JOURNAL_FILE))
+        );
+    }
+
+    #[test]
+    fn appends_entries_and_reads_them_newest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join(".kanban").join("demo");
+        fs::create_dir_all(&project_path).unwrap();
+
+        let task = Task::new(3, "Write docs".to_string(), "todo".to_string());
+        record(
+            &project_path,
+            JournalAction::Create,
+            &task,
+            None,
+            Some("todo"),
+        );
+        record(
+            &project_path,
+            JournalAction::Move,
+            &task,
+            Some("todo"),
+            Some("done"),
+        );
+
+        let path = journal_path(&project_path).unwrap();
+        let demo = ProjectKey {
+            id: String::new(),
+            name: "demo".to_string(),
+        };
+        let entries = read_recent(std::slice::from_ref(&path), Some(&demo), 10);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, JournalAction::Move);
+        assert_eq!(entries[0].from.as_deref(), Some("todo"));
+        assert_eq!(entries[1].action, JournalAction::Create);
+        let other = ProjectKey {
+            id: String::new(),
+            name: "other".to_string(),
+        };
+        assert!(read_recent(&[path], Some(&other), 10).is_empty());
+        assert!(format_entry(&entries[0]).contains("(todo → done)"));
+    }
+
+    #[test]
+    fn history_follows_project_id_across_renames() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().join(".kanban").join("renamed");
+        fs::create_dir_all(&project_path).unwrap();
+        let task = Task::new(1, "Ship".to_string(), "todo".to_string());
+
+        remember_project(&project_path, "p-1", "before");
+        record(
+            &project_path,
+            JournalAction::Create,
+            &task,
+            None,
+            Some("todo"),
+        );
+        remember_project(&project_path, "p-1", "after");
+        record(
+            &project_path,
+            JournalAction::Edit,
+            &task,
+            None,
+            Some("todo"),
+        );
+
+        let path = journal_path(&project_path).unwrap();
+        let key = ProjectKey {
+            id: "p-1".to_string(),
+            name: "after".to_string(),
+        };
+        let entries = read_recent(std::slice::from_ref(&path), Some(&key), 10);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].project, "before");
+        assert_eq!(entries[1].project_id, "p-1");
+    }
+
+    #[test]
+    fn entries_are_ordered_by_time_not_by_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(JOURNAL_FILE);
+        let mut task = Task::new(1, "x".to_string(), "todo".to_string());
+        for (ts, title) in [
+            ("2026-03-05T09:00:00+08:00", "first"),
+            ("2026-03-05T08:00:00+00:00", "second"),
+        ] {
+            task.title = title.to_string();
+            let entry = JournalEntry {
+                ts: ts.to_string(),
+                actor: "cli".to_string(),
+                project: "demo".to_string(),
+                project_id: String::new(),
+                action: JournalAction::Edit,
+                task_id: task.id,
+                title: task.title.clone(),
+                from: None,
+                to: None,
+            };
+            append_entry(&path, &entry).unwrap();
+        }
+        // 09:00+08:00 是 01:00 UTC，比 08:00 UTC 早，按文本比较则顺序相反
+        let entries = read_recent(&[path], None, 10);
+        assert_eq!(entries[0].title, "second");
+        assert_eq!(entries[1].title, "first");
+    }
+}
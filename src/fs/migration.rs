@@ -0,0 +1,503 @@
+/// 项目文件格式版本与迁移
+///
+/// `.kanban.toml` 中的 `format_version` 记录项目目录布局的版本，缺省视为 0
+/// （引入版本号之前的项目）。打开旧版本项目时先生成迁移计划供用户确认，
+/// 确认后将整个项目备份到 `.kanban-backups/<时间戳>/`，再按版本依次执行迁移步骤，
+/// 最后写入当前版本号。
+///
+/// 版本历史：
+/// - 0：可能是旧 markdown 元数据格式、`tasks.toml` 元数据分离格式，或文件名带前导零/后缀
+/// - 1：所有任务使用 TOML frontmatter，文件名为 `{id}.md`
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::fs::parser::generate_toml_frontmatter;
+use crate::models::task::TaskFrontmatter;
+
+/// 当前项目格式版本
+pub const CURRENT_FORMAT_VERSION: u32 = 1;
+
+/// 备份目录名（以 `.` 开头，不会被当作状态目录扫描）
+pub const BACKUP_DIR: &str = ".kanban-backups";
+
+/// 迁移步骤：把项目从 `to_version - 1` 升级到 `to_version`
+struct Migration {
+    to_version: u32,
+    plan: fn(&Path) -> Result<Vec<Change>, String>,
+}
+
+/// 按版本顺序排列的迁移步骤
+const MIGRATIONS: &[Migration] = &[Migration {
+    to_version: 1,
+    plan: plan_frontmatter_layout,
+}];
+
+/// 迁移中的单项文件变更（路径相对于项目目录）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// 将 `tasks.toml` 中的元数据合并进各任务文件
+    AdoptTasksToml { tasks: usize },
+    /// 旧 markdown 格式转换为 frontmatter，文件名同时规范为 `{id}.md`
+    ConvertTask { from: PathBuf, to: PathBuf },
+    /// frontmatter 文件重命名为 `{id}.md`
+    RenameTask { from: PathBuf, to: PathBuf },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::AdoptTasksToml { tasks } => {
+                write!(f, "合并 tasks.toml 中 {} 个任务的元数据", tasks)
+            }
+            Change::ConvertTask { from, to } if from == to => {
+                write!(f, "转换 {} 为 frontmatter", from.display())
+            }
+            Change::ConvertTask { from, to } => {
+                write!(
+                    f,
+                    "转换 {} → {} 为 frontmatter",
+                    from.display(),
+                    to.display()
+                )
+            }
+            Change::RenameTask { from, to } => {
+                write!(f, "重命名 {} → {}", from.display(), to.display())
+            }
+        }
+    }
+}
+
+/// 迁移计划
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationPlan {
+    /// 项目当前的格式版本
+    pub from_version: u32,
+    /// 需要改动的文件；为空时只需写入版本号
+    pub changes: Vec<Change>,
+}
+
+impl MigrationPlan {
+    /// 项目已是当前版本
+    pub fn is_up_to_date(&self) -> bool {
+        self.from_version >= CURRENT_FORMAT_VERSION
+    }
+
+    /// 迁移会改动任务文件，需要用户确认并备份
+    pub fn needs_confirmation(&self) -> bool {
+        !self.is_up_to_date() && !self.changes.is_empty()
+    }
+}
+
+/// 迁移结果
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    /// 实际执行的文件变更数
+    pub applied: usize,
+    /// 备份目录（没有文件变更时不备份）
+    pub backup: Option<PathBuf>,
+}
+
+/// 读取项目的格式版本（缺省为 0）
+pub fn format_version(project_path: &Path) -> Result<u32, String> {
+    Ok(super::load_project_config(project_path)?
+        .format_version
+        .unwrap_or(0))
+}
+
+/// 生成迁移计划（不修改任何文件）
+pub fn plan_migration(project_path: &Path) -> Result<MigrationPlan, String> {
+    let from_version = format_version(project_path)?;
+    let mut changes = Vec::new();
+
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.to_version > from_version)
+    {
+        changes.extend((migration.plan)(project_path)?);
+    }
+
+    Ok(MigrationPlan {
+        from_version,
+        changes,
+    })
+}
+
+/// 执行迁移：有文件变更时先备份，然后依次执行迁移步骤并写入当前版本号
+pub fn migrate_project(project_path: &Path) -> Result<MigrationReport, String> {
+    let plan = plan_migration(project_path)?;
+    if plan.is_up_to_date() {
+        return Ok(MigrationReport {
+            from_version: plan.from_version,
+            ..Default::default()
+        });
+    }
+
+    let backup = if plan.changes.is_empty() {
+        None
+    } else {
+        Some(backup_project(project_path)?)
+    };
+
+    // tasks.toml 必须先合并，之后的文件才是完整的 frontmatter
+    if project_path.join("tasks.toml").exists() {
+        super::task::migrate_metadata_to_frontmatter(project_path)?;
+    }
+    for change in &plan.changes {
+        apply_change(project_path, change)?;
+    }
+
+    let mut config = super::load_project_config(project_path)?;
+    config.format_version = Some(CURRENT_FORMAT_VERSION);
+    super::save_project_config(project_path, &config)?;
+
+    Ok(MigrationReport {
+        from_version: plan.from_version,
+        applied: plan.changes.len(),
+        backup,
+    })
+}
+
+/// 合并 tasks.toml（写入任务时无法回退到旧格式，先备份再合并）
+pub fn adopt_tasks_toml(project_path: &Path) -> Result<(), String> {
+    if super::task::load_tasks_metadata(project_path)?.is_empty() {
+        return super::task::migrate_metadata_to_frontmatter(project_path);
+    }
+    backup_project(project_path)?;
+    super::task::migrate_metadata_to_frontmatter(project_path)
+}
+
+/// 将项目目录完整复制到 `.kanban-backups/<时间戳>/`，返回备份目录
+pub fn backup_project(project_path: &Path) -> Result<PathBuf, String> {
+    let backups = project_path.join(BACKUP_DIR);
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+
+    // 同一秒内多次备份时追加序号，避免覆盖
+    let mut target = backups.join(&stamp);
+    let mut suffix = 1;
+    while target.exists() {
+        target = backups.join(format!("{}-{}", stamp, suffix));
+        suffix += 1;
+    }
+
+    copy_dir(project_path, &target, &backups)?;
+    Ok(target)
+}
+
+fn copy_dir(from: &Path, to: &Path, skip: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+
+    for entry in
+        fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?
+    {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+        if path == skip {
+            continue;
+        }
+
+        let dest = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir(&path, &dest, skip)?;
+        } else {
+            fs::copy(&path, &dest)
+                .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 0 → 1：合并 tasks.toml，旧 markdown 转换为 frontmatter，文件名规范为 `{id}.md`
+fn plan_frontmatter_layout(project_path: &Path) -> Result<Vec<Change>, String> {
+    let mut changes = Vec::new();
+
+    // tasks.toml 中登记的文件只有正文，由合并步骤处理
+    let mut adopted = HashSet::new();
+    if project_path.join("tasks.toml").exists() {
+        let metadata = super::task::load_tasks_metadata(project_path)?;
+        if !metadata.is_empty() {
+            changes.push(Change::AdoptTasksToml {
+                tasks: metadata.len(),
+            });
+        }
+        adopted.extend(
+            metadata
+                .iter()
+                .map(|(id, meta)| PathBuf::from(&meta.status).join(format!("{}.md", id))),
+        );
+    }
+
+    let config = super::load_project_config(project_path)?;
+    // 已被占用或将被占用的目标文件名
+    let mut claimed: HashSet<PathBuf> = HashSet::new();
+    let mut pending = Vec::new();
+
+    for status in &config.statuses.order {
+        // 映射到笔记库的目录保持原样：改写或重命名笔记会破坏笔记之间的链接
+        if config
+            .statuses
+            .statuses
+            .get(status)
+            .is_some_and(|s| s.path.is_some())
+        {
+            continue;
+        }
+        let status_dir = project_path.join(status);
+        let Ok(entries) = fs::read_dir(&status_dir) else {
+            continue;
+        };
+
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("md"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let relative = PathBuf::from(status).join(path.file_name().unwrap_or_default());
+            claimed.insert(relative.clone());
+            if adopted.contains(&relative) {
+                continue;
+            }
+
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let is_frontmatter = content.trim_start().starts_with("+++");
+            // 无法解析的文件保持原样，加载时仍按原逻辑容错
+            let Ok(task) = super::task::load_task_file(&path, status) else {
+                continue;
+            };
+
+            let target = PathBuf::from(status).join(format!("{}.md", task.id));
+            pending.push((relative, target, is_frontmatter));
+        }
+    }
+
+    for (from, target, is_frontmatter) in pending {
+        // 目标文件名已被其他任务占用（重复 ID）时保留原文件名
+        let to = if from != target && !claimed.contains(&target) {
+            claimed.insert(target.clone());
+            target
+        } else {
+            from.clone()
+        };
+
+        if !is_frontmatter {
+            changes.push(Change::ConvertTask { from, to });
+        } else if from != to {
+            changes.push(Change::RenameTask { from, to });
+        }
+    }
+
+    Ok(changes)
+}
+
+fn apply_change(project_path: &Path, change: &Change) -> Result<(), String> {
+    match change {
+        Change::AdoptTasksToml { .. } => Ok(()),
+        Change::ConvertTask { from, to } => {
+            let from_path = project_path.join(from);
+            let status = from
+                .parent()
+                .and_then(|p| p.to_str())
+                .ok_or_else(|| format!("Invalid task path: {}", from.display()))?;
+            let task = super::task::load_task(&from_path, status)?;
+
+            let content = generate_toml_frontmatter(
+                &TaskFrontmatter::from(&task),
+                &task.title,
+                &task.content,
+            );
+            fs::write(project_path.join(to), content)
+                .map_err(|e| format!("Failed to write {}: {}", to.display(), e))?;
+            if from != to {
+                fs::remove_file(&from_path)
+                    .map_err(|e| format!("Failed to remove {}: {}", from.display(), e))?;
+            }
+            Ok(())
+        }
+        Change::RenameTask { from, to } => {
+            fs::rename(project_path.join(from), project_path.join(to)).map_err(|e| {
+                format!(
+                    "Failed to rename {} → {}: {}",
+                    from.display(),
+                    to.display(),
+                    e
+                )
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_project(dir: &Path, version: Option<u32>) {
+        let version_line = version
+            .map(|v| format!("format_version = {}\n", v))
+            .unwrap_or_default();
+        fs::write(
+            dir.join(".kanban.toml"),
+            format!(
+                "name = \"legacy\"\ncreated = \"1\"\n{}\n[statuses]\norder = [\"todo\", \"done\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n\n[statuses.done]\ndisplay = \"Done\"\n",
+                version_line
+            ),
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("todo")).unwrap();
+        fs::create_dir_all(dir.join("done")).unwrap();
+    }
+
+    #[test]
+    fn plans_conversion_and_renames_for_legacy_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), None);
+        // 旧 markdown，无 id 元数据，ID 来自文件名
+        fs::write(dir.path().join("todo/001.md"), "# First\n\nbody\n").unwrap();
+        // frontmatter 但文件名带后缀
+        fs::write(
+            dir.path().join("done/002-ship.md"),
+            "+++\nid = 2\ncreated = \"1\"\n+++\n\n# Ship\n",
+        )
+        .unwrap();
+        // 已符合新布局
+        fs::write(
+            dir.path().join("done/3.md"),
+            "+++\nid = 3\ncreated = \"1\"\n+++\n\n# Done\n",
+        )
+        .unwrap();
+
+        let plan = plan_migration(dir.path()).unwrap();
+
+        assert_eq!(plan.from_version, 0);
+        assert!(plan.needs_confirmation());
+        assert_eq!(
+            plan.changes,
+            vec![
+                Change::ConvertTask {
+                    from: PathBuf::from("todo/001.md"),
+                    to: PathBuf::from("todo/1.md"),
+                },
+                Change::RenameTask {
+                    from: PathBuf::from("done/002-ship.md"),
+                    to: PathBuf::from("done/2.md"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn migrate_backs_up_rewrites_files_and_stamps_version() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), None);
+        fs::write(
+            dir.path().join("todo/001.md"),
+            "# First\n\npriority: high\n\nbody\n",
+        )
+        .unwrap();
+
+        let report = migrate_project(dir.path()).unwrap();
+
+        assert_eq!(report.applied, 1);
+        let backup = report.backup.unwrap();
+        assert!(backup.starts_with(dir.path().join(BACKUP_DIR)));
+        assert_eq!(
+            fs::read_to_string(backup.join("todo/001.md")).unwrap(),
+            "# First\n\npriority: high\n\nbody\n"
+        );
+
+        assert!(!dir.path().join("todo/001.md").exists());
+        let migrated = fs::read_to_string(dir.path().join("todo/1.md")).unwrap();
+        assert!(migrated.starts_with("+++"));
+        assert!(migrated.contains("id = 1"));
+        assert!(migrated.contains("priority = \"high\""));
+
+        assert_eq!(format_version(dir.path()).unwrap(), CURRENT_FORMAT_VERSION);
+        assert!(plan_migration(dir.path()).unwrap().is_up_to_date());
+
+        // 备份目录不会被识别为状态列
+        let project = crate::fs::load_project(dir.path()).unwrap();
+        assert_eq!(project.statuses.len(), 2);
+        assert_eq!(project.tasks.len(), 1);
+    }
+
+    #[test]
+    fn adopts_tasks_toml_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), None);
+        fs::write(
+            dir.path().join("tasks.toml"),
+            "[7]\nid = 7\ntitle = \"From toml\"\nstatus = \"todo\"\norder = 1000\ncreated = \"1\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("todo/7.md"), "just the body\n").unwrap();
+
+        let plan = plan_migration(dir.path()).unwrap();
+        assert_eq!(plan.changes, vec![Change::AdoptTasksToml { tasks: 1 }]);
+
+        let report = migrate_project(dir.path()).unwrap();
+        assert!(report.backup.unwrap().join("tasks.toml").exists());
+        assert!(!dir.path().join("tasks.toml").exists());
+
+        let project = crate::fs::load_project(dir.path()).unwrap();
+        assert_eq!(project.tasks[0].title, "From toml");
+        assert_eq!(project.tasks[0].id, 7);
+    }
+
+    #[test]
+    fn current_layout_only_needs_version_stamp() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), None);
+        fs::write(
+            dir.path().join("todo/1.md"),
+            "+++\nid = 1\ncreated = \"1\"\n+++\n\n# Fine\n",
+        )
+        .unwrap();
+
+        let plan = plan_migration(dir.path()).unwrap();
+        assert!(!plan.is_up_to_date());
+        assert!(!plan.needs_confirmation());
+
+        let report = migrate_project(dir.path()).unwrap();
+        assert_eq!(report.backup, None);
+        assert!(!dir.path().join(BACKUP_DIR).exists());
+        assert_eq!(format_version(dir.path()).unwrap(), CURRENT_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn duplicate_ids_keep_original_file_names() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path(), Some(0));
+        fs::write(
+            dir.path().join("todo/1.md"),
+            "+++\nid = 1\ncreated = \"1\"\n+++\n\n# One\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("done/001-dup.md"),
+            "+++\nid = 1\ncreated = \"1\"\n+++\n\n# Dup\n",
+        )
+        .unwrap();
+
+        let plan = plan_migration(dir.path()).unwrap();
+        assert_eq!(
+            plan.changes,
+            vec![Change::RenameTask {
+                from: PathBuf::from("done/001-dup.md"),
+                to: PathBuf::from("done/1.md"),
+            }]
+        );
+
+        fs::write(
+            dir.path().join("done/1.md"),
+            "+++\nid = 1\ncreated = \"1\"\n+++\n\n# Taken\n",
+        )
+        .unwrap();
+        assert!(plan_migration(dir.path()).unwrap().changes.is_empty());
+    }
+}
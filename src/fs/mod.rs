@@ -1,14 +1,24 @@
+pub mod atomic;
+pub mod backup;
+pub mod ignore;
+pub mod index;
+pub mod journal;
+pub mod migration;
 pub mod parser;
 pub mod project;
+pub mod settings;
 pub mod status;
 pub mod task;
+pub mod toml_preserve;
 
 pub use project::{
-    create_local_project, create_project, delete_project_by_path, ensure_global_ai_config,
-    ensure_global_claude_md, get_data_dir, get_local_kanban_dir, get_projects_dir, init_data_dir,
-    list_local_project_dirs, list_project_dirs, load_project, load_project_config,
+    Workflow, create_local_project, create_project, delete_project_by_path,
+    ensure_global_ai_config, ensure_global_claude_md, get_data_dir, get_local_kanban_dir,
+    init_data_dir, list_local_project_dirs, list_project_dirs, load_project, load_project_config,
     load_project_with_type, save_project_config, update_project_metadata,
 };
+#[cfg(feature = "scripting")]
+pub use task::move_task_without_hooks;
 pub use task::{delete_task, get_max_order_in_status, get_next_task_id, move_task, save_task};
 
 use crate::models::{Project, ProjectType};
@@ -18,21 +28,48 @@ use anyhow::Result;
 pub fn load_all_projects() -> Result<Vec<Project>> {
     // 加载配置以获取隐藏项目列表
     let config = crate::config::load_config().unwrap_or_default();
+    load_all_projects_with_config(&config)
+}
+
+/// 使用已加载的配置加载所有项目（TUI 中复用 App 缓存的配置）
+pub fn load_all_projects_with_config(config: &crate::config::Config) -> Result<Vec<Project>> {
+    load_all_projects_using(config, load_project_with_type)
+}
 
+/// 同 [`load_all_projects_with_config`]，但只读取项目配置、不加载任务（TUI 启动时使用，
+/// 任务随后在 IO 线程中加载）
+pub fn load_all_project_headers_with_config(
+    config: &crate::config::Config,
+) -> Result<Vec<Project>> {
+    load_all_projects_using(config, project::load_project_header_with_type)
+}
+
+fn load_all_projects_using(
+    config: &crate::config::Config,
+    load: fn(&std::path::Path, ProjectType) -> Result<Project, String>,
+) -> Result<Vec<Project>> {
     init_data_dir()?;
     let mut projects = Vec::new();
 
-    // 加载全局项目 (~/.kanban/projects)，过滤隐藏的
+    // 当前目录通过 .kanban-link 关联的全局项目
+    let linked = project::read_linked_projects(&project::get_link_file_path());
+
+    // 加载全局项目 (~/.kanban/projects)，过滤隐藏的（关联项目始终显示）
     let global_project_dirs = list_project_dirs()?;
     for dir_name in global_project_dirs {
-        match load_project_with_type(&dir_name, ProjectType::Global) {
-            Ok(project) => {
+        crate::safe_mode::log(format!("加载全局项目 {}", dir_name.display()));
+        match load(&dir_name, ProjectType::Global) {
+            Ok(mut project) => {
+                project.pinned = linked.contains(&project.name);
                 // 检查项目是否被隐藏
-                if !crate::config::is_project_hidden(&config, &project.name) {
+                if project.pinned || !crate::config::is_project_hidden(config, &project) {
                     projects.push(project);
                 }
             }
-            Err(e) => eprintln!("警告: 无法加载全局项目 {}: {}", dir_name.display(), e),
+            Err(e) => {
+                crate::safe_mode::log(format!("加载全局项目失败 {}: {}", dir_name.display(), e));
+                eprintln!("警告: 无法加载全局项目 {}: {}", dir_name.display(), e);
+            }
         }
     }
 
@@ -43,19 +80,40 @@ pub fn load_all_projects() -> Result<Vec<Project>> {
     let current_local_dir = get_local_kanban_dir();
 
     for dir_name in local_project_dirs {
-        match load_project_with_type(&dir_name, ProjectType::Local) {
+        crate::safe_mode::log(format!("加载本地项目 {}", dir_name.display()));
+        match load(&dir_name, ProjectType::Local) {
             Ok(project) => {
-                // 判断是否是当前目录的项目
-                let is_current_dir = dir_name == current_local_dir;
+                // 判断是否是当前目录的项目（包括 .kanban/ 中的多个看板）
+                let is_current_dir = dir_name.starts_with(&current_local_dir);
 
                 // 当前目录的项目永远显示，其他项目检查软删除状态
-                if is_current_dir || !crate::config::is_project_hidden(&config, &project.name) {
+                if is_current_dir || !crate::config::is_project_hidden(config, &project) {
                     projects.push(project);
                 }
             }
-            Err(e) => eprintln!("警告: 无法加载本地项目 {}: {}", dir_name.display(), e),
+            Err(e) => {
+                crate::safe_mode::log(format!("加载本地项目失败 {}: {}", dir_name.display(), e));
+                eprintln!("警告: 无法加载本地项目 {}: {}", dir_name.display(), e);
+            }
+        }
+    }
+
+    // 复制出来的项目目录会带着相同的 id，为后出现的项目重新分配
+    let mut seen_ids = std::collections::HashSet::new();
+    for project in &mut projects {
+        if !seen_ids.insert(project.id.clone()) {
+            project.id = project::reassign_project_id(&project.path);
+            seen_ids.insert(project.id.clone());
         }
     }
 
+    // 关联项目按 .kanban-link 中的顺序置顶，其余保持原顺序
+    projects.sort_by_key(|project| {
+        linked
+            .iter()
+            .position(|name| project.pinned && *name == project.name)
+            .unwrap_or(usize::MAX)
+    });
+
     Ok(projects)
 }
@@ -70,11 +70,15 @@ pub fn parse_task_md(content: &str) -> Result<ParsedTask, String> {
     let mut in_content = false;
 
     // Parse metadata and content
-    for line in &lines[1..] {
-        let line = line.trim();
+    for raw_line in &lines[1..] {
+        let line = raw_line.trim();
 
         // Skip empty lines between title and metadata
         if line.is_empty() && !in_content {
+            // 元数据之后的空行结束元数据块，之后形如 key: value 的行也属于正文
+            if !metadata.is_empty() {
+                in_content = true;
+            }
             continue;
         }
 
@@ -93,25 +97,39 @@ pub fn parse_task_md(content: &str) -> Result<ParsedTask, String> {
             in_content = true;
         }
 
+        // 正文保留原始缩进
         if in_content {
-            content_lines.push(line);
+            content_lines.push(raw_line);
         }
     }
 
     Ok(ParsedTask {
         title,
         metadata,
-        content: content_lines.join("\n").trim().to_string(),
+        content: trim_blank_lines(&content_lines.join("\n")),
     })
 }
 
+/// 旧格式元数据的写出顺序，其余字段按字母序排在后面
+const LEGACY_KEY_ORDER: [&str; 5] = ["id", "order", "created", "priority", "tags"];
+
 /// Generate markdown content for a task
 pub fn generate_task_md(title: &str, metadata: &HashMap<String, String>, content: &str) -> String {
     let mut output = format!("# {}\n\n", title);
 
-    // Write metadata
-    for (key, value) in metadata {
-        output.push_str(&format!("{}: {}\n", key, value));
+    // Write metadata（固定顺序，保证多次保存结果一致）
+    let mut keys: Vec<&String> = metadata.keys().collect();
+    keys.sort_by_key(|key| {
+        (
+            LEGACY_KEY_ORDER
+                .iter()
+                .position(|known| known == key)
+                .unwrap_or(LEGACY_KEY_ORDER.len()),
+            key.as_str(),
+        )
+    });
+    for key in keys {
+        output.push_str(&format!("{}: {}\n", key, metadata[key]));
     }
 
     // Write content if exists
@@ -177,6 +195,24 @@ pub fn parse_toml_frontmatter(content: &str) -> Result<ParsedFrontmatterTask, St
     })
 }
 
+/// 标题行（第一个 `# ` 开头的行，跳过 frontmatter）的行号，从 1 开始
+pub fn title_line(content: &str) -> Option<usize> {
+    let mut in_frontmatter = false;
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed == "+++" {
+            in_frontmatter = !in_frontmatter;
+        } else if !in_frontmatter
+            && trimmed
+                .strip_prefix("# ")
+                .is_some_and(|title| !title.trim().is_empty())
+        {
+            return Some(idx + 1);
+        }
+    }
+    None
+}
+
 /// 从内容中提取标题（第一个 # 开头的行）和剩余内容
 fn extract_title_and_content(content: &str) -> (String, String) {
     let mut title = String::new();
@@ -220,56 +256,76 @@ fn extract_title_and_content(content: &str) -> (String, String) {
     (title, body)
 }
 
-/// 从内容中移除嵌入的 frontmatter 块
-/// 处理损坏文件中可能存在的重复 frontmatter
+/// 从正文开头移除嵌入的 frontmatter 块
+/// 处理损坏文件中可能存在的重复 frontmatter；只处理正文开头，
+/// 正文中间出现的 `+++`（例如 TOML 示例）原样保留
 fn strip_embedded_frontmatter(content: &str) -> String {
-    let mut result = String::new();
-    let mut in_frontmatter = false;
-    let mut skip_next_title = false;
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    let mut after_block = false;
 
-    for line in content.lines() {
+    while let Some(line) = lines.get(start) {
         let trimmed = line.trim();
 
-        // 检测 frontmatter 边界
-        if trimmed == "+++" {
-            in_frontmatter = !in_frontmatter;
-            if !in_frontmatter {
-                // 刚结束一个 frontmatter 块，跳过紧随其后的 "# +++" 或空标题
-                skip_next_title = true;
-            }
-            continue;
-        }
-
-        // 跳过 frontmatter 内容
-        if in_frontmatter {
+        if trimmed.is_empty() {
+            start += 1;
             continue;
         }
 
-        // 跳过 "# +++" 这样的无效标题行
-        if skip_next_title {
-            if trimmed.is_empty() {
-                continue;
-            }
-            if trimmed == "# +++" || trimmed == "#" {
-                continue;
-            }
-            // 如果是另一个有效的标题行，也跳过（因为真正的标题已经提取了）
-            if trimmed.starts_with("# ") && trimmed.len() > 2 {
-                let title_text = trimmed[2..].trim();
-                if title_text != "+++" && !title_text.is_empty() {
-                    // 这可能是重复的标题，跳过
-                    skip_next_title = false;
+        if trimmed == "+++" {
+            // 找到配对的 +++，跳过整个块；没有配对说明是正文
+            match lines[start + 1..].iter().position(|l| l.trim() == "+++") {
+                Some(offset) => {
+                    start += offset + 2;
+                    after_block = true;
                     continue;
                 }
+                None => break,
             }
-            skip_next_title = false;
         }
 
-        result.push_str(line);
-        result.push('\n');
+        // 块之后的 "# +++" 或重复标题（真正的标题已经提取了）
+        if after_block && (trimmed == "# +++" || trimmed == "#") {
+            start += 1;
+            continue;
+        }
+        if after_block && trimmed.starts_with("# ") {
+            start += 1;
+            after_block = false;
+            continue;
+        }
+
+        break;
+    }
+
+    trim_blank_lines(&lines[start.min(lines.len())..].join("\n"))
+}
+
+/// 去掉首尾的空行和结尾空白，保留正文内部的缩进和空行
+fn trim_blank_lines(content: &str) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+    while lines.first().is_some_and(|line| line.trim().is_empty()) {
+        lines.remove(0);
     }
+    lines.join("\n").trim_end().to_string()
+}
 
-    result.trim().to_string()
+/// 解析在外部编辑器中写好的新任务草稿
+///
+/// 第一个非空行（去掉开头的 `#`）是标题，其余是正文；没有标题时返回 `None`。
+pub fn parse_task_draft(content: &str) -> Option<(String, String)> {
+    let mut lines = content.lines().skip_while(|line| line.trim().is_empty());
+    let title = lines
+        .next()?
+        .trim()
+        .trim_start_matches('#')
+        .trim()
+        .to_string();
+    if title.is_empty() {
+        return None;
+    }
+    let body = trim_blank_lines(&lines.collect::<Vec<_>>().join("\n"));
+    Some((title, body))
 }
 
 /// 生成 TOML frontmatter 格式的任务文件内容
@@ -319,6 +375,14 @@ pub fn parse_toml_frontmatter_with_recovery(
     recover_from_corrupted_frontmatter(content, file_path)
 }
 
+/// 从文件名解析任务 ID：纯数字（`001.md`）或数字前缀（`001-checkout-flow.md`）
+pub fn id_from_file_name(path: &std::path::Path) -> Option<u32> {
+    let stem = path.file_stem()?.to_str()?;
+    stem.parse::<u32>()
+        .ok()
+        .or_else(|| stem.split('-').next()?.parse::<u32>().ok())
+}
+
 /// 从损坏的 frontmatter 中恢复任务数据
 fn recover_from_corrupted_frontmatter(
     content: &str,
@@ -326,16 +390,31 @@ fn recover_from_corrupted_frontmatter(
 ) -> Result<ParsedFrontmatterTask, String> {
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    // 1. id: 从文件名恢复 (1.md → id=1)
-    let id = file_path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .and_then(|s| s.parse::<u32>().ok())
-        .ok_or("Cannot recover id from filename")?;
+    // 1. id: 从文件名恢复 (1.md → id=1, 001-checkout.md → id=1)
+    let id = id_from_file_name(file_path).ok_or("Cannot recover id from filename")?;
 
     // 2. order: 使用 id * 1000 保持可预测的排序
     let order = id as i32 * 1000;
 
+    // 2.5 frontmatter 仍是合法 TOML、只是缺少 id 时，保留其余字段
+    if let Some(mut table) = frontmatter_table(content) {
+        table
+            .entry("id")
+            .or_insert_with(|| toml::Value::Integer(id.into()));
+        if let Ok(frontmatter) = table.try_into::<TaskFrontmatter>() {
+            let (title, body_content) = extract_title_and_content(after_frontmatter_block(content));
+            return Ok(ParsedFrontmatterTask {
+                frontmatter,
+                title: if title.is_empty() {
+                    format!("Task {}", id)
+                } else {
+                    title
+                },
+                content: body_content,
+            });
+        }
+    }
+
     // 3. title: 从内容中提取
     let title = extract_title_from_content_only(content).unwrap_or_else(|| format!("Task {}", id));
 
@@ -352,16 +431,33 @@ fn recover_from_corrupted_frontmatter(
     Ok(ParsedFrontmatterTask {
         frontmatter: TaskFrontmatter {
             id,
-            order,
+            order: Some(order),
             created,
             priority: None,
             tags: Vec::new(),
+            extra: toml::Table::new(),
         },
         title,
         content: body_content,
     })
 }
 
+/// 提取 `+++` 之间的 frontmatter 并解析为 TOML 表（用于容错恢复）
+fn frontmatter_table(content: &str) -> Option<toml::Table> {
+    let after_first = content.trim_start().strip_prefix("+++")?;
+    let end = after_first.find("\n+++")?;
+    after_first[..end].trim().parse::<toml::Table>().ok()
+}
+
+/// frontmatter 块之后的内容
+fn after_frontmatter_block(content: &str) -> &str {
+    content
+        .trim_start()
+        .strip_prefix("+++")
+        .and_then(|after_first| after_first.find("\n+++").map(|end| &after_first[end + 4..]))
+        .unwrap_or(content)
+}
+
 /// 仅从内容中提取标题（用于容错恢复）
 fn extract_title_from_content_only(content: &str) -> Option<String> {
     for line in content.lines() {
@@ -413,6 +509,28 @@ fn extract_content_after_corrupted_frontmatter(content: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn task_draft_uses_first_non_empty_line_as_title() {
+        assert_eq!(
+            parse_task_draft("\n\n# 写周报 \n\n  - 汇总进度\n\n"),
+            Some(("写周报".to_string(), "  - 汇总进度".to_string()))
+        );
+        assert_eq!(
+            parse_task_draft("Plain title"),
+            Some(("Plain title".to_string(), String::new()))
+        );
+        assert_eq!(parse_task_draft(" \n#\nbody"), None);
+        assert_eq!(parse_task_draft(""), None);
+    }
+
+    #[test]
+    fn title_line_skips_frontmatter() {
+        let content = "+++\nid = 1\n# not a title = 1\n+++\n\n# 任务标题\n\n正文\n";
+        assert_eq!(title_line(content), Some(6));
+        assert_eq!(title_line("# Legacy\n\ncreated: x\n"), Some(1));
+        assert_eq!(title_line("no title"), None);
+    }
+
     #[test]
     fn test_parse_simple_task() {
         let md = r#"# Test Task
@@ -637,4 +755,239 @@ Content.
             Some(&"echo \"hello world\"".to_string())
         );
     }
+
+    // ===== 回归语料：src/fs/testdata/tasks/*.md 与对应的 .golden =====
+    //
+    // 每个样例经过「解析 → 生成」后必须与 .golden 完全一致，且对 .golden 再做一次
+    // 往返结果不变。修改格式后可用 `UPDATE_GOLDEN=1 cargo test` 重新生成 .golden。
+
+    fn testdata_dir() -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/fs/testdata/tasks")
+    }
+
+    fn roundtrip(content: &str, path: &std::path::Path) -> String {
+        if content.trim_start().starts_with("+++") {
+            let parsed = parse_toml_frontmatter_with_recovery(content, path).unwrap();
+            generate_toml_frontmatter(&parsed.frontmatter, &parsed.title, &parsed.content)
+        } else {
+            let parsed = parse_task_md(content).unwrap();
+            generate_task_md(&parsed.title, &parsed.metadata, &parsed.content)
+        }
+    }
+
+    #[test]
+    fn golden_corpus_roundtrips() {
+        let mut fixtures: Vec<_> = std::fs::read_dir(testdata_dir())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .collect();
+        fixtures.sort();
+        assert!(!fixtures.is_empty());
+
+        for path in fixtures {
+            let input = std::fs::read_to_string(&path).unwrap();
+            let output = roundtrip(&input, &path);
+            let golden_path = path.with_extension("md.golden");
+
+            if std::env::var_os("UPDATE_GOLDEN").is_some() {
+                std::fs::write(&golden_path, &output).unwrap();
+            }
+            let golden = std::fs::read_to_string(&golden_path)
+                .unwrap_or_else(|_| panic!("missing golden file {:?}", golden_path));
+
+            assert_eq!(output, golden, "golden mismatch for {:?}", path);
+            assert_eq!(
+                roundtrip(&golden, &path),
+                golden,
+                "second roundtrip changed {:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn recovers_missing_id_from_file_name_and_keeps_other_fields() {
+        let content = "+++\norder = 7000\nowner = \"dave\"\n+++\n\n# Title\n\nBody.\n";
+        let parsed = parse_toml_frontmatter_with_recovery(
+            content,
+            std::path::Path::new("todo/007-missing-id.md"),
+        )
+        .unwrap();
+        assert_eq!(parsed.frontmatter.id, 7);
+        assert_eq!(parsed.frontmatter.order(), 7000);
+        assert_eq!(parsed.frontmatter.created, "0");
+        assert_eq!(
+            parsed.frontmatter.extra.get("owner"),
+            Some(&toml::Value::String("dave".to_string()))
+        );
+        assert_eq!(parsed.title, "Title");
+        assert_eq!(parsed.content, "Body.");
+    }
+
+    // ===== 性质测试：随机生成任务，往返后内容不变 =====
+
+    /// 简单的 xorshift 伪随机数（固定种子，失败时可复现）
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[self.below(items.len())]
+        }
+    }
+
+    const TITLE_WORDS: &[&str] = &[
+        "Fix",
+        "parser",
+        "#12",
+        "C#",
+        "中文标题",
+        "emoji 🚀",
+        "key: value",
+        "a+b",
+        "[x]",
+        "`code`",
+    ];
+
+    const BODY_LINES: &[&str] = &[
+        "",
+        "plain text",
+        "    indented code",
+        "\tTabbed line",
+        "## Heading",
+        "# Another h1",
+        "- [ ] todo item",
+        "- [x] done item",
+        "key: value in body",
+        "+++",
+        "trailing spaces  ",
+        "中文正文，带标点。",
+        "```",
+    ];
+
+    fn random_title(rng: &mut Rng) -> String {
+        let words: Vec<&str> = (0..1 + rng.below(4))
+            .map(|_| rng.pick(TITLE_WORDS))
+            .collect();
+        words.join(" ")
+    }
+
+    fn random_body(rng: &mut Rng) -> String {
+        // 首行不能是 +++（会被视为嵌入的 frontmatter）
+        let mut lines = vec![rng.pick(&BODY_LINES[1..9])];
+        for _ in 0..rng.below(8) {
+            lines.push(rng.pick(BODY_LINES));
+        }
+        lines.join("\n")
+    }
+
+    fn random_frontmatter(rng: &mut Rng) -> TaskFrontmatter {
+        let mut extra = toml::Table::new();
+        for i in 0..rng.below(4) {
+            let value = match rng.below(4) {
+                0 => toml::Value::String(format!("value {} : # +++", i)),
+                1 => toml::Value::Integer(rng.below(1000) as i64),
+                2 => toml::Value::Boolean(rng.below(2) == 0),
+                _ => toml::Value::Array(vec![toml::Value::String("a".to_string())]),
+            };
+            extra.insert(format!("custom_{}", i), value);
+        }
+
+        TaskFrontmatter {
+            id: 1 + rng.below(500) as u32,
+            order: if rng.below(3) == 0 {
+                None
+            } else {
+                Some(rng.below(100_000) as i32)
+            },
+            created: "1700000000".to_string(),
+            priority: [None, Some("high".to_string()), Some("low".to_string())][rng.below(3)]
+                .clone(),
+            tags: (0..rng.below(3)).map(|i| format!("tag{}", i)).collect(),
+            extra,
+        }
+    }
+
+    #[test]
+    fn frontmatter_roundtrip_property() {
+        let mut rng = Rng(0x5eed_1234_abcd_9876);
+
+        for case in 0..300 {
+            let frontmatter = random_frontmatter(&mut rng);
+            let title = random_title(&mut rng);
+            let body = random_body(&mut rng);
+
+            let file = generate_toml_frontmatter(&frontmatter, &title, &body);
+            let parsed = parse_toml_frontmatter(&file)
+                .unwrap_or_else(|e| panic!("case {}: {}\n{}", case, e, file));
+
+            assert_eq!(parsed.title, title, "case {}:\n{}", case, file);
+            assert_eq!(
+                parsed.content,
+                trim_blank_lines(&body),
+                "case {}:\n{}",
+                case,
+                file
+            );
+            assert_eq!(
+                toml::to_string(&parsed.frontmatter).unwrap(),
+                toml::to_string(&frontmatter).unwrap(),
+                "case {}:\n{}",
+                case,
+                file
+            );
+            assert_eq!(
+                generate_toml_frontmatter(&parsed.frontmatter, &parsed.title, &parsed.content),
+                generate_toml_frontmatter(&frontmatter, &title, &trim_blank_lines(&body)),
+                "case {}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn legacy_roundtrip_property() {
+        let mut rng = Rng(0x0ddb_a11c_afe0_0001);
+
+        for case in 0..300 {
+            let title = random_title(&mut rng);
+            let mut metadata = HashMap::new();
+            metadata.insert("id".to_string(), (1 + rng.below(500)).to_string());
+            for i in 0..rng.below(4) {
+                metadata.insert(format!("custom_{}", i), format!("v{} with: colon", i));
+            }
+            let body = random_body(&mut rng);
+
+            let file = generate_task_md(&title, &metadata, &body);
+            let parsed = parse_task_md(&file).unwrap();
+
+            assert_eq!(parsed.title, title, "case {}", case);
+            assert_eq!(parsed.metadata, metadata, "case {}:\n{}", case, file);
+            assert_eq!(
+                parsed.content,
+                trim_blank_lines(&body),
+                "case {}:\n{}",
+                case,
+                file
+            );
+            let regenerated = generate_task_md(&parsed.title, &parsed.metadata, &parsed.content);
+            assert_eq!(
+                parse_task_md(&regenerated).unwrap().content,
+                parsed.content,
+                "case {}",
+                case
+            );
+        }
+    }
 }
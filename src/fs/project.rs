@@ -1,7 +1,7 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::models::{Project, ProjectConfig, ProjectType, Status, StatusConfig};
+use crate::models::{Project, ProjectConfig, ProjectType, Status, StatusConfig, Task};
 use serde::{Deserialize, Serialize};
 
 /// 本地项目索引结构
@@ -57,6 +57,68 @@ pub fn add_local_project_to_index(project_path: &Path) -> std::io::Result<()> {
     Ok(())
 }
 
+/// 目录关联文件名：记录在该目录启动时置顶显示的全局项目（每行一个项目名）
+pub const LINK_FILE: &str = ".kanban-link";
+
+/// 获取当前目录的关联文件路径
+pub fn get_link_file_path() -> PathBuf {
+    std::env::current_dir()
+        .expect("Failed to get current directory")
+        .join(LINK_FILE)
+}
+
+/// 读取关联文件中的项目名（忽略空行和 # 注释）
+pub fn read_linked_projects(link_file: &Path) -> Vec<String> {
+    fs::read_to_string(link_file)
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(ToString::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn write_linked_projects(link_file: &Path, names: &[String]) -> Result<(), String> {
+    if names.is_empty() {
+        if link_file.exists() {
+            fs::remove_file(link_file)
+                .map_err(|e| format!("Failed to remove {}: {}", link_file.display(), e))?;
+        }
+        return Ok(());
+    }
+
+    let mut content = names.join("\n");
+    content.push('\n');
+    fs::write(link_file, content)
+        .map_err(|e| format!("Failed to write {}: {}", link_file.display(), e))
+}
+
+/// 将全局项目关联到目录，返回是否新增
+pub fn link_project(link_file: &Path, name: &str) -> Result<bool, String> {
+    let mut names = read_linked_projects(link_file);
+    if names.iter().any(|n| n == name) {
+        return Ok(false);
+    }
+    names.push(name.to_string());
+    write_linked_projects(link_file, &names)?;
+    Ok(true)
+}
+
+/// 取消关联，返回是否存在该关联；最后一个关联移除后删除文件
+pub fn unlink_project(link_file: &Path, name: &str) -> Result<bool, String> {
+    let mut names = read_linked_projects(link_file);
+    let before = names.len();
+    names.retain(|n| n != name);
+    if names.len() == before {
+        return Ok(false);
+    }
+    write_linked_projects(link_file, &names)?;
+    Ok(true)
+}
+
 /// 从索引中移除无效路径，返回有效路径列表
 fn clean_and_get_valid_paths() -> std::io::Result<Vec<PathBuf>> {
     let mut index = load_local_projects_index();
@@ -140,18 +202,45 @@ pub fn list_project_dirs() -> std::io::Result<Vec<PathBuf>> {
     Ok(project_paths)
 }
 
+/// `.kanban/` 中的所有看板：目录本身（有 `.kanban.toml` 时）在前，
+/// 其后是带有自己 `.kanban.toml` 的子目录（按名称排序）
+pub fn list_local_boards(kanban_dir: &Path) -> Vec<PathBuf> {
+    let mut boards = Vec::new();
+    if kanban_dir.join(".kanban.toml").exists() {
+        boards.push(kanban_dir.to_path_buf());
+    }
+    let mut nested: Vec<PathBuf> = fs::read_dir(kanban_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_nested_board(path))
+        .collect();
+    nested.sort();
+    boards.extend(nested);
+    boards
+}
+
+/// 项目目录中带有自己 `.kanban.toml` 的子目录是另一个看板，不是状态列
+fn is_nested_board(path: &Path) -> bool {
+    path.is_dir()
+        && path.join(".kanban.toml").exists()
+        && !path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
 /// List all local project directories (.kanban if exists)
 /// Returns: current directory's .kanban + all valid paths from index
 pub fn list_local_project_dirs() -> std::io::Result<Vec<PathBuf>> {
     let mut all_paths = Vec::new();
 
     // 1. 优先添加当前目录的本地项目（如果存在）
-    let local_kanban_dir = get_local_kanban_dir();
-    if local_kanban_dir.exists() && local_kanban_dir.join(".kanban.toml").exists() {
-        all_paths.push(local_kanban_dir.clone());
-
-        // 同时确保当前目录在索引中（自动注册）
-        let _ = add_local_project_to_index(&local_kanban_dir);
+    // .kanban/ 中可以有多个看板（.kanban/<名称>/.kanban.toml），各自作为一个本地项目
+    for board in list_local_boards(&get_local_kanban_dir()) {
+        // 同时确保当前目录的看板在索引中（自动注册）
+        let _ = add_local_project_to_index(&board);
+        all_paths.push(board);
     }
 
     // 2. 从索引中加载其他本地项目（自动清理无效路径）
@@ -220,33 +309,74 @@ pub fn load_project_with_type(
     project_path: &Path,
     project_type: ProjectType,
 ) -> Result<Project, String> {
-    // 0. 如果存在 tasks.toml，先迁移到 frontmatter 格式
-    let tasks_toml = project_path.join("tasks.toml");
-    if tasks_toml.exists() {
-        super::task::migrate_metadata_to_frontmatter(project_path)?;
+    let (mut project, config) = load_project_header(project_path, project_type)?;
+
+    // 上次重新编号被中断时先按日志完成，避免新旧 ID 的文件同时出现
+    super::task::resume_renumber(project_path)?;
+
+    let status_dirs = resolve_status_dirs(project_path, &config);
+
+    // Load tasks from all status directories
+    let rules = super::ignore::IgnoreRules::for_project(project_path);
+    for (status, status_dir) in &status_dirs {
+        if status_dir.exists()
+            && let Ok(tasks) = super::task::load_tasks_with_rules(status_dir, status, &rules)
+        {
+            project.tasks.extend(tasks);
+        }
     }
+    project.tasks = dedup_linked_tasks(project.tasks);
 
-    // 1. 扫描实际存在的目录
-    let actual_dirs = scan_status_directories(project_path)?;
+    Ok(project)
+}
 
-    // 2. 加载或创建配置
+/// 只加载项目配置（名称、id、状态列等），不读取任务文件；TUI 启动时先显示项目，
+/// 任务由 IO 线程随后加载
+pub fn load_project_header_with_type(
+    project_path: &Path,
+    project_type: ProjectType,
+) -> Result<Project, String> {
+    load_project_header(project_path, project_type).map(|(project, _)| project)
+}
+
+fn load_project_header(
+    project_path: &Path,
+    project_type: ProjectType,
+) -> Result<(Project, ProjectConfig), String> {
+    // 1. 加载配置
     let mut config = load_project_config(project_path)?;
 
-    // 3. 同步配置：移除不存在的、添加新发现的
-    let config_updated = sync_status_config(&mut config, &actual_dirs);
+    // 2. 补建配置中有、磁盘上缺失的状态目录（手动编辑配置或 git 检出会丢掉空目录）
+    create_missing_status_dirs(project_path, &config);
+
+    // 3. 扫描实际存在的目录，把新发现的目录加入配置（有映射目录的项目不自动发现）
+    let config_updated = if config.has_mapped_statuses() {
+        false
+    } else {
+        let actual_dirs = scan_status_directories(project_path)?;
+        sync_status_config(&mut config, &actual_dirs)
+    };
 
     // 4. 如果配置有更新，保存回文件
     if config_updated {
         save_project_config(project_path, &config)?;
     }
 
+    // 5. 旧项目没有 id：全局项目生成一个写回配置，写入失败（只读目录等）时用路径作为本次运行的 id；
+    //    本地项目的配置通常纳入版本控制，不为此改动它，直接用路径
+    let id = match config.id.clone().filter(|id| !id.is_empty()) {
+        Some(id) => id,
+        None if project_type == ProjectType::Global => reassign_project_id(project_path),
+        None => project_path.to_string_lossy().to_string(),
+    };
+
     let mut statuses = Vec::new();
     for status_name in &config.statuses.order {
         if let Some(status_config) = config.statuses.statuses.get(status_name) {
-            statuses.push(Status::new(
-                status_name.clone(),
-                status_config.display.clone(),
-            ));
+            let mut status = Status::new(status_name.clone(), status_config.display.clone());
+            status.color = status_config.color.clone();
+            status.wip_limit = status_config.wip_limit;
+            statuses.push(status);
         }
     }
 
@@ -255,21 +385,82 @@ pub fn load_project_with_type(
         project_path.to_path_buf(),
         project_type,
     );
+    project.id = id;
     project.statuses = statuses;
     project.project_order = config.project_order;
     project.tags = config.tags.clone();
+    project.fields = config.fields.clone();
+    project.color = config.color.clone();
+    project.group = config.group.clone();
+    project.pane = config.pane.clone();
+    super::journal::remember_project(project_path, &project.id, &project.name);
 
-    // Load tasks from all status directories
-    for status in &project.statuses {
-        let status_dir = project_path.join(&status.name);
-        if status_dir.exists()
-            && let Ok(tasks) = super::task::load_tasks_from_dir(&status_dir, &status.name)
-        {
-            project.tasks.extend(tasks);
+    Ok((project, config))
+}
+
+/// 状态的目录：配置了 `path` 时使用映射的目录（相对项目目录或绝对路径，`~` 展开为主目录），
+/// 否则为项目目录下的同名目录
+pub fn status_dir_in(project_path: &Path, config: &ProjectConfig, status: &str) -> PathBuf {
+    let mapped = config
+        .statuses
+        .statuses
+        .get(status)
+        .and_then(|s| s.path.as_deref());
+    match mapped {
+        Some(path) => match path.strip_prefix("~/") {
+            Some(rest) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(rest),
+            None => project_path.join(path),
+        },
+        None => project_path.join(status),
+    }
+}
+
+/// 读取项目配置后确定状态目录，配置无法读取时使用项目目录下的同名目录
+pub fn status_dir(project_path: &Path, status: &str) -> PathBuf {
+    match load_project_config(project_path) {
+        Ok(config) => status_dir_in(project_path, &config, status),
+        Err(_) => project_path.join(status),
+    }
+}
+
+/// 状态是否映射到项目目录之外（映射的目录属于笔记库，不随状态删除或重命名）
+pub fn is_mapped_status(project_path: &Path, status: &str) -> bool {
+    load_project_config(project_path).is_ok_and(|config| {
+        config
+            .statuses
+            .statuses
+            .get(status)
+            .is_some_and(|s| s.path.is_some())
+    })
+}
+
+/// 按列顺序列出 (状态名, 目录)；通过符号链接指向同一目录的状态只保留第一个，避免任务重复显示
+pub fn resolve_status_dirs(project_path: &Path, config: &ProjectConfig) -> Vec<(String, PathBuf)> {
+    let mut seen = std::collections::HashSet::new();
+    let mut dirs = Vec::new();
+    for status in &config.statuses.order {
+        let dir = status_dir_in(project_path, config, status);
+        let real = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+        if seen.insert(real) {
+            dirs.push((status.clone(), dir));
         }
     }
+    dirs
+}
 
-    Ok(project)
+/// 去掉通过符号链接重复出现的任务文件（同一个真实文件只保留第一次出现）
+fn dedup_linked_tasks(tasks: Vec<Task>) -> Vec<Task> {
+    let mut seen = std::collections::HashSet::new();
+    tasks
+        .into_iter()
+        .filter(|task| {
+            let real = task
+                .file_path
+                .canonicalize()
+                .unwrap_or_else(|_| task.file_path.clone());
+            seen.insert(real)
+        })
+        .collect()
 }
 
 /// 扫描项目目录下的所有状态目录
@@ -287,8 +478,9 @@ fn scan_status_directories(project_path: &Path) -> Result<Vec<String>, String> {
         let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
         let path = entry.path();
 
-        // 只处理目录，排除以 . 开头的目录
+        // 只处理目录，排除以 . 开头的目录和嵌套的看板
         if path.is_dir()
+            && !is_nested_board(&path)
             && let Some(name) = path.file_name()
         {
             let name_str = name.to_string_lossy().to_string();
@@ -304,33 +496,50 @@ fn scan_status_directories(project_path: &Path) -> Result<Vec<String>, String> {
     Ok(dirs)
 }
 
-/// 同步配置文件中的状态定义
-/// 返回是否有更新
-fn sync_status_config(config: &mut ProjectConfig, actual_dirs: &[String]) -> bool {
-    let mut updated = false;
+/// 生成新的项目 ID（16 位十六进制）
+pub fn generate_project_id() -> String {
+    use std::hash::{BuildHasher, Hasher};
 
-    // 1. 移除 order 中不存在的目录
-    let original_len = config.statuses.order.len();
-    config.statuses.order.retain(|s| actual_dirs.contains(s));
-    if config.statuses.order.len() != original_len {
-        updated = true;
-    }
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    // RandomState 每次创建都使用不同的随机种子
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(nanos);
+    format!("{:016x}", hasher.finish())
+}
 
-    // 2. 同时清理 statuses map 中不存在的条目
-    let keys_to_remove: Vec<String> = config
-        .statuses
-        .statuses
-        .keys()
-        .filter(|k| !actual_dirs.contains(k))
-        .cloned()
-        .collect();
+/// 为复制出来、与其他项目 id 重复的项目重新生成 id，写入失败时退回使用路径
+pub fn reassign_project_id(project_path: &Path) -> String {
+    let id = generate_project_id();
+    let saved = load_project_config(project_path).and_then(|mut config| {
+        config.id = Some(id.clone());
+        save_project_config(project_path, &config)
+    });
+    match saved {
+        Ok(()) => id,
+        Err(_) => project_path.to_string_lossy().to_string(),
+    }
+}
 
-    for key in keys_to_remove {
-        config.statuses.statuses.remove(&key);
-        updated = true;
+/// 为配置中缺少目录的状态创建目录
+///
+/// 创建失败（如只读目录）时保留配置，列显示为空，移动任务进来时会再次尝试创建。
+fn create_missing_status_dirs(project_path: &Path, config: &ProjectConfig) {
+    for name in &config.statuses.order {
+        let dir = status_dir_in(project_path, config, name);
+        if !dir.exists() {
+            let _ = fs::create_dir_all(&dir);
+        }
     }
+}
+
+/// 把新发现的目录作为状态加入配置
+/// 返回是否有更新
+fn sync_status_config(config: &mut ProjectConfig, actual_dirs: &[String]) -> bool {
+    let mut updated = false;
 
-    // 3. 添加新发现的目录到 order 末尾
     for dir in actual_dirs {
         if !config.statuses.order.contains(dir) {
             config.statuses.order.push(dir.clone());
@@ -338,6 +547,9 @@ fn sync_status_config(config: &mut ProjectConfig, actual_dirs: &[String]) -> boo
                 dir.clone(),
                 StatusConfig {
                     display: capitalize_first(dir),
+                    color: None,
+                    wip_limit: None,
+                    path: None,
                 },
             );
             updated = true;
@@ -347,6 +559,77 @@ fn sync_status_config(config: &mut ProjectConfig, actual_dirs: &[String]) -> boo
     updated
 }
 
+/// 新任务的默认状态和优先级（`default_status` / `default_priority`）
+///
+/// 项目 `.kanban.toml` 中的设置优先于全局配置；项目中不存在的状态和无效的优先级被忽略。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewTaskDefaults {
+    /// 未设置时由调用方决定（TUI 使用当前列，CLI 使用 todo）
+    pub status: Option<String>,
+    pub priority: Option<String>,
+}
+
+impl NewTaskDefaults {
+    pub fn resolve(project: &ProjectConfig, global: &crate::config::Config) -> Self {
+        let status = [&project.default_status, &global.default_status]
+            .into_iter()
+            .flatten()
+            .find(|status| project.statuses.order.contains(status))
+            .cloned();
+        let priority = project
+            .default_priority
+            .as_ref()
+            .or(global.default_priority.as_ref())
+            .filter(|p| matches!(p.as_str(), "high" | "medium" | "low"))
+            .cloned();
+        Self { status, priority }
+    }
+
+    /// 读取项目配置后计算默认值，读取失败时没有默认值
+    pub fn load(project_path: &Path, global: &crate::config::Config) -> Self {
+        load_project_config(project_path)
+            .map(|config| Self::resolve(&config, global))
+            .unwrap_or_default()
+    }
+}
+
+/// `hxk doctor` 的状态目录检查结果
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct StatusDirReport {
+    /// 配置中有、磁盘上没有的状态目录（下次加载时自动创建）
+    pub missing: Vec<String>,
+    /// 磁盘上有、配置中没有的目录（下次加载时作为新状态加入配置）
+    pub unlisted: Vec<String>,
+}
+
+impl StatusDirReport {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.unlisted.is_empty()
+    }
+}
+
+/// 只读检查状态目录与配置是否一致，不修改任何文件
+pub fn check_status_dirs(project_path: &Path) -> Result<StatusDirReport, String> {
+    let config = load_project_config(project_path)?;
+    let missing = config
+        .statuses
+        .order
+        .iter()
+        .filter(|name| !status_dir_in(project_path, &config, name).is_dir())
+        .cloned()
+        .collect();
+    // 有映射目录的项目不自动发现新状态，其他目录不算问题
+    let unlisted = if config.has_mapped_statuses() {
+        Vec::new()
+    } else {
+        scan_status_directories(project_path)?
+            .into_iter()
+            .filter(|dir| !config.statuses.order.contains(dir))
+            .collect()
+    };
+    Ok(StatusDirReport { missing, unlisted })
+}
+
 /// 首字母大写
 fn capitalize_first(s: &str) -> String {
     let mut chars = s.chars();
@@ -364,10 +647,16 @@ fn capitalize_first(s: &str) -> String {
 pub fn save_project_config(project_path: &Path, config: &ProjectConfig) -> Result<(), String> {
     let config_path = project_path.join(".kanban.toml");
 
-    let content =
-        toml::to_string_pretty(config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    // 保留用户手写的注释和本版本不认识的键
+    let existing = fs::read_to_string(&config_path).ok();
+    let content = super::toml_preserve::to_string_preserving(config, existing.as_deref())
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(&config_path, content).map_err(|e| format!("Failed to write config: {}", e))?;
+    super::atomic::write_atomic(&config_path, content)
+        .map_err(|e| format!("Failed to write config: {}", e))?;
+    if let Some(id) = &config.id {
+        super::journal::remember_project(project_path, id, &config.name);
+    }
 
     Ok(())
 }
@@ -384,79 +673,124 @@ pub fn update_project_metadata(
     Ok(config)
 }
 
-/// Create a new project
-pub fn create_project(name: &str) -> Result<PathBuf, String> {
-    let project_dir = get_projects_dir().join(name);
-
-    if project_dir.exists() {
-        return Err(format!("Project '{}' already exists", name));
-    }
+/// 新建项目时的默认状态集合
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Workflow {
+    /// todo / doing / done
+    #[default]
+    Basic,
+    /// backlog / todo / doing / review / blocked / done（done 保持在最后一列）
+    Dev,
+}
 
-    // Create project directory
-    fs::create_dir_all(&project_dir)
-        .map_err(|e| format!("Failed to create project directory: {}", e))?;
+impl Workflow {
+    pub const ALL: [Workflow; 2] = [Workflow::Basic, Workflow::Dev];
 
-    // Create default statuses directories
-    let default_statuses = vec!["todo", "doing", "done"];
-    for status in &default_statuses {
-        fs::create_dir_all(project_dir.join(status))
-            .map_err(|e| format!("Failed to create status directory: {}", e))?;
+    /// 根据名称解析（CLI `--workflow` 参数）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "basic" | "default" => Some(Workflow::Basic),
+            "dev" => Some(Workflow::Dev),
+            _ => None,
+        }
     }
 
-    // Create .kanban.toml with default configuration
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-
-    let config = format!(
-        concat!(
-            "name = \"{}\"\n",
-            "created = \"{}\"\n",
-            "tags = []\n",
-            "\n",
-            "[statuses]\n",
-            "order = [\"todo\", \"doing\", \"done\"]\n",
-            "\n",
-            "[statuses.todo]\n",
-            "display = \"Todo\"\n",
-            "\n",
-            "[statuses.doing]\n",
-            "display = \"Doing\"\n",
-            "\n",
-            "[statuses.done]\n",
-            "display = \"Done\"\n"
-        ),
-        name, timestamp
-    );
+    pub fn name(self) -> &'static str {
+        match self {
+            Workflow::Basic => "basic",
+            Workflow::Dev => "dev",
+        }
+    }
 
-    fs::write(project_dir.join(".kanban.toml"), config)
-        .map_err(|e| format!("Failed to write config: {}", e))?;
+    /// 状态列表：(目录名, 显示名, 颜色)
+    pub fn statuses(self) -> &'static [(&'static str, &'static str, Option<&'static str>)] {
+        match self {
+            Workflow::Basic => &[
+                ("todo", "Todo", None),
+                ("doing", "Doing", None),
+                ("done", "Done", None),
+            ],
+            Workflow::Dev => &[
+                ("backlog", "Backlog", Some("#7b88a1")),
+                ("todo", "Todo", Some("#88c0d0")),
+                ("doing", "Doing", Some("#ebcb8b")),
+                ("review", "Review", Some("#b48ead")),
+                ("blocked", "Blocked", Some("#bf616a")),
+                ("done", "Done", Some("#a3be8c")),
+            ],
+        }
+    }
 
-    // Create empty tasks.toml for new format (metadata separated)
-    // 使用空字符串而不是 "[tasks]"，因为 TasksConfig 使用 #[serde(flatten)]
-    fs::write(project_dir.join("tasks.toml"), "")
-        .map_err(|e| format!("Failed to create tasks.toml: {}", e))?;
+    /// 切换到下一个工作流（新建项目对话框中 Tab 切换）
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|w| *w == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
 
-    Ok(project_dir)
+/// 生成新项目的 .kanban.toml 内容
+fn default_project_toml(name: &str, timestamp: u64, workflow: Workflow) -> Result<String, String> {
+    let statuses = workflow.statuses();
+    let config = ProjectConfig {
+        name: name.to_string(),
+        description: None,
+        color: None,
+        group: None,
+        pane: None,
+        created: timestamp.to_string(),
+        id: Some(generate_project_id()),
+        format_version: Some(super::migration::CURRENT_FORMAT_VERSION),
+        project_order: None,
+        tags: Vec::new(),
+        fields: Vec::new(),
+        ignore: Vec::new(),
+        automations: Vec::new(),
+        default_status: None,
+        default_priority: None,
+        statuses: crate::models::project::StatusesConfig {
+            order: statuses
+                .iter()
+                .map(|(status, _, _)| status.to_string())
+                .collect(),
+            statuses: statuses
+                .iter()
+                .map(|(status, display, color)| {
+                    (
+                        status.to_string(),
+                        StatusConfig {
+                            display: display.to_string(),
+                            color: color.map(str::to_string),
+                            wip_limit: None,
+                            path: None,
+                        },
+                    )
+                })
+                .collect(),
+        },
+    };
+    toml::to_string_pretty(&config).map_err(|e| format!("Failed to serialize config: {}", e))
 }
 
-/// Create a new local project in .kanban directory
-pub fn create_local_project(name: &str) -> Result<PathBuf, String> {
-    let project_dir = get_local_kanban_dir();
+/// Create a new global project with the statuses of the given workflow
+pub fn create_project(name: &str, workflow: Workflow) -> Result<PathBuf, String> {
+    let project_dir = get_projects_dir().join(name);
 
-    // Check if .kanban already exists
     if project_dir.exists() {
-        return Err("本地看板已存在，一个目录只能有一个本地项目".to_string());
+        return Err(format!("Project '{}' already exists", name));
     }
 
-    // Create .kanban directory
-    fs::create_dir_all(&project_dir)
-        .map_err(|e| format!("Failed to create .kanban directory: {}", e))?;
+    create_project_in(&project_dir, name, workflow)?;
+    Ok(project_dir)
+}
 
-    // Create default statuses directories
-    let default_statuses = vec!["todo", "doing", "done"];
-    for status in &default_statuses {
+/// Create a project in the given directory (e.g. the `:tutor` practice project)
+pub fn create_project_in(project_dir: &Path, name: &str, workflow: Workflow) -> Result<(), String> {
+    // Create project directory
+    fs::create_dir_all(project_dir)
+        .map_err(|e| format!("Failed to create project directory: {}", e))?;
+
+    // Create statuses directories for the chosen workflow
+    for (status, _, _) in workflow.statuses() {
         fs::create_dir_all(project_dir.join(status))
             .map_err(|e| format!("Failed to create status directory: {}", e))?;
     }
@@ -467,34 +801,15 @@ pub fn create_local_project(name: &str) -> Result<PathBuf, String> {
         .unwrap()
         .as_secs();
 
-    let config = format!(
-        concat!(
-            "name = \"{}\"\n",
-            "created = \"{}\"\n",
-            "tags = []\n",
-            "\n",
-            "[statuses]\n",
-            "order = [\"todo\", \"doing\", \"done\"]\n",
-            "\n",
-            "[statuses.todo]\n",
-            "display = \"Todo\"\n",
-            "\n",
-            "[statuses.doing]\n",
-            "display = \"Doing\"\n",
-            "\n",
-            "[statuses.done]\n",
-            "display = \"Done\"\n"
-        ),
-        name, timestamp
-    );
+    let config = default_project_toml(name, timestamp, workflow)?;
 
     fs::write(project_dir.join(".kanban.toml"), config)
-        .map_err(|e| format!("Failed to write config: {}", e))?;
+        .map_err(|e| format!("Failed to write config: {}", e))
+}
 
-    // Create empty tasks.toml for new format (metadata separated)
-    // 使用空字符串而不是 "[tasks]"，因为 TasksConfig 使用 #[serde(flatten)]
-    fs::write(project_dir.join("tasks.toml"), "")
-        .map_err(|e| format!("Failed to create tasks.toml: {}", e))?;
+/// Create a new local project in .kanban directory
+pub fn create_local_project(name: &str, workflow: Workflow) -> Result<PathBuf, String> {
+    let project_dir = create_local_board(&get_local_kanban_dir(), name, workflow)?;
 
     // 自动将新创建的本地项目添加到索引
     let _ = add_local_project_to_index(&project_dir);
@@ -502,6 +817,33 @@ pub fn create_local_project(name: &str) -> Result<PathBuf, String> {
     Ok(project_dir)
 }
 
+/// 在 `.kanban/` 中创建看板：第一个看板直接使用 `.kanban/`，之后的看板放在
+/// `.kanban/<名称>/`（例如 monorepo 中每个团队一个看板）
+fn create_local_board(
+    kanban_dir: &Path,
+    name: &str,
+    workflow: Workflow,
+) -> Result<PathBuf, String> {
+    if list_local_boards(kanban_dir).is_empty() {
+        create_project_in(kanban_dir, name, workflow)?;
+        return Ok(kanban_dir.to_path_buf());
+    }
+
+    let dir_name = name.trim().replace(['/', '\\'], "-");
+    if dir_name.is_empty() || dir_name.starts_with('.') {
+        return Err(format!("看板名称无效: {}", name));
+    }
+    let project_dir = kanban_dir.join(&dir_name);
+    if project_dir.exists() {
+        return Err(format!(
+            "本地看板目录 .kanban/{} 已存在（已有看板或状态列），请换一个名称",
+            dir_name
+        ));
+    }
+    create_project_in(&project_dir, name, workflow)?;
+    Ok(project_dir)
+}
+
 /// Rename a project
 #[allow(dead_code)]
 pub fn rename_project(old_name: &str, new_name: &str) -> Result<(), String> {
@@ -522,20 +864,9 @@ pub fn rename_project(old_name: &str, new_name: &str) -> Result<(), String> {
         .map_err(|e| format!("Failed to rename project directory: {}", e))?;
 
     // Update the name in .kanban.toml
-    let config_path = new_path.join(".kanban.toml");
-    let content =
-        fs::read_to_string(&config_path).map_err(|e| format!("Failed to read config: {}", e))?;
-
-    // Parse and update the config
-    let mut config: ProjectConfig =
-        toml::from_str(&content).map_err(|e| format!("Failed to parse TOML: {}", e))?;
-
+    let mut config = load_project_config(&new_path)?;
     config.name = new_name.to_string();
-
-    let new_content =
-        toml::to_string(&config).map_err(|e| format!("Failed to serialize TOML: {}", e))?;
-
-    fs::write(config_path, new_content).map_err(|e| format!("Failed to write config: {}", e))?;
+    save_project_config(&new_path, &config)?;
 
     Ok(())
 }
@@ -547,6 +878,20 @@ pub fn delete_project_by_path(project_path: &std::path::Path) -> Result<(), Stri
         return Err(format!("项目路径 '{}' 不存在", project_path.display()));
     }
 
+    // .kanban/ 本身是看板时，其中还可能有其他看板，不能连同删除
+    let nested: Vec<String> = list_local_boards(project_path)
+        .iter()
+        .filter(|board| board.as_path() != project_path)
+        .filter_map(|board| board.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect();
+    if !nested.is_empty() {
+        return Err(format!(
+            "项目目录中还有其他看板（{}），请先删除它们",
+            nested.join(", ")
+        ));
+    }
+
     // 删除整个项目目录及其所有内容
     std::fs::remove_dir_all(project_path).map_err(|e| format!("删除项目目录失败: {}", e))?;
 
@@ -587,20 +932,18 @@ TUI 中按 `Space` → `p` → `i` 复制项目信息（类型、名称、看板
 
 ## 存储格式
 
-**tasks.toml**（元数据）：
-```toml
-[1]
+**{status}/{id}.md**（TOML frontmatter + 正文）：
+```markdown
++++
 id = 1
 order = 1000
-title = "任务标题"
-status = "todo"
 created = "1234567890"
 priority = "high"  # high/medium/low/none
 tags = ["feature", "urgent"]
-```
++++
+
+# 任务标题
 
-**{status}/{id}.md**（纯内容）：
-```markdown
 任务描述内容
 
 ## 子任务
@@ -760,16 +1103,58 @@ pub fn ensure_global_ai_config() -> Result<(), String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{load_project_config, save_project_config};
+    use super::{
+        LINK_FILE, NewTaskDefaults, Workflow, check_status_dirs, create_local_board,
+        default_project_toml, delete_project_by_path, link_project, list_local_boards,
+        load_project, load_project_config, read_linked_projects, save_project_config,
+        unlink_project,
+    };
+    use crate::models::field::FieldKind;
     use crate::models::project::StatusesConfig;
     use crate::models::{ProjectConfig, StatusConfig};
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
     use tempfile::TempDir;
 
     fn write_config(dir: &TempDir, content: &str) {
         std::fs::write(dir.path().join(".kanban.toml"), content).unwrap();
     }
 
+    #[test]
+    fn dev_workflow_config_lists_statuses_with_colors() {
+        let content = default_project_toml("web", 0, Workflow::Dev).unwrap();
+        let config: ProjectConfig = toml::from_str(&content).unwrap();
+
+        assert_eq!(
+            config.statuses.order,
+            vec!["backlog", "todo", "doing", "review", "blocked", "done"]
+        );
+        assert_eq!(
+            config.statuses.statuses["blocked"].color.as_deref(),
+            Some("#bf616a")
+        );
+
+        let basic: ProjectConfig =
+            toml::from_str(&default_project_toml("web", 0, Workflow::Basic).unwrap()).unwrap();
+        assert_eq!(basic.statuses.order, vec!["todo", "doing", "done"]);
+        assert!(basic.statuses.statuses["todo"].color.is_none());
+    }
+
+    #[test]
+    fn link_file_adds_and_removes_project_names() {
+        let dir = TempDir::new().unwrap();
+        let link_file = dir.path().join(LINK_FILE);
+
+        assert!(link_project(&link_file, "work").unwrap());
+        assert!(!link_project(&link_file, "work").unwrap());
+        assert!(link_project(&link_file, "notes").unwrap());
+        assert_eq!(read_linked_projects(&link_file), vec!["work", "notes"]);
+
+        assert!(unlink_project(&link_file, "work").unwrap());
+        assert!(!unlink_project(&link_file, "missing").unwrap());
+        assert!(unlink_project(&link_file, "notes").unwrap());
+        assert!(!link_file.exists());
+    }
+
     #[test]
     fn old_project_config_without_metadata_loads() {
         let dir = TempDir::new().unwrap();
@@ -793,21 +1178,322 @@ display = "Todo"
         assert!(config.tags.is_empty());
     }
 
+    #[test]
+    fn missing_status_dirs_are_created_and_unlisted_dirs_reported() {
+        let dir = TempDir::new().unwrap();
+        write_config(
+            &dir,
+            "name = \"web\"\ncreated = \"1\"\n\n[statuses]\norder = [\"todo\", \"review\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n\n[statuses.review]\ndisplay = \"Code review\"\ncolor = \"#b48ead\"\n",
+        );
+        std::fs::create_dir(dir.path().join("todo")).unwrap();
+        std::fs::create_dir(dir.path().join("assets")).unwrap();
+
+        let report = check_status_dirs(dir.path()).unwrap();
+        assert_eq!(report.missing, vec!["review"]);
+        assert_eq!(report.unlisted, vec!["assets"]);
+        assert!(!dir.path().join("review").exists());
+
+        let project = load_project(dir.path()).unwrap();
+        assert!(dir.path().join("review").is_dir());
+        let review = project
+            .statuses
+            .iter()
+            .find(|s| s.name == "review")
+            .unwrap();
+        assert_eq!(review.display, "Code review");
+        assert_eq!(review.color.as_deref(), Some("#b48ead"));
+        assert!(check_status_dirs(dir.path()).unwrap().is_ok());
+    }
+
+    #[test]
+    fn local_kanban_dir_holds_multiple_boards() {
+        let dir = TempDir::new().unwrap();
+        let kanban = dir.path().join(".kanban");
+
+        assert_eq!(
+            create_local_board(&kanban, "repo", Workflow::Basic).unwrap(),
+            kanban
+        );
+        let web = create_local_board(&kanban, "web", Workflow::Basic).unwrap();
+        let api = create_local_board(&kanban, "api", Workflow::Dev).unwrap();
+        assert_eq!(web, kanban.join("web"));
+        assert_eq!(list_local_boards(&kanban), vec![kanban.clone(), api, web]);
+        // 与已有看板或状态列重名
+        assert!(create_local_board(&kanban, "todo", Workflow::Basic).is_err());
+        assert!(create_local_board(&kanban, "web", Workflow::Basic).is_err());
+
+        // 嵌套的看板不会被当作根看板的新状态列
+        let root = load_project(&kanban).unwrap();
+        let statuses: Vec<&str> = root.statuses.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(statuses, vec!["todo", "doing", "done"]);
+        assert_eq!(load_project(&kanban.join("web")).unwrap().name, "web");
+
+        // 根看板中还有其他看板时不能整体删除
+        assert!(delete_project_by_path(&kanban).is_err());
+        assert!(kanban.join("api").exists());
+    }
+
+    #[test]
+    fn mapped_status_dirs_share_notes_with_a_vault() {
+        let vault = TempDir::new().unwrap();
+        write_config(
+            &vault,
+            "name = \"notes\"\ncreated = \"1\"\n\n[statuses]\norder = [\"todo\", \"done\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\npath = \"Projects/Todo\"\n\n[statuses.done]\ndisplay = \"Done\"\npath = \"Projects/Done\"\n",
+        );
+        std::fs::create_dir_all(vault.path().join("Daily")).unwrap();
+        std::fs::create_dir_all(vault.path().join("Projects/Todo")).unwrap();
+        let note = vault.path().join("Projects/Todo/Fix login.md");
+        std::fs::write(&note, "# Fix login\n\nid: 1\n\nSee [[Auth]].\n").unwrap();
+
+        let project = load_project(vault.path()).unwrap();
+        let names: Vec<&str> = project.statuses.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["todo", "done"]);
+        assert!(vault.path().join("Projects/Done").is_dir());
+        assert!(check_status_dirs(vault.path()).unwrap().is_ok());
+
+        // 移动后保留笔记的文件名，正文不变
+        let mut task = project.tasks[0].clone();
+        crate::fs::move_task(vault.path(), &mut task, "done").unwrap();
+        let moved = vault.path().join("Projects/Done/Fix login.md");
+        assert_eq!(task.file_path, moved);
+        assert!(!note.exists());
+        assert!(
+            std::fs::read_to_string(&moved)
+                .unwrap()
+                .contains("See [[Auth]].")
+        );
+        assert_eq!(crate::fs::get_next_task_id(vault.path()).unwrap(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_tasks_load_once_and_stay_linked_when_moved() {
+        let dir = TempDir::new().unwrap();
+        let elsewhere = TempDir::new().unwrap();
+        write_config(
+            &dir,
+            "name = \"web\"\ncreated = \"1\"\n\n[statuses]\norder = [\"todo\", \"done\", \"alias\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n\n[statuses.done]\ndisplay = \"Done\"\n\n[statuses.alias]\ndisplay = \"Alias\"\n",
+        );
+        std::fs::create_dir(dir.path().join("todo")).unwrap();
+        std::fs::create_dir(dir.path().join("done")).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("todo"), dir.path().join("alias")).unwrap();
+        let target = elsewhere.path().join("note.md");
+        std::fs::write(&target, "# Linked\n\nid: 1\n").unwrap();
+        std::os::unix::fs::symlink(&target, dir.path().join("todo/1.md")).unwrap();
+
+        let project = load_project(dir.path()).unwrap();
+        assert_eq!(project.tasks.len(), 1);
+
+        let mut task = project.tasks[0].clone();
+        task.title = "Linked and moved".to_string();
+        crate::fs::move_task(dir.path(), &mut task, "done").unwrap();
+        let link = dir.path().join("done/1.md");
+        assert!(
+            std::fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert!(
+            std::fs::read_to_string(&target)
+                .unwrap()
+                .contains("Linked and moved")
+        );
+    }
+
+    #[test]
+    fn new_task_defaults_prefer_project_settings() {
+        let global = crate::config::Config {
+            default_status: Some("backlog".to_string()),
+            default_priority: Some("low".to_string()),
+            ..Default::default()
+        };
+        let mut project: ProjectConfig =
+            toml::from_str(&default_project_toml("web", 0, Workflow::Basic).unwrap()).unwrap();
+
+        // 项目中没有 backlog 状态，忽略全局的默认状态
+        assert_eq!(
+            NewTaskDefaults::resolve(&project, &global),
+            NewTaskDefaults {
+                status: None,
+                priority: Some("low".to_string()),
+            }
+        );
+
+        project.default_status = Some("doing".to_string());
+        project.default_priority = Some("none".to_string());
+        assert_eq!(
+            NewTaskDefaults::resolve(&project, &global),
+            NewTaskDefaults {
+                status: Some("doing".to_string()),
+                priority: None,
+            }
+        );
+    }
+
+    #[test]
+    fn project_id_is_persisted_and_survives_renames() {
+        let root = TempDir::new().unwrap();
+        let dir = root.path().join("old-dir");
+        std::fs::create_dir_all(dir.join("todo")).unwrap();
+        std::fs::write(
+            dir.join(".kanban.toml"),
+            "name = \"legacy\"\ncreated = \"1\"\n\n[statuses]\norder = [\"todo\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n",
+        )
+        .unwrap();
+
+        let first = load_project(&dir).unwrap();
+        assert_eq!(first.id.len(), 16);
+        assert_eq!(
+            load_project_config(&dir).unwrap().id,
+            Some(first.id.clone())
+        );
+
+        // 手动改目录名和配置中的项目名后 ID 不变
+        let renamed = root.path().join("new-dir");
+        std::fs::rename(&dir, &renamed).unwrap();
+        let mut config = load_project_config(&renamed).unwrap();
+        config.name = "renamed".to_string();
+        save_project_config(&renamed, &config).unwrap();
+
+        let second = load_project(&renamed).unwrap();
+        assert_eq!(second.name, "renamed");
+        assert_eq!(second.id, first.id);
+    }
+
+    #[test]
+    fn legacy_project_without_id_loads_when_config_is_not_written() {
+        let root = TempDir::new().unwrap();
+        let config = "name = \"legacy\"\ncreated = \"1\"\n\n[statuses]\norder = [\"todo\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n";
+
+        // 本地项目：不改动配置文件，用路径作为 id
+        let local = root.path().join("repo").join(".kanban");
+        std::fs::create_dir_all(local.join("todo")).unwrap();
+        std::fs::write(local.join(".kanban.toml"), config).unwrap();
+        let project =
+            super::load_project_with_type(&local, crate::models::ProjectType::Local).unwrap();
+        assert_eq!(project.id, local.to_string_lossy());
+        assert_eq!(
+            std::fs::read_to_string(local.join(".kanban.toml")).unwrap(),
+            config
+        );
+
+        // 全局项目写不进配置时仍然加载
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let global = root.path().join("shared");
+            std::fs::create_dir_all(global.join("todo")).unwrap();
+            std::fs::write(global.join(".kanban.toml"), config).unwrap();
+            std::fs::set_permissions(&global, std::fs::Permissions::from_mode(0o555)).unwrap();
+            let readonly = std::fs::write(global.join("probe"), "").is_err();
+            let project =
+                super::load_project_with_type(&global, crate::models::ProjectType::Global);
+            std::fs::set_permissions(&global, std::fs::Permissions::from_mode(0o755)).unwrap();
+            let project = project.unwrap();
+            if readonly {
+                assert_eq!(project.id, global.to_string_lossy());
+            }
+        }
+    }
+
+    #[test]
+    fn custom_fields_load_from_config_and_round_trip() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("todo")).unwrap();
+        write_config(
+            &dir,
+            r#"name = "fields"
+created = "1"
+
+[[fields]]
+name = "estimate"
+type = "number"
+display = "估算"
+
+[[fields]]
+name = "customer"
+
+[statuses]
+order = ["todo"]
+
+[statuses.todo]
+display = "Todo"
+"#,
+        );
+
+        let project = load_project(dir.path()).unwrap();
+        assert_eq!(project.fields.len(), 2);
+        assert_eq!(project.fields[0].kind, FieldKind::Number);
+        assert_eq!(project.fields[0].label(), "估算");
+        assert_eq!(project.fields[1].kind, FieldKind::Text);
+
+        // 保存配置（如项目改名）时字段定义保持不变
+        let config = load_project_config(dir.path()).unwrap();
+        save_project_config(dir.path(), &config).unwrap();
+        assert_eq!(
+            load_project_config(dir.path()).unwrap().fields,
+            config.fields
+        );
+    }
+
+    #[test]
+    fn ignored_files_in_status_directories_are_not_loaded() {
+        let dir = TempDir::new().unwrap();
+        let todo = dir.path().join("todo");
+        std::fs::create_dir_all(&todo).unwrap();
+        write_config(
+            &dir,
+            r#"name = "ignore"
+created = "1"
+ignore = ["*.draft.md", "todo/NOTES.md"]
+
+[statuses]
+order = ["todo"]
+
+[statuses.todo]
+display = "Todo"
+"#,
+        );
+        std::fs::write(todo.join("1.md"), "+++\nid = 1\n+++\n\n# Real task\n").unwrap();
+        std::fs::write(todo.join("2.draft.md"), "+++\nid = 2\n+++\n\n# Draft\n").unwrap();
+        std::fs::write(todo.join("NOTES.md"), "scratch notes without frontmatter").unwrap();
+        std::fs::write(todo.join(".DS_Store"), "").unwrap();
+
+        let project = load_project(dir.path()).unwrap();
+        let titles: Vec<&str> = project.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Real task"]);
+    }
+
     #[test]
     fn project_config_round_trips_metadata() {
         let dir = TempDir::new().unwrap();
-        let mut statuses = HashMap::new();
+        let mut statuses = BTreeMap::new();
         statuses.insert(
             "todo".to_string(),
             StatusConfig {
                 display: "Todo".to_string(),
+                color: None,
+                wip_limit: None,
+                path: None,
             },
         );
         let config = ProjectConfig {
             name: "ordered".to_string(),
+            description: None,
+            color: None,
+            group: None,
+            pane: None,
             created: "2".to_string(),
+            id: None,
+            format_version: None,
             project_order: Some(42),
             tags: vec!["urgent".to_string(), "client".to_string()],
+            fields: Vec::new(),
+            ignore: Vec::new(),
+            automations: Vec::new(),
+            default_status: None,
+            default_priority: None,
             statuses: StatusesConfig {
                 order: vec!["todo".to_string()],
                 statuses,
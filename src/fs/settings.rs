@@ -0,0 +1,321 @@
+/// 项目设置表单（`:project-settings`）
+///
+/// 设置以文本表单的形式在对话框中编辑，每行一项：
+///
+/// ```text
+/// name = web
+/// description = 前端项目
+/// color = #88c0d0
+/// group = work
+/// pane = right
+/// status = todo | Todo | |
+/// status = doing | Doing | #ebcb8b | 3
+/// ```
+///
+/// `color` 是项目标签颜色、`group` 是项目选择器中的分组、`pane` 是偏好的面板位置（留空表示不设置）；`status` 行依次为目录名、显示名、颜色和 WIP 上限，行的顺序就是列的顺序；
+/// 新增一行会创建状态目录，删除一行会删除（空的）状态目录。保存时修改
+/// `ProjectConfig` 后整体序列化写回 `.kanban.toml`。
+use super::status::{validate_display_name, validate_status_name};
+use crate::models::project::StatusesConfig;
+use crate::models::{Status, StatusConfig};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectSettings {
+    pub name: String,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    pub group: Option<String>,
+    pub pane: Option<String>,
+    pub statuses: Vec<Status>,
+}
+
+impl ProjectSettings {
+    /// 从项目的 `.kanban.toml` 读取当前设置
+    pub fn load(project_path: &Path) -> Result<Self, String> {
+        let config = super::load_project_config(project_path)?;
+        let statuses = config
+            .statuses
+            .order
+            .iter()
+            .filter_map(|name| {
+                let status_config = config.statuses.statuses.get(name)?;
+                Some(Status {
+                    name: name.clone(),
+                    display: status_config.display.clone(),
+                    color: status_config.color.clone(),
+                    wip_limit: status_config.wip_limit,
+                })
+            })
+            .collect();
+        Ok(Self {
+            name: config.name,
+            description: config.description,
+            color: config.color,
+            group: config.group,
+            pane: config.pane,
+            statuses,
+        })
+    }
+
+    /// 生成对话框中编辑的文本
+    pub fn to_form(&self) -> String {
+        let mut lines = vec![
+            format!("name = {}", self.name),
+            format!(
+                "description = {}",
+                self.description.as_deref().unwrap_or("")
+            ),
+            format!("color = {}", self.color.as_deref().unwrap_or("")),
+            format!("group = {}", self.group.as_deref().unwrap_or("")),
+            format!("pane = {}", self.pane.as_deref().unwrap_or("")),
+        ];
+        for status in &self.statuses {
+            lines.push(format!(
+                "status = {} | {} | {} | {}",
+                status.name,
+                status.display,
+                status.color.as_deref().unwrap_or(""),
+                status
+                    .wip_limit
+                    .map(|limit| limit.to_string())
+                    .unwrap_or_default()
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// 解析编辑后的文本，空行和 `#` 开头的行忽略
+    pub fn parse_form(input: &str) -> Result<Self, String> {
+        let mut name = None;
+        let mut description = None;
+        let mut color = None;
+        let mut group = None;
+        let mut pane = None;
+        let mut statuses: Vec<Status> = Vec::new();
+
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("无法解析: {}（应为 键 = 值）", line));
+            };
+            let value = value.trim();
+            match key.trim() {
+                "name" => name = Some(value.to_string()),
+                "description" => {
+                    description = (!value.is_empty()).then(|| value.to_string());
+                }
+                "color" => color = (!value.is_empty()).then(|| value.to_string()),
+                "group" => group = (!value.is_empty()).then(|| value.to_string()),
+                "pane" if value.is_empty() => pane = None,
+                "pane" => {
+                    if crate::ui::layout::Direction::from_name(value).is_none() {
+                        return Err(format!(
+                            "面板位置无效: {}（可用 left、right、top、bottom）",
+                            value
+                        ));
+                    }
+                    pane = Some(value.to_lowercase());
+                }
+                "status" => {
+                    let status = parse_status(value)?;
+                    validate_status_name(&status.name, &statuses)?;
+                    statuses.push(status);
+                }
+                other => return Err(format!("未知的设置项: {}", other)),
+            }
+        }
+
+        let name = name.filter(|name| !name.is_empty());
+        let Some(name) = name else {
+            return Err("项目名称不能为空".to_string());
+        };
+        if statuses.is_empty() {
+            return Err("至少需要一个状态".to_string());
+        }
+        Ok(Self {
+            name,
+            description,
+            color,
+            group,
+            pane,
+            statuses,
+        })
+    }
+}
+
+/// 解析 `目录名 | 显示名 | 颜色 | WIP 上限`，显示名缺省时使用目录名
+fn parse_status(value: &str) -> Result<Status, String> {
+    let mut parts = value.split('|').map(str::trim);
+    let name = parts.next().unwrap_or("").to_string();
+    let display = match parts.next() {
+        Some(display) if !display.is_empty() => display.to_string(),
+        _ => name.clone(),
+    };
+    validate_display_name(&display)?;
+    let color = parts
+        .next()
+        .filter(|color| !color.is_empty())
+        .map(str::to_string);
+    let wip_limit = match parts.next() {
+        Some(limit) if !limit.is_empty() => match limit.parse::<usize>() {
+            Ok(limit) if limit > 0 => Some(limit),
+            _ => return Err(format!("状态 {} 的 WIP 上限无效: {}", name, limit)),
+        },
+        _ => None,
+    };
+    Ok(Status {
+        name,
+        display,
+        color,
+        wip_limit,
+    })
+}
+
+/// 保存设置（不含项目名称，改名需要同时处理目录，由调用方负责）
+///
+/// 新增的状态创建目录；删除的状态要求目录为空，否则不做任何修改并返回错误。
+pub fn apply_project_settings(
+    project_path: &Path,
+    settings: &ProjectSettings,
+) -> Result<(), String> {
+    let mut config = super::load_project_config(project_path)?;
+    let is_kept = |name: &String| settings.statuses.iter().any(|s| &s.name == name);
+    let removed: Vec<&String> = config
+        .statuses
+        .order
+        .iter()
+        .filter(|name| !is_kept(name))
+        .collect();
+
+    for name in &removed {
+        let dir = super::project::status_dir_in(project_path, &config, name);
+        let is_empty = fs::read_dir(&dir)
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(true);
+        if !is_empty {
+            return Err(format!("状态「{}」的目录不为空，不能删除", name));
+        }
+    }
+    for name in removed {
+        let dir = super::project::status_dir_in(project_path, &config, name);
+        if dir.exists() {
+            fs::remove_dir(&dir).map_err(|e| format!("删除目录失败: {}", e))?;
+        }
+    }
+    for status in &settings.statuses {
+        fs::create_dir_all(super::project::status_dir_in(
+            project_path,
+            &config,
+            &status.name,
+        ))
+        .map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+
+    config.description = settings.description.clone();
+    config.color = settings.color.clone();
+    config.group = settings.group.clone();
+    config.pane = settings.pane.clone();
+    config.statuses = StatusesConfig {
+        order: settings.statuses.iter().map(|s| s.name.clone()).collect(),
+        statuses: settings
+            .statuses
+            .iter()
+            .map(|status| {
+                (
+                    status.name.clone(),
+                    StatusConfig {
+                        display: status.display.clone(),
+                        color: status.color.clone(),
+                        wip_limit: status.wip_limit,
+                        path: config
+                            .statuses
+                            .statuses
+                            .get(&status.name)
+                            .and_then(|old| old.path.clone()),
+                    },
+                )
+            })
+            .collect(),
+    };
+    super::save_project_config(project_path, &config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join(".kanban.toml"),
+            "name = \"web\"\ncreated = \"0\"\n\n[statuses]\norder = [\"todo\", \"doing\", \"done\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n\n[statuses.doing]\ndisplay = \"Doing\"\n\n[statuses.done]\ndisplay = \"Done\"\n",
+        )
+        .unwrap();
+        for status in ["todo", "doing", "done"] {
+            std::fs::create_dir(dir.path().join(status)).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn form_round_trips_and_validates() {
+        let settings = ProjectSettings::parse_form(
+            "# 注释\nname = web\ndescription = 前端\ncolor = magenta\ngroup = work\npane = Right\nstatus = todo | 待办\nstatus = doing | Doing | #ebcb8b | 3\n",
+        )
+        .unwrap();
+        assert_eq!(settings.description.as_deref(), Some("前端"));
+        assert_eq!(settings.color.as_deref(), Some("magenta"));
+        assert_eq!(settings.group.as_deref(), Some("work"));
+        assert_eq!(settings.pane.as_deref(), Some("right"));
+        assert_eq!(settings.statuses[1].wip_limit, Some(3));
+        assert_eq!(settings.statuses[1].color.as_deref(), Some("#ebcb8b"));
+        assert_eq!(
+            ProjectSettings::parse_form(&settings.to_form()).unwrap(),
+            settings
+        );
+
+        assert!(ProjectSettings::parse_form("name = web").is_err());
+        assert!(ProjectSettings::parse_form("name = web\nstatus = a\nstatus = a").is_err());
+        assert!(ProjectSettings::parse_form("name = web\nstatus = a | A | | 0").is_err());
+        assert!(ProjectSettings::parse_form("name = web\ncolour = red").is_err());
+    }
+
+    #[test]
+    fn applying_reorders_adds_and_removes_statuses() {
+        let dir = project_dir();
+        let mut settings = ProjectSettings::load(dir.path()).unwrap();
+        settings.statuses.swap(0, 1);
+        settings.statuses.remove(2);
+        settings
+            .statuses
+            .push(Status::new("review".to_string(), "Review".to_string()));
+        settings.statuses[0].wip_limit = Some(2);
+        settings.color = Some("cyan".to_string());
+
+        apply_project_settings(dir.path(), &settings).unwrap();
+
+        let config = crate::fs::load_project_config(dir.path()).unwrap();
+        assert_eq!(config.statuses.order, vec!["doing", "todo", "review"]);
+        assert_eq!(config.statuses.statuses["doing"].wip_limit, Some(2));
+        assert_eq!(config.color.as_deref(), Some("cyan"));
+        assert!(dir.path().join("review").is_dir());
+        assert!(!dir.path().join("done").exists());
+    }
+
+    #[test]
+    fn refuses_to_remove_a_status_with_files() {
+        let dir = project_dir();
+        std::fs::write(dir.path().join("done/1.md"), "# Task").unwrap();
+        let mut settings = ProjectSettings::load(dir.path()).unwrap();
+        settings.statuses.pop();
+
+        assert!(apply_project_settings(dir.path(), &settings).is_err());
+        let config = crate::fs::load_project_config(dir.path()).unwrap();
+        assert_eq!(config.statuses.order, vec!["todo", "doing", "done"]);
+    }
+}
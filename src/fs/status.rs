@@ -77,6 +77,9 @@ pub fn create_status(
         status_name.to_string(),
         crate::models::StatusConfig {
             display: display_name.to_string(),
+            color: None,
+            wip_limit: None,
+            path: None,
         },
     );
 
@@ -95,19 +98,21 @@ pub fn rename_status(
 ) -> Result<(), String> {
     let old_dir = project_path.join(old_name);
     let new_dir = project_path.join(new_name);
+    // 映射到其他目录的状态只改配置，目录（笔记库中的文件夹）保持不变
+    let mapped = super::project::is_mapped_status(project_path, old_name);
 
     // 1. 检查旧目录存在
-    if !old_dir.exists() {
+    if !mapped && !old_dir.exists() {
         return Err(format!("状态目录 '{}' 不存在", old_name));
     }
 
     // 2. 检查新目录不存在（避免覆盖）
-    if new_dir.exists() && new_dir != old_dir {
+    if !mapped && new_dir.exists() && new_dir != old_dir {
         return Err(format!("目标目录 '{}' 已存在", new_name));
     }
 
     // 3. 重命名目录（所有任务文件自动移动）
-    if old_dir != new_dir {
+    if !mapped && old_dir != new_dir {
         fs::rename(&old_dir, &new_dir).map_err(|e| format!("重命名目录失败: {}", e))?;
     }
 
@@ -137,12 +142,15 @@ pub fn rename_status(
         config.statuses.order[pos] = new_name.to_string();
     }
 
-    // 删除旧配置，添加新配置
-    config.statuses.statuses.remove(old_name);
+    // 删除旧配置，添加新配置（保留颜色和 WIP 上限）
+    let old = config.statuses.statuses.remove(old_name);
     config.statuses.statuses.insert(
         new_name.to_string(),
         crate::models::StatusConfig {
             display: new_display.to_string(),
+            color: old.as_ref().and_then(|old| old.color.clone()),
+            wip_limit: old.as_ref().and_then(|old| old.wip_limit),
+            path: old.and_then(|old| old.path),
         },
     );
 
@@ -180,11 +188,12 @@ pub fn delete_status(
     status_name: &str,
     move_to_status: Option<&str>,
 ) -> Result<(), String> {
-    let status_dir = project_path.join(status_name);
+    let status_dir = super::project::status_dir(project_path, status_name);
+    let mapped = super::project::is_mapped_status(project_path, status_name);
 
     // 1. 如果需要移动任务
     if let Some(target_status) = move_to_status {
-        let target_dir = project_path.join(target_status);
+        let target_dir = super::project::status_dir(project_path, target_status);
 
         // 确保目标目录存在
         if !target_dir.exists() {
@@ -208,8 +217,10 @@ pub fn delete_status(
         }
     }
 
-    // 2. 删除状态目录（如果存在）
-    if status_dir.exists() {
+    // 2. 删除状态目录（如果存在）；映射的目录属于笔记库，只在已清空时删除
+    if mapped {
+        let _ = fs::remove_dir(&status_dir);
+    } else if status_dir.exists() {
         fs::remove_dir_all(&status_dir).map_err(|e| format!("删除目录失败: {}", e))?;
     }
 
@@ -336,6 +347,8 @@ display = "Done"
         let existing = vec![Status {
             name: "todo".to_string(),
             display: "Todo".to_string(),
+            color: None,
+            wip_limit: None,
         }];
         assert!(validate_status_name("todo", &existing).is_err());
     }
@@ -1,23 +1,28 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
+use crate::fs::atomic::write_atomic;
+use crate::fs::ignore::IgnoreRules;
+use crate::fs::journal::JournalAction;
 use crate::fs::parser::{
-    generate_task_md, generate_toml_frontmatter, parse_task_md,
+    generate_task_md, generate_toml_frontmatter, id_from_file_name, parse_task_md,
     parse_toml_frontmatter_with_recovery,
 };
 use crate::models::Task;
-use crate::models::task::TaskFrontmatter;
+use crate::models::automation::{self, Placeholders};
+use crate::models::task::{BodyStats, TaskFrontmatter};
 
-/// 检测目录是否使用 frontmatter 格式
-fn detect_frontmatter_format(dir: &Path) -> bool {
+/// 检测目录是否使用 frontmatter 格式（忽略的文件不参与判断）
+fn detect_frontmatter_format(dir: &Path, rules: &IgnoreRules) -> bool {
     if let Ok(entries) = fs::read_dir(dir) {
         let mut has_files = false;
         let mut all_frontmatter = true;
 
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("md") {
+            if rules.is_task_file(&path) {
                 has_files = true;
                 if let Ok(content) = fs::read_to_string(&path)
                     && !content.trim_start().starts_with("+++")
@@ -38,13 +43,27 @@ fn detect_frontmatter_format(dir: &Path) -> bool {
 
 /// Load all tasks from a status directory (supports legacy, metadata, and frontmatter formats)
 pub fn load_tasks_from_dir(dir: &Path, status: &str) -> Result<Vec<Task>, String> {
+    // 项目配置中的忽略规则（状态目录的上一级是项目目录）
+    let rules = dir
+        .parent()
+        .map(IgnoreRules::for_project)
+        .unwrap_or_default();
+    load_tasks_with_rules(dir, status, &rules)
+}
+
+/// 使用指定的忽略规则加载状态目录（映射到项目之外的目录，上一级不是项目目录）
+pub fn load_tasks_with_rules(
+    dir: &Path,
+    status: &str,
+    rules: &IgnoreRules,
+) -> Result<Vec<Task>, String> {
     if !dir.exists() {
         return Ok(Vec::new());
     }
 
     // 检测 frontmatter 格式
-    if detect_frontmatter_format(dir) {
-        return load_tasks_from_frontmatter(dir, status);
+    if detect_frontmatter_format(dir, rules) {
+        return load_tasks_from_frontmatter(dir, status, rules);
     }
 
     // 否则使用旧格式（从 markdown 文件直接解析）
@@ -54,8 +73,8 @@ pub fn load_tasks_from_dir(dir: &Path, status: &str) -> Result<Vec<Task>, String
         let entry = entry.map_err(|e| e.to_string())?;
         let path = entry.path();
 
-        if path.extension().and_then(|s| s.to_str()) == Some("md")
-            && let Ok(task) = load_task(&path, status)
+        if rules.is_task_file(&path)
+            && let Ok(task) = load_task_file(&path, status)
         {
             tasks.push(task);
         }
@@ -67,6 +86,9 @@ pub fn load_tasks_from_dir(dir: &Path, status: &str) -> Result<Vec<Task>, String
     Ok(tasks)
 }
 
+/// 旧格式中有专门字段对应的元数据键
+const LEGACY_KNOWN_KEYS: [&str; 5] = ["id", "order", "created", "priority", "tags"];
+
 /// Load a single task from a markdown file
 pub fn load_task(path: &Path, status: &str) -> Result<Task, String> {
     let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
@@ -78,19 +100,8 @@ pub fn load_task(path: &Path, status: &str) -> Result<Task, String> {
         .metadata
         .get("id")
         .and_then(|s| s.parse::<u32>().ok())
-        .or_else(|| {
-            // 如果没有ID，尝试从旧文件名格式解析（向后兼容）
-            path.file_stem().and_then(|s| s.to_str()).and_then(|s| {
-                // 尝试纯数字文件名（001.md）
-                if let Ok(id) = s.parse::<u32>() {
-                    return Some(id);
-                }
-                // 尝试数字前缀（001-checkout-flow.md）
-                s.split('-')
-                    .next()
-                    .and_then(|prefix| prefix.parse::<u32>().ok())
-            })
-        })
+        // 如果没有ID，尝试从旧文件名格式解析（向后兼容）
+        .or_else(|| id_from_file_name(path))
         .ok_or_else(|| {
             format!(
                 "Task file missing 'id' field and filename is not numeric: {:?}",
@@ -125,15 +136,25 @@ pub fn load_task(path: &Path, status: &str) -> Result<Task, String> {
         })
         .unwrap_or_else(Vec::new);
 
+    // 其余字段原样保留
+    let extra = parsed
+        .metadata
+        .into_iter()
+        .filter(|(key, _)| !LEGACY_KNOWN_KEYS.contains(&key.as_str()))
+        .map(|(key, value)| (key, toml::Value::String(value)))
+        .collect();
+
     Ok(Task {
         id,
         order,
         title: parsed.title,
+        stats: BodyStats::of(&parsed.content),
         content: parsed.content,
         created,
         priority,
         status: status.to_string(),
         tags,
+        extra,
         file_path: path.to_path_buf(),
     })
 }
@@ -177,6 +198,23 @@ pub fn get_next_task_id(project_path: &Path) -> Result<u32, String> {
                 }
             }
         }
+
+        // 映射到项目目录之外的状态目录
+        if let Ok(config) = super::load_project_config(project_path)
+            && config.has_mapped_statuses()
+        {
+            let rules = IgnoreRules::new(&config.ignore);
+            for (status, dir) in super::project::resolve_status_dirs(project_path, &config) {
+                if let Ok(tasks) = load_tasks_with_rules(&dir, &status, &rules) {
+                    max_id = tasks.iter().map(|task| task.id).fold(max_id, u32::max);
+                }
+            }
+        }
+    }
+
+    // 已归档的任务 ID 不再复用，避免 `#<id>` 引用指向新任务
+    if let Ok(archived) = load_tasks_from_dir(&project_path.join(ARCHIVE_DIR), ARCHIVE_DIR) {
+        max_id = archived.iter().map(|task| task.id).fold(max_id, u32::max);
     }
 
     Ok(max_id + 1)
@@ -184,22 +222,72 @@ pub fn get_next_task_id(project_path: &Path) -> Result<u32, String> {
 
 /// Save a task to a markdown file (supports legacy and frontmatter formats)
 pub fn save_task(project_path: &Path, task: &Task) -> Result<PathBuf, String> {
-    let status_dir = project_path.join(&task.status);
+    let action = if task.file_path.exists() {
+        JournalAction::Edit
+    } else {
+        JournalAction::Create
+    };
+    let path = write_task(project_path, task)?;
+    super::journal::record(project_path, action, task, None, Some(&task.status));
+    Ok(path)
+}
+
+/// 写入任务文件（不记录变更日志）
+fn write_task(project_path: &Path, task: &Task) -> Result<PathBuf, String> {
+    let status_dir = super::project::status_dir(project_path, &task.status);
 
     if !status_dir.exists() {
         fs::create_dir_all(&status_dir).map_err(|e| e.to_string())?;
     }
 
     if project_path.join("tasks.toml").exists() {
-        migrate_metadata_to_frontmatter(project_path)?;
-        save_task_frontmatter_format(project_path, task)
-    } else if detect_frontmatter_format(&status_dir) || is_new_project(project_path) {
+        super::migration::adopt_tasks_toml(project_path)?;
+        save_task_frontmatter_format(project_path, &status_dir, task)
+    } else if detect_frontmatter_format(&status_dir, &IgnoreRules::for_project(project_path))
+        || is_new_project(project_path)
+    {
         // frontmatter 格式：元数据和内容都在 .md 文件中
-        save_task_frontmatter_format(project_path, task)
+        save_task_frontmatter_format(project_path, &status_dir, task)
     } else {
         // 旧格式：保存完整数据到 .md 文件
-        save_task_legacy_format(project_path, task)
+        save_task_legacy_format(project_path, &status_dir, task)
+    }
+}
+
+/// 映射到笔记库的项目中保留任务文件原来的文件名（笔记之间按文件名互相链接）
+fn keeps_file_name(project_path: &Path, task: &Task) -> bool {
+    task.file_path.exists()
+        && super::load_project_config(project_path).is_ok_and(|c| c.has_mapped_statuses())
+}
+
+/// 写入任务文件；位置变了时删除旧文件
+///
+/// 旧文件是符号链接时在新位置重建指向同一目标的链接再写入，链接的笔记仍是同一个文件。
+fn write_task_file(old: &Path, new: &Path, content: &str) -> Result<(), String> {
+    if old != new && fs::symlink_metadata(old).is_ok_and(|m| m.file_type().is_symlink()) {
+        let target = old.canonicalize().map_err(|e| e.to_string())?;
+        relink(&target, new)?;
+        fs::remove_file(old).map_err(|e| e.to_string())?;
+        return write_atomic(new, content).map_err(|e| e.to_string());
+    }
+
+    write_atomic(new, content).map_err(|e| e.to_string())?;
+    if old.exists() && old != new {
+        let _ = fs::remove_file(old);
     }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn relink(target: &Path, link: &Path) -> Result<(), String> {
+    std::os::unix::fs::symlink(target, link).map_err(|e| e.to_string())
+}
+
+#[cfg(not(unix))]
+fn relink(target: &Path, link: &Path) -> Result<(), String> {
+    fs::copy(target, link)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
 }
 
 /// 检查是否是新项目（没有任何任务文件）
@@ -221,13 +309,14 @@ fn is_new_project(project_path: &Path) -> bool {
     };
 
     // 检查所有状态目录是否都为空
+    let rules = IgnoreRules::new(&project_config.ignore);
     for status in &project_config.statuses.order {
-        let status_dir = project_path.join(status);
+        let status_dir = super::project::status_dir_in(project_path, &project_config, status);
         if status_dir.exists()
             && let Ok(entries) = fs::read_dir(&status_dir)
         {
             for entry in entries.flatten() {
-                if entry.path().extension().and_then(|s| s.to_str()) == Some("md") {
+                if rules.is_task_file(&entry.path()) {
                     return false;
                 }
             }
@@ -238,25 +327,36 @@ fn is_new_project(project_path: &Path) -> bool {
 }
 
 /// 保存任务（旧格式：元数据+内容都在 markdown 文件中）
-fn save_task_legacy_format(project_path: &Path, task: &Task) -> Result<PathBuf, String> {
-    let status_dir = project_path.join(&task.status);
-
+fn save_task_legacy_format(
+    project_path: &Path,
+    status_dir: &Path,
+    task: &Task,
+) -> Result<PathBuf, String> {
     // 生成文件名：使用任务ID
-    let filename =
-        if task.file_path.exists() && task.file_path.parent() == Some(status_dir.as_path()) {
-            // 任务已存在且在同一目录，保持原文件名（避免不必要的重命名）
-            task.file_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .ok_or_else(|| "Invalid file path".to_string())?
-                .to_string()
-        } else {
-            // 新任务或跨目录移动，使用任务ID作为文件名
-            format!("{}.md", task.id)
-        };
+    let filename = if task.file_path.exists() && task.file_path.parent() == Some(status_dir)
+        || keeps_file_name(project_path, task)
+    {
+        // 任务已存在且在同一目录，保持原文件名（避免不必要的重命名）
+        task.file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| "Invalid file path".to_string())?
+            .to_string()
+    } else {
+        // 新任务或跨目录移动，使用任务ID作为文件名
+        format!("{}.md", task.id)
+    };
 
     let file_path = status_dir.join(&filename);
 
+    let content = generate_legacy_task_md(task);
+    write_task_file(&task.file_path, &file_path, &content)?;
+
+    Ok(file_path)
+}
+
+/// 生成旧格式的任务文件内容
+fn generate_legacy_task_md(task: &Task) -> String {
     // 构建元数据
     let mut metadata = HashMap::new();
     metadata.insert("id".to_string(), task.id.to_string());
@@ -269,36 +369,35 @@ fn save_task_legacy_format(project_path: &Path, task: &Task) -> Result<PathBuf,
     if !task.tags.is_empty() {
         metadata.insert("tags".to_string(), task.tags.join(", "));
     }
-
-    let content = generate_task_md(&task.title, &metadata, &task.content);
-
-    // 如果文件路径变了（例如重命名），删除旧文件
-    if task.file_path.exists() && task.file_path != file_path {
-        let _ = fs::remove_file(&task.file_path);
+    for (key, value) in &task.extra {
+        let value = match value {
+            toml::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        metadata.insert(key.clone(), value);
     }
 
-    fs::write(&file_path, content).map_err(|e| e.to_string())?;
-
-    Ok(file_path)
+    generate_task_md(&task.title, &metadata, &task.content)
 }
 
 /// 保存任务（frontmatter 格式：元数据和内容都在 .md 文件中）
-fn save_task_frontmatter_format(project_path: &Path, task: &Task) -> Result<PathBuf, String> {
-    let status_dir = project_path.join(&task.status);
-    let file_path = status_dir.join(format!("{}.md", task.id));
+fn save_task_frontmatter_format(
+    project_path: &Path,
+    status_dir: &Path,
+    task: &Task,
+) -> Result<PathBuf, String> {
+    let file_name = match task.file_path.file_name() {
+        Some(name) if keeps_file_name(project_path, task) => name.to_os_string(),
+        _ => format!("{}.md", task.id).into(),
+    };
+    let file_path = status_dir.join(file_name);
 
     // 构建 frontmatter
     let frontmatter = TaskFrontmatter::from(task);
 
     // 生成 frontmatter 格式内容
     let content = generate_toml_frontmatter(&frontmatter, &task.title, &task.content);
-
-    // 如果旧文件存在且路径不同，删除旧文件
-    if task.file_path.exists() && task.file_path != file_path {
-        let _ = fs::remove_file(&task.file_path);
-    }
-
-    fs::write(&file_path, content).map_err(|e| e.to_string())?;
+    write_task_file(&task.file_path, &file_path, &content)?;
 
     Ok(file_path)
 }
@@ -309,32 +408,61 @@ pub fn move_task(
     task: &mut Task,
     new_status: &str,
 ) -> Result<PathBuf, String> {
+    move_task_inner(project_path, task, new_status, true)
+}
+
+/// 移动任务但不调用脚本的 `on_move` 钩子（脚本写回时使用，避免钩子互相触发）
+#[cfg(feature = "scripting")]
+pub fn move_task_without_hooks(
+    project_path: &Path,
+    task: &mut Task,
+    new_status: &str,
+) -> Result<PathBuf, String> {
+    move_task_inner(project_path, task, new_status, false)
+}
+
+fn move_task_inner(
+    project_path: &Path,
+    task: &mut Task,
+    new_status: &str,
+    run_hooks: bool,
+) -> Result<PathBuf, String> {
+    let original = task.clone();
     let old_status = task.status.clone();
-    let old_order = task.order;
-    let old_file_path = task.file_path.clone();
-    let new_dir = project_path.join(new_status);
+    let new_dir = super::project::status_dir(project_path, new_status);
 
     if !new_dir.exists() {
         fs::create_dir_all(&new_dir).map_err(|e| e.to_string())?;
     }
 
     if project_path.join("tasks.toml").exists() {
-        migrate_metadata_to_frontmatter(project_path)?;
+        super::migration::adopt_tasks_toml(project_path)?;
     }
 
     let new_order = get_top_order_in_status(project_path, new_status, task.id)?;
     task.status = new_status.to_string();
     task.order = new_order;
+    if old_status != new_status {
+        run_automations(project_path, task);
+        if run_hooks {
+            crate::script::run_move_hooks(task, &old_status);
+        }
+    }
 
-    match save_task(project_path, task) {
+    match write_task(project_path, task) {
         Ok(new_path) => {
             task.file_path = new_path.clone();
+            super::journal::record(
+                project_path,
+                JournalAction::Move,
+                task,
+                Some(&old_status),
+                Some(new_status),
+            );
             Ok(new_path)
         }
         Err(e) => {
-            task.status = old_status;
-            task.order = old_order;
-            task.file_path = old_file_path;
+            *task = original;
             Err(format!(
                 "Failed to move task {} to status '{}': {}",
                 task.id, new_status, e
@@ -343,12 +471,59 @@ pub fn move_task(
     }
 }
 
+/// 运行项目配置中进入新状态的自动化规则（`[[automations]]`）
+fn run_automations(project_path: &Path, task: &mut Task) {
+    if crate::safe_mode::is_enabled() {
+        return;
+    }
+    let Ok(config) = super::project::load_project_config(project_path) else {
+        return;
+    };
+    if !config
+        .automations
+        .iter()
+        .any(|rule| rule.on_enter == task.status)
+    {
+        return;
+    }
+    let user_name = config
+        .automations
+        .iter()
+        .any(|rule| rule.on_enter == task.status && rule.needs_user_name())
+        .then(|| git_user_name(project_path))
+        .flatten();
+    let values = Placeholders {
+        now: chrono::Local::now(),
+        user_name,
+    };
+    automation::run_on_enter(&config.automations, task, &values);
+}
+
+/// `{user.name}` 的取值：项目目录下的 `git config user.name`，没有时使用系统用户名
+fn git_user_name(project_path: &Path) -> Option<String> {
+    std::process::Command::new("git")
+        .args(["config", "user.name"])
+        .current_dir(project_path)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| {
+            std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok()
+        })
+        .filter(|name| !name.is_empty())
+}
+
 fn get_top_order_in_status(
     project_path: &Path,
     status: &str,
     moving_task_id: u32,
 ) -> Result<i32, String> {
-    let status_dir = project_path.join(status);
+    let status_dir = super::project::status_dir(project_path, status);
     if !status_dir.exists() {
         return Ok(0);
     }
@@ -371,6 +546,32 @@ fn get_top_order_in_status(
     }
 }
 
+/// 把任务复制或移动到另一个项目的指定状态（`Space t >`）
+///
+/// 新任务使用目标项目的下一个 ID，放在目标状态末尾，并执行目标状态的自动化规则；
+/// 移动时写入成功后才删除原任务。返回目标项目中的新任务。
+pub fn transfer_task(
+    source_path: &Path,
+    task: &Task,
+    target_path: &Path,
+    status: &str,
+    copy: bool,
+) -> Result<Task, String> {
+    let mut transferred = task.clone();
+    transferred.id = get_next_task_id(target_path)?;
+    transferred.status = status.to_string();
+    transferred.order = get_max_order_in_status(target_path, status)? + 1000;
+    // 清空原路径，避免写入时把原文件当作旧位置删除
+    transferred.file_path = PathBuf::new();
+    run_automations(target_path, &mut transferred);
+
+    transferred.file_path = save_task(target_path, &transferred)?;
+    if !copy {
+        delete_task(source_path, task)?;
+    }
+    Ok(transferred)
+}
+
 /// Delete a task (removes file and metadata if using metadata-separated format)
 pub fn delete_task(project_path: &Path, task: &Task) -> Result<(), String> {
     // 1. 删除文件
@@ -386,12 +587,98 @@ pub fn delete_task(project_path: &Path, task: &Task) -> Result<(), String> {
         }
     }
 
+    super::journal::record(
+        project_path,
+        JournalAction::Delete,
+        task,
+        Some(&task.status),
+        None,
+    );
+
     Ok(())
 }
 
+/// 撤销外部修改：写回修改前的任务，外部新增的任务（`before` 为 None）直接删除
+///
+/// 写回时删除外部修改后的文件（例如被移动到其他状态目录的副本）。
+pub fn restore_task(
+    project_path: &Path,
+    before: Option<&Task>,
+    after: Option<&Task>,
+) -> Result<(), String> {
+    match (before, after) {
+        (Some(before), after) => {
+            let mut task = before.clone();
+            if let Some(after) = after {
+                task.file_path = after.file_path.clone();
+            }
+            save_task(project_path, &task).map(|_| ())
+        }
+        (None, Some(after)) => delete_task(project_path, after),
+        (None, None) => Ok(()),
+    }
+}
+
+/// 归档目录名：以点开头，加载项目时不会被当作状态列
+pub const ARCHIVE_DIR: &str = ".archive";
+
+/// 任务文件的最后修改时间（移动和编辑都会重写文件）
+pub fn task_modified_at(task: &Task) -> Option<SystemTime> {
+    fs::metadata(&task.file_path).ok()?.modified().ok()
+}
+
+/// 找出某状态中超过 `days` 天未修改的任务
+pub fn stale_tasks<'a>(
+    tasks: &'a [Task],
+    status: &str,
+    days: u64,
+    now: SystemTime,
+) -> Vec<&'a Task> {
+    let cutoff = now
+        .checked_sub(Duration::from_secs(days * 24 * 60 * 60))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    tasks
+        .iter()
+        .filter(|task| task.status == status)
+        .filter(|task| task_modified_at(task).is_some_and(|modified| modified < cutoff))
+        .collect()
+}
+
+/// Move a task file into the project's archive directory
+///
+/// The file keeps its content; name clashes with earlier archived tasks get a numeric suffix.
+pub fn archive_task(project_path: &Path, task: &Task) -> Result<PathBuf, String> {
+    if project_path.join("tasks.toml").exists() {
+        super::migration::adopt_tasks_toml(project_path)?;
+    }
+
+    let archive_dir = project_path.join(ARCHIVE_DIR);
+    fs::create_dir_all(&archive_dir).map_err(|e| e.to_string())?;
+
+    let mut target = archive_dir.join(format!("{}.md", task.id));
+    let mut suffix = 1;
+    while target.exists() {
+        target = archive_dir.join(format!("{}-{}.md", task.id, suffix));
+        suffix += 1;
+    }
+
+    fs::rename(&task.file_path, &target)
+        .map_err(|e| format!("Failed to archive task {}: {}", task.id, e))?;
+
+    super::journal::record(
+        project_path,
+        JournalAction::Archive,
+        task,
+        Some(&task.status),
+        None,
+    );
+
+    Ok(target)
+}
+
 /// Get the maximum order value in a status directory
 pub fn get_max_order_in_status(project_path: &Path, status: &str) -> Result<i32, String> {
-    let status_dir = project_path.join(status);
+    let status_dir = super::project::status_dir(project_path, status);
 
     if !status_dir.exists() {
         return Ok(-1000); // 返回一个小于0的值，便于第一个任务order=0
@@ -441,7 +728,7 @@ pub fn save_tasks_metadata(
     let content = toml::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize tasks.toml: {}", e))?;
 
-    fs::write(&tasks_toml, content).map_err(|e| format!("Failed to write tasks.toml: {}", e))?;
+    write_atomic(&tasks_toml, content).map_err(|e| format!("Failed to write tasks.toml: {}", e))?;
 
     Ok(())
 }
@@ -456,14 +743,18 @@ pub fn get_task_full_content(task: &Task) -> Result<String, String> {
 /// 自动迁移项目从旧格式到新格式
 ///
 /// 从 frontmatter 格式加载任务
-fn load_tasks_from_frontmatter(dir: &Path, status: &str) -> Result<Vec<Task>, String> {
+fn load_tasks_from_frontmatter(
+    dir: &Path,
+    status: &str,
+    rules: &IgnoreRules,
+) -> Result<Vec<Task>, String> {
     let mut tasks = Vec::new();
 
     for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
         let entry = entry.map_err(|e| e.to_string())?;
         let path = entry.path();
 
-        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+        if !rules.is_task_file(&path) {
             continue;
         }
 
@@ -472,26 +763,10 @@ fn load_tasks_from_frontmatter(dir: &Path, status: &str) -> Result<Vec<Task>, St
             Err(_) => continue,
         };
 
-        // 使用带容错的解析器
-        let parsed = match parse_toml_frontmatter_with_recovery(&content, &path) {
-            Ok(p) => p,
-            Err(e) => {
-                eprintln!("任务解析错误 {}: {}", path.display(), e);
-                continue;
-            }
-        };
-
-        tasks.push(Task {
-            id: parsed.frontmatter.id,
-            order: parsed.frontmatter.order,
-            title: parsed.title,
-            content: parsed.content,
-            created: parsed.frontmatter.created,
-            priority: parsed.frontmatter.priority,
-            status: status.to_string(),
-            tags: parsed.frontmatter.tags,
-            file_path: path,
-        });
+        match task_from_frontmatter(&content, &path, status) {
+            Ok(task) => tasks.push(task),
+            Err(e) => eprintln!("任务解析错误 {}: {}", path.display(), e),
+        }
     }
 
     // 按 order 排序
@@ -500,6 +775,35 @@ fn load_tasks_from_frontmatter(dir: &Path, status: &str) -> Result<Vec<Task>, St
     Ok(tasks)
 }
 
+fn task_from_frontmatter(content: &str, path: &Path, status: &str) -> Result<Task, String> {
+    // 使用带容错的解析器
+    let parsed = parse_toml_frontmatter_with_recovery(content, path)?;
+
+    Ok(Task {
+        id: parsed.frontmatter.id,
+        order: parsed.frontmatter.order(),
+        title: parsed.title,
+        stats: BodyStats::of(&parsed.content),
+        content: parsed.content,
+        created: parsed.frontmatter.created,
+        priority: parsed.frontmatter.priority,
+        status: status.to_string(),
+        tags: parsed.frontmatter.tags,
+        extra: parsed.frontmatter.extra,
+        file_path: path.to_path_buf(),
+    })
+}
+
+/// 加载单个任务文件，按文件内容自动识别 frontmatter / 旧格式（供索引缓存使用）
+pub fn load_task_file(path: &Path, status: &str) -> Result<Task, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    if content.trim_start().starts_with("+++") {
+        task_from_frontmatter(&content, path, status)
+    } else {
+        load_task(path, status)
+    }
+}
+
 /// 从 metadata-separated 格式迁移到 frontmatter 格式
 pub fn migrate_metadata_to_frontmatter(project_path: &Path) -> Result<(), String> {
     let tasks_toml = project_path.join("tasks.toml");
@@ -534,10 +838,11 @@ pub fn migrate_metadata_to_frontmatter(project_path: &Path) -> Result<(), String
         // 构建 frontmatter
         let frontmatter = TaskFrontmatter {
             id: metadata.id,
-            order: metadata.order,
+            order: Some(metadata.order),
             created: metadata.created.clone(),
             priority: metadata.priority.clone(),
             tags: metadata.tags.clone(),
+            extra: toml::Table::new(),
         };
 
         // 生成 frontmatter 格式内容
@@ -554,6 +859,236 @@ pub fn migrate_metadata_to_frontmatter(project_path: &Path) -> Result<(), String
     Ok(())
 }
 
+/// 计算重新编号方案：按旧 ID 排序后从 1 开始连续编号，只返回 ID 发生变化的任务
+///
+/// 返回 `(旧 ID, 新 ID)` 列表，顺序与任务在排序后的位置一致。
+pub fn plan_task_renumber(tasks: &[Task]) -> Vec<(u32, u32)> {
+    let mut sorted: Vec<&Task> = tasks.iter().collect();
+    sorted.sort_by(|a, b| {
+        a.id.cmp(&b.id)
+            .then_with(|| a.status.cmp(&b.status))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(index, task)| (task.id, index as u32 + 1))
+        .filter(|(old_id, new_id)| old_id != new_id)
+        .collect()
+}
+
+/// 重新编号日志（项目目录下），记录每个文件的临时文件和最终文件名
+const RENUMBER_JOURNAL: &str = ".renumber-journal.json";
+
+/// 已写好临时文件和日志、等待改名的重新编号
+struct StagedRenumber {
+    plan: Vec<(u32, u32)>,
+    steps: Vec<RenumberStep>,
+}
+
+/// 重新编号中一个文件的改写步骤
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct RenumberStep {
+    original: PathBuf,
+    temp: PathBuf,
+    target: PathBuf,
+}
+
+/// Rewrite task ids sequentially (1..=n) for a project.
+///
+/// Id-based filenames (`7.md`, `007.md`, `007-slug.md`) are renamed to match
+/// the new id, and `#<id>` references inside task bodies are updated.
+/// Returns the `(old, new)` mapping that was applied.
+///
+/// 新内容先全部写入临时文件，再写日志，之后才改名和删除旧文件；中途中断时旧文件仍在，
+/// 下次加载项目时按日志完成剩余步骤（见 [`resume_renumber`]）。
+/// 元数据保存在 `tasks.toml` 中的旧项目需要先迁移。
+pub fn renumber_tasks(project_path: &Path) -> Result<Vec<(u32, u32)>, String> {
+    resume_renumber(project_path)?;
+    let staged = stage_renumber(project_path)?;
+    if staged.steps.is_empty() {
+        return Ok(staged.plan);
+    }
+    apply_renumber(&staged.steps)?;
+    fs::remove_file(project_path.join(RENUMBER_JOURNAL))
+        .map_err(|e| format!("Failed to remove renumber journal: {}", e))?;
+    Ok(staged.plan)
+}
+
+/// 第一阶段：把改写后的内容写入临时文件（不动原文件），全部写完后记录日志
+fn stage_renumber(project_path: &Path) -> Result<StagedRenumber, String> {
+    if project_path.join("tasks.toml").exists() {
+        return Err(
+            "Task metadata is kept in tasks.toml; run `hxk project migrate <project> --yes` before renumbering"
+                .to_string(),
+        );
+    }
+
+    let project = super::load_project(project_path)?;
+    let mut tasks = project.tasks;
+    tasks.sort_by(|a, b| {
+        a.id.cmp(&b.id)
+            .then_with(|| a.status.cmp(&b.status))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+    });
+
+    let plan = plan_task_renumber(&tasks);
+    if plan.is_empty() {
+        return Ok(StagedRenumber {
+            plan,
+            steps: Vec::new(),
+        });
+    }
+
+    // 引用映射：重复 ID 时以第一个任务为准
+    let mut id_map = HashMap::new();
+    for (old_id, new_id) in &plan {
+        id_map.entry(*old_id).or_insert(*new_id);
+    }
+
+    let mut steps = Vec::new();
+    for (index, task) in tasks.iter_mut().enumerate() {
+        let new_id = index as u32 + 1;
+        let old_id = task.id;
+        let new_content = rewrite_task_references(&task.content, &id_map);
+
+        if old_id == new_id && new_content == task.content {
+            continue;
+        }
+
+        let is_frontmatter = fs::read_to_string(&task.file_path)
+            .map(|content| content.trim_start().starts_with("+++"))
+            .map_err(|e| format!("Failed to read {:?}: {}", task.file_path, e))?;
+
+        task.id = new_id;
+        task.set_content(new_content);
+        let file_content = if is_frontmatter {
+            generate_toml_frontmatter(&TaskFrontmatter::from(&*task), &task.title, &task.content)
+        } else {
+            generate_legacy_task_md(task)
+        };
+
+        let file_name = task
+            .file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| format!("Invalid file path: {:?}", task.file_path))?;
+        let dir = task
+            .file_path
+            .parent()
+            .ok_or_else(|| format!("Invalid file path: {:?}", task.file_path))?;
+
+        let temp = dir.join(format!(".renumber-{}.tmp", new_id));
+        write_atomic(&temp, file_content).map_err(|e| e.to_string())?;
+        steps.push(RenumberStep {
+            original: task.file_path.clone(),
+            temp,
+            target: dir.join(renumbered_file_name(file_name, old_id, new_id)),
+        });
+    }
+
+    let journal = serde_json::to_string(&steps).map_err(|e| e.to_string())?;
+    write_atomic(&project_path.join(RENUMBER_JOURNAL), journal)
+        .map_err(|e| format!("Failed to write renumber journal: {}", e))?;
+    Ok(StagedRenumber { plan, steps })
+}
+
+/// 第二阶段：临时文件改为最终文件名（覆盖同名旧文件），再删除不再使用的旧文件
+///
+/// 可重复执行：已改名的临时文件和已删除的旧文件会跳过。
+fn apply_renumber(steps: &[RenumberStep]) -> Result<(), String> {
+    for step in steps {
+        if step.temp.exists() {
+            fs::rename(&step.temp, &step.target)
+                .map_err(|e| format!("Failed to rename {:?}: {}", step.temp, e))?;
+        }
+    }
+    let targets: std::collections::HashSet<&Path> =
+        steps.iter().map(|step| step.target.as_path()).collect();
+    for step in steps {
+        if !targets.contains(step.original.as_path()) && step.original.exists() {
+            fs::remove_file(&step.original)
+                .map_err(|e| format!("Failed to remove {:?}: {}", step.original, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// 完成上次被中断的重新编号，返回是否找到了日志
+pub fn resume_renumber(project_path: &Path) -> Result<bool, String> {
+    let path = project_path.join(RENUMBER_JOURNAL);
+    let Ok(journal) = fs::read_to_string(&path) else {
+        return Ok(false);
+    };
+    let steps: Vec<RenumberStep> = serde_json::from_str(&journal)
+        .map_err(|e| format!("Invalid renumber journal {}: {}", path.display(), e))?;
+    apply_renumber(&steps)?;
+    fs::remove_file(&path).map_err(|e| format!("Failed to remove renumber journal: {}", e))?;
+    Ok(true)
+}
+
+/// 计算重新编号后的文件名：只改写以 ID 命名的文件，保留原有的零填充宽度
+fn renumbered_file_name(file_name: &str, old_id: u32, new_id: u32) -> String {
+    let Some(stem) = file_name.strip_suffix(".md") else {
+        return file_name.to_string();
+    };
+
+    let (prefix, rest) = match stem.find('-') {
+        Some(pos) => stem.split_at(pos),
+        None => (stem, ""),
+    };
+
+    if prefix.parse::<u32>().ok() != Some(old_id) {
+        return file_name.to_string();
+    }
+
+    // 只有零填充的文件名（如 007.md）才保持宽度
+    let width = if prefix.starts_with('0') {
+        prefix.len()
+    } else {
+        0
+    };
+    format!("{:0width$}{}.md", new_id, rest, width = width)
+}
+
+/// 改写任务正文中的 `#<id>` 引用
+pub fn rewrite_task_references(content: &str, id_map: &HashMap<u32, u32>) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut result = String::with_capacity(content.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let boundary_before = i == 0
+            || chars[i - 1].is_whitespace()
+            || matches!(chars[i - 1], '(' | '[' | '{' | ',' | ';' | ':');
+
+        if c == '#' && boundary_before {
+            let digits_end = (i + 1..chars.len())
+                .find(|&j| !chars[j].is_ascii_digit())
+                .unwrap_or(chars.len());
+            let boundary_after = digits_end == chars.len()
+                || !(chars[digits_end].is_alphanumeric() || chars[digits_end] == '_');
+
+            if digits_end > i + 1 && boundary_after {
+                let digits: String = chars[i + 1..digits_end].iter().collect();
+                if let Some(new_id) = digits.parse::<u32>().ok().and_then(|id| id_map.get(&id)) {
+                    result.push('#');
+                    result.push_str(&new_id.to_string());
+                    i = digits_end;
+                    continue;
+                }
+            }
+        }
+
+        result.push(c);
+        i += 1;
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -780,7 +1315,9 @@ Existing content.
             priority: Some("medium".to_string()),
             status: "todo".to_string(),
             tags: vec!["feature".to_string()],
+            extra: toml::Table::new(),
             file_path: PathBuf::new(),
+            stats: Default::default(),
         };
 
         let result = save_task(project_path, &task);
@@ -812,7 +1349,9 @@ Existing content.
             priority: Some("high".to_string()),
             status: "todo".to_string(),
             tags: vec!["bug".to_string(), "urgent".to_string()],
+            extra: toml::Table::new(),
             file_path: PathBuf::new(),
+            stats: Default::default(),
         };
 
         let result = save_task(project_path, &task);
@@ -875,7 +1414,9 @@ Content.
             priority: None,
             status: "todo".to_string(),
             tags: vec![],
+            extra: toml::Table::new(),
             file_path: PathBuf::new(),
+            stats: Default::default(),
         };
         save_task(project_path, &task).unwrap();
 
@@ -945,6 +1486,57 @@ Content.
         assert!(!project_path.join("todo/1.md").exists());
     }
 
+    #[test]
+    fn transfer_task_copies_or_moves_into_another_project() {
+        let source = setup_legacy_project();
+        let target = setup_legacy_project();
+        let mut task = Task::new(1, "Shared".to_string(), "todo".to_string());
+        task.tags = vec!["api".to_string()];
+        save_task(source.path(), &task).unwrap();
+        save_task(
+            target.path(),
+            &Task::new(1, "Existing".to_string(), "doing".to_string()),
+        )
+        .unwrap();
+        let task = load_tasks_from_dir(&source.path().join("todo"), "todo").unwrap()[0].clone();
+
+        let copied = transfer_task(source.path(), &task, target.path(), "doing", true).unwrap();
+        assert_eq!(copied.id, 2);
+        assert!(task.file_path.exists());
+
+        let moved = transfer_task(source.path(), &task, target.path(), "done", false).unwrap();
+        assert_eq!(moved.id, 3);
+        assert!(!task.file_path.exists());
+        let done = load_tasks_from_dir(&target.path().join("done"), "done").unwrap();
+        assert_eq!(done[0].title, "Shared");
+        assert_eq!(done[0].tags, vec!["api".to_string()]);
+    }
+
+    #[test]
+    fn restore_task_undoes_an_external_move() {
+        let temp_dir = setup_legacy_project();
+        let project_path = temp_dir.path();
+        fs::create_dir_all(project_path.join("doing")).unwrap();
+
+        let task = Task::new(1, "Test Task".to_string(), "todo".to_string());
+        save_task(project_path, &task).unwrap();
+        let before = load_tasks_from_dir(&project_path.join("todo"), "todo").unwrap()[0].clone();
+
+        // 模拟外部工具把任务移动到 doing 并改了标题
+        fs::remove_file(project_path.join("todo/1.md")).unwrap();
+        let mut moved = before.clone();
+        moved.status = "doing".to_string();
+        moved.title = "Renamed".to_string();
+        save_task(project_path, &moved).unwrap();
+        let after = load_tasks_from_dir(&project_path.join("doing"), "doing").unwrap()[0].clone();
+
+        restore_task(project_path, Some(&before), Some(&after)).unwrap();
+
+        assert!(!project_path.join("doing/1.md").exists());
+        let restored = load_tasks_from_dir(&project_path.join("todo"), "todo").unwrap();
+        assert_eq!(restored[0].title, "Test Task");
+    }
+
     #[test]
     fn test_get_max_order_in_status() {
         let temp_dir = setup_legacy_project();
@@ -979,7 +1571,9 @@ Content.
             priority: None,
             status: "todo".to_string(),
             tags: vec![],
+            extra: toml::Table::new(),
             file_path: PathBuf::new(),
+            stats: Default::default(),
         };
         let task2 = Task {
             id: 2,
@@ -990,7 +1584,9 @@ Content.
             priority: Some("high".to_string()),
             status: "todo".to_string(),
             tags: vec!["urgent".to_string()],
+            extra: toml::Table::new(),
             file_path: PathBuf::new(),
+            stats: Default::default(),
         };
         let task3 = Task {
             id: 3,
@@ -1001,7 +1597,9 @@ Content.
             priority: None,
             status: "done".to_string(),
             tags: vec![],
+            extra: toml::Table::new(),
             file_path: PathBuf::new(),
+            stats: Default::default(),
         };
 
         save_task(project_path, &task1).unwrap();
@@ -1074,7 +1672,7 @@ Another task.
         // 执行迁移
         let result = auto_migrate_project_to_new_format(&test_dir);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
 
         // 验证 tasks.toml 已创建
         assert!(test_dir.join("tasks.toml").exists());
@@ -1111,7 +1709,7 @@ Another task.
         // 已经是新格式，不应该迁移
         let result = auto_migrate_project_to_new_format(project_path);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), false);
+        assert!(!result.unwrap());
     }
 
     #[test]
@@ -1123,7 +1721,7 @@ Another task.
         // 空项目迁移
         let result = auto_migrate_project_to_new_format(project_path);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
 
         // 应该创建空的 tasks.toml
         assert!(project_path.join("tasks.toml").exists());
@@ -1246,6 +1844,37 @@ Another task.
         assert_eq!(done_tasks[2].id, 2);
     }
 
+    #[test]
+    fn test_move_task_runs_status_enter_automations() {
+        let temp_dir = setup_legacy_project();
+        let project_path = temp_dir.path();
+        let config = fs::read_to_string(project_path.join(".kanban.toml")).unwrap();
+        fs::write(
+            project_path.join(".kanban.toml"),
+            format!(
+                "{}\n[[automations]]\non_enter = \"done\"\nset = {{ completed = \"{{today}}\" }}\nclear = [\"due\"]\n",
+                config
+            ),
+        )
+        .unwrap();
+
+        let mut task = Task::new(1, "Ship".to_string(), "todo".to_string());
+        task.extra.insert("due".to_string(), "2026-03-05".into());
+        save_task(project_path, &task).unwrap();
+        let mut task = load_tasks_from_dir(&project_path.join("todo"), "todo").unwrap()[0].clone();
+
+        move_task(project_path, &mut task, "doing").unwrap();
+        assert!(task.extra.contains_key("due"));
+        move_task(project_path, &mut task, "done").unwrap();
+
+        let saved = &load_tasks_from_dir(&project_path.join("done"), "done").unwrap()[0];
+        assert!(!saved.extra.contains_key("due"));
+        assert_eq!(
+            saved.extra["completed"].as_str(),
+            Some(chrono::Local::now().format("%Y-%m-%d").to_string().as_str())
+        );
+    }
+
     #[test]
     fn test_save_task_frontmatter_format() {
         let temp_dir = setup_legacy_project();
@@ -1261,7 +1890,9 @@ Another task.
             priority: Some("high".to_string()),
             status: "todo".to_string(),
             tags: vec!["feature".to_string(), "urgent".to_string()],
+            extra: toml::Table::new(),
             file_path: PathBuf::new(),
+            stats: Default::default(),
         };
 
         let result = save_task(project_path, &task);
@@ -1320,6 +1951,44 @@ This is the task content.
         assert!(task.content.contains("This is the task content."));
     }
 
+    #[test]
+    fn saving_task_preserves_unknown_frontmatter_keys() {
+        let temp_dir = setup_legacy_project();
+        let project_path = temp_dir.path();
+
+        let task_content = r#"+++
+id = 1
+order = 1000
+created = "1234567890"
+due = 2025-03-01
+estimate = 3
+
+[links]
+issue = "https://example.com/1"
++++
+
+# Task from another tool
+
+    indented body
+"#;
+        fs::write(project_path.join("todo/1.md"), task_content).unwrap();
+
+        let mut task = load_task_file(&project_path.join("todo/1.md"), "todo").unwrap();
+        assert_eq!(task.content, "    indented body");
+        task.title = "Renamed".to_string();
+        save_task(project_path, &task).unwrap();
+
+        let reloaded = load_task_file(&project_path.join("todo/1.md"), "todo").unwrap();
+        assert_eq!(reloaded.title, "Renamed");
+        assert_eq!(reloaded.extra, task.extra);
+        assert_eq!(
+            reloaded.extra.get("estimate"),
+            Some(&toml::Value::Integer(3))
+        );
+        assert!(reloaded.extra.contains_key("links"));
+        assert_eq!(reloaded.content, "    indented body");
+    }
+
     #[test]
     fn test_frontmatter_recovery() {
         let temp_dir = setup_legacy_project();
@@ -1345,4 +2014,134 @@ Content here.
         assert_eq!(task.id, 42); // 从文件名恢复
         assert_eq!(task.title, "Recovered Task");
     }
+
+    #[test]
+    fn renumber_compacts_ids_and_renames_id_based_files() {
+        let temp_dir = setup_legacy_project();
+        let project_path = temp_dir.path();
+
+        let task = |id: u32, title: &str, body: &str| {
+            format!(
+                "+++\nid = {}\norder = {}\ncreated = \"1234567890\"\n+++\n\n# {}\n\n{}\n",
+                id,
+                id * 1000,
+                title,
+                body
+            )
+        };
+        fs::write(project_path.join("todo/3.md"), task(3, "First", "")).unwrap();
+        fs::write(
+            project_path.join("todo/17.md"),
+            task(17, "Second", "Blocked by #3, see #99 and page#3"),
+        )
+        .unwrap();
+        fs::write(project_path.join("done/42.md"), task(42, "Third", "")).unwrap();
+
+        let plan = renumber_tasks(project_path).unwrap();
+        assert_eq!(plan, vec![(3, 1), (17, 2), (42, 3)]);
+
+        assert!(project_path.join("todo/1.md").exists());
+        assert!(project_path.join("todo/2.md").exists());
+        assert!(project_path.join("done/3.md").exists());
+        assert!(!project_path.join("todo/17.md").exists());
+
+        let tasks = load_tasks_from_dir(&project_path.join("todo"), "todo").unwrap();
+        let second = tasks.iter().find(|t| t.title == "Second").unwrap();
+        assert_eq!(second.id, 2);
+        assert_eq!(second.content, "Blocked by #1, see #99 and page#3");
+    }
+
+    #[test]
+    fn interrupted_renumber_is_completed_on_next_load() {
+        let temp_dir = setup_legacy_project();
+        let project_path = temp_dir.path();
+        for (id, title) in [(2, "First"), (3, "Second")] {
+            fs::write(
+                project_path.join(format!("todo/{}.md", id)),
+                format!(
+                    "+++\nid = {}\norder = {}\ncreated = \"0\"\n+++\n\n# {}\n\nsee #2\n",
+                    id,
+                    id * 1000,
+                    title
+                ),
+            )
+            .unwrap();
+        }
+
+        // 临时文件和日志已写好，改名进行到一半时中断
+        let steps = stage_renumber(project_path).unwrap().steps;
+        assert!(project_path.join("todo/2.md").exists());
+        fs::rename(&steps[0].temp, &steps[0].target).unwrap();
+
+        let project = crate::fs::load_project(project_path).unwrap();
+        let mut tasks: Vec<(u32, &str, &str)> = project
+            .tasks
+            .iter()
+            .map(|t| (t.id, t.title.as_str(), t.content.as_str()))
+            .collect();
+        tasks.sort();
+        assert_eq!(tasks, [(1, "First", "see #1"), (2, "Second", "see #1")]);
+        assert!(!project_path.join(RENUMBER_JOURNAL).exists());
+        assert!(!project_path.join("todo/3.md").exists());
+    }
+
+    #[test]
+    fn renumber_refuses_tasks_toml_projects() {
+        let temp_dir = setup_legacy_project();
+        fs::write(temp_dir.path().join("tasks.toml"), "").unwrap();
+        assert!(renumber_tasks(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn renumber_is_noop_for_sequential_ids() {
+        let temp_dir = setup_legacy_project();
+        let project_path = temp_dir.path();
+
+        fs::write(
+            project_path.join("todo/1.md"),
+            "+++\nid = 1\norder = 0\ncreated = \"0\"\n+++\n\n# Only\n",
+        )
+        .unwrap();
+
+        assert!(renumber_tasks(project_path).unwrap().is_empty());
+        assert!(project_path.join("todo/1.md").exists());
+    }
+
+    #[test]
+    fn renumbered_file_name_keeps_padding_and_slug() {
+        assert_eq!(renumbered_file_name("017.md", 17, 2), "002.md");
+        assert_eq!(
+            renumbered_file_name("17-checkout.md", 17, 2),
+            "2-checkout.md"
+        );
+        assert_eq!(renumbered_file_name("checkout.md", 17, 2), "checkout.md");
+    }
+
+    #[test]
+    fn archives_stale_done_tasks_and_keeps_their_ids_reserved() {
+        let temp_dir = setup_legacy_project();
+        let project_path = temp_dir.path();
+        for (id, status) in [(1, "done"), (2, "done"), (3, "todo")] {
+            let task = Task::new(id, format!("Task {}", id), status.to_string());
+            save_task(project_path, &task).unwrap();
+        }
+        let tasks = crate::fs::load_project(project_path).unwrap().tasks;
+
+        // 文件刚写入：按 0 天计算时全部过期，按 30 天计算时都不过期
+        let later = SystemTime::now() + Duration::from_secs(60);
+        assert!(stale_tasks(&tasks, "done", 30, later).is_empty());
+        let stale = stale_tasks(&tasks, "done", 0, later);
+        let mut stale_ids: Vec<u32> = stale.iter().map(|t| t.id).collect();
+        stale_ids.sort();
+        assert_eq!(stale_ids, vec![1, 2]);
+
+        for task in stale {
+            archive_task(project_path, task).unwrap();
+        }
+
+        let remaining = crate::fs::load_project(project_path).unwrap().tasks;
+        assert_eq!(remaining.iter().map(|t| t.id).collect::<Vec<_>>(), vec![3]);
+        assert!(project_path.join(ARCHIVE_DIR).join("2.md").exists());
+        assert_eq!(get_next_task_id(project_path).unwrap(), 4);
+    }
 }
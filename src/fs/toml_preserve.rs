@@ -0,0 +1,152 @@
+/// 改写 TOML 配置文件时保留用户的注释、格式和不认识的键
+///
+/// 直接序列化结构体会丢掉注释，也会丢掉新版本加入、旧版本不认识的键。这里把新内容
+/// 合并进原文件的文档树：值没变的键原样保留，变了的键只替换值（键上方的注释保留），
+/// 结构体会读取却不再输出的键删除，结构体不认识的键保持不动。
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+/// 把 `value` 序列化为 TOML，并尽量保留 `existing`（原文件内容）中的注释和未知键
+///
+/// 原文件为空或无法解析时直接返回新的序列化结果。
+pub fn to_string_preserving<T: Serialize + DeserializeOwned>(
+    value: &T,
+    existing: Option<&str>,
+) -> Result<String, String> {
+    let fresh = toml::to_string_pretty(value).map_err(|e| e.to_string())?;
+    let Some(existing) = existing.filter(|s| !s.trim().is_empty()) else {
+        return Ok(fresh);
+    };
+    let (Ok(mut document), Ok(new)) = (
+        existing.parse::<DocumentMut>(),
+        fresh.parse::<DocumentMut>(),
+    ) else {
+        return Ok(fresh);
+    };
+    // 原文件中结构体能读取的键：读出后重新序列化，不在其中的键就是未知键
+    let Some(known) = toml::from_str::<T>(existing)
+        .ok()
+        .and_then(|parsed| toml::to_string_pretty(&parsed).ok())
+        .and_then(|s| s.parse::<DocumentMut>().ok())
+    else {
+        return Ok(fresh);
+    };
+
+    merge_table(
+        document.as_table_mut(),
+        new.as_table(),
+        Some(known.as_table()),
+    );
+    Ok(document.to_string())
+}
+
+/// 把 `new` 合并进 `old`；`known` 为 None 时 `old` 中多出的键都视为未知键保留
+fn merge_table(old: &mut Table, new: &Table, known: Option<&Table>) {
+    let removed: Vec<String> = old
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !new.contains_key(key) && known.is_some_and(|k| k.contains_key(key)))
+        .collect();
+    for key in removed {
+        old.remove(&key);
+    }
+
+    for (key, new_item) in new.iter() {
+        let known_item = known.and_then(|k| k.get(key));
+        match (old.get_mut(key), new_item) {
+            (Some(Item::Table(old_table)), Item::Table(new_table)) => {
+                merge_table(old_table, new_table, known_item.and_then(Item::as_table));
+            }
+            (Some(Item::ArrayOfTables(old_array)), Item::ArrayOfTables(new_array))
+                if old_array.len() == new_array.len() =>
+            {
+                let known_array = known_item.and_then(Item::as_array_of_tables);
+                for (i, (old_table, new_table)) in
+                    old_array.iter_mut().zip(new_array.iter()).enumerate()
+                {
+                    merge_table(
+                        old_table,
+                        new_table,
+                        known_array.and_then(|array| array.get(i)),
+                    );
+                }
+            }
+            (Some(Item::Value(old_value)), Item::Value(new_value)) => {
+                if !same_value(old_value, new_value) {
+                    let mut value = new_value.clone();
+                    *value.decor_mut() = old_value.decor().clone();
+                    *old_value = value;
+                }
+            }
+            _ => {
+                old.insert(key, new_item.clone());
+            }
+        }
+    }
+}
+
+/// 值相同（按语义比较，忽略空白、换行和注释）
+fn same_value(a: &Value, b: &Value) -> bool {
+    let parse = |value: &Value| {
+        let mut value = value.clone();
+        value.decor_mut().clear();
+        toml::from_str::<toml::Table>(&format!("v = {}", value)).ok()
+    };
+    matches!((parse(a), parse(b)), (Some(a), Some(b)) if a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Sample {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        editor: Option<String>,
+        #[serde(default)]
+        widths: BTreeMap<String, Vec<u16>>,
+    }
+
+    #[test]
+    fn keeps_comments_and_unknown_keys() {
+        let existing = "# 我的配置\nname = \"old\" # 名称\nfuture_key = true\n\n\
+                        # 外部编辑器\neditor = \"vim\"\n\n[widths]\nweb = [50, 50]\nold = [100]\n\n\
+                        [future_table]\nx = 1\n";
+        let sample = Sample {
+            name: "new".to_string(),
+            editor: None,
+            widths: BTreeMap::from([("web".to_string(), vec![50, 50])]),
+        };
+
+        let output = to_string_preserving(&sample, Some(existing)).unwrap();
+        assert!(output.starts_with("# 我的配置\nname = \"new\" # 名称\nfuture_key = true\n"));
+        // 结构体读取但不再输出的键被删除
+        assert!(!output.contains("editor"));
+        assert!(!output.contains("old = "));
+        assert!(output.contains("web = [50, 50]"));
+        assert!(output.contains("[future_table]\nx = 1"));
+
+        let parsed: Sample = toml::from_str(&output).unwrap();
+        assert_eq!(parsed.name, "new");
+        assert_eq!(parsed.widths.len(), 1);
+    }
+
+    #[test]
+    fn falls_back_to_plain_serialization() {
+        let sample = Sample {
+            name: "a".to_string(),
+            editor: Some("hx".to_string()),
+            widths: BTreeMap::new(),
+        };
+        let fresh = toml::to_string_pretty(&sample).unwrap();
+        assert_eq!(to_string_preserving(&sample, None).unwrap(), fresh);
+        assert_eq!(
+            to_string_preserving(&sample, Some("not = [valid")).unwrap(),
+            fresh
+        );
+    }
+}
@@ -0,0 +1,411 @@
+/// 从其他工具导入任务（Emacs Org-mode、Taskwarrior）
+use crate::models::Task;
+use std::path::Path;
+
+/// 导入来源格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Emacs org 文件（TODO/DONE 等关键字标题）
+    Org,
+    /// Taskwarrior `task export` 输出的 JSON
+    Taskwarrior,
+}
+
+impl ImportFormat {
+    /// 根据名称解析格式（CLI 参数）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "org" => Some(Self::Org),
+            "taskwarrior" | "tw" => Some(Self::Taskwarrior),
+            _ => None,
+        }
+    }
+
+    /// 根据文件扩展名推断格式
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("org") => Some(Self::Org),
+            Some("json") => Some(Self::Taskwarrior),
+            _ => None,
+        }
+    }
+}
+
+/// 待导入的任务
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedTask {
+    pub title: String,
+    pub status: String,
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+    pub content: String,
+}
+
+/// 解析 org 文件：带关键字的标题成为任务，关键字映射为状态
+///
+/// 关键字只认 TODO / DONE 和文件中 `#+TODO:` / `#+SEQ_TODO:` / `#+TYP_TODO:` 声明的关键字；
+/// 不带关键字的标题视为分组标题，直接跳过。
+pub fn parse_org(content: &str) -> Vec<ImportedTask> {
+    let keywords = org_todo_keywords(content);
+    let mut tasks: Vec<ImportedTask> = Vec::new();
+    let mut in_task = false;
+
+    for line in content.lines() {
+        if let Some(heading) = org_heading(line) {
+            in_task = false;
+            if let Some(task) = parse_org_heading(heading, &keywords) {
+                tasks.push(task);
+                in_task = true;
+            }
+            continue;
+        }
+
+        if !in_task {
+            continue;
+        }
+
+        // 跳过 SCHEDULED/DEADLINE 和属性抽屉
+        let trimmed = line.trim();
+        if trimmed.starts_with("SCHEDULED:")
+            || trimmed.starts_with("DEADLINE:")
+            || trimmed.starts_with("CLOSED:")
+            || trimmed.starts_with(':')
+        {
+            continue;
+        }
+
+        if let Some(task) = tasks.last_mut() {
+            if !task.content.is_empty() {
+                task.content.push('\n');
+            }
+            task.content.push_str(line.trim_end());
+        }
+    }
+
+    for task in &mut tasks {
+        task.content = task.content.trim().to_string();
+    }
+
+    tasks
+}
+
+/// 匹配 org 标题行（`* ` / `** ` ...），返回星号之后的部分
+fn org_heading(line: &str) -> Option<&str> {
+    let stars = line.chars().take_while(|c| *c == '*').count();
+    if stars == 0 {
+        return None;
+    }
+    line[stars..].strip_prefix(' ')
+}
+
+/// 文件中可用的 TODO 关键字：TODO、DONE 和 `#+TODO:` 等行声明的关键字
+///
+/// 声明中的 `|` 分隔符和快捷键后缀（`WAIT(w@/!)` 中的 `(w@/!)`）会被去掉。
+fn org_todo_keywords(content: &str) -> Vec<String> {
+    let mut keywords = vec!["TODO".to_string(), "DONE".to_string()];
+    for line in content.lines() {
+        let line = line.trim();
+        let Some((key, value)) = line.strip_prefix("#+").and_then(|l| l.split_once(':')) else {
+            continue;
+        };
+        if !["TODO", "SEQ_TODO", "TYP_TODO"]
+            .iter()
+            .any(|k| key.eq_ignore_ascii_case(k))
+        {
+            continue;
+        }
+        for word in value.split_whitespace().filter(|w| *w != "|") {
+            let keyword = word.split('(').next().unwrap_or_default();
+            if !keyword.is_empty() && !keywords.iter().any(|k| k == keyword) {
+                keywords.push(keyword.to_string());
+            }
+        }
+    }
+    keywords
+}
+
+fn parse_org_heading(heading: &str, keywords: &[String]) -> Option<ImportedTask> {
+    let mut rest = heading.trim();
+
+    // 关键字：TODO / DONE 或文件中声明的关键字（NEXT、WAITING ...），其余单词留在标题中
+    let (keyword, after) = rest.split_once(' ').unwrap_or((rest, ""));
+    if !keywords.iter().any(|k| k == keyword) {
+        return None;
+    }
+    let status = match keyword {
+        "TODO" => "todo".to_string(),
+        "DONE" => "done".to_string(),
+        other => other.to_lowercase(),
+    };
+    rest = after.trim();
+
+    // 优先级：[#A] / [#B] / [#C]
+    let mut priority = None;
+    if let Some(after_priority) = rest.strip_prefix("[#")
+        && let Some((level, after)) = after_priority.split_once(']')
+    {
+        priority = match level {
+            "A" => Some("high".to_string()),
+            "B" => Some("medium".to_string()),
+            "C" => Some("low".to_string()),
+            _ => None,
+        };
+        rest = after.trim();
+    }
+
+    // 标签：行尾的 :tag1:tag2:
+    let mut tags = Vec::new();
+    if rest.ends_with(':')
+        && let Some(pos) = rest.rfind(char::is_whitespace)
+    {
+        let candidate = &rest[pos + 1..];
+        if candidate.starts_with(':') && candidate.len() > 1 {
+            tags = candidate
+                .split(':')
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string())
+                .collect();
+            rest = rest[..pos].trim();
+        }
+    }
+
+    if rest.is_empty() {
+        return None;
+    }
+
+    Some(ImportedTask {
+        title: rest.to_string(),
+        status,
+        priority,
+        tags,
+        content: String::new(),
+    })
+}
+
+/// 解析 Taskwarrior `task export` 的 JSON 数组
+///
+/// pending/waiting → todo，已开始（有 start）→ doing，completed → done，deleted 跳过。
+pub fn parse_taskwarrior(content: &str) -> Result<Vec<ImportedTask>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("无效的 Taskwarrior JSON: {}", e))?;
+    let items = value
+        .as_array()
+        .ok_or_else(|| "Taskwarrior 导出应为 JSON 数组".to_string())?;
+
+    let mut tasks = Vec::new();
+    for item in items {
+        let Some(title) = item.get("description").and_then(|v| v.as_str()) else {
+            continue;
+        };
+
+        let status = match item.get("status").and_then(|v| v.as_str()) {
+            Some("deleted") | Some("recurring") => continue,
+            Some("completed") => "done",
+            _ if item.get("start").is_some() => "doing",
+            _ => "todo",
+        };
+
+        let priority = match item.get("priority").and_then(|v| v.as_str()) {
+            Some("H") => Some("high".to_string()),
+            Some("M") => Some("medium".to_string()),
+            Some("L") => Some("low".to_string()),
+            _ => None,
+        };
+
+        let mut tags: Vec<String> = item
+            .get("tags")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|t| t.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(project) = item.get("project").and_then(|v| v.as_str()) {
+            tags.push(project.to_string());
+        }
+
+        let content = item
+            .get("annotations")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|a| a.get("description").and_then(|d| d.as_str()))
+                    .map(|d| format!("- {}", d))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default();
+
+        tasks.push(ImportedTask {
+            title: title.to_string(),
+            status: status.to_string(),
+            priority,
+            tags,
+            content,
+        });
+    }
+
+    Ok(tasks)
+}
+
+/// 读取文件并按格式解析
+pub fn parse_file(path: &Path, format: ImportFormat) -> Result<Vec<ImportedTask>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("读取 {:?} 失败: {}", path, e))?;
+    match format {
+        ImportFormat::Org => Ok(parse_org(&content)),
+        ImportFormat::Taskwarrior => parse_taskwarrior(&content),
+    }
+}
+
+/// 将任务写入项目，缺失的状态会自动创建；返回导入数量
+pub fn import_into_project(project_path: &Path, tasks: &[ImportedTask]) -> Result<usize, String> {
+    import_into_project_with(project_path, tasks, |_| true)
+}
+
+/// 同 `import_into_project`，每写入一个任务调用一次 `on_progress(已导入数)`，
+/// 返回 false 时停止导入，返回已导入的数量
+pub fn import_into_project_with(
+    project_path: &Path,
+    tasks: &[ImportedTask],
+    mut on_progress: impl FnMut(usize) -> bool,
+) -> Result<usize, String> {
+    let mut config = crate::fs::load_project_config(project_path)?;
+
+    for task in tasks {
+        if !config.statuses.order.contains(&task.status) {
+            crate::fs::status::create_status(
+                project_path,
+                &task.status,
+                &capitalize(&task.status),
+            )?;
+            config = crate::fs::load_project_config(project_path)?;
+        }
+    }
+
+    let first_id = crate::fs::get_next_task_id(project_path)?;
+    for (id, imported) in (first_id..).zip(tasks) {
+        let order = crate::fs::get_max_order_in_status(project_path, &imported.status)? + 1000;
+
+        let mut task = Task::new(id, imported.title.clone(), imported.status.clone());
+        task.order = order;
+        task.priority = imported.priority.clone();
+        task.tags = imported.tags.clone();
+        task.set_content(imported.content.clone());
+        crate::fs::save_task(project_path, &task)?;
+
+        let imported = (id - first_id) as usize + 1;
+        if !on_progress(imported) {
+            return Ok(imported);
+        }
+    }
+
+    Ok(tasks.len())
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_org_headings_with_keywords_priority_and_tags() {
+        let org = "\
+#+TODO: TODO NEXT(n) WAITING(w@/!) | DONE CANCELLED(c)
+* Inbox
+** TODO [#A] Write release notes :docs:release:
+   SCHEDULED: <2025-01-01 Wed>
+   Mention the new importer.
+** DONE Ship 0.3
+** WAITING Reply from design
+Plain text outside tasks.
+";
+        let tasks = parse_org(org);
+        assert_eq!(tasks.len(), 3);
+
+        assert_eq!(tasks[0].title, "Write release notes");
+        assert_eq!(tasks[0].status, "todo");
+        assert_eq!(tasks[0].priority, Some("high".to_string()));
+        assert_eq!(tasks[0].tags, vec!["docs", "release"]);
+        assert_eq!(tasks[0].content, "Mention the new importer.");
+
+        assert_eq!(tasks[1].status, "done");
+        assert_eq!(tasks[2].status, "waiting");
+        assert_eq!(tasks[2].content, "Plain text outside tasks.");
+    }
+
+    #[test]
+    fn undeclared_uppercase_words_are_not_org_keywords() {
+        let org = "\
+* API design
+** TODO API design review
+** NEXT Write docs
+";
+        let tasks = parse_org(org);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "API design review");
+        assert_eq!(tasks[0].status, "todo");
+
+        let declared = parse_org(&format!("#+SEQ_TODO: NEXT | DONE\n{}", org));
+        assert_eq!(declared.len(), 2);
+        assert_eq!(declared[1].title, "Write docs");
+        assert_eq!(declared[1].status, "next");
+    }
+
+    #[test]
+    fn parses_taskwarrior_export() {
+        let json = r#"[
+            {"description": "Fix bug", "status": "pending", "priority": "H", "tags": ["bug"], "project": "web"},
+            {"description": "In flight", "status": "pending", "start": "20250101T000000Z"},
+            {"description": "Old", "status": "completed",
+             "annotations": [{"entry": "20250101T000000Z", "description": "closed via PR"}]},
+            {"description": "Gone", "status": "deleted"}
+        ]"#;
+
+        let tasks = parse_taskwarrior(json).unwrap();
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].status, "todo");
+        assert_eq!(tasks[0].priority, Some("high".to_string()));
+        assert_eq!(tasks[0].tags, vec!["bug", "web"]);
+        assert_eq!(tasks[1].status, "doing");
+        assert_eq!(tasks[2].status, "done");
+        assert_eq!(tasks[2].content, "- closed via PR");
+    }
+
+    #[test]
+    fn imports_into_project_creating_missing_statuses() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        std::fs::write(
+            project_path.join(".kanban.toml"),
+            "name = \"demo\"\ncreated = \"0\"\n\n[statuses]\norder = [\"todo\", \"done\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n\n[statuses.done]\ndisplay = \"Done\"\n",
+        )
+        .unwrap();
+
+        let tasks = parse_org("#+TODO: TODO WAITING | DONE\n* TODO First\n* WAITING Second\n");
+        assert_eq!(import_into_project(project_path, &tasks).unwrap(), 2);
+
+        let project = crate::fs::load_project(project_path).unwrap();
+        assert!(project.statuses.iter().any(|s| s.name == "waiting"));
+        assert_eq!(project.tasks.len(), 2);
+        assert!(
+            project
+                .tasks
+                .iter()
+                .any(|t| t.title == "Second" && t.status == "waiting")
+        );
+    }
+
+    #[test]
+    fn rejects_non_array_taskwarrior_json() {
+        assert!(parse_taskwarrior("{}").is_err());
+    }
+}
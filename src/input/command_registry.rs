@@ -1,5 +1,6 @@
 /// 命令注册表 - 类似 Helix 的命令系统
-use std::collections::HashMap;
+use crate::ui::fuzzy::fuzzy_match;
+use std::collections::{BTreeMap, HashMap};
 
 /// 命令定义
 #[derive(Debug, Clone)]
@@ -13,11 +14,16 @@ pub struct CommandDef {
     pub description: &'static str,
 }
 
+/// 用户别名中分隔多条命令的符号
+pub const ALIAS_SEPARATOR: char = ';';
+
 /// 命令注册表
 pub struct CommandRegistry {
     commands: Vec<CommandDef>,
     // 命令名/别名 -> 命令索引的映射
     lookup: HashMap<String, usize>,
+    // 用户在配置中定义的别名 -> 展开的命令序列
+    user_aliases: HashMap<&'static str, String>,
 }
 
 impl CommandRegistry {
@@ -25,6 +31,7 @@ impl CommandRegistry {
         let mut registry = Self {
             commands: Vec::new(),
             lookup: HashMap::new(),
+            user_aliases: HashMap::new(),
         };
         registry.register_default_commands();
         registry
@@ -38,6 +45,16 @@ impl CommandRegistry {
             aliases: vec!["q"],
             description: "退出应用",
         });
+        self.register(CommandDef {
+            name: "write",
+            aliases: vec!["w"],
+            description: "确认保存状态（所有修改都会立即写入磁盘）",
+        });
+        self.register(CommandDef {
+            name: "write-quit",
+            aliases: vec!["wq", "x"],
+            description: "退出应用（修改已实时保存）",
+        });
 
         // 项目管理命令
         self.register(CommandDef {
@@ -70,6 +87,24 @@ impl CommandRegistry {
             description: "重命名项目",
         });
 
+        self.register(CommandDef {
+            name: "project-settings",
+            aliases: vec!["settings"],
+            description: "编辑项目设置：名称、说明、状态列表（增删、排序）、WIP 上限和颜色",
+        });
+
+        self.register(CommandDef {
+            name: "project-hide",
+            aliases: vec!["hide"],
+            description: "隐藏项目（软删除，不删除文件）",
+        });
+
+        self.register(CommandDef {
+            name: "project-info",
+            aliases: vec!["copy-info"],
+            description: "复制项目信息（名称、看板路径）到剪贴板",
+        });
+
         // 任务管理命令
         self.register(CommandDef {
             name: "task-new",
@@ -107,6 +142,96 @@ impl CommandRegistry {
             description: "用外部编辑器编辑任务",
         });
 
+        self.register(CommandDef {
+            name: "task-from-clipboard",
+            aliases: vec!["paste-task"],
+            description: "用剪贴板内容在当前列新建任务（第一行为标题，其余为正文）",
+        });
+
+        self.register(CommandDef {
+            name: "task-fields",
+            aliases: vec!["fields"],
+            description: "编辑任务的自定义字段",
+        });
+
+        self.register(CommandDef {
+            name: "task-copy",
+            aliases: vec!["yank"],
+            description: "复制任务到剪贴板",
+        });
+
+        self.register(CommandDef {
+            name: "yank-column",
+            aliases: vec!["yc"],
+            description: "把当前列的任务标题复制为 Markdown 列表（参数 ids 附带 ID，check 附带复选框）",
+        });
+
+        self.register(CommandDef {
+            name: "tag",
+            aliases: vec![],
+            description: "增删标签：+标签 添加，-标签 删除（有标记的任务时批量应用，Tab 补全已有标签）",
+        });
+
+        self.register(CommandDef {
+            name: "tags",
+            aliases: vec!["tag-cloud"],
+            description: "标签概览：当前项目的全部标签和任务数，Enter 按标签筛选面板",
+        });
+
+        self.register(CommandDef {
+            name: "estimate",
+            aliases: vec!["est"],
+            description: "设置当前任务的估算点数（不带参数清除）",
+        });
+
+        self.register(CommandDef {
+            name: "task-renumber",
+            aliases: vec!["renumber"],
+            description: "按顺序重新编号任务 ID（--dry-run 仅预览）",
+        });
+
+        self.register(CommandDef {
+            name: "done-cleanup",
+            aliases: vec!["archive-done"],
+            description: "归档完成列中超过 N 天未变动的任务（默认 30 天）",
+        });
+
+        self.register(CommandDef {
+            name: "backup",
+            aliases: vec![],
+            description: "备份当前项目到 ~/.kanban/backups/",
+        });
+
+        self.register(CommandDef {
+            name: "reveal",
+            aliases: vec!["files"],
+            description: "在文件管理器中打开当前项目目录",
+        });
+
+        self.register(CommandDef {
+            name: "shell",
+            aliases: vec![],
+            description: "在当前项目目录中打开 $SHELL，退出 shell 后返回看板",
+        });
+
+        self.register(CommandDef {
+            name: "peek",
+            aliases: vec![],
+            description: "只读预览另一个项目的看板（各列任务数和前几个任务），不改变面板布局",
+        });
+
+        self.register(CommandDef {
+            name: "journal",
+            aliases: vec!["log"],
+            description: "查看当前项目的任务变更日志",
+        });
+
+        self.register(CommandDef {
+            name: "task-import",
+            aliases: vec!["import"],
+            description: "导入 org / Taskwarrior 任务（:import <文件>）",
+        });
+
         // 任务优先级命令
         self.register(CommandDef {
             name: "priority-high",
@@ -128,11 +253,119 @@ impl CommandRegistry {
 
         self.register(CommandDef {
             name: "priority-none",
-            aliases: vec!["pn", "pri-none", "no-priority"],
+            aliases: vec!["pri-none", "no-priority"],
             description: "移除任务优先级",
         });
 
+        self.register(CommandDef {
+            name: "diff-panes",
+            aliases: vec!["diff"],
+            description: "比较当前面板与另一个面板的项目，标出对方没有的任务（参数: 面板编号 / off）",
+        });
+
+        self.register(CommandDef {
+            name: "last",
+            aliases: vec![],
+            description: "跳到当前项目中最近创建或编辑的任务（同 g;）",
+        });
+
+        self.register(CommandDef {
+            name: "lock",
+            aliases: vec![],
+            description: "立即隐藏看板，按任意键恢复（idle_lock_minutes 设置无操作自动隐藏）",
+        });
+
+        self.register(CommandDef {
+            name: "note",
+            aliases: vec!["add-note"],
+            description: "在任务的 Notes 小节追加带时间戳的笔记（不带参数时打开输入框）",
+        });
+
+        self.register(CommandDef {
+            name: "task-color",
+            aliases: vec!["color"],
+            description: "设置卡片颜色（red / orange / yellow / green / cyan / blue / purple / none）",
+        });
+
+        self.register(CommandDef {
+            name: "filter-priority",
+            aliases: vec!["fp"],
+            description: "按优先级筛选当前面板（high / medium / all）",
+        });
+
         // 窗口管理命令
+        self.register(CommandDef {
+            name: "zoom-column",
+            aliases: vec!["zoom", "zc"],
+            description: "聚焦当前列（全宽显示卡片和正文预览，再次执行恢复）",
+        });
+
+        self.register(CommandDef {
+            name: "column-layout",
+            aliases: vec!["auto-width"],
+            description: "当前面板的列宽策略（auto 按任务数分配 / equal 等宽，省略时切换）",
+        });
+
+        self.register(CommandDef {
+            name: "focus",
+            aliases: vec!["focus-mode"],
+            description: "专注模式：隐藏完成列，其他面板折叠为窄条（再次执行恢复）",
+        });
+
+        self.register(CommandDef {
+            name: "report",
+            aliases: vec![],
+            description: "报告：aging 按停留时间列出未完成任务（:report aging 文件.md 导出）",
+        });
+
+        self.register(CommandDef {
+            name: "spell-add",
+            aliases: vec![],
+            description: "把单词加入当前项目的拼写词典（.kanban 下的 dictionary.txt）",
+        });
+
+        self.register(CommandDef {
+            name: "open-url",
+            aliases: vec!["ou"],
+            description: "用系统默认程序打开选中任务正文中的第 N 个链接（:open-url 2）",
+        });
+
+        self.register(CommandDef {
+            name: "script",
+            aliases: vec![],
+            description: "用户脚本：:script list 列出，:script run <名称> 对当前项目执行",
+        });
+
+        self.register(CommandDef {
+            name: "hints",
+            aliases: vec![],
+            description: "切换面板底边的常用键位提示",
+        });
+
+        self.register(CommandDef {
+            name: "version",
+            aliases: vec!["about"],
+            description: "关于：版本、构建信息（提交、日期、功能）和配置/数据路径",
+        });
+
+        self.register(CommandDef {
+            name: "changelog",
+            aliases: vec!["whats-new"],
+            description: "显示本版本的新功能说明（升级后启动时自动显示），:changelog all 查看完整日志",
+        });
+
+        self.register(CommandDef {
+            name: "tutor",
+            aliases: vec![],
+            description: "交互式教程：在练习项目中学习导航、新建、移动和分屏（再次执行退出）",
+        });
+
+        self.register(CommandDef {
+            name: "redact",
+            aliases: vec!["screen-share"],
+            description: "屏幕共享模式：隐藏所有面板的任务标题、正文和标签（再次执行恢复）",
+        });
+
         self.register(CommandDef {
             name: "split-horizontal",
             aliases: vec!["sh", "hsplit"],
@@ -157,6 +390,36 @@ impl CommandRegistry {
             description: "切换到下一个窗口",
         });
 
+        self.register(CommandDef {
+            name: "pane-maximize",
+            aliases: vec!["maximize", "max"],
+            description: "最大化当前面板（再次执行恢复布局）",
+        });
+
+        self.register(CommandDef {
+            name: "pane-grab",
+            aliases: vec!["grab"],
+            description: "抓取当前面板，再用方向键与相邻面板交换项目",
+        });
+
+        self.register(CommandDef {
+            name: "pane-lock",
+            aliases: vec!["readonly", "ro"],
+            description: "锁定/解锁当前面板为只读，防止误改参考项目",
+        });
+
+        self.register(CommandDef {
+            name: "home",
+            aliases: vec![],
+            description: "恢复主页布局（同 Space w 0），丢弃临时增加的分屏",
+        });
+
+        self.register(CommandDef {
+            name: "home-set",
+            aliases: vec!["set-home"],
+            description: "把当前分屏布局保存为主页布局",
+        });
+
         // 导航命令
         self.register(CommandDef {
             name: "focus-left",
@@ -182,6 +445,56 @@ impl CommandRegistry {
             description: "聚焦下方面板",
         });
 
+        // 状态管理命令
+
+        self.register(CommandDef {
+            name: "status-new",
+            aliases: vec!["sn"],
+            description: "在当前项目中新建状态列",
+        });
+
+        self.register(CommandDef {
+            name: "status-rename",
+            aliases: vec!["sr"],
+            description: "重命名当前状态",
+        });
+
+        self.register(CommandDef {
+            name: "status-edit",
+            aliases: vec!["se"],
+            description: "编辑当前状态的显示名",
+        });
+
+        self.register(CommandDef {
+            name: "status-move-left",
+            aliases: vec!["sml"],
+            description: "当前状态左移一列",
+        });
+
+        self.register(CommandDef {
+            name: "status-move-right",
+            aliases: vec!["smr"],
+            description: "当前状态右移一列",
+        });
+
+        self.register(CommandDef {
+            name: "status-move-first",
+            aliases: vec![],
+            description: "当前状态移到最左侧",
+        });
+
+        self.register(CommandDef {
+            name: "status-move-last",
+            aliases: vec![],
+            description: "当前状态移到最右侧",
+        });
+
+        self.register(CommandDef {
+            name: "status-delete",
+            aliases: vec!["sd"],
+            description: "删除当前状态",
+        });
+
         // 重新加载命令
         self.register(CommandDef {
             name: "reload",
@@ -195,6 +508,25 @@ impl CommandRegistry {
             description: "重新加载所有项目",
         });
 
+        self.register(CommandDef {
+            name: "revert-external",
+            aliases: vec!["undo-external"],
+            description: "撤销最近一次检测到的外部修改（编辑器或 AI 助手直接改动的任务文件）",
+        });
+
+        // 配置命令
+        self.register(CommandDef {
+            name: "set",
+            aliases: vec![],
+            description: "修改配置：set editor <命令> / set viewer <命令>",
+        });
+
+        self.register(CommandDef {
+            name: "config-detect",
+            aliases: vec!["detect"],
+            description: "重新检测编辑器和预览器",
+        });
+
         // 帮助命令
         self.register(CommandDef {
             name: "help",
@@ -218,38 +550,82 @@ impl CommandRegistry {
         self.commands.push(cmd);
     }
 
-    /// 根据输入查找匹配的命令（支持模糊匹配）
-    #[allow(dead_code)]
-    pub fn find_matches(&self, input: &str) -> Vec<&CommandDef> {
-        if input.is_empty() {
-            // 如果输入为空，返回所有命令
-            return self.commands.iter().collect();
+    /// 注册配置文件 `[aliases]` 中的自定义别名，返回因名称无效、与已有命令冲突
+    /// 或内容为空而被忽略的别名
+    pub fn register_user_aliases(&mut self, aliases: &BTreeMap<String, String>) -> Vec<String> {
+        let mut skipped = Vec::new();
+        for (name, expansion) in aliases {
+            let (name, expansion) = (name.trim(), expansion.trim());
+            if name.is_empty()
+                || name.contains(char::is_whitespace)
+                || self.lookup.contains_key(name)
+                || expansion
+                    .split(ALIAS_SEPARATOR)
+                    .all(|step| step.trim().is_empty())
+            {
+                skipped.push(name.to_string());
+                continue;
+            }
+
+            // 别名只在启动时注册一次，命令定义需要 'static 字符串
+            let name: &'static str = name.to_string().leak();
+            let description: &'static str = format!("别名 → {}", expansion).leak();
+            self.user_aliases.insert(name, expansion.to_string());
+            self.register(CommandDef {
+                name,
+                aliases: Vec::new(),
+                description,
+            });
         }
+        skipped
+    }
 
-        let input_lower = input.to_lowercase();
-        let mut matches = Vec::new();
+    /// 用户别名展开后的命令序列，不是用户别名时返回 None
+    pub fn user_alias(&self, name: &str) -> Option<Vec<String>> {
+        let expansion = self.user_aliases.get(name)?;
+        Some(
+            expansion
+                .split(ALIAS_SEPARATOR)
+                .map(str::trim)
+                .filter(|step| !step.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )
+    }
 
-        for cmd in &self.commands {
-            // 检查主命令名是否匹配
-            if cmd.name.starts_with(&input_lower) {
-                matches.push(cmd);
-                continue;
-            }
+    /// 是否是用户定义的别名
+    pub fn is_user_alias(&self, name: &str) -> bool {
+        self.user_aliases.contains_key(name)
+    }
 
-            // 检查别名是否匹配
-            let alias_match = cmd
-                .aliases
-                .iter()
-                .any(|alias| alias.starts_with(&input_lower));
-            if alias_match {
-                matches.push(cmd);
-            }
+    /// 根据输入查找匹配的命令（模糊匹配命令名和别名，按得分排序）
+    pub fn find_matches(&self, input: &str) -> Vec<&CommandDef> {
+        if input.is_empty() {
+            // 如果输入为空，返回所有命令
+            return self.commands.iter().collect();
         }
 
-        // 按命令名长度排序（优先显示短命令）
-        matches.sort_by_key(|cmd| cmd.name.len());
+        let mut matches: Vec<(i64, &CommandDef)> = self
+            .commands
+            .iter()
+            .filter_map(|cmd| {
+                std::iter::once(cmd.name)
+                    .chain(cmd.aliases.iter().copied())
+                    .filter_map(|candidate| fuzzy_match(input, candidate))
+                    .map(|m| m.score)
+                    .max()
+                    .map(|score| (score, cmd))
+            })
+            .collect();
+
+        // 得分高的在前，同分时优先显示短命令
+        matches.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.name.len().cmp(&b.name.len()))
+        });
 
-        matches
+        matches.into_iter().map(|(_, cmd)| cmd).collect()
     }
 
     /// 精确查找命令（用于执行）
@@ -290,6 +666,69 @@ mod tests {
         // 测试空输入
         let matches = registry.find_matches("");
         assert!(!matches.is_empty());
+
+        // 测试模糊匹配：非连续字符也能命中，边界匹配排在前面
+        let matches = registry.find_matches("tren");
+        assert_eq!(matches[0].name, "task-renumber");
+    }
+
+    #[test]
+    fn user_aliases_expand_to_command_sequences() {
+        let mut registry = CommandRegistry::new();
+        let aliases = BTreeMap::from([
+            (
+                "triage".to_string(),
+                "filter-priority high; zoom".to_string(),
+            ),
+            ("q".to_string(), "reload".to_string()),
+            ("empty".to_string(), " ; ".to_string()),
+        ]);
+
+        let skipped = registry.register_user_aliases(&aliases);
+
+        assert_eq!(skipped, vec!["empty", "q"]);
+        assert_eq!(
+            registry.user_alias("triage"),
+            Some(vec!["filter-priority high".to_string(), "zoom".to_string()])
+        );
+        assert!(registry.is_user_alias("triage"));
+        assert!(!registry.is_user_alias("quit"));
+        assert_eq!(registry.find_exact("q").map(|cmd| cmd.name), Some("quit"));
+        assert!(
+            registry
+                .find_matches("tri")
+                .iter()
+                .any(|cmd| cmd.name == "triage")
+        );
+    }
+
+    #[test]
+    fn names_and_aliases_are_unique() {
+        let registry = CommandRegistry::new();
+        let mut seen = std::collections::HashSet::new();
+        for cmd in registry.all_commands() {
+            for key in std::iter::once(&cmd.name).chain(&cmd.aliases) {
+                assert!(seen.insert(*key), "重复的命令名或别名: {}", key);
+            }
+        }
+    }
+
+    #[test]
+    fn space_menu_actions_have_commands() {
+        let registry = CommandRegistry::new();
+        for name in [
+            "project-hide",
+            "project-info",
+            "task-copy",
+            "pane-maximize",
+            "pane-grab",
+            "status-new",
+            "status-move-last",
+            "status-delete",
+            "reload-all",
+        ] {
+            assert!(registry.find_exact(name).is_some(), "缺少命令: {}", name);
+        }
     }
 
     #[test]
@@ -301,6 +740,11 @@ mod tests {
 
         // 测试别名
         assert!(registry.find_exact("q").is_some());
+        assert_eq!(registry.find_exact("w").map(|cmd| cmd.name), Some("write"));
+        assert_eq!(
+            registry.find_exact("wq").map(|cmd| cmd.name),
+            Some("write-quit")
+        );
         assert_eq!(registry.find_exact("q").unwrap().name, "quit");
 
         // 测试不存在的命令
@@ -1,3 +1,5 @@
+use crate::app::PriorityFilter;
+
 /// 应用命令枚举
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -24,6 +26,14 @@ pub enum Command {
     FocusDown,
     /// 最大化/恢复当前面板
     MaximizePane,
+    /// 抓取当前面板的项目，随后按方向键与相邻面板交换
+    GrabPane,
+    /// 锁定/解锁当前面板（只读参考面板，禁止修改其中的项目）
+    TogglePaneReadOnly,
+    /// 恢复主页布局
+    RestoreHomeLayout,
+    /// 把当前布局保存为主页布局
+    SetHomeLayout,
 
     // ===== 任务操作 =====
     /// 将任务移到左边的状态列
@@ -50,6 +60,8 @@ pub enum Command {
     NewTask,
     /// 用外部编辑器创建新任务
     NewTaskInEditor,
+    /// 在当前列底部连续快速添加任务
+    QuickAdd,
     /// 编辑当前任务
     EditTask,
     /// 用外部编辑器编辑任务
@@ -60,10 +72,32 @@ pub enum Command {
     ViewTaskExternal,
     /// 复制任务到剪贴板
     CopyTask,
+    /// 用剪贴板内容在当前列新建任务（第一行为标题，其余为正文）
+    NewTaskFromClipboard,
+    /// 把选中的任务移动或复制到其他面板的项目（选择面板编号和目标状态）
+    SendTaskToPane,
+    /// 展开/收起选中任务卡片（在看板中显示正文）
+    ToggleTaskExpand,
     /// 设置任务优先级
     SetTaskPriority(String), // "high", "medium", "low", "none"
+    /// 设置任务卡片颜色（`ui::theme::CARD_COLORS` 中的名称，"none" 清除）
+    SetTaskColor(String),
+    /// 切换聚焦面板的优先级筛选（再次选择同一筛选时恢复显示全部）
+    TogglePriorityFilter(PriorityFilter),
+    /// 按选中任务的第 N 个标签（从 0 开始）筛选聚焦面板，再次选择同一标签时取消
+    ToggleTagFilter(usize),
+    /// 跳到聚焦项目中最近创建或编辑的任务（`g;` / `:last`）
+    JumpToLastEdited,
+    /// 在任务正文的 `## Notes` 小节追加一条带时间戳的笔记
+    AddTaskNote,
     /// 编辑任务标签
     EditTags,
+    /// 编辑任务的自定义字段（项目 `.kanban.toml` 中定义）
+    EditFields,
+    /// 重新编号当前项目的任务 ID
+    RenumberTasks,
+    /// 撤销刚移到完成列的任务（完成提示显示期间）
+    UndoCompletion,
 
     // ===== 列宽调整 =====
     /// 增加当前列宽度
@@ -74,6 +108,12 @@ pub enum Command {
     ResetColumnWidths,
     /// 切换最大化当前列
     ToggleMaximizeColumn,
+    /// 切换聚焦单列（全宽显示卡片和正文预览）
+    ToggleZoomColumn,
+    /// 切换专注模式（隐藏完成列，其他面板折叠为窄条）
+    ToggleFocusMode,
+    /// 切换屏幕共享模式（隐藏任务标题和正文）
+    ToggleRedact,
 
     // ===== 项目操作 (Space p 前缀) =====
     /// 打开项目
@@ -92,6 +132,12 @@ pub enum Command {
     RenameProject,
     /// 复制项目信息到剪贴板
     CopyProjectInfo,
+    /// 编辑项目设置（名称、说明、状态列表）
+    ProjectSettings,
+    /// 在文件管理器中打开项目目录
+    RevealProject,
+    /// 在项目目录中打开 shell，退出后返回
+    OpenShell,
     /// 重新加载当前项目
     ReloadCurrentProject,
     /// 重新加载所有项目
@@ -126,10 +172,104 @@ pub enum Command {
     EnterMarkSelect,
 
     // ===== 模式切换 =====
-    // /// 进入命令模式 - 已注释
-    // EnterCommandMode,
+    /// 进入命令模式
+    EnterCommandMode,
     /// 进入正常模式
     EnterNormalMode,
     /// 取消当前操作
     Cancel,
 }
+
+impl Command {
+    /// 键位速查中显示的简短说明，未绑定单键的命令返回 None
+    pub fn description(&self) -> Option<&'static str> {
+        let text = match self {
+            Command::TaskDown => "下一个任务",
+            Command::TaskUp => "上一个任务",
+            Command::ColumnLeft => "左边的列",
+            Command::ColumnRight => "右边的列",
+            Command::MoveTaskLeft => "任务移到左列",
+            Command::MoveTaskRight => "任务移到右列",
+            Command::MoveTaskUp => "任务在列内上移",
+            Command::MoveTaskDown => "任务在列内下移",
+            Command::ToggleTaskMark => "标记/取消标记",
+            Command::ToggleTaskExpand => "展开/收起卡片",
+            Command::EnterMarkSelect => "标记管理",
+            Command::NewTask => "新建任务",
+            Command::NewTaskInEditor => "用编辑器新建任务",
+            Command::QuickAdd => "快速添加任务",
+            Command::EditTask => "编辑标题",
+            Command::EditTaskInEditor => "用编辑器编辑任务",
+            Command::ViewTask => "预览任务",
+            Command::ViewTaskExternal => "外部预览任务",
+            Command::CopyTask => "复制任务",
+            Command::NewTaskFromClipboard => "从剪贴板新建任务",
+            Command::SendTaskToPane => "发送任务到其他面板",
+            Command::DeleteTask => "删除任务",
+            Command::UndoCompletion => "撤销刚完成的任务",
+            Command::AddTaskNote => "追加笔记",
+            Command::JumpToLastEdited => "跳到最近编辑的任务",
+            Command::EditTags => "编辑标签",
+            Command::EditFields => "编辑自定义字段",
+            Command::EnterSearch => "搜索任务",
+            Command::EnterStatusSelect => "选择目标状态",
+            Command::NewLocalProject => "新建本地项目",
+            Command::NewGlobalProject => "新建全局项目",
+            Command::IncreaseColumnWidth => "增加列宽",
+            Command::DecreaseColumnWidth => "减少列宽",
+            Command::ResetColumnWidths => "重置列宽",
+            Command::ToggleMaximizeColumn => "最大化/恢复列",
+            Command::ToggleZoomColumn => "聚焦单列",
+            Command::TogglePriorityFilter(PriorityFilter::High) => "只看高优先级",
+            Command::TogglePriorityFilter(PriorityFilter::Medium) => "只看中及以上优先级",
+            Command::TogglePriorityFilter(PriorityFilter::All) => "显示全部优先级",
+            Command::MoveStatusLeft => "状态列左移",
+            Command::MoveStatusRight => "状态列右移",
+            Command::MoveStatusToFirst => "状态列移到最左",
+            Command::MoveStatusToLast => "状态列移到最右",
+            Command::EnterCommandMode => "命令模式",
+            Command::EnterNormalMode => "取消/返回",
+            _ => return None,
+        };
+        Some(text)
+    }
+    /// 是否会修改项目（任务、状态列或项目本身）或更换面板中的项目，只读面板中拒绝执行
+    pub fn modifies_project(&self) -> bool {
+        matches!(
+            self,
+            Command::MoveTaskLeft
+                | Command::MoveTaskRight
+                | Command::MoveTaskUp
+                | Command::MoveTaskDown
+                | Command::DeleteTask
+                | Command::NewTask
+                | Command::NewTaskInEditor
+                | Command::QuickAdd
+                | Command::EditTask
+                | Command::EditTaskInEditor
+                | Command::NewTaskFromClipboard
+                | Command::SendTaskToPane
+                | Command::SetTaskPriority(_)
+                | Command::SetTaskColor(_)
+                | Command::AddTaskNote
+                | Command::EditTags
+                | Command::EditFields
+                | Command::RenumberTasks
+                | Command::OpenProject
+                | Command::DeleteProject
+                | Command::RenameProject
+                | Command::ProjectSettings
+                | Command::CreateStatus
+                | Command::RenameStatus
+                | Command::DeleteStatus
+                | Command::MoveStatusLeft
+                | Command::MoveStatusRight
+                | Command::MoveStatusToFirst
+                | Command::MoveStatusToLast
+                | Command::EditStatusDisplay
+                | Command::EnterStatusSelect
+                | Command::EnterMarkSelect
+                | Command::GrabPane
+        )
+    }
+}
@@ -0,0 +1,151 @@
+/// 输入法自动切换
+///
+/// 进入文本输入（输入对话框、快速添加、搜索）时恢复上次使用的输入法，离开时记下当前输入法
+/// 并切换到英文，避免普通模式的按键被中文输入法拦截。默认关闭（`ime_auto_switch = true` 开启）。
+/// 切换命令可以在配置中自定义；未配置时 macOS 使用 `im-select`，Linux 使用 `fcitx5-remote`，
+/// 找不到切换工具的平台上什么也不做。
+use crate::config::Config;
+use std::process::{Command, Stdio};
+
+/// 切换命令中代表输入法 ID 的占位符
+const PLACEHOLDER: &str = "{}";
+
+/// 查询、切换输入法的命令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImeCommands {
+    /// 输出当前输入法 ID 的命令
+    pub query: String,
+    /// 切换输入法的命令，`{}` 替换为输入法 ID
+    pub switch: String,
+    /// 普通模式使用的英文输入法 ID
+    pub english: String,
+}
+
+impl ImeCommands {
+    /// 根据配置确定切换命令：未开启、平台不支持或找不到工具时返回 None
+    pub fn from_config(config: &Config) -> Option<Self> {
+        resolve(
+            config,
+            std::env::consts::OS,
+            crate::config::command_available,
+        )
+    }
+
+    fn switch_command(&self, id: &str) -> String {
+        self.switch.replace(PLACEHOLDER, id)
+    }
+}
+
+fn resolve(config: &Config, os: &str, available: impl Fn(&str) -> bool) -> Option<ImeCommands> {
+    if !config.ime_auto_switch {
+        return None;
+    }
+    let (query, switch, english) = match os {
+        "macos" => ("im-select", "im-select {}", "com.apple.keylayout.ABC"),
+        "linux" => ("fcitx5-remote -n", "fcitx5-remote -s {}", "keyboard-us"),
+        _ => ("", "", ""),
+    };
+    let commands = ImeCommands {
+        query: config
+            .ime_query_command
+            .clone()
+            .unwrap_or(query.to_string()),
+        switch: config
+            .ime_switch_command
+            .clone()
+            .unwrap_or(switch.to_string()),
+        english: config.ime_english.clone().unwrap_or(english.to_string()),
+    };
+    let usable = !commands.english.is_empty()
+        && commands.switch.contains(PLACEHOLDER)
+        && available(&commands.query)
+        && available(&commands.switch);
+    usable.then_some(commands)
+}
+
+/// 输入法切换状态
+#[derive(Debug, Default)]
+pub struct ImeState {
+    commands: Option<ImeCommands>,
+    /// 离开文本输入时记下的输入法
+    saved: Option<String>,
+}
+
+impl ImeState {
+    pub fn new(commands: Option<ImeCommands>) -> Self {
+        Self {
+            commands,
+            saved: None,
+        }
+    }
+
+    /// 进入文本输入：恢复上次在输入框中使用的输入法
+    pub fn enter_dialog(&mut self) {
+        let (Some(commands), Some(saved)) = (&self.commands, &self.saved) else {
+            return;
+        };
+        if saved != &commands.english {
+            run(&commands.switch_command(saved));
+        }
+    }
+
+    /// 离开文本输入：记下当前输入法并切换到英文
+    pub fn exit_dialog(&mut self) {
+        let Some(commands) = &self.commands else {
+            return;
+        };
+        if let Some(current) = query(&commands.query) {
+            self.saved = Some(current);
+        }
+        run(&commands.switch_command(&commands.english));
+    }
+}
+
+fn command(cmd: &str) -> Option<Command> {
+    let mut parts = cmd.split_whitespace();
+    let mut command = Command::new(parts.next()?);
+    command
+        .args(parts)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null());
+    Some(command)
+}
+
+fn query(cmd: &str) -> Option<String> {
+    let output = command(cmd)?.output().ok()?;
+    let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !id.is_empty()).then_some(id)
+}
+
+fn run(cmd: &str) {
+    if let Some(mut command) = command(cmd) {
+        let _ = command.stdout(Stdio::null()).status();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_platform_defaults_and_overrides() {
+        let mut config = Config::default();
+        assert_eq!(resolve(&config, "macos", |_| true), None);
+
+        config.ime_auto_switch = true;
+        let mac = resolve(&config, "macos", |_| true).unwrap();
+        assert_eq!(
+            mac.switch_command(&mac.english),
+            "im-select com.apple.keylayout.ABC"
+        );
+        assert_eq!(resolve(&config, "linux", |_| false), None);
+        assert_eq!(resolve(&config, "windows", |_| true), None);
+
+        config.ime_query_command = Some("ibus engine".to_string());
+        config.ime_switch_command = Some("ibus engine {}".to_string());
+        config.ime_english = Some("xkb:us::eng".to_string());
+        let custom = resolve(&config, "windows", |_| true).unwrap();
+        assert_eq!(custom.query, "ibus engine");
+        assert_eq!(custom.switch_command("rime"), "ibus engine rime");
+    }
+}
@@ -1 +1,432 @@
-// Helix-style keybindings
+/// 键位速查（Ctrl+/ 浮层）
+///
+/// 普通模式的键位直接从 `match_key_sequence` 探测生成，保证与实际键位一致；
+/// 其他模式的按键分散在各自的处理函数中，这里按模式列出。
+use super::Command;
+use super::keyboard::{is_pending_key_sequence, match_key_sequence};
+use crate::app::{App, Mode};
+use crate::models::Project;
+use crate::ui::dialogs::DialogType;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::OnceLock;
+
+/// 一条键位提示
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHint {
+    pub keys: String,
+    pub description: String,
+}
+
+impl KeyHint {
+    fn new(keys: &str, description: &str) -> Self {
+        Self {
+            keys: keys.to_string(),
+            description: description.to_string(),
+        }
+    }
+}
+
+/// 是否是切换键位速查的按键（Ctrl+/，部分终端上报为 Ctrl+_ 或 Ctrl+7）
+pub fn is_cheat_sheet_key(key: KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(
+            key.code,
+            KeyCode::Char('/') | KeyCode::Char('_') | KeyCode::Char('7')
+        )
+}
+
+/// 当前上下文的速查标题和键位
+pub fn cheat_sheet(app: &App) -> (&'static str, Vec<KeyHint>) {
+    let hints = |list: &[(&str, &str)]| {
+        list.iter()
+            .map(|(keys, description)| KeyHint::new(keys, description))
+            .collect()
+    };
+
+    match app.mode {
+        Mode::Normal if app.grabbed_pane.is_some() => (
+            "抓取面板",
+            hints(&[("h/j/k/l", "与相邻面板交换项目"), ("Esc", "放下")]),
+        ),
+        Mode::Normal => ("普通模式", normal_mode_hints()),
+        Mode::Command => (
+            "命令模式",
+            hints(&[
+                ("Enter", "执行命令"),
+                ("Tab/S-Tab", "切换补全项"),
+                ("Esc", "取消"),
+                ("←/→", "移动光标"),
+                ("Home/End", "行首/行尾"),
+                ("Alt+b/f", "按单词移动"),
+                ("Ctrl+W", "删除前一个单词"),
+                ("Ctrl+U/K", "删除到行首/行尾"),
+            ]),
+        ),
+        Mode::Dialog => dialog_hints(app.dialog.as_ref()),
+        Mode::Help => ("帮助", hints(&[("Esc/?", "关闭帮助")])),
+        Mode::SpaceMenu => (
+            "命令菜单",
+            hints(&[
+                ("字母", "执行菜单项"),
+                ("↑/↓", "上下选择"),
+                ("Enter", "执行选中项"),
+                ("Esc", "返回主菜单/关闭"),
+            ]),
+        ),
+        Mode::Preview => (
+            "预览",
+            hints(&[
+                ("↑/↓", "滚动"),
+                ("+/-", "调整弹窗大小"),
+                ("Tab/S-Tab", "选择链接"),
+                ("Enter", "打开选中的链接"),
+                ("Esc", "关闭预览"),
+            ]),
+        ),
+        Mode::Search => (
+            "搜索",
+            hints(&[
+                ("输入", "搜索 ID 或标题"),
+                ("Enter", "进入选择/跳转"),
+                ("j/k", "下一个/上一个匹配"),
+                ("h/l", "上一列/下一列的匹配"),
+                ("Esc", "返回输入/退出"),
+            ]),
+        ),
+        Mode::StatusSelect => (
+            "选择状态",
+            hints(&[
+                ("输入", "匹配状态名"),
+                ("1-9", "按序号选择"),
+                ("h/l", "上一个/下一个"),
+                ("Enter", "移动到该状态"),
+                ("Esc", "取消"),
+            ]),
+        ),
+        Mode::MarkSelect => (
+            "标记管理",
+            hints(&[
+                ("a", "标记本列全部"),
+                ("n", "清除本列标记"),
+                ("i", "反选本列"),
+                ("x", "标记当前任务"),
+                ("s", "批量移动到状态"),
+                ("h/j/k/l", "移动选择"),
+                ("Esc", "退出"),
+            ]),
+        ),
+        Mode::QuickAdd => (
+            "快速添加",
+            hints(&[
+                ("输入", "任务标题"),
+                ("Enter", "创建并继续"),
+                ("Esc", "关闭"),
+            ]),
+        ),
+        Mode::TaskSelect => ("任务选择", hints(&[("↑/↓", "选择任务"), ("Esc", "返回")])),
+        Mode::TagOverview => (
+            "标签概览",
+            hints(&[
+                ("j/k", "选择标签"),
+                ("Enter", "按标签筛选面板"),
+                ("Esc", "关闭"),
+            ]),
+        ),
+        Mode::SendToPane => (
+            "发送到面板",
+            hints(&[
+                ("面板编号", "选择目标面板"),
+                ("c", "切换移动/复制"),
+                ("1-9", "选择状态"),
+                ("Enter", "确认"),
+                ("Esc", "取消"),
+            ]),
+        ),
+        Mode::StatusRename => (
+            "编辑显示名",
+            hints(&[("输入", "新的显示名"), ("Enter", "保存"), ("Esc", "取消")]),
+        ),
+    }
+}
+
+/// 对话框的键位（按对话框类型区分）
+fn dialog_hints(dialog: Option<&DialogType>) -> (&'static str, Vec<KeyHint>) {
+    let list: &[(&str, &str)] = match dialog {
+        Some(DialogType::Confirm { .. }) => &[
+            ("y", "确认"),
+            ("n/Esc", "取消"),
+            ("h/l", "切换 否/是"),
+            ("Enter", "执行所选"),
+        ],
+        Some(DialogType::ProjectGrid { state, .. }) if !state.selecting => {
+            &[("输入", "筛选项目"), ("Enter/Esc", "进入选择")]
+        }
+        Some(DialogType::ProjectGrid { .. }) => &[
+            ("h/j/k/l", "选择项目"),
+            ("H/J/K/L", "调整项目顺序"),
+            ("Enter", "打开项目"),
+            ("Alt+Enter", "在当前面板打开副本"),
+            ("/", "筛选"),
+            ("t", "编辑项目标签"),
+            ("Esc", "关闭"),
+        ],
+        _ => &[
+            ("Ctrl+S", "提交"),
+            ("Esc", "插入→普通模式，普通模式下取消"),
+            ("i/a/o", "进入插入模式"),
+            (":w / :q", "普通模式下提交 / 取消"),
+        ],
+    };
+    (
+        "对话框",
+        list.iter()
+            .map(|(keys, description)| KeyHint::new(keys, description))
+            .collect(),
+    )
+}
+
+/// 从普通模式键位表探测出所有绑定，同一命令的多个按键合并为一条
+fn normal_mode_hints() -> Vec<KeyHint> {
+    let mut hints: Vec<KeyHint> = normal_mode_bindings()
+        .iter()
+        .filter_map(|(cmd, labels)| {
+            cmd.description()
+                .map(|description| KeyHint::new(&labels.join("/"), description))
+        })
+        .collect();
+    // 在模式切换之外处理的全局键
+    hints.push(KeyHint::new("#1-9", "按详情条中的标签筛选（Esc 清除）"));
+    hints.push(KeyHint::new("k → r", "在列标题上就地编辑显示名"));
+    hints.push(KeyHint::new("Space", "命令菜单"));
+    hints.push(KeyHint::new("?", "完整帮助"));
+    hints
+}
+
+/// 面板底边的键位提示（`:hints`）：按面板当前的上下文挑出最常用的几个键位，
+/// 按键从键位表中查出，键位改动后提示随之更新
+pub fn pane_hints(app: &App, project: &Project, pane_id: usize) -> Vec<KeyHint> {
+    use Command::*;
+
+    let column = app.selected_column.get(&pane_id).copied().unwrap_or(0);
+    let has_task = project
+        .statuses
+        .get(column)
+        .is_some_and(|status| !app.column_tasks(pane_id, project, &status.name).is_empty());
+    let has_marks = app
+        .marked_tasks
+        .iter()
+        .any(|(project_id, _)| *project_id == project.id);
+
+    // 列标题上的 r 在键位表之外处理
+    if app.column_header_focus == Some(pane_id) {
+        let mut hints = vec![KeyHint::new("r", "改显示名")];
+        hints.extend(keys_for(&[TaskDown]).map(|keys| KeyHint::new(&keys, "回到任务")));
+        return hints;
+    }
+
+    let list: Vec<(Vec<Command>, &str)> = if app.is_pane_read_only(pane_id) {
+        vec![
+            (vec![TaskDown, TaskUp], "选择"),
+            (vec![ColumnLeft, ColumnRight], "切换列"),
+            (vec![ViewTask], "预览"),
+            (vec![EnterSearch], "搜索"),
+            (vec![CopyTask], "复制"),
+        ]
+    } else if has_marks {
+        vec![
+            (vec![ToggleTaskMark], "标记"),
+            (vec![EnterStatusSelect], "批量移动"),
+            (vec![EnterMarkSelect], "标记管理"),
+            (vec![EnterNormalMode], "清除标记"),
+            (vec![TaskDown, TaskUp], "选择"),
+        ]
+    } else if has_task {
+        vec![
+            (vec![EditTask], "编辑"),
+            (vec![MoveTaskLeft, MoveTaskRight], "移动"),
+            (vec![NewTask], "新建"),
+            (vec![ViewTask], "预览"),
+            (vec![EnterStatusSelect], "移到状态"),
+        ]
+    } else {
+        vec![
+            (vec![NewTask], "新建"),
+            (vec![QuickAdd], "快速添加"),
+            (vec![ColumnLeft, ColumnRight], "切换列"),
+            (vec![EnterSearch], "搜索"),
+        ]
+    };
+
+    list.into_iter()
+        .filter_map(|(commands, description)| {
+            keys_for(&commands).map(|keys| KeyHint::new(&keys, description))
+        })
+        .take(5)
+        .collect()
+}
+
+/// 命令的第一个按键，多个命令用 / 连接；有命令没有绑定按键时返回 None
+fn keys_for(commands: &[Command]) -> Option<String> {
+    let keys: Option<Vec<&str>> = commands
+        .iter()
+        .map(|cmd| {
+            normal_mode_bindings()
+                .iter()
+                .find(|(bound, _)| bound == cmd)
+                .and_then(|(_, labels)| labels.first())
+                .map(String::as_str)
+        })
+        .collect();
+    keys.map(|keys| keys.join("/"))
+}
+
+/// 普通模式的所有绑定（命令, 按键），键位表是静态的，只探测一次
+fn normal_mode_bindings() -> &'static [(Command, Vec<String>)] {
+    static BINDINGS: OnceLock<Vec<(Command, Vec<String>)>> = OnceLock::new();
+    BINDINGS.get_or_init(probe_normal_mode_bindings)
+}
+
+fn probe_normal_mode_bindings() -> Vec<(Command, Vec<String>)> {
+    let mut bound: Vec<(Command, Vec<String>)> = Vec::new();
+    let mut bind = |cmd: Command, label: String| {
+        if let Some((_, labels)) = bound.iter_mut().find(|(c, _)| *c == cmd) {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        } else {
+            bound.push((cmd, vec![label]));
+        }
+    };
+
+    for key in probe_keys() {
+        if let Some(cmd) = match_key_sequence(&[], key) {
+            bind(cmd, key_label(key));
+        } else if is_pending_key_sequence(&[], key)
+            && let KeyCode::Char(prefix) = key.code
+        {
+            for second in probe_keys() {
+                if let Some(cmd) = match_key_sequence(&[prefix], second) {
+                    bind(cmd, format!("{}{}", prefix, key_label(second)));
+                }
+            }
+        }
+    }
+
+    bound
+}
+
+/// 探测用的按键：字母、数字、符号、方向键等，以及 Ctrl 组合
+fn probe_keys() -> Vec<KeyEvent> {
+    let mut keys = Vec::new();
+    for c in ('a'..='z').chain('0'..='9').chain("+-=:;".chars()) {
+        keys.push(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    for c in 'A'..='Z' {
+        keys.push(KeyEvent::new(KeyCode::Char(c), KeyModifiers::SHIFT));
+    }
+    for code in [
+        KeyCode::Down,
+        KeyCode::Up,
+        KeyCode::Left,
+        KeyCode::Right,
+        KeyCode::Tab,
+        KeyCode::Esc,
+    ] {
+        keys.push(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+    for c in ('a'..='z').chain('A'..='Z') {
+        keys.push(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+    keys
+}
+
+fn key_label(key: KeyEvent) -> String {
+    let base = match key.code {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        other => format!("{:?}", other),
+    };
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        format!("C-{}", base)
+    } else {
+        base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_mode_hints_come_from_the_keymap() {
+        let hints = normal_mode_hints();
+        let find = |description: &str| {
+            hints
+                .iter()
+                .find(|hint| hint.description == description)
+                .map(|hint| hint.keys.as_str())
+        };
+
+        assert_eq!(find("下一个任务"), Some("j/↓"));
+        assert_eq!(find("最大化/恢复列"), Some("mm"));
+        assert_eq!(find("显示全部优先级"), Some("0/3"));
+        assert_eq!(find("状态列移到最右"), Some("C-L"));
+    }
+
+    #[test]
+    fn pane_hints_follow_the_selection() {
+        use crate::models::{Status, Task};
+        let mut app = crate::app::tests::test_app();
+        let mut project = app.projects[0].clone();
+        project.statuses = vec![Status::new("todo".to_string(), "待办".to_string())];
+        let descriptions = |hints: Vec<KeyHint>| {
+            hints
+                .into_iter()
+                .map(|hint| format!("{} {}", hint.keys, hint.description))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            descriptions(pane_hints(&app, &project, 0))[..2],
+            ["a 新建".to_string(), "o 快速添加".to_string()]
+        );
+
+        project
+            .tasks
+            .push(Task::new(1, "task".to_string(), "todo".to_string()));
+        let hints = descriptions(pane_hints(&app, &project, 0));
+        assert_eq!(hints.len(), 5);
+        assert_eq!(hints[..2], ["e 编辑".to_string(), "H/L 移动".to_string()]);
+
+        app.column_header_focus = Some(0);
+        assert_eq!(descriptions(pane_hints(&app, &project, 0))[0], "r 改显示名");
+    }
+
+    #[test]
+    fn every_bound_normal_command_has_a_description() {
+        for key in probe_keys() {
+            if let Some(cmd) = match_key_sequence(&[], key) {
+                assert!(cmd.description().is_some(), "{:?} 缺少说明", cmd);
+            }
+        }
+    }
+
+    #[test]
+    fn recognises_ctrl_slash_variants() {
+        for c in ['/', '_', '7'] {
+            assert!(is_cheat_sheet_key(KeyEvent::new(
+                KeyCode::Char(c),
+                KeyModifiers::CONTROL
+            )));
+        }
+        assert!(!is_cheat_sheet_key(KeyEvent::new(
+            KeyCode::Char('/'),
+            KeyModifiers::NONE
+        )));
+    }
+}
@@ -1,11 +1,17 @@
-use crate::app::{App, Mode, NotificationLevel, StatusSelectState};
+use crate::app::{
+    App, ColumnLayout, Mode, NotificationLevel, PriorityFilter, SaveError, StatusSelectState,
+};
 use crate::input::Command;
+use crate::job::{JobKind, JobResult};
+use crate::models::field::task_field_values;
 use crate::ui::dialogs::{
-    DialogType, ProjectGridNavigation, ProjectGridOrderMove, ProjectGridState,
-    filter_project_grid_items, navigate_project_grid, normalize_project_tags,
-    project_grid_state_from_projects, reordered_project_grid_state, update_project_grid_item_tags,
+    DialogPurpose, DialogType, ProjectGridNavigation, ProjectGridOrderMove, ProjectGridState,
+    collapsed_group_card, navigate_project_grid, normalize_project_tags,
+    project_grid_state_from_projects, reordered_project_grid_state, toggle_project_grid_group,
+    update_project_grid_item_tags, visible_project_grid_items,
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(350);
@@ -19,9 +25,25 @@ pub fn handle_key_input(app: &mut App, key: KeyEvent) -> bool {
         return true;
     }
 
-    match app.mode {
+    // 后台任务执行期间只响应 Esc（取消），避免在任务写文件时修改项目
+    if let Some(job) = &app.job {
+        if key.code == KeyCode::Esc {
+            job.cancel();
+        }
+        return true;
+    }
+
+    // 键位速查浮层：Ctrl+/ 切换，其他任意按键关闭后照常处理
+    if super::keybindings::is_cheat_sheet_key(key) {
+        app.show_cheat_sheet = !app.show_cheat_sheet;
+        return true;
+    }
+    app.show_cheat_sheet = false;
+
+    let was_typing = is_typing_text(app);
+    let handled = match app.mode {
         Mode::Normal => handle_normal_mode(app, key),
-        // Mode::Command => handle_command_mode(app, key), // 已注释
+        Mode::Command => handle_command_mode(app, key),
         Mode::TaskSelect => handle_task_select_mode(app, key),
         Mode::Dialog => handle_dialog_mode(app, key),
         Mode::Help => handle_help_mode(app, key),
@@ -30,6 +52,31 @@ pub fn handle_key_input(app: &mut App, key: KeyEvent) -> bool {
         Mode::Search => handle_search_mode(app, key),
         Mode::StatusSelect => handle_status_select_mode(app, key),
         Mode::MarkSelect => handle_mark_select_mode(app, key),
+        Mode::QuickAdd => handle_quick_add_mode(app, key),
+        Mode::TagOverview => handle_tag_overview_mode(app, key),
+        Mode::SendToPane => handle_send_to_pane_mode(app, key),
+        Mode::StatusRename => handle_status_rename_mode(app, key),
+    };
+
+    // 进出文本输入时切换输入法（`ime_auto_switch` 关闭或不支持时什么也不做）
+    match (was_typing, is_typing_text(app)) {
+        (false, true) => app.ime.enter_dialog(),
+        (true, false) => app.ime.exit_dialog(),
+        _ => {}
+    }
+    handled
+}
+
+/// 是否正在输入可能包含中文的文本（输入对话框、快速添加、搜索）
+fn is_typing_text(app: &App) -> bool {
+    match app.mode {
+        Mode::QuickAdd | Mode::StatusRename => true,
+        Mode::Search => app.search_state.as_ref().is_some_and(|s| !s.selecting),
+        Mode::Dialog => matches!(
+            app.dialog,
+            Some(DialogType::Input { .. } | DialogType::ProjectTagsInput { .. })
+        ),
+        _ => false,
     }
 }
 
@@ -52,10 +99,30 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> bool {
         return true;
     }
 
-    if key.code == KeyCode::Esc && clear_focused_project_marks(app) {
+    if app.grabbed_pane.is_some() {
+        handle_grabbed_pane_key(app, key);
+        return true;
+    }
+
+    if key.code == KeyCode::Esc && (clear_focused_project_marks(app) || clear_tag_filter(app)) {
         return true;
     }
 
+    // 列标题被选中时：r 就地编辑显示名，Esc 回到第一个任务
+    if app.column_header_focus == Some(app.focused_pane) && app.key_buffer.is_empty() {
+        match key.code {
+            KeyCode::Char('r') => {
+                execute_command(app, Command::EditStatusDisplay);
+                return true;
+            }
+            KeyCode::Esc => {
+                app.column_header_focus = None;
+                return true;
+            }
+            _ => {}
+        }
+    }
+
     // 尝试匹配命令（使用当前缓冲区和新按键）
     if let Some(cmd) = match_key_sequence(&app.key_buffer, key) {
         clear_key_buffer(app);
@@ -82,19 +149,40 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> bool {
     }
 
     clear_key_buffer(app);
+    if let KeyCode::Char(c) = key.code
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        flash_unbound_key(app, c);
+    }
 
     true
 }
 
+/// 在角落短暂提示按键序列没有对应的命令
+fn flash_unbound_key(app: &mut App, c: char) {
+    let sequence = format!("{}{}", app.pending_key_label().unwrap_or_default(), c);
+    app.key_flash = Some((format!("{} 未绑定", sequence), Instant::now()));
+}
+
 fn clear_key_buffer(app: &mut App) {
     app.key_buffer.clear();
     app.key_buffer_started_at = None;
 }
 
-fn is_pending_key_sequence(buffer: &[char], key: KeyEvent) -> bool {
+pub(super) fn is_pending_key_sequence(buffer: &[char], key: KeyEvent) -> bool {
     matches!(
         (buffer, key.code, key.modifiers),
-        ([], KeyCode::Char('m'), KeyModifiers::NONE)
+        (
+            [],
+            KeyCode::Char('m') | KeyCode::Char('g'),
+            KeyModifiers::NONE
+        ) | (
+            [],
+            KeyCode::Char('#'),
+            KeyModifiers::NONE | KeyModifiers::SHIFT
+        )
     )
 }
 
@@ -129,50 +217,74 @@ pub fn flush_pending_key_sequence(app: &mut App) -> bool {
     flush_pending_key_sequence_now(app)
 }
 
-// /// 处理命令模式的按键 - 已注释
-// fn handle_command_mode(app: &mut App, key: KeyEvent) -> bool {
-//     match key.code {
-//         KeyCode::Esc => {
-//             app.mode = Mode::Normal;
-//             app.command_input.clear();
-//             app.completion_selected_index = None;
-//         }
-//         KeyCode::Tab => {
-//             // 下一个补全项
-//             let matches = app.command_registry.find_matches(&app.command_input);
-//             if !matches.is_empty() {
-//                 let current = app.completion_selected_index.unwrap_or(0);
-//                 let next = if current + 1 >= matches.len() { 0 } else { current + 1 };
-//                 app.completion_selected_index = Some(next);
-//             }
-//         }
-//         KeyCode::BackTab => {
-//             // 上一个补全项
-//             let matches = app.command_registry.find_matches(&app.command_input);
-//             if !matches.is_empty() {
-//                 let current = app.completion_selected_index.unwrap_or(0);
-//                 let prev = if current == 0 { matches.len() - 1 } else { current - 1 };
-//                 app.completion_selected_index = Some(prev);
-//             }
-//         }
-//         KeyCode::Enter => {
-//             // 执行命令
-//             let should_continue = execute_text_command(app, &app.command_input.clone());
-//             app.command_input.clear();
-//             app.mode = Mode::Normal;
-//             app.completion_selected_index = None;
-//             return should_continue;
-//         }
-//         KeyCode::Backspace => {
-//             app.command_input.pop();
-//         }
-//         KeyCode::Char(c) => {
-//             app.command_input.push(c);
-//         }
-//         _ => {}
-//     }
-//     true
-// }
+/// 处理命令模式的按键
+fn handle_command_mode(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+            app.command_input.clear();
+            app.completion_selected_index = None;
+        }
+        KeyCode::Tab if complete_tag_argument(app) => {}
+        KeyCode::Tab => {
+            // 下一个补全项
+            let matches = app.command_registry.find_matches(app.command_input.value());
+            if !matches.is_empty() {
+                let current = app.completion_selected_index.unwrap_or(0);
+                let next = if current + 1 >= matches.len() {
+                    0
+                } else {
+                    current + 1
+                };
+                app.completion_selected_index = Some(next);
+            }
+        }
+        KeyCode::BackTab => {
+            // 上一个补全项
+            let matches = app.command_registry.find_matches(app.command_input.value());
+            if !matches.is_empty() {
+                let current = app.completion_selected_index.unwrap_or(0);
+                let prev = if current == 0 {
+                    matches.len() - 1
+                } else {
+                    current - 1
+                };
+                app.completion_selected_index = Some(prev);
+            }
+        }
+        KeyCode::Enter => {
+            // 执行命令：输入不是完整命令名时，使用选中的补全项
+            let mut input = app.command_input.value().trim().to_string();
+            let name = input.split_whitespace().next().unwrap_or("");
+            if !name.is_empty()
+                && app.command_registry.find_exact(name).is_none()
+                && !input.contains(' ')
+                && let Some(cmd) = app
+                    .command_registry
+                    .find_matches(name)
+                    .get(app.completion_selected_index.unwrap_or(0))
+            {
+                input = cmd.name.to_string();
+            }
+
+            app.command_input.clear();
+            app.mode = Mode::Normal;
+            app.completion_selected_index = None;
+            return execute_text_command(app, &input);
+        }
+        KeyCode::Backspace if app.command_input.is_empty() => {
+            // 空输入时退格退出命令模式
+            app.mode = Mode::Normal;
+            app.completion_selected_index = None;
+        }
+        _ => {
+            if app.command_input.handle_key(key) {
+                app.completion_selected_index = None;
+            }
+        }
+    }
+    true
+}
 
 /// 处理任务选择模式的按键
 fn handle_task_select_mode(app: &mut App, key: KeyEvent) -> bool {
@@ -202,12 +314,25 @@ fn handle_dialog_mode(app: &mut App, key: KeyEvent) -> bool {
 
     if let Some(dialog) = &mut app.dialog {
         match dialog {
-            DialogType::Input { textarea, .. } => {
-                // 使用 HelixTextArea 处理按键
-                match textarea.handle_key(key) {
+            DialogType::Input {
+                prompt,
+                input,
+                purpose,
+                ..
+            } => {
+                // 新建项目对话框：Tab 切换工作流
+                if key.code == KeyCode::Tab
+                    && let DialogPurpose::CreateProject { workflow, .. } = purpose
+                {
+                    *workflow = workflow.next();
+                    *prompt = new_project_prompt(*workflow);
+                    return true;
+                }
+
+                match input.handle_key(key) {
                     InputAction::Submit => {
                         // 提交内容
-                        let content = textarea.get_content();
+                        let content = input.content();
                         let dialog_clone = app.dialog.take().unwrap();
                         handle_dialog_submit(app, dialog_clone, content);
                         if app.dialog.is_some() {
@@ -215,14 +340,12 @@ fn handle_dialog_mode(app: &mut App, key: KeyEvent) -> bool {
                         } else {
                             app.mode = Mode::Normal;
                         }
-                        // app.ime_state.exit_dialog();  // 已禁用输入法自动切换
                         return true;
                     }
                     InputAction::Cancel => {
                         // 取消对话框
                         app.dialog = None;
                         app.mode = Mode::Normal;
-                        // app.ime_state.exit_dialog();  // 已禁用输入法自动切换
                         return true;
                     }
                     InputAction::Continue => {
@@ -231,9 +354,9 @@ fn handle_dialog_mode(app: &mut App, key: KeyEvent) -> bool {
                     }
                 }
             }
-            DialogType::ProjectTagsInput { textarea, .. } => match textarea.handle_key(key) {
+            DialogType::ProjectTagsInput { input, .. } => match input.handle_key(key) {
                 InputAction::Submit => {
-                    let content = textarea.get_content();
+                    let content = input.content();
                     let dialog_clone = app.dialog.take().unwrap();
                     handle_dialog_submit(app, dialog_clone, content);
                     if app.dialog.is_some() {
@@ -262,19 +385,35 @@ fn handle_dialog_mode(app: &mut App, key: KeyEvent) -> bool {
                 }
             },
             DialogType::ProjectGrid { .. } => {}
-            DialogType::Confirm { yes_selected, .. } => {
+            DialogType::Confirm {
+                yes_selected,
+                action,
+                ..
+            } => {
                 match key.code {
+                    // 重复任务确认中按序号打开已有任务，不再新建
+                    KeyCode::Char(c @ '1'..='9') => {
+                        let index = c as usize - '1' as usize;
+                        if let crate::ui::dialogs::ConfirmAction::CreateDuplicateTask {
+                            duplicates,
+                            ..
+                        } = action
+                            && let Some(&id) = duplicates.get(index)
+                        {
+                            app.dialog = None;
+                            app.mode = Mode::Normal;
+                            open_existing_task(app, id);
+                        }
+                    }
                     KeyCode::Esc | KeyCode::Char('n') => {
                         app.dialog = None;
                         app.mode = Mode::Normal;
                         // 退出对话框，保存用户输入法并切换回英文（已禁用）
-                        // app.ime_state.exit_dialog();
                     }
                     KeyCode::Enter => {
                         let confirmed = *yes_selected;
                         let dialog_clone = app.dialog.take().unwrap();
                         app.mode = Mode::Normal;
-                        // app.ime_state.exit_dialog();  // 已禁用输入法自动切换
                         if confirmed {
                             handle_dialog_submit(app, dialog_clone, String::new());
                         }
@@ -291,7 +430,6 @@ fn handle_dialog_mode(app: &mut App, key: KeyEvent) -> bool {
                         // 直接确认
                         let dialog_clone = app.dialog.take().unwrap();
                         app.mode = Mode::Normal;
-                        // app.ime_state.exit_dialog();  // 已禁用输入法自动切换
                         handle_dialog_submit(app, dialog_clone, String::new());
                         return true;
                     }
@@ -326,27 +464,21 @@ fn handle_project_grid_search_mode(app: &mut App, key: KeyEvent) -> bool {
         }
         KeyCode::Enter => {
             if let Some(DialogType::ProjectGrid { state, .. }) = &mut app.dialog {
-                let filtered_count = filter_project_grid_items(&state.items, &state.filter).len();
+                let filtered_count = visible_project_grid_items(state).len();
                 if filtered_count > 0 {
                     state.selecting = true;
                 }
             }
         }
-        KeyCode::Backspace => {
+        _ => {
             if let Some(DialogType::ProjectGrid { state, .. }) = &mut app.dialog {
-                state.filter.pop();
-                state.selected = 0;
-            }
-        }
-        KeyCode::Char(c) => {
-            if (key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT)
-                && let Some(DialogType::ProjectGrid { state, .. }) = &mut app.dialog
-            {
-                state.filter.push(c);
-                state.selected = 0;
+                let before = state.filter.value().to_string();
+                state.filter.handle_key(key);
+                if state.filter.value() != before {
+                    state.selected = 0;
+                }
             }
         }
-        _ => {}
     }
 
     true
@@ -364,10 +496,22 @@ fn handle_project_grid_select_mode(app: &mut App, key: KeyEvent) -> bool {
             }
         }
         KeyCode::Enter => {
-            if let Some(item) = selected_project_grid_item_from_app(app) {
+            // 选中折叠的分组卡片时展开分组
+            if let Some(DialogType::ProjectGrid { state, .. }) = &app.dialog
+                && visible_project_grid_items(state)
+                    .get(state.selected)
+                    .and_then(|&index| collapsed_group_card(state, &state.items[index]))
+                    .is_some()
+            {
+                toggle_open_project_grid_group(app);
+            } else if let Some(item) = selected_project_grid_item_from_app(app) {
                 app.dialog = None;
                 app.mode = Mode::Normal;
-                app.open_project(item.name, item.path, item.project_type);
+                // Alt/Shift+Enter：即使项目已在其他面板打开，也在当前面板再打开一份
+                let allow_duplicate = key
+                    .modifiers
+                    .intersects(KeyModifiers::ALT | KeyModifiers::SHIFT);
+                app.open_or_focus_project(item.path, item.project_type, allow_duplicate);
             }
         }
         KeyCode::Left | KeyCode::Char('h') => {
@@ -397,6 +541,9 @@ fn handle_project_grid_select_mode(app: &mut App, key: KeyEvent) -> bool {
         KeyCode::Char('t') => {
             open_project_tags_input(app);
         }
+        KeyCode::Char('z') => {
+            toggle_open_project_grid_group(app);
+        }
         _ => {}
     }
 
@@ -414,19 +561,34 @@ fn selected_project_grid_item_from_app(app: &App) -> Option<crate::ui::dialogs::
 fn selected_project_grid_item(
     state: &ProjectGridState,
 ) -> Option<crate::ui::dialogs::ProjectGridItem> {
-    let filtered_indices = filter_project_grid_items(&state.items, &state.filter);
+    let filtered_indices = visible_project_grid_items(state);
     let item_index = filtered_indices.get(state.selected)?;
     state.items.get(*item_index).cloned()
 }
 
 fn navigate_open_project_grid(app: &mut App, direction: ProjectGridNavigation) {
     if let Some(DialogType::ProjectGrid { state, .. }) = &mut app.dialog {
-        let filtered_count = filter_project_grid_items(&state.items, &state.filter).len();
+        let filtered_count = visible_project_grid_items(state).len();
         state.selected =
             navigate_project_grid(state.selected, filtered_count, state.columns, direction);
     }
 }
 
+/// 折叠/展开选中项目所在的分组，折叠状态随界面状态保存
+fn toggle_open_project_grid_group(app: &mut App) {
+    let Some(DialogType::ProjectGrid { state, .. }) = &mut app.dialog else {
+        return;
+    };
+    if toggle_project_grid_group(state) {
+        app.collapsed_project_groups = state.collapsed_groups.clone();
+    } else {
+        app.show_notification(
+            "项目没有分组（在 .kanban.toml 或 :project-settings 中设置 group）".to_string(),
+            NotificationLevel::Info,
+        );
+    }
+}
+
 fn reorder_open_project_grid(app: &mut App, direction: ProjectGridOrderMove) {
     let Some(DialogType::ProjectGrid { title, state }) = app.dialog.take() else {
         return;
@@ -447,6 +609,7 @@ fn reorder_open_project_grid(app: &mut App, direction: ProjectGridOrderMove) {
         }
         Err(e) => {
             app.dialog = Some(DialogType::ProjectGrid { title, state });
+            app.last_save_error = Some(SaveError::Files(e.clone()));
             app.show_notification(format!("保存项目排序失败: {}", e), NotificationLevel::Error);
         }
     }
@@ -465,11 +628,7 @@ fn open_project_tags_input(app: &mut App) {
     app.dialog = Some(DialogType::ProjectTagsInput {
         title: format!("编辑项目标签: {}", item.name),
         prompt: "请输入项目标签，使用逗号分隔:".to_string(),
-        textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-            item.tags.join(", "),
-            true,
-            false,
-        )),
+        input: crate::ui::dialogs::DialogInput::line(&item.tags.join(", ")),
         project_path: item.path,
         project_name: item.name,
         grid_state,
@@ -542,191 +701,185 @@ fn log_debug(msg: String) {
     // 移除 eprintln! 避免干扰 TUI 界面
 }
 
+/// 新建项目对话框的提示（包含当前选择的工作流）
+fn new_project_prompt(workflow: crate::fs::Workflow) -> String {
+    let statuses: Vec<&str> = workflow
+        .statuses()
+        .iter()
+        .map(|(name, _, _)| *name)
+        .collect();
+    format!(
+        "请输入项目名称:\n工作流: {} ({})  Tab 切换",
+        workflow.name(),
+        statuses.join("/")
+    )
+}
+
 /// 处理对话框提交
 fn handle_dialog_submit(app: &mut App, dialog: crate::ui::dialogs::DialogType, value: String) {
     use crate::ui::dialogs::DialogType;
 
     match dialog {
-        DialogType::Input { title, .. } => {
+        DialogType::Input { title, purpose, .. } => {
             log_debug(format!("对话框提交: title='{}', value='{}'", title, value));
 
-            if (title.contains("创建") || title.contains("新建")) && title.contains("项目") {
-                // 创建新项目
-                if !value.is_empty() {
-                    log_debug(format!("调试: 准备创建项目 '{}'", value));
-
-                    // 根据标题判断是本地项目还是全局项目
-                    let is_local = title.contains("[L]");
-                    let is_global = title.contains("[G]");
-
-                    let result = if is_local {
-                        // 创建本地项目
-                        log_debug("调试: 创建本地项目".to_string());
-                        crate::fs::create_local_project(&value)
-                    } else if is_global {
-                        // 创建全局项目
-                        log_debug("调试: 创建全局项目".to_string());
-                        crate::fs::create_project(&value)
-                    } else {
-                        // 默认创建全局项目（向后兼容）
-                        log_debug("调试: 创建默认项目（全局）".to_string());
-                        crate::fs::create_project(&value)
-                    };
+            match purpose {
+                DialogPurpose::CreateProject { local, workflow } => {
+                    // 创建新项目
+                    if !value.is_empty() {
+                        log_debug(format!("调试: 准备创建项目 '{}'", value));
 
-                    match result {
-                        Ok(path) => {
-                            log_debug(format!("调试: 项目创建成功于 {:?}", path));
-                            // 重新加载项目列表
-                            match crate::fs::load_all_projects() {
-                                Ok(projects) => {
-                                    log_debug(format!(
-                                        "调试: 重新加载了 {} 个项目",
-                                        projects.len()
-                                    ));
-                                    app.projects = projects;
-                                }
-                                Err(e) => {
-                                    log_debug(format!("调试: 重新加载项目失败: {}", e));
+                        let result = if local {
+                            // 创建本地项目
+                            log_debug("调试: 创建本地项目".to_string());
+                            crate::fs::create_local_project(&value, workflow)
+                        } else {
+                            // 创建全局项目
+                            log_debug("调试: 创建全局项目".to_string());
+                            crate::fs::create_project(&value, workflow)
+                        };
+
+                        match result {
+                            Ok(path) => {
+                                log_debug(format!("调试: 项目创建成功于 {:?}", path));
+                                // 重新加载项目列表
+                                match crate::fs::load_all_projects_with_config(&app.config) {
+                                    Ok(projects) => {
+                                        log_debug(format!(
+                                            "调试: 重新加载了 {} 个项目",
+                                            projects.len()
+                                        ));
+                                        app.projects = projects;
+                                    }
+                                    Err(e) => {
+                                        log_debug(format!("调试: 重新加载项目失败: {}", e));
+                                    }
                                 }
+                                // 在当前面板打开新项目
+                                let project_type = if local {
+                                    crate::models::ProjectType::Local
+                                } else {
+                                    crate::models::ProjectType::Global
+                                };
+                                app.open_project(path, project_type);
+                            }
+                            Err(e) => {
+                                log_debug(format!("创建项目失败: {}", e));
                             }
-                            // 在当前面板打开新项目
-                            let project_type = if is_local {
-                                crate::models::ProjectType::Local
-                            } else {
-                                crate::models::ProjectType::Global
-                            };
-                            app.open_project(value, path, project_type);
-                        }
-                        Err(e) => {
-                            log_debug(format!("创建项目失败: {}", e));
                         }
+                    } else {
+                        log_debug("调试: 项目名称为空".to_string());
                     }
-                } else {
-                    log_debug("调试: 项目名称为空".to_string());
                 }
-            } else if (title.contains("创建") || title.contains("新建")) && title.contains("任务")
-            {
-                // 创建新任务
-                log_debug("调试: 识别为创建任务请求".to_string());
-                if !value.is_empty() {
-                    create_new_task(app, value);
-                } else {
-                    log_debug("调试: 任务标题为空".to_string());
+                DialogPurpose::CreateTask => {
+                    // 创建新任务
+                    log_debug("调试: 识别为创建任务请求".to_string());
+                    if !value.is_empty() {
+                        create_new_task(app, value);
+                    } else {
+                        log_debug("调试: 任务标题为空".to_string());
+                    }
                 }
-            } else if title.contains("编辑任务") {
-                // 编辑任务
-                if !value.is_empty() {
-                    update_task_title(app, value);
+                DialogPurpose::ImportTasks => {
+                    if !value.trim().is_empty() {
+                        import_tasks_from_file(app, &value);
+                    }
                 }
-            } else if title.contains("编辑标签") {
-                // 编辑标签
-                update_task_tags(app, value);
-            } else if title.contains("重命名项目") {
-                // 重命名项目
-                if !value.is_empty() {
-                    rename_current_project(app, value);
+                DialogPurpose::EditTask => {
+                    // 编辑任务
+                    if !value.is_empty() {
+                        update_task_title(app, value);
+                    }
                 }
-            } else if title.contains("创建新状态") {
-                // 创建新状态
-                if !value.is_empty()
-                    && let Some(project) = app.get_focused_project()
-                {
-                    let project_path = project.path.clone();
-
-                    // 使用输入值作为显示名
-                    match crate::fs::status::create_status(&project_path, &value, &value) {
-                        Ok(_) => {
-                            // 重新加载项目
-                            if let Err(e) = app.reload_current_project() {
-                                log_debug(format!("重新加载项目失败: {}", e));
-                            }
-                            app.show_notification(
-                                format!("已创建状态「{}」", value),
-                                crate::app::NotificationLevel::Success,
-                            );
-                        }
-                        Err(e) => {
-                            app.show_notification(
-                                format!("创建失败: {}", e),
-                                crate::app::NotificationLevel::Error,
-                            );
-                        }
+                DialogPurpose::AddNote => {
+                    if !value.trim().is_empty() {
+                        add_task_note(app, &value);
                     }
                 }
-            } else if title.contains("重命名状态") {
-                // 重命名状态
-                if !value.is_empty()
-                    && let Some(project) = app.get_focused_project()
-                {
-                    let column = app
-                        .selected_column
-                        .get(&app.focused_pane)
-                        .copied()
-                        .unwrap_or(0);
-                    if let Some(status) = project.statuses.get(column) {
-                        let old_name = status.name.clone();
-                        let old_display = status.display.clone();
+                DialogPurpose::EditTags => {
+                    // 编辑标签
+                    update_task_tags(app, value);
+                }
+                DialogPurpose::EditFields => {
+                    update_task_fields(app, &value);
+                }
+                DialogPurpose::ProjectSettings => {
+                    save_project_settings(app, &value);
+                }
+                DialogPurpose::RenameProject => {
+                    // 重命名项目
+                    if !value.is_empty() {
+                        rename_current_project(app, value);
+                    }
+                }
+                DialogPurpose::CreateStatus => {
+                    // 创建新状态
+                    if !value.is_empty()
+                        && let Some(project) = app.get_focused_project()
+                    {
                         let project_path = project.path.clone();
 
-                        match crate::fs::status::rename_status(
-                            &project_path,
-                            &old_name,
-                            &value,
-                            &value,
-                        ) {
+                        // 使用输入值作为显示名
+                        match crate::fs::status::create_status(&project_path, &value, &value) {
                             Ok(_) => {
                                 // 重新加载项目
                                 if let Err(e) = app.reload_current_project() {
                                     log_debug(format!("重新加载项目失败: {}", e));
                                 }
                                 app.show_notification(
-                                    format!("已将「{}」重命名为「{}」", old_display, value),
+                                    format!("已创建状态「{}」", value),
                                     crate::app::NotificationLevel::Success,
                                 );
                             }
                             Err(e) => {
                                 app.show_notification(
-                                    format!("重命名失败: {}", e),
+                                    format!("创建失败: {}", e),
                                     crate::app::NotificationLevel::Error,
                                 );
                             }
                         }
                     }
                 }
-            } else if title.contains("编辑显示名") {
-                // 编辑状态显示名
-                if !value.is_empty()
-                    && let Some(project) = app.get_focused_project()
-                {
-                    let column = app
-                        .selected_column
-                        .get(&app.focused_pane)
-                        .copied()
-                        .unwrap_or(0);
-                    if let Some(status) = project.statuses.get(column) {
-                        let status_name = status.name.clone();
-                        let project_path = project.path.clone();
+                DialogPurpose::RenameStatus => {
+                    // 重命名状态
+                    if !value.is_empty()
+                        && let Some(project) = app.get_focused_project()
+                    {
+                        let column = app
+                            .selected_column
+                            .get(&app.focused_pane)
+                            .copied()
+                            .unwrap_or(0);
+                        if let Some(status) = project.statuses.get(column) {
+                            let old_name = status.name.clone();
+                            let old_display = status.display.clone();
+                            let project_path = project.path.clone();
 
-                        match crate::fs::status::update_status_display(
-                            &project_path,
-                            &status_name,
-                            &value,
-                        ) {
-                            Ok(_) => {
-                                // 重新加载项目
-                                if let Err(e) = app.reload_current_project() {
-                                    log_debug(format!("重新加载项目失败: {}", e));
+                            match crate::fs::backup::before(&project_path, "rename-status")
+                                .and_then(|_| {
+                                    crate::fs::status::rename_status(
+                                        &project_path,
+                                        &old_name,
+                                        &value,
+                                        &value,
+                                    )
+                                }) {
+                                Ok(_) => {
+                                    // 重新加载项目
+                                    if let Err(e) = app.reload_current_project() {
+                                        log_debug(format!("重新加载项目失败: {}", e));
+                                    }
+                                    app.show_notification(
+                                        format!("已将「{}」重命名为「{}」", old_display, value),
+                                        crate::app::NotificationLevel::Success,
+                                    );
+                                }
+                                Err(e) => {
+                                    app.show_notification(
+                                        format!("重命名失败: {}", e),
+                                        crate::app::NotificationLevel::Error,
+                                    );
                                 }
-                                app.show_notification(
-                                    format!("已更新显示名为「{}」", value),
-                                    crate::app::NotificationLevel::Success,
-                                );
-                            }
-                            Err(e) => {
-                                app.show_notification(
-                                    format!("更新失败: {}", e),
-                                    crate::app::NotificationLevel::Error,
-                                );
                             }
                         }
                     }
@@ -772,16 +925,16 @@ fn handle_dialog_submit(app: &mut App, dialog: crate::ui::dialogs::DialogType, v
                     // 隐藏项目（软删除）
                     if let Some(project) = app.get_focused_project() {
                         let project_name = project.name.clone();
+                        let project_id = project.id.clone();
 
                         // 添加到隐藏列表
-                        if let Err(e) = crate::config::hide_project(&mut app.config, &project_name)
-                        {
+                        if let Err(e) = crate::config::hide_project(&mut app.config, &project_id) {
                             log_debug(format!("隐藏项目失败: {}", e));
                         } else {
                             log_debug(format!("成功隐藏项目: {}", project_name));
 
                             // 从项目列表中移除
-                            app.projects.retain(|p| p.name != project_name);
+                            app.projects.retain(|p| p.id != project_id);
 
                             // 清除当前面板的项目引用
                             if let Some(crate::ui::layout::SplitNode::Leaf { project_id, .. }) =
@@ -797,6 +950,7 @@ fn handle_dialog_submit(app: &mut App, dialog: crate::ui::dialogs::DialogType, v
                     log_debug("收到 DeleteProject 确认".to_string());
                     if let Some(project) = app.get_focused_project() {
                         let project_name = project.name.clone();
+                        let project_id = project.id.clone();
                         let project_path = project.path.clone();
 
                         log_debug(format!(
@@ -805,7 +959,9 @@ fn handle_dialog_submit(app: &mut App, dialog: crate::ui::dialogs::DialogType, v
                         ));
 
                         // 使用项目路径直接删除
-                        match crate::fs::delete_project_by_path(&project_path) {
+                        match crate::fs::backup::before(&project_path, "delete-project")
+                            .and_then(|_| crate::fs::delete_project_by_path(&project_path))
+                        {
                             Err(e) => {
                                 log_debug(format!("删除项目失败: {}", e));
                                 app.show_notification(
@@ -817,14 +973,14 @@ fn handle_dialog_submit(app: &mut App, dialog: crate::ui::dialogs::DialogType, v
                                 log_debug(format!("成功删除项目: {}", project_name));
 
                                 // 从项目列表中移除
-                                app.projects.retain(|p| p.name != project_name);
+                                app.projects.retain(|p| p.id != project_id);
                                 log_debug(format!(
                                     "已从项目列表移除，剩余项目数: {}",
                                     app.projects.len()
                                 ));
 
                                 // 清除所有面板中对该项目的引用
-                                app.split_tree.clear_project_from_all_panes(&project_name);
+                                app.split_tree.clear_project_from_all_panes(&project_id);
                                 log_debug("已清除所有面板中的项目引用".to_string());
 
                                 // 显示删除成功通知
@@ -847,7 +1003,7 @@ fn handle_dialog_submit(app: &mut App, dialog: crate::ui::dialogs::DialogType, v
 
                             // 删除任务（包括文件和 tasks.toml 中的元数据）
                             if let Err(e) = crate::fs::delete_task(&project_path, task) {
-                                log_debug(format!("删除任务失败: {}", e));
+                                report_save_error(app, e);
                             } else {
                                 // 重新加载当前项目
                                 if let Err(e) = app.reload_current_project() {
@@ -864,6 +1020,69 @@ fn handle_dialog_submit(app: &mut App, dialog: crate::ui::dialogs::DialogType, v
                         }
                     }
                 }
+                crate::ui::dialogs::ConfirmAction::RenumberTasks => {
+                    if let Some(project) = app.get_focused_project() {
+                        let project_path = project.path.clone();
+
+                        match crate::fs::backup::before(&project_path, "renumber")
+                            .and_then(|_| crate::fs::task::renumber_tasks(&project_path))
+                        {
+                            Ok(plan) => {
+                                if let Err(e) = app.reload_current_project() {
+                                    log_debug(format!("重新加载项目失败: {}", e));
+                                }
+                                app.show_notification(
+                                    format!("已重新编号 {} 个任务", plan.len()),
+                                    NotificationLevel::Success,
+                                );
+                            }
+                            Err(e) => {
+                                log_debug(format!("重新编号失败: {}", e));
+                                app.show_notification(
+                                    format!("重新编号失败: {}", e),
+                                    NotificationLevel::Error,
+                                );
+                            }
+                        }
+                    }
+                }
+                crate::ui::dialogs::ConfirmAction::MigrateProject => {
+                    if let Some(project) = app.get_focused_project() {
+                        let project_path = project.path.clone();
+
+                        match crate::fs::migration::migrate_project(&project_path) {
+                            Ok(report) => {
+                                if let Err(e) = app.reload_current_project() {
+                                    log_debug(format!("重新加载项目失败: {}", e));
+                                }
+                                let backup = report
+                                    .backup
+                                    .map(|path| format!("，备份于 {}", path.display()))
+                                    .unwrap_or_default();
+                                app.show_notification(
+                                    format!(
+                                        "已升级项目格式（{} 项变更）{}",
+                                        report.applied, backup
+                                    ),
+                                    NotificationLevel::Success,
+                                );
+                            }
+                            Err(e) => {
+                                log_debug(format!("项目迁移失败: {}", e));
+                                app.show_notification(
+                                    format!("项目迁移失败: {}", e),
+                                    NotificationLevel::Error,
+                                );
+                            }
+                        }
+                    }
+                }
+                crate::ui::dialogs::ConfirmAction::RevertExternalChanges => {
+                    revert_external_changes(app);
+                }
+                crate::ui::dialogs::ConfirmAction::CreateDuplicateTask { input, .. } => {
+                    save_new_task(app, input);
+                }
                 crate::ui::dialogs::ConfirmAction::DeleteStatus => {
                     // 删除状态
                     if let Some(project) = app.get_focused_project() {
@@ -877,11 +1096,14 @@ fn handle_dialog_submit(app: &mut App, dialog: crate::ui::dialogs::DialogType, v
                             let status_display = status.display.clone();
                             let project_path = project.path.clone();
 
-                            match crate::fs::status::delete_status(
-                                &project_path,
-                                &status_name,
-                                None,
-                            ) {
+                            match crate::fs::backup::before(&project_path, "delete-status")
+                                .and_then(|_| {
+                                    crate::fs::status::delete_status(
+                                        &project_path,
+                                        &status_name,
+                                        None,
+                                    )
+                                }) {
                                 Ok(_) => {
                                     log_debug(format!("成功删除状态: {}", status_name));
 
@@ -924,6 +1146,7 @@ pub fn match_key_sequence(buffer: &[char], key: KeyEvent) -> Option<Command> {
         ([], KeyCode::Char('j'), KeyModifiers::NONE) => Some(Command::TaskDown),
         ([], KeyCode::Char('k'), KeyModifiers::NONE) => Some(Command::TaskUp),
         ([], KeyCode::Char('x'), KeyModifiers::NONE) => Some(Command::ToggleTaskMark),
+        ([], KeyCode::Tab, KeyModifiers::NONE) => Some(Command::ToggleTaskExpand),
         ([], KeyCode::Char('X'), KeyModifiers::SHIFT) => Some(Command::EnterMarkSelect),
         ([], KeyCode::Char('X'), KeyModifiers::NONE) => Some(Command::EnterMarkSelect),
         ([], KeyCode::Char('h'), KeyModifiers::NONE) => Some(Command::ColumnLeft),
@@ -932,12 +1155,15 @@ pub fn match_key_sequence(buffer: &[char], key: KeyEvent) -> Option<Command> {
         ([], KeyCode::Char('L'), KeyModifiers::SHIFT) => Some(Command::MoveTaskRight),
         ([], KeyCode::Char('J'), KeyModifiers::SHIFT) => Some(Command::MoveTaskDown),
         ([], KeyCode::Char('K'), KeyModifiers::SHIFT) => Some(Command::MoveTaskUp),
-        // ([], KeyCode::Char(':'), KeyModifiers::NONE) => Some(Command::EnterCommandMode), // 已注释
+        ([], KeyCode::Char(':'), KeyModifiers::NONE) => Some(Command::EnterCommandMode),
+        ([], KeyCode::Char(':'), KeyModifiers::SHIFT) => Some(Command::EnterCommandMode),
         ([], KeyCode::Esc, _) => Some(Command::EnterNormalMode),
         ([], KeyCode::Char('d'), KeyModifiers::NONE) => Some(Command::DeleteTask), // 删除任务
+        ([], KeyCode::Char('u'), KeyModifiers::NONE) => Some(Command::UndoCompletion),
         ([], KeyCode::Char('D'), KeyModifiers::SHIFT) => Some(Command::DeleteTask), // 删除任务
         ([], KeyCode::Char('a'), KeyModifiers::NONE) => Some(Command::NewTask),
         ([], KeyCode::Char('A'), KeyModifiers::SHIFT) => Some(Command::NewTaskInEditor), // 外部编辑器创建任务
+        ([], KeyCode::Char('o'), KeyModifiers::NONE) => Some(Command::QuickAdd), // 列底部快速添加
         ([], KeyCode::Char('n'), KeyModifiers::NONE) => Some(Command::NewLocalProject),
         ([], KeyCode::Char('N'), KeyModifiers::SHIFT) => Some(Command::NewGlobalProject),
         ([], KeyCode::Char('e'), KeyModifiers::NONE) => Some(Command::EditTask),
@@ -954,6 +1180,24 @@ pub fn match_key_sequence(buffer: &[char], key: KeyEvent) -> Option<Command> {
         ([], KeyCode::Char('-'), KeyModifiers::NONE) => Some(Command::DecreaseColumnWidth),
         ([], KeyCode::Char('='), KeyModifiers::NONE) => Some(Command::ResetColumnWidths),
         (['m'], KeyCode::Char('m'), KeyModifiers::NONE) => Some(Command::ToggleMaximizeColumn),
+        (['g'], KeyCode::Char(';'), KeyModifiers::NONE) => Some(Command::JumpToLastEdited),
+        ([], KeyCode::Char('z'), KeyModifiers::NONE) => Some(Command::ToggleZoomColumn),
+
+        // 优先级筛选 (1=高, 2=中及以上, 3/0=全部)
+        ([], KeyCode::Char('1'), KeyModifiers::NONE) => {
+            Some(Command::TogglePriorityFilter(PriorityFilter::High))
+        }
+        ([], KeyCode::Char('2'), KeyModifiers::NONE) => {
+            Some(Command::TogglePriorityFilter(PriorityFilter::Medium))
+        }
+        ([], KeyCode::Char('3') | KeyCode::Char('0'), KeyModifiers::NONE) => {
+            Some(Command::TogglePriorityFilter(PriorityFilter::All))
+        }
+
+        // 标签筛选 (#1..#9 = 选中任务的第 N 个标签)
+        (['#'], KeyCode::Char(digit @ '1'..='9'), KeyModifiers::NONE) => {
+            Some(Command::ToggleTagFilter(digit as usize - '1' as usize))
+        }
 
         // 状态列移动 (Ctrl+h/l/H/L)
         ([], KeyCode::Char('h'), KeyModifiers::CONTROL) => Some(Command::MoveStatusLeft),
@@ -974,6 +1218,29 @@ pub fn match_key_sequence(buffer: &[char], key: KeyEvent) -> Option<Command> {
 fn execute_command(app: &mut App, cmd: Command) {
     use crate::ui::dialogs::DialogType;
 
+    if cmd.modifies_project() && reject_read_only_pane(app) {
+        return;
+    }
+
+    // 列标题被选中时 j 回到第一个任务；k/h/l 和编辑显示名之外的命令都离开列标题
+    if let Some(pane) = app.column_header_focus {
+        if pane == app.focused_pane && cmd == Command::TaskDown {
+            app.column_header_focus = None;
+            return;
+        }
+        if pane != app.focused_pane
+            || !matches!(
+                cmd,
+                Command::TaskUp
+                    | Command::ColumnLeft
+                    | Command::ColumnRight
+                    | Command::EditStatusDisplay
+            )
+        {
+            app.column_header_focus = None;
+        }
+    }
+
     match cmd {
         Command::SplitHorizontal => {
             // 水平分割线 = 上下分屏
@@ -993,8 +1260,7 @@ fn execute_command(app: &mut App, cmd: Command) {
                 ));
 
                 // 保存状态
-                let state = crate::state::extract_state(app);
-                let _ = crate::state::save_state(&state);
+                app.save_state();
             } else {
                 log_debug("找不到当前面板".to_string());
             }
@@ -1017,8 +1283,7 @@ fn execute_command(app: &mut App, cmd: Command) {
                 ));
 
                 // 保存状态
-                let state = crate::state::extract_state(app);
-                let _ = crate::state::save_state(&state);
+                app.save_state();
             } else {
                 log_debug("找不到当前面板".to_string());
             }
@@ -1039,7 +1304,7 @@ fn execute_command(app: &mut App, cmd: Command) {
                     return;
                 };
 
-                let task_count = project.tasks.iter().filter(|t| t.status == status).count();
+                let task_count = app.column_tasks(app.focused_pane, project, &status).len();
 
                 if task_count > 0 {
                     let idx = app.selected_task_index.entry(app.focused_pane).or_insert(0);
@@ -1050,81 +1315,84 @@ fn execute_command(app: &mut App, cmd: Command) {
         Command::ToggleTaskMark => {
             toggle_selected_task_mark(app);
         }
+        Command::ToggleTaskExpand => {
+            toggle_selected_task_expand(app);
+        }
         Command::EnterMarkSelect => {
             app.mode = Mode::MarkSelect;
         }
         Command::TaskUp => {
+            // 在第一个任务上按 k 选中列标题
             let idx = app.selected_task_index.entry(app.focused_pane).or_insert(0);
+            if *idx == 0 {
+                app.column_header_focus = Some(app.focused_pane);
+            }
             *idx = idx.saturating_sub(1);
         }
-        Command::ColumnLeft => {
-            let col = app.selected_column.entry(app.focused_pane).or_insert(0);
-            *col = col.saturating_sub(1);
-            // 切换列时重置任务索引到 0
-            app.selected_task_index.insert(app.focused_pane, 0);
+        Command::ColumnLeft | Command::ColumnRight => {
+            // 跳过专注模式隐藏的列
+            let forward = matches!(cmd, Command::ColumnRight);
+            if let Some(column) = app.adjacent_visible_column(forward) {
+                app.switch_column(column);
+            }
+        }
+        Command::EnterCommandMode => {
+            app.mode = Mode::Command;
+            app.command_input.clear();
+            app.completion_selected_index = None;
         }
-        Command::ColumnRight => {
-            let max_col = app.get_status_count().saturating_sub(1);
-            let focused_pane = app.focused_pane;
-            let col = app.selected_column.entry(focused_pane).or_insert(0);
-            *col = (*col + 1).min(max_col);
-            // 切换列时重置任务索引到 0
-            app.selected_task_index.insert(focused_pane, 0);
-        }
-        // Command::EnterCommandMode => {
-        //     app.mode = Mode::Command; // 已注释
-        // }
         Command::EnterNormalMode | Command::Cancel => {
             app.mode = Mode::Normal;
             app.key_buffer.clear();
-            app.command_input.clear(); // 已注释，但仍保留清理逻辑
+            app.command_input.clear();
+            app.zoomed_pane = None;
         }
         Command::NewProject => {
             app.mode = Mode::Dialog;
-            // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
             app.dialog = Some(DialogType::Input {
                 title: "创建新项目".to_string(),
-                prompt: "请输入项目名称:".to_string(),
-                textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                    String::new(),
-                    true,
-                    false,
-                )), // 默认 Insert 模式
+                purpose: DialogPurpose::CreateProject {
+                    local: false,
+                    workflow: crate::fs::Workflow::Basic,
+                },
+                prompt: new_project_prompt(crate::fs::Workflow::Basic),
+                input: crate::ui::dialogs::DialogInput::line(""),
             });
         }
         Command::NewLocalProject => {
             app.mode = Mode::Dialog;
-            // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
             app.dialog = Some(DialogType::Input {
                 title: "创建新本地项目 [L]".to_string(),
-                prompt: "请输入项目名称:".to_string(),
-                textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                    String::new(),
-                    true,
-                    false,
-                )), // 默认 Insert 模式
+                purpose: DialogPurpose::CreateProject {
+                    local: true,
+                    workflow: crate::fs::Workflow::Basic,
+                },
+                prompt: new_project_prompt(crate::fs::Workflow::Basic),
+                input: crate::ui::dialogs::DialogInput::line(""),
             });
         }
         Command::NewGlobalProject => {
             app.mode = Mode::Dialog;
-            // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
             app.dialog = Some(DialogType::Input {
                 title: "创建新全局项目 [G]".to_string(),
-                prompt: "请输入项目名称:".to_string(),
-                textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                    String::new(),
-                    true,
-                    false,
-                )), // 默认 Insert 模式
+                purpose: DialogPurpose::CreateProject {
+                    local: false,
+                    workflow: crate::fs::Workflow::Basic,
+                },
+                prompt: new_project_prompt(crate::fs::Workflow::Basic),
+                input: crate::ui::dialogs::DialogInput::line(""),
             });
         }
         Command::OpenProject => {
             app.mode = Mode::Dialog;
-            // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
             let current_project_path = app
                 .get_focused_project()
                 .map(|project| project.path.as_path());
-            let state = project_grid_state_from_projects(&app.projects, current_project_path);
+            let state = project_grid_state_from_projects(
+                &app.projects,
+                current_project_path,
+                &app.collapsed_project_groups,
+            );
 
             app.dialog = Some(DialogType::ProjectGrid {
                 title: "快速切换项目...".to_string(),
@@ -1136,15 +1404,11 @@ fn execute_command(app: &mut App, cmd: Command) {
             if let Some(project) = app.get_focused_project() {
                 let current_name = project.name.clone();
                 app.mode = Mode::Dialog;
-                // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
                 app.dialog = Some(DialogType::Input {
                     title: "重命名项目".to_string(),
+                    purpose: DialogPurpose::RenameProject,
                     prompt: "请输入新的项目名称:".to_string(),
-                    textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                        current_name,
-                        true,
-                        false,
-                    )), // 默认 Insert 模式
+                    input: crate::ui::dialogs::DialogInput::line(&current_name),
                 });
             }
         }
@@ -1169,7 +1433,6 @@ fn execute_command(app: &mut App, cmd: Command) {
 
                 // 其他项目（全局项目或其他目录的本地项目）：显示确认对话框
                 app.mode = Mode::Dialog;
-                // app.ime_state.enter_dialog();  // 已禁用输入法自动切换
                 app.dialog = Some(DialogType::Confirm {
                     title: "隐藏项目".to_string(),
                     message: format!(
@@ -1188,7 +1451,6 @@ fn execute_command(app: &mut App, cmd: Command) {
 
                 // 显示确认对话框
                 app.mode = Mode::Dialog;
-                // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
                 app.dialog = Some(DialogType::Confirm {
                     title: "删除项目文件".to_string(),
                     message: format!(
@@ -1202,77 +1464,42 @@ fn execute_command(app: &mut App, cmd: Command) {
         }
         Command::NewTask => {
             app.mode = Mode::Dialog;
-            // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
             app.dialog = Some(DialogType::Input {
                 title: "创建新任务".to_string(),
+                purpose: DialogPurpose::CreateTask,
                 prompt: "任务标题和内容:".to_string(),
-                textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                    String::new(),
-                    true,
-                    false,
-                )), // 默认 Insert 模式
+                input: crate::ui::dialogs::DialogInput::text(String::new(), true, false),
             });
         }
-        Command::NewTaskInEditor => {
-            // 用外部编辑器创建新任务
-            // 直接在项目中创建任务文件
-            use std::io::Write;
-
-            // 获取当前项目
-            let (project_path, status) = if let Some(crate::ui::layout::SplitNode::Leaf {
-                project_id: Some(name),
-                ..
-            }) = app.split_tree.find_pane(app.focused_pane)
-            {
-                if let Some(project) = app.projects.iter().find(|p| p.name == *name) {
-                    let column = app
-                        .selected_column
-                        .get(&app.focused_pane)
-                        .copied()
-                        .unwrap_or(0);
-                    let status = app
-                        .get_status_name_by_column(column)
-                        .unwrap_or_else(|| "todo".to_string());
-                    (project.path.clone(), status)
-                } else {
-                    return;
-                }
+        Command::QuickAdd => {
+            if app.get_focused_project().is_some() {
+                app.quick_add_input.clear();
+                app.mode = Mode::QuickAdd;
             } else {
+                app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+            }
+        }
+        Command::NewTaskInEditor => {
+            // 用外部编辑器写新任务草稿：编辑器关闭后再分配 ID 并保存到当前列
+            if app.get_focused_project().is_none() {
+                app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
                 return;
-            };
+            }
 
-            // 获取下一个任务 ID
-            if let Ok(next_id) = crate::fs::get_next_task_id(&project_path) {
-                let status_dir = project_path.join(&status);
-                if !status_dir.exists() {
-                    let _ = std::fs::create_dir_all(&status_dir);
+            let draft = std::env::temp_dir().join(format!(
+                "hxk-new-task-{}-{}.md",
+                std::process::id(),
+                chrono::Local::now().format("%Y%m%d%H%M%S%3f")
+            ));
+            match std::fs::write(&draft, EDITOR_TASK_TEMPLATE) {
+                Ok(()) => {
+                    app.pending_editor_file = Some(draft.to_string_lossy().to_string());
+                    app.is_new_task_file = true;
                 }
-
-                let task_file = status_dir.join(format!("{}.md", next_id));
-
-                // 获取当前列的最大order值
-                let max_order =
-                    crate::fs::get_max_order_in_status(&project_path, &status).unwrap_or(-1000);
-                let new_order = max_order + 1000;
-
-                // 写入 frontmatter 格式的模板内容
-                let template = format!(
-                    "+++\nid = {}\norder = {}\ncreated = \"{}\"\n+++\n\n# 任务标题\n\n任务描述内容...\n\n## 子任务\n\n- [ ] 子任务 1\n- [ ] 子任务 2\n",
-                    next_id,
-                    new_order,
-                    std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs()
-                );
-
-                if let Ok(mut file) = std::fs::File::create(&task_file) {
-                    let _ = file.write_all(template.as_bytes());
-                }
-
-                // 直接打开项目文件
-                app.pending_editor_file = Some(task_file.to_string_lossy().to_string());
-                app.is_new_task_file = false; // 不是临时文件，是真实的项目文件
+                Err(e) => app.show_notification(
+                    format!("无法创建临时文件: {}", e),
+                    NotificationLevel::Error,
+                ),
             }
         }
         Command::EditTask => {
@@ -1280,16 +1507,15 @@ fn execute_command(app: &mut App, cmd: Command) {
             if let Some(task) = get_selected_task(app) {
                 let title = task.title.clone();
                 app.mode = Mode::Dialog;
-                // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
                 app.dialog = Some(DialogType::Input {
                     title: "编辑任务".to_string(),
+                    purpose: DialogPurpose::EditTask,
                     prompt: "任务标题和内容:".to_string(),
-                    textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                        title, true, true,
-                    )), // 默认 Normal 模式
+                    input: crate::ui::dialogs::DialogInput::text(title, true, true),
                 });
             }
         }
+        Command::UndoCompletion => undo_completion(app),
         Command::MoveTaskLeft => {
             move_task_to_status_if_unmarked(app, -1);
         }
@@ -1365,6 +1591,7 @@ fn execute_command(app: &mut App, cmd: Command) {
                 let current_pane = app.focused_pane;
                 log_debug(format!("关闭面板: {}", current_pane));
                 if app.split_tree.close_pane(current_pane) {
+                    app.read_only_panes.remove(&current_pane);
                     // 关闭成功，重新聚焦到一个有效的面板
                     let all_panes = app.split_tree.collect_pane_ids();
                     if let Some(&first_pane) = all_panes.first() {
@@ -1373,8 +1600,7 @@ fn execute_command(app: &mut App, cmd: Command) {
                     }
 
                     // 保存状态
-                    let state = crate::state::extract_state(app);
-                    let _ = crate::state::save_state(&state);
+                    app.save_state();
                 } else {
                     log_debug("无法关闭面板".to_string());
                 }
@@ -1383,6 +1609,7 @@ fn execute_command(app: &mut App, cmd: Command) {
                 log_debug(format!("关闭面板: {}", app.focused_pane));
                 let current_pane = app.focused_pane;
                 if app.split_tree.close_pane(current_pane) {
+                    app.read_only_panes.remove(&current_pane);
                     // 关闭成功，需要重新聚焦到一个有效的面板
                     let all_panes = app.split_tree.collect_pane_ids();
                     if let Some(&first_pane) = all_panes.first() {
@@ -1391,8 +1618,7 @@ fn execute_command(app: &mut App, cmd: Command) {
                     }
 
                     // 保存状态
-                    let state = crate::state::extract_state(app);
-                    let _ = crate::state::save_state(&state);
+                    app.save_state();
                 } else {
                     // 只有一个面板时，清空该面板的项目
                     log_debug("只有一个面板，清空当前项目".to_string());
@@ -1400,25 +1626,78 @@ fn execute_command(app: &mut App, cmd: Command) {
                         app.split_tree.find_pane_mut(app.focused_pane)
                     {
                         *project_id = None;
+                        app.read_only_panes.remove(&app.focused_pane);
 
                         // 保存状态
-                        let state = crate::state::extract_state(app);
-                        let _ = crate::state::save_state(&state);
+                        app.save_state();
 
                         log_debug("已清空项目".to_string());
                     }
                 }
             }
         }
+        Command::GrabPane => {
+            if app.saved_layout.is_some() {
+                app.show_notification(
+                    "最大化时无法交换面板，请先恢复布局".to_string(),
+                    NotificationLevel::Warning,
+                );
+            } else if app.split_tree.collect_pane_ids().len() <= 1 {
+                app.show_notification("只有一个面板".to_string(), NotificationLevel::Info);
+            } else {
+                app.grabbed_pane = Some(app.focused_pane);
+                app.show_notification(
+                    "已抓取面板：h/j/k/l 与相邻面板交换项目，Esc 取消".to_string(),
+                    NotificationLevel::Info,
+                );
+            }
+        }
         Command::MaximizePane => {
             // 最大化/恢复当前面板
             app.toggle_maximize();
         }
+        Command::TogglePaneReadOnly => {
+            let pane = app.focused_pane;
+            let message = if app.read_only_panes.remove(&pane) {
+                "已解锁面板"
+            } else {
+                app.read_only_panes.insert(pane);
+                "已锁定面板（只读）"
+            };
+            app.save_state();
+            app.show_notification(message.to_string(), NotificationLevel::Info);
+        }
+        Command::RestoreHomeLayout => {
+            if app.home_layout.is_none() {
+                app.show_notification(
+                    "还没有主页布局，先用 :home-set 保存当前布局".to_string(),
+                    NotificationLevel::Warning,
+                );
+            } else if app.restore_home_layout() {
+                app.show_notification("已恢复主页布局".to_string(), NotificationLevel::Info);
+            } else {
+                app.show_notification(
+                    "主页布局中的项目都已不存在".to_string(),
+                    NotificationLevel::Warning,
+                );
+            }
+        }
+        Command::SetHomeLayout => {
+            app.set_home_layout();
+            app.show_notification(
+                "已把当前布局保存为主页布局（Space w 0 或 :home 恢复）".to_string(),
+                NotificationLevel::Success,
+            );
+        }
         Command::EditTaskInEditor => {
             // 用外部编辑器编辑当前选中的任务
             if let Some(task) = get_selected_task(app) {
+                let task_id = task.id;
                 // 直接打开项目文件
                 app.pending_editor_file = Some(task.file_path.to_string_lossy().to_string());
+                if let Some(project_id) = get_focused_project_id(app) {
+                    app.remember_edited(&project_id, task_id);
+                }
             }
         }
         Command::ViewTaskExternal => {
@@ -1432,8 +1711,25 @@ fn execute_command(app: &mut App, cmd: Command) {
             // TUI 内预览当前选中的任务
             if let Some(task) = get_selected_task(app) {
                 // 读取任务文件内容
+                let header = app
+                    .get_focused_project()
+                    .map(|project| {
+                        task_field_values(&project.card_fields(), &task.extra)
+                            .into_iter()
+                            .map(|(label, value)| format!("{}: {}", label, value))
+                            .collect::<Vec<_>>()
+                            .join("  ·  ")
+                    })
+                    .unwrap_or_default();
+                let base_dir = task.file_path.parent().map(|p| p.to_path_buf());
                 if let Ok(content) = std::fs::read_to_string(&task.file_path) {
+                    app.preview_title = match task.stats.summary() {
+                        Some(summary) => format!("任务预览 · {}", summary),
+                        None => "任务预览".to_string(),
+                    };
+                    app.preview_header = header;
                     app.preview_content = content;
+                    app.preview_base_dir = base_dir;
                     app.preview_scroll = 0;
                     app.mode = Mode::Preview;
                 } else {
@@ -1448,7 +1744,6 @@ fn execute_command(app: &mut App, cmd: Command) {
 
                 // 显示确认对话框
                 app.mode = Mode::Dialog;
-                // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
                 app.dialog = Some(DialogType::Confirm {
                     title: "删除任务".to_string(),
                     message: format!("确定要删除任务 \"{}\" 吗？", task_title),
@@ -1457,6 +1752,30 @@ fn execute_command(app: &mut App, cmd: Command) {
                 });
             }
         }
+        Command::RenumberTasks => {
+            // 重新编号任务 ID：先展示方案，确认后执行
+            if let Some(project) = app.get_focused_project() {
+                let plan = crate::fs::task::plan_task_renumber(&project.tasks);
+                if plan.is_empty() {
+                    app.show_notification(
+                        "任务编号已连续，无需调整".to_string(),
+                        NotificationLevel::Info,
+                    );
+                } else {
+                    app.mode = Mode::Dialog;
+                    app.dialog = Some(DialogType::Confirm {
+                        title: "重新编号任务".to_string(),
+                        message: format!(
+                            "将重写 {} 个任务的 ID 和文件名：{}",
+                            plan.len(),
+                            format_renumber_plan(&plan)
+                        ),
+                        yes_selected: false,
+                        action: crate::ui::dialogs::ConfirmAction::RenumberTasks,
+                    });
+                }
+            }
+        }
         Command::CopyTask => {
             // 复制任务到剪贴板
             #[cfg(feature = "clipboard")]
@@ -1505,6 +1824,39 @@ fn execute_command(app: &mut App, cmd: Command) {
                 );
             }
         }
+        Command::SendTaskToPane => start_send_to_pane(app),
+        Command::NewTaskFromClipboard => {
+            #[cfg(feature = "clipboard")]
+            {
+                match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+                    Ok(text) => {
+                        // 去掉开头的空行，第一行非空内容作为标题
+                        let text = text.trim_start_matches(['\n', '\r']).trim_end();
+                        if text.trim().is_empty() {
+                            app.show_notification(
+                                "剪贴板中没有文本".to_string(),
+                                crate::app::NotificationLevel::Warning,
+                            );
+                        } else {
+                            create_new_task(app, text.to_string());
+                        }
+                    }
+                    Err(e) => {
+                        app.show_notification(
+                            format!("无法读取剪贴板: {}", e),
+                            crate::app::NotificationLevel::Error,
+                        );
+                    }
+                }
+            }
+            #[cfg(not(feature = "clipboard"))]
+            {
+                app.show_notification(
+                    "剪贴板功能未启用".to_string(),
+                    crate::app::NotificationLevel::Warning,
+                );
+            }
+        }
         Command::SetTaskPriority(priority) => {
             // 设置任务优先级
             let task_id = if let Some(id) = get_selected_task_id(app) {
@@ -1513,18 +1865,18 @@ fn execute_command(app: &mut App, cmd: Command) {
                 return;
             };
 
-            let project_name = if let Some(crate::ui::layout::SplitNode::Leaf {
-                project_id: Some(name),
+            let project_id = if let Some(crate::ui::layout::SplitNode::Leaf {
+                project_id: Some(id),
                 ..
             }) = app.split_tree.find_pane(app.focused_pane)
             {
-                name.clone()
+                id.clone()
             } else {
                 return;
             };
 
             let mut result = Ok(());
-            if let Some(project) = app.projects.iter_mut().find(|p| p.name == project_name)
+            if let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id)
                 && let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id)
             {
                 let old_priority = task.priority.clone();
@@ -1543,6 +1895,7 @@ fn execute_command(app: &mut App, cmd: Command) {
 
             match result {
                 Ok(_) => {
+                    app.remember_edited(&project_id, task_id);
                     app.show_notification(
                         format!(
                             "优先级已设置为: {}",
@@ -1561,23 +1914,102 @@ fn execute_command(app: &mut App, cmd: Command) {
                 }
             }
         }
+        Command::SetTaskColor(color) => {
+            // 设置任务卡片颜色
+            let Some(task_id) = get_selected_task_id(app) else {
+                return;
+            };
+            let Some(project) = app.get_focused_project_mut() else {
+                return;
+            };
+            let project_path = project.path.clone();
+            let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+                return;
+            };
+
+            let old_color = task.color().map(str::to_string);
+            task.set_color((color != "none").then_some(color.as_str()));
+            if let Err(e) = crate::fs::save_task(&project_path, task) {
+                task.set_color(old_color.as_deref()); // 回滚
+                app.show_notification(
+                    format!("保存卡片颜色失败: {}", e),
+                    crate::app::NotificationLevel::Error,
+                );
+                return;
+            }
+
+            let project_id = project.id.clone();
+            app.remember_edited(&project_id, task_id);
+            let label = crate::ui::theme::CARD_COLORS
+                .iter()
+                .find(|(name, _, _)| *name == color)
+                .map_or("无", |(_, _, label)| *label);
+            app.show_notification(
+                format!("卡片颜色已设置为: {}", label),
+                crate::app::NotificationLevel::Success,
+            );
+        }
+        Command::JumpToLastEdited => jump_to_last_edited(app),
+        Command::AddTaskNote => {
+            // 追加笔记：输入内容后写入正文的 Notes 小节
+            if let Some(task) = get_selected_task(app) {
+                let title = format!("添加笔记 #{}", task.id);
+                app.mode = Mode::Dialog;
+                app.dialog = Some(DialogType::Input {
+                    title,
+                    purpose: DialogPurpose::AddNote,
+                    prompt: "笔记内容（自动加上时间戳）:".to_string(),
+                    input: crate::ui::dialogs::DialogInput::text(String::new(), false, false),
+                });
+            }
+        }
         Command::EditTags => {
             // 编辑任务标签
             if let Some(task) = get_selected_task(app) {
                 let current_tags = task.tags.join(", ");
                 app.mode = Mode::Dialog;
-                // app.ime_state.enter_dialog();  // 进入对话框，恢复用户输入法（已禁用）
                 app.dialog = Some(DialogType::Input {
                     title: "编辑标签".to_string(),
+                    purpose: DialogPurpose::EditTags,
                     prompt: "标签（逗号分隔）:".to_string(),
-                    textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                        current_tags,
-                        true,
-                        false,
-                    )),
+                    input: crate::ui::dialogs::DialogInput::line(&current_tags),
                 });
             }
         }
+        Command::EditFields => {
+            // 编辑自定义字段：每行一个 "键名 = 值"，留空表示清除
+            let Some(task) = get_selected_task(app) else {
+                return;
+            };
+            let Some(fields) = app.get_focused_project().map(|p| p.card_fields()) else {
+                return;
+            };
+
+            let content = fields
+                .iter()
+                .map(|field| {
+                    let value = task
+                        .extra
+                        .get(&field.name)
+                        .map(crate::models::field::format_field_value)
+                        .unwrap_or_default();
+                    format!("{} = {}", field.name, value)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let kinds = fields
+                .iter()
+                .map(|field| format!("{}:{}", field.label(), field.kind.label()))
+                .collect::<Vec<_>>()
+                .join(" ");
+            app.mode = Mode::Dialog;
+            app.dialog = Some(DialogType::Input {
+                title: "编辑字段".to_string(),
+                purpose: DialogPurpose::EditFields,
+                prompt: format!("每行 键名 = 值，留空清除（{}）:", kinds),
+                input: crate::ui::dialogs::DialogInput::text(content, true, false),
+            });
+        }
         Command::EnterSearch => {
             // 进入搜索模式
             app.mode = Mode::Search;
@@ -1636,29 +2068,66 @@ fn execute_command(app: &mut App, cmd: Command) {
         Command::ToggleMaximizeColumn => {
             toggle_maximize_column(app);
         }
-        Command::ReloadCurrentProject => {
-            // 重新加载当前项目
-            if let Err(e) = app.reload_current_project() {
-                log_debug(format!("重新加载当前项目失败: {}", e));
+        Command::ToggleZoomColumn => {
+            if app.zoomed_pane == Some(app.focused_pane) {
+                app.zoomed_pane = None;
+            } else if app.get_focused_project().is_some() {
+                app.zoomed_pane = Some(app.focused_pane);
+            }
+        }
+        Command::TogglePriorityFilter(filter) => {
+            toggle_priority_filter(app, filter);
+        }
+        Command::ToggleTagFilter(index) => {
+            toggle_tag_filter(app, index);
+        }
+        Command::ToggleFocusMode => {
+            app.toggle_focus_mode();
+            let message = if app.focus_mode {
+                "专注模式：已隐藏完成列，其他面板折叠（再次 :focus 退出）"
             } else {
-                log_debug("重新加载当前项目成功".to_string());
-            }
-        }
-        Command::ReloadAllProjects => {
-            // 重新加载所有项目（本地+全局）
-            match crate::fs::load_all_projects() {
-                Ok(projects) => {
-                    app.projects = projects;
-                    log_debug(format!(
-                        "重新加载所有项目成功，共 {} 个",
-                        app.projects.len()
-                    ));
-                }
-                Err(e) => {
-                    log_debug(format!("重新加载所有项目失败: {}", e));
+                "已退出专注模式"
+            };
+            app.show_notification(message.to_string(), NotificationLevel::Info);
+        }
+        Command::ToggleRedact => {
+            app.redact = !app.redact;
+            let message = if app.redact {
+                "屏幕共享模式：已隐藏任务标题和正文（再次 :redact 恢复）"
+            } else {
+                "已退出屏幕共享模式"
+            };
+            app.show_notification(message.to_string(), NotificationLevel::Info);
+        }
+        Command::ReloadCurrentProject => app.request_reload_current(),
+        Command::ReloadAllProjects => app.start_reload_all(),
+        Command::ProjectSettings => {
+            let Some(path) = app.get_focused_project().map(|p| p.path.clone()) else {
+                return;
+            };
+            match crate::fs::settings::ProjectSettings::load(&path) {
+                Ok(settings) => {
+                    app.mode = Mode::Dialog;
+                    app.dialog = Some(DialogType::Input {
+                        title: "项目设置".to_string(),
+                        purpose: DialogPurpose::ProjectSettings,
+                        prompt: "color = 项目标签颜色；status = 目录名 | 显示名 | 颜色 | WIP 上限；行序即列序，新增行创建状态，删除行删除空状态:".to_string(),
+                        input: crate::ui::dialogs::DialogInput::text(settings.to_form(), true, false),
+                    });
                 }
+                Err(e) => app.show_notification(
+                    format!("读取项目配置失败: {}", e),
+                    NotificationLevel::Error,
+                ),
             }
         }
+        Command::RevealProject => reveal_project(app),
+        Command::OpenShell => match app.get_focused_project() {
+            Some(project) => app.pending_shell_dir = Some(project.path.clone()),
+            None => {
+                app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning)
+            }
+        },
         Command::CopyProjectInfo => {
             // 复制项目信息到剪贴板
             #[cfg(feature = "clipboard")]
@@ -1714,15 +2183,11 @@ fn execute_command(app: &mut App, cmd: Command) {
         Command::CreateStatus => {
             // 创建新状态
             app.mode = Mode::Dialog;
-            // app.ime_state.enter_dialog();  // 已禁用输入法自动切换
             app.dialog = Some(DialogType::Input {
                 title: "创建新状态".to_string(),
+                purpose: DialogPurpose::CreateStatus,
                 prompt: "请输入状态内部名称（英文、数字、下划线）:".to_string(),
-                textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                    String::new(),
-                    true,
-                    false,
-                )), // 默认 Insert 模式
+                input: crate::ui::dialogs::DialogInput::line(""),
             });
         }
         Command::RenameStatus => {
@@ -1746,15 +2211,11 @@ fn execute_command(app: &mut App, cmd: Command) {
 
             if let Some((current_name, current_display)) = status_info {
                 app.mode = Mode::Dialog;
-                // app.ime_state.enter_dialog();  // 已禁用输入法自动切换
                 app.dialog = Some(DialogType::Input {
                     title: format!("重命名状态: {}", current_display),
+                    purpose: DialogPurpose::RenameStatus,
                     prompt: "请输入新的状态名称（英文、数字、下划线）:".to_string(),
-                    textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                        current_name,
-                        true,
-                        false,
-                    )), // 默认 Insert 模式
+                    input: crate::ui::dialogs::DialogInput::line(&current_name),
                 });
             }
         }
@@ -1768,27 +2229,15 @@ fn execute_command(app: &mut App, cmd: Command) {
                         .get(&app.focused_pane)
                         .copied()
                         .unwrap_or(0);
-                    project
-                        .statuses
-                        .get(column)
-                        .map(|s| (s.name.clone(), s.display.clone()))
+                    project.statuses.get(column).map(|s| s.display.clone())
                 } else {
                     None
                 }
             };
 
-            if let Some((status_name, current_display)) = status_info {
-                app.mode = Mode::Dialog;
-                // app.ime_state.enter_dialog();  // 已禁用输入法自动切换
-                app.dialog = Some(DialogType::Input {
-                    title: format!("编辑显示名: {}", status_name),
-                    prompt: "请输入新的显示名称:".to_string(),
-                    textarea: Box::new(crate::ui::text_input::HelixTextArea::new(
-                        current_display,
-                        true,
-                        false,
-                    )),
-                });
+            if let Some(current_display) = status_info {
+                app.status_rename_input = crate::ui::text_field::TextField::new(&current_display);
+                app.mode = Mode::StatusRename;
             }
         }
         Command::MoveStatusLeft => {
@@ -2026,7 +2475,6 @@ fn execute_command(app: &mut App, cmd: Command) {
                     };
 
                     app.mode = Mode::Dialog;
-                    // app.ime_state.enter_dialog();  // 已禁用输入法自动切换
                     app.dialog = Some(DialogType::Confirm {
                         title: "删除状态".to_string(),
                         message,
@@ -2043,116 +2491,869 @@ fn execute_command(app: &mut App, cmd: Command) {
     }
 }
 
+/// 执行别名展开的命令序列，遇到嵌套别名或未知命令时停止
+fn run_alias_steps(app: &mut App, steps: &[String]) -> bool {
+    for step in steps {
+        let name = step.split_whitespace().next().unwrap_or("");
+        if app.command_registry.is_user_alias(name) {
+            app.show_notification(
+                format!("别名中不能再使用别名: {}", name),
+                NotificationLevel::Warning,
+            );
+            return true;
+        }
+        if app.command_registry.find_exact(name).is_none() {
+            app.show_notification(
+                format!("别名中的未知命令: {}", name),
+                NotificationLevel::Warning,
+            );
+            return true;
+        }
+        if !execute_text_command(app, step) {
+            return false;
+        }
+    }
+    true
+}
+
+/// 不经过 `Command` 直接修改项目的文本命令
+const READ_ONLY_BLOCKED_COMMANDS: &[&str] = &[
+    "tag",
+    "tags",
+    "estimate",
+    "note",
+    "task-renumber",
+    "done-cleanup",
+    "task-import",
+    "revert-external",
+];
+
+/// 当前面板锁定为只读时提示并返回 true
+fn reject_read_only_pane(app: &mut App) -> bool {
+    if !app.is_pane_read_only(app.focused_pane) {
+        return false;
+    }
+    app.show_notification(
+        "面板已锁定（只读），Space w r 解锁".to_string(),
+        NotificationLevel::Warning,
+    );
+    true
+}
+
 /// 执行文本命令（从命令模式输入）
 /// 返回 false 表示应该退出应用
-#[allow(dead_code)]
 fn execute_text_command(app: &mut App, cmd_str: &str) -> bool {
-    let cmd_str = cmd_str.trim();
+    let mut parts = cmd_str.split_whitespace();
+    let Some(name) = parts.next() else {
+        return true;
+    };
+    let args: Vec<&str> = parts.collect();
 
     // 查找命令定义
-    let cmd_def = app.command_registry.find_exact(cmd_str);
-
-    if let Some(cmd_def) = cmd_def {
-        // 根据命令名执行对应操作
-        match cmd_def.name {
-            "quit" => {
-                return false; // 退出应用
-            }
-            "project-open" => execute_command(app, Command::OpenProject),
-            "project-new" => execute_command(app, Command::NewGlobalProject),
-            "project-new-local" => execute_command(app, Command::NewLocalProject),
-            "project-delete" => execute_command(app, Command::DeleteProject),
-            "project-rename" => execute_command(app, Command::RenameProject),
-            "task-new" => execute_command(app, Command::NewTask),
-            "task-edit" => execute_command(app, Command::EditTask),
-            "task-delete" => execute_command(app, Command::DeleteTask),
-            "task-view" => execute_command(app, Command::ViewTask),
-            "task-view-external" => execute_command(app, Command::ViewTaskExternal),
-            "task-edit-external" => execute_command(app, Command::EditTaskInEditor),
-            "priority-high" => execute_command(app, Command::SetTaskPriority("high".to_string())),
-            "priority-medium" => {
-                execute_command(app, Command::SetTaskPriority("medium".to_string()))
-            }
-            "priority-low" => execute_command(app, Command::SetTaskPriority("low".to_string())),
-            "priority-none" => execute_command(app, Command::SetTaskPriority("none".to_string())),
-            "split-horizontal" => execute_command(app, Command::SplitHorizontal),
-            "split-vertical" => execute_command(app, Command::SplitVertical),
-            "close-pane" => execute_command(app, Command::ClosePane),
-            "focus-next" => execute_command(app, Command::FocusNextPane),
-            "focus-left" => execute_command(app, Command::FocusLeft),
-            "focus-right" => execute_command(app, Command::FocusRight),
-            "focus-up" => execute_command(app, Command::FocusUp),
-            "focus-down" => execute_command(app, Command::FocusDown),
-            "reload" => execute_command(app, Command::ReloadCurrentProject),
-            "reload-all" => execute_command(app, Command::ReloadAllProjects),
-            "help" => {
-                app.mode = Mode::Help;
+    let Some(cmd_def) = app.command_registry.find_exact(name) else {
+        app.show_notification(format!("未知命令: {}", name), NotificationLevel::Warning);
+        return true;
+    };
+
+    // 用户别名：依次执行展开的命令，输入的参数附加到最后一条命令
+    if let Some(mut steps) = app.command_registry.user_alias(cmd_def.name) {
+        if let Some(last) = steps.last_mut()
+            && !args.is_empty()
+        {
+            last.push(' ');
+            last.push_str(&args.join(" "));
+        }
+        return run_alias_steps(app, &steps);
+    }
+
+    // 直接修改项目的文本命令（不经过 execute_command）在只读面板中拒绝执行；
+    // 不带参数的 :tags 只查看标签
+    let command = cmd_def.name;
+    let views_only = command == "tags" && args.is_empty();
+    if READ_ONLY_BLOCKED_COMMANDS.contains(&command) && !views_only && reject_read_only_pane(app) {
+        return true;
+    }
+
+    // 根据命令名执行对应操作
+    match command {
+        "quit" | "write-quit" => {
+            return false; // 退出应用
+        }
+        "write" => write_pending(app),
+        "project-open" => execute_command(app, Command::OpenProject),
+        "project-new" => execute_command(app, Command::NewGlobalProject),
+        "project-new-local" => execute_command(app, Command::NewLocalProject),
+        "project-delete" => execute_command(app, Command::DeleteProject),
+        "project-rename" => execute_command(app, Command::RenameProject),
+        "project-hide" => execute_command(app, Command::HideProject),
+        "project-settings" => execute_command(app, Command::ProjectSettings),
+        "project-info" => execute_command(app, Command::CopyProjectInfo),
+        "task-new" => execute_command(app, Command::NewTask),
+        "task-edit" => execute_command(app, Command::EditTask),
+        "task-delete" => execute_command(app, Command::DeleteTask),
+        "task-view" => execute_command(app, Command::ViewTask),
+        "task-view-external" => execute_command(app, Command::ViewTaskExternal),
+        "task-edit-external" => execute_command(app, Command::EditTaskInEditor),
+        "task-fields" => execute_command(app, Command::EditFields),
+        "task-from-clipboard" => execute_command(app, Command::NewTaskFromClipboard),
+        "task-copy" => execute_command(app, Command::CopyTask),
+        "tag" if args.is_empty() => execute_command(app, Command::EditTags),
+        "tags" if args.is_empty() => show_tag_overview(app),
+        // :tags +标签 -标签 与 :tag 相同（tags 曾是 tag 的别名）
+        "tag" | "tags" => match crate::models::task::TagEdit::parse(&args) {
+            Ok(edit) => edit_tags(app, &edit),
+            Err(e) => app.show_notification(e, NotificationLevel::Warning),
+        },
+        "estimate" => update_task_fields(
+            app,
+            &format!(
+                "{} = {}",
+                crate::models::field::ESTIMATE_FIELD,
+                args.join(" ")
+            ),
+        ),
+        "task-renumber" => {
+            if args.contains(&"--dry-run") {
+                show_renumber_preview(app);
+            } else {
+                execute_command(app, Command::RenumberTasks);
             }
-            _ => {
-                // 未实现的命令：静默忽略
+        }
+        "journal" => show_journal(app),
+        "report" => match args.split_first() {
+            Some((&"aging", export)) => show_aging_report(app, export.first().copied()),
+            _ => app.show_notification(
+                "用法: :report aging [导出文件.md]".to_string(),
+                NotificationLevel::Warning,
+            ),
+        },
+        "peek" => peek_project(app, &args.join(" ")),
+        "reveal" => execute_command(app, Command::RevealProject),
+        "backup" => backup_current_project(app),
+        "yank-column" => yank_column(app, &args),
+        "shell" => execute_command(app, Command::OpenShell),
+        "done-cleanup" => match args.first().map(|days| days.parse::<u64>()) {
+            None => cleanup_done_tasks(app, DEFAULT_DONE_CLEANUP_DAYS),
+            Some(Ok(days)) => cleanup_done_tasks(app, days),
+            Some(Err(_)) => app.show_notification(
+                format!("天数无效: {}（用法 :done-cleanup 30）", args[0]),
+                NotificationLevel::Warning,
+            ),
+        },
+        "zoom-column" => execute_command(app, Command::ToggleZoomColumn),
+        "focus" => execute_command(app, Command::ToggleFocusMode),
+        "redact" => execute_command(app, Command::ToggleRedact),
+        "tutor" => app.toggle_tutor(),
+        "version" => show_about(app),
+        "changelog" => app.show_changelog(args.first() == Some(&"all")),
+        "open-url" => open_task_url(app, args.first().copied()),
+        "spell-add" => add_spelling_words(app, &args),
+        "column-layout" => {
+            let layout = match args.first().copied() {
+                None => match app.column_layout(app.focused_pane) {
+                    ColumnLayout::Equal => Some(ColumnLayout::Auto),
+                    ColumnLayout::Auto => Some(ColumnLayout::Equal),
+                },
+                Some(value) => ColumnLayout::parse(value),
+            };
+            match layout {
+                Some(layout) => set_column_layout(app, layout),
+                None => app.show_notification(
+                    format!("未知的列宽策略: {}（可选 auto / equal）", args[0]),
+                    NotificationLevel::Warning,
+                ),
             }
         }
-    } else {
-        // 未知命令：静默忽略
+        "filter-priority" => match args.first().copied() {
+            Some("high") => {
+                execute_command(app, Command::TogglePriorityFilter(PriorityFilter::High))
+            }
+            Some("medium") => {
+                execute_command(app, Command::TogglePriorityFilter(PriorityFilter::Medium))
+            }
+            Some("all") | None => {
+                execute_command(app, Command::TogglePriorityFilter(PriorityFilter::All))
+            }
+            Some(other) => app.show_notification(
+                format!("未知的优先级筛选: {}（可选 high / medium / all）", other),
+                NotificationLevel::Warning,
+            ),
+        },
+        "task-import" => {
+            if args.is_empty() {
+                open_import_dialog(app);
+            } else {
+                import_tasks_from_file(app, &args.join(" "));
+            }
+        }
+        "priority-high" => execute_command(app, Command::SetTaskPriority("high".to_string())),
+        "priority-medium" => execute_command(app, Command::SetTaskPriority("medium".to_string())),
+        "priority-low" => execute_command(app, Command::SetTaskPriority("low".to_string())),
+        "priority-none" => execute_command(app, Command::SetTaskPriority("none".to_string())),
+        "diff-panes" => match args.first().copied() {
+            Some("off") => {
+                app.pane_diff.clear();
+                app.show_notification("已关闭面板比较".to_string(), NotificationLevel::Info);
+            }
+            Some(number) => match number.parse() {
+                Ok(number) => diff_panes(app, Some(number)),
+                Err(_) => app.show_notification(
+                    "用法: :diff-panes [面板编号|off]".to_string(),
+                    NotificationLevel::Warning,
+                ),
+            },
+            None => diff_panes(app, None),
+        },
+        "note" if args.is_empty() => execute_command(app, Command::AddTaskNote),
+        "note" => add_task_note(app, &args.join(" ")),
+        "task-color" => match args.first().copied() {
+            Some("none") => execute_command(app, Command::SetTaskColor("none".to_string())),
+            Some(name) => match crate::ui::theme::card_color_name(name) {
+                Some(color) => execute_command(app, Command::SetTaskColor(color.to_string())),
+                None => app.show_notification(
+                    format!("未知的卡片颜色: {}（可选 {}）", name, card_color_choices()),
+                    NotificationLevel::Warning,
+                ),
+            },
+            None => app.show_notification(
+                format!("用法: :task-color <{}|none>", card_color_choices()),
+                NotificationLevel::Warning,
+            ),
+        },
+        "split-horizontal" => execute_command(app, Command::SplitHorizontal),
+        "split-vertical" => execute_command(app, Command::SplitVertical),
+        "close-pane" => execute_command(app, Command::ClosePane),
+        "focus-next" => execute_command(app, Command::FocusNextPane),
+        "focus-left" => execute_command(app, Command::FocusLeft),
+        "focus-right" => execute_command(app, Command::FocusRight),
+        "focus-up" => execute_command(app, Command::FocusUp),
+        "focus-down" => execute_command(app, Command::FocusDown),
+        "pane-maximize" => execute_command(app, Command::MaximizePane),
+        "pane-grab" => execute_command(app, Command::GrabPane),
+        "pane-lock" => execute_command(app, Command::TogglePaneReadOnly),
+        "home" => execute_command(app, Command::RestoreHomeLayout),
+        "home-set" => execute_command(app, Command::SetHomeLayout),
+        "status-new" => execute_command(app, Command::CreateStatus),
+        "status-rename" => execute_command(app, Command::RenameStatus),
+        "status-edit" => execute_command(app, Command::EditStatusDisplay),
+        "status-move-left" => execute_command(app, Command::MoveStatusLeft),
+        "status-move-right" => execute_command(app, Command::MoveStatusRight),
+        "status-move-first" => execute_command(app, Command::MoveStatusToFirst),
+        "status-move-last" => execute_command(app, Command::MoveStatusToLast),
+        "status-delete" => execute_command(app, Command::DeleteStatus),
+        "reload" => execute_command(app, Command::ReloadCurrentProject),
+        "reload-all" => execute_command(app, Command::ReloadAllProjects),
+        "revert-external" => confirm_revert_external_changes(app),
+        "set" => set_config_option(app, &args),
+        "hints" => toggle_key_hints(app),
+        "lock" => app.lock_screen(),
+        "last" => execute_command(app, Command::JumpToLastEdited),
+        "script" => match args.as_slice() {
+            [] | ["list"] => list_user_scripts(app),
+            ["run", name] => run_user_script(app, name),
+            _ => app.show_notification(
+                "用法: :script list / :script run <名称>".to_string(),
+                NotificationLevel::Warning,
+            ),
+        },
+        "config-detect" => {
+            crate::config::redetect_tools(&mut app.config);
+            match save_app_config(app) {
+                Ok(()) => app.show_notification(
+                    format!(
+                        "编辑器: {} | 预览器: {}",
+                        app.config.editor,
+                        crate::config::viewer_label(&app.config.markdown_viewer)
+                    ),
+                    NotificationLevel::Success,
+                ),
+                Err(e) => {
+                    app.show_notification(format!("保存配置失败: {}", e), NotificationLevel::Error)
+                }
+            }
+        }
+        "help" => {
+            app.mode = Mode::Help;
+        }
+        _ => {
+            // 未实现的命令：静默忽略
+        }
     }
 
     true // 继续运行
 }
 
-/// 跳转到搜索匹配项
-#[allow(dead_code)]
-fn jump_to_search_match(app: &mut App, state: &crate::app::SearchState) {
-    if state.matches.is_empty() {
+/// 在预览窗口中显示当前项目的变更日志（最新的在前）
+fn show_journal(app: &mut App) {
+    let Some(project) = app.get_focused_project() else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
         return;
-    }
-
-    let (task_index, status) = &state.matches[state.selected];
-
-    // 找到对应的列
-    let project = match app.get_focused_project() {
-        Some(p) => p,
-        None => return,
-    };
-
-    let column = match project.statuses.iter().position(|s| &s.name == status) {
-        Some(c) => c,
-        None => return,
     };
 
-    // 设置选中的列
-    app.selected_column.insert(app.focused_pane, column);
+    let paths: Vec<_> = crate::fs::journal::journal_path(&project.path)
+        .into_iter()
+        .collect();
+    let entries = crate::fs::journal::read_recent(
+        &paths,
+        Some(&crate::fs::journal::ProjectKey::of(project)),
+        500,
+    );
 
-    // 设置选中的任务
-    app.selected_task_index
-        .insert(app.focused_pane, *task_index);
+    app.preview_title = format!("变更日志 - {}", project.name);
+    app.preview_header.clear();
+    app.preview_base_dir = None;
+    app.preview_content = if entries.is_empty() {
+        "暂无变更记录".to_string()
+    } else {
+        entries
+            .iter()
+            .map(crate::fs::journal::format_entry)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    app.preview_scroll = 0;
+    app.mode = Mode::Preview;
 }
 
-/// 获取当前选中的任务
-fn get_selected_task(app: &App) -> Option<&crate::models::Task> {
-    let project = app.get_focused_project()?;
-    let column = app
-        .selected_column
-        .get(&app.focused_pane)
-        .copied()
-        .unwrap_or(0);
-    let task_idx = app
-        .selected_task_index
-        .get(&app.focused_pane)
-        .copied()
-        .unwrap_or(0);
+/// `:version`：在预览弹窗中显示版本和构建信息，便于报告问题时复制
+fn show_about(app: &mut App) {
+    let info = crate::build_info::BuildInfo::current();
+    app.preview_title = format!("关于 {}", info.name);
+    app.preview_header = "hxk --version --json 输出 JSON".to_string();
+    app.preview_base_dir = None;
+    app.preview_content = info.to_text();
+    app.preview_scroll = 0;
+    app.mode = Mode::Preview;
+}
 
-    let status = app.get_status_name_by_column(column)?;
+/// `:report aging`：在预览中显示任务停留时间报告，指定文件时导出为 Markdown
+fn show_aging_report(app: &mut App, export: Option<&str>) {
+    let Some(project) = app.get_focused_project() else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
 
-    let tasks: Vec<_> = project
-        .tasks
-        .iter()
-        .filter(|t| t.status == status)
+    let paths: Vec<_> = crate::fs::journal::journal_path(&project.path)
+        .into_iter()
         .collect();
-    tasks.get(task_idx).copied()
+    let journal = crate::fs::journal::read_recent(
+        &paths,
+        Some(&crate::fs::journal::ProjectKey::of(project)),
+        usize::MAX,
+    );
+    let now = chrono::Local::now().fixed_offset();
+    let rows = crate::report::aging(project, &journal, now);
+    let markdown = crate::report::to_markdown(&project.name, &rows, now);
+
+    if let Some(path) = export {
+        match std::fs::write(path, &markdown) {
+            Ok(()) => app.show_notification(
+                format!("已导出任务停留时间报告: {}", path),
+                NotificationLevel::Success,
+            ),
+            Err(e) => app.show_notification(
+                format!("无法写入 {}: {}", path, e),
+                NotificationLevel::Error,
+            ),
+        }
+        return;
+    }
+
+    app.preview_title = format!("任务停留时间 - {}", project.name);
+    app.preview_header = format!("{} 个未完成任务 · :report aging <文件> 导出", rows.len());
+    app.preview_base_dir = None;
+    app.preview_content = markdown;
+    app.preview_scroll = 0;
+    app.mode = Mode::Preview;
 }
 
-/// 获取当前选中的任务（可变）- 返回任务 ID
-fn get_selected_task_id(app: &App) -> Option<u32> {
+/// `:set editor <命令>` / `:set viewer <命令>`：修改外部工具并保存配置
+fn set_config_option(app: &mut App, args: &[&str]) {
+    let value = args.get(1..).unwrap_or_default().join(" ");
+    match (args.first().copied(), value.is_empty()) {
+        (Some("editor"), false) => app.config.editor = value,
+        (Some("viewer"), _) => app.config.markdown_viewer = value,
+        _ => {
+            app.show_notification(
+                "用法: :set editor <命令> / :set viewer <命令>".to_string(),
+                NotificationLevel::Warning,
+            );
+            return;
+        }
+    }
+
+    if let Err(e) = save_app_config(app) {
+        app.show_notification(format!("保存配置失败: {}", e), NotificationLevel::Error);
+        return;
+    }
+    match crate::config::tool_warnings(&app.config).first() {
+        Some(warning) => {
+            app.show_notification(format!("已保存，但{}", warning), NotificationLevel::Warning)
+        }
+        None => app.show_notification(
+            format!(
+                "编辑器: {} | 预览器: {}",
+                app.config.editor,
+                crate::config::viewer_label(&app.config.markdown_viewer)
+            ),
+            NotificationLevel::Success,
+        ),
+    }
+}
+
+/// `:script list`：列出 `~/.kanban/scripts` 中的脚本
+fn list_user_scripts(app: &mut App) {
+    let dir = crate::script::scripts_dir();
+    let names = crate::script::list_scripts(&dir);
+    if names.is_empty() {
+        app.show_notification(
+            format!("{} 中没有 .rhai 脚本", dir.display()),
+            NotificationLevel::Info,
+        );
+    } else {
+        app.show_notification(
+            format!("脚本: {}（:script run <名称>）", names.join(", ")),
+            NotificationLevel::Info,
+        );
+    }
+}
+
+/// `:script run <名称>`：对聚焦的项目执行脚本，先备份项目，结束后重新加载
+fn run_user_script(app: &mut App, name: &str) {
+    if reject_read_only_pane(app) {
+        return;
+    }
+    let Some(project) = app.get_focused_project().cloned() else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    let result = crate::fs::backup::before(&project.path, "script")
+        .and_then(|_| crate::script::run_named(&project, name));
+    match result {
+        Ok(outcome) => {
+            app.reload_project(&project.id);
+            let mut message = format!("脚本 {}：更新了 {} 个任务", name, outcome.updated);
+            if !outcome.messages.is_empty() {
+                message.push_str(&format!(" · {}", outcome.messages.join(" · ")));
+            }
+            app.show_notification(message, NotificationLevel::Success);
+        }
+        Err(e) => app.show_notification(e, NotificationLevel::Error),
+    }
+}
+
+/// 切换面板底边的键位提示，并写回配置
+fn toggle_key_hints(app: &mut App) {
+    app.config.key_hints = !app.config.key_hints;
+    if let Err(e) = save_app_config(app) {
+        app.show_notification(format!("保存配置失败: {}", e), NotificationLevel::Error);
+        return;
+    }
+    let state = if app.config.key_hints {
+        "开启"
+    } else {
+        "关闭"
+    };
+    app.show_notification(format!("键位提示已{}", state), NotificationLevel::Info);
+}
+
+/// `:peek` 每列显示的任务数
+const PEEK_TASKS_PER_COLUMN: usize = 5;
+
+/// 在预览窗口中只读显示另一个项目的看板，按名称匹配（不区分大小写，可用唯一前缀）
+fn peek_project(app: &mut App, name: &str) {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        app.show_notification(
+            "用法: :peek <项目名>".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+
+    let visible: Vec<&crate::models::Project> = app
+        .projects
+        .iter()
+        .filter(|p| !crate::config::is_project_hidden(&app.config, p))
+        .collect();
+    let exact = visible
+        .iter()
+        .copied()
+        .find(|p| p.name.to_lowercase() == name);
+    let prefixed: Vec<&crate::models::Project> = visible
+        .iter()
+        .copied()
+        .filter(|p| p.name.to_lowercase().starts_with(&name))
+        .collect();
+    let project = match (exact, prefixed.as_slice()) {
+        (Some(project), _) => project,
+        (None, [project]) => *project,
+        (None, []) => {
+            app.show_notification(format!("找不到项目: {}", name), NotificationLevel::Warning);
+            return;
+        }
+        (None, candidates) => {
+            let names: Vec<&str> = candidates.iter().map(|p| p.name.as_str()).collect();
+            app.show_notification(
+                format!("匹配多个项目: {}", names.join(", ")),
+                NotificationLevel::Warning,
+            );
+            return;
+        }
+    };
+
+    let title = format!("{} · 只读", project.name);
+    let header = format!(
+        "{} 个任务  ·  {}",
+        project.tasks.len(),
+        project.path.display()
+    );
+    let content = peek_content(project, PEEK_TASKS_PER_COLUMN);
+    app.preview_title = title;
+    app.preview_header = header;
+    app.preview_content = content;
+    app.preview_base_dir = None;
+    app.preview_scroll = 0;
+    app.mode = Mode::Preview;
+}
+
+/// 项目看板的文字摘要：每列的任务数和按顺序排在前面的任务
+fn peek_content(project: &crate::models::Project, limit: usize) -> String {
+    let mut lines = Vec::new();
+    for status in &project.statuses {
+        let mut tasks: Vec<&crate::models::Task> = project
+            .tasks
+            .iter()
+            .filter(|t| t.status == status.name)
+            .collect();
+        tasks.sort_by_key(|t| t.order);
+
+        lines.push(format!("## {} · {}", status.display, tasks.len()));
+        for task in tasks.iter().take(limit) {
+            let priority = task
+                .priority
+                .as_deref()
+                .map(|p| format!(" ({})", p))
+                .unwrap_or_default();
+            lines.push(format!("- #{} {}{}", task.id, task.title, priority));
+        }
+        if tasks.len() > limit {
+            lines.push(format!("  … 还有 {} 个", tasks.len() - limit));
+        }
+        lines.push(String::new());
+    }
+    lines.join("\n").trim_end().to_string()
+}
+
+/// `:done-cleanup` 不带参数时的天数
+const DEFAULT_DONE_CLEANUP_DAYS: u64 = 30;
+
+/// 把完成列中超过 `days` 天未变动的任务移入归档目录
+fn cleanup_done_tasks(app: &mut App, days: u64) {
+    let Some(project) = app.get_focused_project() else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    let Some(done) = project.done_status() else {
+        return;
+    };
+
+    let project_path = project.path.clone();
+    let done_display = done.display.clone();
+    let stale: Vec<crate::models::Task> = crate::fs::task::stale_tasks(
+        &project.tasks,
+        &done.name,
+        days,
+        std::time::SystemTime::now(),
+    )
+    .into_iter()
+    .cloned()
+    .collect();
+
+    if stale.is_empty() {
+        app.show_notification(
+            format!("「{}」中没有超过 {} 天的任务", done_display, days),
+            NotificationLevel::Info,
+        );
+        return;
+    }
+
+    app.selected_task_index.insert(app.focused_pane, 0);
+    let total = stale.len();
+    app.start_job(JobKind::Archive, total, move |job| {
+        let mut archived = 0;
+        let mut first_error = None;
+        for (done, task) in stale.iter().enumerate() {
+            if job.is_cancelled() {
+                return JobResult::done(
+                    crate::job::cancelled_message(JobKind::Archive, done, total),
+                    NotificationLevel::Warning,
+                );
+            }
+            match crate::fs::task::archive_task(&project_path, task) {
+                Ok(_) => archived += 1,
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+            job.progress(done + 1, total);
+        }
+
+        match first_error {
+            None => JobResult::done(
+                format!(
+                    "已归档「{}」中 {} 个超过 {} 天的任务到 {}/",
+                    done_display,
+                    archived,
+                    days,
+                    crate::fs::task::ARCHIVE_DIR
+                ),
+                NotificationLevel::Success,
+            ),
+            Some(e) => JobResult::done(
+                format!("已归档 {}/{} 个任务，其余失败: {}", archived, total, e),
+                NotificationLevel::Error,
+            ),
+        }
+    });
+}
+
+/// `:backup` 在后台备份当前项目
+fn backup_current_project(app: &mut App) {
+    let Some(project_path) = app.get_focused_project().map(|p| p.path.clone()) else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    app.start_job(
+        JobKind::Backup,
+        0,
+        move |_| match crate::fs::backup::create_backup(&project_path) {
+            Ok(archive) => JobResult::done(
+                format!("已备份到 {}", archive.display()),
+                NotificationLevel::Success,
+            ),
+            Err(e) => JobResult::done(format!("备份失败: {}", e), NotificationLevel::Error),
+        },
+    );
+}
+
+/// `:yank-column [ids] [check]` 把当前列（按当前筛选）的任务标题复制为 Markdown 列表
+fn yank_column(app: &mut App, args: &[&str]) {
+    let mut with_ids = false;
+    let mut checkbox = false;
+    for arg in args {
+        match *arg {
+            "ids" | "id" => with_ids = true,
+            "check" | "checkbox" => checkbox = true,
+            other => {
+                app.show_notification(
+                    format!("未知参数: {}（用法: :yank-column [ids] [check]）", other),
+                    NotificationLevel::Warning,
+                );
+                return;
+            }
+        }
+    }
+
+    let Some(project) = app.get_focused_project() else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    let column = app
+        .selected_column
+        .get(&app.focused_pane)
+        .copied()
+        .unwrap_or(0);
+    let Some(status) = project.statuses.get(column) else {
+        return;
+    };
+    let tasks = app.column_tasks(app.focused_pane, project, &status.name);
+    if tasks.is_empty() {
+        app.show_notification(
+            format!("「{}」列没有任务", status.display),
+            NotificationLevel::Info,
+        );
+        return;
+    }
+    let text = crate::export::column_markdown(
+        &tasks,
+        with_ids,
+        checkbox.then(|| project.is_done_column(column)),
+    );
+    let message = format!(
+        "已复制「{}」列的 {} 个任务到剪贴板",
+        status.display,
+        tasks.len()
+    );
+
+    #[cfg(feature = "clipboard")]
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        Ok(()) => app.show_notification(message, NotificationLevel::Success),
+        Err(e) => app.show_notification(format!("复制失败: {}", e), NotificationLevel::Error),
+    }
+    #[cfg(not(feature = "clipboard"))]
+    {
+        let _ = (text, message);
+        app.show_notification("剪贴板功能未启用".to_string(), NotificationLevel::Warning);
+    }
+}
+
+/// 打开导入任务对话框
+fn open_import_dialog(app: &mut App) {
+    use crate::ui::dialogs::DialogType;
+
+    if app.get_focused_project().is_none() {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    }
+
+    app.mode = Mode::Dialog;
+    app.dialog = Some(DialogType::Input {
+        title: "导入任务".to_string(),
+        purpose: DialogPurpose::ImportTasks,
+        prompt: "文件路径（.org 或 Taskwarrior 导出的 .json）:".to_string(),
+        input: crate::ui::dialogs::DialogInput::line(""),
+    });
+}
+
+/// 从文件导入任务到当前项目（按扩展名识别格式）
+fn import_tasks_from_file(app: &mut App, path: &str) {
+    let Some(project) = app.get_focused_project() else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    let project_path = project.path.clone();
+
+    let path = expand_home(path.trim());
+    let Some(format) = crate::import::ImportFormat::from_path(&path) else {
+        app.show_notification(
+            "无法识别文件格式，请使用 .org 或 .json".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    };
+
+    app.start_job(JobKind::Import, 0, move |job| {
+        let mut total = 0;
+        let result = crate::import::parse_file(&path, format).and_then(|tasks| {
+            total = tasks.len();
+            crate::import::import_into_project_with(&project_path, &tasks, |done| {
+                job.progress(done, tasks.len());
+                !job.is_cancelled()
+            })
+        });
+        match result {
+            Ok(count) if job.is_cancelled() => JobResult::done(
+                crate::job::cancelled_message(JobKind::Import, count, total),
+                NotificationLevel::Warning,
+            ),
+            Ok(count) => JobResult::done(
+                format!("已导入 {} 个任务", count),
+                NotificationLevel::Success,
+            ),
+            Err(e) => JobResult::done(format!("导入失败: {}", e), NotificationLevel::Error),
+        }
+    });
+}
+
+/// 展开路径开头的 ~
+fn expand_home(path: &str) -> std::path::PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Ok(home) = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"))
+    {
+        return std::path::PathBuf::from(home).join(rest);
+    }
+    std::path::PathBuf::from(path)
+}
+
+/// 格式化重新编号方案（用于确认对话框和预览）
+fn format_renumber_plan(plan: &[(u32, u32)]) -> String {
+    const MAX_SHOWN: usize = 8;
+
+    let mut text = plan
+        .iter()
+        .take(MAX_SHOWN)
+        .map(|(old_id, new_id)| format!("#{} → #{}", old_id, new_id))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if plan.len() > MAX_SHOWN {
+        text.push_str(&format!(" … 共 {} 个", plan.len()));
+    }
+    text
+}
+
+/// 预览重新编号方案（dry-run，不写入文件）
+fn show_renumber_preview(app: &mut App) {
+    let Some(project) = app.get_focused_project() else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+
+    let plan = crate::fs::task::plan_task_renumber(&project.tasks);
+    if plan.is_empty() {
+        app.show_notification(
+            "任务编号已连续，无需调整".to_string(),
+            NotificationLevel::Info,
+        );
+    } else {
+        app.show_notification(
+            format!("预览: {}", format_renumber_plan(&plan)),
+            NotificationLevel::Info,
+        );
+    }
+}
+
+/// 跳转到搜索匹配项
+#[allow(dead_code)]
+fn jump_to_search_match(app: &mut App, state: &crate::app::SearchState) {
+    if state.matches.is_empty() {
+        return;
+    }
+
+    let (task_index, status) = &state.matches[state.selected];
+
+    // 找到对应的列
+    let project = match app.get_focused_project() {
+        Some(p) => p,
+        None => return,
+    };
+
+    let column = match project.statuses.iter().position(|s| &s.name == status) {
+        Some(c) => c,
+        None => return,
+    };
+
+    // 设置选中的列
+    app.selected_column.insert(app.focused_pane, column);
+
+    // 设置选中的任务
+    app.selected_task_index
+        .insert(app.focused_pane, *task_index);
+}
+
+/// 获取当前选中的任务
+fn get_selected_task(app: &App) -> Option<&crate::models::Task> {
+    let project = app.get_focused_project()?;
+    let column = app
+        .selected_column
+        .get(&app.focused_pane)
+        .copied()
+        .unwrap_or(0);
+    let task_idx = app
+        .selected_task_index
+        .get(&app.focused_pane)
+        .copied()
+        .unwrap_or(0);
+
+    let status = app.get_status_name_by_column(column)?;
+
+    app.column_tasks(app.focused_pane, project, &status)
+        .get(task_idx)
+        .copied()
+}
+
+/// 获取当前选中的任务（可变）- 返回任务 ID
+fn get_selected_task_id(app: &App) -> Option<u32> {
     let column = app
         .selected_column
         .get(&app.focused_pane)
@@ -2167,57 +3368,653 @@ fn get_selected_task_id(app: &App) -> Option<u32> {
     let status = app.get_status_name_by_column(column)?;
 
     let project = app.get_focused_project()?;
-    let tasks: Vec<_> = project
-        .tasks
+    app.column_tasks(app.focused_pane, project, &status)
+        .get(task_idx)
+        .map(|t| t.id)
+}
+
+/// 移动任务到相邻状态
+fn move_task_to_status_if_unmarked(app: &mut App, direction: i32) {
+    if let Some(project_id) = get_focused_project_id(app)
+        && app.marked_tasks.iter().any(|(id, _)| id == &project_id)
+    {
+        app.show_notification(
+            "已有标记任务，请使用 s 选择目标状态后按 Enter 批量移动".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+
+    move_task_to_status(app, direction);
+}
+
+/// 移动任务到相邻状态
+fn move_task_to_status(app: &mut App, direction: i32) {
+    let column = app
+        .selected_column
+        .get(&app.focused_pane)
+        .copied()
+        .unwrap_or(0);
+    let status_count = app.get_status_count();
+    let new_column = (column as i32 + direction).clamp(0, status_count as i32 - 1) as usize;
+
+    if new_column == column {
+        return; // 已经在边界
+    }
+
+    let new_status = if let Some(status) = app.get_status_name_by_column(new_column) {
+        status
+    } else {
+        return;
+    };
+
+    move_selected_task_to_status(app, new_column, &new_status);
+}
+
+/// 完成提示显示期间把刚完成的任务恢复成移动前的样子，并在看板中选中它
+///
+/// 直接写回移动前的快照，不重新运行原状态的自动化规则，进入完成时被改掉的字段也一并恢复
+fn undo_completion(app: &mut App) {
+    let Some(undo) = app.pending_undo().cloned() else {
+        app.show_notification(
+            "没有可撤销的完成（完成提示消失后不能撤销）".to_string(),
+            NotificationLevel::Info,
+        );
+        return;
+    };
+    app.notification = None;
+    let task_id = undo.task.id;
+    let from_status = undo.task.status.clone();
+
+    let result = app
+        .projects
+        .iter()
+        .find(|project| project.id == undo.project_id)
+        .and_then(|project| {
+            let current = project.tasks.iter().find(|task| task.id == task_id)?;
+            let mut snapshot = undo.task.clone();
+            // 从任务现在的位置写回原状态目录，旧文件由存储层删除
+            snapshot.file_path = current.file_path.clone();
+            Some(crate::fs::save_task(&project.path, &snapshot))
+        });
+    match result {
+        Some(Ok(_)) => {
+            app.reload_project(&undo.project_id);
+            if get_focused_project_id(app).as_deref() == Some(undo.project_id.as_str())
+                && let Some(index) = app
+                    .get_focused_project()
+                    .and_then(|project| project.tasks.iter().position(|task| task.id == task_id))
+            {
+                jump_to_task(app, &from_status, index);
+            }
+            app.show_notification(
+                format!("已撤销：任务 #{} 回到 {}", task_id, from_status),
+                NotificationLevel::Info,
+            );
+        }
+        Some(Err(e)) => app.show_notification(format!("撤销失败: {}", e), NotificationLevel::Error),
+        None => app.show_notification(
+            format!("撤销失败：找不到任务 #{}", task_id),
+            NotificationLevel::Error,
+        ),
+    }
+}
+
+/// 切换聚焦面板的优先级筛选，重复选择同一筛选时恢复显示全部
+fn toggle_priority_filter(app: &mut App, filter: PriorityFilter) {
+    let pane = app.focused_pane;
+    let next = if app.priority_filter(pane) == filter {
+        PriorityFilter::All
+    } else {
+        filter
+    };
+
+    if next == PriorityFilter::All {
+        app.priority_filters.remove(&pane);
+    } else {
+        app.priority_filters.insert(pane, next);
+    }
+    // 可见任务变化后从第一项开始选择
+    app.selected_task_index.insert(pane, 0);
+
+    let message = match next {
+        PriorityFilter::All => "显示全部优先级",
+        PriorityFilter::Medium => "只显示中、高优先级任务",
+        PriorityFilter::High => "只显示高优先级任务",
+    };
+    app.show_notification(message.to_string(), NotificationLevel::Info);
+}
+
+/// 按选中任务的第 `index` 个标签筛选聚焦面板，已按该标签筛选时取消
+fn toggle_tag_filter(app: &mut App, index: usize) {
+    let Some(tag) = get_selected_task(app).and_then(|task| task.tags.get(index).cloned()) else {
+        app.show_notification(
+            format!("选中任务没有第 {} 个标签", index + 1),
+            NotificationLevel::Warning,
+        );
+        return;
+    };
+
+    if app.tag_filters.get(&app.focused_pane) == Some(&tag) {
+        clear_tag_filter(app);
+        return;
+    }
+    set_tag_filter(app, tag);
+}
+
+/// 聚焦面板只显示带 `tag` 标签的任务
+fn set_tag_filter(app: &mut App, tag: String) {
+    let pane = app.focused_pane;
+    app.show_notification(
+        format!("只显示标签 #{} 的任务，Esc 清除", tag),
+        NotificationLevel::Info,
+    );
+    app.tag_filters.insert(pane, tag);
+    // 可见任务变化后从第一项开始选择
+    app.selected_task_index.insert(pane, 0);
+}
+
+/// `:tags`：显示当前项目的标签概览
+/// 把单词加入当前项目的拼写词典（:spell-add 词...）
+fn add_spelling_words(app: &mut App, words: &[&str]) {
+    if words.is_empty() {
+        app.show_notification(
+            "用法: :spell-add <单词>...".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+    let Some(project_path) = app.get_focused_project().map(|p| p.path.clone()) else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    for word in words {
+        if let Err(e) = crate::ui::spell::add_to_dictionary(&project_path, word) {
+            app.show_notification(e, NotificationLevel::Error);
+            return;
+        }
+    }
+    if let Some(checker) = &mut app.spell_checker {
+        checker.reload_dictionary();
+    }
+    app.show_notification(
+        format!("已加入项目词典: {}", words.join(" ")),
+        NotificationLevel::Success,
+    );
+}
+
+fn show_tag_overview(app: &mut App) {
+    if app.redact {
+        app.show_notification(
+            "屏幕共享模式下不显示标签，:redact 关闭后再试".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+    let Some(project) = app.get_focused_project() else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    let mut overview = crate::ui::tag_overview::TagOverview::new(project);
+    // 已按标签筛选时选中该标签
+    if let Some(active) = app.tag_filters.get(&app.focused_pane) {
+        overview.selected = overview
+            .tags
+            .iter()
+            .position(|(tag, _)| tag == active)
+            .unwrap_or(0);
+    }
+    app.tag_overview = Some(overview);
+    app.mode = Mode::TagOverview;
+}
+
+/// 标签概览中的按键：j/k 选择，Enter 按选中的标签筛选面板
+fn handle_tag_overview_mode(app: &mut App, key: KeyEvent) -> bool {
+    let Some(overview) = app.tag_overview.as_mut() else {
+        app.mode = Mode::Normal;
+        return true;
+    };
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => overview.move_selection(true),
+        KeyCode::Char('k') | KeyCode::Up => overview.move_selection(false),
+        KeyCode::Enter => {
+            let tag = overview.selected_tag().map(str::to_string);
+            app.tag_overview = None;
+            app.mode = Mode::Normal;
+            if let Some(tag) = tag {
+                set_tag_filter(app, tag);
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.tag_overview = None;
+            app.mode = Mode::Normal;
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Space t >：列出显示其他项目的面板，选择面板编号后再选择目标状态
+fn start_send_to_pane(app: &mut App) {
+    use crate::ui::send_to_pane::{SendTarget, SendToPane};
+
+    let Some(task) = get_selected_task(app) else {
+        app.show_notification("没有选中的任务".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    let (task_id, task_title) = (task.id, task.title.clone());
+    let source = app.get_focused_project().map(|p| p.id.clone());
+
+    // 显示同一项目的面板不作为目标（同项目内移动用 s 或 H/L）
+    let targets: Vec<SendTarget> = app
+        .split_tree
+        .collect_pane_ids()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, pane_id)| {
+            let Some(crate::ui::layout::SplitNode::Leaf {
+                project_id: Some(pid),
+                ..
+            }) = app.split_tree.find_pane(pane_id)
+            else {
+                return None;
+            };
+            if source.as_ref() == Some(pid) {
+                return None;
+            }
+            let project = app.projects.iter().find(|p| &p.id == pid)?;
+            Some(SendTarget {
+                number: i + 1,
+                pane_id,
+                project_id: pid.clone(),
+                project_name: project.name.clone(),
+            })
+        })
+        .collect();
+    if targets.is_empty() {
+        app.show_notification(
+            "没有显示其他项目的面板，先用 Space w v 分屏并打开目标项目".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+
+    app.send_to_pane = Some(SendToPane {
+        task_id,
+        task_title,
+        targets,
+        ..Default::default()
+    });
+    app.mode = Mode::SendToPane;
+}
+
+/// 发送任务的按键：先按编号选择面板（c 切换移动/复制），再用 1-9 或 h/l 选择状态，Enter 确认
+fn handle_send_to_pane_mode(app: &mut App, key: KeyEvent) -> bool {
+    let Some(state) = app.send_to_pane.as_mut() else {
+        app.mode = Mode::Normal;
+        return true;
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.send_to_pane = None;
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Char('c') if state.target.is_none() => state.copy = !state.copy,
+        KeyCode::Char('h') | KeyCode::Left => state.move_selection(false),
+        KeyCode::Char('l') | KeyCode::Right => state.move_selection(true),
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            let number = c.to_digit(10).unwrap_or(0) as usize;
+            if state.target.is_none() {
+                match state.target_by_number(number) {
+                    Some(index) => choose_send_target(app, index),
+                    None => app.show_notification(
+                        format!("面板 {} 没有可以接收任务的项目", number),
+                        NotificationLevel::Warning,
+                    ),
+                }
+            } else if (1..=state.statuses.len()).contains(&number) {
+                state.selected = number - 1;
+            } else {
+                let message = format!(
+                    "状态编号 {} 无效：目标项目只有 {} 个状态",
+                    number,
+                    state.statuses.len()
+                );
+                app.show_notification(message, NotificationLevel::Warning);
+            }
+        }
+        KeyCode::Enter => {
+            if state.target.is_none() {
+                let index = state.selected;
+                choose_send_target(app, index);
+            } else {
+                send_task_to_pane(app);
+            }
+        }
+        _ => {}
+    }
+    true
+}
+
+/// 选定目标面板，进入状态选择（默认选中与任务当前状态同名的状态）
+fn choose_send_target(app: &mut App, index: usize) {
+    let Some(state) = app.send_to_pane.as_ref() else {
+        return;
+    };
+    let Some(target) = state.targets.get(index).cloned() else {
+        return;
+    };
+    if app.is_pane_read_only(target.pane_id) {
+        app.show_notification(
+            format!("面板 {} 已锁定（只读）", target.number),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+    let statuses: Vec<(String, String)> = app
+        .projects
+        .iter()
+        .find(|p| p.id == target.project_id)
+        .map(|p| {
+            p.statuses
+                .iter()
+                .map(|s| (s.name.clone(), s.display.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if statuses.is_empty() {
+        app.show_notification(
+            format!("项目 {} 没有状态列", target.project_name),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+    let current_status = get_selected_task(app).map(|t| t.status.clone());
+
+    if let Some(state) = app.send_to_pane.as_mut() {
+        state.selected = statuses
+            .iter()
+            .position(|(name, _)| Some(name) == current_status.as_ref())
+            .unwrap_or(0);
+        state.statuses = statuses;
+        state.target = Some(index);
+    }
+}
+
+/// 执行发送：写入目标项目，移动时删除原任务，然后重新加载两个项目
+fn send_task_to_pane(app: &mut App) {
+    let Some(state) = app.send_to_pane.take() else {
+        return;
+    };
+    app.mode = Mode::Normal;
+    let (Some(target), Some((status, status_display))) =
+        (state.selected_target(), state.statuses.get(state.selected))
+    else {
+        return;
+    };
+    let Some(source) = app.get_focused_project() else {
+        return;
+    };
+    let Some(task) = source.tasks.iter().find(|t| t.id == state.task_id).cloned() else {
+        app.show_notification("任务已不存在".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    let (source_id, source_path) = (source.id.clone(), source.path.clone());
+    let Some(target_path) = app
+        .projects
         .iter()
-        .filter(|t| t.status == status)
-        .collect();
-    tasks.get(task_idx).map(|t| t.id)
+        .find(|p| p.id == target.project_id)
+        .map(|p| p.path.clone())
+    else {
+        return;
+    };
+
+    match crate::fs::task::transfer_task(&source_path, &task, &target_path, status, state.copy) {
+        Ok(new_task) => {
+            app.reload_project(&target.project_id);
+            if !state.copy {
+                app.reload_project(&source_id);
+                let task_idx = app.selected_task_index.entry(app.focused_pane).or_insert(0);
+                *task_idx = task_idx.saturating_sub(1);
+            }
+            app.show_notification(
+                format!(
+                    "已{}任务「{}」到 {} / {}（#{}）",
+                    if state.copy { "复制" } else { "移动" },
+                    state.task_title,
+                    target.project_name,
+                    status_display,
+                    new_task.id
+                ),
+                NotificationLevel::Success,
+            );
+        }
+        Err(e) => report_save_error(app, e),
+    }
 }
 
-/// 移动任务到相邻状态
-fn move_task_to_status_if_unmarked(app: &mut App, direction: i32) {
-    if let Some(project_name) = get_focused_project_name(app)
-        && app
-            .marked_tasks
-            .iter()
-            .any(|(name, _)| name == &project_name)
-    {
+/// 比较聚焦面板和另一个面板的项目，标出在对方没有对应任务的卡片
+///
+/// 不指定面板编号时，只有另外一个打开了其他项目的面板才能自动选中
+fn diff_panes(app: &mut App, number: Option<usize>) {
+    let project_of = |pane_id: usize| match app.split_tree.find_pane(pane_id) {
+        Some(crate::ui::layout::SplitNode::Leaf {
+            project_id: Some(project_id),
+            ..
+        }) => Some(project_id.clone()),
+        _ => None,
+    };
+    let Some(focused_project) = project_of(app.focused_pane) else {
         app.show_notification(
-            "已有标记任务，请使用 s 选择目标状态后按 Enter 批量移动".to_string(),
+            "当前面板没有打开项目".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    };
+    let panes = app.split_tree.collect_pane_ids();
+    let other = match number {
+        Some(number) => match panes.get(number.wrapping_sub(1)) {
+            Some(&pane_id) if pane_id != app.focused_pane => project_of(pane_id),
+            _ => {
+                app.show_notification(
+                    format!("面板 {} 不存在或是当前面板", number),
+                    NotificationLevel::Warning,
+                );
+                return;
+            }
+        },
+        None => {
+            let others: Vec<String> = panes
+                .iter()
+                .filter(|&&pane_id| pane_id != app.focused_pane)
+                .filter_map(|&pane_id| project_of(pane_id))
+                .filter(|project_id| *project_id != focused_project)
+                .collect();
+            if others.len() != 1 {
+                app.show_notification(
+                    "请指定要比较的面板编号，如 :diff-panes 2（按布局顺序从 1 编号）".to_string(),
+                    NotificationLevel::Warning,
+                );
+                return;
+            }
+            others.into_iter().next()
+        }
+    };
+    let Some(other_project) = other else {
+        app.show_notification(
+            "目标面板没有打开项目".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    };
+    if other_project == focused_project {
+        app.show_notification(
+            "两个面板打开的是同一个项目".to_string(),
             NotificationLevel::Warning,
         );
         return;
     }
 
-    move_task_to_status(app, direction);
+    let find = |id: &str| app.projects.iter().find(|p| p.id == id);
+    let (Some(left), Some(right)) = (find(&focused_project), find(&other_project)) else {
+        return;
+    };
+    let (only_left, only_right) = left.diff_tasks(right);
+    let message = format!(
+        "「{}」独有 {} 个任务，「{}」独有 {} 个（:diff-panes off 关闭）",
+        left.name,
+        only_left.len(),
+        right.name,
+        only_right.len()
+    );
+    app.pane_diff = only_left
+        .into_iter()
+        .map(|id| (focused_project.clone(), id))
+        .chain(only_right.into_iter().map(|id| (other_project.clone(), id)))
+        .collect();
+    app.show_notification(message, NotificationLevel::Info);
 }
 
-/// 移动任务到相邻状态
-fn move_task_to_status(app: &mut App, direction: i32) {
-    let column = app
-        .selected_column
-        .get(&app.focused_pane)
-        .copied()
-        .unwrap_or(0);
-    let status_count = app.get_status_count();
-    let new_column = (column as i32 + direction).clamp(0, status_count as i32 - 1) as usize;
+/// 清除聚焦面板的标签筛选，没有筛选时返回 false
+fn clear_tag_filter(app: &mut App) -> bool {
+    let pane = app.focused_pane;
+    if app.tag_filters.remove(&pane).is_none() {
+        return false;
+    }
+    app.selected_task_index.insert(pane, 0);
+    app.show_notification("已清除标签筛选".to_string(), NotificationLevel::Info);
+    true
+}
 
-    if new_column == column {
-        return; // 已经在边界
+/// 列出最近一次外部修改，确认后撤销
+fn confirm_revert_external_changes(app: &mut App) {
+    if app.external_changes.is_empty() {
+        app.show_notification("没有可撤销的外部修改".to_string(), NotificationLevel::Info);
+        return;
     }
 
-    let new_status = if let Some(status) = app.get_status_name_by_column(new_column) {
-        status
+    const MAX_SHOWN: usize = 8;
+    let mut message = format!(
+        "将以下 {} 个任务恢复为外部修改前的版本：",
+        app.external_changes.len()
+    );
+    for change in app.external_changes.iter().take(MAX_SHOWN) {
+        message.push_str(&format!("\n{}", change.summary()));
+    }
+    if app.external_changes.len() > MAX_SHOWN {
+        message.push_str(&format!("\n… 共 {} 项", app.external_changes.len()));
+    }
+
+    app.mode = Mode::Dialog;
+    app.dialog = Some(DialogType::Confirm {
+        title: "撤销外部修改".to_string(),
+        message,
+        yes_selected: false,
+        action: crate::ui::dialogs::ConfirmAction::RevertExternalChanges,
+    });
+}
+
+/// 撤销外部修改；检测之后又被改动过的任务保持不变，避免覆盖新的编辑
+fn revert_external_changes(app: &mut App) {
+    let changes = std::mem::take(&mut app.external_changes);
+    let mut restored = 0;
+    let mut skipped = 0;
+    let mut failed = Vec::new();
+    let mut touched: Vec<std::path::PathBuf> = Vec::new();
+
+    for change in &changes {
+        let id = change
+            .after
+            .as_ref()
+            .or(change.before.as_ref())
+            .map(|t| t.id);
+        let current = app
+            .projects
+            .iter()
+            .find(|p| p.path == change.project_path)
+            .and_then(|p| p.tasks.iter().find(|t| Some(t.id) == id));
+        let unchanged = match (current, change.after.as_ref()) {
+            (Some(current), Some(after)) => crate::models::change::same_task(current, after),
+            (None, None) => true,
+            _ => false,
+        };
+        if !unchanged {
+            skipped += 1;
+            continue;
+        }
+
+        match crate::fs::task::restore_task(
+            &change.project_path,
+            change.before.as_ref(),
+            change.after.as_ref(),
+        ) {
+            Ok(()) => {
+                restored += 1;
+                if !touched.contains(&change.project_path) {
+                    touched.push(change.project_path.clone());
+                }
+            }
+            Err(e) => failed.push(format!("#{}: {}", id.unwrap_or_default(), e)),
+        }
+    }
+
+    for path in touched {
+        if let Some(project) = app.projects.iter_mut().find(|p| p.path == path)
+            && let Ok(updated) = crate::fs::load_project_with_type(&path, project.project_type)
+        {
+            *project = updated;
+        }
+    }
+
+    if !failed.is_empty() {
+        app.show_notification(
+            format!("部分任务恢复失败: {}", failed.join("; ")),
+            NotificationLevel::Error,
+        );
+    } else if skipped > 0 {
+        app.show_notification(
+            format!(
+                "已恢复 {} 个任务，{} 个之后又被修改，已跳过",
+                restored, skipped
+            ),
+            NotificationLevel::Warning,
+        );
     } else {
-        return;
-    };
+        app.show_notification(
+            format!("已恢复 {} 个任务", restored),
+            NotificationLevel::Success,
+        );
+    }
+}
 
-    move_selected_task_to_status(app, new_column, &new_status);
+/// 设置聚焦面板的列宽策略并保存到状态文件
+fn set_column_layout(app: &mut App, layout: ColumnLayout) {
+    let pane = app.focused_pane;
+    if layout == ColumnLayout::Equal {
+        app.column_layouts.remove(&pane);
+    } else {
+        app.column_layouts.insert(pane, layout);
+    }
+    app.save_state();
+
+    let message = match layout {
+        ColumnLayout::Equal => "列宽：等宽",
+        ColumnLayout::Auto => "列宽：按任务数自动分配",
+    };
+    app.show_notification(message.to_string(), NotificationLevel::Info);
 }
 
 /// 在列内上下移动任务
 fn move_task_in_column(app: &mut App, direction: i32) {
+    // 筛选时隐藏的任务也参与排序，无法按可见位置计算新顺序
+    if app.task_filter(app.focused_pane).is_active() {
+        app.show_notification(
+            "筛选中无法调整顺序，按 3 / Esc 取消筛选后再移动".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+
     let column = app
         .selected_column
         .get(&app.focused_pane)
@@ -2235,14 +4032,14 @@ fn move_task_in_column(app: &mut App, direction: i32) {
         return;
     };
 
-    // 获取项目名称和路径
-    let (project_name, project_path) = if let Some(crate::ui::layout::SplitNode::Leaf {
-        project_id: Some(name),
+    // 获取项目 ID 和路径
+    let (project_id, project_path) = if let Some(crate::ui::layout::SplitNode::Leaf {
+        project_id: Some(id),
         ..
     }) = app.split_tree.find_pane(app.focused_pane)
     {
-        if let Some(project) = app.projects.iter().find(|p| &p.name == name) {
-            (name.clone(), project.path.clone())
+        if let Some(project) = app.projects.iter().find(|p| &p.id == id) {
+            (id.clone(), project.path.clone())
         } else {
             return;
         }
@@ -2251,7 +4048,7 @@ fn move_task_in_column(app: &mut App, direction: i32) {
     };
 
     // 获取当前列的所有任务（已按order排序）
-    if let Some(project) = app.projects.iter_mut().find(|p| p.name == project_name) {
+    if let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id) {
         let mut tasks: Vec<&mut crate::models::Task> = project
             .tasks
             .iter_mut()
@@ -2264,6 +4061,7 @@ fn move_task_in_column(app: &mut App, direction: i32) {
 
         // 按order排序
         tasks.sort_by_key(|t| t.order);
+        let original_orders: HashMap<u32, i32> = tasks.iter().map(|t| (t.id, t.order)).collect();
 
         let new_idx = (task_idx as i32 + direction).clamp(0, tasks.len() as i32 - 1) as usize;
 
@@ -2328,49 +4126,129 @@ fn move_task_in_column(app: &mut App, direction: i32) {
             }
         };
 
-        // 更新当前任务的order
-        let need_rebalance = tasks
-            .iter()
-            .any(|t| t.order % 1000 == 0 && t.id != current_task_id);
-
         if let Some(task) = project.tasks.iter_mut().find(|t| t.id == current_task_id) {
             task.order = new_order;
-
-            // 持久化到文件
-            if let Err(e) = crate::fs::save_task(&project_path, task) {
-                log_debug(format!("保存任务失败: {}", e));
-                return;
-            }
-
-            log_debug(format!("任务 {} 的order更新为 {}", task.id, task.order));
         }
 
-        // 如果进行了重平衡，保存所有任务
-        if need_rebalance {
-            let tasks_to_save: Vec<crate::models::Task> = project
-                .tasks
-                .iter()
-                .filter(|t| t.status == status)
-                .cloned()
-                .collect();
+        // 立即写回所有 order 变化的任务（包括重平衡影响的任务）
+        let changed: Vec<crate::models::Task> = project
+            .tasks
+            .iter()
+            .filter(|t| t.status == status && original_orders.get(&t.id) != Some(&t.order))
+            .cloned()
+            .collect();
+        let result = changed
+            .iter()
+            .try_for_each(|task| crate::fs::save_task(&project_path, task).map(|_| ()));
 
-            for task in tasks_to_save {
-                let _ = crate::fs::save_task(&project_path, &task);
+        // 重新加载项目以刷新排序；写入失败时同时丢弃内存中未落盘的修改
+        let _ = app.reload_current_project();
+        match result {
+            Ok(()) => {
+                log_debug(format!(
+                    "任务 {} 的order更新为 {}，写入 {} 个文件",
+                    current_task_id,
+                    new_order,
+                    changed.len()
+                ));
+                app.selected_task_index.insert(app.focused_pane, new_idx);
             }
+            Err(e) => report_save_error(app, e),
         }
-
-        // 更新UI选中索引
-        app.selected_task_index.insert(app.focused_pane, new_idx);
-
-        // 重新加载项目以刷新排序
-        let _ = app.reload_current_project();
     }
 }
 
 /// 创建新任务
+/// 重复任务确认中列出的相近任务数（按 1-9 打开）
+const MAX_LISTED_DUPLICATES: usize = 5;
+
+/// 新建任务：标题与未完成的任务相近时先列出这些任务，确认后才创建
 fn create_new_task(app: &mut App, input: String) {
-    use crate::models::Task;
+    if !confirm_near_duplicates(app, &input) {
+        save_new_task(app, input);
+    }
+}
+
+/// 新任务（第一行为标题）与未完成的任务相近时打开确认对话框，返回是否打开了；
+/// 确认后由对话框调用 [`save_new_task`] 创建
+fn confirm_near_duplicates(app: &mut App, input: &str) -> bool {
+    let title = input.lines().next().unwrap_or_default().trim();
+    let duplicates: Vec<(u32, String)> = app
+        .get_focused_project()
+        .map(|project| {
+            project
+                .near_duplicates(title)
+                .into_iter()
+                .take(MAX_LISTED_DUPLICATES)
+                .map(|task| (task.id, task.title.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if duplicates.is_empty() {
+        return false;
+    }
+
+    let listed: Vec<String> = duplicates
+        .iter()
+        .enumerate()
+        .map(|(i, (id, title))| format!("{}. #{} {}", i + 1, id, title))
+        .collect();
+    app.mode = Mode::Dialog;
+    app.dialog = Some(DialogType::Confirm {
+        title: "可能重复的任务".to_string(),
+        message: format!(
+            "已有相近的未完成任务：\n\n{}\n\n按序号打开已有任务，y 仍然创建「{}」",
+            listed.join("\n"),
+            title
+        ),
+        yes_selected: false,
+        action: crate::ui::dialogs::ConfirmAction::CreateDuplicateTask {
+            input: input.to_string(),
+            duplicates: duplicates.into_iter().map(|(id, _)| id).collect(),
+        },
+    });
+    true
+}
+
+/// 在看板中选中已有任务并打开预览
+fn open_existing_task(app: &mut App, id: u32) {
+    let Some((index, status)) = app.get_focused_project().and_then(|project| {
+        let index = project.tasks.iter().position(|task| task.id == id)?;
+        Some((index, project.tasks[index].status.clone()))
+    }) else {
+        return;
+    };
+    jump_to_task(app, &status, index);
+    if get_selected_task_id(app) == Some(id) {
+        execute_command(app, Command::ViewTask);
+    } else {
+        app.show_notification(
+            format!("任务 #{} 被当前筛选隐藏", id),
+            NotificationLevel::Warning,
+        );
+    }
+}
+
+/// 选中聚焦项目中最近创建或编辑的任务
+fn jump_to_last_edited(app: &mut App) {
+    let Some((id, status, index)) = app.last_edited_task().and_then(|task| {
+        let project = app.get_focused_project()?;
+        let index = project.tasks.iter().position(|t| t.id == task.id)?;
+        Some((task.id, task.status.clone(), index))
+    }) else {
+        app.show_notification("没有最近编辑的任务".to_string(), NotificationLevel::Info);
+        return;
+    };
+    jump_to_task(app, &status, index);
+    if get_selected_task_id(app) != Some(id) {
+        app.show_notification(
+            format!("任务 #{} 被当前筛选隐藏", id),
+            NotificationLevel::Warning,
+        );
+    }
+}
 
+fn save_new_task(app: &mut App, input: String) {
     log_debug(format!("调试: 准备创建任务，输入内容: '{}'", input));
 
     // 解析输入：第一行是标题，其余是内容
@@ -2394,153 +4272,219 @@ fn create_new_task(app: &mut App, input: String) {
         content.len()
     ));
 
+    if let Err(e) = insert_new_task(app, title, content) {
+        report_save_error(app, e);
+    }
+}
+
+/// 在聚焦面板的项目中创建任务（ID、状态、排序和默认优先级自动确定），保存后选中新卡片
+fn insert_new_task(app: &mut App, title: String, content: String) -> Result<u32, String> {
+    use crate::models::Task;
+
     // 获取当前项目
-    let project_name = if let Some(crate::ui::layout::SplitNode::Leaf { project_id, .. }) =
-        app.split_tree.find_pane(app.focused_pane)
-    {
-        if let Some(name) = project_id {
-            log_debug(format!("调试: 当前项目 '{}'", name));
-            name.clone()
-        } else {
-            log_debug("调试: 当前面板没有项目".to_string());
-            return;
-        }
-    } else {
-        log_debug("调试: 找不到当前面板".to_string());
-        return;
+    let project_id = match app.split_tree.find_pane(app.focused_pane) {
+        Some(crate::ui::layout::SplitNode::Leaf {
+            project_id: Some(id),
+            ..
+        }) => id.clone(),
+        _ => return Err("当前面板没有项目".to_string()),
     };
 
     // 获取项目路径（支持本地和全局项目）
-    let project_path = if let Some(project) = app.projects.iter().find(|p| p.name == project_name) {
-        project.path.clone()
-    } else {
-        log_debug("调试: 在项目列表中找不到项目".to_string());
-        return;
-    };
+    let project_path = app
+        .projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .map(|p| p.path.clone())
+        .ok_or_else(|| format!("在项目列表中找不到项目 '{}'", project_id))?;
 
     // 获取下一个任务 ID
-    if let Ok(next_id) = crate::fs::get_next_task_id(&project_path) {
-        log_debug(format!("调试: 下一个任务ID {}", next_id));
-        // 获取当前选中的列作为初始状态
-        let column = app
-            .selected_column
-            .get(&app.focused_pane)
-            .copied()
-            .unwrap_or(0);
-        let status = app
-            .get_status_name_by_column(column)
-            .unwrap_or_else(|| "todo".to_string());
-        log_debug(format!("调试: 状态 '{}'", status));
-
-        // 获取当前列的最大order值
-        let max_order = crate::fs::get_max_order_in_status(&project_path, &status).unwrap_or(-1000);
-        let new_order = max_order + 1000;
-        log_debug(format!("调试: 新任务order值 {}", new_order));
-
-        // 创建任务并设置order和content
-        let mut task = Task::new(next_id, title.clone(), status.clone());
-        task.order = new_order;
-        task.content = content;
-
-        // 保存到文件
-        match crate::fs::save_task(&project_path, &task) {
-            Ok(_) => {
-                log_debug("调试: 任务保存成功".to_string());
+    let next_id = crate::fs::get_next_task_id(&project_path)?;
+    log_debug(format!("调试: 下一个任务ID {}", next_id));
+    // 初始状态：配置的默认状态或当前选中的列
+    let (status, priority) = app.new_task_target(&project_path);
+    log_debug(format!("调试: 状态 '{}'", status));
+
+    // 获取当前列的最大order值
+    let max_order = crate::fs::get_max_order_in_status(&project_path, &status).unwrap_or(-1000);
+    let new_order = max_order + 1000;
+    log_debug(format!("调试: 新任务order值 {}", new_order));
+
+    // 创建任务并设置order和content
+    let mut task = Task::new(next_id, title, status.clone());
+    task.order = new_order;
+    task.priority = priority;
+    task.set_content(content);
+
+    // 保存到文件
+    crate::fs::save_task(&project_path, &task)?;
+    log_debug("调试: 任务保存成功".to_string());
+    app.remember_edited(&project_id, next_id);
+
+    // 重新加载项目以确保任务列表是最新的
+    match crate::fs::load_project(&project_path) {
+        Ok(updated_project) => {
+            log_debug(format!(
+                "调试: 重新加载项目，共 {} 个任务",
+                updated_project.tasks.len()
+            ));
+            if let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id) {
+                *project = updated_project;
             }
-            Err(e) => {
-                log_debug(format!("保存任务失败: {}", e));
-                return;
+            // 自动选中新创建的任务（按 ID 查找，与列的排序方式无关）
+            if let Some(index) = app
+                .get_focused_project()
+                .and_then(|p| p.tasks.iter().position(|t| t.id == next_id))
+            {
+                jump_to_task(app, &status, index);
             }
         }
+        Err(e) => {
+            log_debug(format!("调试: 重新加载项目失败: {}", e));
+        }
+    }
 
-        // 重新加载项目以确保任务列表是最新的
-        match crate::fs::load_project(&project_path) {
-            Ok(updated_project) => {
-                log_debug(format!(
-                    "调试: 重新加载项目，共 {} 个任务",
-                    updated_project.tasks.len()
-                ));
-                if let Some(project) = app.projects.iter_mut().find(|p| p.name == project_name) {
-                    *project = updated_project;
+    Ok(next_id)
+}
 
-                    // 找到新任务在当前列的索引（应该是最后一个）
-                    let new_task_idx = project
-                        .tasks
-                        .iter()
-                        .filter(|t| t.status == status)
-                        .count()
-                        .saturating_sub(1);
-
-                    // 自动选中新创建的任务
-                    app.selected_task_index
-                        .insert(app.focused_pane, new_task_idx);
-                    log_debug(format!("调试: 选中任务索引 {}", new_task_idx));
-                } else {
-                    log_debug(format!(
-                        "调试: 在 app.projects 中找不到项目 '{}'",
-                        project_name
-                    ));
-                }
-            }
-            Err(e) => {
-                log_debug(format!("调试: 重新加载项目失败: {}", e));
-            }
-        }
-    } else {
-        log_debug("调试: 获取下一个任务ID失败".to_string());
+/// 外部编辑器中新建任务的模板，标题仍为 [`EDITOR_TASK_TEMPLATE_TITLE`] 时视为取消
+const EDITOR_TASK_TEMPLATE_TITLE: &str = "任务标题";
+const EDITOR_TASK_TEMPLATE: &str =
+    "# 任务标题\n\n任务描述内容...\n\n## 子任务\n\n- [ ] 子任务 1\n- [ ] 子任务 2\n";
+
+/// 编辑器关闭后读取新任务草稿并创建任务
+///
+/// 返回 `Err` 时任务没有保存，调用方应保留草稿文件以便恢复；
+/// 草稿为空或标题仍是模板标题时视为取消，不创建任务。
+/// 与对话框新建一样先检查相近的任务，需要确认时草稿内容保存在确认对话框中。
+pub fn create_task_from_editor_file(app: &mut App, path: &std::path::Path) -> Result<(), String> {
+    let draft = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let Some((title, content)) = crate::fs::parser::parse_task_draft(&draft)
+        .filter(|(title, _)| title != EDITOR_TASK_TEMPLATE_TITLE)
+    else {
+        app.show_notification(
+            "未填写任务标题，已取消新建".to_string(),
+            NotificationLevel::Info,
+        );
+        return Ok(());
+    };
+
+    if confirm_near_duplicates(app, &format!("{}\n{}", title, content)) {
+        return Ok(());
+    }
+    let id = insert_new_task(app, title, content)?;
+    app.show_notification(format!("已创建任务 #{}", id), NotificationLevel::Success);
+    Ok(())
+}
+
+/// 用系统默认程序（open / xdg-open / explorer）打开当前项目目录
+fn reveal_project(app: &mut App) {
+    let Some(path) = app.get_focused_project().map(|p| p.path.clone()) else {
+        app.show_notification("当前面板没有项目".to_string(), NotificationLevel::Warning);
+        return;
+    };
+
+    let opener = crate::config::file_opener();
+    // 文件管理器是独立的 GUI 程序，不等待退出，也不让它的输出打乱界面
+    let result = std::process::Command::new(opener)
+        .arg(&path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+    match result {
+        Ok(_) => app.show_notification(
+            format!("已打开 {}", path.display()),
+            NotificationLevel::Success,
+        ),
+        Err(e) => app.show_notification(
+            format!("无法用 {} 打开目录: {}", opener, e),
+            NotificationLevel::Error,
+        ),
     }
 }
 
 /// 重命名当前项目
+///
+/// 面板通过项目 ID 引用项目，重命名后引用保持不变；全局项目的目录同步改名，
+/// 本地项目目录固定为 `.kanban`，只修改配置中的名称。
 fn rename_current_project(app: &mut App, new_name: String) {
-    // 获取当前项目名
-    let old_name = if let Some(crate::ui::layout::SplitNode::Leaf {
-        project_id: Some(name),
-        ..
-    }) = app.split_tree.find_pane(app.focused_pane)
-    {
-        name.clone()
-    } else {
+    let Some(project) = app.get_focused_project().cloned() else {
         return;
     };
 
-    if old_name == new_name {
-        return; // 名称没有变化
+    if project.name == new_name {
+        return; // 名称没有变化
+    }
+
+    let project_path = if project.project_type == crate::models::ProjectType::Global {
+        let new_path = project.path.with_file_name(&new_name);
+        if new_path.exists() {
+            app.show_notification(
+                format!("项目「{}」已存在", new_name),
+                NotificationLevel::Error,
+            );
+            return;
+        }
+        if let Err(e) = crate::fs::backup::before(&project.path, "rename-project")
+            .and_then(|_| std::fs::rename(&project.path, &new_path).map_err(|e| e.to_string()))
+        {
+            log_debug(format!("重命名项目目录失败: {}", e));
+            app.show_notification(format!("重命名项目失败: {}", e), NotificationLevel::Error);
+            return;
+        }
+        new_path
+    } else {
+        project.path.clone()
+    };
+
+    // 更新配置文件中的项目名
+    let result = crate::fs::load_project_config(&project_path).and_then(|mut config| {
+        config.name = new_name.clone();
+        crate::fs::save_project_config(&project_path, &config)
+    });
+    if let Err(e) = result {
+        app.show_notification(format!("重命名项目失败: {}", e), NotificationLevel::Error);
     }
 
-    // 找到项目
-    if let Some(project) = app.projects.iter().find(|p| p.name == old_name).cloned() {
-        let old_path = project.path.clone();
-        let new_path = old_path.parent().unwrap().join(&new_name);
+    // 重新加载所有项目
+    match crate::fs::load_all_projects_with_config(&app.config) {
+        Ok(projects) => app.projects = projects,
+        Err(e) => log_debug(format!("重新加载项目失败: {}", e)),
+    }
+}
 
-        // 重命名目录
-        if let Err(e) = std::fs::rename(&old_path, &new_path) {
-            log_debug(format!("重命名项目目录失败: {}", e));
+/// 保存 `:project-settings` 表单：先写入说明和状态，名称变化时再重命名项目
+fn save_project_settings(app: &mut App, input: &str) {
+    let Some((path, old_name)) = app
+        .get_focused_project()
+        .map(|p| (p.path.clone(), p.name.clone()))
+    else {
+        return;
+    };
+    let settings = match crate::fs::settings::ProjectSettings::parse_form(input) {
+        Ok(settings) => settings,
+        Err(e) => {
+            app.show_notification(e, NotificationLevel::Warning);
             return;
         }
+    };
+    if let Err(e) = crate::fs::backup::before(&path, "project-settings")
+        .and_then(|_| crate::fs::settings::apply_project_settings(&path, &settings))
+    {
+        app.show_notification(e, NotificationLevel::Error);
+        return;
+    }
 
-        // 更新配置文件中的项目名
-        let config_path = new_path.join(".kanban.toml");
-        if let Ok(mut content) = std::fs::read_to_string(&config_path) {
-            // 简单替换项目名（第一行）
-            let lines: Vec<&str> = content.lines().collect();
-            if !lines.is_empty() {
-                content = format!("name = \"{}\"\n{}", new_name, lines[1..].join("\n"));
-                let _ = std::fs::write(&config_path, content);
-            }
-        }
+    let _ = app.reload_current_project();
+    let column_count = settings.statuses.len();
+    if let Some(column) = app.selected_column.get_mut(&app.focused_pane) {
+        *column = (*column).min(column_count - 1);
+    }
+    app.show_notification("项目设置已保存".to_string(), NotificationLevel::Success);
 
-        // 重新加载所有项目
-        match crate::fs::load_all_projects() {
-            Ok(projects) => {
-                app.projects = projects;
-                // 更新当前面板的项目ID
-                app.set_focused_project(new_name);
-            }
-            Err(e) => {
-                log_debug(format!("重新加载项目失败: {}", e));
-            }
-        }
+    if settings.name != old_name {
+        rename_current_project(app, settings.name);
     }
 }
 
@@ -2553,19 +4497,20 @@ fn update_task_title(app: &mut App, new_title: String) {
         return;
     };
 
-    // 获取项目名称
-    let project_name = if let Some(crate::ui::layout::SplitNode::Leaf {
-        project_id: Some(name),
+    // 获取项目 ID
+    let project_id = if let Some(crate::ui::layout::SplitNode::Leaf {
+        project_id: Some(id),
         ..
     }) = app.split_tree.find_pane(app.focused_pane)
     {
-        name.clone()
+        id.clone()
     } else {
         return;
     };
 
     // 找到任务并更新
-    if let Some(project) = app.projects.iter_mut().find(|p| p.name == project_name)
+    let mut result = Ok(());
+    if let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id)
         && let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id)
     {
         let old_title = task.title.clone();
@@ -2574,10 +4519,112 @@ fn update_task_title(app: &mut App, new_title: String) {
         // 保存到文件（使用项目的实际路径）
         let project_path = project.path.clone();
         if let Err(e) = crate::fs::save_task(&project_path, task) {
-            log_debug(format!("保存任务失败: {}", e));
+            result = Err(e);
             task.title = old_title; // 回滚
         }
     }
+
+    match result {
+        Ok(_) => app.remember_edited(&project_id, task_id),
+        Err(e) => report_save_error(app, e),
+    }
+}
+
+/// 解析字段编辑框的内容（每行 "键名 = 值"），返回要写入的值，None 表示清除
+fn parse_field_input(
+    fields: &[crate::models::FieldDef],
+    input: &str,
+) -> Result<Vec<(String, Option<toml::Value>)>, String> {
+    let mut updates = Vec::new();
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let Some((name, value)) = line.split_once('=') else {
+            return Err(format!("缺少 '=': {}", line.trim()));
+        };
+        let name = name.trim();
+        let Some(field) = fields.iter().find(|f| f.name == name) else {
+            return Err(format!("未定义的字段: {}", name));
+        };
+        updates.push((field.name.clone(), field.parse_value(value)?));
+    }
+    Ok(updates)
+}
+
+/// 更新任务的自定义字段
+fn update_task_fields(app: &mut App, input: &str) {
+    let Some(task_id) = get_selected_task_id(app) else {
+        return;
+    };
+    let Some(project_id) = app.get_focused_project().map(|p| p.id.clone()) else {
+        return;
+    };
+
+    let mut result = Ok(());
+    if let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id) {
+        let fields = project.card_fields();
+        let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return;
+        };
+        result = parse_field_input(&fields, input).and_then(|updates| {
+            let old_extra = task.extra.clone();
+            for (name, value) in updates {
+                match value {
+                    Some(value) => task.extra.insert(name, value),
+                    None => task.extra.remove(&name),
+                };
+            }
+            crate::fs::save_task(&project.path, task)
+                .map(|_| ())
+                .inspect_err(|_| {
+                    task.extra = old_extra; // 回滚
+                })
+        });
+    }
+
+    match result {
+        Ok(_) => {
+            app.remember_edited(&project_id, task_id);
+            app.show_notification("字段已更新".to_string(), NotificationLevel::Success);
+        }
+        Err(e) => {
+            app.last_save_error = Some(SaveError::Files(e.clone()));
+            app.show_notification(format!("保存字段失败: {}", e), NotificationLevel::Error);
+        }
+    }
+}
+
+/// 在选中任务的 Notes 小节追加一条带时间戳的笔记并保存
+fn add_task_note(app: &mut App, note: &str) {
+    let Some(task_id) = get_selected_task_id(app) else {
+        return;
+    };
+    let Some(project) = app.get_focused_project_mut() else {
+        return;
+    };
+    let project_path = project.path.clone();
+    let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+        return;
+    };
+
+    let old_content = task.content.clone();
+    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+    task.append_note(note, &timestamp);
+    match crate::fs::save_task(&project_path, task) {
+        Ok(_) => {
+            let project_id = project.id.clone();
+            app.remember_edited(&project_id, task_id);
+            app.show_notification(
+                format!("已添加笔记到任务 #{}", task_id),
+                crate::app::NotificationLevel::Success,
+            );
+        }
+        Err(e) => {
+            task.set_content(old_content); // 回滚
+            app.show_notification(
+                format!("保存笔记失败: {}", e),
+                crate::app::NotificationLevel::Error,
+            );
+        }
+    }
 }
 
 /// 更新任务标签
@@ -2589,13 +4636,13 @@ fn update_task_tags(app: &mut App, tags_string: String) {
         return;
     };
 
-    // 获取项目名称
-    let project_name = if let Some(crate::ui::layout::SplitNode::Leaf {
-        project_id: Some(name),
+    // 获取项目 ID
+    let project_id = if let Some(crate::ui::layout::SplitNode::Leaf {
+        project_id: Some(id),
         ..
     }) = app.split_tree.find_pane(app.focused_pane)
     {
-        name.clone()
+        id.clone()
     } else {
         return;
     };
@@ -2609,7 +4656,7 @@ fn update_task_tags(app: &mut App, tags_string: String) {
 
     // 找到任务并更新
     let mut result = Ok(());
-    if let Some(project) = app.projects.iter_mut().find(|p| p.name == project_name)
+    if let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id)
         && let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id)
     {
         let old_tags = task.tags.clone();
@@ -2626,6 +4673,7 @@ fn update_task_tags(app: &mut App, tags_string: String) {
     // 显示通知
     match result {
         Ok(_) => {
+            app.remember_edited(&project_id, task_id);
             app.show_notification(
                 "标签已更新".to_string(),
                 crate::app::NotificationLevel::Success,
@@ -2640,6 +4688,128 @@ fn update_task_tags(app: &mut App, tags_string: String) {
     }
 }
 
+/// `:tag` 增删标签：有标记的任务时应用到所有标记任务，否则应用到选中任务
+fn edit_tags(app: &mut App, edit: &crate::models::task::TagEdit) {
+    let Some(project_id) = get_focused_project_id(app) else {
+        return;
+    };
+    let mut task_ids: Vec<u32> = app
+        .marked_tasks
+        .iter()
+        .filter(|(id, _)| id == &project_id)
+        .map(|(_, task_id)| *task_id)
+        .collect();
+    if task_ids.is_empty() {
+        task_ids.extend(get_selected_task_id(app));
+    }
+    let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id) else {
+        return;
+    };
+    if task_ids.is_empty() {
+        app.show_notification("没有选中的任务".to_string(), NotificationLevel::Warning);
+        return;
+    }
+
+    let project_path = project.path.clone();
+    if task_ids.len() > 1
+        && let Err(e) = crate::fs::backup::before(&project_path, "bulk-tag")
+    {
+        app.show_notification(
+            format!("批量修改标签已取消: {}", e),
+            NotificationLevel::Error,
+        );
+        return;
+    }
+    let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id) else {
+        return;
+    };
+    let mut changed = 0usize;
+    let mut failures = Vec::new();
+    for task in project
+        .tasks
+        .iter_mut()
+        .filter(|task| task_ids.contains(&task.id))
+    {
+        let old_tags = task.tags.clone();
+        if !edit.apply(&mut task.tags) {
+            continue;
+        }
+        match crate::fs::save_task(&project_path, task) {
+            Ok(_) => changed += 1,
+            Err(e) => {
+                task.tags = old_tags; // 回滚
+                failures.push(format!("任务 {}: {}", task.id, e));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        app.show_notification(
+            format!("已更新 {} 个任务的标签", changed),
+            NotificationLevel::Success,
+        );
+    } else {
+        app.show_notification(
+            format!(
+                "{} 个任务已更新，保存失败: {}",
+                changed,
+                failures.join("；")
+            ),
+            NotificationLevel::Error,
+        );
+    }
+}
+
+/// `:tag` 参数补全：把最后一个 `+前缀` / `-前缀` 补全为项目中已有的标签
+///
+/// 唯一匹配时补全整个标签，多个匹配时补全到公共前缀并提示候选项。
+/// 返回 false 表示不是标签参数，交给命令名补全处理。
+fn complete_tag_argument(app: &mut App) -> bool {
+    let input = app.command_input.value().to_string();
+    let name = input.split_whitespace().next().unwrap_or("");
+    if !matches!(
+        app.command_registry.find_exact(name).map(|cmd| cmd.name),
+        Some("tag" | "tags")
+    ) || input.ends_with(' ')
+    {
+        return false;
+    }
+    let last = input.rsplit(' ').next().unwrap_or("");
+    let Some((sign @ ("+" | "-"), prefix)) = last.split_at_checked(1) else {
+        return false;
+    };
+
+    let tags = app
+        .get_focused_project()
+        .map(|project| project.all_tags())
+        .unwrap_or_default();
+    let candidates: Vec<&String> = tags.iter().filter(|t| t.starts_with(prefix)).collect();
+    let completion = match candidates.as_slice() {
+        [] => return true,
+        [only] => format!("{} ", only),
+        [first, rest @ ..] => rest.iter().fold(first.to_string(), |common, tag| {
+            common
+                .chars()
+                .zip(tag.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+        }),
+    };
+    if candidates.len() > 1 {
+        let names: Vec<&str> = candidates.iter().map(|t| t.as_str()).collect();
+        app.show_notification(
+            format!("标签: {}", names.join(", ")),
+            NotificationLevel::Info,
+        );
+    }
+
+    let head = &input[..input.len() - last.len()];
+    app.command_input =
+        crate::ui::text_field::TextField::new(&format!("{}{}{}", head, sign, completion));
+    true
+}
+
 /// 处理帮助模式的按键
 fn handle_help_mode(app: &mut App, key: KeyEvent) -> bool {
     match key.code {
@@ -2657,7 +4827,7 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
 
     match key.code {
         KeyCode::Esc => {
-            // ESC：子菜单返回主菜单，主菜单退出
+            // ESC：子菜单返回上一级菜单，主菜单退出
             match app.menu_state {
                 Some(MenuState::Main) | None => {
                     app.menu_state = None;
@@ -2665,6 +4835,10 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                     app.mode = Mode::Normal;
                     app.key_buffer.clear();
                 }
+                Some(MenuState::Color) => {
+                    app.menu_state = Some(MenuState::Task);
+                    app.menu_selected_index = Some(0);
+                }
                 Some(_) => {
                     app.menu_state = Some(MenuState::Main);
                     app.menu_selected_index = Some(0); // 返回主菜单时重置选中索引
@@ -2729,7 +4903,7 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                             app.menu_state = None;
                             app.key_buffer.clear();
                         }
-                        _ => {}
+                        _ => flash_unbound_key(app, c),
                     }
                 }
                 Some(MenuState::Project) => {
@@ -2742,6 +4916,8 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                         'D' => Some(Command::DeleteProject), // 大写D = 硬删除
                         'r' => Some(Command::RenameProject),
                         'i' => Some(Command::CopyProjectInfo), // 复制项目信息
+                        'f' => Some(Command::RevealProject),   // 文件管理器中打开
+                        's' => Some(Command::ProjectSettings), // 项目设置
                         _ => None,
                     };
                     if let Some(cmd) = cmd {
@@ -2749,6 +4925,8 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                         app.menu_state = None;
                         app.key_buffer.clear();
                         execute_command(app, cmd);
+                    } else {
+                        flash_unbound_key(app, c);
                     }
                 }
                 Some(MenuState::Window) => {
@@ -2763,6 +4941,9 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                         'k' => Some(Command::FocusUp),
                         'j' => Some(Command::FocusDown),
                         'm' => Some(Command::MaximizePane),
+                        'g' => Some(Command::GrabPane),
+                        'r' => Some(Command::TogglePaneReadOnly),
+                        '0' => Some(Command::RestoreHomeLayout),
                         _ => None,
                     };
                     if let Some(cmd) = cmd {
@@ -2771,8 +4952,13 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                         app.menu_state = None;
                         app.key_buffer.clear();
                         execute_command(app, cmd);
+                    } else {
+                        flash_unbound_key(app, c);
                     }
                 }
+                Some(MenuState::Task) if c == 'c' => {
+                    app.menu_state = Some(MenuState::Color);
+                }
                 Some(MenuState::Task) => {
                     // 任务子菜单：立即执行命令并退出菜单
                     let cmd = match c {
@@ -2781,13 +4967,17 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                         'E' => Some(Command::EditTaskInEditor),
                         'v' => Some(Command::ViewTask),
                         'V' => Some(Command::ViewTaskExternal),
-                        't' => Some(Command::EditTags), // 编辑标签
+                        't' => Some(Command::EditTags),   // 编辑标签
+                        'f' => Some(Command::EditFields), // 编辑自定义字段
+                        'n' => Some(Command::AddTaskNote),
                         'd' => Some(Command::DeleteTask),
                         'Y' => Some(Command::CopyTask), // 大写 Y 复制任务
+                        'P' => Some(Command::NewTaskFromClipboard), // 大写 P 从剪贴板新建
+                        '>' => Some(Command::SendTaskToPane),
                         'h' => Some(Command::SetTaskPriority("high".to_string())),
                         'm' => Some(Command::SetTaskPriority("medium".to_string())),
                         'l' => Some(Command::SetTaskPriority("low".to_string())),
-                        'n' => Some(Command::SetTaskPriority("none".to_string())),
+                        'x' => Some(Command::SetTaskPriority("none".to_string())),
                         _ => None,
                     };
                     if let Some(cmd) = cmd {
@@ -2795,6 +4985,19 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                         app.menu_state = None;
                         app.key_buffer.clear();
                         execute_command(app, cmd);
+                    } else {
+                        flash_unbound_key(app, c);
+                    }
+                }
+                Some(MenuState::Color) => {
+                    // 颜色子菜单：设置选中任务的卡片颜色并退出菜单
+                    if let Some(color) = menu_card_color(c) {
+                        app.mode = Mode::Normal;
+                        app.menu_state = None;
+                        app.key_buffer.clear();
+                        execute_command(app, Command::SetTaskColor(color.to_string()));
+                    } else {
+                        flash_unbound_key(app, c);
                     }
                 }
                 Some(MenuState::Status) => {
@@ -2815,6 +5018,8 @@ fn handle_space_menu_mode(app: &mut App, key: KeyEvent) -> bool {
                         app.menu_state = None;
                         app.key_buffer.clear();
                         execute_command(app, cmd);
+                    } else {
+                        flash_unbound_key(app, c);
                     }
                 }
                 None => {}
@@ -2925,6 +5130,91 @@ fn handle_search_mode(app: &mut App, key: KeyEvent) -> bool {
     true
 }
 
+/// 处理快速添加模式的按键
+///
+/// Enter 在当前列创建任务后清空输入框，保持打开以便连续录入；Esc 关闭。
+fn handle_quick_add_mode(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc => {
+            app.quick_add_input.clear();
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Enter => {
+            let title = app.quick_add_input.value().trim().to_string();
+            if !title.is_empty() {
+                create_new_task(app, title);
+                app.quick_add_input.clear();
+            }
+        }
+        _ => {
+            app.quick_add_input.handle_key(key);
+        }
+    }
+    true
+}
+
+/// 处理就地编辑状态显示名的按键
+///
+/// Enter 通过 `update_status_display` 写入 `.kanban.toml` 并重新加载项目；Esc 放弃修改。
+fn handle_status_rename_mode(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc => {
+            app.status_rename_input.clear();
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Enter => {
+            let value = app.status_rename_input.value().trim().to_string();
+            app.status_rename_input.clear();
+            app.mode = Mode::Normal;
+            if !value.is_empty() {
+                rename_status_display(app, &value);
+            }
+        }
+        _ => {
+            app.status_rename_input.handle_key(key);
+        }
+    }
+    true
+}
+
+/// 修改聚焦列的状态显示名
+fn rename_status_display(app: &mut App, value: &str) {
+    let column = app
+        .selected_column
+        .get(&app.focused_pane)
+        .copied()
+        .unwrap_or(0);
+    let Some((project_path, status_name, old_display)) =
+        app.get_focused_project().and_then(|project| {
+            project
+                .statuses
+                .get(column)
+                .map(|s| (project.path.clone(), s.name.clone(), s.display.clone()))
+        })
+    else {
+        return;
+    };
+    if old_display == value {
+        return;
+    }
+
+    match crate::fs::status::update_status_display(&project_path, &status_name, value) {
+        Ok(_) => {
+            // 重新加载项目
+            if let Err(e) = app.reload_current_project() {
+                log_debug(format!("重新加载项目失败: {}", e));
+            }
+            app.show_notification(
+                format!("已更新显示名为「{}」", value),
+                NotificationLevel::Success,
+            );
+        }
+        Err(e) => {
+            app.show_notification(format!("更新失败: {}", e), NotificationLevel::Error);
+        }
+    }
+}
+
 /// 移动到上一列的第一个匹配
 fn move_to_prev_column(app: &mut App) {
     // 先从 search_state 中取出需要的信息
@@ -3013,11 +5303,19 @@ fn jump_to_task(app: &mut App, status: &str, task_index: usize) {
         None => return,
     };
 
+    // 全局任务索引转换为该列可见任务中的位置
+    let position = project.tasks.get(task_index).and_then(|target| {
+        app.column_tasks(app.focused_pane, project, status)
+            .iter()
+            .position(|t| t.id == target.id)
+    });
+
     // 设置选中的列
     app.selected_column.insert(app.focused_pane, column);
 
     // 设置选中的任务
-    app.selected_task_index.insert(app.focused_pane, task_index);
+    app.selected_task_index
+        .insert(app.focused_pane, position.unwrap_or(0));
 }
 
 /// 执行搜索并更新搜索状态
@@ -3056,8 +5354,12 @@ fn run_search(app: &mut App) {
     // 结构: (column_index, task_in_column_index, task_global_index, status)
     let mut raw_matches: Vec<(usize, usize, usize, String)> = Vec::new();
 
-    // 按列收集任务
+    // 按列收集任务（跳过被优先级筛选隐藏的任务）
+    let filter = app.task_filter(app.focused_pane);
     for (idx, task) in project.tasks.iter().enumerate() {
+        if !filter.matches(task) {
+            continue;
+        }
         let task_text = format!("{} {}", task.id, task.title).to_lowercase();
         let task_id_str = task.id.to_string();
 
@@ -3073,7 +5375,7 @@ fn run_search(app: &mut App) {
                 .tasks
                 .iter()
                 .enumerate()
-                .filter(|(_, t)| t.status == task.status)
+                .filter(|(_, t)| t.status == task.status && filter.matches(t))
                 .position(|(i, _)| i == idx)
                 .unwrap_or(0);
 
@@ -3270,6 +5572,54 @@ fn move_task_to_status_by_name(app: &mut App, target_status: &str) {
     move_selected_task_to_status(app, target_column, target_status);
 }
 
+/// 抓取面板后处理方向键：与相邻面板交换项目，其他键取消抓取
+fn handle_grabbed_pane_key(app: &mut App, key: KeyEvent) {
+    use crate::ui::layout::Direction;
+
+    let direction = match key.code {
+        KeyCode::Char('h') | KeyCode::Left => Some(Direction::Left),
+        KeyCode::Char('l') | KeyCode::Right => Some(Direction::Right),
+        KeyCode::Char('k') | KeyCode::Up => Some(Direction::Up),
+        KeyCode::Char('j') | KeyCode::Down => Some(Direction::Down),
+        _ => None,
+    };
+    app.grabbed_pane = None;
+
+    let Some(direction) = direction else {
+        return;
+    };
+    if app
+        .split_tree
+        .find_adjacent_pane(app.focused_pane, direction)
+        .is_some_and(|target| app.is_pane_read_only(target))
+    {
+        app.show_notification(
+            "目标面板已锁定（只读），不能交换".to_string(),
+            NotificationLevel::Warning,
+        );
+        return;
+    }
+    if app.swap_pane_project(direction) {
+        app.save_state();
+        app.show_notification("已交换面板项目".to_string(), NotificationLevel::Success);
+    } else {
+        app.show_notification("该方向没有面板".to_string(), NotificationLevel::Warning);
+    }
+}
+
+/// 展开/收起选中任务卡片，展开时在卡片下方显示正文和子任务
+fn toggle_selected_task_expand(app: &mut App) {
+    let (Some(task_id), Some(project_id)) =
+        (get_selected_task_id(app), get_focused_project_id(app))
+    else {
+        return;
+    };
+    let key = (project_id, task_id);
+    if !app.expanded_tasks.remove(&key) {
+        app.expanded_tasks.insert(key);
+    }
+}
+
 fn toggle_selected_task_mark(app: &mut App) {
     let Some(task_id) = get_selected_task_id(app) else {
         app.show_notification(
@@ -3278,14 +5628,14 @@ fn toggle_selected_task_mark(app: &mut App) {
         );
         return;
     };
-    let Some(project_name) = get_focused_project_name(app) else {
+    let Some(project_id) = get_focused_project_id(app) else {
         app.show_notification(
             "当前面板没有打开项目".to_string(),
             NotificationLevel::Warning,
         );
         return;
     };
-    let key = (project_name, task_id);
+    let key = (project_id, task_id);
     let marked = if app.marked_tasks.remove(&key) {
         false
     } else {
@@ -3303,11 +5653,11 @@ fn toggle_selected_task_mark(app: &mut App) {
 }
 
 fn clear_focused_project_marks(app: &mut App) -> bool {
-    let Some(project_name) = get_focused_project_name(app) else {
+    let Some(project_id) = get_focused_project_id(app) else {
         return false;
     };
     let before = app.marked_tasks.len();
-    app.marked_tasks.retain(|(name, _)| name != &project_name);
+    app.marked_tasks.retain(|(id, _)| id != &project_id);
     let cleared = before != app.marked_tasks.len();
     if cleared {
         app.show_notification(
@@ -3345,7 +5695,7 @@ enum MarkOperation {
 }
 
 fn update_focused_column_marks(app: &mut App, operation: MarkOperation) {
-    let Some(project_name) = get_focused_project_name(app) else {
+    let Some(project_id) = get_focused_project_id(app) else {
         app.show_notification(
             "当前面板没有打开项目".to_string(),
             NotificationLevel::Warning,
@@ -3367,10 +5717,8 @@ fn update_focused_column_marks(app: &mut App, operation: MarkOperation) {
     let task_ids: Vec<u32> = app
         .get_focused_project()
         .map(|project| {
-            project
-                .tasks
+            app.column_tasks(app.focused_pane, project, &status)
                 .iter()
-                .filter(|task| task.status == status)
                 .map(|task| task.id)
                 .collect()
         })
@@ -3379,17 +5727,17 @@ fn update_focused_column_marks(app: &mut App, operation: MarkOperation) {
     match operation {
         MarkOperation::SelectAll => {
             for task_id in task_ids {
-                app.marked_tasks.insert((project_name.clone(), task_id));
+                app.marked_tasks.insert((project_id.clone(), task_id));
             }
         }
         MarkOperation::ClearAll => {
             for task_id in task_ids {
-                app.marked_tasks.remove(&(project_name.clone(), task_id));
+                app.marked_tasks.remove(&(project_id.clone(), task_id));
             }
         }
         MarkOperation::Invert => {
             for task_id in task_ids {
-                let key = (project_name.clone(), task_id);
+                let key = (project_id.clone(), task_id);
                 if !app.marked_tasks.remove(&key) {
                     app.marked_tasks.insert(key);
                 }
@@ -3399,14 +5747,14 @@ fn update_focused_column_marks(app: &mut App, operation: MarkOperation) {
 }
 
 fn move_marked_tasks_to_status(app: &mut App, target_column: usize, target_status: &str) {
-    let Some(project_name) = get_focused_project_name(app) else {
+    let Some(project_id) = get_focused_project_id(app) else {
         move_task_to_status_by_name(app, target_status);
         return;
     };
     let marked_ids: Vec<u32> = app
         .marked_tasks
         .iter()
-        .filter(|(name, _)| name == &project_name)
+        .filter(|(id, _)| id == &project_id)
         .map(|(_, task_id)| *task_id)
         .collect();
     if marked_ids.is_empty() {
@@ -3414,63 +5762,76 @@ fn move_marked_tasks_to_status(app: &mut App, target_column: usize, target_statu
         return;
     }
 
-    let Some(project) = app.projects.iter_mut().find(|p| p.name == project_name) else {
+    let Some(project) = app.projects.iter_mut().find(|p| p.id == project_id) else {
         app.show_notification(
-            format!("无法批量移动：找不到项目 '{}'", project_name),
+            format!("无法批量移动：找不到项目 '{}'", project_id),
             NotificationLevel::Error,
         );
         return;
     };
     let project_path = project.path.clone();
-    let mut moved = 0usize;
-    let mut skipped = 0usize;
     let mut failures = Vec::new();
+    let mut tasks = Vec::new();
     for task_id in marked_ids.iter().copied() {
-        let Some(task) = project.tasks.iter_mut().find(|task| task.id == task_id) else {
-            failures.push(format!("任务 {} 不存在", task_id));
-            continue;
-        };
-        if task.status == target_status {
-            skipped += 1;
-            continue;
-        }
-        match crate::fs::move_task(&project_path, task, target_status) {
-            Ok(_) => moved += 1,
-            Err(error) => failures.push(format!("任务 {}: {}", task_id, error)),
+        match project.tasks.iter().find(|task| task.id == task_id) {
+            Some(task) => tasks.push(task.clone()),
+            None => failures.push(format!("任务 {} 不存在", task_id)),
         }
     }
 
     for task_id in marked_ids {
-        app.marked_tasks.remove(&(project_name.clone(), task_id));
+        app.marked_tasks.remove(&(project_id.clone(), task_id));
     }
     app.selected_column.insert(app.focused_pane, target_column);
     app.selected_task_index.insert(app.focused_pane, 0);
-    if let Err(error) = app.reload_current_project() {
-        failures.push(format!("重新加载项目失败: {}", error));
-    }
 
-    let message = if failures.is_empty() {
-        format!(
-            "批量移动完成：{} 个任务已移动，{} 个任务已在目标状态",
-            moved, skipped
-        )
-    } else {
-        format!(
-            "批量移动完成：{} 个成功，{} 个已在目标状态，{} 个失败: {}",
-            moved,
-            skipped,
-            failures.len(),
-            failures.join("；")
-        )
-    };
-    app.show_notification(
-        message,
+    let target_status = target_status.to_string();
+    let total = tasks.len();
+    app.start_job(JobKind::BulkMove, total, move |job| {
+        if let Err(e) = crate::fs::backup::before(&project_path, "bulk-move") {
+            return JobResult::done(format!("批量移动已取消: {}", e), NotificationLevel::Error);
+        }
+        let mut moved = 0usize;
+        let mut skipped = 0usize;
+        for (done, mut task) in tasks.into_iter().enumerate() {
+            if job.is_cancelled() {
+                return JobResult::done(
+                    crate::job::cancelled_message(JobKind::BulkMove, done, total),
+                    NotificationLevel::Warning,
+                );
+            }
+            if task.status == target_status {
+                skipped += 1;
+            } else {
+                match crate::fs::move_task(&project_path, &mut task, &target_status) {
+                    Ok(_) => moved += 1,
+                    Err(error) => failures.push(format!("任务 {}: {}", task.id, error)),
+                }
+            }
+            job.progress(done + 1, total);
+        }
+
         if failures.is_empty() {
-            NotificationLevel::Success
+            JobResult::done(
+                format!(
+                    "批量移动完成：{} 个任务已移动，{} 个任务已在目标状态",
+                    moved, skipped
+                ),
+                NotificationLevel::Success,
+            )
         } else {
-            NotificationLevel::Error
-        },
-    );
+            JobResult::done(
+                format!(
+                    "批量移动完成：{} 个成功，{} 个已在目标状态，{} 个失败: {}",
+                    moved,
+                    skipped,
+                    failures.len(),
+                    failures.join("；")
+                ),
+                NotificationLevel::Error,
+            )
+        }
+    });
 }
 
 fn move_selected_task_to_status(app: &mut App, target_column: usize, target_status: &str) {
@@ -3486,14 +5847,33 @@ fn move_selected_task_to_status(app: &mut App, target_column: usize, target_stat
     let Some(task_id) = get_selected_task_id(app) else {
         return;
     };
-    let Some(project_name) = get_focused_project_name(app) else {
+    let Some(project_id) = get_focused_project_id(app) else {
         return;
     };
 
+    // 移到完成列时显示可撤销的完成提示
+    let completion = app
+        .get_focused_project()
+        .filter(|project| project.is_done_column(target_column))
+        .and_then(|project| {
+            let task = project.tasks.iter().find(|task| task.id == task_id)?;
+            let display = project.statuses.get(target_column)?.display.clone();
+            let title = if app.redact {
+                format!("#{}", task.id)
+            } else {
+                task.title.clone()
+            };
+            let undo = crate::app::UndoMove {
+                project_id: project_id.clone(),
+                task: task.clone(),
+            };
+            Some((format!("✓ {} → {}  （u 撤销）", title, display), undo))
+        });
+
     let move_result = app
         .projects
         .iter_mut()
-        .find(|project| project.name == project_name)
+        .find(|project| project.id == project_id)
         .and_then(|project| {
             let project_path = project.path.clone();
             project
@@ -3523,10 +5903,13 @@ fn move_selected_task_to_status(app: &mut App, target_column: usize, target_stat
                 );
                 return;
             }
-            app.show_notification(
-                format!("已移动到 {}", target_status),
-                NotificationLevel::Success,
-            );
+            match completion {
+                Some((message, undo)) => app.show_completion(message, undo),
+                None => app.show_notification(
+                    format!("已移动到 {}", target_status),
+                    NotificationLevel::Success,
+                ),
+            }
         }
         Some((project_path, Err(error))) => app.show_notification(
             format!(
@@ -3541,7 +5924,7 @@ fn move_selected_task_to_status(app: &mut App, target_column: usize, target_stat
         None => app.show_notification(
             format!(
                 "无法移动任务 {}：在项目 '{}' 中找不到该任务",
-                task_id, project_name
+                task_id, project_id
             ),
             NotificationLevel::Error,
         ),
@@ -3555,6 +5938,15 @@ fn handle_preview_mode(app: &mut App, key: KeyEvent) -> bool {
             app.mode = Mode::Normal;
             app.preview_content.clear();
             app.preview_scroll = 0;
+            app.preview_link = None;
+        }
+        KeyCode::Tab => select_preview_link(app, true),
+        KeyCode::BackTab => select_preview_link(app, false),
+        KeyCode::Enter => {
+            let urls = crate::ui::links::find_urls(&app.preview_content);
+            if let Some(url) = app.preview_link.and_then(|i| urls.get(i)) {
+                open_link(app, url);
+            }
         }
         KeyCode::Down => {
             // 向下滚动
@@ -3564,11 +5956,105 @@ fn handle_preview_mode(app: &mut App, key: KeyEvent) -> bool {
             // 向上滚动
             app.preview_scroll = app.preview_scroll.saturating_sub(1);
         }
+        KeyCode::Char('+') | KeyCode::Char('=') => resize_preview(app, true),
+        KeyCode::Char('-') => resize_preview(app, false),
         _ => {}
     }
     true
 }
 
+/// 预览中切换选中的链接（循环），并滚动到链接所在的行
+fn select_preview_link(app: &mut App, forward: bool) {
+    let urls = crate::ui::links::find_urls(&app.preview_content);
+    if urls.is_empty() {
+        app.show_notification("正文中没有链接".to_string(), NotificationLevel::Info);
+        return;
+    }
+    let index = match (app.preview_link, forward) {
+        (Some(i), true) => (i + 1) % urls.len(),
+        (Some(i), false) => (i + urls.len() - 1) % urls.len(),
+        (None, true) => 0,
+        (None, false) => urls.len() - 1,
+    };
+    app.preview_link = Some(index);
+
+    // 链接在可见区域之外时滚动到该行（预览正文约占终端高度的 90%，减去标题栏和边框）
+    if let Some(line) = crate::ui::links::line_of(&app.preview_content, &urls[index]) {
+        let rows = crossterm::terminal::size().map(|(_, h)| h).unwrap_or(24);
+        let visible = (rows as usize * 9 / 10).saturating_sub(6).max(1);
+        let scroll = app.preview_scroll as usize;
+        if line < scroll || line >= scroll + visible {
+            app.preview_scroll = line.saturating_sub(visible / 3) as u16;
+        }
+    }
+}
+
+/// 用系统默认程序打开链接
+fn open_link(app: &mut App, url: &str) {
+    match crate::ui::links::open_url(url) {
+        Ok(()) => app.show_notification(format!("已打开 {}", url), NotificationLevel::Success),
+        Err(e) => app.show_notification(
+            format!("无法用 {} 打开链接: {}", crate::config::file_opener(), e),
+            NotificationLevel::Error,
+        ),
+    }
+}
+
+/// `:open-url [序号]`：打开选中任务正文中的第 N 个链接；只有一个链接时可省略序号
+fn open_task_url(app: &mut App, arg: Option<&str>) {
+    let Some(task) = get_selected_task(app) else {
+        app.show_notification("没有选中的任务".to_string(), NotificationLevel::Warning);
+        return;
+    };
+    let urls = crate::ui::links::find_urls(&task.content);
+    let index = match arg.map(str::parse::<usize>) {
+        Some(Ok(n)) if (1..=urls.len()).contains(&n) => n - 1,
+        Some(_) => {
+            app.show_notification(
+                format!("用法: :open-url <1-{}>", urls.len().max(1)),
+                NotificationLevel::Warning,
+            );
+            return;
+        }
+        None if urls.len() == 1 => 0,
+        None if urls.is_empty() => {
+            app.show_notification("任务正文中没有链接".to_string(), NotificationLevel::Info);
+            return;
+        }
+        None => {
+            app.show_notification(
+                format!(
+                    "任务中有 {} 个链接，用 :open-url <序号> 打开，或在预览中按 Tab 选择",
+                    urls.len()
+                ),
+                NotificationLevel::Info,
+            );
+            return;
+        }
+    };
+    open_link(app, &urls[index]);
+}
+
+/// 调整预览弹窗宽度并保存到配置，下次打开预览时沿用
+fn resize_preview(app: &mut App, grow: bool) {
+    use crate::config::{PREVIEW_SIZE_MAX, PREVIEW_SIZE_MIN, PREVIEW_SIZE_STEP};
+
+    let size = app.config.preview_size;
+    let size = if grow {
+        size.saturating_add(PREVIEW_SIZE_STEP)
+    } else {
+        size.saturating_sub(PREVIEW_SIZE_STEP)
+    }
+    .clamp(PREVIEW_SIZE_MIN, PREVIEW_SIZE_MAX);
+    if size == app.config.preview_size {
+        return;
+    }
+    app.config.preview_size = size;
+    if let Err(e) = save_app_config(app) {
+        log_debug(format!("保存配置失败: {}", e));
+    }
+}
+
 /// 获取当前光标所在行的起始位置
 #[allow(dead_code)]
 fn get_line_start(text: &str, cursor_pos: usize) -> usize {
@@ -3651,6 +6137,54 @@ fn move_cursor_vertical(text: &str, cursor_pos: usize, direction: i32) -> usize
     }
 }
 
+/// 写入失败时提示用户；所有修改都是立即落盘的，失败的修改不会保留在内存中
+fn report_save_error(app: &mut App, error: String) {
+    log_debug(format!("保存任务失败: {}", error));
+    app.last_save_error = Some(SaveError::Files(error.clone()));
+    app.show_notification(format!("保存失败: {}", error), NotificationLevel::Error);
+}
+
+/// 写回配置；失败时记录下来，`:write` 会重试
+fn save_app_config(app: &mut App) -> Result<(), String> {
+    match crate::config::save_config(&app.config) {
+        Ok(()) => {
+            if matches!(app.last_save_error, Some(SaveError::Config(_))) {
+                app.last_save_error = None;
+            }
+            Ok(())
+        }
+        Err(e) => {
+            app.last_save_error = Some(SaveError::Config(e.to_string()));
+            Err(e.to_string())
+        }
+    }
+}
+
+/// `:write`：修改在发生时已同步写入，这里重试或再次提示上一次保存失败，
+/// 没有未解决的失败时才报告成功
+fn write_pending(app: &mut App) {
+    match app.last_save_error.take() {
+        None => app.show_notification("所有修改已写入磁盘".to_string(), NotificationLevel::Success),
+        Some(SaveError::Config(_)) => match save_app_config(app) {
+            Ok(()) => {
+                app.show_notification("所有修改已写入磁盘".to_string(), NotificationLevel::Success)
+            }
+            Err(e) => {
+                app.show_notification(format!("保存配置失败: {}", e), NotificationLevel::Error)
+            }
+        },
+        Some(SaveError::State(_)) => {
+            // 后台保存，再次失败时会重新提示
+            app.save_state();
+            app.show_notification("正在重新保存界面状态".to_string(), NotificationLevel::Info);
+        }
+        Some(SaveError::Files(e)) => app.show_notification(
+            format!("上次保存失败，修改未写入: {}", e),
+            NotificationLevel::Error,
+        ),
+    }
+}
+
 /// 重平衡列内任务的order值，使其均匀分布
 fn rebalance_order_in_column(tasks: &mut [&mut crate::models::Task]) {
     // tasks已按order排序
@@ -3661,15 +6195,11 @@ fn rebalance_order_in_column(tasks: &mut [&mut crate::models::Task]) {
 
 /// 调整当前列的宽度
 fn adjust_column_width(app: &mut App, delta: i16) {
-    let project_name = match get_focused_project_name(app) {
-        Some(name) => name,
-        None => return,
-    };
-
     let project = match app.get_focused_project() {
-        Some(p) => p,
+        Some(p) => p.clone(),
         None => return,
     };
+    crate::config::migrate_column_layout(&mut app.config, &project);
 
     let column = app
         .selected_column
@@ -3682,7 +6212,7 @@ fn adjust_column_width(app: &mut App, delta: i16) {
     let widths = app
         .config
         .column_widths
-        .entry(project_name.clone())
+        .entry(project.id.clone())
         .or_insert_with(|| vec![100 / num_columns as u16; num_columns]);
 
     if column >= widths.len() {
@@ -3717,7 +6247,7 @@ fn adjust_column_width(app: &mut App, delta: i16) {
     normalize_widths(widths);
 
     // 保存配置
-    if let Err(e) = crate::config::save_config(&app.config) {
+    if let Err(e) = save_app_config(app) {
         log_debug(format!("保存配置失败: {}", e));
     }
 
@@ -3727,17 +6257,17 @@ fn adjust_column_width(app: &mut App, delta: i16) {
 
 /// 重置为等宽
 fn reset_column_widths(app: &mut App) {
-    let project_name = match get_focused_project_name(app) {
-        Some(name) => name,
-        None => return,
+    let Some(project) = app.get_focused_project().cloned() else {
+        return;
     };
 
     // 移除配置和最大化状态
-    app.config.column_widths.remove(&project_name);
-    app.config.maximized_column.remove(&project_name);
+    crate::config::migrate_column_layout(&mut app.config, &project);
+    app.config.column_widths.remove(&project.id);
+    app.config.maximized_column.remove(&project.id);
 
     // 保存配置
-    if let Err(e) = crate::config::save_config(&app.config) {
+    if let Err(e) = save_app_config(app) {
         log_debug(format!("保存配置失败: {}", e));
     }
 }
@@ -3746,27 +6276,21 @@ fn reset_column_widths(app: &mut App) {
 fn toggle_maximize_column(app: &mut App) {
     log_debug("调用 toggle_maximize_column".to_string());
 
-    let project_name = match get_focused_project_name(app) {
-        Some(name) => name,
-        None => {
-            log_debug("无法获取项目名称".to_string());
-            return;
-        }
+    let Some(project) = app.get_focused_project().cloned() else {
+        log_debug("无法获取项目".to_string());
+        return;
     };
+    crate::config::migrate_column_layout(&mut app.config, &project);
 
     let column = app
         .selected_column
         .get(&app.focused_pane)
         .copied()
         .unwrap_or(0);
-    log_debug(format!("当前列: {}, 项目: {}", column, project_name));
+    log_debug(format!("当前列: {}, 项目: {}", column, project.name));
 
     // 获取当前最大化状态
-    let current_max = app
-        .config
-        .maximized_column
-        .get(&project_name)
-        .and_then(|&opt| opt);
+    let current_max = crate::config::maximized_column_for(&app.config, &project);
 
     log_debug(format!("当前最大化状态: {:?}", current_max));
 
@@ -3774,20 +6298,18 @@ fn toggle_maximize_column(app: &mut App) {
     if current_max == Some(column) {
         // 已最大化当前列 -> 取消最大化
         log_debug("取消最大化".to_string());
-        app.config
-            .maximized_column
-            .insert(project_name.clone(), None);
+        app.config.maximized_column.insert(project.id.clone(), None);
     } else {
         // 最大化当前列
         log_debug(format!("最大化列 {}", column));
-        app.config.column_widths.remove(&project_name);
+        app.config.column_widths.remove(&project.id);
         app.config
             .maximized_column
-            .insert(project_name.clone(), Some(column));
+            .insert(project.id.clone(), Some(column));
     }
 
     // 保存配置
-    if let Err(e) = crate::config::save_config(&app.config) {
+    if let Err(e) = save_app_config(app) {
         log_debug(format!("保存配置失败: {}", e));
     }
 
@@ -3816,8 +6338,8 @@ fn normalize_widths(widths: &mut [u16]) {
     }
 }
 
-/// 获取当前聚焦项目的名称
-fn get_focused_project_name(app: &App) -> Option<String> {
+/// 获取当前聚焦面板的项目 ID
+fn get_focused_project_id(app: &App) -> Option<String> {
     if let Some(crate::ui::layout::SplitNode::Leaf { project_id, .. }) =
         app.split_tree.find_pane(app.focused_pane)
     {
@@ -3836,20 +6358,47 @@ fn get_menu_commands(menu_state: Option<crate::app::MenuState>) -> Vec<char> {
             vec!['f', 'p', 'w', 't', 's', 'r', 'R', '?', 'q']
         }
         Some(MenuState::Project) => {
-            vec!['o', 'n', 'N', 'd', 'D', 'r', 'i']
+            vec!['o', 'n', 'N', 'd', 'D', 'r', 's', 'i', 'f']
         }
         Some(MenuState::Window) => {
-            vec!['w', 'v', 's', 'q', 'm', 'h', 'l', 'k', 'j']
+            vec!['w', 'v', 's', 'q', 'm', 'g', 'r', '0', 'h', 'l', 'k', 'j']
         }
         Some(MenuState::Task) => {
-            vec!['a', 'e', 'E', 'v', 'V', 't', 'Y', 'd', 'h', 'm', 'l', 'n']
+            vec![
+                'a', 'e', 'E', 'v', 'V', 't', 'f', 'n', 'Y', 'P', '>', 'd', 'h', 'm', 'l', 'x', 'c',
+            ]
         }
+        Some(MenuState::Color) => crate::ui::theme::CARD_COLORS
+            .iter()
+            .filter_map(|(_, key, _)| key.chars().next())
+            .chain(['x'])
+            .collect(),
         Some(MenuState::Status) => {
             vec!['a', 'r', 'e', 'h', 'l', 'd']
         }
     }
 }
 
+/// 颜色子菜单按键对应的卡片颜色，`x` 清除颜色
+fn menu_card_color(c: char) -> Option<&'static str> {
+    if c == 'x' {
+        return Some("none");
+    }
+    crate::ui::theme::CARD_COLORS
+        .iter()
+        .find(|(_, key, _)| key.chars().eq([c]))
+        .map(|(name, _, _)| *name)
+}
+
+/// 可选的卡片颜色名称，用于提示
+fn card_color_choices() -> String {
+    crate::ui::theme::CARD_COLORS
+        .iter()
+        .map(|(name, _, _)| *name)
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
 /// 向上导航菜单（跳过空行）
 fn navigate_menu_up(app: &mut App) {
     let commands = get_menu_commands(app.menu_state);
@@ -3957,6 +6506,8 @@ fn execute_selected_menu_command(app: &mut App, index: usize) {
                 'D' => Some(Command::DeleteProject),
                 'r' => Some(Command::RenameProject),
                 'i' => Some(Command::CopyProjectInfo),
+                'f' => Some(Command::RevealProject),
+                's' => Some(Command::ProjectSettings),
                 _ => None,
             };
             if let Some(cmd) = cmd {
@@ -3978,6 +6529,9 @@ fn execute_selected_menu_command(app: &mut App, index: usize) {
                 'k' => Some(Command::FocusUp),
                 'j' => Some(Command::FocusDown),
                 'm' => Some(Command::MaximizePane),
+                'g' => Some(Command::GrabPane),
+                'r' => Some(Command::TogglePaneReadOnly),
+                '0' => Some(Command::RestoreHomeLayout),
                 _ => None,
             };
             if let Some(cmd) = cmd {
@@ -3988,6 +6542,10 @@ fn execute_selected_menu_command(app: &mut App, index: usize) {
                 execute_command(app, cmd);
             }
         }
+        Some(MenuState::Task) if c == 'c' => {
+            app.menu_state = Some(MenuState::Color);
+            app.menu_selected_index = Some(0);
+        }
         Some(MenuState::Task) => {
             let cmd = match c {
                 'a' => Some(Command::NewTask),
@@ -3996,12 +6554,16 @@ fn execute_selected_menu_command(app: &mut App, index: usize) {
                 'v' => Some(Command::ViewTask),
                 'V' => Some(Command::ViewTaskExternal),
                 't' => Some(Command::EditTags),
+                'f' => Some(Command::EditFields),
+                'n' => Some(Command::AddTaskNote),
                 'd' => Some(Command::DeleteTask),
                 'Y' => Some(Command::CopyTask),
+                'P' => Some(Command::NewTaskFromClipboard),
+                '>' => Some(Command::SendTaskToPane),
                 'h' => Some(Command::SetTaskPriority("high".to_string())),
                 'm' => Some(Command::SetTaskPriority("medium".to_string())),
                 'l' => Some(Command::SetTaskPriority("low".to_string())),
-                'n' => Some(Command::SetTaskPriority("none".to_string())),
+                'x' => Some(Command::SetTaskPriority("none".to_string())),
                 _ => None,
             };
             if let Some(cmd) = cmd {
@@ -4012,6 +6574,15 @@ fn execute_selected_menu_command(app: &mut App, index: usize) {
                 execute_command(app, cmd);
             }
         }
+        Some(MenuState::Color) => {
+            if let Some(color) = menu_card_color(c) {
+                app.mode = Mode::Normal;
+                app.menu_state = None;
+                app.menu_selected_index = None;
+                app.key_buffer.clear();
+                execute_command(app, Command::SetTaskColor(color.to_string()));
+            }
+        }
         Some(MenuState::Status) => {
             let cmd = match c {
                 'a' => Some(Command::CreateStatus),
@@ -4043,6 +6614,109 @@ mod tests {
         KeyEvent::new(code, modifiers)
     }
 
+    #[test]
+    fn peek_lists_counts_and_top_tasks_per_column() {
+        use crate::models::{Project, ProjectType, Status, Task};
+        let mut project = Project::new(
+            "web".to_string(),
+            std::path::PathBuf::from("/tmp/web"),
+            ProjectType::Global,
+        );
+        project.statuses = vec![
+            Status::new("todo".to_string(), "待办".to_string()),
+            Status::new("done".to_string(), "完成".to_string()),
+        ];
+        for (id, order) in [(1, 3000), (2, 1000), (3, 2000)] {
+            let mut task = Task::new(id, format!("task {}", id), "todo".to_string());
+            task.order = order;
+            project.tasks.push(task);
+        }
+        project.tasks[1].priority = Some("high".to_string());
+
+        assert_eq!(
+            peek_content(&project, 2),
+            "## 待办 · 3\n- #2 task 2 (high)\n- #3 task 3\n  … 还有 1 个\n\n## 完成 · 0"
+        );
+    }
+
+    #[test]
+    fn field_input_is_validated_against_project_fields() {
+        use crate::models::FieldDef;
+        use crate::models::field::FieldKind;
+        let field = |name: &str, kind| FieldDef {
+            name: name.to_string(),
+            kind,
+            display: None,
+        };
+        let fields = vec![
+            field("estimate", FieldKind::Number),
+            field("due", FieldKind::Date),
+        ];
+
+        assert_eq!(
+            parse_field_input(&fields, "estimate = 3\ndue =\n").unwrap(),
+            vec![
+                ("estimate".to_string(), Some(toml::Value::Integer(3))),
+                ("due".to_string(), None),
+            ]
+        );
+        assert!(parse_field_input(&fields, "estimate = many").is_err());
+        assert!(parse_field_input(&fields, "owner = me").is_err());
+        assert!(parse_field_input(&fields, "estimate 3").is_err());
+    }
+
+    #[test]
+    fn write_reports_a_pending_save_failure_instead_of_success() {
+        let mut app = crate::app::tests::test_app();
+        let level = |app: &App| app.notification.as_ref().map(|n| n.level);
+
+        report_save_error(&mut app, "disk full".to_string());
+        app.notification = None;
+        assert!(execute_text_command(&mut app, "write"));
+        assert_eq!(level(&app), Some(NotificationLevel::Error));
+        assert!(
+            app.notification
+                .as_ref()
+                .is_some_and(|n| n.message.contains("disk full"))
+        );
+
+        // 失败已提示过，修改已回滚，之后没有未解决的失败
+        assert!(execute_text_command(&mut app, "write"));
+        assert_eq!(level(&app), Some(NotificationLevel::Success));
+        assert_eq!(app.last_save_error, None);
+    }
+
+    #[test]
+    fn read_only_pane_rejects_changes_and_swaps() {
+        let mut app = crate::app::tests::test_app();
+        app.split_tree = crate::app::tests::three_pane_layout();
+        app.read_only_panes.insert(0);
+
+        execute_command(&mut app, Command::DeleteTask);
+        assert!(app.dialog.is_none());
+        assert!(matches!(
+            app.notification.as_ref().map(|n| n.level),
+            Some(NotificationLevel::Warning)
+        ));
+        execute_command(&mut app, Command::GrabPane);
+        assert_eq!(app.grabbed_pane, None);
+        app.notification = None;
+        assert!(execute_text_command(&mut app, "tag +urgent"));
+        assert!(app.notification.is_some());
+
+        // 从相邻面板交换到锁定面板也会被拒绝
+        app.focused_pane = 1;
+        execute_command(&mut app, Command::GrabPane);
+        assert_eq!(app.grabbed_pane, Some(1));
+        handle_grabbed_pane_key(&mut app, key(KeyCode::Char('h'), KeyModifiers::NONE));
+        match app.split_tree.find_pane(0) {
+            Some(crate::ui::layout::SplitNode::Leaf { project_id, .. }) => {
+                assert_eq!(project_id.as_deref(), Some("alpha"))
+            }
+            _ => panic!("pane 0 should be a leaf"),
+        }
+    }
+
     #[test]
     fn first_m_waits_for_second_key() {
         assert_eq!(
@@ -4071,6 +6745,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn colon_enters_command_mode() {
+        assert_eq!(
+            match_key_sequence(&[], key(KeyCode::Char(':'), KeyModifiers::NONE)),
+            Some(Command::EnterCommandMode)
+        );
+    }
+
+    #[test]
+    fn o_opens_quick_add() {
+        assert_eq!(
+            match_key_sequence(&[], key(KeyCode::Char('o'), KeyModifiers::NONE)),
+            Some(Command::QuickAdd)
+        );
+    }
+
+    #[test]
+    fn renumber_plan_summary_is_truncated() {
+        let plan: Vec<(u32, u32)> = (1..=10).map(|i| (i * 10, i)).collect();
+        let summary = format_renumber_plan(&plan);
+        assert!(summary.starts_with("#10 → #1, #20 → #2"));
+        assert!(summary.ends_with("… 共 10 个"));
+    }
+
     #[test]
     fn mm_toggles_column_maximize() {
         assert_eq!(
@@ -4079,6 +6777,385 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tab_toggles_task_expand() {
+        assert_eq!(
+            match_key_sequence(&[], key(KeyCode::Tab, KeyModifiers::NONE)),
+            Some(Command::ToggleTaskExpand)
+        );
+    }
+
+    #[test]
+    fn digits_toggle_priority_filter() {
+        assert_eq!(
+            match_key_sequence(&[], key(KeyCode::Char('1'), KeyModifiers::NONE)),
+            Some(Command::TogglePriorityFilter(PriorityFilter::High))
+        );
+        assert_eq!(
+            match_key_sequence(&[], key(KeyCode::Char('2'), KeyModifiers::NONE)),
+            Some(Command::TogglePriorityFilter(PriorityFilter::Medium))
+        );
+        assert_eq!(
+            match_key_sequence(&[], key(KeyCode::Char('0'), KeyModifiers::NONE)),
+            Some(Command::TogglePriorityFilter(PriorityFilter::All))
+        );
+    }
+
+    #[test]
+    fn hash_digit_filters_by_selected_task_tag() {
+        let hash = key(KeyCode::Char('#'), KeyModifiers::SHIFT);
+        assert!(is_pending_key_sequence(&[], hash));
+        assert_eq!(match_key_sequence(&[], hash), None);
+        assert_eq!(
+            match_key_sequence(&['#'], key(KeyCode::Char('2'), KeyModifiers::NONE)),
+            Some(Command::ToggleTagFilter(1))
+        );
+        assert_eq!(
+            match_key_sequence(&['#'], key(KeyCode::Char('0'), KeyModifiers::NONE)),
+            None
+        );
+    }
+
+    #[test]
+    fn tag_overview_enter_filters_focused_pane() {
+        let mut app = crate::app::tests::test_app();
+        // 只读面板也可以查看标签概览
+        app.read_only_panes.insert(0);
+        for (id, tags) in [(1, vec!["api"]), (2, vec!["ui", "api"])] {
+            let mut task = crate::models::Task::new(id, "t".to_string(), "todo".to_string());
+            task.tags = tags.into_iter().map(str::to_string).collect();
+            app.projects[0].tasks.push(task);
+        }
+
+        assert!(execute_text_command(&mut app, "tags"));
+        assert_eq!(app.mode, Mode::TagOverview);
+        handle_key_input(&mut app, key(KeyCode::Char('j'), KeyModifiers::NONE));
+        handle_key_input(&mut app, key(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.tag_overview.is_none());
+        assert_eq!(app.tag_filters.get(&0).map(String::as_str), Some("ui"));
+    }
+
+    #[test]
+    fn creating_a_near_duplicate_asks_first_and_can_open_the_existing_task() {
+        use crate::ui::dialogs::ConfirmAction;
+        let mut app = crate::app::tests::test_app();
+        app.projects[0].statuses = ["todo", "doing", "done"]
+            .iter()
+            .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+            .collect();
+        for (id, title, status) in [(1, "Fix the login bug", "doing"), (2, "Docs", "todo")] {
+            let task = crate::models::Task::new(id, title.to_string(), status.to_string());
+            app.projects[0].tasks.push(task);
+        }
+
+        create_new_task(&mut app, "fix login bug\nsteps".to_string());
+        assert_eq!(app.mode, Mode::Dialog);
+        match &app.dialog {
+            Some(DialogType::Confirm {
+                action: ConfirmAction::CreateDuplicateTask { input, duplicates },
+                ..
+            }) => {
+                assert_eq!(input, "fix login bug\nsteps");
+                assert_eq!(duplicates, &vec![1]);
+            }
+            _ => panic!("应显示重复任务确认"),
+        }
+
+        handle_key_input(&mut app, key(KeyCode::Char('1'), KeyModifiers::NONE));
+        assert!(app.dialog.is_none());
+        assert_eq!(app.selected_column.get(&0), Some(&1));
+        assert_eq!(get_selected_task_id(&app), Some(1));
+    }
+
+    #[test]
+    fn g_semicolon_jumps_to_the_last_edited_task() {
+        let mut app = crate::app::tests::test_app();
+        app.projects[0].statuses = ["todo", "doing"]
+            .iter()
+            .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+            .collect();
+        for (id, status) in [(1, "todo"), (2, "doing"), (3, "doing")] {
+            let task = crate::models::Task::new(id, format!("task {}", id), status.to_string());
+            app.projects[0].tasks.push(task);
+        }
+
+        handle_key_input(&mut app, key(KeyCode::Char('g'), KeyModifiers::NONE));
+        handle_key_input(&mut app, key(KeyCode::Char(';'), KeyModifiers::NONE));
+        assert_eq!(get_selected_task_id(&app), Some(1), "没有记录时保持不动");
+
+        app.remember_edited("alpha", 3);
+        handle_key_input(&mut app, key(KeyCode::Char('g'), KeyModifiers::NONE));
+        handle_key_input(&mut app, key(KeyCode::Char(';'), KeyModifiers::NONE));
+        assert_eq!(app.selected_column.get(&0), Some(&1));
+        assert_eq!(get_selected_task_id(&app), Some(3));
+
+        app.remember_edited("alpha", 1);
+        execute_text_command(&mut app, "last");
+        assert_eq!(get_selected_task_id(&app), Some(1));
+    }
+
+    #[test]
+    fn diff_panes_needs_a_pane_number_when_ambiguous() {
+        let mut app = crate::app::tests::test_app();
+        app.split_tree = crate::app::tests::three_pane_layout();
+        for (project, titles) in [
+            (0, ["Fix login", "Release notes"]),
+            (2, ["fix login", "Buy milk"]),
+        ] {
+            for (id, title) in titles.iter().enumerate() {
+                let task =
+                    crate::models::Task::new(id as u32 + 1, title.to_string(), "todo".into());
+                app.projects[project].tasks.push(task);
+            }
+        }
+
+        execute_text_command(&mut app, "diff-panes");
+        assert!(app.pane_diff.is_empty());
+
+        execute_text_command(&mut app, "diff-panes 3");
+        let mut unmatched: Vec<_> = app.pane_diff.iter().cloned().collect();
+        unmatched.sort();
+        assert_eq!(
+            unmatched,
+            vec![("alpha".to_string(), 2), ("gamma".to_string(), 2)]
+        );
+
+        execute_text_command(&mut app, "diff off");
+        assert!(app.pane_diff.is_empty());
+    }
+
+    #[test]
+    fn note_command_appends_timestamped_note_and_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = crate::app::tests::test_app();
+        app.projects[0].path = dir.path().to_path_buf();
+        app.projects[0].statuses = vec![crate::models::Status::new(
+            "todo".to_string(),
+            "todo".to_string(),
+        )];
+        app.projects[0].tasks.push(crate::models::Task::new(
+            1,
+            "Call ACME".to_string(),
+            "todo".to_string(),
+        ));
+
+        execute_text_command(&mut app, "note left a voicemail");
+
+        let content = &app.projects[0].tasks[0].content;
+        assert!(content.starts_with("## Notes\n\n- "), "{}", content);
+        assert!(content.ends_with(" left a voicemail"), "{}", content);
+        let saved = std::fs::read_to_string(dir.path().join("todo").join("1.md")).unwrap();
+        assert!(saved.contains("left a voicemail"), "{}", saved);
+    }
+
+    #[test]
+    fn editor_draft_becomes_selected_task_in_current_column() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::fs::project::create_project_in(dir.path(), "draft", crate::fs::Workflow::Basic)
+            .unwrap();
+        let mut app = crate::app::tests::test_app();
+        app.open_project(dir.path().to_path_buf(), crate::models::ProjectType::Global);
+        app.selected_column.insert(app.focused_pane, 1);
+
+        execute_command(&mut app, Command::NewTaskInEditor);
+        assert!(app.is_new_task_file);
+        let draft = std::path::PathBuf::from(app.pending_editor_file.take().unwrap());
+        assert!(!draft.starts_with(dir.path()));
+
+        // 没改模板标题就关闭编辑器：不创建任务
+        create_task_from_editor_file(&mut app, &draft).unwrap();
+        assert!(app.get_focused_project().unwrap().tasks.is_empty());
+
+        std::fs::write(&draft, "\n# 写周报\n\n- [ ] 汇总进度\n").unwrap();
+        create_task_from_editor_file(&mut app, &draft).unwrap();
+        std::fs::remove_file(&draft).unwrap();
+
+        let task = get_selected_task(&app).unwrap();
+        assert_eq!(task.title, "写周报");
+        assert_eq!(task.status, "doing");
+        assert_eq!(task.content, "- [ ] 汇总进度");
+        assert!(task.file_path.starts_with(dir.path().join("doing")));
+        assert_eq!(app.last_edited_task().map(|t| t.id), Some(task.id));
+    }
+
+    #[test]
+    fn undo_completion_restores_fields_changed_by_automations() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::fs::project::create_project_in(dir.path(), "undo", crate::fs::Workflow::Basic)
+            .unwrap();
+        let config_path = dir.path().join(".kanban.toml");
+        let config = std::fs::read_to_string(&config_path).unwrap();
+        std::fs::write(
+            &config_path,
+            format!(
+                "{}\n[[automations]]\non_enter = \"done\"\nset = {{ completed = \"{{today}}\" }}\nclear = [\"due\"]\n\n[[automations]]\non_enter = \"doing\"\nset = {{ started = \"{{today}}\" }}\n",
+                config
+            ),
+        )
+        .unwrap();
+        let mut task = crate::models::Task::new(1, "Ship".to_string(), "doing".to_string());
+        task.extra.insert("due".to_string(), "2026-03-05".into());
+        crate::fs::save_task(dir.path(), &task).unwrap();
+
+        let mut app = crate::app::tests::test_app();
+        app.open_project(dir.path().to_path_buf(), crate::models::ProjectType::Global);
+        jump_to_task(&mut app, "doing", 0);
+        execute_command(&mut app, Command::MoveTaskRight);
+        let done = &app.get_focused_project().unwrap().tasks[0];
+        assert_eq!(done.status, "done");
+        assert!(!done.extra.contains_key("due"));
+
+        execute_command(&mut app, Command::UndoCompletion);
+        let task = get_selected_task(&app).unwrap();
+        assert_eq!(task.status, "doing");
+        assert_eq!(task.extra["due"].as_str(), Some("2026-03-05"));
+        assert!(!task.extra.contains_key("completed"));
+        assert!(
+            !task.extra.contains_key("started"),
+            "不重新运行原状态的规则"
+        );
+        assert!(task.file_path.starts_with(dir.path().join("doing")));
+        assert!(!dir.path().join("done").join("1.md").exists());
+    }
+
+    #[test]
+    fn editor_draft_with_near_duplicate_title_asks_first() {
+        use crate::ui::dialogs::ConfirmAction;
+        let dir = tempfile::tempdir().unwrap();
+        crate::fs::project::create_project_in(dir.path(), "draft", crate::fs::Workflow::Basic)
+            .unwrap();
+        let existing = crate::models::Task::new(1, "Fix the login bug".to_string(), "todo".into());
+        crate::fs::save_task(dir.path(), &existing).unwrap();
+        let mut app = crate::app::tests::test_app();
+        app.open_project(dir.path().to_path_buf(), crate::models::ProjectType::Global);
+
+        let draft = dir.path().join("draft.md");
+        std::fs::write(&draft, "# fix login bug\n\nsteps\n").unwrap();
+        create_task_from_editor_file(&mut app, &draft).unwrap();
+        assert_eq!(app.get_focused_project().unwrap().tasks.len(), 1);
+        assert_eq!(app.mode, Mode::Dialog);
+        assert!(matches!(
+            &app.dialog,
+            Some(DialogType::Confirm {
+                action: ConfirmAction::CreateDuplicateTask { duplicates, .. },
+                ..
+            }) if duplicates == &vec![1]
+        ));
+
+        handle_key_input(&mut app, key(KeyCode::Char('y'), KeyModifiers::NONE));
+        let tasks = &app.get_focused_project().unwrap().tasks;
+        assert_eq!(tasks.len(), 2);
+        let created = tasks.iter().find(|task| task.id == 2).unwrap();
+        assert_eq!(created.title, "fix login bug");
+        assert_eq!(created.content, "steps");
+    }
+
+    #[test]
+    fn done_cleanup_archives_done_column_not_trailing_blocked() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = crate::app::tests::test_app();
+        let project = &mut app.projects[0];
+        project.path = dir.path().to_path_buf();
+        // 旧版 dev 工作流创建的项目：blocked 在 done 之后
+        project.statuses = ["backlog", "todo", "doing", "review", "done", "blocked"]
+            .iter()
+            .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+            .collect();
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(90 * 24 * 3600);
+        for (id, status) in [(1, "done"), (2, "blocked")] {
+            let mut task = crate::models::Task::new(id, "old".to_string(), status.to_string());
+            task.file_path = crate::fs::save_task(dir.path(), &task).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&task.file_path)
+                .unwrap()
+                .set_modified(old)
+                .unwrap();
+            project.tasks.push(task);
+        }
+
+        cleanup_done_tasks(&mut app, 30);
+        let job = app.job.as_mut().expect("应启动归档任务");
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while job.poll().is_none() {
+            assert!(std::time::Instant::now() < deadline, "归档任务未完成");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        assert!(!dir.path().join("done").join("1.md").exists());
+        assert!(dir.path().join("blocked").join("2.md").exists());
+    }
+
+    #[test]
+    fn task_menu_c_opens_card_color_picker() {
+        use crate::app::MenuState;
+        let mut app = crate::app::tests::test_app();
+        app.mode = Mode::SpaceMenu;
+        app.menu_state = Some(MenuState::Task);
+
+        handle_key_input(&mut app, key(KeyCode::Char('c'), KeyModifiers::NONE));
+        assert_eq!(app.menu_state, Some(MenuState::Color));
+        assert_eq!(app.pending_key_label().as_deref(), Some("␣tc"));
+        let colors: Vec<_> = get_menu_commands(app.menu_state)
+            .into_iter()
+            .filter_map(menu_card_color)
+            .collect();
+        assert_eq!(colors.first(), Some(&"red"));
+        assert_eq!(colors.last(), Some(&"none"));
+        assert_eq!(colors.len(), crate::ui::theme::CARD_COLORS.len() + 1);
+
+        // Esc 返回任务子菜单而不是主菜单
+        handle_key_input(&mut app, key(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.menu_state, Some(MenuState::Task));
+    }
+
+    #[test]
+    fn send_to_pane_picks_pane_by_number_then_status() {
+        let mut app = crate::app::tests::test_app();
+        app.split_tree = crate::app::tests::three_pane_layout();
+        for project in &mut app.projects {
+            project.statuses = ["todo", "doing", "done"]
+                .iter()
+                .map(|name| crate::models::Status::new(name.to_string(), name.to_string()))
+                .collect();
+        }
+        let task = crate::models::Task::new(7, "Triage".to_string(), "doing".to_string());
+        app.projects[0].tasks.push(task);
+        app.selected_column.insert(0, 1);
+
+        execute_command(&mut app, Command::SendTaskToPane);
+        assert_eq!(app.mode, Mode::SendToPane);
+        let state = app.send_to_pane.as_ref().unwrap();
+        let numbers: Vec<usize> = state.targets.iter().map(|t| t.number).collect();
+        assert_eq!(numbers, vec![2, 3]);
+
+        // 面板 1 是任务所在的面板，不能作为目标
+        handle_key_input(&mut app, key(KeyCode::Char('1'), KeyModifiers::NONE));
+        assert!(app.send_to_pane.as_ref().unwrap().target.is_none());
+        handle_key_input(&mut app, key(KeyCode::Char('c'), KeyModifiers::NONE));
+        handle_key_input(&mut app, key(KeyCode::Char('3'), KeyModifiers::NONE));
+        let state = app.send_to_pane.as_ref().unwrap();
+        assert!(state.copy);
+        assert_eq!(state.selected_target().unwrap().project_id, "gamma");
+        // 默认选中与任务当前状态同名的状态
+        assert_eq!(state.selected, 1);
+        handle_key_input(&mut app, key(KeyCode::Char('3'), KeyModifiers::NONE));
+        assert_eq!(app.send_to_pane.as_ref().unwrap().selected, 2);
+
+        handle_key_input(&mut app, key(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.send_to_pane.is_none());
+    }
+
+    #[test]
+    fn z_toggles_column_zoom() {
+        assert_eq!(
+            match_key_sequence(&[], key(KeyCode::Char('z'), KeyModifiers::NONE)),
+            Some(Command::ToggleZoomColumn)
+        );
+    }
+
     #[test]
     fn status_number_only_updates_one_based_selection() {
         let mut state = StatusSelectState {
@@ -4114,4 +7191,68 @@ mod tests {
         );
         assert_eq!(state.selected, 1);
     }
+
+    #[test]
+    fn k_on_first_task_focuses_column_header_for_inline_rename() {
+        let mut app = crate::app::tests::test_app();
+        app.projects[0].statuses = vec![crate::models::Status::new(
+            "todo".to_string(),
+            "待办".to_string(),
+        )];
+        for id in [1, 2] {
+            let task = crate::models::Task::new(id, "t".to_string(), "todo".to_string());
+            app.projects[0].tasks.push(task);
+        }
+        app.selected_task_index.insert(0, 1);
+
+        handle_key_input(&mut app, key(KeyCode::Char('k'), KeyModifiers::NONE));
+        assert_eq!(app.column_header_focus, None);
+        handle_key_input(&mut app, key(KeyCode::Char('k'), KeyModifiers::NONE));
+        assert_eq!(app.column_header_focus, Some(0));
+        // j 从列标题回到第一个任务
+        handle_key_input(&mut app, key(KeyCode::Char('j'), KeyModifiers::NONE));
+        assert_eq!(app.column_header_focus, None);
+        assert_eq!(app.selected_task_index[&0], 0);
+
+        handle_key_input(&mut app, key(KeyCode::Char('k'), KeyModifiers::NONE));
+        handle_key_input(&mut app, key(KeyCode::Char('r'), KeyModifiers::NONE));
+        assert_eq!(app.mode, Mode::StatusRename);
+        assert_eq!(app.status_rename_input.value(), "待办");
+        handle_key_input(&mut app, key(KeyCode::Char('!'), KeyModifiers::NONE));
+        assert_eq!(app.status_rename_input.value(), "待办!");
+        handle_key_input(&mut app, key(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.projects[0].statuses[0].display, "待办");
+        assert_eq!(app.column_header_focus, Some(0));
+    }
+
+    #[test]
+    fn new_project_dialog_tab_cycles_workflow_in_purpose() {
+        use crate::fs::Workflow;
+        let mut app = crate::app::tests::test_app();
+        execute_command(&mut app, Command::NewLocalProject);
+        assert_eq!(app.mode, Mode::Dialog);
+
+        handle_key_input(&mut app, key(KeyCode::Tab, KeyModifiers::NONE));
+        let Some(DialogType::Input {
+            title,
+            prompt,
+            purpose,
+            ..
+        }) = &app.dialog
+        else {
+            panic!("应打开输入对话框");
+        };
+        // 标题只用于显示，用途里记录本地/全局和工作流
+        assert_eq!(title, "创建新本地项目 [L]");
+        assert_eq!(
+            purpose,
+            &DialogPurpose::CreateProject {
+                local: true,
+                workflow: Workflow::Basic.next(),
+            }
+        );
+        assert!(prompt.contains(Workflow::Basic.next().name()), "{}", prompt);
+        assert!(!purpose.is_task_input());
+    }
 }
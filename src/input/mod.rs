@@ -1,7 +1,10 @@
 pub mod command_registry;
 mod commands;
+pub mod ime;
+mod keybindings;
 mod keyboard;
 
 pub use command_registry::{CommandDef, CommandRegistry};
 pub use commands::Command;
-pub use keyboard::{flush_pending_key_sequence, handle_key_input};
+pub use keybindings::{KeyHint, cheat_sheet, pane_hints};
+pub use keyboard::{create_task_from_editor_file, flush_pending_key_sequence, handle_key_input};
@@ -0,0 +1,210 @@
+/// 后台任务
+///
+/// 导入、批量移动、归档、备份和重新加载所有项目在大项目上可能需要几秒，
+/// 这些操作在后台线程中执行，通过 channel 把进度发回主循环；界面显示带转圈和计数的
+/// 进度对话框，按 Esc 取消（已完成的部分保留）。同一时间只运行一个后台任务。
+use crate::app::NotificationLevel;
+use crate::models::Project;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::Instant;
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// 后台任务的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Import,
+    BulkMove,
+    Archive,
+    Backup,
+    ReloadAll,
+}
+
+impl JobKind {
+    pub fn title(self) -> &'static str {
+        match self {
+            JobKind::Import => "导入任务",
+            JobKind::BulkMove => "批量移动",
+            JobKind::Archive => "归档任务",
+            JobKind::Backup => "备份项目",
+            JobKind::ReloadAll => "重新加载所有项目",
+        }
+    }
+}
+
+/// 后台任务的结果
+pub enum JobResult {
+    /// 操作完成：重新加载当前项目并显示通知
+    Done {
+        message: String,
+        level: NotificationLevel,
+    },
+    /// 所有项目已重新加载
+    Reloaded(Vec<Project>),
+}
+
+impl JobResult {
+    pub fn done(message: String, level: NotificationLevel) -> Self {
+        JobResult::Done { message, level }
+    }
+}
+
+enum JobEvent {
+    Progress(usize, usize),
+    Finished(JobResult),
+}
+
+/// 交给工作线程，用于报告进度和检查是否已取消
+pub struct JobHandle {
+    sender: Sender<JobEvent>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl JobHandle {
+    pub fn progress(&self, done: usize, total: usize) {
+        let _ = self.sender.send(JobEvent::Progress(done, total));
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// 正在执行的后台任务
+pub struct Job {
+    pub kind: JobKind,
+    pub done: usize,
+    /// 总数未知时为 0，只显示已用时间
+    pub total: usize,
+    pub started_at: Instant,
+    cancelled: Arc<AtomicBool>,
+    receiver: Receiver<JobEvent>,
+}
+
+impl Job {
+    pub fn spawn(
+        kind: JobKind,
+        total: usize,
+        work: impl FnOnce(&JobHandle) -> JobResult + Send + 'static,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let handle = JobHandle {
+            sender,
+            cancelled: Arc::clone(&cancelled),
+        };
+        std::thread::spawn(move || {
+            let result = work(&handle);
+            let _ = handle.sender.send(JobEvent::Finished(result));
+        });
+        Self {
+            kind,
+            done: 0,
+            total,
+            started_at: Instant::now(),
+            cancelled,
+            receiver,
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// 取出已到达的进度，任务结束时返回结果
+    pub fn poll(&mut self) -> Option<JobResult> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(JobEvent::Progress(done, total)) => {
+                    self.done = done;
+                    self.total = total;
+                }
+                Ok(JobEvent::Finished(result)) => return Some(result),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    return Some(JobResult::done(
+                        format!("{}意外中止", self.kind.title()),
+                        NotificationLevel::Error,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// 进度行，如 `⠹ 12/40` 或 `⠹ 3s`
+    pub fn progress_label(&self) -> String {
+        let elapsed = self.started_at.elapsed();
+        let frame = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+        if self.total > 0 {
+            format!("{} {}/{}", frame, self.done, self.total)
+        } else {
+            format!("{} {}s", frame, elapsed.as_secs())
+        }
+    }
+}
+
+/// 取消时的提示，如 “批量移动已取消：已完成 3/10”
+pub fn cancelled_message(kind: JobKind, done: usize, total: usize) -> String {
+    format!("{}已取消：已完成 {}/{}", kind.title(), done, total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn wait(job: &mut Job) -> JobResult {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(result) = job.poll() {
+                return result;
+            }
+            assert!(Instant::now() < deadline, "job did not finish");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn reports_progress_and_result() {
+        let mut job = Job::spawn(JobKind::Archive, 3, |handle| {
+            for i in 1..=3 {
+                handle.progress(i, 3);
+            }
+            JobResult::done("ok".to_string(), NotificationLevel::Success)
+        });
+        let JobResult::Done { message, .. } = wait(&mut job) else {
+            panic!("expected Done");
+        };
+        assert_eq!(message, "ok");
+        assert_eq!((job.done, job.total), (3, 3));
+        assert!(job.progress_label().ends_with(" 3/3"));
+    }
+
+    #[test]
+    fn worker_sees_cancellation() {
+        let (started_tx, started_rx) = mpsc::channel();
+        let mut job = Job::spawn(JobKind::BulkMove, 0, move |handle| {
+            started_tx.send(()).unwrap();
+            while !handle.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            JobResult::done(
+                cancelled_message(JobKind::BulkMove, 0, 2),
+                NotificationLevel::Warning,
+            )
+        });
+        started_rx.recv().unwrap();
+        job.cancel();
+        let JobResult::Done { message, level } = wait(&mut job) else {
+            panic!("expected Done");
+        };
+        assert_eq!(level, NotificationLevel::Warning);
+        assert_eq!(message, "批量移动已取消：已完成 0/2");
+    }
+}
@@ -1,125 +1,39 @@
 use anyhow::Result;
 use crossterm::{
+    cursor::MoveTo,
     event::{self, Event},
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode,
+    },
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::io;
+use std::io::{self, Write};
 
 mod app;
+mod build_info;
+mod changelog;
 mod cli;
 mod config;
 mod core;
 mod error;
+mod export;
 mod fs;
+mod import;
 mod input;
+mod job;
 mod mcp;
+mod metrics;
 mod models;
+mod report;
+mod safe_mode;
+mod script;
 mod state;
 mod ui;
+mod worker;
 
 use app::App;
 
-/// 从临时文件创建新任务
-fn handle_new_task_from_file(app: &mut App, temp_file_path: &str) -> Result<()> {
-    use crate::models::Task;
-    use crate::ui::layout::SplitNode;
-
-    // 读取临时文件内容
-    let content = std::fs::read_to_string(temp_file_path)?;
-
-    // 如果文件为空或只有空白，不创建任务
-    if content.trim().is_empty() {
-        return Ok(());
-    }
-
-    // 解析第一行作为任务标题
-    let title = content
-        .lines()
-        .next()
-        .unwrap_or("未命名任务")
-        .trim_start_matches('#')
-        .trim()
-        .to_string();
-
-    // 如果标题为空或是默认模板标题，不创建任务
-    if title.is_empty() || title == "任务标题" {
-        return Ok(());
-    }
-
-    // 获取当前项目名称
-    let project_name = if let Some(SplitNode::Leaf { project_id, .. }) =
-        app.split_tree.find_pane(app.focused_pane)
-    {
-        if let Some(name) = project_id {
-            name.clone()
-        } else {
-            anyhow::bail!("当前面板没有项目");
-        }
-    } else {
-        anyhow::bail!("找不到当前面板");
-    };
-
-    // 获取项目路径
-    let project_path = if let Some(project) = app.projects.iter().find(|p| p.name == project_name) {
-        project.path.clone()
-    } else {
-        anyhow::bail!("在项目列表中找不到项目");
-    };
-
-    // 获取下一个任务 ID
-    let next_id = crate::fs::get_next_task_id(&project_path).map_err(|e| anyhow::anyhow!(e))?;
-
-    // 获取当前选中的列作为初始状态
-    let column = app
-        .selected_column
-        .get(&app.focused_pane)
-        .copied()
-        .unwrap_or(0);
-    let status = app
-        .get_status_name_by_column(column)
-        .unwrap_or_else(|| "todo".to_string());
-
-    // 创建任务
-    let mut task = Task::new(next_id, title.clone(), status.clone());
-
-    // 保存完整的文件内容到任务文件
-    let task_dir = project_path.join(&status);
-    std::fs::create_dir_all(&task_dir)?;
-    let task_file = task_dir.join(format!("{:03}.md", next_id));
-    std::fs::write(&task_file, &content)?;
-
-    // 更新任务的文件路径
-    task.file_path = task_file;
-
-    // 重新加载项目以确保任务列表是最新的
-    match crate::fs::load_project(&project_path) {
-        Ok(updated_project) => {
-            if let Some(project) = app.projects.iter_mut().find(|p| p.name == project_name) {
-                *project = updated_project;
-
-                // 找到新任务在当前列的索引（应该是最后一个）
-                let new_task_idx = project
-                    .tasks
-                    .iter()
-                    .filter(|t| t.status == status)
-                    .count()
-                    .saturating_sub(1);
-
-                // 自动选中新创建的任务
-                app.selected_task_index
-                    .insert(app.focused_pane, new_task_idx);
-            }
-        }
-        Err(e) => {
-            anyhow::bail!("重新加载项目失败: {}", e);
-        }
-    }
-
-    Ok(())
-}
-
 fn main() -> Result<()> {
     // 处理 CLI 命令
     let should_run_tui = cli::handle_cli()?;
@@ -143,10 +57,18 @@ fn main() -> Result<()> {
     // 创建应用
     let mut app = App::new()?;
 
+    // 保存原终端标题，退出时恢复
+    if app.config.terminal_title {
+        write!(terminal.backend_mut(), "{}", PUSH_TITLE)?;
+    }
+
     // 运行应用
     let res = run_app(&mut terminal, &mut app);
 
     // 恢复终端
+    if app.config.terminal_title {
+        write!(terminal.backend_mut(), "{}", POP_TITLE)?;
+    }
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
@@ -158,6 +80,11 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// 把当前终端标题压入标题栈（xterm 扩展，不支持的终端会忽略）
+const PUSH_TITLE: &str = "\x1b[22;0t";
+/// 从标题栈恢复原标题
+const POP_TITLE: &str = "\x1b[23;0t";
+
 /// 暂停终端（用于调用外部编辑器）
 pub fn suspend_terminal<B>(terminal: &mut Terminal<B>) -> Result<()>
 where
@@ -183,11 +110,27 @@ fn run_app<B>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()>
 where
     B: ratatui::backend::Backend + std::io::Write,
 {
+    // 已设置的终端标题，内容变化时才重新发送
+    let mut shown_title: Option<String> = None;
+
     loop {
-        // 清除过期的通知
+        // 清除过期的通知，收取后台任务的进度
         app.clear_expired_notification();
+        app.poll_job();
+        app.poll_io();
+        app.advance_tutor();
+        app.check_idle_lock();
 
         terminal.draw(|f| ui::render(f, app))?;
+        draw_preview_images(terminal, app)?;
+
+        if app.config.terminal_title && !safe_mode::is_enabled() {
+            let title = app.window_title();
+            if shown_title.as_ref() != Some(&title) {
+                execute!(terminal.backend_mut(), SetTitle(&title))?;
+                shown_title = Some(title);
+            }
+        }
 
         // 检查是否需要打开外部编辑器
         if let Some(file_path) = app.pending_editor_file.take() {
@@ -197,7 +140,8 @@ where
             suspend_terminal(terminal)?;
 
             // 调用外部编辑器
-            if let Err(e) = open_external_editor(&file_path, &app.config.editor) {
+            let edited = open_external_editor(&file_path, &app.config.editor);
+            if let Err(e) = &edited {
                 app.show_notification(
                     format!("打开编辑器失败: {}", e),
                     app::NotificationLevel::Error,
@@ -205,12 +149,21 @@ where
             }
 
             resume_terminal(terminal)?;
+            // 重新进入备用屏幕后之前绘制的图片已不存在，编辑器也可能改过标题
+            app.shown_images.clear();
+            shown_title = None;
 
             if is_new_task {
-                // 处理新任务创建
-                match handle_new_task_from_file(app, &file_path) {
-                    Ok(_) => {
-                        // 只在成功时删除临时文件
+                // 编辑器异常退出（如 Vim 的 :cq）时不创建任务
+                let created = match edited {
+                    Ok(()) => {
+                        input::create_task_from_editor_file(app, std::path::Path::new(&file_path))
+                    }
+                    Err(_) => Ok(()),
+                };
+                match created {
+                    Ok(()) => {
+                        // 只在成功或取消时删除临时文件
                         let _ = std::fs::remove_file(&file_path);
                     }
                     Err(e) => {
@@ -222,13 +175,8 @@ where
                     }
                 }
             } else {
-                // 编辑现有任务：直接编辑的项目文件，重新加载项目即可
-                if let Err(e) = app.reload_current_project() {
-                    app.show_notification(
-                        format!("重新加载项目失败: {}", e),
-                        app::NotificationLevel::Error,
-                    );
-                }
+                // 编辑现有任务：直接编辑的项目文件，在后台重新加载项目并记录外部修改
+                app.request_reload_current();
             }
         }
 
@@ -245,13 +193,41 @@ where
             }
 
             resume_terminal(terminal)?;
+            // 重新进入备用屏幕后之前绘制的图片已不存在，编辑器也可能改过标题
+            app.shown_images.clear();
+            shown_title = None;
+        }
+
+        // 检查是否需要在项目目录中打开 shell
+        if let Some(dir) = app.pending_shell_dir.take() {
+            suspend_terminal(terminal)?;
+
+            let shell = config::shell_command();
+            println!("{} — 退出 shell 返回看板", dir.display());
+            if let Err(e) = std::process::Command::new(&shell)
+                .current_dir(&dir)
+                .status()
+            {
+                app.show_notification(
+                    format!("启动 {} 失败: {}", shell, e),
+                    app::NotificationLevel::Error,
+                );
+            }
+
+            resume_terminal(terminal)?;
+            app.shown_images.clear();
+            shown_title = None;
+
+            // 在 shell 中可能直接改了任务文件
+            app.request_reload_current();
         }
 
         if event::poll(std::time::Duration::from_millis(100))? {
             if let Event::Key(key) = event::read()?
                 && !app.handle_key(key)
             {
-                // 退出前保存状态
+                // 退出前等待后台写入完成，再同步保存最终状态
+                app.io.shutdown();
                 let state = state::extract_state(app);
                 if let Err(e) = state::save_state(&state) {
                     eprintln!("保存状态失败: {}", e);
@@ -261,7 +237,44 @@ where
         } else {
             input::flush_pending_key_sequence(app);
         }
+
+        app.autosave_state();
+    }
+}
+
+/// 在预览弹窗预留的位置绘制图片（终端图形协议）
+fn draw_preview_images<B>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()>
+where
+    B: ratatui::backend::Backend + std::io::Write,
+{
+    let Some(protocol) = app.image_protocol else {
+        return Ok(());
+    };
+    if app.preview_images == app.shown_images {
+        return Ok(());
+    }
+
+    // 先清除上一次绘制的图片，不支持删除的协议通过重绘整个屏幕覆盖
+    if !app.shown_images.is_empty() {
+        match ui::image::clear_sequence(protocol) {
+            Some(sequence) => write!(terminal.backend_mut(), "{}", sequence)?,
+            None => {
+                terminal.clear()?;
+                terminal.draw(|f| ui::render(f, app))?;
+            }
+        }
     }
+
+    for placement in &app.preview_images {
+        if let Some(sequence) = ui::image::escape_sequence(protocol, placement) {
+            let backend = terminal.backend_mut();
+            execute!(backend, MoveTo(placement.area.x, placement.area.y))?;
+            write!(backend, "{}", sequence)?;
+        }
+    }
+    Write::flush(terminal.backend_mut())?;
+    app.shown_images = app.preview_images.clone();
+    Ok(())
 }
 
 /// 调用外部编辑器打开文件
@@ -278,7 +291,7 @@ fn open_external_editor(file_path: &str, editor_cmd: &str) -> Result<()> {
     for arg in args {
         cmd.arg(arg);
     }
-    cmd.arg(file_path);
+    cmd.arg(editor_target(file_path, editor_cmd));
 
     let status = cmd.status()?;
 
@@ -289,31 +302,47 @@ fn open_external_editor(file_path: &str, editor_cmd: &str) -> Result<()> {
     Ok(())
 }
 
+/// 传给编辑器的文件参数：Helix 使用 `文件:行:列` 把光标定位到任务标题
+fn editor_target(file_path: &str, editor_cmd: &str) -> String {
+    if !config::is_helix(editor_cmd) {
+        return file_path.to_string();
+    }
+    std::fs::read_to_string(file_path)
+        .ok()
+        .and_then(|content| fs::parser::title_line(&content))
+        // 列号跳过 "# "，光标落在标题文字上
+        .map(|line| format!("{}:{}:3", file_path, line))
+        .unwrap_or_else(|| file_path.to_string())
+}
+
 /// 调用外部预览工具查看文件
+///
+/// 预览器未设置、找不到或运行失败时改用 `$PAGER` 查看，保证最小化系统上也能预览。
 fn open_external_previewer(file_path: &str, viewer_cmd: &str) -> Result<()> {
-    // 解析预览器命令（可能包含参数）
-    let parts: Vec<&str> = viewer_cmd.split_whitespace().collect();
-    let (viewer, args) = if parts.is_empty() {
-        ("cat", vec![])
-    } else {
-        (parts[0], parts[1..].to_vec())
-    };
-
-    let mut cmd = std::process::Command::new(viewer);
-    for arg in args {
-        cmd.arg(arg);
+    if config::command_available(viewer_cmd) && run_with_file(viewer_cmd, file_path).is_ok() {
+        // 等待用户按任意键继续
+        println!("\n按任意键返回...");
+        std::io::stdin().read_line(&mut String::new())?;
+        return Ok(());
     }
-    cmd.arg(file_path);
 
-    let status = cmd.status()?;
+    // 分页器自身可交互，退出后直接返回界面
+    run_with_file(&config::pager_command(), file_path)
+}
+
+/// 运行带参数的命令并把文件路径作为最后一个参数
+fn run_with_file(cmd_line: &str, file_path: &str) -> Result<()> {
+    let parts: Vec<&str> = cmd_line.split_whitespace().collect();
+    let Some((program, args)) = parts.split_first() else {
+        anyhow::bail!("命令为空");
+    };
 
+    let status = std::process::Command::new(program)
+        .args(args)
+        .arg(file_path)
+        .status()?;
     if !status.success() {
-        anyhow::bail!("预览工具退出异常: {}", status);
+        anyhow::bail!("{} 退出异常: {}", program, status);
     }
-
-    // 等待用户按任意键继续
-    println!("\n按任意键返回...");
-    std::io::stdin().read_line(&mut String::new())?;
-
     Ok(())
 }
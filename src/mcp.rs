@@ -3,6 +3,7 @@ use serde_json::{Value, json};
 use std::io::{self, BufRead, Write};
 
 use crate::fs;
+use crate::fs::project::NewTaskDefaults;
 use crate::models::{ProjectType, Task};
 
 // MCP Protocol Types
@@ -211,20 +212,23 @@ fn handle_tool_call(name: &str, arguments: &Value) -> Result<Value, String> {
             let project_name = arguments["project"]
                 .as_str()
                 .ok_or("Missing project parameter")?;
-            let status_filter = arguments["status"].as_str();
+            let text_arg = |key: &str| arguments[key].as_str().map(str::to_string);
+            let sort = match arguments["sort"].as_str() {
+                None => fs::index::TaskSort::default(),
+                Some(name) => fs::index::TaskSort::parse(name)
+                    .ok_or_else(|| format!("Unknown sort '{}'", name))?,
+            };
+            let query = fs::index::TaskQuery {
+                status: text_arg("status"),
+                priority: text_arg("priority"),
+                tag: text_arg("tag"),
+                text: text_arg("search"),
+                sort,
+            };
 
             let project_path = find_project_path(project_name)?;
-            let project = fs::load_project(&project_path)?;
-
-            let tasks: Vec<&Task> = if let Some(status) = status_filter {
-                project
-                    .tasks
-                    .iter()
-                    .filter(|t| t.status == status)
-                    .collect()
-            } else {
-                project.tasks.iter().collect()
-            };
+            // 建立了索引的项目只读取索引
+            let tasks = fs::index::query(&project_path, &query)?;
 
             let task_infos: Vec<TaskInfo> = tasks
                 .iter()
@@ -283,15 +287,19 @@ fn handle_tool_call(name: &str, arguments: &Value) -> Result<Value, String> {
             let title = arguments["title"]
                 .as_str()
                 .ok_or("Missing title parameter")?;
-            let status = arguments["status"].as_str().unwrap_or("todo");
-
             let project_path = find_project_path(project_name)?;
+            let defaults = new_task_defaults(&project_path);
+            let status = arguments["status"]
+                .as_str()
+                .or(defaults.status.as_deref())
+                .unwrap_or("todo");
             let next_id = fs::get_next_task_id(&project_path)?;
             let max_order = fs::get_max_order_in_status(&project_path, status)?;
             let new_order = max_order + 1000;
 
             let mut task = Task::new(next_id, title.to_string(), status.to_string());
             task.order = new_order;
+            task.priority = defaults.priority;
 
             if let Some(priority) = arguments["priority"].as_str() {
                 task.priority = Some(priority.to_string());
@@ -346,7 +354,7 @@ fn handle_tool_call(name: &str, arguments: &Value) -> Result<Value, String> {
             }
 
             if let Some(content) = arguments["content"].as_str() {
-                task.content = content.to_string();
+                task.set_content(content.to_string());
             }
 
             fs::save_task(&project_path, task)?;
@@ -417,11 +425,16 @@ fn handle_tool_call(name: &str, arguments: &Value) -> Result<Value, String> {
             let is_local = arguments["local"].as_bool().unwrap_or(false);
             let project_order = parse_optional_project_order(arguments)?;
             let tags = parse_optional_tags(arguments)?;
+            let workflow = match arguments["workflow"].as_str() {
+                Some(name) => fs::Workflow::from_name(name)
+                    .ok_or_else(|| format!("Unknown workflow: {} (expected basic or dev)", name))?,
+                None => fs::Workflow::Basic,
+            };
 
             let path = if is_local {
-                fs::create_local_project(name)?
+                fs::create_local_project(name, workflow)?
             } else {
-                fs::create_project(name)?
+                fs::create_project(name, workflow)?
             };
 
             if project_order.is_some() || !tags.is_empty() {
@@ -487,14 +500,18 @@ fn handle_tool_call(name: &str, arguments: &Value) -> Result<Value, String> {
             let tasks = arguments["tasks"].as_array().ok_or("Missing tasks array")?;
 
             let project_path = find_project_path(project_name)?;
+            let defaults = new_task_defaults(&project_path);
             let mut results = Vec::new();
             let mut errors = Vec::new();
 
             for task_data in tasks {
                 let title = task_data["title"].as_str().unwrap_or("");
-                let status = task_data["status"].as_str().unwrap_or("todo");
+                let status = task_data["status"]
+                    .as_str()
+                    .or(defaults.status.as_deref())
+                    .unwrap_or("todo");
 
-                match create_single_task(&project_path, title, status, task_data) {
+                match create_single_task(&project_path, title, status, &defaults, task_data) {
                     Ok(task_id) => results.push(json!({
                         "title": title,
                         "status": "created",
@@ -566,6 +583,7 @@ fn handle_tool_call(name: &str, arguments: &Value) -> Result<Value, String> {
             fs::status::validate_display_name(new_display)?;
 
             // 重命名状态
+            fs::backup::before(&project_path, "rename-status")?;
             fs::status::rename_status(&project_path, old_name, new_name, new_display)?;
 
             Ok(json!({
@@ -609,6 +627,7 @@ fn handle_tool_call(name: &str, arguments: &Value) -> Result<Value, String> {
             let project_path = find_project_path(project_name)?;
 
             // 删除状态
+            fs::backup::before(&project_path, "delete-status")?;
             fs::status::delete_status(&project_path, status_name, move_to)?;
 
             let message = if let Some(target) = move_to {
@@ -660,6 +679,7 @@ fn create_single_task(
     project_path: &std::path::Path,
     title: &str,
     status: &str,
+    defaults: &NewTaskDefaults,
     task_data: &Value,
 ) -> Result<u32, String> {
     let next_id = fs::get_next_task_id(project_path)?;
@@ -668,6 +688,7 @@ fn create_single_task(
 
     let mut task = Task::new(next_id, title.to_string(), status.to_string());
     task.order = new_order;
+    task.priority = defaults.priority.clone();
 
     if let Some(priority) = task_data["priority"].as_str() {
         task.priority = Some(priority.to_string());
@@ -682,6 +703,12 @@ fn create_single_task(
     Ok(task.id)
 }
 
+/// 项目和全局配置中的新任务默认状态、优先级（调用方未指定时使用）
+fn new_task_defaults(project_path: &std::path::Path) -> NewTaskDefaults {
+    let config = crate::config::load_config().unwrap_or_default();
+    NewTaskDefaults::load(project_path, &config)
+}
+
 fn find_project_path(project_name: &str) -> Result<std::path::PathBuf, String> {
     let projects = fs::load_all_projects().map_err(|e| e.to_string())?;
     projects
@@ -703,7 +730,7 @@ fn get_tools() -> Vec<Tool> {
         },
         Tool {
             name: "helix-kanban_list_tasks".to_string(),
-            description: "List all tasks in a specific project. Returns structured JSON with task details (ID, title, status, priority, tags).".to_string(),
+            description: "List tasks in a specific project, optionally filtered, searched and sorted. Returns structured JSON with task details (ID, title, status, priority, tags).".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -714,6 +741,23 @@ fn get_tools() -> Vec<Tool> {
                     "status": {
                         "type": "string",
                         "description": "Optional: Filter by status (e.g., 'todo', 'doing', 'done')"
+                    },
+                    "priority": {
+                        "type": "string",
+                        "description": "Optional: Filter by priority (high, medium, low)"
+                    },
+                    "tag": {
+                        "type": "string",
+                        "description": "Optional: Only tasks with this tag"
+                    },
+                    "search": {
+                        "type": "string",
+                        "description": "Optional: Case-insensitive text to find in titles and tags"
+                    },
+                    "sort": {
+                        "type": "string",
+                        "enum": ["board", "id", "priority", "created", "title"],
+                        "description": "Optional: Sort order (default: board column order)"
                     }
                 },
                 "required": ["project"]
@@ -753,8 +797,7 @@ fn get_tools() -> Vec<Tool> {
                     },
                     "status": {
                         "type": "string",
-                        "description": "Task status (e.g., 'todo', 'doing', 'done')",
-                        "default": "todo"
+                        "description": "Task status (e.g., 'todo', 'doing', 'done'). Defaults to the configured default_status, or 'todo'"
                     },
                     "priority": {
                         "type": "string",
@@ -866,6 +909,12 @@ fn get_tools() -> Vec<Tool> {
                     "tags": {
                         "type": "string",
                         "description": "Optional comma-separated project tags"
+                    },
+                    "workflow": {
+                        "type": "string",
+                        "enum": ["basic", "dev"],
+                        "description": "Default statuses: basic (todo/doing/done) or dev (backlog/todo/doing/review/blocked/done)",
+                        "default": "basic"
                     }
                 },
                 "required": ["name"]
@@ -926,8 +975,7 @@ fn get_tools() -> Vec<Tool> {
                                 "title": { "type": "string", "description": "Task title" },
                                 "status": {
                                     "type": "string",
-                                    "description": "Task status",
-                                    "default": "todo"
+                                    "description": "Task status. Defaults to the configured default_status, or 'todo'"
                                 },
                                 "priority": {
                                     "type": "string",
@@ -0,0 +1,240 @@
+/// 看板指标（`hxk metrics`）：供 Grafana、tmux 状态栏等外部工具使用
+///
+/// 每个项目输出各状态的任务数、逾期任务数（`due` 字段早于今天且不在完成列）
+/// 和最近 7 天的吞吐量（变更日志中进入完成列的不同任务数）。
+use crate::fs::journal::{JournalAction, JournalEntry};
+use crate::models::{Project, ProjectType, Task};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// 吞吐量统计的天数
+pub const THROUGHPUT_DAYS: i64 = 7;
+
+/// 单个状态的任务数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusCount {
+    pub status: String,
+    pub display: String,
+    pub count: usize,
+}
+
+/// 单个项目的指标
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProjectMetrics {
+    pub project: String,
+    #[serde(rename = "type")]
+    pub project_type: &'static str,
+    pub total: usize,
+    pub statuses: Vec<StatusCount>,
+    pub overdue: usize,
+    pub throughput_7d: usize,
+}
+
+/// 任务的截止日期（`due` 字段，`YYYY-MM-DD` 字符串或 TOML 日期）
+pub fn due_date(task: &Task) -> Option<NaiveDate> {
+    let text = match task.extra.get("due")? {
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Datetime(d) => d.to_string(),
+        _ => return None,
+    };
+    NaiveDate::parse_from_str(text.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// 计算项目的指标；`journal` 是该项目的变更日志，`now` 决定"今天"和吞吐量窗口
+pub fn collect(
+    project: &Project,
+    journal: &[JournalEntry],
+    now: DateTime<FixedOffset>,
+) -> ProjectMetrics {
+    let done_statuses: Vec<&str> = (0..project.statuses.len())
+        .filter(|&col| project.is_done_column(col))
+        .map(|col| project.statuses[col].name.as_str())
+        .collect();
+
+    let statuses = project
+        .statuses
+        .iter()
+        .map(|status| StatusCount {
+            status: status.name.clone(),
+            display: status.display.clone(),
+            count: project
+                .tasks
+                .iter()
+                .filter(|t| t.status == status.name)
+                .count(),
+        })
+        .collect();
+
+    let today = now.date_naive();
+    let overdue = project
+        .tasks
+        .iter()
+        .filter(|t| !done_statuses.contains(&t.status.as_str()))
+        .filter(|t| due_date(t).is_some_and(|due| due < today))
+        .count();
+
+    // 同一任务在窗口内多次进入完成列只计一次
+    let since = now - Duration::days(THROUGHPUT_DAYS);
+    let finished: HashSet<u32> = journal
+        .iter()
+        .filter(|e| e.is_for(&project.id, &project.name))
+        .filter(|e| matches!(e.action, JournalAction::Move | JournalAction::Create))
+        .filter(|e| {
+            e.to.as_deref()
+                .is_some_and(|to| done_statuses.contains(&to))
+        })
+        .filter(|e| DateTime::parse_from_rfc3339(&e.ts).is_ok_and(|ts| ts >= since && ts <= now))
+        .map(|e| e.task_id)
+        .collect();
+
+    ProjectMetrics {
+        project: project.name.clone(),
+        project_type: match project.project_type {
+            ProjectType::Global => "global",
+            ProjectType::Local => "local",
+        },
+        total: project.tasks.len(),
+        statuses,
+        overdue,
+        throughput_7d: finished.len(),
+    }
+}
+
+/// Prometheus 标签值转义
+fn label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Prometheus 文本格式（可由 node_exporter 的 textfile collector 读取）
+pub fn to_prometheus(metrics: &[ProjectMetrics]) -> String {
+    let mut out = String::new();
+    let mut gauge = |name: &str, help: &str, rows: Vec<(String, usize)>| {
+        out.push_str(&format!(
+            "# HELP {} {}\n# TYPE {} gauge\n",
+            name, help, name
+        ));
+        for (labels, value) in rows {
+            out.push_str(&format!("{}{{{}}} {}\n", name, labels, value));
+        }
+    };
+    let project_label = |m: &ProjectMetrics| {
+        format!(
+            "project=\"{}\",type=\"{}\"",
+            label(&m.project),
+            m.project_type
+        )
+    };
+
+    gauge(
+        "hxk_tasks",
+        "Number of tasks per status.",
+        metrics
+            .iter()
+            .flat_map(|m| {
+                m.statuses.iter().map(move |s| {
+                    (
+                        format!("{},status=\"{}\"", project_label(m), label(&s.status)),
+                        s.count,
+                    )
+                })
+            })
+            .collect(),
+    );
+    gauge(
+        "hxk_tasks_overdue",
+        "Tasks outside the done column whose due date has passed.",
+        metrics
+            .iter()
+            .map(|m| (project_label(m), m.overdue))
+            .collect(),
+    );
+    gauge(
+        "hxk_throughput_7d",
+        "Distinct tasks moved into the done column in the last 7 days.",
+        metrics
+            .iter()
+            .map(|m| (project_label(m), m.throughput_7d))
+            .collect(),
+    );
+    out
+}
+
+/// JSON 格式：`{"generated_at": ..., "projects": [...]}`
+pub fn to_json(metrics: &[ProjectMetrics], now: DateTime<FixedOffset>) -> String {
+    let value = serde_json::json!({
+        "generated_at": now.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        "projects": metrics,
+    });
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Status;
+    use std::path::PathBuf;
+
+    fn entry(ts: &str, task_id: u32, to: &str) -> JournalEntry {
+        JournalEntry {
+            ts: ts.to_string(),
+            actor: "cli".to_string(),
+            project: "web".to_string(),
+            project_id: String::new(),
+            action: JournalAction::Move,
+            task_id,
+            title: String::new(),
+            from: Some("todo".to_string()),
+            to: Some(to.to_string()),
+        }
+    }
+
+    #[test]
+    fn collects_counts_overdue_and_weekly_throughput() {
+        let mut project = Project::new(
+            "web".to_string(),
+            PathBuf::from("/tmp/web"),
+            ProjectType::Global,
+        );
+        project.statuses = ["todo", "done"]
+            .iter()
+            .map(|s| Status::new(s.to_string(), s.to_uppercase()))
+            .collect();
+        for (id, status, due) in [
+            (1, "todo", Some("2026-03-01")),
+            (2, "todo", Some("2026-03-20")),
+            (3, "done", Some("2026-03-01")),
+            (4, "todo", None),
+        ] {
+            let mut task = Task::new(id, format!("Task {}", id), status.to_string());
+            if let Some(due) = due {
+                task.extra.insert("due".to_string(), due.into());
+            }
+            project.tasks.push(task);
+        }
+        let journal = vec![
+            entry("2026-03-09T10:00:00+08:00", 3, "done"),
+            entry("2026-03-09T11:00:00+08:00", 3, "done"),
+            entry("2026-03-08T10:00:00+08:00", 5, "done"),
+            entry("2026-03-09T12:00:00+08:00", 4, "todo"),
+            // 超出 7 天窗口
+            entry("2026-02-20T10:00:00+08:00", 6, "done"),
+        ];
+        let now = DateTime::parse_from_rfc3339("2026-03-10T09:00:00+08:00").unwrap();
+
+        let metrics = collect(&project, &journal, now);
+        assert_eq!(metrics.total, 4);
+        assert_eq!(metrics.statuses[0].count, 3);
+        assert_eq!(metrics.overdue, 1);
+        assert_eq!(metrics.throughput_7d, 2);
+
+        let text = to_prometheus(&[metrics]);
+        assert!(text.contains("hxk_tasks{project=\"web\",type=\"global\",status=\"todo\"} 3\n"));
+        assert!(text.contains("hxk_tasks_overdue{project=\"web\",type=\"global\"} 1\n"));
+        assert!(text.contains("# TYPE hxk_throughput_7d gauge\n"));
+        assert_eq!(label("a\"b\\c"), "a\\\"b\\\\c");
+    }
+}
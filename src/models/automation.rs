@@ -0,0 +1,197 @@
+use crate::models::Task;
+use serde::{Deserialize, Serialize};
+
+/// 进入状态时自动修改任务（`.kanban.toml` 中的 `[[automations]]`）
+///
+/// ```toml
+/// [[automations]]
+/// on_enter = "done"
+/// set = { completed = "{now}" }
+/// clear = ["due"]
+/// ```
+///
+/// 任务移动到 `on_enter` 状态时先删除 `clear` 中的字段，再写入 `set` 中的字段。
+/// 字符串值中的 `{now}`、`{today}`、`{user.name}` 在移动时替换。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Automation {
+    /// 触发规则的目标状态
+    pub on_enter: String,
+    /// 写入的字段（`priority` 设置优先级，`tags` 添加标签，其他写入 frontmatter）
+    #[serde(default, skip_serializing_if = "toml::Table::is_empty")]
+    pub set: toml::Table,
+    /// 删除的字段（`tags` 清空全部标签）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clear: Vec<String>,
+}
+
+/// 规则不能修改的任务元数据
+const RESERVED_KEYS: [&str; 5] = ["id", "order", "title", "status", "created"];
+
+const USER_NAME: &str = "{user.name}";
+
+/// 规则值中占位符的取值
+#[derive(Debug, Clone)]
+pub struct Placeholders {
+    pub now: chrono::DateTime<chrono::Local>,
+    /// 未配置用户名时，引用 `{user.name}` 的字段保持不变
+    pub user_name: Option<String>,
+}
+
+impl Placeholders {
+    /// 替换占位符，需要的值缺失时返回 None
+    fn expand(&self, text: &str) -> Option<String> {
+        let mut text = text
+            .replace(
+                "{now}",
+                &self.now.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            )
+            .replace("{today}", &self.now.format("%Y-%m-%d").to_string());
+        if text.contains(USER_NAME) {
+            text = text.replace(USER_NAME, self.user_name.as_deref()?);
+        }
+        Some(text)
+    }
+}
+
+impl Automation {
+    /// 规则是否引用了 `{user.name}`（只有这时才需要查询用户名）
+    pub fn needs_user_name(&self) -> bool {
+        fn mentions(value: &toml::Value) -> bool {
+            match value {
+                toml::Value::String(s) => s.contains(USER_NAME),
+                toml::Value::Array(items) => items.iter().any(mentions),
+                _ => false,
+            }
+        }
+        self.set.values().any(mentions)
+    }
+
+    /// 应用到任务，返回是否有变化
+    pub fn apply(&self, task: &mut Task, values: &Placeholders) -> bool {
+        let mut changed = false;
+        for key in &self.clear {
+            changed |= match key.as_str() {
+                "priority" => task.priority.take().is_some(),
+                "tags" => !std::mem::take(&mut task.tags).is_empty(),
+                key if RESERVED_KEYS.contains(&key) => false,
+                key => task.extra.remove(key).is_some(),
+            };
+        }
+
+        for (key, value) in &self.set {
+            if RESERVED_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            let Some(value) = expand_value(value, values) else {
+                continue;
+            };
+            changed |= match (key.as_str(), value) {
+                ("priority", toml::Value::String(priority)) => {
+                    task.priority.replace(priority.clone()) != Some(priority)
+                }
+                ("priority", _) => false,
+                ("tags", value) => {
+                    let tags: Vec<String> = match value {
+                        toml::Value::String(tag) => vec![tag],
+                        toml::Value::Array(items) => items
+                            .into_iter()
+                            .filter_map(|item| item.as_str().map(str::to_string))
+                            .collect(),
+                        _ => Vec::new(),
+                    };
+                    crate::models::task::TagEdit {
+                        add: tags,
+                        remove: Vec::new(),
+                    }
+                    .apply(&mut task.tags)
+                }
+                (key, value) => task.extra.insert(key.to_string(), value.clone()) != Some(value),
+            };
+        }
+        changed
+    }
+}
+
+fn expand_value(value: &toml::Value, values: &Placeholders) -> Option<toml::Value> {
+    match value {
+        toml::Value::String(s) => values.expand(s).map(toml::Value::String),
+        toml::Value::Array(items) => items
+            .iter()
+            .map(|item| expand_value(item, values))
+            .collect::<Option<Vec<_>>>()
+            .map(toml::Value::Array),
+        other => Some(other.clone()),
+    }
+}
+
+/// 任务进入当前状态后运行匹配的规则，返回是否修改了任务
+pub fn run_on_enter(rules: &[Automation], task: &mut Task, values: &Placeholders) -> bool {
+    let status = task.status.clone();
+    let mut changed = false;
+    for rule in rules.iter().filter(|rule| rule.on_enter == status) {
+        changed |= rule.apply(task, values);
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn rules() -> Vec<Automation> {
+        toml::from_str::<toml::Table>(
+            r#"
+[[automations]]
+on_enter = "done"
+set = { completed = "{now}", priority = "low" }
+clear = ["due", "id"]
+
+[[automations]]
+on_enter = "doing"
+set = { assignee = "{user.name}", started = "{today}", tags = ["active"] }
+"#,
+        )
+        .unwrap()["automations"]
+            .clone()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn rules_run_for_the_entered_status_only() {
+        let rules = rules();
+        let mut values = Placeholders {
+            now: chrono::Local
+                .with_ymd_and_hms(2026, 3, 1, 9, 30, 0)
+                .unwrap(),
+            user_name: None,
+        };
+        let mut task = Task::new(7, "ship".to_string(), "done".to_string());
+        task.extra.insert("due".to_string(), "2026-03-05".into());
+
+        assert!(run_on_enter(&rules, &mut task, &values));
+        assert_eq!(task.id, 7);
+        assert!(!task.extra.contains_key("due"));
+        assert_eq!(task.priority.as_deref(), Some("low"));
+        assert!(
+            task.extra["completed"]
+                .as_str()
+                .unwrap()
+                .starts_with("2026-03-01T09:30:00")
+        );
+        assert!(!run_on_enter(&rules, &mut task, &values));
+
+        // 没有用户名时跳过引用 {user.name} 的字段
+        task.status = "doing".to_string();
+        assert!(rules[1].needs_user_name());
+        assert!(run_on_enter(&rules, &mut task, &values));
+        assert!(!task.extra.contains_key("assignee"));
+        assert_eq!(task.extra["started"].as_str(), Some("2026-03-01"));
+        assert_eq!(task.tags, vec!["active".to_string()]);
+
+        values.user_name = Some("Ada".to_string());
+        assert!(run_on_enter(&rules, &mut task, &values));
+        assert_eq!(task.extra["assignee"].as_str(), Some("Ada"));
+    }
+}
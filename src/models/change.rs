@@ -0,0 +1,109 @@
+use super::{Project, Task};
+use std::path::PathBuf;
+
+/// 外部修改（编辑器、AI 助手直接改文件）前后的一个任务
+///
+/// `before` 为 None 表示外部新增的任务，`after` 为 None 表示被外部删除的任务。
+#[derive(Debug, Clone)]
+pub struct TaskChange {
+    pub project_name: String,
+    pub project_path: PathBuf,
+    pub before: Option<Task>,
+    pub after: Option<Task>,
+}
+
+impl TaskChange {
+    /// 变更摘要，如 "~ web #3 修复登录（todo → doing）"
+    pub fn summary(&self) -> String {
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) if before.status != after.status => format!(
+                "~ {} #{} {}（{} → {}）",
+                self.project_name, before.id, before.title, before.status, after.status
+            ),
+            (Some(before), Some(_)) => {
+                format!("~ {} #{} {}", self.project_name, before.id, before.title)
+            }
+            (Some(before), None) => {
+                format!("- {} #{} {}", self.project_name, before.id, before.title)
+            }
+            (None, Some(after)) => {
+                format!("+ {} #{} {}", self.project_name, after.id, after.title)
+            }
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// 比较同一项目重新加载前后的任务（按任务 ID 对应）
+pub fn diff_project_tasks(before: &Project, after: &Project) -> Vec<TaskChange> {
+    let change = |old: Option<&Task>, new: Option<&Task>| TaskChange {
+        project_name: before.name.clone(),
+        project_path: before.path.clone(),
+        before: old.cloned(),
+        after: new.cloned(),
+    };
+
+    let mut changes = Vec::new();
+    for old in &before.tasks {
+        match after.tasks.iter().find(|t| t.id == old.id) {
+            Some(new) if same_task(old, new) => {}
+            new => changes.push(change(Some(old), new)),
+        }
+    }
+    for new in &after.tasks {
+        if !before.tasks.iter().any(|t| t.id == new.id) {
+            changes.push(change(None, Some(new)));
+        }
+    }
+    changes
+}
+
+/// 两个版本的任务内容是否一致（忽略文件路径和缓存的统计）
+pub fn same_task(a: &Task, b: &Task) -> bool {
+    a.title == b.title
+        && a.content == b.content
+        && a.status == b.status
+        && a.order == b.order
+        && a.priority == b.priority
+        && a.tags == b.tags
+        && a.extra == b.extra
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectType;
+
+    #[test]
+    fn diff_reports_edited_removed_and_added_tasks() {
+        let mut before = Project::new(
+            "web".to_string(),
+            PathBuf::from("/tmp/web"),
+            ProjectType::Global,
+        );
+        for id in 1..=3 {
+            before
+                .tasks
+                .push(Task::new(id, format!("task {}", id), "todo".to_string()));
+        }
+        let mut after = before.clone();
+        after.tasks[0].status = "doing".to_string();
+        after.tasks.remove(1);
+        after
+            .tasks
+            .push(Task::new(4, "from agent".to_string(), "todo".to_string()));
+
+        let summaries: Vec<String> = diff_project_tasks(&before, &after)
+            .iter()
+            .map(TaskChange::summary)
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "~ web #1 task 1（todo → doing）",
+                "- web #2 task 2",
+                "+ web #4 from agent"
+            ]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+/// 内置的估算点数字段，未在 `[[fields]]` 中声明时也可编辑，并在列底部汇总
+pub const ESTIMATE_FIELD: &str = "estimate";
+
+/// 卡片颜色字段，可选值见 `ui::theme::CARD_COLORS`
+pub const COLOR_FIELD: &str = "color";
+
+/// 对应任务的 ID 字段（另一个看板中的同一任务），`:diff-panes` 优先按它配对
+pub const LINK_FIELD: &str = "link";
+
+/// 项目自定义的卡片字段（`.kanban.toml` 中的 `[[fields]]`）
+///
+/// 字段值保存在任务 frontmatter 中，与 `priority`、`tags` 同级。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldDef {
+    /// frontmatter 中的键名
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: FieldKind,
+    /// 显示名，缺省使用键名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+/// 字段类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldKind {
+    #[default]
+    Text,
+    Number,
+    Bool,
+    /// `YYYY-MM-DD`
+    Date,
+}
+
+impl FieldKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            FieldKind::Text => "文本",
+            FieldKind::Number => "数字",
+            FieldKind::Bool => "是/否",
+            FieldKind::Date => "日期",
+        }
+    }
+}
+
+impl FieldDef {
+    /// 内置估算字段的默认定义
+    pub fn estimate() -> Self {
+        Self {
+            name: ESTIMATE_FIELD.to_string(),
+            kind: FieldKind::Number,
+            display: Some("估算".to_string()),
+        }
+    }
+
+    /// 显示名
+    pub fn label(&self) -> &str {
+        self.display.as_deref().unwrap_or(&self.name)
+    }
+
+    /// 按字段类型解析输入，空字符串表示删除该字段
+    pub fn parse_value(&self, input: &str) -> Result<Option<toml::Value>, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let value = match self.kind {
+            FieldKind::Text => toml::Value::String(input.to_string()),
+            FieldKind::Number => {
+                if let Ok(n) = input.parse::<i64>() {
+                    toml::Value::Integer(n)
+                } else {
+                    input
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|n| n.is_finite())
+                        .map(toml::Value::Float)
+                        .ok_or_else(|| format!("{} 需要数字，收到 \"{}\"", self.label(), input))?
+                }
+            }
+            FieldKind::Bool => match input.to_lowercase().as_str() {
+                "true" | "yes" | "y" | "1" | "是" => toml::Value::Boolean(true),
+                "false" | "no" | "n" | "0" | "否" => toml::Value::Boolean(false),
+                _ => {
+                    return Err(format!(
+                        "{} 需要 true/false，收到 \"{}\"",
+                        self.label(),
+                        input
+                    ));
+                }
+            },
+            FieldKind::Date => {
+                chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d").map_err(|_| {
+                    format!("{} 需要 YYYY-MM-DD 日期，收到 \"{}\"", self.label(), input)
+                })?;
+                toml::Value::String(input.to_string())
+            }
+        };
+        Ok(Some(value))
+    }
+}
+
+/// 字段值的显示文本（字符串不带引号）
+pub fn format_field_value(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Boolean(true) => "是".to_string(),
+        toml::Value::Boolean(false) => "否".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// 任务上已填写的自定义字段，按项目定义的顺序返回 (显示名, 值)
+pub fn task_field_values<'a>(
+    fields: &'a [FieldDef],
+    extra: &toml::Table,
+) -> Vec<(&'a str, String)> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            extra
+                .get(&field.name)
+                .map(|value| (field.label(), format_field_value(value)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, kind: FieldKind) -> FieldDef {
+        FieldDef {
+            name: name.to_string(),
+            kind,
+            display: None,
+        }
+    }
+
+    #[test]
+    fn parses_values_by_type() {
+        let estimate = field("estimate", FieldKind::Number);
+        assert_eq!(
+            estimate.parse_value("3").unwrap(),
+            Some(toml::Value::Integer(3))
+        );
+        assert_eq!(
+            estimate.parse_value("1.5").unwrap(),
+            Some(toml::Value::Float(1.5))
+        );
+        assert!(estimate.parse_value("three").is_err());
+        assert_eq!(estimate.parse_value("  ").unwrap(), None);
+
+        let due = field("due", FieldKind::Date);
+        assert!(due.parse_value("2026-02-30").is_err());
+        assert_eq!(
+            due.parse_value("2026-03-01").unwrap(),
+            Some(toml::Value::String("2026-03-01".to_string()))
+        );
+
+        let billable = field("billable", FieldKind::Bool);
+        assert_eq!(
+            billable.parse_value("yes").unwrap(),
+            Some(toml::Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn lists_filled_fields_in_definition_order() {
+        let fields = vec![
+            FieldDef {
+                display: Some("估算".to_string()),
+                ..field("estimate", FieldKind::Number)
+            },
+            field("customer", FieldKind::Text),
+        ];
+        let mut extra = toml::Table::new();
+        extra.insert("customer".to_string(), "ACME".into());
+        extra.insert("estimate".to_string(), 5.into());
+        extra.insert("unrelated".to_string(), true.into());
+
+        assert_eq!(
+            task_field_values(&fields, &extra),
+            vec![("估算", "5".to_string()), ("customer", "ACME".to_string())]
+        );
+    }
+}
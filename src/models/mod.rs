@@ -1,7 +1,11 @@
+pub mod automation;
+pub mod change;
+pub mod field;
 pub mod project;
 pub mod status;
 pub mod task;
 
+pub use field::FieldDef;
 pub use project::{Project, ProjectConfig, ProjectType, StatusConfig, TasksConfig};
 pub use status::Status;
 pub use task::{Task, TaskMetadata};
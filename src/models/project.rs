@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 
+use super::field::{ESTIMATE_FIELD, FieldDef};
 use super::status::Status;
 use super::task::{Task, TaskMetadata};
 
@@ -17,24 +18,75 @@ pub enum ProjectType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
     pub name: String,
+    /// 项目说明（`:project-settings` 中编辑）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 项目标签颜色（如 `#88c0d0` 或 `magenta`），用于项目选择器和聚焦面板的边框
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// 项目分组（如 `work`、`personal`），项目选择器按分组显示
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// 偏好的面板位置（`left`/`right`/`top`/`bottom`），多面板布局中打开项目时放到该侧的面板
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pane: Option<String>,
     pub created: String,
+    /// 项目唯一标识，重命名项目或目录后保持不变
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// 项目文件格式版本（缺省为引入版本号之前的旧项目），见 `fs::migration`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format_version: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub project_order: Option<i64>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// 自定义卡片字段（`[[fields]]`）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldDef>,
+    /// 状态目录中不当作任务加载的文件模式（如 `*.draft.md`），见 `fs::ignore`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    /// 进入状态时自动修改任务的规则（`[[automations]]`），见 `models::automation`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub automations: Vec<super::automation::Automation>,
+    /// 新任务的默认状态（覆盖全局配置），见 `fs::project::NewTaskDefaults`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_status: Option<String>,
+    /// 新任务的默认优先级（覆盖全局配置，`none` 表示不设置）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_priority: Option<String>,
     pub statuses: StatusesConfig,
 }
 
+impl ProjectConfig {
+    /// 是否有状态映射到其他目录（此时项目目录可能是笔记库，不再把未配置的目录当作新状态）
+    pub fn has_mapped_statuses(&self) -> bool {
+        self.statuses.statuses.values().any(|s| s.path.is_some())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatusesConfig {
     pub order: Vec<String>,
+    /// 按名称排序，保证写出的 `.kanban.toml` 内容稳定
     #[serde(flatten)]
-    pub statuses: HashMap<String, StatusConfig>,
+    pub statuses: BTreeMap<String, StatusConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatusConfig {
     pub display: String,
+    /// 列标题颜色（如 `#a3be8c` 或 `green`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// WIP 上限：列中任务数超过时标题标红
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<usize>,
+    /// 映射的状态目录（相对项目目录或绝对路径，支持 `~`），例如 Obsidian 库中的文件夹；
+    /// 缺省为项目目录下的同名目录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
 }
 
 /// 任务元数据配置文件结构（tasks.toml）
@@ -46,6 +98,8 @@ pub struct TasksConfig {
 
 #[derive(Debug, Clone)]
 pub struct Project {
+    /// 稳定的项目标识（来自 `.kanban.toml` 的 `id`），面板、状态文件和隐藏列表都用它引用项目
+    pub id: String,
     pub name: String,
     pub path: PathBuf,
     pub statuses: Vec<Status>,
@@ -53,11 +107,23 @@ pub struct Project {
     pub project_type: ProjectType,
     pub project_order: Option<i64>,
     pub tags: Vec<String>,
+    /// 项目定义的自定义卡片字段
+    pub fields: Vec<FieldDef>,
+    /// 通过当前目录的 `.kanban-link` 关联的全局项目（置顶显示）
+    pub pinned: bool,
+    /// 项目标签颜色（`.kanban.toml` 的 `color`）
+    pub color: Option<String>,
+    /// 项目分组（`.kanban.toml` 的 `group`）
+    pub group: Option<String>,
+    /// 偏好的面板位置（`.kanban.toml` 的 `pane`）
+    pub pane: Option<String>,
 }
 
 impl Project {
     pub fn new(name: String, path: PathBuf, project_type: ProjectType) -> Self {
         Self {
+            // 配置中没有 id 时以路径作为本次运行的标识
+            id: path.to_string_lossy().to_string(),
             name,
             path,
             statuses: Vec::new(),
@@ -65,11 +131,254 @@ impl Project {
             project_type,
             project_order: None,
             tags: Vec::new(),
+            fields: Vec::new(),
+            pinned: false,
+            color: None,
+            group: None,
+            pane: None,
         }
     }
 
+    /// 完成列的状态（规则同 [`Project::is_done_column`]）
+    pub fn done_status(&self) -> Option<&Status> {
+        (0..self.statuses.len())
+            .find(|&column| self.is_done_column(column))
+            .and_then(|column| self.statuses.get(column))
+    }
+
+    /// 是否是完成列：名为 done 的状态，项目没有 done 状态时为最后一列
+    pub fn is_done_column(&self, column: usize) -> bool {
+        let is_done = |status: &Status| status.name.eq_ignore_ascii_case("done");
+        if self.statuses.iter().any(is_done) {
+            self.statuses.get(column).is_some_and(is_done)
+        } else {
+            column + 1 == self.statuses.len()
+        }
+    }
+
+    /// 项目中已使用的标签（去重并排序）
+    pub fn all_tags(&self) -> Vec<String> {
+        let tags: std::collections::BTreeSet<&String> =
+            self.tasks.iter().flat_map(|task| &task.tags).collect();
+        tags.into_iter().cloned().collect()
+    }
+
+    /// 卡片上可编辑的字段：项目声明的字段，加上未声明时的内置估算字段
+    pub fn card_fields(&self) -> Vec<FieldDef> {
+        let mut fields = self.fields.clone();
+        if !fields.iter().any(|f| f.name == ESTIMATE_FIELD) {
+            fields.push(FieldDef::estimate());
+        }
+        fields
+    }
+
     #[allow(dead_code)]
     pub fn get_tasks_by_status(&self, status: &str) -> Vec<&Task> {
         self.tasks.iter().filter(|t| t.status == status).collect()
     }
+
+    /// 标题与 `title` 相近的未完成任务，按相似度从高到低
+    ///
+    /// 标题忽略大小写、标点和空白后比较：完全相同，或字符二元组的 Dice 系数
+    /// 不低于 0.75 即视为相近（很短的标题只认完全相同）。
+    pub fn near_duplicates(&self, title: &str) -> Vec<&Task> {
+        let wanted = normalize_title(title);
+        if wanted.is_empty() {
+            return Vec::new();
+        }
+        let mut matches: Vec<(f64, &Task)> = self
+            .tasks
+            .iter()
+            .filter(|task| {
+                self.statuses
+                    .iter()
+                    .position(|status| status.name == task.status)
+                    .is_none_or(|column| !self.is_done_column(column))
+            })
+            .filter_map(|task| {
+                let score = title_similarity(&wanted, &normalize_title(&task.title));
+                (score >= NEAR_DUPLICATE_THRESHOLD).then_some((score, task))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.id.cmp(&b.1.id)));
+        matches.into_iter().map(|(_, task)| task).collect()
+    }
+
+    /// 与另一个项目比较任务，返回 (只在本项目中的任务 ID, 只在对方项目中的任务 ID)
+    ///
+    /// 任务的 `link` 字段指向对方存在的任务时直接配对，其余按标题相近程度配对
+    /// （规则同 `near_duplicates`，包括已完成的任务），每个任务最多配对一次。
+    pub fn diff_tasks(&self, other: &Project) -> (Vec<u32>, Vec<u32>) {
+        let mut pairs: Vec<(u32, u32)> = Vec::new();
+        for task in &self.tasks {
+            if let Some(link) = task
+                .link()
+                .filter(|id| other.tasks.iter().any(|t| t.id == *id))
+            {
+                pairs.push((task.id, link));
+            }
+        }
+        for task in &other.tasks {
+            if let Some(link) = task
+                .link()
+                .filter(|id| self.tasks.iter().any(|t| t.id == *id))
+            {
+                pairs.push((link, task.id));
+            }
+        }
+
+        let titles = |project: &Project| -> Vec<(u32, Vec<char>)> {
+            project
+                .tasks
+                .iter()
+                .map(|task| (task.id, normalize_title(&task.title)))
+                .collect()
+        };
+        let (left, right) = (titles(self), titles(other));
+        let mut candidates: Vec<(f64, u32, u32)> = Vec::new();
+        for (left_id, left_title) in &left {
+            for (right_id, right_title) in &right {
+                let score = title_similarity(left_title, right_title);
+                if !left_title.is_empty() && score >= NEAR_DUPLICATE_THRESHOLD {
+                    candidates.push((score, *left_id, *right_id));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+        pairs.extend(candidates.into_iter().map(|(_, l, r)| (l, r)));
+
+        // 显式链接在前，相似度高的在前，先到先得
+        let mut matched_left = HashSet::new();
+        let mut matched_right = HashSet::new();
+        for (l, r) in pairs {
+            if !matched_left.contains(&l) && !matched_right.contains(&r) {
+                matched_left.insert(l);
+                matched_right.insert(r);
+            }
+        }
+        let unmatched = |project: &Project, matched: &HashSet<u32>| -> Vec<u32> {
+            project
+                .tasks
+                .iter()
+                .map(|task| task.id)
+                .filter(|id| !matched.contains(id))
+                .collect()
+        };
+        (
+            unmatched(self, &matched_left),
+            unmatched(other, &matched_right),
+        )
+    }
+}
+
+/// 标题相似度达到该值视为重复
+const NEAR_DUPLICATE_THRESHOLD: f64 = 0.75;
+
+/// 只保留小写字母和数字（含中文等），去掉标点和空白
+fn normalize_title(title: &str) -> Vec<char> {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 两个规范化标题的相似度（0-1），按字符二元组计算 Dice 系数
+fn title_similarity(a: &[char], b: &[char]) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < 4 || b.len() < 4 {
+        return 0.0;
+    }
+    let mut bigrams: HashMap<(char, char), usize> = HashMap::new();
+    for pair in a.windows(2) {
+        *bigrams.entry((pair[0], pair[1])).or_default() += 1;
+    }
+    let mut common = 0;
+    for pair in b.windows(2) {
+        if let Some(count) = bigrams.get_mut(&(pair[0], pair[1]))
+            && *count > 0
+        {
+            *count -= 1;
+            common += 1;
+        }
+    }
+    2.0 * common as f64 / (a.len() + b.len() - 2) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn near_duplicates_ignore_case_punctuation_and_done_tasks() {
+        let mut project = Project::new(
+            "web".to_string(),
+            PathBuf::from("/tmp/web"),
+            ProjectType::Global,
+        );
+        project.statuses = vec![
+            Status::new("todo".to_string(), "待办".to_string()),
+            Status::new("done".to_string(), "完成".to_string()),
+        ];
+        project.tasks = vec![
+            Task::new(1, "Fix the login bug".to_string(), "todo".to_string()),
+            Task::new(2, "Fix logout page".to_string(), "todo".to_string()),
+            Task::new(3, "Fix login bug".to_string(), "done".to_string()),
+            Task::new(4, "修复登录页面的错误".to_string(), "todo".to_string()),
+        ];
+
+        let ids = |title: &str| {
+            project
+                .near_duplicates(title)
+                .iter()
+                .map(|task| task.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("fix login bug!"), vec![1]);
+        assert_eq!(ids("修复登录页面错误"), vec![4]);
+        assert!(ids("Fix").is_empty());
+        assert!(ids("Write release notes").is_empty());
+    }
+
+    #[test]
+    fn diff_tasks_pairs_by_link_then_title() {
+        let project = |name: &str, tasks: &[(u32, &str)]| {
+            let mut project = Project::new(
+                name.to_string(),
+                PathBuf::from("/tmp").join(name),
+                ProjectType::Global,
+            );
+            project.tasks = tasks
+                .iter()
+                .map(|(id, title)| Task::new(*id, title.to_string(), "todo".to_string()))
+                .collect();
+            project
+        };
+        let team = project(
+            "team",
+            &[
+                (1, "Fix the login bug"),
+                (2, "Write release notes"),
+                (3, "Update dependencies"),
+            ],
+        );
+        let mut mine = project(
+            "mine",
+            &[
+                (7, "fix login bug"),
+                (8, "Notes for release"),
+                (9, "Buy milk"),
+            ],
+        );
+        assert_eq!(mine.diff_tasks(&team), (vec![8, 9], vec![2, 3]));
+
+        // 标题改得认不出来时，用 link 指向团队看板中的任务
+        mine.tasks[1]
+            .extra
+            .insert(crate::models::field::LINK_FIELD.to_string(), "#2".into());
+        assert_eq!(mine.diff_tasks(&team), (vec![9], vec![3]));
+        assert_eq!(team.diff_tasks(&mine), (vec![3], vec![9]));
+    }
 }
@@ -1,13 +1,24 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Status {
     pub name: String,
     pub display: String,
+    /// 列标题颜色（如 `#a3be8c` 或 `green`），未设置时使用默认配色
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// WIP 上限，未设置时不限制
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<usize>,
 }
 
 impl Status {
     pub fn new(name: String, display: String) -> Self {
-        Self { name, display }
+        Self {
+            name,
+            display,
+            color: None,
+            wip_limit: None,
+        }
     }
 }
@@ -8,22 +8,40 @@ use std::path::PathBuf;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskFrontmatter {
     pub id: u32,
-    pub order: i32,
+    /// 缺失时按 id * 1000 处理
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i32>,
+    #[serde(default = "default_created")]
     pub created: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// 未识别的字段（其他工具或手写的元数据），保存时原样写回
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+fn default_created() -> String {
+    "0".to_string()
+}
+
+impl TaskFrontmatter {
+    /// 排序值，缺失时使用 id * 1000（与旧任务兼容）
+    pub fn order(&self) -> i32 {
+        self.order.unwrap_or(self.id as i32 * 1000)
+    }
 }
 
 impl From<&Task> for TaskFrontmatter {
     fn from(task: &Task) -> Self {
         Self {
             id: task.id,
-            order: task.order,
+            order: Some(task.order),
             created: task.created.clone(),
             priority: task.priority.clone(),
             tags: task.tags.clone(),
+            extra: task.extra.clone(),
         }
     }
 }
@@ -66,11 +84,155 @@ pub struct Task {
     pub priority: Option<String>,
     pub status: String,
     pub tags: Vec<String>,
+    /// 未识别的元数据字段，保存时原样写回
+    #[serde(default, skip_serializing_if = "toml::Table::is_empty")]
+    pub extra: toml::Table,
     #[serde(skip)]
     pub file_path: PathBuf,
+    /// 正文统计（解析时计算，修改正文请使用 `set_content`）
+    #[serde(skip)]
+    pub stats: BodyStats,
+}
+
+/// `:tag +urgent -later` 的标签增删
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagEdit {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl TagEdit {
+    /// 解析参数：`+标签` 添加，`-标签` 删除，不带符号视为添加
+    pub fn parse(args: &[&str]) -> Result<Self, String> {
+        let mut edit = TagEdit::default();
+        for arg in args {
+            let (list, tag) = match arg.split_at_checked(1) {
+                Some(("-", tag)) => (&mut edit.remove, tag),
+                Some(("+", tag)) => (&mut edit.add, tag),
+                _ => (&mut edit.add, *arg),
+            };
+            if tag.is_empty() {
+                return Err(format!("标签不能为空: {}", arg));
+            }
+            if !list.iter().any(|t| t == tag) {
+                list.push(tag.to_string());
+            }
+        }
+        if edit.add.is_empty() && edit.remove.is_empty() {
+            return Err("用法: :tag +标签 -标签".to_string());
+        }
+        Ok(edit)
+    }
+
+    /// 应用到标签列表，返回是否有变化
+    pub fn apply(&self, tags: &mut Vec<String>) -> bool {
+        let before = tags.len();
+        tags.retain(|tag| !self.remove.contains(tag));
+        let mut changed = tags.len() != before;
+        for tag in &self.add {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+                changed = true;
+            }
+        }
+        changed
+    }
 }
 
 impl Task {
+    /// 替换正文并更新统计
+    pub fn set_content(&mut self, content: String) {
+        self.stats = BodyStats::of(&content);
+        self.content = content;
+    }
+
+    /// 在正文的 `## Notes` 小节末尾追加一条带时间戳的笔记，没有该小节时在正文末尾新建
+    ///
+    /// 笔记写成 `- 时间 内容` 列表项，多行内容的后续行缩进两格
+    pub fn append_note(&mut self, note: &str, timestamp: &str) {
+        let mut entry = format!("- {}", timestamp);
+        for (i, line) in note.trim().lines().enumerate() {
+            if i == 0 {
+                entry.push(' ');
+            } else {
+                entry.push_str("\n  ");
+            }
+            entry.push_str(line.trim_end());
+        }
+
+        let mut lines: Vec<&str> = self.content.lines().collect();
+        let heading = lines
+            .iter()
+            .position(|line| line.trim().eq_ignore_ascii_case(NOTES_HEADING));
+        let content = match heading {
+            Some(start) => {
+                // 插入到小节最后一个非空行之后（下一个同级或更高级标题之前）
+                let end = lines[start + 1..]
+                    .iter()
+                    .position(|line| line.starts_with("# ") || line.starts_with("## "))
+                    .map_or(lines.len(), |i| start + 1 + i);
+                let insert_at = (start + 1..end)
+                    .rev()
+                    .find(|&i| !lines[i].trim().is_empty())
+                    .map_or(start + 1, |i| i + 1);
+                if insert_at == start + 1 {
+                    lines.insert(insert_at, "");
+                    lines.insert(insert_at + 1, &entry);
+                } else {
+                    lines.insert(insert_at, &entry);
+                }
+                lines.join("\n")
+            }
+            None => {
+                let body = self.content.trim_end();
+                if body.is_empty() {
+                    format!("{}\n\n{}", NOTES_HEADING, entry)
+                } else {
+                    format!("{}\n\n{}\n\n{}", body, NOTES_HEADING, entry)
+                }
+            }
+        };
+        self.set_content(content);
+    }
+
+    /// frontmatter 中的 `estimate` 估算点数，缺失或不是数字时为 None
+    pub fn estimate(&self) -> Option<f64> {
+        match self.extra.get(crate::models::field::ESTIMATE_FIELD)? {
+            toml::Value::Integer(n) => Some(*n as f64),
+            toml::Value::Float(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// frontmatter 中的 `color` 卡片颜色，缺失或不是字符串时为 None
+    pub fn color(&self) -> Option<&str> {
+        self.extra.get(crate::models::field::COLOR_FIELD)?.as_str()
+    }
+
+    /// frontmatter 中的 `link` 对应任务 ID，可写成 `12` 或 `"#12"`
+    pub fn link(&self) -> Option<u32> {
+        match self.extra.get(crate::models::field::LINK_FIELD)? {
+            toml::Value::Integer(id) => u32::try_from(*id).ok(),
+            toml::Value::String(id) => id.trim().trim_start_matches('#').parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// 设置或清除卡片颜色
+    pub fn set_color(&mut self, color: Option<&str>) {
+        match color {
+            Some(color) => {
+                self.extra.insert(
+                    crate::models::field::COLOR_FIELD.to_string(),
+                    color.to_string().into(),
+                );
+            }
+            None => {
+                self.extra.remove(crate::models::field::COLOR_FIELD);
+            }
+        }
+    }
+
     pub fn new(id: u32, title: String, status: String) -> Self {
         use std::time::{SystemTime, UNIX_EPOCH};
         let timestamp = SystemTime::now()
@@ -87,7 +249,9 @@ impl Task {
             priority: None,
             status,
             tags: Vec::new(),
+            extra: toml::Table::new(),
             file_path: PathBuf::new(),
+            stats: BodyStats::default(),
         }
     }
 
@@ -95,6 +259,7 @@ impl Task {
     #[allow(dead_code)]
     pub fn from_metadata(metadata: TaskMetadata, content: String, file_path: PathBuf) -> Self {
         Self {
+            stats: BodyStats::of(&content),
             id: metadata.id,
             order: metadata.order,
             title: metadata.title,
@@ -103,7 +268,251 @@ impl Task {
             priority: metadata.priority,
             status: metadata.status,
             tags: metadata.tags,
+            extra: toml::Table::new(),
             file_path,
         }
     }
 }
+
+/// 任务正文中笔记小节的标题（`Space t n` 追加）
+pub const NOTES_HEADING: &str = "## Notes";
+
+/// 按每分钟阅读的词数估算阅读时间
+const WORDS_PER_MINUTE: usize = 200;
+
+/// 任务正文的统计：行数、词数（中日韩文字按字计）和子任务完成情况
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BodyStats {
+    /// 非空行数
+    pub lines: usize,
+    pub words: usize,
+    pub checklist_done: usize,
+    pub checklist_total: usize,
+}
+
+impl BodyStats {
+    pub fn of(content: &str) -> Self {
+        let mut stats = Self::default();
+        for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            stats.lines += 1;
+
+            // 子任务的勾选标记不计入词数
+            let item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+            let text = if let Some(text) = item.and_then(|i| i.strip_prefix("[ ]")) {
+                stats.checklist_total += 1;
+                text
+            } else if let Some(text) =
+                item.and_then(|i| i.strip_prefix("[x]").or_else(|| i.strip_prefix("[X]")))
+            {
+                stats.checklist_total += 1;
+                stats.checklist_done += 1;
+                text
+            } else {
+                line
+            };
+            stats.words += count_words(text);
+        }
+        stats
+    }
+
+    /// 估算阅读时间（分钟），空正文为 0
+    pub fn reading_minutes(&self) -> usize {
+        self.words.div_ceil(WORDS_PER_MINUTE)
+    }
+
+    /// 单行摘要，如 "12 行 · 340 词 · ☑ 2/5 · 约 2 分钟"；空正文返回 None
+    pub fn summary(&self) -> Option<String> {
+        if self.lines == 0 {
+            return None;
+        }
+        let mut parts = vec![format!("{} 行", self.lines), format!("{} 词", self.words)];
+        if self.checklist_total > 0 {
+            parts.push(format!(
+                "☑ {}/{}",
+                self.checklist_done, self.checklist_total
+            ));
+        }
+        parts.push(format!("约 {} 分钟", self.reading_minutes().max(1)));
+        Some(parts.join(" · "))
+    }
+}
+
+/// 统计词数：连续的字母数字算一个词，中日韩文字每个字算一个词
+fn count_words(text: &str) -> usize {
+    let mut words = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk(c) {
+            words += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                words += 1;
+            }
+            in_word = true;
+        } else {
+            in_word = false;
+        }
+    }
+    words
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30ff}' // 平假名、片假名
+            | '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{ac00}'..='\u{d7af}' // 韩文
+            | '\u{f900}'..='\u{faff}'
+    )
+}
+
+/// 任务估算点数之和，没有任何任务填写估算时为 None
+pub fn estimate_total<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> Option<f64> {
+    tasks
+        .into_iter()
+        .filter_map(Task::estimate)
+        .fold(None, |total, points| Some(total.unwrap_or(0.0) + points))
+}
+
+/// 面板标题中的完成进度算法（配置项 `progress`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProgressMetric {
+    /// 完成卡片数 / 总卡片数
+    #[default]
+    Count,
+    /// 按估算点数加权，未填写估算的任务按 1 点计
+    Estimate,
+    /// 按优先级加权：高 3、中 2、低或未设置 1
+    Priority,
+}
+
+impl ProgressMetric {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "count" => Some(ProgressMetric::Count),
+            "estimate" => Some(ProgressMetric::Estimate),
+            "priority" => Some(ProgressMetric::Priority),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ProgressMetric::Count => "count",
+            ProgressMetric::Estimate => "estimate",
+            ProgressMetric::Priority => "priority",
+        }
+    }
+
+    pub fn weight(self, task: &Task) -> f64 {
+        match self {
+            ProgressMetric::Count => 1.0,
+            ProgressMetric::Estimate => task.estimate().unwrap_or(1.0),
+            ProgressMetric::Priority => match task.priority.as_deref() {
+                Some("high") => 3.0,
+                Some("medium") => 2.0,
+                _ => 1.0,
+            },
+        }
+    }
+
+    /// 完成百分比（0-100），没有任务时为 None
+    pub fn percent<'a>(
+        self,
+        tasks: impl IntoIterator<Item = &'a Task>,
+        done_status: &str,
+    ) -> Option<u32> {
+        let (done, total) = tasks.into_iter().fold((0.0, 0.0), |(done, total), task| {
+            let weight = self.weight(task);
+            let done = if task.status == done_status {
+                done + weight
+            } else {
+                done
+            };
+            (done, total + weight)
+        });
+        (total > 0.0).then(|| (done / total * 100.0).round() as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_are_appended_under_the_notes_section() {
+        let mut task = Task::new(1, "Release".to_string(), "todo".to_string());
+        task.append_note("kicked off", "2026-01-05 09:00");
+        assert_eq!(task.content, "## Notes\n\n- 2026-01-05 09:00 kicked off");
+
+        task.set_content(
+            "Ship it.\n\n## Notes\n\n- 2026-01-05 09:00 kicked off\n\n## 子任务\n\n- [ ] tag"
+                .to_string(),
+        );
+        task.append_note("blocked on CI\nwaiting for runner", "2026-01-06 17:30");
+        assert_eq!(
+            task.content,
+            "Ship it.\n\n## Notes\n\n- 2026-01-05 09:00 kicked off\n\
+             - 2026-01-06 17:30 blocked on CI\n  waiting for runner\n\n## 子任务\n\n- [ ] tag"
+        );
+
+        task.set_content("Ship it.\n".to_string());
+        task.append_note("done", "2026-01-07 10:00");
+        assert_eq!(
+            task.content,
+            "Ship it.\n\n## Notes\n\n- 2026-01-07 10:00 done"
+        );
+        assert_eq!(task.stats, BodyStats::of(&task.content));
+    }
+
+    #[test]
+    fn weighted_progress_uses_estimates_or_priorities() {
+        let mut tasks = vec![
+            Task::new(1, "a".to_string(), "done".to_string()),
+            Task::new(2, "b".to_string(), "todo".to_string()),
+            Task::new(3, "c".to_string(), "todo".to_string()),
+        ];
+        tasks[0]
+            .extra
+            .insert("estimate".to_string(), toml::Value::Integer(6));
+        tasks[1].priority = Some("high".to_string());
+
+        assert_eq!(ProgressMetric::Count.percent(&tasks, "done"), Some(33));
+        assert_eq!(ProgressMetric::Estimate.percent(&tasks, "done"), Some(75));
+        assert_eq!(ProgressMetric::Priority.percent(&tasks, "done"), Some(20));
+        assert_eq!(ProgressMetric::Priority.percent(&[], "done"), None);
+    }
+
+    #[test]
+    fn tag_edit_adds_and_removes_in_one_pass() {
+        let edit = TagEdit::parse(&["+urgent", "-later", "bug", "+urgent"]).unwrap();
+        assert_eq!(edit.add, vec!["urgent", "bug"]);
+        assert_eq!(edit.remove, vec!["later"]);
+
+        let mut tags = vec!["later".to_string(), "bug".to_string()];
+        assert!(edit.apply(&mut tags));
+        assert_eq!(tags, vec!["bug", "urgent"]);
+        assert!(!edit.apply(&mut tags));
+
+        assert!(TagEdit::parse(&[]).is_err());
+        assert!(TagEdit::parse(&["+"]).is_err());
+    }
+
+    #[test]
+    fn body_stats_count_lines_words_and_checklist() {
+        let stats = BodyStats::of(
+            "## 背景\n\nRefactor the parser 模块\n\n- [x] split lexer\n- [ ] add tests\n* [X] 文档\n",
+        );
+        assert_eq!(stats.lines, 5);
+        assert_eq!(stats.words, 2 + 5 + 2 + 2 + 2);
+        assert_eq!((stats.checklist_done, stats.checklist_total), (2, 3));
+        assert_eq!(
+            stats.summary().as_deref(),
+            Some("5 行 · 13 词 · ☑ 2/3 · 约 1 分钟")
+        );
+        assert_eq!(BodyStats::of("  \n").summary(), None);
+    }
+}
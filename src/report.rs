@@ -0,0 +1,201 @@
+/// 任务停留时间报告（`:report aging`）：按任务在当前状态停留的时间从长到短列出未完成的任务，
+/// 超过阈值的任务分组突出显示，用于站会上逐个过一遍卡住的任务
+///
+/// 进入当前状态的时间取变更日志中最后一次移入（或创建到）该状态的记录；没有记录时使用任务文件的修改时间，
+/// 再退而使用创建时间。
+use crate::fs::journal::{JournalAction, JournalEntry};
+use crate::models::{Project, Task};
+use chrono::{DateTime, FixedOffset, Local};
+
+/// 停留超过此天数的任务标为 🟡
+pub const AGING_WARN_DAYS: i64 = 3;
+/// 停留超过此天数的任务标为 🔴
+pub const AGING_ALERT_DAYS: i64 = 7;
+
+/// 报告中的一行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgingRow {
+    pub task_id: u32,
+    pub title: String,
+    pub status: String,
+    pub since: DateTime<FixedOffset>,
+    pub days: i64,
+}
+
+/// 任务进入当前状态的时间
+fn entered_status(
+    project: &Project,
+    task: &Task,
+    journal: &[JournalEntry],
+) -> Option<DateTime<FixedOffset>> {
+    let from_journal = journal
+        .iter()
+        .filter(|e| e.is_for(&project.id, &project.name) && e.task_id == task.id)
+        .filter(|e| matches!(e.action, JournalAction::Move | JournalAction::Create))
+        .filter(|e| e.to.as_deref() == Some(task.status.as_str()))
+        .filter_map(|e| DateTime::parse_from_rfc3339(&e.ts).ok())
+        .max();
+    from_journal
+        .or_else(|| {
+            let modified = crate::fs::task::task_modified_at(task)?;
+            Some(DateTime::<Local>::from(modified).fixed_offset())
+        })
+        .or_else(|| {
+            let created = DateTime::from_timestamp(task.created.parse().ok()?, 0)?;
+            Some(created.with_timezone(&Local).fixed_offset())
+        })
+}
+
+/// 计算未完成任务的停留时间，按停留时间从长到短排列
+///
+/// `journal` 是该项目的变更日志。
+pub fn aging(
+    project: &Project,
+    journal: &[JournalEntry],
+    now: DateTime<FixedOffset>,
+) -> Vec<AgingRow> {
+    let mut rows: Vec<AgingRow> = project
+        .statuses
+        .iter()
+        .enumerate()
+        .filter(|(col, _)| !project.is_done_column(*col))
+        .flat_map(|(_, status)| {
+            project
+                .tasks
+                .iter()
+                .filter(move |t| t.status == status.name)
+                .map(move |t| (t, status))
+        })
+        .filter_map(|(task, status)| {
+            let since = entered_status(project, task, journal)?;
+            Some(AgingRow {
+                task_id: task.id,
+                title: task.title.clone(),
+                status: status.display.clone(),
+                since,
+                days: (now - since).num_days().max(0),
+            })
+        })
+        .collect();
+    rows.sort_by(|a, b| a.since.cmp(&b.since).then(a.task_id.cmp(&b.task_id)));
+    rows
+}
+
+/// 生成 Markdown 报告（预览中显示，也可以导出到文件）
+pub fn to_markdown(project_name: &str, rows: &[AgingRow], now: DateTime<FixedOffset>) -> String {
+    let mut out = format!(
+        "# 任务停留时间 - {}\n\n生成于 {} · 不含完成列 · 🔴 {} 天以上 · 🟡 {} 天以上\n",
+        project_name,
+        now.format("%Y-%m-%d %H:%M"),
+        AGING_ALERT_DAYS,
+        AGING_WARN_DAYS
+    );
+    if rows.is_empty() {
+        out.push_str("\n没有未完成的任务\n");
+        return out;
+    }
+
+    let groups = [
+        (
+            format!("🔴 {} 天以上", AGING_ALERT_DAYS),
+            AGING_ALERT_DAYS..i64::MAX,
+        ),
+        (
+            format!("🟡 {}-{} 天", AGING_WARN_DAYS, AGING_ALERT_DAYS),
+            AGING_WARN_DAYS..AGING_ALERT_DAYS,
+        ),
+        (format!("{} 天以内", AGING_WARN_DAYS), 0..AGING_WARN_DAYS),
+    ];
+    for (heading, range) in groups {
+        let group: Vec<&AgingRow> = rows.iter().filter(|r| range.contains(&r.days)).collect();
+        if group.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n## {} ({})\n\n", heading, group.len()));
+        for row in group {
+            out.push_str(&format!(
+                "- #{} {} — {} · {} 天（自 {}）\n",
+                row.task_id,
+                row.title,
+                row.status,
+                row.days,
+                row.since.format("%Y-%m-%d")
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectType, Status};
+    use std::path::PathBuf;
+
+    fn entry(ts: &str, task_id: u32, action: JournalAction, to: &str) -> JournalEntry {
+        JournalEntry {
+            ts: ts.to_string(),
+            actor: "cli".to_string(),
+            project: "web".to_string(),
+            project_id: String::new(),
+            action,
+            task_id,
+            title: String::new(),
+            from: None,
+            to: Some(to.to_string()),
+        }
+    }
+
+    #[test]
+    fn aging_sorts_by_time_in_current_status_and_groups_by_threshold() {
+        let mut project = Project::new(
+            "web".to_string(),
+            PathBuf::from("/tmp/web"),
+            ProjectType::Global,
+        );
+        project.statuses = ["todo", "doing", "done"]
+            .iter()
+            .map(|s| Status::new(s.to_string(), s.to_uppercase()))
+            .collect();
+        for (id, status) in [(1, "todo"), (2, "doing"), (3, "done"), (4, "todo")] {
+            project
+                .tasks
+                .push(Task::new(id, format!("Task {}", id), status.to_string()));
+        }
+        let journal = vec![
+            entry(
+                "2026-03-01T10:00:00+08:00",
+                1,
+                JournalAction::Create,
+                "todo",
+            ),
+            entry(
+                "2026-02-20T10:00:00+08:00",
+                2,
+                JournalAction::Create,
+                "todo",
+            ),
+            entry("2026-03-05T10:00:00+08:00", 2, JournalAction::Move, "doing"),
+            entry("2026-02-01T10:00:00+08:00", 3, JournalAction::Move, "done"),
+            entry(
+                "2026-03-09T10:00:00+08:00",
+                4,
+                JournalAction::Create,
+                "todo",
+            ),
+        ];
+        let now = DateTime::parse_from_rfc3339("2026-03-10T12:00:00+08:00").unwrap();
+
+        let rows = aging(&project, &journal, now);
+        let summary: Vec<(u32, i64)> = rows.iter().map(|r| (r.task_id, r.days)).collect();
+        assert_eq!(summary, vec![(1, 9), (2, 5), (4, 1)]);
+        assert_eq!(rows[1].status, "DOING");
+
+        let markdown = to_markdown("web", &rows, now);
+        assert!(
+            markdown.contains("## 🔴 7 天以上 (1)\n\n- #1 Task 1 — TODO · 9 天（自 2026-03-01）\n")
+        );
+        assert!(markdown.contains("## 🟡 3-7 天 (1)\n"));
+        assert!(markdown.contains("## 3 天以内 (1)\n"));
+    }
+}
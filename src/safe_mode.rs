@@ -0,0 +1,44 @@
+/// 安全模式（`hxk --safe-mode`）
+///
+/// 损坏的状态文件或有问题的项目导致 TUI 启动即崩溃时的应急入口：以单个空面板启动，
+/// 不读取也不写入 `state.toml`，不运行状态自动化、输入法切换、内嵌图片和外部修改检测，
+/// 并把启动过程和崩溃信息详细记录到调试日志。
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 调试日志路径，与其他模块的 `log_debug` 相同
+pub const LOG_FILE: &str = "/tmp/kanban_debug.log";
+
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// 由命令行参数开启安全模式，并把 panic 信息写入调试日志
+pub fn enable() {
+    SAFE_MODE.store(true, Ordering::Relaxed);
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log(format!("panic: {}", info));
+        default_hook(info);
+    }));
+    log("以安全模式启动".to_string());
+}
+
+pub fn is_enabled() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+}
+
+/// 安全模式下写入调试日志，正常模式下什么也不做
+pub fn log(msg: String) {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    if !is_enabled() {
+        return;
+    }
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(LOG_FILE) {
+        let _ = writeln!(
+            file,
+            "[{}] [safe-mode] {}",
+            chrono::Local::now().format("%H:%M:%S"),
+            msg
+        );
+    }
+}
@@ -0,0 +1,449 @@
+/// 用户脚本（`scripting` feature，Rhai 语言）
+///
+/// 脚本放在 `~/.kanban/scripts/<名称>.rhai`，有两种用法：
+///
+/// - `:script run <名称>` 对聚焦的项目执行整个脚本，实现自定义命令；
+/// - 脚本中定义 `fn on_move(task, from, to)` 时，任何任务移动到新状态后都会调用它
+///   （在 `[[automations]]` 之后），返回修改后的 task 即可改写任务。脚本自己写回的移动不会再触发钩子。
+///
+/// 脚本中可用的函数：
+///
+/// ```text
+/// tasks()          项目中的全部任务（#{id, title, status, priority, tags, order, content}）
+/// statuses()       状态目录名，按列顺序
+/// project_name()   项目名称
+/// update(task)     写回修改后的任务（改 status 即移动到该状态）
+/// notify(message)  运行结束后在通知中显示（print 也一样）
+/// ```
+///
+/// 修改在脚本执行完后通过存储层统一写入，脚本出错时不写入任何修改。
+use crate::models::{Project, Task};
+use std::path::{Path, PathBuf};
+
+/// 脚本文件扩展名
+pub const SCRIPT_EXTENSION: &str = "rhai";
+
+/// 脚本目录 `~/.kanban/scripts`
+pub fn scripts_dir() -> PathBuf {
+    crate::fs::get_data_dir().join("scripts")
+}
+
+/// 目录中的脚本名（不含扩展名），按名称排序
+pub fn list_scripts(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// 脚本执行结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScriptOutcome {
+    /// 写回的任务数
+    pub updated: usize,
+    /// `notify` / `print` 输出的消息
+    pub messages: Vec<String>,
+}
+
+/// 读取 `~/.kanban/scripts/<名称>.rhai` 并对项目执行
+pub fn run_named(project: &Project, name: &str) -> Result<ScriptOutcome, String> {
+    let path = scripts_dir().join(format!("{}.{}", name, SCRIPT_EXTENSION));
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| format!("无法读取脚本 {}: {}", path.display(), e))?;
+    let (updates, messages) = engine::run(project, &source)?;
+    engine::write_back(project, &updates)?;
+    Ok(ScriptOutcome {
+        updated: updates.len(),
+        messages,
+    })
+}
+
+/// 任务移动到新状态后调用脚本中的 `on_move` 钩子
+///
+/// 脚本在本进程（线程）第一次移动任务时读取并编译一次，之后复用；
+/// 修改脚本后需要重新启动才会生效。
+pub fn run_move_hooks(task: &mut Task, from: &str) {
+    if crate::safe_mode::is_enabled() {
+        return;
+    }
+    engine::run_move_hooks(task, from);
+}
+
+/// 读取目录中的全部脚本源码（名称, 源码）
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+fn read_scripts(dir: &Path) -> Vec<(String, String)> {
+    list_scripts(dir)
+        .into_iter()
+        .filter_map(|name| {
+            let path = dir.join(format!("{}.{}", name, SCRIPT_EXTENSION));
+            let source = std::fs::read_to_string(&path).ok()?;
+            Some((name, source))
+        })
+        .collect()
+}
+
+#[cfg(feature = "scripting")]
+mod engine {
+    use super::*;
+    use rhai::{Array, Dynamic, Engine, Map, Scope};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// 单个脚本最多执行的操作数，防止死循环卡住界面
+    const MAX_OPERATIONS: u64 = 1_000_000;
+
+    #[derive(Default)]
+    struct Shared {
+        updates: Vec<Task>,
+        messages: Vec<String>,
+    }
+
+    fn new_engine(shared: &Rc<RefCell<Shared>>) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let messages = Rc::clone(shared);
+        engine.on_print(move |text| messages.borrow_mut().messages.push(text.to_string()));
+        let messages = Rc::clone(shared);
+        engine.register_fn("notify", move |text: &str| {
+            messages.borrow_mut().messages.push(text.to_string())
+        });
+        engine
+    }
+
+    /// 执行脚本，返回要写回的任务和输出的消息
+    pub fn run(project: &Project, source: &str) -> Result<(Vec<Task>, Vec<String>), String> {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let mut engine = new_engine(&shared);
+
+        let tasks: Array = project
+            .tasks
+            .iter()
+            .map(|task| Dynamic::from_map(task_to_map(task)))
+            .collect();
+        engine.register_fn("tasks", move || tasks.clone());
+        let statuses: Array = project
+            .statuses
+            .iter()
+            .map(|status| Dynamic::from(status.name.clone()))
+            .collect();
+        engine.register_fn("statuses", move || statuses.clone());
+        let name = project.name.clone();
+        engine.register_fn("project_name", move || name.clone());
+
+        let originals = project.tasks.clone();
+        let status_names: Vec<String> = project.statuses.iter().map(|s| s.name.clone()).collect();
+        let updates = Rc::clone(&shared);
+        engine.register_fn(
+            "update",
+            move |map: Map| -> Result<(), Box<rhai::EvalAltResult>> {
+                let id = map
+                    .get("id")
+                    .and_then(|id| id.as_int().ok())
+                    .ok_or("update: 任务缺少 id")?;
+                let mut task = originals
+                    .iter()
+                    .find(|task| i64::from(task.id) == id)
+                    .cloned()
+                    .ok_or_else(|| format!("update: 找不到任务 #{}", id))?;
+                apply_map(&mut task, &map, true)?;
+                if !status_names.contains(&task.status) {
+                    return Err(format!("update: 状态 {} 不存在", task.status).into());
+                }
+                let mut shared = updates.borrow_mut();
+                shared.updates.retain(|t| t.id != task.id);
+                shared.updates.push(task);
+                Ok(())
+            },
+        );
+
+        engine
+            .run_with_scope(&mut Scope::new(), source)
+            .map_err(|e| format!("脚本出错: {}", e))?;
+        drop(engine);
+        let shared = Rc::try_unwrap(shared)
+            .map(RefCell::into_inner)
+            .unwrap_or_default();
+        Ok((shared.updates, shared.messages))
+    }
+
+    /// 通过存储层写入脚本修改的任务：状态变化的任务移动过去，其他的直接保存
+    pub fn write_back(project: &Project, updates: &[Task]) -> Result<(), String> {
+        for update in updates {
+            let Some(original) = project.tasks.iter().find(|task| task.id == update.id) else {
+                continue;
+            };
+            let mut task = update.clone();
+            if task.status != original.status {
+                let target = std::mem::replace(&mut task.status, original.status.clone());
+                // 脚本自己的写回不再触发 on_move 钩子
+                crate::fs::move_task_without_hooks(&project.path, &mut task, &target)?;
+            } else {
+                crate::fs::save_task(&project.path, &task)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 编译好的 `on_move` 钩子
+    pub struct MoveHooks {
+        engine: Engine,
+        hooks: Vec<(String, rhai::AST)>,
+    }
+
+    impl MoveHooks {
+        /// 编译脚本，只保留定义了 `on_move` 的；编译失败的脚本记录日志后跳过
+        pub fn compile(scripts: Vec<(String, String)>) -> Self {
+            let mut engine = Engine::new();
+            engine.set_max_operations(MAX_OPERATIONS);
+            let mut hooks = Vec::new();
+            for (name, source) in scripts {
+                match engine.compile(&source) {
+                    Ok(ast) if ast.iter_functions().any(|f| f.name == "on_move") => {
+                        hooks.push((name, ast))
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        crate::safe_mode::log(format!("脚本 {} 编译失败: {}", name, e));
+                    }
+                }
+            }
+            MoveHooks { engine, hooks }
+        }
+
+        /// 依次调用钩子，出错的钩子记录日志后跳过
+        pub fn run(&self, task: &mut Task, from: &str) {
+            for (name, ast) in &self.hooks {
+                if let Err(e) = self.call(ast, task, from) {
+                    crate::safe_mode::log(format!("脚本 {} 的 on_move 失败: {}", name, e));
+                }
+            }
+        }
+
+        fn call(&self, ast: &rhai::AST, task: &mut Task, from: &str) -> Result<(), String> {
+            // 只调用钩子，不执行脚本顶层的命令代码
+            let options = rhai::CallFnOptions::new().eval_ast(false);
+            let result: Dynamic = self
+                .engine
+                .call_fn_with_options(
+                    options,
+                    &mut Scope::new(),
+                    ast,
+                    "on_move",
+                    (
+                        Dynamic::from_map(task_to_map(task)),
+                        from.to_string(),
+                        task.status.clone(),
+                    ),
+                )
+                .map_err(|e| e.to_string())?;
+            if let Some(map) = result.try_cast::<Map>() {
+                apply_map(task, &map, false)?;
+            }
+            Ok(())
+        }
+    }
+
+    thread_local! {
+        /// 本线程编译好的钩子（Rhai 的 AST 不能跨线程共享）
+        static MOVE_HOOKS: std::cell::OnceCell<MoveHooks> = const { std::cell::OnceCell::new() };
+    }
+
+    pub fn run_move_hooks(task: &mut Task, from: &str) {
+        MOVE_HOOKS.with(|hooks| {
+            hooks
+                .get_or_init(|| MoveHooks::compile(read_scripts(&scripts_dir())))
+                .run(task, from)
+        });
+    }
+
+    fn task_to_map(task: &Task) -> Map {
+        let mut map = Map::new();
+        map.insert("id".into(), Dynamic::from(i64::from(task.id)));
+        map.insert("title".into(), Dynamic::from(task.title.clone()));
+        map.insert("status".into(), Dynamic::from(task.status.clone()));
+        map.insert(
+            "priority".into(),
+            task.priority.clone().map_or(Dynamic::UNIT, Dynamic::from),
+        );
+        let tags: Array = task.tags.iter().cloned().map(Dynamic::from).collect();
+        map.insert("tags".into(), Dynamic::from_array(tags));
+        map.insert("order".into(), Dynamic::from(i64::from(task.order)));
+        map.insert("content".into(), Dynamic::from(task.content.clone()));
+        map
+    }
+
+    /// 把脚本修改后的字段写回任务；id 不能修改，`with_status` 为 false 时也不改状态
+    fn apply_map(task: &mut Task, map: &Map, with_status: bool) -> Result<(), String> {
+        let text = |key: &str| -> Result<Option<String>, String> {
+            match map.get(key) {
+                None => Ok(None),
+                Some(value) if value.is_unit() => Ok(Some(String::new())),
+                Some(value) => value
+                    .clone()
+                    .into_string()
+                    .map(Some)
+                    .map_err(|_| format!("{} 应为字符串", key)),
+            }
+        };
+        if let Some(title) = text("title")?.filter(|title| !title.trim().is_empty()) {
+            task.title = title;
+        }
+        if with_status && let Some(status) = text("status")? {
+            task.status = status;
+        }
+        if let Some(priority) = text("priority")? {
+            task.priority = (!priority.is_empty()).then_some(priority);
+        }
+        if let Some(content) = text("content")?
+            && content != task.content
+        {
+            task.set_content(content);
+        }
+        if let Some(order) = map.get("order") {
+            let order = order.as_int().map_err(|_| "order 应为整数".to_string())?;
+            task.order = order.clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+        }
+        if let Some(tags) = map.get("tags") {
+            task.tags = tags
+                .clone()
+                .into_array()
+                .map_err(|_| "tags 应为数组".to_string())?
+                .into_iter()
+                .map(|tag| {
+                    tag.into_string()
+                        .map_err(|_| "tags 应为字符串数组".to_string())
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::models::{ProjectType, Status};
+
+        fn project() -> Project {
+            let mut project = Project::new(
+                "web".to_string(),
+                PathBuf::from("/tmp/web"),
+                ProjectType::Global,
+            );
+            project.statuses = vec![
+                Status::new("todo".to_string(), "待办".to_string()),
+                Status::new("done".to_string(), "完成".to_string()),
+            ];
+            for (id, title) in [(1, "plain"), (2, "🐛 login"), (3, "✨ search")] {
+                project
+                    .tasks
+                    .push(Task::new(id, title.to_string(), "todo".to_string()));
+            }
+            project
+        }
+
+        #[test]
+        fn scripts_update_tasks_and_report_messages() {
+            let source = r#"
+                let n = 0;
+                for t in tasks() {
+                    if t.title.starts_with("🐛") {
+                        t.priority = "high";
+                        t.tags.push("bug");
+                        t.status = "done";
+                        update(t);
+                        n += 1;
+                    }
+                }
+                notify(`${project_name()}: ${n}`);
+            "#;
+            let (updates, messages) = run(&project(), source).unwrap();
+            assert_eq!(messages, vec!["web: 1"]);
+            assert_eq!(updates.len(), 1);
+            let task = &updates[0];
+            assert_eq!(task.priority.as_deref(), Some("high"));
+            assert_eq!(task.tags, vec!["bug"]);
+            assert_eq!(task.status, "done");
+
+            assert!(
+                run(
+                    &project(),
+                    r#"let t = tasks()[0]; t.status = "x"; update(t);"#
+                )
+                .is_err()
+            );
+            assert!(run(&project(), "loop {}").is_err());
+        }
+
+        #[test]
+        fn readme_sort_by_emoji_example_runs() {
+            let source = r#"
+                let todo = tasks().filter(|t| t.status == "todo");
+                let emoji = todo.filter(|t| t.title[0] > '~');
+                let rest = todo.filter(|t| t.title[0] <= '~');
+                let n = 0;
+                for t in emoji + rest {
+                    n += 1;
+                    t.order = n * 1000;
+                    update(t);
+                }
+                notify(`排序了 ${n} 个任务`);
+            "#;
+            let (updates, messages) = run(&project(), source).unwrap();
+            let orders: Vec<(u32, i32)> = updates.iter().map(|t| (t.id, t.order)).collect();
+            assert_eq!(orders, vec![(2, 1000), (3, 2000), (1, 3000)]);
+            assert_eq!(messages, vec!["排序了 3 个任务"]);
+        }
+
+        #[test]
+        fn on_move_hook_rewrites_the_moved_task_only() {
+            let source = r#"
+                notify("top level code is not run by hooks");
+                fn on_move(task, from, to) {
+                    if to == "done" { task.tags.push(`from-${from}`); }
+                    task.status = "todo";
+                    task
+                }
+            "#;
+            let hooks = MoveHooks::compile(vec![
+                ("tag".to_string(), source.to_string()),
+                ("plain".to_string(), "let x = 1;".to_string()),
+                ("broken".to_string(), "fn on_move(".to_string()),
+            ]);
+            assert_eq!(hooks.hooks.len(), 1);
+
+            let mut task = Task::new(1, "x".to_string(), "done".to_string());
+            hooks.run(&mut task, "todo");
+            assert_eq!(task.tags, vec!["from-todo"]);
+            assert_eq!(task.status, "done");
+
+            // 编译后的钩子可以重复调用
+            hooks.run(&mut task, "doing");
+            assert_eq!(task.tags, vec!["from-todo", "from-doing"]);
+        }
+    }
+}
+
+#[cfg(not(feature = "scripting"))]
+mod engine {
+    use super::*;
+
+    const DISABLED: &str =
+        "编译时未启用 scripting 功能（cargo install helix-kanban --features scripting）";
+
+    pub fn run(_project: &Project, _source: &str) -> Result<(Vec<Task>, Vec<String>), String> {
+        Err(DISABLED.to_string())
+    }
+
+    pub fn write_back(_project: &Project, _updates: &[Task]) -> Result<(), String> {
+        Err(DISABLED.to_string())
+    }
+
+    pub fn run_move_hooks(_task: &mut Task, _from: &str) {}
+}
@@ -1,9 +1,16 @@
 /// 应用状态持久化
-use crate::ui::layout::SplitNode;
+use crate::app::ColumnLayout;
+use crate::ui::layout::{PruneReport, SplitNode};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// 自动保存最多每隔多久检查一次状态是否变化
+const AUTOSAVE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// 状态变化后等待多久写入（期间的后续变化合并为一次写入）
+const AUTOSAVE_DELAY: Duration = Duration::from_secs(2);
 
 /// 应用状态（用于持久化）
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +23,32 @@ pub struct AppState {
     pub selected_task_indices: HashMap<usize, usize>,
     /// 当前聚焦的面板ID
     pub focused_pane: usize,
+    /// 每个面板的列宽策略
+    #[serde(default)]
+    pub column_layouts: HashMap<usize, ColumnLayout>,
+    /// 锁定为只读的面板
+    #[serde(default)]
+    pub read_only_panes: HashSet<usize>,
+    /// 主页布局（`:home-set` 保存，`:home` / `Space w 0` 恢复）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home_layout: Option<HomeLayout>,
+    /// 项目选择器中折叠的分组
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub collapsed_project_groups: BTreeSet<String>,
+    /// 上次运行的版本（版本更新后启动时显示新功能说明）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_version: Option<String>,
+}
+
+/// 主页布局：用户指定的默认分屏，随时可以一键恢复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HomeLayout {
+    pub split_tree: SplitNode,
+    pub focused_pane: usize,
+    #[serde(default)]
+    pub column_layouts: HashMap<usize, ColumnLayout>,
+    #[serde(default)]
+    pub read_only_panes: HashSet<usize>,
 }
 
 impl Default for AppState {
@@ -25,13 +58,18 @@ impl Default for AppState {
             selected_columns: HashMap::new(),
             selected_task_indices: HashMap::new(),
             focused_pane: 0,
+            column_layouts: HashMap::new(),
+            read_only_panes: HashSet::new(),
+            home_layout: None,
+            collapsed_project_groups: BTreeSet::new(),
+            last_run_version: None,
         }
     }
 }
 
 /// 获取状态文件路径
 /// All platforms: ~/.kanban/state.toml
-fn get_state_file_path() -> PathBuf {
+pub fn get_state_file_path() -> PathBuf {
     let home_dir = std::env::var("HOME")
         .or_else(|_| std::env::var("USERPROFILE"))
         .expect("Failed to get home directory");
@@ -43,9 +81,16 @@ fn reload_all_pane_projects(app: &mut crate::app::App) {
     fn reload_node_projects(node: &mut SplitNode, app: &mut crate::app::App) {
         match node {
             SplitNode::Leaf { project_id, .. } => {
-                if let Some(project_name) = project_id {
+                if let Some(id) = project_id {
+                    // 旧版本状态文件按项目名记录面板项目，迁移为项目 ID
+                    if !app.projects.iter().any(|p| &p.id == id)
+                        && let Some(project) = app.projects.iter().find(|p| &p.name == id)
+                    {
+                        *id = project.id.clone();
+                    }
+
                     // 检查项目是否存在，并重新加载项目数据
-                    if let Some(project) = app.projects.iter().find(|p| &p.name == project_name) {
+                    if let Some(project) = app.projects.iter().find(|p| &p.id == id) {
                         let project_path = project.path.clone();
                         let project_type = project.project_type;
 
@@ -54,9 +99,7 @@ fn reload_all_pane_projects(app: &mut crate::app::App) {
                             crate::fs::load_project_with_type(&project_path, project_type)
                         {
                             // 更新项目列表中的数据
-                            if let Some(idx) =
-                                app.projects.iter().position(|p| &p.name == project_name)
-                            {
+                            if let Some(idx) = app.projects.iter().position(|p| &p.id == id) {
                                 app.projects[idx] = reloaded_project;
                             }
                         }
@@ -86,11 +129,20 @@ pub fn extract_state(app: &crate::app::App) -> AppState {
         focused_pane: app.focused_pane,
         selected_columns: app.selected_column.clone(),
         selected_task_indices: app.selected_task_index.clone(),
+        column_layouts: app.column_layouts.clone(),
+        read_only_panes: app.read_only_panes.clone(),
+        home_layout: app.home_layout.clone(),
+        collapsed_project_groups: app.collapsed_project_groups.clone(),
+        last_run_version: Some(crate::changelog::current_version().to_string()),
     }
 }
 
 /// 保存状态到文件
 pub fn save_state(state: &AppState) -> Result<()> {
+    // 安全模式不覆盖原状态文件，退出后仍可检查或手动修复
+    if crate::safe_mode::is_enabled() {
+        return Ok(());
+    }
     let state_path = get_state_file_path();
 
     // 确保目录存在
@@ -100,11 +152,54 @@ pub fn save_state(state: &AppState) -> Result<()> {
 
     let toml = toml::to_string_pretty(state)
         .map_err(|e| anyhow::anyhow!("Failed to serialize state: {}", e))?;
-    std::fs::write(state_path, toml)?;
+    crate::fs::atomic::write_atomic(&state_path, toml)?;
 
     Ok(())
 }
 
+/// 会话中的状态自动保存：布局或选中项变化后限速写入，崩溃或终端被关闭时不丢失布局
+#[derive(Debug, Default)]
+pub struct Autosave {
+    /// 最后一次保存的状态（序列化后比较）
+    saved: Option<String>,
+    /// 检测到未保存变化的时间
+    changed_at: Option<Instant>,
+    checked_at: Option<Instant>,
+}
+
+impl Autosave {
+    /// 记录已保存的状态
+    pub fn mark_saved(&mut self, state: &AppState) {
+        self.saved = toml::to_string(state).ok();
+        self.changed_at = None;
+    }
+
+    /// 主循环每轮调用；状态变化并等待 `AUTOSAVE_DELAY` 后返回需要保存的状态
+    pub fn poll(&mut self, now: Instant, extract: impl FnOnce() -> AppState) -> Option<AppState> {
+        if self
+            .checked_at
+            .is_some_and(|checked| now.duration_since(checked) < AUTOSAVE_CHECK_INTERVAL)
+        {
+            return None;
+        }
+        self.checked_at = Some(now);
+
+        let state = extract();
+        let serialized = toml::to_string(&state).ok()?;
+        if self.saved.as_ref() == Some(&serialized) {
+            self.changed_at = None;
+            return None;
+        }
+        let changed_at = *self.changed_at.get_or_insert(now);
+        if now.duration_since(changed_at) < AUTOSAVE_DELAY {
+            return None;
+        }
+        self.saved = Some(serialized);
+        self.changed_at = None;
+        Some(state)
+    }
+}
+
 /// 从文件加载状态
 pub fn load_state() -> Result<AppState> {
     let state_path = get_state_file_path();
@@ -128,9 +223,47 @@ pub fn apply_state(app: &mut crate::app::App, state: AppState) {
     // 重新加载所有面板中的项目数据
     reload_all_pane_projects(app);
 
-    // 恢复选中状态
+    // 移除项目已不存在的面板和重复的面板，避免一直显示"项目未找到"
+    let mut report = PruneReport::default();
+    let tree = std::mem::replace(&mut app.split_tree, SplitNode::new_leaf(0));
+    let projects = &app.projects;
+    if let Some(tree) = tree.pruned(
+        &|id| projects.iter().any(|p| p.id == id),
+        &mut HashSet::new(),
+        &mut report,
+    ) {
+        app.split_tree = tree;
+    }
+    let all_panes: HashSet<usize> = app.split_tree.collect_pane_ids().into_iter().collect();
+
+    // 恢复选中状态（只保留仍存在的面板）
     app.selected_column = state.selected_columns;
     app.selected_task_index = state.selected_task_indices;
+    app.column_layouts = state.column_layouts;
+    app.read_only_panes = state.read_only_panes;
+    app.home_layout = state.home_layout;
+    app.collapsed_project_groups = state.collapsed_project_groups;
+    app.selected_column
+        .retain(|pane, _| all_panes.contains(pane));
+    app.selected_task_index
+        .retain(|pane, _| all_panes.contains(pane));
+    app.column_layouts
+        .retain(|pane, _| all_panes.contains(pane));
+    app.read_only_panes.retain(|pane| all_panes.contains(pane));
+
+    if !report.is_empty() {
+        let mut pruned = Vec::new();
+        if report.missing_projects > 0 {
+            pruned.push(format!("{} 个项目已不存在的面板", report.missing_projects));
+        }
+        if report.duplicate_panes > 0 {
+            pruned.push(format!("{} 个重复的面板", report.duplicate_panes));
+        }
+        app.show_notification(
+            format!("已清理保存的布局：移除{}", pruned.join("、")),
+            crate::app::NotificationLevel::Warning,
+        );
+    }
 
     // 恢复聚焦面板（确保面板存在）
     let all_panes = app.split_tree.collect_pane_ids();
@@ -145,3 +278,96 @@ pub fn apply_state(app: &mut crate::app::App, state: AppState) {
         app.next_pane_id = max_id + 1;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tests::test_app;
+
+    fn leaf(id: usize, project: &str) -> Box<SplitNode> {
+        Box::new(SplitNode::Leaf {
+            id,
+            project_id: Some(project.to_string()),
+        })
+    }
+
+    #[test]
+    fn apply_state_prunes_missing_projects_and_duplicate_panes() {
+        let mut app = test_app();
+        let state = AppState {
+            split_tree: SplitNode::Horizontal {
+                left: Box::new(SplitNode::Vertical {
+                    top: leaf(0, "alpha"),
+                    bottom: leaf(1, "deleted"),
+                    ratio: 0.5,
+                }),
+                right: Box::new(SplitNode::Vertical {
+                    top: leaf(2, "beta"),
+                    bottom: leaf(0, "gamma"),
+                    ratio: 0.5,
+                }),
+                ratio: 0.3,
+            },
+            selected_columns: HashMap::from([(0, 1), (1, 2), (2, 0)]),
+            selected_task_indices: HashMap::from([(1, 4)]),
+            focused_pane: 1,
+            column_layouts: HashMap::new(),
+            read_only_panes: HashSet::from([1, 2]),
+            home_layout: None,
+            collapsed_project_groups: BTreeSet::new(),
+            last_run_version: None,
+        };
+
+        apply_state(&mut app, state);
+
+        // 空出一侧的分割折叠，剩下的两个面板直接左右分割
+        match &app.split_tree {
+            SplitNode::Horizontal { left, right, ratio } => {
+                assert!(matches!(**left, SplitNode::Leaf { id: 0, .. }));
+                assert!(matches!(**right, SplitNode::Leaf { id: 2, .. }));
+                assert_eq!(*ratio, 0.3);
+            }
+            other => panic!("unexpected tree: {:?}", other),
+        }
+        assert_eq!(app.selected_column, HashMap::from([(0, 1), (2, 0)]));
+        assert!(app.selected_task_index.is_empty());
+        assert_eq!(app.read_only_panes, HashSet::from([2]));
+        assert_eq!(app.focused_pane, 0);
+        assert_eq!(app.next_pane_id, 3);
+        let message = &app.notification.as_ref().unwrap().message;
+        assert!(message.contains("1 个项目已不存在的面板"));
+        assert!(message.contains("1 个重复的面板"));
+    }
+
+    #[test]
+    fn autosave_waits_for_changes_to_settle() {
+        let mut autosave = Autosave::default();
+        let start = Instant::now();
+        let state = AppState::default();
+        autosave.mark_saved(&state);
+        assert!(autosave.poll(start, AppState::default).is_none());
+
+        let changed = || AppState {
+            focused_pane: 3,
+            ..AppState::default()
+        };
+        // 变化刚出现时不写入；检查间隔内不重复提取状态
+        assert!(
+            autosave
+                .poll(start + Duration::from_secs(1), changed)
+                .is_none()
+        );
+        assert!(
+            autosave
+                .poll(start + Duration::from_millis(1500), || unreachable!())
+                .is_none()
+        );
+        let saved = autosave.poll(start + Duration::from_secs(3), changed);
+        assert_eq!(saved.map(|s| s.focused_pane), Some(3));
+        assert!(
+            autosave
+                .poll(start + Duration::from_secs(5), changed)
+                .is_none()
+        );
+    }
+}
@@ -25,11 +25,7 @@ fn render_task_card(_ctx: &Context, task: &Task, is_selected: bool) -> Node {
     // Truncate content preview
     let content_preview = if !task.content.is_empty() {
         let max_len = 100;
-        if task.content.len() > max_len {
-            format!("{}...", &task.content[..max_len])
-        } else {
-            task.content.clone()
-        }
+        crate::ui::width::truncate_to_width(&task.content, max_len)
     } else {
         String::new()
     };
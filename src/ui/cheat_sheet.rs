@@ -0,0 +1,90 @@
+/// 键位速查浮层（Ctrl+/）：只列出当前模式下可用的键位
+use crate::app::App;
+use crate::input::{KeyHint, cheat_sheet};
+use crate::ui::width::{display_width, truncate_to_width};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+/// 每列的宽度（键位 + 说明）
+const COLUMN_WIDTH: usize = 30;
+/// 为底部通知栏预留的行数
+const NOTIFICATION_HEIGHT: u16 = 3;
+
+/// 在右下角渲染速查浮层，条目较多时分多列
+pub fn render(f: &mut Frame, area: Rect, app: &App) {
+    let (title, hints) = cheat_sheet(app);
+    if hints.is_empty() || area.width < 20 || area.height < NOTIFICATION_HEIGHT + 4 {
+        return;
+    }
+
+    let max_rows = (area.height - NOTIFICATION_HEIGHT - 2) as usize;
+    let max_columns = ((area.width as usize).saturating_sub(2) / COLUMN_WIDTH).max(1);
+    let columns = hints.len().div_ceil(max_rows).clamp(1, max_columns);
+    let rows = hints.len().div_ceil(columns);
+    let key_width = hints
+        .iter()
+        .map(|hint| display_width(&hint.keys))
+        .max()
+        .unwrap_or(0)
+        .min(12);
+
+    let width = ((columns * COLUMN_WIDTH + 2) as u16).min(area.width);
+    let height = (rows as u16 + 2).min(area.height - NOTIFICATION_HEIGHT);
+    let popup = Rect {
+        x: area.x + area.width - width,
+        y: area.y + area.height - NOTIFICATION_HEIGHT - height,
+        width,
+        height,
+    };
+
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let spans = (0..columns)
+                .filter_map(|col| hints.get(row + col * rows))
+                .flat_map(|hint| hint_spans(hint, key_width))
+                .collect::<Vec<_>>();
+            Line::from(spans)
+        })
+        .collect();
+
+    f.render_widget(Clear, popup);
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .title(format!(" 速查 · {} ", title))
+                .title_bottom(Line::from(" Ctrl+/ 关闭 ").right_aligned())
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Rgb(136, 192, 208)))
+                .style(Style::default().bg(Color::Rgb(40, 40, 40))),
+        ),
+        popup,
+    );
+}
+
+/// 一条键位：键名左对齐到统一宽度，说明截断到列宽
+fn hint_spans(hint: &KeyHint, key_width: usize) -> Vec<Span<'static>> {
+    let keys = truncate_to_width(&hint.keys, key_width);
+    let padding = key_width.saturating_sub(display_width(&keys));
+    let description_width = COLUMN_WIDTH.saturating_sub(key_width + 3);
+    let description = truncate_to_width(&hint.description, description_width);
+    let trailing = description_width.saturating_sub(display_width(&description));
+
+    vec![
+        Span::raw(" "),
+        Span::styled(
+            format!("{}{}", keys, " ".repeat(padding)),
+            Style::default()
+                .fg(Color::Rgb(235, 203, 139))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("{}{} ", description, " ".repeat(trailing)),
+            Style::default().fg(Color::Rgb(216, 222, 233)),
+        ),
+    ]
+}
@@ -1,6 +1,9 @@
 /// 命令补全 UI - 类似 Helix 的命令提示
 use crate::app::App;
 use crate::input::CommandDef;
+use crate::ui::fuzzy::{fuzzy_match, highlight_spans};
+use crate::ui::text_field::TextField;
+use crate::ui::width::{display_width, truncate_to_width};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Rect};
 use ratatui::style::{Color, Modifier, Style};
@@ -8,16 +11,18 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 /// 渲染命令补全提示
-#[allow(dead_code)]
 pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
-    let input = &app.command_input;
-
-    // 获取匹配的命令列表
-    let matches = app.command_registry.find_matches(input);
-
-    if matches.is_empty() {
-        return;
-    }
+    let field = app.command_input.clone();
+    let input = field.value();
+
+    // 只用第一个词匹配命令名，后面的部分是参数
+    let name = input.split_whitespace().next().unwrap_or("");
+    let has_args = input.trim_start().contains(' ');
+    let matches = if has_args {
+        app.command_registry.find_exact(name).into_iter().collect()
+    } else {
+        app.command_registry.find_matches(name)
+    };
 
     // 确保有选中的索引，如果没有则默认选中第一项
     if app.completion_selected_index.is_none()
@@ -26,79 +31,110 @@ pub fn render(f: &mut Frame, area: Rect, app: &mut App) {
         app.completion_selected_index = Some(0);
     }
 
-    // 计算布局：上方输入框，下方补全列表
+    // 计算布局：最底部一行是命令行，上方补全列表
     let total_height = area.height.min(20); // 最多20行
-    let input_height = 2; // 输入框占2行
-    let list_height = total_height - input_height;
+    let input_height = 1.min(total_height);
+    let list_height = if matches.is_empty() {
+        0
+    } else {
+        total_height - input_height
+    };
 
     let input_area = Rect {
         x: area.x,
-        y: area.y,
+        y: area.y + area.height - input_height,
         width: area.width,
         height: input_height,
     };
 
     let completion_area = Rect {
         x: area.x,
-        y: area.y + input_height,
+        y: input_area.y - list_height,
         width: area.width,
         height: list_height,
     };
 
     // 渲染输入框
-    render_command_input(f, input_area, input);
+    render_command_input(f, input_area, &field);
+
+    if matches.is_empty() {
+        return;
+    }
 
-    // 渲染命令列表
-    render_command_list(f, completion_area, &matches, app.completion_selected_index);
+    // 渲染命令列表（参数模式下不高亮）
+    let pattern = if has_args { "" } else { name };
+    render_command_list(
+        f,
+        completion_area,
+        &matches,
+        app.completion_selected_index,
+        pattern,
+        |cmd| app.command_registry.is_user_alias(cmd.name),
+    );
 
     // 如果只有一个匹配且是精确匹配，显示详细信息
     if matches.len() == 1
-        || (!input.is_empty()
+        || (!name.is_empty()
             && matches
                 .iter()
-                .any(|cmd| cmd.name == input || cmd.aliases.contains(&input.as_str())))
+                .any(|cmd| cmd.name == name || cmd.aliases.contains(&name)))
     {
         let cmd = matches[0];
         render_command_detail(f, area, cmd);
     }
 }
 
-/// 渲染命令输入框
-#[allow(dead_code)]
-fn render_command_input(f: &mut Frame, area: Rect, input: &str) {
-    // 清空区域
+/// 渲染命令行（Helix 风格，占用屏幕最底部一行）
+fn render_command_input(f: &mut Frame, area: Rect, input: &TextField) {
     f.render_widget(Clear, area);
 
-    // 创建输入框
-    let block = Block::default()
-        .title(" 输入命令 ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(136, 192, 208)))
-        .border_type(ratatui::widgets::BorderType::Rounded)
-        .style(Style::default().bg(Color::Rgb(46, 52, 64)));
-
-    f.render_widget(block.clone(), area);
-
-    let inner = block.inner(area);
-
-    // 显示当前输入
-    let input_text = format!(":{}", input);
-    let line = Line::from(vec![Span::raw(input_text)]);
-
-    let paragraph = Paragraph::new(line)
-        .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Left);
+    let bg = Style::default().bg(Color::Rgb(46, 52, 64));
+    f.render_widget(Block::default().style(bg), area);
+
+    // 右侧按键提示，空间不足时省略
+    let hint = " Tab 补全 · Enter 执行 · Esc 取消 ";
+    let hint_width = display_width(hint) as u16;
+    let show_hint = area.width > hint_width + 20;
+    let input_width = if show_hint {
+        area.width - hint_width
+    } else {
+        area.width
+    };
 
-    f.render_widget(paragraph, inner);
+    let line = input.line(
+        ":",
+        input_width as usize,
+        bg.fg(Color::Rgb(236, 239, 244)),
+        true,
+    );
+    f.render_widget(
+        Paragraph::new(line).alignment(Alignment::Left),
+        Rect {
+            width: input_width,
+            ..area
+        },
+    );
+
+    if show_hint {
+        f.render_widget(
+            Paragraph::new(hint).style(bg.fg(Color::Rgb(129, 161, 193))),
+            Rect {
+                x: area.x + input_width,
+                width: hint_width,
+                ..area
+            },
+        );
+    }
 }
 
 /// 渲染命令列表（底部多列布局）
-#[allow(dead_code)]
 fn render_command_list(
     f: &mut Frame,
     area: Rect,
     commands: &[&CommandDef],
     selected_index: Option<usize>,
+    pattern: &str,
+    is_user_alias: impl Fn(&CommandDef) -> bool,
 ) {
     // 清空区域
     f.render_widget(Clear, area);
@@ -131,11 +167,18 @@ fn render_command_list(
                 // 计算在显示列表中的绝对索引
                 let absolute_idx = idx;
 
-                // 命令名（主名称或第一个别名）
-                let display_name = if cmd.aliases.is_empty() {
-                    cmd.name
+                // 命令名（主名称或第一个别名，优先显示能匹配输入的那个）
+                let display_name = if pattern.is_empty() {
+                    cmd.aliases.first().copied().unwrap_or(cmd.name)
                 } else {
-                    cmd.aliases[0]
+                    std::iter::once(cmd.name)
+                        .chain(cmd.aliases.iter().copied())
+                        .filter_map(|candidate| {
+                            fuzzy_match(pattern, candidate).map(|m| (m.score, candidate))
+                        })
+                        .max_by_key(|(score, _)| *score)
+                        .map(|(_, candidate)| candidate)
+                        .unwrap_or(cmd.name)
                 };
 
                 // 检查是否被选中
@@ -151,11 +194,41 @@ fn render_command_list(
                     Style::default().fg(Color::Rgb(136, 192, 208))
                 };
 
-                // 添加命令名
+                // 用户别名在名称后标记 *
+                let marker = if is_user_alias(cmd) { "*" } else { "" };
+
+                // 添加命令名（匹配字符高亮），截断并补齐到列宽
+                let label = truncate_to_width(display_name, column_width - 1 - marker.len());
+                let indices = fuzzy_match(pattern, &label)
+                    .map(|m| m.indices)
+                    .unwrap_or_default();
+                spans.extend(highlight_spans(
+                    &label,
+                    &indices,
+                    style,
+                    style
+                        .fg(if is_selected {
+                            Color::Rgb(191, 97, 106)
+                        } else {
+                            Color::Rgb(235, 203, 139)
+                        })
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                ));
+                spans.push(Span::styled(
+                    marker,
+                    style.fg(if is_selected {
+                        Color::Black
+                    } else {
+                        Color::Rgb(180, 142, 173)
+                    }),
+                ));
                 spans.push(Span::styled(
-                    format!("{:<width$}", display_name, width = column_width - 1),
+                    " ".repeat(
+                        (column_width - 1).saturating_sub(display_width(&label) + marker.len()),
+                    ),
                     style,
                 ));
+                spans.push(Span::raw(" "));
             } else {
                 spans.push(Span::raw(" ".repeat(column_width)));
             }
@@ -169,7 +242,6 @@ fn render_command_list(
 }
 
 /// 渲染命令详细信息（中央弹窗）
-#[allow(dead_code)]
 fn render_command_detail(f: &mut Frame, area: Rect, cmd: &CommandDef) {
     // 创建居中弹窗
     let popup_width = 60.min(area.width.saturating_sub(4));
@@ -9,7 +9,8 @@ use ratatui::widgets::{Block, Borders, Clear, List, ListItem};
 struct CommandItem {
     key: &'static str,
     label: &'static str,
-    color: Option<Color>,
+    /// 优先级菜单项显示对应的优先级标记
+    priority: Option<&'static str>,
 }
 
 /// 渲染空格命令菜单（小型弹窗样式）
@@ -17,12 +18,6 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
     // 渲染半透明背景遮罩
     render_backdrop(f, area);
 
-    // 创建小型居中弹窗（固定宽度40，高度自适应内容）
-    let popup_area = centered_rect_fixed(40, 20, area);
-
-    // 清空弹窗区域
-    f.render_widget(Clear, popup_area);
-
     use crate::app::MenuState;
 
     // 根据菜单状态显示不同的命令列表
@@ -44,27 +39,27 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 CommandItem {
                     key: "o",
                     label: "打开项目",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "n",
                     label: "新建本地项目 [L]",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "N",
                     label: "新建全局项目 [G]",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
             ];
 
@@ -74,36 +69,46 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 commands.push(CommandItem {
                     key: "D",
                     label: "删除项目文件",
-                    color: None,
+                    priority: None,
                 });
             } else {
                 // 全局项目或其他目录的本地项目：可以软删除或硬删除
                 commands.push(CommandItem {
                     key: "d",
                     label: "隐藏项目（软删除）",
-                    color: None,
+                    priority: None,
                 });
                 commands.push(CommandItem {
                     key: "D",
                     label: "删除项目文件",
-                    color: None,
+                    priority: None,
                 });
             }
 
             commands.push(CommandItem {
                 key: "",
                 label: "",
-                color: None,
+                priority: None,
             });
             commands.push(CommandItem {
                 key: "r",
                 label: "重命名项目",
-                color: None,
+                priority: None,
+            });
+            commands.push(CommandItem {
+                key: "s",
+                label: "项目设置",
+                priority: None,
             });
             commands.push(CommandItem {
                 key: "i",
                 label: "复制项目信息",
-                color: None,
+                priority: None,
+            });
+            commands.push(CommandItem {
+                key: "f",
+                label: "在文件管理器中打开",
+                priority: None,
             });
 
             (commands, " 项目操作 ")
@@ -113,57 +118,72 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 CommandItem {
                     key: "w",
                     label: "下一个窗口",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "v",
                     label: "垂直分屏",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "s",
                     label: "水平分屏",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "q",
                     label: "关闭当前窗口",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "m",
                     label: "最大化/恢复",
-                    color: None,
+                    priority: None,
+                },
+                CommandItem {
+                    key: "g",
+                    label: "抓取并交换项目",
+                    priority: None,
+                },
+                CommandItem {
+                    key: "r",
+                    label: "锁定/解锁（只读）",
+                    priority: None,
+                },
+                CommandItem {
+                    key: "0",
+                    label: "恢复主页布局",
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "h",
                     label: "聚焦左侧",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "l",
                     label: "聚焦右侧",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "k",
                     label: "聚焦上方",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "j",
                     label: "聚焦下方",
-                    color: None,
+                    priority: None,
                 },
             ],
             " 窗口操作 ",
@@ -173,132 +193,173 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 CommandItem {
                     key: "a",
                     label: "新建任务",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "e",
                     label: "编辑任务",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "E",
                     label: "用编辑器编辑",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "v",
                     label: "预览任务",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "V",
                     label: "外部预览",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "t",
                     label: "编辑标签",
-                    color: None,
+                    priority: None,
+                },
+                CommandItem {
+                    key: "f",
+                    label: "编辑字段",
+                    priority: None,
+                },
+                CommandItem {
+                    key: "n",
+                    label: "追加笔记",
+                    priority: None,
                 },
                 CommandItem {
                     key: "Y",
                     label: "复制到剪贴板",
-                    color: None,
+                    priority: None,
+                },
+                CommandItem {
+                    key: "P",
+                    label: "从剪贴板新建",
+                    priority: None,
+                },
+                CommandItem {
+                    key: ">",
+                    label: "发送到面板",
+                    priority: None,
                 },
                 CommandItem {
                     key: "d",
                     label: "删除任务",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "h",
                     label: "优先级：高",
-                    color: Some(Color::Red),
+                    priority: Some("high"),
                 },
                 CommandItem {
                     key: "m",
                     label: "优先级：中",
-                    color: Some(Color::Yellow),
+                    priority: Some("medium"),
                 },
                 CommandItem {
                     key: "l",
                     label: "优先级：低",
-                    color: Some(Color::Green),
+                    priority: Some("low"),
                 },
                 CommandItem {
-                    key: "n",
+                    key: "x",
                     label: "优先级：无",
-                    color: None,
+                    priority: None,
+                },
+                CommandItem {
+                    key: "c",
+                    label: "卡片颜色...",
+                    priority: None,
                 },
             ],
             " 任务操作 ",
         ),
+        Some(MenuState::Color) => (
+            super::theme::CARD_COLORS
+                .into_iter()
+                .map(|(_, key, label)| CommandItem {
+                    key,
+                    label,
+                    priority: None,
+                })
+                .chain([CommandItem {
+                    key: "x",
+                    label: "清除颜色",
+                    priority: None,
+                }])
+                .collect(),
+            " 卡片颜色 ",
+        ),
         Some(MenuState::Status) => (
             vec![
                 CommandItem {
                     key: "a",
                     label: "创建新状态",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "r",
                     label: "重命名状态",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "e",
                     label: "编辑显示名",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "h",
                     label: "左移状态列",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "l",
                     label: "右移状态列",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "H",
                     label: "移到最左侧",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "L",
                     label: "移到最右侧",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "d",
                     label: "删除状态",
-                    color: None,
+                    priority: None,
                 },
             ],
             " 状态管理 ",
@@ -308,68 +369,74 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                 CommandItem {
                     key: "f",
                     label: "快速切换项目",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "p",
                     label: "项目操作...",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "w",
                     label: "窗口操作...",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "t",
                     label: "任务操作...",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "s",
                     label: "状态管理...",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "r",
                     label: "重新加载当前项目",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "R",
                     label: "重新加载所有项目",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "",
                     label: "",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "?",
                     label: "显示帮助",
-                    color: None,
+                    priority: None,
                 },
                 CommandItem {
                     key: "q",
                     label: "退出",
-                    color: None,
+                    priority: None,
                 },
             ],
             " 命令菜单 ",
         ),
     };
 
+    // 创建小型居中弹窗（固定宽度40，高度至少20，命令较多时随内容增高）
+    let popup_area = centered_rect_fixed(40, (commands.len() as u16 + 2).max(20), area);
+
+    // 清空弹窗区域
+    f.render_widget(Clear, popup_area);
+
     // 获取有效命令索引映射（排除空行）
     let mut valid_cmd_indices: Vec<usize> = Vec::new();
     for (i, cmd) in commands.iter().enumerate() {
@@ -404,9 +471,20 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
                     Span::raw("  "),
                 ];
 
-                // 如果有颜色，添加彩色圆点
-                if let Some(color) = cmd.color {
-                    spans.push(Span::styled("● ", Style::default().fg(color)));
+                // 优先级菜单项添加优先级标记
+                if cmd.priority.is_some() {
+                    spans.push(super::theme::priority_span(cmd.priority));
+                }
+                // 颜色菜单项添加色块
+                if app.menu_state == Some(MenuState::Color) {
+                    let swatch = super::theme::CARD_COLORS
+                        .iter()
+                        .find(|(_, key, _)| *key == cmd.key)
+                        .and_then(|(name, _, _)| super::theme::card_color(name));
+                    spans.push(match swatch {
+                        Some(color) => Span::styled("██ ", Style::default().fg(color)),
+                        None => Span::raw("   "),
+                    });
                 }
 
                 spans.push(Span::styled(cmd.label, Style::default().fg(Color::White)));
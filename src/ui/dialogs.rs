@@ -1,5 +1,7 @@
 use crate::models::{Project, ProjectType};
+use crate::ui::fuzzy::{fuzzy_match, highlight_spans};
 use crate::ui::tags::tag_color;
+use crate::ui::width::display_width;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -7,9 +9,12 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
-use super::text_input::HelixTextArea;
+use super::text_field::TextField;
+use super::text_input::{HelixTextArea, InputAction};
+use crossterm::event::{KeyCode, KeyEvent};
 
 const PROJECT_GRID_CARD_HEIGHT: u16 = 6;
 const PROJECT_GRID_ORDER_STEP: i64 = 1000;
@@ -23,17 +28,24 @@ pub struct ProjectGridItem {
     pub task_count: usize,
     pub done_count: usize,
     pub is_current: bool,
+    pub pinned: bool,
     pub project_order: Option<i64>,
     pub tags: Vec<String>,
+    /// 项目标签颜色（`.kanban.toml` 的 `color`）
+    pub color: Option<String>,
+    /// 项目分组（`.kanban.toml` 的 `group`）
+    pub group: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProjectGridState {
     pub items: Vec<ProjectGridItem>,
     pub selected: usize,
-    pub filter: String,
+    pub filter: TextField,
     pub selecting: bool,
     pub columns: usize,
+    /// 折叠的分组：没有搜索关键字时只显示一张分组卡片
+    pub collapsed_groups: BTreeSet<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -55,6 +67,7 @@ pub enum ProjectGridOrderMove {
 pub fn project_grid_state_from_projects(
     projects: &[Project],
     current_project_path: Option<&Path>,
+    collapsed_groups: &BTreeSet<String>,
 ) -> ProjectGridState {
     let mut items: Vec<ProjectGridItem> = projects
         .iter()
@@ -62,15 +75,20 @@ pub fn project_grid_state_from_projects(
         .collect();
 
     sort_project_grid_items(&mut items);
-    let selected = items.iter().position(|item| item.is_current).unwrap_or(0);
 
-    ProjectGridState {
+    let mut state = ProjectGridState {
         items,
-        selected,
-        filter: String::new(),
+        selected: 0,
+        filter: TextField::default(),
         selecting: true,
         columns: 1,
+        collapsed_groups: collapsed_groups.clone(),
+    };
+    // 当前项目所在的分组折叠时选中该分组的卡片
+    if let Some(current) = state.items.iter().position(|item| item.is_current) {
+        state.selected = visible_position(&state, current);
     }
+    state
 }
 
 pub fn project_grid_item_from_project(
@@ -85,15 +103,25 @@ pub fn project_grid_item_from_project(
         task_count: project.tasks.len(),
         done_count: project_done_count(project),
         is_current: current_project_path == Some(project.path.as_path()),
+        pinned: project.pinned,
         project_order: project.project_order,
         tags: project.tags.clone(),
+        color: project.color.clone(),
+        group: project.group.clone(),
     }
 }
 
 pub fn sort_project_grid_items(items: &mut [ProjectGridItem]) {
     items.sort_by(|left, right| {
-        project_order_sort_key(left.project_order)
-            .cmp(&project_order_sort_key(right.project_order))
+        // 关联到当前目录的项目置顶，其余按分组排列（未分组的在最后）
+        right
+            .pinned
+            .cmp(&left.pinned)
+            .then_with(|| group_sort_key(left).cmp(&group_sort_key(right)))
+            .then_with(|| {
+                project_order_sort_key(left.project_order)
+                    .cmp(&project_order_sort_key(right.project_order))
+            })
             .then_with(|| {
                 project_type_sort_key(left.project_type)
                     .cmp(&project_type_sort_key(right.project_type))
@@ -109,19 +137,87 @@ pub fn filter_project_grid_items(items: &[ProjectGridItem], filter: &str) -> Vec
         return (0..items.len()).collect();
     }
 
-    items
+    let mut scored: Vec<(i64, usize)> = items
         .iter()
         .enumerate()
-        .filter_map(|(index, item)| {
-            if project_grid_item_matches(item, &query) {
-                Some(index)
-            } else {
-                None
-            }
+        .filter_map(|(index, item)| project_grid_item_score(item, &query).map(|s| (s, index)))
+        .collect();
+
+    // 得分高的在前，同分保持原有排序
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+    scored.into_iter().map(|(_, index)| index).collect()
+}
+
+/// 选择器中显示的项目：搜索结果，没有搜索关键字时折叠的分组只保留第一个项目（显示为分组卡片）
+pub fn visible_project_grid_items(state: &ProjectGridState) -> Vec<usize> {
+    let filtered = filter_project_grid_items(&state.items, state.filter.value());
+    if !state.filter.value().trim().is_empty() || state.collapsed_groups.is_empty() {
+        return filtered;
+    }
+
+    let mut shown_groups = BTreeSet::new();
+    filtered
+        .into_iter()
+        .filter(|&index| match collapsed_group(state, &state.items[index]) {
+            Some(group) => shown_groups.insert(group.to_string()),
+            None => true,
         })
         .collect()
 }
 
+/// 项目所在的折叠分组（置顶的项目不参与分组）
+fn collapsed_group<'a>(state: &ProjectGridState, item: &'a ProjectGridItem) -> Option<&'a str> {
+    item.group
+        .as_deref()
+        .filter(|group| !item.pinned && state.collapsed_groups.contains(*group))
+}
+
+/// 项目在显示卡片中的位置；所在分组折叠时为分组卡片的位置
+fn visible_position(state: &ProjectGridState, item_index: usize) -> usize {
+    let group = collapsed_group(state, &state.items[item_index]);
+    visible_project_grid_items(state)
+        .iter()
+        .position(|&index| {
+            index == item_index
+                || (group.is_some() && collapsed_group(state, &state.items[index]) == group)
+        })
+        .unwrap_or(0)
+}
+
+/// 该卡片是否显示为折叠的分组：返回分组名和分组中的项目
+pub fn collapsed_group_card<'a>(
+    state: &'a ProjectGridState,
+    item: &'a ProjectGridItem,
+) -> Option<(&'a str, Vec<&'a ProjectGridItem>)> {
+    if !state.filter.value().trim().is_empty() {
+        return None;
+    }
+    let group = collapsed_group(state, item)?;
+    let members = state
+        .items
+        .iter()
+        .filter(|other| collapsed_group(state, other) == Some(group))
+        .collect();
+    Some((group, members))
+}
+
+/// 折叠或展开选中项目所在的分组，选中项保持在该分组上；项目没有分组时返回 false
+pub fn toggle_project_grid_group(state: &mut ProjectGridState) -> bool {
+    let visible = visible_project_grid_items(state);
+    let Some(&item_index) = visible.get(state.selected) else {
+        return false;
+    };
+    let item = &state.items[item_index];
+    let Some(group) = item.group.clone().filter(|_| !item.pinned) else {
+        return false;
+    };
+    if !state.collapsed_groups.remove(&group) {
+        state.collapsed_groups.insert(group);
+    }
+    state.selected = visible_position(state, item_index);
+    true
+}
+
 pub fn navigate_project_grid(
     selected: usize,
     item_count: usize,
@@ -168,7 +264,7 @@ pub fn reordered_project_grid_state(
     state: &ProjectGridState,
     direction: ProjectGridOrderMove,
 ) -> Option<ProjectGridState> {
-    let filtered_indices = filter_project_grid_items(&state.items, &state.filter);
+    let filtered_indices = visible_project_grid_items(state);
     if filtered_indices.is_empty() || state.selected >= filtered_indices.len() {
         return None;
     }
@@ -191,6 +287,7 @@ pub fn reordered_project_grid_state(
         filter: state.filter.clone(),
         selecting: state.selecting,
         columns: state.columns,
+        collapsed_groups: state.collapsed_groups.clone(),
     })
 }
 
@@ -240,17 +337,35 @@ pub fn update_project_grid_item_tags(
         filter: state.filter.clone(),
         selecting: state.selecting,
         columns: state.columns,
+        collapsed_groups: state.collapsed_groups.clone(),
     }
 }
 
-fn project_grid_item_matches(item: &ProjectGridItem, query: &str) -> bool {
-    item.name.to_lowercase().contains(query)
-        || item.path_label.to_lowercase().contains(query)
-        || item.path.to_string_lossy().to_lowercase().contains(query)
-        || item
-            .tags
-            .iter()
-            .any(|tag| tag.to_lowercase().contains(query))
+/// 项目名、标签和分组使用模糊匹配，路径只做子串匹配（路径太长，模糊匹配噪音大）
+fn project_grid_item_score(item: &ProjectGridItem, query: &str) -> Option<i64> {
+    let name_score = fuzzy_match(query, &item.name).map(|m| m.score);
+    let tag_score = item
+        .tags
+        .iter()
+        .chain(item.group.as_ref())
+        .filter_map(|tag| fuzzy_match(query, tag))
+        .map(|m| m.score / 2)
+        .max();
+    let path_score = (item.path_label.to_lowercase().contains(query)
+        || item.path.to_string_lossy().to_lowercase().contains(query))
+    .then_some(0);
+
+    [name_score, tag_score, path_score]
+        .into_iter()
+        .flatten()
+        .max()
+}
+
+fn group_sort_key(item: &ProjectGridItem) -> (bool, String) {
+    match &item.group {
+        Some(group) => (false, group.to_lowercase()),
+        None => (true, String::new()),
+    }
 }
 
 fn project_order_sort_key(project_order: Option<i64>) -> (u8, i64) {
@@ -306,6 +421,95 @@ pub enum ConfirmAction {
     DeleteProject,
     HideProject,
     DeleteStatus,
+    RenumberTasks,
+    MigrateProject,
+    RevertExternalChanges,
+    /// 新任务与已有任务相近：确认后仍然创建，`duplicates` 是可直接打开的相近任务
+    CreateDuplicateTask {
+        input: String,
+        duplicates: Vec<u32>,
+    },
+}
+
+/// 输入对话框的用途，提交时按它处理（标题和提示只用于显示）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogPurpose {
+    /// 新建项目，Tab 切换工作流；`local` 为 false 时创建全局项目
+    CreateProject {
+        local: bool,
+        workflow: crate::fs::Workflow,
+    },
+    RenameProject,
+    ProjectSettings,
+    /// 新建任务（第一行为标题，其余为正文）
+    CreateTask,
+    /// 编辑选中任务的标题和正文
+    EditTask,
+    /// 在选中任务的 Notes 小节追加笔记
+    AddNote,
+    EditTags,
+    EditFields,
+    /// 从文件导入任务（输入文件路径）
+    ImportTasks,
+    CreateStatus,
+    /// 重命名当前列的状态
+    RenameStatus,
+}
+
+impl DialogPurpose {
+    /// 任务标题和正文的输入框更高
+    pub fn is_task_input(&self) -> bool {
+        matches!(self, DialogPurpose::CreateTask | DialogPurpose::EditTask)
+    }
+}
+
+/// 输入对话框的编辑器：名称、路径、标签等单行内容用 [`TextField`]，
+/// 任务正文、字段、项目设置等多行内容用 Helix 风格的编辑器
+pub enum DialogInput {
+    Line(TextField),
+    Text(Box<HelixTextArea>),
+}
+
+impl DialogInput {
+    /// 单行输入框，光标位于末尾
+    pub fn line(value: &str) -> Self {
+        DialogInput::Line(TextField::new(value))
+    }
+
+    /// 多行编辑器，`start_in_normal_mode` 为 false 时从 Insert 模式开始
+    pub fn text(value: String, show_line_numbers: bool, start_in_normal_mode: bool) -> Self {
+        DialogInput::Text(Box::new(HelixTextArea::new(
+            value,
+            show_line_numbers,
+            start_in_normal_mode,
+        )))
+    }
+
+    pub fn content(&self) -> String {
+        match self {
+            DialogInput::Line(field) => field.value().to_string(),
+            DialogInput::Text(textarea) => textarea.get_content(),
+        }
+    }
+
+    /// 处理按键：单行输入框 Enter 提交、Esc 取消，其余交给输入框编辑
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputAction {
+        match self {
+            DialogInput::Line(field) => match key.code {
+                KeyCode::Enter => InputAction::Submit,
+                KeyCode::Esc => InputAction::Cancel,
+                _ => {
+                    field.handle_key(key);
+                    InputAction::Continue
+                }
+            },
+            DialogInput::Text(textarea) => textarea.handle_key(key),
+        }
+    }
+
+    fn is_maximized(&self) -> bool {
+        matches!(self, DialogInput::Text(textarea) if textarea.is_maximized())
+    }
 }
 
 /// 对话框类型
@@ -314,7 +518,8 @@ pub enum DialogType {
     Input {
         title: String,
         prompt: String,
-        textarea: Box<HelixTextArea>,
+        input: DialogInput,
+        purpose: DialogPurpose,
     },
     ProjectGrid {
         title: String,
@@ -323,7 +528,7 @@ pub enum DialogType {
     ProjectTagsInput {
         title: String,
         prompt: String,
-        textarea: Box<HelixTextArea>,
+        input: DialogInput,
         project_path: PathBuf,
         project_name: String,
         grid_state: ProjectGridState,
@@ -338,14 +543,18 @@ pub enum DialogType {
 }
 
 /// 渲染居中的对话框
-pub fn render_dialog(f: &mut Frame, dialog: &mut DialogType) {
+pub fn render_dialog(
+    f: &mut Frame,
+    dialog: &mut DialogType,
+    spell: Option<&crate::ui::spell::SpellChecker>,
+) {
     // 渲染半透明背景遮罩
     render_backdrop(f, f.area());
 
     // 根据对话框类型和最大化状态决定大小
     let area = match dialog {
-        DialogType::Input { textarea, .. } | DialogType::ProjectTagsInput { textarea, .. } => {
-            if textarea.is_maximized() {
+        DialogType::Input { input, .. } | DialogType::ProjectTagsInput { input, .. } => {
+            if input.is_maximized() {
                 // 最大化：占据 90% 的屏幕空间
                 centered_rect(90, 90, f.area())
             } else {
@@ -364,14 +573,23 @@ pub fn render_dialog(f: &mut Frame, dialog: &mut DialogType) {
         DialogType::Input {
             title,
             prompt,
-            textarea,
-        } => render_input_dialog(f, area, title, prompt, textarea),
+            input,
+            purpose,
+        } => render_input_dialog(
+            f,
+            area,
+            title,
+            prompt,
+            input,
+            purpose.is_task_input(),
+            spell,
+        ),
         DialogType::ProjectTagsInput {
             title,
             prompt,
-            textarea,
+            input,
             ..
-        } => render_input_dialog(f, area, title, prompt, textarea),
+        } => render_input_dialog(f, area, title, prompt, input, false, None),
         DialogType::ProjectGrid { title, state } => {
             render_project_grid_dialog(f, area, title, state)
         }
@@ -396,11 +614,10 @@ fn render_input_dialog(
     area: Rect,
     title: &str,
     prompt: &str,
-    textarea: &mut HelixTextArea,
+    input: &mut DialogInput,
+    is_task_input: bool,
+    spell: Option<&crate::ui::spell::SpellChecker>,
 ) {
-    // 判断是否是任务输入（需要更大的输入框）
-    let is_task_input = title.contains("任务");
-
     let block = Block::default()
         .title(format!("  {}  ", title))
         .title_alignment(Alignment::Left)
@@ -412,8 +629,17 @@ fn render_input_dialog(
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    // 分割内部区域 - 任务输入使用更大的输入框
-    let chunks = if is_task_input {
+    // 分割内部区域 - 任务输入使用更大的输入框，单行输入框只占一行
+    let chunks = if let DialogInput::Line(_) = input {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // 提示文本
+                Constraint::Length(3), // 单行输入框
+                Constraint::Length(2), // 按键提示
+            ])
+            .split(inner)
+    } else if is_task_input {
         Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -445,7 +671,7 @@ fn render_input_dialog(
     };
     f.render_widget(prompt_text, chunks[0]);
 
-    // 输入框 - 使用 HelixTextArea 渲染
+    // 输入框
     let input_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Rgb(136, 192, 208))) // Nord cyan
@@ -454,9 +680,27 @@ fn render_input_dialog(
     let input_inner = input_block.inner(chunks[1]);
     f.render_widget(input_block, chunks[1]);
 
+    let textarea = match input {
+        DialogInput::Line(field) => {
+            let style = Style::default().fg(Color::Rgb(236, 239, 244));
+            let line = field.line("", input_inner.width as usize, style, true);
+            f.render_widget(Paragraph::new(line), input_inner);
+            let hint = Paragraph::new("Enter 提交 · Esc 取消")
+                .style(Style::default().fg(Color::Rgb(76, 86, 106)));
+            f.render_widget(hint, chunks[2]);
+            return;
+        }
+        DialogInput::Text(textarea) => textarea,
+    };
+
     // 渲染 TextArea
     textarea.render(f, input_inner);
 
+    // 任务标题中拼写错误的单词加下划线
+    if is_task_input && let Some(spell) = spell {
+        crate::ui::spell::underline_unknown(f.buffer_mut(), input_inner, spell);
+    }
+
     // 渲染模式指示器
     textarea.render_mode_indicator(f, chunks[2]);
 }
@@ -489,7 +733,7 @@ fn render_project_grid_dialog(
 
     render_project_grid_search(f, chunks[0], &state.filter, state.selecting);
 
-    let filtered_indices = filter_project_grid_items(&state.items, &state.filter);
+    let filtered_indices = visible_project_grid_items(state);
     if filtered_indices.is_empty() {
         state.selected = 0;
     } else {
@@ -508,9 +752,12 @@ fn render_project_grid_dialog(
 
     let count_text = format!("{}/{}", filtered_indices.len(), state.items.len());
     let count_area = Rect {
-        x: area.x + area.width.saturating_sub(count_text.len() as u16 + 3),
+        x: area.x
+            + area
+                .width
+                .saturating_sub(display_width(&count_text) as u16 + 3),
         y: area.y,
-        width: count_text.len() as u16 + 2,
+        width: display_width(&count_text) as u16 + 2,
         height: 1,
     };
     let count_paragraph =
@@ -518,7 +765,7 @@ fn render_project_grid_dialog(
     f.render_widget(count_paragraph, count_area);
 }
 
-fn render_project_grid_search(f: &mut Frame, area: Rect, filter: &str, selecting: bool) {
+fn render_project_grid_search(f: &mut Frame, area: Rect, filter: &TextField, selecting: bool) {
     let title = if selecting { " Select " } else { " Search " };
     let search_block = Block::default()
         .title(title)
@@ -528,22 +775,23 @@ fn render_project_grid_search(f: &mut Frame, area: Rect, filter: &str, selecting
     let search_inner = search_block.inner(area);
     f.render_widget(search_block, area);
 
-    let search_text = if filter.is_empty() {
-        "Search projects...".to_string()
-    } else {
-        format!("Search: {}", filter)
-    };
-
-    let search_style = if filter.is_empty() {
-        Style::default().fg(Color::Rgb(129, 161, 193))
-    } else {
-        Style::default().fg(Color::Rgb(136, 192, 208))
-    };
+    if filter.is_empty() && selecting {
+        f.render_widget(
+            Paragraph::new("Search projects...")
+                .style(Style::default().fg(Color::Rgb(129, 161, 193))),
+            search_inner,
+        );
+        return;
+    }
 
-    f.render_widget(
-        Paragraph::new(search_text).style(search_style),
-        search_inner,
+    // 搜索模式下显示光标，长关键字水平滚动
+    let line = filter.line(
+        "Search: ",
+        search_inner.width as usize,
+        Style::default().fg(Color::Rgb(136, 192, 208)),
+        !selecting,
     );
+    f.render_widget(Paragraph::new(line), search_inner);
 }
 
 fn render_project_grid_cards(
@@ -579,8 +827,15 @@ fn render_project_grid_cards(
             continue;
         }
 
-        if let Some(item) = state.items.get(*item_index) {
-            render_project_grid_card(f, card_area, item, filtered_index == state.selected);
+        let Some(item) = state.items.get(*item_index) else {
+            continue;
+        };
+        let is_selected = filtered_index == state.selected;
+        match collapsed_group_card(state, item) {
+            Some((group, members)) => {
+                render_project_grid_group_card(f, card_area, group, &members, is_selected)
+            }
+            None => render_project_grid_card(f, card_area, item, is_selected, state.filter.value()),
         }
     }
 }
@@ -594,7 +849,7 @@ fn render_project_grid_footer(
 ) {
     let help_text = if selecting {
         format!(
-            "h/j/k/l select  Enter open  H/J/K/L move card  t tags  / search  Esc close  [{}/{}]",
+            "h/j/k/l select  Enter open  Alt+Enter open copy  H/J/K/L move card  z fold group  t tags  / search  Esc close  [{}/{}]",
             filtered_count, total_count
         )
     } else {
@@ -609,7 +864,13 @@ fn render_project_grid_footer(
     f.render_widget(help_paragraph, area);
 }
 
-fn render_project_grid_card(f: &mut Frame, area: Rect, item: &ProjectGridItem, is_selected: bool) {
+fn render_project_grid_card(
+    f: &mut Frame,
+    area: Rect,
+    item: &ProjectGridItem,
+    is_selected: bool,
+    filter: &str,
+) {
     let border_color = if is_selected {
         Color::Rgb(136, 192, 208)
     } else {
@@ -621,42 +882,64 @@ fn render_project_grid_card(f: &mut Frame, area: Rect, item: &ProjectGridItem, i
         Style::default().bg(Color::Rgb(46, 52, 64))
     };
 
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
         .border_type(ratatui::widgets::BorderType::Rounded)
         .style(card_style);
+    if let Some(group) = item.group.as_deref().filter(|_| !item.pinned) {
+        block = block.title(Span::styled(
+            format!(" {} ", group),
+            Style::default().fg(Color::Rgb(129, 161, 193)),
+        ));
+    }
     let inner = block.inner(area);
     f.render_widget(block, area);
 
     let type_marker = match item.project_type {
+        ProjectType::Global if item.pinned => "[P]",
         ProjectType::Global => "[G]",
         ProjectType::Local => "[L]",
     };
     let current_marker = if item.is_current { " *" } else { "" };
 
+    // 项目名中与搜索匹配的字符高亮；设置了标签颜色时项目名使用该颜色
+    let label_color = item.color.as_deref().and_then(|c| c.parse::<Color>().ok());
+    let name_style = Style::default()
+        .fg(label_color.unwrap_or(Color::Rgb(236, 239, 244)))
+        .add_modifier(Modifier::BOLD);
+    let name_indices = fuzzy_match(filter.trim(), &item.name)
+        .map(|m| m.indices)
+        .unwrap_or_default();
+    let mut title_spans = vec![
+        Span::styled(
+            type_marker,
+            Style::default()
+                .fg(Color::Rgb(136, 192, 208))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+    ];
+    if let Some(color) = label_color {
+        title_spans.push(Span::styled("● ", Style::default().fg(color)));
+    }
+    title_spans.extend(highlight_spans(
+        &item.name,
+        &name_indices,
+        name_style,
+        name_style
+            .fg(Color::Rgb(235, 203, 139))
+            .add_modifier(Modifier::UNDERLINED),
+    ));
+    title_spans.push(Span::styled(
+        current_marker,
+        Style::default()
+            .fg(Color::Rgb(163, 190, 140))
+            .add_modifier(Modifier::BOLD),
+    ));
+
     let mut lines = vec![
-        Line::from(vec![
-            Span::styled(
-                type_marker,
-                Style::default()
-                    .fg(Color::Rgb(136, 192, 208))
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" "),
-            Span::styled(
-                item.name.clone(),
-                Style::default()
-                    .fg(Color::Rgb(236, 239, 244))
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                current_marker,
-                Style::default()
-                    .fg(Color::Rgb(163, 190, 140))
-                    .add_modifier(Modifier::BOLD),
-            ),
-        ]),
+        Line::from(title_spans),
         Line::from(vec![Span::styled(
             format!("Tasks: {}/{}", item.done_count, item.task_count),
             Style::default().fg(Color::Rgb(216, 222, 233)),
@@ -697,6 +980,53 @@ fn render_project_grid_card(f: &mut Frame, area: Rect, item: &ProjectGridItem, i
     f.render_widget(paragraph, inner);
 }
 
+/// 折叠的分组显示为一张卡片：分组名、项目数和项目名列表
+fn render_project_grid_group_card(
+    f: &mut Frame,
+    area: Rect,
+    group: &str,
+    members: &[&ProjectGridItem],
+    is_selected: bool,
+) {
+    let (border_color, background) = if is_selected {
+        (Color::Rgb(136, 192, 208), Color::Rgb(59, 66, 82))
+    } else {
+        (Color::Rgb(76, 86, 106), Color::Rgb(46, 52, 64))
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .border_type(ratatui::widgets::BorderType::Double)
+        .style(Style::default().bg(background));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let task_count: usize = members.iter().map(|item| item.task_count).sum();
+    let names: Vec<&str> = members.iter().map(|item| item.name.as_str()).collect();
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("▸ {}", group),
+            Style::default()
+                .fg(Color::Rgb(235, 203, 139))
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("{} projects · {} tasks", members.len(), task_count),
+            Style::default().fg(Color::Rgb(216, 222, 233)),
+        )),
+        Line::from(Span::styled(
+            names.join(", "),
+            Style::default().fg(Color::Rgb(129, 161, 193)),
+        )),
+    ];
+    f.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().bg(background))
+            .wrap(Wrap { trim: true }),
+        inner,
+    );
+}
+
 fn project_grid_columns(width: u16) -> usize {
     if width >= 100 {
         3
@@ -804,6 +1134,48 @@ fn render_confirm_dialog(
     f.render_widget(yes_button, button_chunks[2]);
 }
 
+/// 渲染后台任务的进度对话框（转圈 + 计数），不遮挡背后的看板
+pub fn render_progress_dialog(f: &mut Frame, job: &crate::job::Job) {
+    let screen = f.area();
+    let width = 36.min(screen.width);
+    let height = 5.min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!("  {}  ", job.kind.title()))
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Rgb(136, 192, 208))) // Nord frost
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(Color::Rgb(46, 52, 64))); // Nord background
+
+    let hint = if job.is_cancelled() {
+        "正在取消…"
+    } else {
+        "Esc 取消"
+    };
+    let lines = vec![
+        Line::from(Span::styled(
+            job.progress_label(),
+            Style::default()
+                .fg(Color::Rgb(216, 222, 233))
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(block);
+    f.render_widget(paragraph, area);
+}
+
 /// 创建一个居中的矩形区域
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -828,10 +1200,11 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 #[cfg(test)]
 mod tests {
     use super::{
-        ProjectGridItem, ProjectGridNavigation, ProjectGridOrderMove, ProjectGridState,
-        filter_project_grid_items, navigate_project_grid, normalize_project_tags,
-        reassigned_project_grid_orders, reorder_target_index, reordered_project_grid_state,
-        sort_project_grid_items,
+        DialogInput, ProjectGridItem, ProjectGridNavigation, ProjectGridOrderMove,
+        ProjectGridState, TextField, filter_project_grid_items, navigate_project_grid,
+        normalize_project_tags, reassigned_project_grid_orders, reorder_target_index,
+        reordered_project_grid_state, sort_project_grid_items, toggle_project_grid_group,
+        visible_project_grid_items,
     };
     use crate::models::ProjectType;
     use std::path::PathBuf;
@@ -851,8 +1224,11 @@ mod tests {
             task_count: 0,
             done_count: 0,
             is_current: false,
+            pinned: false,
             project_order,
             tags: tags.into_iter().map(ToString::to_string).collect(),
+            color: None,
+            group: None,
         }
     }
 
@@ -860,12 +1236,74 @@ mod tests {
         ProjectGridState {
             items,
             selected,
-            filter: String::new(),
+            filter: TextField::default(),
             selecting: true,
             columns: 2,
+            collapsed_groups: Default::default(),
         }
     }
 
+    #[test]
+    fn groups_sort_together_and_collapse_to_one_card() {
+        let grouped = |name: &str, group: Option<&str>| {
+            let mut item = item(
+                name,
+                ProjectType::Global,
+                &format!("/g/{}", name),
+                None,
+                vec![],
+            );
+            item.group = group.map(ToString::to_string);
+            item
+        };
+        let mut items = vec![
+            grouped("blog", None),
+            grouped("api", Some("work")),
+            grouped("garden", Some("personal")),
+            grouped("web", Some("work")),
+        ];
+        sort_project_grid_items(&mut items);
+        let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["garden", "api", "web", "blog"]);
+        // 分组名也参与搜索
+        assert_eq!(filter_project_grid_items(&items, "work"), vec![1, 2]);
+
+        let mut grid = state(items, 2);
+        assert!(toggle_project_grid_group(&mut grid));
+        assert_eq!(visible_project_grid_items(&grid), vec![0, 1, 3]);
+        // 选中项移到分组卡片上
+        assert_eq!(grid.selected, 1);
+        // 有搜索关键字时折叠的分组照常显示匹配的项目
+        grid.filter = TextField::new("web");
+        assert_eq!(visible_project_grid_items(&grid), vec![2]);
+        grid.filter = TextField::default();
+
+        assert!(toggle_project_grid_group(&mut grid));
+        assert_eq!(visible_project_grid_items(&grid).len(), 4);
+        grid.selected = 3;
+        assert!(!toggle_project_grid_group(&mut grid));
+    }
+
+    #[test]
+    fn sorts_pinned_projects_first() {
+        let mut pinned = item("work", ProjectType::Global, "/global/work", None, vec![]);
+        pinned.pinned = true;
+        let mut items = vec![
+            item(
+                "alpha",
+                ProjectType::Global,
+                "/global/alpha",
+                Some(0),
+                vec![],
+            ),
+            pinned,
+        ];
+
+        sort_project_grid_items(&mut items);
+
+        assert_eq!(items[0].name, "work");
+    }
+
     #[test]
     fn sorts_ordered_projects_before_unordered_with_stable_fallback() {
         let mut items = vec![
@@ -919,6 +1357,9 @@ mod tests {
             filter_project_grid_items(&items, "missing"),
             Vec::<usize>::new()
         );
+        // 模糊匹配：非连续字符
+        assert_eq!(filter_project_grid_items(&items, "bknd"), vec![1]);
+        assert_eq!(filter_project_grid_items(&items, "fe"), vec![0]);
     }
 
     #[test]
@@ -1029,4 +1470,28 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn single_line_dialog_input_edits_with_text_field_keys() {
+        use crate::ui::text_input::InputAction;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+
+        let mut input = DialogInput::line("backend api");
+        assert_eq!(
+            input.handle_key(key(KeyCode::Char('w'), KeyModifiers::CONTROL)),
+            InputAction::Continue
+        );
+        input.handle_key(key(KeyCode::Char('后'), KeyModifiers::NONE));
+        input.handle_key(key(KeyCode::Char('端'), KeyModifiers::NONE));
+        assert_eq!(input.content(), "backend 后端");
+        assert_eq!(
+            input.handle_key(key(KeyCode::Enter, KeyModifiers::NONE)),
+            InputAction::Submit
+        );
+        assert_eq!(
+            input.handle_key(key(KeyCode::Esc, KeyModifiers::NONE)),
+            InputAction::Cancel
+        );
+    }
 }
@@ -0,0 +1,185 @@
+/// 模糊匹配（fzy 风格打分），用于命令补全和项目选择器
+///
+/// 输入按字符顺序出现在候选中即可匹配（忽略大小写），连续匹配、
+/// 单词边界（开头、`-`/`_`/空格/`/` 之后、驼峰）得分更高，间隔会扣分。
+use ratatui::style::Style;
+use ratatui::text::Span;
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 12;
+const BONUS_BOUNDARY: i64 = 10;
+const BONUS_CAMEL: i64 = 8;
+const PENALTY_GAP: i64 = 1;
+const NONE: i64 = i64::MIN / 2;
+
+/// 匹配结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// 匹配到的字符位置（按字符计，不是字节）
+    pub indices: Vec<usize>,
+}
+
+/// 对候选文本进行模糊匹配，不匹配时返回 None
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(lower)
+        .collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            indices: Vec::new(),
+        });
+    }
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let lowered: Vec<char> = chars.iter().copied().map(lower).collect();
+    let (n, m) = (pattern.len(), chars.len());
+    if n > m || !is_subsequence(&pattern, &lowered) {
+        return None;
+    }
+
+    let bonus: Vec<i64> = (0..m).map(|j| position_bonus(&chars, j)).collect();
+
+    // d[i][j]：pattern[i] 恰好匹配在 j 处的最高分；best[i][j]：pattern[..=i] 在 candidate[..=j] 内的最高分
+    let mut d = vec![vec![NONE; m]; n];
+    let mut best = vec![vec![NONE; m]; n];
+
+    for i in 0..n {
+        let mut prev_best = NONE;
+        for j in 0..m {
+            if lowered[j] == pattern[i] {
+                let score = if i == 0 {
+                    // 首字符：越靠前越好
+                    SCORE_MATCH + bonus[j] * 2 - j as i64 * PENALTY_GAP
+                } else if j > 0 {
+                    let gapped = best[i - 1][j - 1].saturating_add(SCORE_MATCH + bonus[j]);
+                    let consecutive =
+                        d[i - 1][j - 1].saturating_add(SCORE_MATCH + BONUS_CONSECUTIVE);
+                    gapped.max(consecutive)
+                } else {
+                    NONE
+                };
+                d[i][j] = score.max(NONE);
+            }
+            best[i][j] = d[i][j].max(prev_best.saturating_sub(PENALTY_GAP));
+            prev_best = best[i][j];
+        }
+    }
+
+    let score = best[n - 1][m - 1];
+    if score <= NONE / 2 {
+        return None;
+    }
+
+    // 回溯出匹配位置
+    let mut indices = vec![0; n];
+    let mut match_required = false;
+    let mut j = m;
+    for i in (0..n).rev() {
+        while j > 0 {
+            j -= 1;
+            if d[i][j] > NONE / 2 && (match_required || d[i][j] == best[i][j]) {
+                match_required = i > 0
+                    && j > 0
+                    && d[i - 1][j - 1] > NONE / 2
+                    && d[i][j] == d[i - 1][j - 1] + SCORE_MATCH + BONUS_CONSECUTIVE;
+                indices[i] = j;
+                break;
+            }
+        }
+    }
+
+    Some(FuzzyMatch { score, indices })
+}
+
+/// 按匹配位置拆分文本，匹配字符使用高亮样式
+pub fn highlight_spans(
+    text: &str,
+    indices: &[usize],
+    base: Style,
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_highlighted = false;
+
+    for (idx, c) in text.chars().enumerate() {
+        let highlighted = indices.contains(&idx);
+        if highlighted != current_highlighted && !current.is_empty() {
+            let style = if current_highlighted { highlight } else { base };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_highlighted = highlighted;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        let style = if current_highlighted { highlight } else { base };
+        spans.push(Span::styled(current, style));
+    }
+
+    spans
+}
+
+fn lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_subsequence(pattern: &[char], candidate: &[char]) -> bool {
+    let mut remaining = pattern.iter().peekable();
+    for c in candidate {
+        if remaining.peek() == Some(&c) {
+            remaining.next();
+        }
+    }
+    remaining.peek().is_none()
+}
+
+fn position_bonus(chars: &[char], j: usize) -> i64 {
+    if j == 0 {
+        return BONUS_BOUNDARY;
+    }
+    let prev = chars[j - 1];
+    if matches!(prev, '-' | '_' | ' ' | '/' | '.' | ':') {
+        BONUS_BOUNDARY
+    } else if prev.is_lowercase() && chars[j].is_uppercase() {
+        BONUS_CAMEL
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_subsequences_case_insensitively() {
+        assert!(fuzzy_match("po", "project-open").is_some());
+        assert!(fuzzy_match("PRJ", "project").is_some());
+        assert!(fuzzy_match("xyz", "project").is_none());
+        assert!(fuzzy_match("", "anything").is_some());
+    }
+
+    #[test]
+    fn prefers_word_boundaries_and_consecutive_runs() {
+        let boundary = fuzzy_match("po", "project-open").unwrap();
+        assert_eq!(boundary.indices, vec![0, 8]);
+
+        let prefix = fuzzy_match("task", "task-new").unwrap().score;
+        let scattered = fuzzy_match("task", "tags-mask").unwrap().score;
+        assert!(prefix > scattered);
+    }
+
+    #[test]
+    fn indices_are_char_positions_for_wide_text() {
+        let m = fuzzy_match("看板", "我的看板").unwrap();
+        assert_eq!(m.indices, vec![2, 3]);
+
+        let spans = highlight_spans("我的看板", &m.indices, Style::default(), Style::default());
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[1].content, "看板");
+    }
+}
@@ -92,6 +92,10 @@ pub fn render(f: &mut Frame, area: Rect) {
             Span::styled("A", Style::default().fg(Color::Rgb(136, 192, 208))),
             Span::raw("          编辑器创建任务"),
         ]),
+        Line::from(vec![
+            Span::styled("o", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("          在列底部连续快速添加"),
+        ]),
         Line::from(vec![
             Span::styled("e", Style::default().fg(Color::Rgb(136, 192, 208))),
             Span::raw("          编辑任务标题"),
@@ -112,6 +116,14 @@ pub fn render(f: &mut Frame, area: Rect) {
             Span::styled("Y", Style::default().fg(Color::Rgb(136, 192, 208))),
             Span::raw("          复制到剪贴板"),
         ]),
+        Line::from(vec![
+            Span::styled("Space t P", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("  从剪贴板新建任务"),
+        ]),
+        Line::from(vec![
+            Span::styled("Space t >", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("  移动/复制到其他面板的项目"),
+        ]),
         Line::from(vec![
             Span::styled("d", Style::default().fg(Color::Rgb(136, 192, 208))),
             Span::raw("          删除任务"),
@@ -171,6 +183,10 @@ pub fn render(f: &mut Frame, area: Rect) {
             Span::styled("x", Style::default().fg(Color::Rgb(136, 192, 208))),
             Span::raw("          标记/取消标记任务"),
         ]),
+        Line::from(vec![
+            Span::styled("Tab", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("        展开/收起卡片正文"),
+        ]),
         Line::from(vec![
             Span::styled("X", Style::default().fg(Color::Rgb(136, 192, 208))),
             Span::raw("          标记管理模式"),
@@ -251,6 +267,14 @@ pub fn render(f: &mut Frame, area: Rect) {
             Span::styled("Space q", Style::default().fg(Color::Rgb(136, 192, 208))),
             Span::raw("     退出程序"),
         ]),
+        Line::from(vec![
+            Span::styled(":", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("          命令模式 (Tab 补全)"),
+        ]),
+        Line::from(vec![
+            Span::styled("Ctrl+/", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("     当前模式键位速查"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "状态管理 (Space s)",
@@ -269,7 +293,9 @@ pub fn render(f: &mut Frame, area: Rect) {
         ]),
         Line::from(vec![
             Span::styled("  e", Style::default().fg(Color::Rgb(136, 192, 208))),
-            Span::raw("         编辑显示名"),
+            Span::raw(
+                "         编辑显示名（在列标题中就地编辑，也可在第一个任务上按 k 选中列标题后按 r）",
+            ),
         ]),
         Line::from(vec![
             Span::styled("  h", Style::default().fg(Color::Rgb(136, 192, 208))),
@@ -307,6 +333,18 @@ pub fn render(f: &mut Frame, area: Rect) {
             Span::styled("m", Style::default().fg(Color::Rgb(136, 192, 208))),
             Span::raw("          最大化/恢复列"),
         ]),
+        Line::from(vec![
+            Span::styled("z", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("          聚焦单列（卡片 + 正文预览）"),
+        ]),
+        Line::from(vec![
+            Span::styled("1/2/3", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("      只看高 / 中及以上 / 全部优先级"),
+        ]),
+        Line::from(vec![
+            Span::styled("#1..#9", Style::default().fg(Color::Rgb(136, 192, 208))),
+            Span::raw("     按详情条中选中任务的标签筛选（Esc 清除）"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "窗口管理",
@@ -376,9 +414,13 @@ pub fn render(f: &mut Frame, area: Rect) {
             ), // 略浅的蓝色
             Span::raw(" 任务操作"),
         ]),
+        Line::from(vec![
+            Span::styled("    n", Style::default().fg(Color::Rgb(129, 161, 193))),
+            Span::raw("           追加带时间戳的笔记"),
+        ]),
         Line::from(vec![
             Span::styled(
-                "    h/m/l/n",
+                "    h/m/l/x",
                 Style::default().fg(Color::Rgb(129, 161, 193)),
             ),
             Span::raw("     优先级: 高/中/低/无"),
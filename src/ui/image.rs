@@ -0,0 +1,222 @@
+/// 预览弹窗中的图片
+///
+/// 任务正文中独占一行的 `![说明](路径)` 会在预览里显示：终端支持图形协议时
+/// 预留一块区域，绘制完一帧后由主循环把图片写到该位置；否则显示带路径的占位行。
+/// 需要编译时启用 `images` feature 并在配置中打开 `inline_images`。
+use ratatui::layout::Rect;
+use std::path::{Path, PathBuf};
+
+/// 图片在预览中占用的行数（不含说明行）
+pub const IMAGE_ROWS: u16 = 10;
+
+/// 通过 iTerm2 协议发送的图片大小上限，避免一次写出过多数据卡住终端
+const MAX_INLINE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// 终端图形协议
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// kitty 图形协议（kitty、ghostty）
+    Kitty,
+    /// iTerm2 内联图片（iTerm2、WezTerm）
+    Iterm2,
+    /// sixel（foot、mlterm 等），需要解码图片，目前只显示占位
+    Sixel,
+}
+
+/// 预览中一张图片的位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImagePlacement {
+    pub area: Rect,
+    pub path: PathBuf,
+}
+
+/// 启动时根据环境变量检测终端支持的图形协议
+///
+/// 未启用 `images` feature 或配置关闭时返回 None。tmux 中不透传图形序列，也返回 None。
+pub fn detect(enabled: bool) -> Option<ImageProtocol> {
+    if !enabled || !cfg!(feature = "images") {
+        return None;
+    }
+    detect_from(|key| std::env::var(key).ok())
+}
+
+fn detect_from(env: impl Fn(&str) -> Option<String>) -> Option<ImageProtocol> {
+    if env("TMUX").is_some() {
+        return None;
+    }
+    let term = env("TERM").unwrap_or_default();
+    let program = env("TERM_PROGRAM").unwrap_or_default();
+
+    if env("KITTY_WINDOW_ID").is_some() || term.contains("kitty") || program == "ghostty" {
+        Some(ImageProtocol::Kitty)
+    } else if matches!(program.as_str(), "iTerm.app" | "WezTerm")
+        || env("LC_TERMINAL").as_deref() == Some("iTerm2")
+    {
+        Some(ImageProtocol::Iterm2)
+    } else if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+        Some(ImageProtocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// 解析独占一行的图片链接 `![说明](路径)`，返回 (说明, 路径)
+pub fn parse_image_link(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim().strip_prefix("![")?;
+    let (alt, rest) = rest.split_once("](")?;
+    let target = rest.strip_suffix(')')?;
+    // 去掉可选的标题：![说明](路径 "标题")
+    let target = target.split_once(" \"").map_or(target, |(path, _)| path);
+    let target = target.trim().trim_start_matches('<').trim_end_matches('>');
+    if target.is_empty() {
+        return None;
+    }
+    Some((alt, target))
+}
+
+/// 把链接目标解析为本地文件路径，相对路径以任务文件所在目录为基准；网络图片返回 None
+pub fn resolve_image_path(base_dir: Option<&Path>, target: &str) -> Option<PathBuf> {
+    if target.contains("://") {
+        return target.strip_prefix("file://").map(PathBuf::from);
+    }
+    let path = Path::new(target);
+    if path.is_absolute() {
+        return Some(path.to_path_buf());
+    }
+    Some(base_dir?.join(path))
+}
+
+/// 当前协议能否显示这个文件
+pub fn can_display(protocol: ImageProtocol, path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    match protocol {
+        // kitty 直接读取文件时只接受 PNG
+        ImageProtocol::Kitty => metadata.is_file() && extension == "png",
+        ImageProtocol::Iterm2 => {
+            metadata.is_file()
+                && metadata.len() <= MAX_INLINE_BYTES
+                && matches!(extension.as_str(), "png" | "jpg" | "jpeg" | "gif" | "webp")
+        }
+        ImageProtocol::Sixel => false,
+    }
+}
+
+/// 在光标位置绘制图片的转义序列
+pub fn escape_sequence(protocol: ImageProtocol, placement: &ImagePlacement) -> Option<String> {
+    let area = placement.area;
+    match protocol {
+        ImageProtocol::Kitty => {
+            // t=f：由终端读取文件；C=1：绘制后不移动光标；q=2：不回复
+            let path = placement.path.canonicalize().ok()?;
+            Some(format!(
+                "\x1b_Ga=T,t=f,f=100,C=1,q=2,c={},r={};{}\x1b\\",
+                area.width,
+                area.height,
+                base64_encode(path.to_string_lossy().as_bytes())
+            ))
+        }
+        ImageProtocol::Iterm2 => {
+            let data = std::fs::read(&placement.path).ok()?;
+            Some(format!(
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+                data.len(),
+                area.width,
+                area.height,
+                base64_encode(&data)
+            ))
+        }
+        ImageProtocol::Sixel => None,
+    }
+}
+
+/// 清除已绘制图片的转义序列；返回 None 的协议需要重绘屏幕来覆盖
+pub fn clear_sequence(protocol: ImageProtocol) -> Option<&'static str> {
+    match protocol {
+        ImageProtocol::Kitty => Some("\x1b_Ga=d,d=a,q=2\x1b\\"),
+        ImageProtocol::Iterm2 | ImageProtocol::Sixel => None,
+    }
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn parses_standalone_image_links() {
+        assert_eq!(
+            parse_image_link("  ![架构图](assets/arch.png)"),
+            Some(("架构图", "assets/arch.png"))
+        );
+        assert_eq!(
+            parse_image_link("![](<shot 1.png> \"截图\")"),
+            Some(("", "shot 1.png"))
+        );
+        assert_eq!(parse_image_link("见 ![图](a.png)"), None);
+        assert_eq!(parse_image_link("[链接](a.png)"), None);
+
+        let base = Path::new("/p/.kanban/todo");
+        assert_eq!(
+            resolve_image_path(Some(base), "img/a.png"),
+            Some(PathBuf::from("/p/.kanban/todo/img/a.png"))
+        );
+        assert_eq!(resolve_image_path(Some(base), "https://x/a.png"), None);
+    }
+
+    #[test]
+    fn detects_protocol_from_environment() {
+        let detect = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            detect_from(|key| vars.get(key).cloned())
+        };
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            Some(ImageProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "iTerm.app")]),
+            Some(ImageProtocol::Iterm2)
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux")]),
+            None
+        );
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+    }
+
+    #[test]
+    fn base64_matches_rfc_vectors() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+}
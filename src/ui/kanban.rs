@@ -1,58 +1,243 @@
-use crate::app::App;
+use crate::app::{App, ColumnLayout, Mode};
 use crate::models::Project;
+use crate::models::field::task_field_values;
+use crate::models::task::{ProgressMetric, estimate_total};
 use crate::ui::tags::tag_color;
+use crate::ui::theme;
+use crate::ui::virtual_list::visible_range;
+use crate::ui::width::{display_width, truncate_to_width};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Flex, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
 };
+use std::collections::HashMap;
+
+/// 卡片上标题至少保留的宽度，不足时隐藏标签
+const MIN_TITLE_WIDTH: usize = 12;
+
+/// 生成面板边框标题
+///
+/// 开启状态计数时显示 `[G] name · todo 5 · doing 2 · done 13`，
+/// 宽度不够时回退为 `[G] name (完成/总数)`。有任务填写估算时末尾附加 `Σ 点数`。
+/// 进度按估算或优先级加权时，完成/总数换成加权后的完成百分比。
+pub fn pane_title(
+    project: &Project,
+    status_counts: bool,
+    progress: ProgressMetric,
+    max_width: usize,
+) -> String {
+    // 添加项目类型标记
+    let project_type_label = match project.project_type {
+        crate::models::ProjectType::Global => "[G]",
+        crate::models::ProjectType::Local => "[L]",
+    };
+
+    let points = estimate_total(&project.tasks)
+        .map(|total| format!(" Σ {}", format_points(total)))
+        .unwrap_or_default();
+
+    let done_status = project.done_status().map(|s| s.name.as_str());
+    let weighted = match (progress, done_status) {
+        (ProgressMetric::Count, _) | (_, None) => None,
+        (_, Some(done)) => progress.percent(&project.tasks, done),
+    };
+
+    if status_counts && !project.statuses.is_empty() {
+        let counts: Vec<String> = project
+            .statuses
+            .iter()
+            .map(|status| {
+                let count = project
+                    .tasks
+                    .iter()
+                    .filter(|t| t.status == status.name)
+                    .count();
+                format!("{} {}", status.name, count)
+            })
+            .collect();
+        let percent = weighted
+            .map(|percent| format!(" · {}%", percent))
+            .unwrap_or_default();
+        let title = format!(
+            " {} {} · {}{}{} ",
+            project_type_label,
+            project.name,
+            counts.join(" · "),
+            percent,
+            points
+        );
+        if display_width(&title) <= max_width {
+            return title;
+        }
+    }
+
+    let counter = match weighted {
+        Some(percent) => format!("{}%", percent),
+        None => {
+            let done_count = done_status
+                .map(|done| project.tasks.iter().filter(|t| t.status == done).count())
+                .unwrap_or(0);
+            format!("{}/{}", done_count, project.tasks.len())
+        }
+    };
+
+    format!(
+        " {} {} ({}){} ",
+        project_type_label, project.name, counter, points
+    )
+}
+
+/// 面板底边的加载状态：重新加载中显示 `⟳`，否则显示最近加载时间
+/// 键位提示行：`e 编辑 · H/L 移动 · …`，放不下的提示整条省略
+fn key_hints_line(hints: &[crate::input::KeyHint], room: usize, is_focused: bool) -> Line<'static> {
+    let (key_color, text_color) = if is_focused {
+        (Color::Cyan, Color::Gray)
+    } else {
+        (Color::DarkGray, Color::DarkGray)
+    };
+    let mut spans = vec![Span::raw(" ")];
+    let mut used = 2;
+    for hint in hints {
+        let separator = if spans.len() > 1 { " · " } else { "" };
+        let width = display_width(separator)
+            + display_width(&hint.keys)
+            + 1
+            + display_width(&hint.description);
+        if used + width > room {
+            break;
+        }
+        used += width;
+        spans.push(Span::styled(
+            separator,
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(
+            hint.keys.clone(),
+            Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(
+            format!(" {}", hint.description),
+            Style::default().fg(text_color),
+        ));
+    }
+    spans.push(Span::raw(" "));
+    if spans.len() == 2 {
+        return Line::default();
+    }
+    Line::from(spans)
+}
+
+fn reload_label(
+    loaded_at: Option<&chrono::DateTime<chrono::Local>>,
+    reloading: bool,
+) -> Option<String> {
+    if reloading {
+        return Some(" ⟳ 重新加载中… ".to_string());
+    }
+    loaded_at.map(|time| format!(" 已加载 {} ", time.format("%H:%M")))
+}
+
+/// 估算点数：整数不带小数位，其他保留一位小数
+fn format_points(points: f64) -> String {
+    if points.fract() == 0.0 {
+        format!("{}", points as i64)
+    } else {
+        format!("{:.1}", points)
+    }
+}
 
 /// 渲染看板视图
-pub fn render(f: &mut Frame, area: Rect, project: &Project, is_focused: bool, app: &mut App) {
-    let border_style = if is_focused {
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    project: &Project,
+    pane_id: usize,
+    is_focused: bool,
+    app: &mut App,
+) {
+    let border_style = if is_focused && app.grabbed_pane.is_some() {
+        // 抓取中：等待方向键交换项目
         Style::default()
-            .fg(Color::Cyan)
+            .fg(Color::Magenta)
             .add_modifier(Modifier::BOLD)
+    } else if is_focused {
+        // 聚焦面板的边框使用项目标签颜色
+        let color = project
+            .color
+            .as_deref()
+            .and_then(|c| c.parse::<Color>().ok())
+            .unwrap_or(Color::Cyan);
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
     } else {
         Style::default().fg(Color::DarkGray)
     };
 
-    // 计算任务统计
-    let total_count = project.tasks.len();
-    let done_count = project
-        .tasks
-        .iter()
-        .filter(|t| {
-            // 找到最后一个状态作为"已完成"状态
-            if let Some(last_status) = project.statuses.last() {
-                t.status == last_status.name
-            } else {
-                false
-            }
-        })
-        .count();
-
-    // 添加项目类型标记
-    let project_type_label = match project.project_type {
-        crate::models::ProjectType::Global => "[G]",
-        crate::models::ProjectType::Local => "[L]",
+    // 只读面板在标题前显示锁图标
+    let lock = if app.is_pane_read_only(pane_id) {
+        "🔒"
+    } else {
+        ""
     };
-
-    let title = format!(
-        " {} {} ({}/{}) ",
-        project_type_label, project.name, done_count, total_count
+    let mut title = pane_title(
+        project,
+        app.config.status_counts,
+        app.config.progress,
+        area.width.saturating_sub(4 + display_width(lock) as u16) as usize,
     );
+    title.insert_str(0, lock);
+    if app.focus_mode && is_focused {
+        title.push_str("[FOCUS] ");
+    }
+    // 发送任务、比较面板时在标题前显示面板编号
+    if (app.mode == crate::app::Mode::SendToPane || !app.pane_diff.is_empty())
+        && let Some(number) = crate::ui::send_to_pane::pane_number(&app.split_tree, pane_id)
+    {
+        title.insert_str(0, &format!("[{}] ", number));
+    }
+    if app.redact {
+        title.push_str("[已隐藏] ");
+    }
+    let unmatched = app
+        .pane_diff
+        .iter()
+        .filter(|(project_id, _)| *project_id == project.id)
+        .count();
+    if unmatched > 0 {
+        title.push_str(&format!("[≠{}] ", unmatched));
+    }
 
-    let block = Block::default()
+    let mut block = Block::default()
         .title(title)
         .title_alignment(ratatui::layout::Alignment::Center)
         .borders(Borders::ALL)
         .border_style(border_style)
         .border_type(ratatui::widgets::BorderType::Rounded);
 
-    let inner = block.inner(area);
+    // 底边右侧显示最近加载时间，外部修改后可判断看到的是否是旧数据
+    let reload = reload_label(
+        app.loaded_at.get(&project.id),
+        app.is_reloading(&project.id),
+    );
+    // 底边左侧显示当前上下文的常用键位（:hints）
+    if app.config.key_hints {
+        let room = (area.width as usize)
+            .saturating_sub(4 + reload.as_deref().map_or(0, display_width) + 2);
+        block = block.title_bottom(key_hints_line(
+            &crate::input::pane_hints(app, project, pane_id),
+            room,
+            is_focused,
+        ));
+    }
+    if let Some(label) = reload {
+        block = block.title_bottom(
+            Line::from(Span::styled(label, Style::default().fg(Color::DarkGray))).right_aligned(),
+        );
+    }
+
+    let mut inner = block.inner(area);
     f.render_widget(block, area);
 
     // 动态列布局：根据状态数量创建等宽列
@@ -61,13 +246,81 @@ pub fn render(f: &mut Frame, area: Rect, project: &Project, is_focused: bool, ap
         return;
     }
 
-    // 获取当前项目的列宽配置
-    let constraints: Vec<Constraint> =
-        if let Some(Some(max_col)) = app.config.maximized_column.get(&project.name) {
+    // 聚焦单列：当前列占满面板，卡片显示正文预览
+    if is_focused && app.zoomed_pane == Some(app.focused_pane) {
+        let col_idx = app
+            .selected_column
+            .get(&app.focused_pane)
+            .copied()
+            .unwrap_or(0)
+            .min(num_columns - 1);
+        let status = &project.statuses[col_idx];
+        let tasks = app.column_tasks(pane_id, project, &status.name);
+        render_zoomed_column(f, inner, &tasks, col_idx, pane_id, app, project);
+        return;
+    }
+
+    // 项目还没有任务时显示上手指引（空间不足时退回到列内提示）
+    if project.tasks.is_empty() && inner.height >= EMPTY_PROJECT_MIN_HEIGHT && inner.width >= 40 {
+        let column = app
+            .selected_column
+            .get(&pane_id)
+            .copied()
+            .unwrap_or(0)
+            .min(num_columns - 1);
+        let lines = empty_project_lines(project, column, is_focused);
+        let top = inner.y + inner.height.saturating_sub(lines.len() as u16) / 2;
+        let guide = Rect {
+            y: top,
+            height: (lines.len() as u16).min(inner.height),
+            ..inner
+        };
+        f.render_widget(
+            Paragraph::new(lines).alignment(ratatui::layout::Alignment::Center),
+            guide,
+        );
+        return;
+    }
+
+    // 聚焦面板底部显示选中任务的详情条（完整标题、优先级、标签）
+    if is_focused
+        && inner.height > 3
+        && let Some(task) = selected_task(project, app)
+    {
+        let strip = Rect {
+            y: inner.y + inner.height - 1,
+            height: 1,
+            ..inner
+        };
+        inner.height -= 1;
+        render_detail_strip(f, strip, task, app.tag_filters.get(&pane_id));
+    }
+
+    // 专注模式隐藏完成列，剩余列等宽
+    let visible_columns: Vec<usize> = (0..num_columns)
+        .filter(|&col| !app.is_column_hidden(pane_id, project, col))
+        .collect();
+
+    // 获取当前项目的列宽配置；按任务数分配时手动最大化的列仍然优先
+    let maximized = crate::config::maximized_column_for(&app.config, project);
+    let is_maximized = maximized.is_some();
+    let mut constraints: Vec<Constraint> =
+        if app.column_layout(pane_id) == ColumnLayout::Auto && !is_maximized {
+            let counts: Vec<usize> = visible_columns
+                .iter()
+                .map(|&col| {
+                    app.column_tasks(pane_id, project, &project.statuses[col].name)
+                        .len()
+                })
+                .collect();
+            auto_column_constraints(&counts)
+        } else if visible_columns.len() < num_columns {
+            vec![Constraint::Fill(1); visible_columns.len()]
+        } else if let Some(max_col) = maximized {
             // 最大化模式：一列占 90%，其他列平分 10%
             (0..num_columns)
                 .map(|i| {
-                    if i == *max_col {
+                    if i == max_col {
                         Constraint::Percentage(90)
                     } else {
                         let remaining = if num_columns > 1 {
@@ -79,7 +332,7 @@ pub fn render(f: &mut Frame, area: Rect, project: &Project, is_focused: bool, ap
                     }
                 })
                 .collect()
-        } else if let Some(widths) = app.config.column_widths.get(&project.name) {
+        } else if let Some(widths) = crate::config::column_widths_for(&app.config, project) {
             // 使用配置的宽度（确保列数匹配）
             if widths.len() == num_columns {
                 widths.iter().map(|&w| Constraint::Percentage(w)).collect()
@@ -92,28 +345,62 @@ pub fn render(f: &mut Frame, area: Rect, project: &Project, is_focused: bool, ap
             vec![Constraint::Fill(1); num_columns]
         };
 
+    // 卡片过多的完成列折叠为窄列，其余列按原比例分配剩下的宽度
+    let collapsed: Vec<bool> = visible_columns
+        .iter()
+        .map(|&col| app.is_column_collapsed(pane_id, project, col))
+        .collect();
+    if collapsed.contains(&true) {
+        for (constraint, &is_collapsed) in constraints.iter_mut().zip(&collapsed) {
+            *constraint = match *constraint {
+                _ if is_collapsed => Constraint::Length(COLLAPSED_COLUMN_WIDTH),
+                Constraint::Percentage(w) => Constraint::Fill(w),
+                other => other,
+            };
+        }
+    }
+
     let columns = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(constraints)
         .flex(Flex::Start)
         .split(inner);
 
-    // 渲染每一列
-    for (col_idx, status) in project.statuses.iter().enumerate() {
+    // 渲染每一列，标题附带优先级筛选标记
+    let filter = app.task_filter(pane_id);
+    for (slot, &col_idx) in visible_columns.iter().enumerate() {
+        let status = &project.statuses[col_idx];
+        if collapsed[slot] {
+            render_collapsed_column(f, columns[slot], project, col_idx);
+            continue;
+        }
         // 收集任务的全局索引和引用
         let tasks_with_idx: Vec<(usize, &crate::models::Task)> = project
             .tasks
             .iter()
             .enumerate()
-            .filter(|(_, t)| t.status == status.name)
+            .filter(|(_, t)| t.status == status.name && filter.matches(t))
             .collect();
 
+        let title = match filter.label() {
+            Some(label) => format!("{} [{}]", status.display, label),
+            None => status.display.clone(),
+        };
+        let hidden_count = project
+            .tasks
+            .iter()
+            .filter(|t| t.status == status.name)
+            .count()
+            - tasks_with_idx.len();
+
         render_column(
             f,
-            columns[col_idx],
-            &status.display,
+            columns[slot],
+            &title,
             &tasks_with_idx,
+            hidden_count,
             col_idx,
+            pane_id,
             app,
             is_focused,
             project,
@@ -121,6 +408,55 @@ pub fn render(f: &mut Frame, area: Rect, project: &Project, is_focused: bool, ap
     }
 }
 
+/// 折叠的完成列的宽度（含边框）
+const COLLAPSED_COLUMN_WIDTH: u16 = 8;
+
+/// 渲染折叠的完成列：只显示状态名和任务数
+fn render_collapsed_column(f: &mut Frame, area: Rect, project: &Project, column_idx: usize) {
+    let status = &project.statuses[column_idx];
+    let count = project
+        .tasks
+        .iter()
+        .filter(|t| t.status == status.name)
+        .count();
+    let width = area.width.saturating_sub(2) as usize;
+    let color = status
+        .color
+        .as_deref()
+        .and_then(|c| c.parse::<Color>().ok())
+        .unwrap_or(Color::Gray);
+    let lines = vec![
+        Line::from(Span::styled(
+            truncate_to_width(&status.display, width),
+            Style::default().fg(color),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            count.to_string(),
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(block),
+        area,
+    );
+}
+
+/// 列标题中的任务数；有 WIP 上限时按列中全部任务（含被筛选隐藏的）计数
+fn column_count(shown: usize, hidden: usize, wip_limit: Option<usize>) -> String {
+    match wip_limit {
+        Some(limit) => format!("{}/{}", shown + hidden, limit),
+        None => shown.to_string(),
+    }
+}
+
 /// 渲染单个列
 #[allow(clippy::too_many_arguments)]
 fn render_column(
@@ -128,7 +464,9 @@ fn render_column(
     area: Rect,
     title: &str,
     tasks: &[(usize, &crate::models::Task)],
+    hidden_count: usize,
     column_idx: usize,
+    pane_id: usize,
     app: &mut App,
     is_pane_focused: bool,
     project: &Project,
@@ -139,17 +477,27 @@ fn render_column(
         .copied()
         .unwrap_or(0);
     let is_column_focused = is_pane_focused && current_column == column_idx;
+    // 列标题被选中（k 越过第一个任务）时标题反色，任务不再高亮
+    let is_header_focused = is_column_focused && app.column_header_focus == Some(pane_id);
 
-    // 简洁配色：聚焦=白色，非聚焦=灰色
+    // 简洁配色：聚焦=白色，非聚焦=灰色；状态配置了颜色时标题使用该颜色
+    let status_color = project
+        .statuses
+        .get(column_idx)
+        .and_then(|s| s.color.as_deref())
+        .and_then(|c| c.parse::<Color>().ok());
     let (border_color, title_style) = if is_column_focused {
         (
             Color::White,
             Style::default()
-                .fg(Color::White)
+                .fg(status_color.unwrap_or(Color::White))
                 .add_modifier(Modifier::BOLD),
         )
     } else {
-        (Color::DarkGray, Style::default().fg(Color::Gray))
+        (
+            Color::DarkGray,
+            Style::default().fg(status_color.unwrap_or(Color::Gray)),
+        )
     };
 
     // 检查是否有搜索匹配 - 使用任务ID匹配
@@ -164,131 +512,861 @@ fn render_column(
         })
         .unwrap_or_default();
 
-    let items: Vec<ListItem> = tasks
-        .iter()
-        .enumerate()
-        .map(|(i, (_, task))| {
-            let selected_idx = app
-                .selected_task_index
-                .get(&app.focused_pane)
-                .copied()
-                .unwrap_or(0);
-            let is_selected = is_column_focused && i == selected_idx;
-            let is_marked = app.marked_tasks.contains(&(project.name.clone(), task.id));
+    // 列内可用宽度（去掉左右边框）
+    let content_width = area.width.saturating_sub(2) as usize;
 
-            // 检查是否是搜索匹配项（用任务ID匹配）
-            let is_search_match = search_match_ids.contains(&task.id);
+    // 列标题（调整后2秒内显示宽度百分比）
+    let show_percentage = app
+        .last_column_resize_time
+        .map(|t| t.elapsed().as_secs() < 2)
+        .unwrap_or(false);
 
-            // 样式：选中 > 搜索匹配 > 默认
-            let style = if is_selected {
-                Style::default()
-                    .bg(Color::Rgb(41, 98, 218))
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD)
-            } else if is_search_match {
-                // 搜索匹配：浅蓝色背景
-                Style::default()
-                    .bg(Color::Rgb(60, 80, 100))
-                    .fg(Color::White)
+    // 设置了 WIP 上限的列显示 "数量/上限"，超出上限时标题变红
+    let wip_limit = project.statuses.get(column_idx).and_then(|s| s.wip_limit);
+    let count = column_count(tasks.len(), hidden_count, wip_limit);
+    let title_style = if wip_limit.is_some_and(|limit| tasks.len() + hidden_count > limit) {
+        title_style.fg(Color::Red)
+    } else {
+        title_style
+    };
+    let title_style = if is_header_focused {
+        title_style.add_modifier(Modifier::REVERSED)
+    } else {
+        title_style
+    };
+
+    let title_with_count = if show_percentage {
+        if let Some(max_col) = crate::config::maximized_column_for(&app.config, project) {
+            if column_idx == max_col {
+                format!(" {} ({}) [MAX] ", title, count)
             } else {
-                Style::default()
-            };
+                format!(" {} ({}) ", title, count)
+            }
+        } else if let Some(widths) = crate::config::column_widths_for(&app.config, project) {
+            if column_idx < widths.len() {
+                format!(" {} ({}) [{}%] ", title, count, widths[column_idx])
+            } else {
+                format!(" {} ({}) ", title, count)
+            }
+        } else {
+            format!(" {} ({}) ", title, count)
+        }
+    } else {
+        format!(" {} ({}) ", title, count)
+    };
 
-            // 优先级指示器
-            let priority_indicator = match task.priority.as_deref() {
-                Some("high") => Span::styled("● ", Style::default().fg(Color::Red)),
-                Some("medium") => Span::styled("● ", Style::default().fg(Color::Yellow)),
-                Some("low") => Span::styled("● ", Style::default().fg(Color::Green)),
-                _ => Span::raw("  "),
-            };
+    // 就地编辑显示名：输入框替换列标题
+    let title_line = if is_column_focused && app.mode == Mode::StatusRename {
+        let width = (area.width as usize).saturating_sub(4);
+        app.status_rename_input
+            .line(" ", width, Style::default().fg(Color::White), true)
+    } else {
+        Line::from(title_with_count)
+    };
 
-            // 构建任务项内容
-            let mut spans = vec![
-                Span::raw(" "),
-                Span::styled(
-                    if is_marked { "✓ " } else { "  " },
-                    Style::default().fg(Color::Yellow),
-                ),
-                priority_indicator,
-                Span::raw(format!("[#{}] {}", task.id, task.title)),
-                Span::raw(" "),
-            ];
+    let mut block = Block::default()
+        .title(title_line)
+        .title_alignment(ratatui::layout::Alignment::Center)
+        .title_style(title_style)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .border_type(ratatui::widgets::BorderType::Rounded);
 
-            // 添加标签
-            for tag in &task.tags {
-                spans.push(Span::raw(" "));
-                spans.push(Span::styled(
-                    format!("[{}]", tag),
+    // 列底部汇总当前可见任务的估算点数
+    if let Some(total) = estimate_total(tasks.iter().map(|(_, task)| *task)) {
+        block =
+            block.title_bottom(Line::from(format!(" Σ {} ", format_points(total))).right_aligned());
+    }
+
+    // 快速添加模式：输入框显示在聚焦列的底边
+    if is_column_focused && app.mode == Mode::QuickAdd {
+        let width = (area.width as usize).saturating_sub(12);
+        let input = app
+            .quick_add_input
+            .line(" + ", width, Style::default().fg(Color::White), true);
+        block = block.title_bottom(input.left_aligned());
+    }
+
+    // 空列显示操作提示
+    if tasks.is_empty() {
+        let placeholder = Paragraph::new(empty_column_lines(is_column_focused, hidden_count))
+            .alignment(ratatui::layout::Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true })
+            .block(block);
+        f.render_widget(placeholder, area);
+        return;
+    }
+
+    // 只构建可见的行：按上一帧的滚动位置和选中项确定范围
+    let selected = (is_column_focused && !is_header_focused).then(|| {
+        app.selected_task_index
+            .get(&app.focused_pane)
+            .copied()
+            .unwrap_or(0)
+            .min(tasks.len() - 1)
+    });
+    let offset = app
+        .list_offsets
+        .get(&(pane_id, column_idx))
+        .copied()
+        .unwrap_or(0);
+    let mut card = |i: usize| {
+        let task = tasks[i].1;
+        let key = (project.id.clone(), task.id);
+        let is_marked = app.marked_tasks.contains(&key);
+        if app.expanded_tasks.contains(&key) {
+            task_card(project, task, content_width, is_marked, true)
+        } else {
+            app.card_cache
+                .get_or_build(&project.id, task, is_marked, content_width, || {
+                    task_card(project, task, content_width, is_marked, false)
+                })
+        }
+    };
+    let mut cards: HashMap<usize, ListItem<'static>> = HashMap::new();
+    let range = visible_range(
+        tasks.len(),
+        selected,
+        offset,
+        area.height.saturating_sub(2) as usize,
+        |i| cards.entry(i).or_insert_with(|| card(i)).height(),
+    );
+    let items: Vec<ListItem> = range
+        .clone()
+        .map(|i| {
+            let item = cards.remove(&i).unwrap_or_else(|| card(i));
+            // 样式：选中 > 搜索匹配 > 面板差异 > 卡片颜色 > 默认
+            if selected == Some(i) {
+                item.style(
                     Style::default()
-                        .fg(tag_color(tag))
+                        .bg(Color::Rgb(41, 98, 218))
+                        .fg(Color::White)
                         .add_modifier(Modifier::BOLD),
-                ));
+                )
+            } else if search_match_ids.contains(&tasks[i].1.id) {
+                // 搜索匹配：浅蓝色背景
+                item.style(
+                    Style::default()
+                        .bg(Color::Rgb(60, 80, 100))
+                        .fg(Color::White),
+                )
+            } else if app.pane_diff.contains(&(project.id.clone(), tasks[i].1.id)) {
+                // 另一个面板中没有对应任务：暗琥珀色背景，无颜色方案下靠下划线区分
+                item.style(
+                    Style::default()
+                        .bg(Color::Rgb(88, 64, 40))
+                        .add_modifier(Modifier::UNDERLINED),
+                )
+            } else if let Some(tint) = tasks[i].1.color().and_then(theme::card_tint) {
+                item.style(Style::default().bg(tint))
+            } else {
+                item
             }
+        })
+        .collect();
+    app.list_offsets.insert((pane_id, column_idx), range.start);
 
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(Color::Rgb(41, 98, 218))
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    );
+    let mut list_state = ListState::default().with_selected(selected.map(|i| i - range.start));
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// 构建任务卡片（不含选中、搜索匹配的背景色）
+fn task_card(
+    project: &Project,
+    task: &crate::models::Task,
+    content_width: usize,
+    is_marked: bool,
+    is_expanded: bool,
+) -> ListItem<'static> {
+    // 优先级指示器
+    let priority_indicator = theme::priority_span(task.priority.as_deref());
+
+    // 按列宽截断标题：前缀 " ✓ ● [#id] " 固定占位
+    let id_label = format!("[#{}] ", task.id);
+    let prefix_width = 5 + display_width(&id_label);
+    let tags_width: usize = task.tags.iter().map(|tag| display_width(tag) + 3).sum();
+    let available = content_width.saturating_sub(prefix_width + 1);
+    // 空间不足时优先保证标题，标签在详情条中查看
+    let show_tags = !task.tags.is_empty()
+        && available.saturating_sub(tags_width) >= MIN_TITLE_WIDTH.min(display_width(&task.title));
+    let title_budget = if show_tags {
+        available - tags_width
+    } else {
+        available
+    };
+
+    // 构建任务项内容
+    // 有卡片颜色时，行首用对应颜色的色条代替空格
+    let color_bar = match task.color().and_then(theme::card_color) {
+        Some(color) => Span::styled("▌", Style::default().fg(color)),
+        None => Span::raw(" "),
+    };
+    let mut spans = vec![
+        color_bar,
+        Span::styled(
+            if is_marked { "✓ " } else { "  " },
+            Style::default().fg(Color::Yellow),
+        ),
+        priority_indicator,
+        Span::raw(format!(
+            "{}{}",
+            id_label,
+            truncate_to_width(&task.title, title_budget)
+        )),
+    ];
+
+    // 添加标签
+    if show_tags {
+        spans.push(Span::raw(" "));
+        for tag in &task.tags {
             spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!("[{}]", tag),
+                Style::default()
+                    .fg(tag_color(tag))
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+    }
 
-            // 任务项（紧凑布局，无额外间距）
-            ListItem::new(Line::from(spans)).style(style)
+    // 展开的卡片在标题下方显示正文和子任务
+    let mut lines = vec![Line::from(spans)];
+    if is_expanded {
+        let body_width = content_width.saturating_sub(EXPANDED_INDENT.len());
+        if let Some(text) = field_line(project, task, body_width) {
+            lines.push(Line::from(Span::styled(
+                format!("{}{}", EXPANDED_INDENT, text),
+                Style::default().fg(Color::Rgb(180, 142, 173)),
+            )));
+        }
+        if let Some(summary) = task.stats.summary() {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}{}",
+                    EXPANDED_INDENT,
+                    truncate_to_width(&summary, body_width)
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for text in expanded_card_lines(&task.content, body_width) {
+            let color = if text.starts_with('☑') {
+                Color::Rgb(163, 190, 140)
+            } else if text.starts_with('☐') {
+                Color::Rgb(136, 192, 208)
+            } else {
+                Color::Gray
+            };
+            lines.push(Line::from(Span::styled(
+                format!("{}{}", EXPANDED_INDENT, text),
+                Style::default().fg(color),
+            )));
+        }
+    }
+
+    ListItem::new(lines)
+}
+
+/// 显示空项目指引所需的最小面板高度
+const EMPTY_PROJECT_MIN_HEIGHT: u16 = 14;
+
+/// 空列中的提示文字：筛选隐藏了任务时提示恢复，否则提示如何添加任务
+fn empty_column_lines(is_column_focused: bool, hidden_count: usize) -> Vec<Line<'static>> {
+    let (text_color, key_color) = if is_column_focused {
+        (Color::Gray, Color::Rgb(136, 192, 208))
+    } else {
+        (Color::DarkGray, Color::DarkGray)
+    };
+    let hint = |key: &str, text: &str| {
+        Line::from(vec![
+            Span::styled(key.to_string(), Style::default().fg(key_color)),
+            Span::styled(format!(" {}", text), Style::default().fg(text_color)),
+        ])
+    };
+
+    if hidden_count > 0 {
+        return vec![
+            Line::from(""),
+            Line::styled(
+                format!("{} 个任务被筛选隐藏", hidden_count),
+                Style::default().fg(text_color),
+            ),
+            hint("3", "显示全部"),
+        ];
+    }
+
+    vec![
+        Line::from(""),
+        Line::styled("暂无任务", Style::default().fg(text_color)),
+        Line::from(""),
+        hint("a", "添加任务"),
+        hint("Space t", "任务菜单"),
+    ]
+}
+
+/// 空项目的上手指引：项目名、状态列和最常用的键位
+fn empty_project_lines(project: &Project, column: usize, is_focused: bool) -> Vec<Line<'static>> {
+    let key_style = Style::default()
+        .fg(if is_focused {
+            Color::Rgb(136, 192, 208)
+        } else {
+            Color::DarkGray
         })
+        .add_modifier(Modifier::BOLD);
+    let text_style = Style::default().fg(Color::Gray);
+    let hint = |key: &str, text: String| {
+        Line::from(vec![
+            Span::styled(format!("{:>9}", key), key_style),
+            Span::styled(format!("  {:<20}", text), text_style),
+        ])
+    };
+
+    let statuses: Vec<&str> = project
+        .statuses
+        .iter()
+        .map(|s| s.display.as_str())
         .collect();
+    let target = project
+        .statuses
+        .get(column)
+        .map(|s| s.display.clone())
+        .unwrap_or_default();
 
-    // 列标题（调整后2秒内显示宽度百分比）
-    let show_percentage = app
-        .last_column_resize_time
-        .map(|t| t.elapsed().as_secs() < 2)
-        .unwrap_or(false);
+    vec![
+        Line::styled(
+            format!("「{}」还没有任务", project.name),
+            Style::default()
+                .fg(Color::Rgb(235, 203, 139))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::styled(
+            format!("状态列：{}", statuses.join(" → ")),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Line::from(""),
+        hint("a", format!("在「{}」创建任务", target)),
+        hint("A", "用外部编辑器创建".to_string()),
+        hint("h / l", "切换目标列".to_string()),
+        hint("Space t", "任务菜单".to_string()),
+        hint("Space s", "管理状态列".to_string()),
+        hint(":import", "导入 org / Taskwarrior".to_string()),
+        hint("Space p o", "打开其他项目".to_string()),
+        hint("?", "查看全部键位".to_string()),
+    ]
+}
 
-    let title_with_count = if show_percentage {
-        if let Some(Some(max_col)) = app.config.maximized_column.get(&project.name) {
-            if column_idx == *max_col {
-                format!(" {} ({}) [MAX] ", title, tasks.len())
-            } else {
-                format!(" {} ({}) ", title, tasks.len())
-            }
-        } else if let Some(widths) = app.config.column_widths.get(&project.name) {
-            if column_idx < widths.len() {
-                format!(" {} ({}) [{}%] ", title, tasks.len(), widths[column_idx])
-            } else {
-                format!(" {} ({}) ", title, tasks.len())
+/// 展开卡片时显示的正文行数（不含子任务）
+const EXPANDED_BODY_LINES: usize = 4;
+/// 展开卡片时最多显示的子任务数
+const EXPANDED_CHECKLIST_ITEMS: usize = 8;
+/// 展开内容的缩进
+const EXPANDED_INDENT: &str = "     ";
+
+/// 卡片上的自定义字段行（如 "估算: 3 · 客户: ACME"），没有填写任何字段时返回 None
+fn field_line(project: &Project, task: &crate::models::Task, width: usize) -> Option<String> {
+    let fields = project.card_fields();
+    let values = task_field_values(&fields, &task.extra);
+    if values.is_empty() {
+        return None;
+    }
+    let text = values
+        .iter()
+        .map(|(label, value)| format!("{}: {}", label, value))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    Some(truncate_to_width(&text, width))
+}
+
+/// 展开卡片的内容：正文前几行，随后是全部子任务（`- [ ]` / `- [x]` 显示为 ☐ / ☑）
+fn expanded_card_lines(content: &str, width: usize) -> Vec<String> {
+    let mut body = Vec::new();
+    let mut checklist = Vec::new();
+
+    for line in content.lines().map(str::trim) {
+        let item = line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .and_then(|rest| {
+                if let Some(text) = rest.strip_prefix("[ ]") {
+                    Some(format!("☐ {}", text.trim()))
+                } else {
+                    rest.strip_prefix("[x]")
+                        .or_else(|| rest.strip_prefix("[X]"))
+                        .map(|text| format!("☑ {}", text.trim()))
+                }
+            });
+        if let Some(item) = item {
+            checklist.push(truncate_to_width(&item, width));
+        } else if body.len() < EXPANDED_BODY_LINES && !line.is_empty() && !line.starts_with("```") {
+            body.push(truncate_to_width(
+                line.trim_start_matches('#').trim_start(),
+                width,
+            ));
+        }
+    }
+
+    let hidden = checklist.len().saturating_sub(EXPANDED_CHECKLIST_ITEMS);
+    checklist.truncate(EXPANDED_CHECKLIST_ITEMS);
+    if hidden > 0 {
+        checklist.push(format!("… 还有 {} 项", hidden));
+    }
+
+    body.extend(checklist);
+    if body.is_empty() {
+        body.push("（无正文）".to_string());
+    }
+    body
+}
+
+/// 聚焦单列时每张卡片显示的正文行数
+const ZOOM_PREVIEW_LINES: usize = 3;
+
+/// 渲染聚焦的单列：每个任务显示为多行卡片（标题、标签、正文预览）
+fn render_zoomed_column(
+    f: &mut Frame,
+    area: Rect,
+    tasks: &[&crate::models::Task],
+    column_idx: usize,
+    pane_id: usize,
+    app: &mut App,
+    project: &Project,
+) {
+    let status = &project.statuses[column_idx];
+    let status_color = status
+        .color
+        .as_deref()
+        .and_then(|c| c.parse::<Color>().ok())
+        .unwrap_or(Color::White);
+    let content_width = area.width.saturating_sub(2) as usize;
+
+    let card = |task: &crate::models::Task| {
+        let is_marked = app.marked_tasks.contains(&(project.id.clone(), task.id));
+        let priority_indicator = theme::priority_span(task.priority.as_deref());
+
+        let id_label = format!("[#{}] ", task.id);
+        let title_budget = content_width.saturating_sub(5 + display_width(&id_label));
+        let mut lines = vec![Line::from(vec![
+            Span::raw(" "),
+            Span::styled(
+                if is_marked { "✓ " } else { "  " },
+                Style::default().fg(Color::Yellow),
+            ),
+            priority_indicator,
+            Span::styled(
+                format!(
+                    "{}{}",
+                    id_label,
+                    truncate_to_width(&task.title, title_budget)
+                ),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ])];
+
+        if !task.tags.is_empty() {
+            let mut spans = vec![Span::raw("     ")];
+            for tag in &task.tags {
+                spans.push(Span::styled(
+                    format!("[{}] ", tag),
+                    Style::default().fg(tag_color(tag)),
+                ));
             }
-        } else {
-            format!(" {} ({}) ", title, tasks.len())
+            lines.push(Line::from(spans));
         }
-    } else {
-        format!(" {} ({}) ", title, tasks.len())
+
+        if let Some(text) = field_line(project, task, content_width.saturating_sub(5)) {
+            lines.push(Line::from(Span::styled(
+                format!("     {}", text),
+                Style::default().fg(Color::Rgb(180, 142, 173)),
+            )));
+        }
+
+        for preview in body_preview(
+            &task.content,
+            ZOOM_PREVIEW_LINES,
+            content_width.saturating_sub(5),
+        ) {
+            lines.push(Line::from(Span::styled(
+                format!("     {}", preview),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        ListItem::new(lines)
     };
 
+    // 只构建可见的卡片
+    let selected = (!tasks.is_empty()).then(|| {
+        app.selected_task_index
+            .get(&app.focused_pane)
+            .copied()
+            .unwrap_or(0)
+            .min(tasks.len() - 1)
+    });
+    let offset = app
+        .list_offsets
+        .get(&(pane_id, column_idx))
+        .copied()
+        .unwrap_or(0);
+    let mut cards: HashMap<usize, ListItem<'static>> = HashMap::new();
+    let range = visible_range(
+        tasks.len(),
+        selected,
+        offset,
+        area.height.saturating_sub(2) as usize,
+        |i| cards.entry(i).or_insert_with(|| card(tasks[i])).height(),
+    );
+    let items: Vec<ListItem> = range
+        .clone()
+        .map(|i| cards.remove(&i).unwrap_or_else(|| card(tasks[i])))
+        .collect();
+    app.list_offsets.insert((pane_id, column_idx), range.start);
+
+    let filter_label = app
+        .task_filter(app.focused_pane)
+        .label()
+        .map(|label| format!("[{}] ", label))
+        .unwrap_or_default();
+    let points = estimate_total(tasks.iter().copied())
+        .map(|total| format!("Σ {} ", format_points(total)))
+        .unwrap_or_default();
+    let title = format!(
+        " {} ({}) {}{}[ZOOM] ",
+        status.display,
+        tasks.len(),
+        points,
+        filter_label
+    );
     let list = List::new(items)
         .block(
             Block::default()
-                .title(title_with_count)
+                .title(title)
                 .title_alignment(ratatui::layout::Alignment::Center)
-                .title_style(title_style)
+                .title_style(
+                    Style::default()
+                        .fg(status_color)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .title_bottom(
+                    Line::from(" h/l 切换列 · z/Esc 恢复 ")
+                        .alignment(ratatui::layout::Alignment::Right),
+                )
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(border_color))
+                .border_style(Style::default().fg(Color::White))
                 .border_type(ratatui::widgets::BorderType::Rounded),
         )
         .highlight_style(
             Style::default()
                 .bg(Color::Rgb(41, 98, 218))
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+                .fg(Color::White),
         );
 
-    // 获取或创建当前面板的 ListState，并设置选中索引
-    let list_state = app.list_states.entry(app.focused_pane).or_default();
+    let mut list_state = ListState::default().with_selected(selected.map(|i| i - range.start));
+    f.render_stateful_widget(list, area, &mut list_state);
+}
 
-    // 如果当前列被聚焦，更新 ListState 的选中索引
-    if is_column_focused {
-        let selected_idx = app
-            .selected_task_index
-            .get(&app.focused_pane)
-            .copied()
-            .unwrap_or(0);
-        list_state.select(Some(selected_idx));
-    } else {
-        list_state.select(None);
+/// 提取正文预览：跳过空行和代码围栏，去掉标题/列表标记，按宽度截断
+fn body_preview(content: &str, max_lines: usize, width: usize) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("```"))
+        .map(|line| {
+            let line = line.trim_start_matches('#').trim_start();
+            truncate_to_width(line, width)
+        })
+        .take(max_lines)
+        .collect()
+}
+
+/// 获取聚焦面板中选中的任务
+fn selected_task<'a>(project: &'a Project, app: &App) -> Option<&'a crate::models::Task> {
+    let column = app
+        .selected_column
+        .get(&app.focused_pane)
+        .copied()
+        .unwrap_or(0);
+    let task_idx = app
+        .selected_task_index
+        .get(&app.focused_pane)
+        .copied()
+        .unwrap_or(0);
+    let status = project.statuses.get(column)?;
+
+    app.column_tasks(app.focused_pane, project, &status.name)
+        .get(task_idx)
+        .copied()
+}
+
+/// 自动列宽下，任务数超过该值的列不再继续变宽
+const AUTO_WIDTH_MAX_TASKS: usize = 10;
+/// 自动列宽的基础权重，保证空列也保留一定宽度
+const AUTO_WIDTH_BASE: u16 = 2;
+
+/// 按任务数分配列宽：空列权重为基础值，任务越多越宽，最宽与最窄之比有上限
+fn auto_column_constraints(task_counts: &[usize]) -> Vec<Constraint> {
+    task_counts
+        .iter()
+        .map(|&count| Constraint::Fill(AUTO_WIDTH_BASE + count.min(AUTO_WIDTH_MAX_TASKS) as u16))
+        .collect()
+}
+
+/// 渲染选中任务的单行详情条
+///
+/// 前 9 个标签带编号，按 `#` 加编号即按该标签筛选面板；正在筛选的标签加下划线。
+fn render_detail_strip(
+    f: &mut Frame,
+    area: Rect,
+    task: &crate::models::Task,
+    active_tag: Option<&String>,
+) {
+    let mut spans = vec![Span::styled(
+        format!(" #{} ", task.id),
+        Style::default().fg(Color::DarkGray),
+    )];
+
+    if let Some(priority) = task.priority.as_deref() {
+        let color = theme::priority_color(Some(priority));
+        spans.push(Span::styled(
+            format!(
+                "{}{} ",
+                theme::palette().priority_marker(Some(priority)),
+                priority
+            ),
+            Style::default().fg(color),
+        ));
+    }
+
+    if let Some(points) = task.estimate() {
+        spans.push(Span::styled(
+            format!("估算 {} ", format_points(points)),
+            Style::default().fg(Color::Rgb(180, 142, 173)),
+        ));
+    }
+
+    let tag_label = |index: usize| {
+        if index < 9 {
+            format!(" {}", index + 1)
+        } else {
+            " ".to_string()
+        }
+    };
+    let tags_width: usize = task
+        .tags
+        .iter()
+        .enumerate()
+        .map(|(index, tag)| display_width(tag) + 2 + tag_label(index).len())
+        .sum();
+    let used: usize = spans.iter().map(|span| span.width()).sum();
+    let title_budget = (area.width as usize).saturating_sub(used + tags_width + 1);
+    spans.push(Span::styled(
+        truncate_to_width(&task.title, title_budget),
+        Style::default().fg(Color::White),
+    ));
+
+    for (index, tag) in task.tags.iter().enumerate() {
+        spans.push(Span::styled(
+            tag_label(index),
+            Style::default().fg(Color::DarkGray),
+        ));
+        let mut style = Style::default().fg(tag_color(tag));
+        if active_tag == Some(tag) {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        spans.push(Span::styled(format!("[{}]", tag), style));
+    }
+
+    f.render_widget(
+        Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::Rgb(30, 34, 42))),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectType, Status, Task};
+    use std::path::PathBuf;
+
+    fn project() -> Project {
+        let mut project = Project::new(
+            "web".to_string(),
+            PathBuf::from("/tmp/web"),
+            ProjectType::Global,
+        );
+        project.statuses = ["todo", "doing", "done"]
+            .iter()
+            .map(|name| Status::new(name.to_string(), name.to_string()))
+            .collect();
+        for (id, status) in [(1, "todo"), (2, "todo"), (3, "doing"), (4, "done")] {
+            project
+                .tasks
+                .push(Task::new(id, format!("Task {}", id), status.to_string()));
+        }
+        project
+    }
+
+    #[test]
+    fn column_count_includes_hidden_tasks_against_wip_limit() {
+        assert_eq!(column_count(2, 1, None), "2");
+        assert_eq!(column_count(2, 1, Some(3)), "3/3");
+    }
+
+    #[test]
+    fn reload_label_shows_spinner_then_time() {
+        use chrono::TimeZone;
+        let time = chrono::Local
+            .with_ymd_and_hms(2024, 5, 1, 13, 42, 7)
+            .unwrap();
+        assert_eq!(
+            reload_label(Some(&time), false).as_deref(),
+            Some(" 已加载 13:42 ")
+        );
+        assert_eq!(
+            reload_label(Some(&time), true).as_deref(),
+            Some(" ⟳ 重新加载中… ")
+        );
+        assert_eq!(reload_label(None, false), None);
+    }
+
+    #[test]
+    fn pane_title_shows_per_status_counts() {
+        assert_eq!(
+            pane_title(&project(), true, ProgressMetric::Count, 80),
+            " [G] web · todo 2 · doing 1 · done 1 "
+        );
+    }
+
+    #[test]
+    fn empty_placeholders_point_to_next_action() {
+        let text = |lines: Vec<Line>| -> String {
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let column = text(empty_column_lines(true, 0));
+        assert!(column.contains("a 添加任务"));
+        assert!(column.contains("Space t 任务菜单"));
+        assert!(text(empty_column_lines(true, 2)).contains("2 个任务被筛选隐藏"));
+
+        let mut empty = project();
+        empty.tasks.clear();
+        let guide = text(empty_project_lines(&empty, 1, true));
+        assert!(guide.contains("「web」还没有任务"));
+        assert!(guide.contains("todo → doing → done"));
+        assert!(guide.contains("在「doing」创建任务"));
+    }
+
+    #[test]
+    fn body_preview_skips_blank_lines_and_truncates() {
+        let content =
+            "# Notes\n\n```\ncode\n```\n- first point\n- second point that is long\n- third";
+        assert_eq!(
+            body_preview(content, 3, 12),
+            vec!["Notes", "code", "- first poi…"]
+        );
+        assert!(body_preview("", 3, 20).is_empty());
     }
 
-    f.render_stateful_widget(list, area, list_state);
+    #[test]
+    fn expanded_card_lists_body_then_checklist() {
+        let content = "# Plan\n\nShip it\n\n## 子任务\n\n- [ ] write docs\n- [x] add tests\n";
+        assert_eq!(
+            expanded_card_lines(content, 40),
+            vec!["Plan", "Ship it", "子任务", "☐ write docs", "☑ add tests"]
+        );
+        assert_eq!(expanded_card_lines("", 40), vec!["（无正文）"]);
+    }
+
+    #[test]
+    fn estimates_are_summed_into_the_pane_title() {
+        let mut project = project();
+        project.tasks[0]
+            .extra
+            .insert("estimate".to_string(), 3.into());
+        project.tasks[2]
+            .extra
+            .insert("estimate".to_string(), 1.5.into());
+        project.tasks[3]
+            .extra
+            .insert("estimate".to_string(), "big".into());
+
+        assert_eq!(project.tasks[3].estimate(), None);
+        assert_eq!(estimate_total(&project.tasks), Some(4.5));
+        assert_eq!(estimate_total(&project.tasks[3..]), None);
+        assert_eq!(
+            pane_title(&project, true, ProgressMetric::Count, 80),
+            " [G] web · todo 2 · doing 1 · done 1 Σ 4.5 "
+        );
+        assert_eq!(
+            pane_title(&project, false, ProgressMetric::Count, 80),
+            " [G] web (1/4) Σ 4.5 "
+        );
+        assert_eq!(format_points(8.0), "8");
+    }
+
+    #[test]
+    fn weighted_progress_replaces_done_total_in_pane_title() {
+        let mut project = project();
+        project.tasks[3].priority = Some("high".to_string());
+        assert_eq!(
+            pane_title(&project, false, ProgressMetric::Priority, 80),
+            " [G] web (50%) "
+        );
+        assert_eq!(
+            pane_title(&project, true, ProgressMetric::Priority, 80),
+            " [G] web · todo 2 · doing 1 · done 1 · 50% "
+        );
+    }
+
+    #[test]
+    fn pane_progress_uses_done_status_before_trailing_blocked() {
+        let mut project = project();
+        project
+            .statuses
+            .push(Status::new("blocked".to_string(), "blocked".to_string()));
+        project.tasks[0].status = "blocked".to_string();
+        project.tasks[3].priority = Some("high".to_string());
+        assert_eq!(
+            pane_title(&project, false, ProgressMetric::Priority, 80),
+            " [G] web (50%) "
+        );
+        assert_eq!(
+            pane_title(&project, false, ProgressMetric::Count, 20),
+            " [G] web (1/4) "
+        );
+    }
+
+    #[test]
+    fn pane_title_falls_back_to_done_total_when_narrow_or_disabled() {
+        assert_eq!(
+            pane_title(&project(), true, ProgressMetric::Count, 20),
+            " [G] web (1/4) "
+        );
+        assert_eq!(
+            pane_title(&project(), false, ProgressMetric::Count, 80),
+            " [G] web (1/4) "
+        );
+    }
+
+    #[test]
+    fn auto_column_widths_follow_task_counts_within_bounds() {
+        assert_eq!(
+            auto_column_constraints(&[0, 4, 30]),
+            vec![
+                Constraint::Fill(2),
+                Constraint::Fill(6),
+                Constraint::Fill(12)
+            ]
+        );
+    }
 }
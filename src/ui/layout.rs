@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// 路径步骤 - 记录在父节点中的位置
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -162,6 +163,54 @@ impl SplitNode {
         }
     }
 
+    /// 移除项目已不存在的面板和重复 ID 的面板（保留第一个），只剩一侧的分割折叠为该侧；
+    /// 全部被移除时返回 None
+    pub fn pruned(
+        self,
+        project_exists: &impl Fn(&str) -> bool,
+        seen: &mut HashSet<usize>,
+        report: &mut PruneReport,
+    ) -> Option<SplitNode> {
+        match self {
+            SplitNode::Leaf { project_id, id } => {
+                if !seen.insert(id) {
+                    report.duplicate_panes += 1;
+                    return None;
+                }
+                if project_id
+                    .as_deref()
+                    .is_some_and(|pid| !project_exists(pid))
+                {
+                    report.missing_projects += 1;
+                    return None;
+                }
+                Some(SplitNode::Leaf { project_id, id })
+            }
+            SplitNode::Horizontal { left, right, ratio } => match (
+                left.pruned(project_exists, seen, report),
+                right.pruned(project_exists, seen, report),
+            ) {
+                (Some(left), Some(right)) => Some(SplitNode::Horizontal {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    ratio,
+                }),
+                (left, right) => left.or(right),
+            },
+            SplitNode::Vertical { top, bottom, ratio } => match (
+                top.pruned(project_exists, seen, report),
+                bottom.pruned(project_exists, seen, report),
+            ) {
+                (Some(top), Some(bottom)) => Some(SplitNode::Vertical {
+                    top: Box::new(top),
+                    bottom: Box::new(bottom),
+                    ratio,
+                }),
+                (top, bottom) => top.or(bottom),
+            },
+        }
+    }
+
     /// 清除所有面板中对指定项目的引用
     pub fn clear_project_from_all_panes(&mut self, project_name: &str) {
         match self {
@@ -235,6 +284,15 @@ impl SplitNode {
         None // 到达根节点仍无匹配
     }
 
+    /// 从指定面板沿方向一直走到边缘的面板（该方向没有相邻面板时是它自己）
+    pub fn edge_pane(&self, from: usize, direction: Direction) -> usize {
+        let mut current = from;
+        while let Some(next) = self.find_adjacent_pane(current, direction) {
+            current = next;
+        }
+        current
+    }
+
     /// 根据路径获取节点引用
     fn get_node_at_path(&self, path: &[PathStep]) -> Option<&SplitNode> {
         let mut current = self;
@@ -376,6 +434,21 @@ impl SplitNode {
     }
 }
 
+/// 清理恢复的布局时移除的面板数
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PruneReport {
+    /// 项目已不存在的面板
+    pub missing_projects: usize,
+    /// 与前面的面板 ID 重复的面板
+    pub duplicate_panes: usize,
+}
+
+impl PruneReport {
+    pub fn is_empty(&self) -> bool {
+        self.missing_projects == 0 && self.duplicate_panes == 0
+    }
+}
+
 /// 导航方向
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -384,3 +457,16 @@ pub enum Direction {
     Up,
     Down,
 }
+
+impl Direction {
+    /// 解析配置中的方向名（`left`/`right`/`top`/`bottom`，也接受 `up`/`down`）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "left" => Some(Direction::Left),
+            "right" => Some(Direction::Right),
+            "top" | "up" => Some(Direction::Up),
+            "bottom" | "down" => Some(Direction::Down),
+            _ => None,
+        }
+    }
+}
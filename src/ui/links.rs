@@ -0,0 +1,147 @@
+/// 任务正文中的链接（预览中 Tab 选择、Enter 打开，`:open-url N`）
+///
+/// 识别 `http://`、`https://` 开头的地址，去掉句末标点和 Markdown 链接的右括号；
+/// 同一地址出现多次只算一个，序号按首次出现的顺序。
+use ratatui::style::{Color, Modifier};
+use ratatui::text::{Line, Span};
+
+const SCHEMES: [&str; 2] = ["https://", "http://"];
+
+/// 文本中的链接（去重，按出现顺序）
+pub fn find_urls(text: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for (_, url) in url_ranges(text) {
+        if !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
+}
+
+/// 链接在文本中的 (起始字节, 链接)
+fn url_ranges(text: &str) -> Vec<(usize, &str)> {
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    while let Some((start, scheme)) = SCHEMES
+        .iter()
+        .filter_map(|scheme| text[pos..].find(scheme).map(|i| (pos + i, scheme)))
+        .min_by_key(|(start, _)| *start)
+    {
+        let rest = &text[start..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '`'))
+            .unwrap_or(rest.len());
+        let url = trim_url(&rest[..end]);
+        if url.len() > scheme.len() {
+            ranges.push((start, url));
+        }
+        pos = start + end.max(1);
+    }
+    ranges
+}
+
+/// 去掉末尾的标点；右括号只在没有配对的左括号时去掉（保留 `wiki/Foo_(bar)`）
+fn trim_url(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        let Some(last) = url.chars().last() else {
+            return url;
+        };
+        let unbalanced = match last {
+            ')' => url.matches('(').count() < url.matches(')').count(),
+            ']' => url.matches('[').count() < url.matches(']').count(),
+            '.' | ',' | ';' | ':' | '!' | '?' | '\'' | '*' | '_' => true,
+            _ => false,
+        };
+        if !unbalanced {
+            return url;
+        }
+        url = &url[..url.len() - last.len_utf8()];
+    }
+}
+
+/// 给一行中的链接加下划线，选中的链接反色显示
+pub fn highlight<'a>(line: Line<'a>, selected: Option<&str>) -> Line<'a> {
+    let mut spans = Vec::new();
+    for span in line.spans {
+        let content = span.content.to_string();
+        let ranges = url_ranges(&content);
+        if ranges.is_empty() {
+            spans.push(span);
+            continue;
+        }
+        let mut pos = 0;
+        for (start, url) in ranges {
+            if start > pos {
+                spans.push(Span::styled(content[pos..start].to_string(), span.style));
+            }
+            let mut style = span
+                .style
+                .fg(Color::Rgb(136, 192, 208))
+                .add_modifier(Modifier::UNDERLINED);
+            if selected == Some(url) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            spans.push(Span::styled(url.to_string(), style));
+            pos = start + url.len();
+        }
+        if pos < content.len() {
+            spans.push(Span::styled(content[pos..].to_string(), span.style));
+        }
+    }
+    Line::from(spans).style(line.style)
+}
+
+/// 选中链接所在的行号（用于滚动到该行）
+pub fn line_of(text: &str, url: &str) -> Option<usize> {
+    text.lines()
+        .position(|line| url_ranges(line).iter().any(|(_, u)| *u == url))
+}
+
+/// 用系统默认程序（open / xdg-open / explorer）打开链接，不等待退出
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    std::process::Command::new(crate::config::file_opener())
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_urls_in_prose_and_markdown_links() {
+        let text = "见 https://jira.example.com/browse/WEB-42.\n\
+                    [设计稿](https://figma.com/file/abc?node=1) 和 <http://wiki/Foo_(bar)>\n\
+                    再次 https://jira.example.com/browse/WEB-42, 结束";
+        assert_eq!(
+            find_urls(text),
+            vec![
+                "https://jira.example.com/browse/WEB-42",
+                "https://figma.com/file/abc?node=1",
+                "http://wiki/Foo_(bar)",
+            ]
+        );
+        assert!(find_urls("https:// 不完整").is_empty());
+        assert_eq!(find_urls("http://x"), vec!["http://x"]);
+        assert_eq!(line_of(text, "http://wiki/Foo_(bar)"), Some(1));
+    }
+
+    #[test]
+    fn highlight_splits_spans_around_links() {
+        let line = Line::from(Span::raw("ticket https://a.io/1 done"));
+        let line = highlight(line, Some("https://a.io/1"));
+        let texts: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["ticket ", "https://a.io/1", " done"]);
+        assert!(
+            line.spans[1]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+    }
+}
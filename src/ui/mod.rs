@@ -1,15 +1,28 @@
+pub mod cheat_sheet;
 pub mod command_completion;
 pub mod command_menu;
 pub mod dialogs;
+pub mod fuzzy;
 pub mod help;
+pub mod image;
 mod kanban;
 pub mod layout;
+pub mod links;
 pub mod preview;
+pub mod redact;
+pub mod send_to_pane;
 mod sidebar;
+pub mod spell;
 mod statusbar;
+pub mod tag_overview;
 pub mod tags;
+pub mod text_field;
 pub mod text_input;
+pub mod theme;
+pub mod tutor;
+pub mod virtual_list;
 pub mod welcome;
+pub mod width;
 
 use crate::app::App;
 use ratatui::Frame;
@@ -17,6 +30,16 @@ use ratatui::layout::{Constraint, Direction, Layout};
 
 /// 主渲染函数
 pub fn render(f: &mut Frame, app: &mut App) {
+    // 隐藏看板：只显示应用名，预览中的图片也随之清除
+    if app.screen_locked {
+        render_lock_screen(f, f.area());
+        app.preview_images = Vec::new();
+        if theme::palette() == theme::Palette::NoColor {
+            theme::strip_colors(f.buffer_mut());
+        }
+        return;
+    }
+
     // 克隆 split_tree 以避免借用冲突
     let split_tree = app.split_tree.clone();
 
@@ -26,9 +49,19 @@ pub fn render(f: &mut Frame, app: &mut App) {
     // 渲染状态栏（已注释）
     // statusbar::render(f, main_chunks[1], app);
 
+    // 显示标题输入框或预览时准备拼写检查
+    if app.dialog.is_some() || app.mode == crate::app::Mode::Preview {
+        app.prepare_spell_checker();
+    }
+
     // 渲染对话框（如果有）
     if let Some(dialog) = &mut app.dialog {
-        dialogs::render_dialog(f, dialog);
+        dialogs::render_dialog(f, dialog, app.spell_checker.as_ref());
+    }
+
+    // 渲染后台任务的进度对话框
+    if let Some(job) = &app.job {
+        dialogs::render_progress_dialog(f, job);
     }
 
     // 渲染帮助面板（如果处于帮助模式）
@@ -36,10 +69,12 @@ pub fn render(f: &mut Frame, app: &mut App) {
         help::render(f, f.area());
     }
 
-    // 渲染预览面板（如果处于预览模式）
-    if app.mode == crate::app::Mode::Preview {
-        preview::render(f, f.area(), app);
-    }
+    // 渲染预览面板（如果处于预览模式），记录需要在本帧之后绘制的图片
+    app.preview_images = if app.mode == crate::app::Mode::Preview {
+        preview::render(f, f.area(), app)
+    } else {
+        Vec::new()
+    };
 
     // 渲染搜索条（如果处于搜索模式）
     if app.mode == crate::app::Mode::Search {
@@ -51,6 +86,20 @@ pub fn render(f: &mut Frame, app: &mut App) {
         render_status_select_bar(f, f.area(), app);
     }
 
+    // 渲染标签概览（:tags）
+    if app.mode == crate::app::Mode::TagOverview
+        && let Some(overview) = &app.tag_overview
+    {
+        tag_overview::render(f, f.area(), overview);
+    }
+
+    // 渲染发送任务的选择条（Space t >）
+    if app.mode == crate::app::Mode::SendToPane
+        && let Some(state) = &app.send_to_pane
+    {
+        send_to_pane::render(f, f.area(), state);
+    }
+
     // 渲染标记管理条（如果处于标记管理模式）
     if app.mode == crate::app::Mode::MarkSelect {
         render_mark_select_bar(f, f.area(), app);
@@ -61,20 +110,94 @@ pub fn render(f: &mut Frame, app: &mut App) {
         command_menu::render(f, f.area(), app);
     }
 
-    // 渲染命令补全（如果处于命令模式且有输入） - 已注释
-    // if app.mode == crate::app::Mode::Command {
-    //     command_completion::render(f, f.area(), app);
-    // }
+    // 渲染命令补全（如果处于命令模式）
+    if app.mode == crate::app::Mode::Command {
+        command_completion::render(f, f.area(), app);
+    }
 
     // 渲染欢迎对话框（如果是首次运行）
     if app.show_welcome_dialog {
         welcome::render(f, f.area(), &app.config);
     }
 
+    // 渲染键位速查浮层（Ctrl+/）
+    if app.show_cheat_sheet {
+        cheat_sheet::render(f, f.area(), app);
+    }
+
+    // 渲染教程浮层（:tutor）
+    if let Some(tutor) = &app.tutor {
+        tutor::render(f, f.area(), tutor);
+    }
+
     // 渲染通知栏（如果有通知）
     if let Some(ref notification) = app.notification {
         render_notification(f, f.area(), notification);
     }
+
+    // 右下角显示正在输入的按键序列或未绑定提示
+    render_key_sequence(f, f.area(), app);
+
+    // 无颜色方案：整帧去掉颜色
+    if theme::palette() == theme::Palette::NoColor {
+        theme::strip_colors(f.buffer_mut());
+    }
+}
+
+/// 隐藏看板时的画面：只显示应用名和恢复提示
+fn render_lock_screen(f: &mut Frame, area: ratatui::layout::Rect) {
+    use ratatui::layout::Alignment;
+    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::text::Line;
+    use ratatui::widgets::{Block, Paragraph};
+
+    f.render_widget(
+        Block::default().style(Style::default().bg(Color::Rgb(46, 52, 64))),
+        area,
+    );
+    let lines = vec![
+        Line::styled(
+            "helix-kanban",
+            Style::default()
+                .fg(Color::Rgb(136, 192, 208))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::from(""),
+        Line::styled("按任意键继续", Style::default().fg(Color::DarkGray)),
+    ];
+    let height = lines.len() as u16;
+    let text_area = ratatui::layout::Rect {
+        y: area.y + area.height.saturating_sub(height) / 2,
+        height: height.min(area.height),
+        ..area
+    };
+    f.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        text_area,
+    );
+}
+
+/// 在右下角显示正在输入的按键序列（如 `␣w`），或短暂显示“未绑定”提示
+fn render_key_sequence(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    use ratatui::style::{Color, Style};
+    use ratatui::widgets::Paragraph;
+
+    let (text, style) = match (&app.key_flash, app.pending_key_label()) {
+        (Some((message, _)), _) => (message.clone(), Style::default().fg(Color::Yellow)),
+        (None, Some(sequence)) => (sequence, Style::default().fg(Color::Cyan)),
+        (None, None) => return,
+    };
+    let text = format!(" {} ", text);
+    let width = (width::display_width(&text) as u16).min(area.width);
+    // 有通知时放在通知栏上方
+    let bottom_offset = if app.notification.is_some() { 4 } else { 1 };
+    let sequence_area = ratatui::layout::Rect {
+        x: area.x + area.width.saturating_sub(width + 2),
+        y: area.y + area.height.saturating_sub(bottom_offset),
+        width,
+        height: 1,
+    };
+    f.render_widget(Paragraph::new(text).style(style), sequence_area);
 }
 
 /// 渲染通知栏
@@ -84,7 +207,7 @@ fn render_notification(
     notification: &crate::app::Notification,
 ) {
     use crate::app::NotificationLevel;
-    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::style::{Modifier, Style};
     use ratatui::text::{Line, Span};
     use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
@@ -97,12 +220,13 @@ fn render_notification(
         height: notification_height,
     };
 
-    // 根据级别选择颜色
-    let (bg_color, fg_color, prefix) = match notification.level {
-        NotificationLevel::Info => (Color::Blue, Color::White, "ℹ"),
-        NotificationLevel::Success => (Color::Green, Color::White, "✓"),
-        NotificationLevel::Warning => (Color::Yellow, Color::Black, "⚠"),
-        NotificationLevel::Error => (Color::Red, Color::White, "✗"),
+    // 根据级别选择颜色，图标区分级别，不只依赖颜色
+    let (bg_color, fg_color) = theme::notification_colors(notification.level);
+    let prefix = match notification.level {
+        NotificationLevel::Info => "ℹ",
+        NotificationLevel::Success => "✓",
+        NotificationLevel::Warning => "⚠",
+        NotificationLevel::Error => "✗",
     };
 
     // 先清除背景区域
@@ -367,7 +491,7 @@ fn render_mark_select_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &mut
                     .tasks
                     .iter()
                     .filter(|task| task.status == status)
-                    .filter(|task| app.marked_tasks.contains(&(project.name.clone(), task.id)))
+                    .filter(|task| app.marked_tasks.contains(&(project.id.clone(), task.id)))
                     .count(),
             )
         })
@@ -430,12 +554,24 @@ fn render_split_tree(
     use layout::SplitNode;
 
     match node {
+        SplitNode::Leaf { project_id, id } if app.focus_mode && *id != app.focused_pane => {
+            // 专注模式：非聚焦面板折叠为只显示项目名的窄条
+            let name = project_id
+                .as_ref()
+                .and_then(|pid| app.projects.iter().find(|p| &p.id == pid))
+                .map(|p| p.name.as_str())
+                .unwrap_or("无项目");
+            render_pane_strip(f, area, name);
+        }
         SplitNode::Leaf { project_id, id } => {
             let is_focused = *id == app.focused_pane;
             if let Some(pid) = project_id {
                 // 克隆项目以避免借用冲突
-                if let Some(project) = app.projects.iter().find(|p| &p.name == pid).cloned() {
-                    kanban::render(f, area, &project, is_focused, app);
+                if let Some(mut project) = app.projects.iter().find(|p| &p.id == pid).cloned() {
+                    if app.redact {
+                        project = redact::redact_project(project);
+                    }
+                    kanban::render(f, area, &project, *id, is_focused, app);
                 } else {
                     render_empty_pane(f, area, "项目未找到", is_focused);
                 }
@@ -446,10 +582,13 @@ fn render_split_tree(
         SplitNode::Horizontal { left, right, ratio } => {
             let chunks = Layout::default()
                 .direction(Direction::Horizontal)
-                .constraints([
-                    Constraint::Percentage((ratio * 100.0) as u16),
-                    Constraint::Percentage(((1.0 - ratio) * 100.0) as u16),
-                ])
+                .constraints(split_constraints(
+                    app,
+                    left,
+                    right,
+                    *ratio,
+                    FOCUS_STRIP_WIDTH,
+                ))
                 .split(area);
 
             render_split_tree(f, chunks[0], left, app);
@@ -458,10 +597,13 @@ fn render_split_tree(
         SplitNode::Vertical { top, bottom, ratio } => {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Percentage((ratio * 100.0) as u16),
-                    Constraint::Percentage(((1.0 - ratio) * 100.0) as u16),
-                ])
+                .constraints(split_constraints(
+                    app,
+                    top,
+                    bottom,
+                    *ratio,
+                    FOCUS_STRIP_HEIGHT,
+                ))
                 .split(area);
 
             render_split_tree(f, chunks[0], top, app);
@@ -470,6 +612,48 @@ fn render_split_tree(
     }
 }
 
+/// 专注模式下折叠面板的宽度（左右分屏）和高度（上下分屏）
+const FOCUS_STRIP_WIDTH: u16 = 16;
+const FOCUS_STRIP_HEIGHT: u16 = 3;
+
+/// 分屏两侧的尺寸：专注模式下不含聚焦面板的一侧折叠为窄条，其余按比例
+fn split_constraints(
+    app: &App,
+    first: &layout::SplitNode,
+    second: &layout::SplitNode,
+    ratio: f32,
+    strip: u16,
+) -> [Constraint; 2] {
+    if app.focus_mode {
+        if first.find_pane(app.focused_pane).is_some() {
+            return [Constraint::Min(0), Constraint::Length(strip)];
+        }
+        if second.find_pane(app.focused_pane).is_some() {
+            return [Constraint::Length(strip), Constraint::Min(0)];
+        }
+    }
+    [
+        Constraint::Percentage((ratio * 100.0) as u16),
+        Constraint::Percentage(((1.0 - ratio) * 100.0) as u16),
+    ]
+}
+
+/// 渲染专注模式下折叠的面板
+fn render_pane_strip(f: &mut Frame, area: ratatui::layout::Rect, name: &str) {
+    use ratatui::style::{Color, Style};
+    use ratatui::widgets::{Block, Borders, Paragraph};
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .border_type(ratatui::widgets::BorderType::Rounded);
+    let paragraph = Paragraph::new(name.to_string())
+        .block(block)
+        .style(Style::default().fg(Color::DarkGray));
+
+    f.render_widget(paragraph, area);
+}
+
 /// 渲染空面板
 fn render_empty_pane(f: &mut Frame, area: ratatui::layout::Rect, message: &str, is_focused: bool) {
     use ratatui::style::{Color, Style};
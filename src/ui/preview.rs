@@ -1,3 +1,6 @@
+use super::image::{self, ImagePlacement};
+use super::links;
+use super::width::display_width;
 use crate::app::App;
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
@@ -5,13 +8,17 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
-/// 渲染预览界面
-pub fn render(f: &mut Frame, area: Rect, app: &App) {
+/// 渲染预览界面，返回正文中需要绘制的图片位置
+pub fn render(f: &mut Frame, area: Rect, app: &App) -> Vec<ImagePlacement> {
     // 渲染半透明背景遮罩
     render_backdrop(f, area);
 
-    // 创建居中弹窗（80% 宽度，90% 高度）
-    let popup_area = centered_rect(80, 90, area);
+    // 创建居中弹窗（宽度按配置，高度至少 90%；100% 时全屏）
+    let size = app.config.preview_size.clamp(
+        crate::config::PREVIEW_SIZE_MIN,
+        crate::config::PREVIEW_SIZE_MAX,
+    );
+    let popup_area = centered_rect(size, size.max(90), area);
 
     // 清空弹窗区域
     f.render_widget(Clear, popup_area);
@@ -26,14 +33,41 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         ])
         .split(popup_area);
 
+    // 屏幕共享模式（:redact）下不显示预览的标题和正文
+    if app.redact {
+        render_header(f, chunks[0], super::redact::PLACEHOLDER, "");
+        f.render_widget(
+            Paragraph::new("屏幕共享模式下不显示内容，:redact 恢复后查看")
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+        render_statusbar(f, chunks[2], size, None);
+        return Vec::new();
+    }
+
     // 渲染标题栏
-    render_header(f, chunks[0]);
+    render_header(f, chunks[0], &app.preview_title, &app.preview_header);
+
+    // 渲染预览内容，正文中的链接加下划线，Tab 选中的链接反色
+    let urls = links::find_urls(&app.preview_content);
+    let selected_url = app
+        .preview_link
+        .and_then(|i| urls.get(i))
+        .map(String::as_str);
+    let images = render_content(f, chunks[1], app, selected_url);
 
-    // 渲染预览内容
-    render_content(f, chunks[1], app);
+    // 标题和正文中拼写错误的单词加下划线
+    if let Some(spell) = &app.spell_checker {
+        crate::ui::spell::underline_unknown(f.buffer_mut(), chunks[0], spell);
+        crate::ui::spell::underline_unknown(f.buffer_mut(), chunks[1], spell);
+    }
 
     // 渲染状态栏
-    render_statusbar(f, chunks[2]);
+    let link_status = (!urls.is_empty()).then_some((app.preview_link, urls.len()));
+    render_statusbar(f, chunks[2], size, link_status);
+
+    images
 }
 
 /// 渲染半透明背景遮罩
@@ -64,9 +98,14 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 }
 
 /// 渲染标题栏
-fn render_header(f: &mut Frame, area: Rect) {
+fn render_header(f: &mut Frame, area: Rect, title: &str, header: &str) {
+    let title = if title.is_empty() {
+        "任务预览"
+    } else {
+        title
+    };
     let title_block = Block::default()
-        .title(" 任务预览 ")
+        .title(format!(" {} ", title))
         .title_style(
             Style::default()
                 .fg(Color::Rgb(136, 192, 208)) // Nord cyan
@@ -77,96 +116,85 @@ fn render_header(f: &mut Frame, area: Rect) {
         .border_type(ratatui::widgets::BorderType::Rounded)
         .style(Style::default().bg(Color::Rgb(46, 52, 64))); // Nord background
 
-    f.render_widget(title_block, area);
+    // 任务的自定义字段显示在标题栏内
+    let header = Paragraph::new(Line::from(Span::styled(
+        format!(" {}", header),
+        Style::default().fg(Color::Rgb(216, 222, 233)),
+    )))
+    .block(title_block);
+    f.render_widget(header, area);
 }
 
-/// 渲染预览内容
-fn render_content(f: &mut Frame, area: Rect, app: &App) {
-    // 按行分割内容并进行 Markdown 高亮
-    let lines: Vec<Line> = app
+/// 渲染预览内容，返回需要在本帧之后绘制的图片位置
+fn render_content(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    selected_url: Option<&str>,
+) -> Vec<ImagePlacement> {
+    // 边框内的区域，用于计算图片所在的行
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+    let mut lines: Vec<Line> = Vec::new();
+    let mut images = Vec::new();
+    // 已占用的屏幕行数（按换行后的宽度估算）
+    let mut row: u16 = 0;
+
+    for line in app
         .preview_content
         .lines()
         .skip(app.preview_scroll as usize)
-        .map(|line| {
-            let trimmed = line.trim_start();
-
-            // 标题（需要严格匹配 # 后面有空格）
-            if trimmed.starts_with("# ") {
-                // 一级标题 - Nord yellow
-                Line::from(Span::styled(
-                    line,
-                    Style::default()
-                        .fg(Color::Rgb(235, 203, 139)) // Nord yellow
-                        .add_modifier(Modifier::BOLD),
-                ))
-            } else if trimmed.starts_with("## ") {
-                // 二级标题 - Nord frost
-                Line::from(Span::styled(
-                    line,
-                    Style::default()
-                        .fg(Color::Rgb(136, 192, 208)) // Nord frost
-                        .add_modifier(Modifier::BOLD),
-                ))
-            } else if trimmed.starts_with("### ") || trimmed.starts_with("#### ") {
-                // 三级/四级标题 - Nord light blue
-                Line::from(Span::styled(
-                    line,
-                    Style::default()
-                        .fg(Color::Rgb(129, 161, 193)) // Nord light blue
-                        .add_modifier(Modifier::BOLD),
-                ))
-            } else if trimmed.starts_with("- [ ]") || trimmed.starts_with("* [ ]") {
-                // 未完成任务列表 - Nord frost
-                Line::from(Span::styled(
-                    line,
-                    Style::default().fg(Color::Rgb(136, 192, 208)),
-                ))
-            } else if trimmed.starts_with("- [x]") || trimmed.starts_with("* [x]") {
-                // 已完成任务列表 - Nord green
-                Line::from(Span::styled(
-                    line,
-                    Style::default().fg(Color::Rgb(163, 190, 140)), // Nord green
-                ))
-            } else if trimmed.starts_with("- ")
-                || trimmed.starts_with("* ")
-                || trimmed.starts_with("+ ")
-            {
-                // 普通列表 - Nord green
-                Line::from(Span::styled(
-                    line,
-                    Style::default().fg(Color::Rgb(163, 190, 140)),
-                ))
-            } else if trimmed.starts_with("> ") {
-                // 引用 - Nord purple (斜体)
-                Line::from(Span::styled(
-                    line,
-                    Style::default()
-                        .fg(Color::Rgb(180, 142, 173)) // Nord purple
-                        .add_modifier(Modifier::ITALIC),
-                ))
-            } else if trimmed.starts_with("```") {
-                // 代码块标记 - Nord orange
-                Line::from(Span::styled(
-                    line,
-                    Style::default().fg(Color::Rgb(208, 135, 112)), // Nord orange
-                ))
-            } else if trimmed.starts_with("    ") || trimmed.starts_with("\t") {
-                // 缩进代码块 - Nord snow storm (dim)
-                Line::from(Span::styled(
-                    line,
-                    Style::default()
-                        .fg(Color::Rgb(216, 222, 233))
-                        .add_modifier(Modifier::DIM),
-                ))
-            } else {
-                // 普通文本 - Nord snow storm
-                Line::from(Span::styled(
-                    line,
-                    Style::default().fg(Color::Rgb(216, 222, 233)),
-                ))
-            }
-        })
-        .collect();
+    {
+        let Some((alt, target)) = image::parse_image_link(line) else {
+            row = row.saturating_add(wrapped_rows(line, inner.width));
+            lines.push(links::highlight(markdown_line(line), selected_url));
+            continue;
+        };
+
+        let path = image::resolve_image_path(app.preview_base_dir.as_deref(), target);
+        let displayable = app
+            .image_protocol
+            .zip(path.as_deref())
+            .is_some_and(|(protocol, path)| image::can_display(protocol, path));
+        let caption = if alt.is_empty() { target } else { alt };
+
+        // 图片放在说明行下方，超出可见区域的部分裁掉
+        let top = row.saturating_add(1);
+        let height = image::IMAGE_ROWS.min(inner.height.saturating_sub(top));
+        if let (true, Some(path)) = (displayable, path)
+            && height >= 2
+        {
+            lines.push(Line::from(Span::styled(
+                format!("🖼 {}", caption),
+                Style::default().fg(Color::Rgb(136, 192, 208)),
+            )));
+            lines.extend((0..image::IMAGE_ROWS).map(|_| Line::from("")));
+            images.push(ImagePlacement {
+                area: Rect {
+                    x: inner.x,
+                    y: inner.y + top,
+                    width: inner.width,
+                    height,
+                },
+                path,
+            });
+            row = top.saturating_add(image::IMAGE_ROWS);
+        } else {
+            // 无法显示时用占位行展示图片路径
+            let placeholder = format!("🖼 [图片: {}] {}", caption, target);
+            row = row.saturating_add(wrapped_rows(&placeholder, inner.width));
+            lines.push(Line::from(Span::styled(
+                placeholder,
+                Style::default()
+                    .fg(Color::Rgb(129, 161, 193))
+                    .add_modifier(Modifier::ITALIC),
+            )));
+        }
+    }
 
     let paragraph = Paragraph::new(lines)
         .block(
@@ -179,17 +207,118 @@ fn render_content(f: &mut Frame, area: Rect, app: &App) {
         .wrap(Wrap { trim: false });
 
     f.render_widget(paragraph, area);
+    images
+}
+
+/// 一行文本换行后占用的行数
+fn wrapped_rows(line: &str, width: u16) -> u16 {
+    let width = usize::from(width.max(1));
+    display_width(line).div_ceil(width).max(1) as u16
+}
+
+/// 按 Markdown 语法高亮一行内容
+fn markdown_line(line: &str) -> Line<'_> {
+    let trimmed = line.trim_start();
+
+    // 标题（需要严格匹配 # 后面有空格）
+    if trimmed.starts_with("# ") {
+        // 一级标题 - Nord yellow
+        Line::from(Span::styled(
+            line,
+            Style::default()
+                .fg(Color::Rgb(235, 203, 139)) // Nord yellow
+                .add_modifier(Modifier::BOLD),
+        ))
+    } else if trimmed.starts_with("## ") {
+        // 二级标题 - Nord frost
+        Line::from(Span::styled(
+            line,
+            Style::default()
+                .fg(Color::Rgb(136, 192, 208)) // Nord frost
+                .add_modifier(Modifier::BOLD),
+        ))
+    } else if trimmed.starts_with("### ") || trimmed.starts_with("#### ") {
+        // 三级/四级标题 - Nord light blue
+        Line::from(Span::styled(
+            line,
+            Style::default()
+                .fg(Color::Rgb(129, 161, 193)) // Nord light blue
+                .add_modifier(Modifier::BOLD),
+        ))
+    } else if trimmed.starts_with("- [ ]") || trimmed.starts_with("* [ ]") {
+        // 未完成任务列表 - Nord frost
+        Line::from(Span::styled(
+            line,
+            Style::default().fg(Color::Rgb(136, 192, 208)),
+        ))
+    } else if trimmed.starts_with("- [x]") || trimmed.starts_with("* [x]") {
+        // 已完成任务列表 - Nord green
+        Line::from(Span::styled(
+            line,
+            Style::default().fg(Color::Rgb(163, 190, 140)), // Nord green
+        ))
+    } else if trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("+ ") {
+        // 普通列表 - Nord green
+        Line::from(Span::styled(
+            line,
+            Style::default().fg(Color::Rgb(163, 190, 140)),
+        ))
+    } else if trimmed.starts_with("> ") {
+        // 引用 - Nord purple (斜体)
+        Line::from(Span::styled(
+            line,
+            Style::default()
+                .fg(Color::Rgb(180, 142, 173)) // Nord purple
+                .add_modifier(Modifier::ITALIC),
+        ))
+    } else if trimmed.starts_with("```") {
+        // 代码块标记 - Nord orange
+        Line::from(Span::styled(
+            line,
+            Style::default().fg(Color::Rgb(208, 135, 112)), // Nord orange
+        ))
+    } else if trimmed.starts_with("    ") || trimmed.starts_with("\t") {
+        // 缩进代码块 - Nord snow storm (dim)
+        Line::from(Span::styled(
+            line,
+            Style::default()
+                .fg(Color::Rgb(216, 222, 233))
+                .add_modifier(Modifier::DIM),
+        ))
+    } else {
+        // 普通文本 - Nord snow storm
+        Line::from(Span::styled(
+            line,
+            Style::default().fg(Color::Rgb(216, 222, 233)),
+        ))
+    }
 }
 
-/// 渲染状态栏
-fn render_statusbar(f: &mut Frame, area: Rect) {
-    let help_text = Line::from(vec![
+/// 渲染状态栏；正文中有链接时显示 (选中的序号, 链接数)
+fn render_statusbar(f: &mut Frame, area: Rect, size: u16, links: Option<(Option<usize>, usize)>) {
+    let mut spans = vec![
         Span::raw("  "),
         Span::styled("j/k", Style::default().fg(Color::Rgb(136, 192, 208))), // Nord cyan
         Span::raw(" 滚动  "),
+        Span::styled("+/-", Style::default().fg(Color::Rgb(136, 192, 208))), // Nord cyan
+        Span::raw(format!(" 大小 {}%  ", size)),
         Span::styled("ESC", Style::default().fg(Color::Rgb(136, 192, 208))), // Nord cyan
         Span::raw(" 返回  "),
-    ]);
+    ];
+    if let Some((selected, count)) = links {
+        let position = selected.map_or("-".to_string(), |i| (i + 1).to_string());
+        spans.push(Span::styled(
+            "Tab",
+            Style::default().fg(Color::Rgb(136, 192, 208)),
+        ));
+        spans.push(Span::raw(format!(" 链接 {}/{}  ", position, count)));
+        spans.push(Span::styled(
+            "Enter",
+            Style::default().fg(Color::Rgb(136, 192, 208)),
+        ));
+        spans.push(Span::raw(" 打开  "));
+    }
+    let help_text = Line::from(spans);
 
     let paragraph = Paragraph::new(help_text)
         .alignment(Alignment::Left)
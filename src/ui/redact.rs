@@ -0,0 +1,53 @@
+/// 屏幕共享时隐藏任务内容（`:redact`）
+///
+/// 渲染前把任务标题换成占位符，并隐藏正文、标签和自定义字段，只保留 ID、优先级、卡片颜色、
+/// 估算点数和各列数量。只影响界面显示，磁盘上的任务不变。
+use crate::models::Project;
+use crate::models::field::{COLOR_FIELD, ESTIMATE_FIELD};
+
+/// 隐藏后显示的标题和正文
+pub const PLACEHOLDER: &str = "••••••";
+
+/// 返回隐藏了任务内容的项目副本
+pub fn redact_project(mut project: Project) -> Project {
+    for task in &mut project.tasks {
+        task.title = PLACEHOLDER.to_string();
+        task.content = PLACEHOLDER.to_string();
+        task.tags.clear();
+        task.extra
+            .retain(|key, _| key == ESTIMATE_FIELD || key == COLOR_FIELD);
+    }
+    project
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectType, Task};
+
+    #[test]
+    fn keeps_only_ids_priorities_colors_and_estimates() {
+        let mut project = Project::new(
+            "clients".to_string(),
+            std::path::PathBuf::from("/tmp/clients"),
+            ProjectType::Global,
+        );
+        let mut task = Task::new(3, "Call ACME about renewal".to_string(), "todo".to_string());
+        task.content = "ACME contact: jane@acme.test".to_string();
+        task.priority = Some("high".to_string());
+        task.tags = vec!["acme".to_string()];
+        task.extra.insert("customer".to_string(), "ACME".into());
+        task.extra.insert(ESTIMATE_FIELD.to_string(), 3.into());
+        task.set_color(Some("red"));
+        project.tasks.push(task);
+
+        let redacted = redact_project(project);
+        let task = &redacted.tasks[0];
+        assert_eq!((task.id, task.status.as_str()), (3, "todo"));
+        assert_eq!(task.priority.as_deref(), Some("high"));
+        assert_eq!(task.estimate(), Some(3.0));
+        assert_eq!(task.color(), Some("red"));
+        assert!(!format!("{:?}", task).contains("ACME"));
+        assert!(!format!("{:?}", task).contains("acme"));
+    }
+}
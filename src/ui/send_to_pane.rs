@@ -0,0 +1,142 @@
+/// 发送任务到其他面板（`Space t >`）：先按编号选择目标面板，再选择目标项目中的状态
+use crate::ui::layout::SplitNode;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+/// 可以接收任务的面板
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendTarget {
+    /// 面板编号（按布局顺序从 1 开始，发送时显示在面板标题中）
+    pub number: usize,
+    pub pane_id: usize,
+    pub project_id: String,
+    pub project_name: String,
+}
+
+/// 发送任务的进度
+#[derive(Debug, Clone, Default)]
+pub struct SendToPane {
+    pub task_id: u32,
+    pub task_title: String,
+    /// true 复制任务，false 移动任务（`c` 切换）
+    pub copy: bool,
+    pub targets: Vec<SendTarget>,
+    /// 已选择的目标面板（`targets` 中的索引），None 表示还在选择面板
+    pub target: Option<usize>,
+    /// 目标项目的状态 (状态名, 显示名)
+    pub statuses: Vec<(String, String)>,
+    pub selected: usize,
+}
+
+impl SendToPane {
+    /// 按面板编号查找目标
+    pub fn target_by_number(&self, number: usize) -> Option<usize> {
+        self.targets.iter().position(|t| t.number == number)
+    }
+
+    pub fn selected_target(&self) -> Option<&SendTarget> {
+        self.targets.get(self.target?)
+    }
+
+    /// 左右移动选中项（面板或状态），到头后停住
+    pub fn move_selection(&mut self, forward: bool) {
+        let len = if self.target.is_some() {
+            self.statuses.len()
+        } else {
+            self.targets.len()
+        };
+        self.selected = if forward {
+            (self.selected + 1).min(len.saturating_sub(1))
+        } else {
+            self.selected.saturating_sub(1)
+        };
+    }
+}
+
+/// 面板编号：按布局顺序从 1 开始
+pub fn pane_number(layout: &SplitNode, pane_id: usize) -> Option<usize> {
+    layout
+        .collect_pane_ids()
+        .iter()
+        .position(|&id| id == pane_id)
+        .map(|i| i + 1)
+}
+
+/// 在底部渲染选择条（与状态选择条相同的样式）
+pub fn render(f: &mut Frame, area: Rect, state: &SendToPane) {
+    let bar_area = Rect {
+        y: area.y + area.height.saturating_sub(3),
+        height: 3.min(area.height),
+        ..area
+    };
+    f.render_widget(Clear, bar_area);
+
+    let bg_color = Color::Rgb(46, 52, 64);
+    let accent_color = Color::Rgb(136, 192, 208);
+    let normal = Style::default().fg(Color::Rgb(129, 161, 193)).bg(bg_color);
+    let highlight = Style::default().fg(Color::Black).bg(accent_color);
+
+    let action = if state.copy { "复制" } else { "移动" };
+    let mut spans = vec![Span::styled(
+        format!(" {} #{} 到", action, state.task_id),
+        Style::default()
+            .fg(accent_color)
+            .bg(bg_color)
+            .add_modifier(Modifier::BOLD),
+    )];
+
+    let hint = match state.selected_target() {
+        None => {
+            spans.push(Span::styled(
+                " 面板: ",
+                Style::default().fg(accent_color).bg(bg_color),
+            ));
+            for (i, target) in state.targets.iter().enumerate() {
+                spans.push(Span::styled(
+                    format!(" [{}] {} ", target.number, target.project_name),
+                    if i == state.selected {
+                        highlight
+                    } else {
+                        normal
+                    },
+                ));
+            }
+            "  面板编号: 选择  c: 移动/复制  Esc: 取消"
+        }
+        Some(target) => {
+            spans.push(Span::styled(
+                format!(" {} 的状态: ", target.project_name),
+                Style::default().fg(accent_color).bg(bg_color),
+            ));
+            for (i, (_, display)) in state.statuses.iter().enumerate() {
+                spans.push(Span::styled(
+                    format!(" [{}] {} ", i + 1, display),
+                    if i == state.selected {
+                        highlight
+                    } else {
+                        normal
+                    },
+                ));
+            }
+            "  1-9: 选择状态  Enter: 确认  Esc: 取消"
+        }
+    };
+    spans.push(Span::styled(
+        hint,
+        Style::default().fg(accent_color).bg(bg_color),
+    ));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent_color).bg(bg_color))
+        .style(Style::default().bg(bg_color));
+    f.render_widget(
+        Paragraph::new(Line::from(spans))
+            .block(block)
+            .style(Style::default().bg(bg_color)),
+        bar_area,
+    );
+}
@@ -24,19 +24,21 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         .projects
         .iter()
         .map(|project| {
-            let is_selected = current_project.as_ref() == Some(&project.name);
+            let is_selected = current_project.as_ref() == Some(&project.id);
 
             // 计算任务数量
             let task_count = project.tasks.len();
 
-            // 项目类型标签
+            // 项目类型标签（关联到当前目录的全局项目显示 [P]）
             let type_tag = match project.project_type {
+                ProjectType::Global if project.pinned => "[P]",
                 ProjectType::Global => "[G]",
                 ProjectType::Local => "[L]",
             };
             let tag_color = match project.project_type {
-                ProjectType::Global => Color::Rgb(136, 192, 208), // 蓝色
-                ProjectType::Local => Color::Rgb(163, 190, 140),  // 绿色
+                ProjectType::Global if project.pinned => Color::Rgb(235, 203, 139), // 黄色
+                ProjectType::Global => Color::Rgb(136, 192, 208),                   // 蓝色
+                ProjectType::Local => Color::Rgb(163, 190, 140),                    // 绿色
             };
 
             // 构建列表项内容
@@ -0,0 +1,231 @@
+/// 拼写检查：在任务标题输入框和任务预览中给不认识的英文单词加下划线
+///
+/// 需要编译时启用 `spellcheck` feature 并在配置中打开 `spell_check`。词表使用系统词典
+/// （默认 `/usr/share/dict/words`，可用 `spell_wordlist` 指定），每个项目还可以在项目目录
+/// （`.kanban/` 下）的 `dictionary.txt` 中添加自己的词（`:spell-add`）。
+/// 只检查 ASCII 字母组成的单词；中文、数字、路径和驼峰标识符不检查。
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// 项目自定义词典的文件名（每行一个词，`#` 开头为注释）
+pub const DICTIONARY_FILE: &str = "dictionary.txt";
+
+/// 未指定 `spell_wordlist` 时依次尝试的系统词表
+const DEFAULT_WORDLISTS: &[&str] = &["/usr/share/dict/words", "/usr/dict/words"];
+
+/// 词表和当前项目的自定义词典
+#[derive(Debug, Clone, Default)]
+pub struct SpellChecker {
+    words: HashSet<String>,
+    project_path: PathBuf,
+    custom: HashSet<String>,
+}
+
+/// 读取词表文件，全部转为小写
+fn read_words(path: &Path) -> Option<HashSet<String>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    Some(
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect(),
+    )
+}
+
+/// 项目自定义词典的路径
+pub fn dictionary_path(project_path: &Path) -> PathBuf {
+    project_path.join(DICTIONARY_FILE)
+}
+
+/// 把单词加入项目的自定义词典（已存在时不重复添加）
+pub fn add_to_dictionary(project_path: &Path, word: &str) -> Result<(), String> {
+    let path = dictionary_path(project_path);
+    let words = read_words(&path).unwrap_or_default();
+    if words.contains(&word.to_lowercase()) {
+        return Ok(());
+    }
+    let mut content = std::fs::read_to_string(&path).unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(word);
+    content.push('\n');
+    std::fs::write(&path, content).map_err(|e| format!("无法写入 {}: {}", path.display(), e))
+}
+
+/// 查找可用的系统词表
+pub fn find_wordlist(configured: Option<&str>) -> Option<PathBuf> {
+    match configured {
+        Some(path) => Some(PathBuf::from(path)),
+        None => DEFAULT_WORDLISTS
+            .iter()
+            .map(PathBuf::from)
+            .find(|path| path.is_file()),
+    }
+}
+
+impl SpellChecker {
+    /// 加载词表和项目词典；未启用 `spellcheck` feature 或找不到词表时返回 None
+    pub fn load(wordlist: Option<&str>, project_path: &Path) -> Option<Self> {
+        if !cfg!(feature = "spellcheck") {
+            return None;
+        }
+        let words = read_words(&find_wordlist(wordlist)?)?;
+        let mut checker = Self {
+            words,
+            ..Default::default()
+        };
+        checker.set_project(project_path);
+        Some(checker)
+    }
+
+    /// 切换到另一个项目时重新读取它的自定义词典
+    pub fn set_project(&mut self, project_path: &Path) {
+        if self.project_path != project_path {
+            self.project_path = project_path.to_path_buf();
+            self.reload_dictionary();
+        }
+    }
+
+    /// 重新读取当前项目的自定义词典（`:spell-add` 之后）
+    pub fn reload_dictionary(&mut self) {
+        self.custom = read_words(&dictionary_path(&self.project_path)).unwrap_or_default();
+    }
+
+    pub fn is_known(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        let stem = lower.strip_suffix("'s").unwrap_or(&lower);
+        self.words.contains(&lower)
+            || self.words.contains(stem)
+            || self.custom.contains(&lower)
+            || self.custom.contains(stem)
+    }
+
+    /// 文本中不认识的单词（按字符计的位置）
+    pub fn unknown_words(&self, text: &str) -> Vec<Range<usize>> {
+        let chars: Vec<char> = text.chars().collect();
+        words(&chars)
+            .into_iter()
+            .filter(|range| {
+                let word: String = chars[range.clone()].iter().collect();
+                !self.is_known(&word)
+            })
+            .collect()
+    }
+}
+
+/// 需要检查的单词：连续的 ASCII 字母（中间可以有撇号）
+///
+/// 跳过单个字母、全大写缩写、驼峰标识符，以及和数字、`_`、`/`、`@`、`\` 相连或用点号连接的部分
+/// （路径、文件名、邮箱、代码）。
+fn words(chars: &[char]) -> Vec<Range<usize>> {
+    let is_letter = |i: usize| chars.get(i).is_some_and(|c| c.is_ascii_alphabetic());
+    let is_code = |i: Option<usize>| {
+        i.and_then(|i| chars.get(i))
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '_' | '/' | '@' | '\\'))
+    };
+    // 文件名、域名等：点号两边都是字母或数字
+    let is_dotted = |dot: usize, other: Option<usize>| {
+        chars.get(dot) == Some(&'.')
+            && other
+                .and_then(|i| chars.get(i))
+                .is_some_and(|c| c.is_ascii_alphanumeric())
+    };
+
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if !is_letter(i) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while is_letter(i) || (chars.get(i) == Some(&'\'') && is_letter(i + 1)) {
+            i += 1;
+        }
+        let word = &chars[start..i];
+        let skip = word.len() < 2
+            || word.iter().all(|c| !c.is_ascii_lowercase())
+            || word[1..].iter().any(|c| c.is_ascii_uppercase())
+            || is_code(start.checked_sub(1))
+            || is_code(Some(i))
+            || start
+                .checked_sub(1)
+                .is_some_and(|dot| is_dotted(dot, dot.checked_sub(1)))
+            || is_dotted(i, Some(i + 1));
+        if !skip {
+            ranges.push(start..i);
+        }
+    }
+    ranges
+}
+
+/// 给缓冲区中指定区域内不认识的单词加红色下划线（在输入框、预览渲染之后调用）
+pub fn underline_unknown(buf: &mut Buffer, area: Rect, checker: &SpellChecker) {
+    let area = area.intersection(buf.area);
+    let style = Style::default()
+        .add_modifier(Modifier::UNDERLINED)
+        .underline_color(Color::Red);
+    for y in area.top()..area.bottom() {
+        // 每个单元格的第一个字符；宽字符后面的空单元格当作分隔
+        let row: Vec<char> = (area.left()..area.right())
+            .map(|x| buf[(x, y)].symbol().chars().next().unwrap_or(' '))
+            .collect();
+        let row: String = row.into_iter().collect();
+        for range in checker.unknown_words(&row) {
+            for offset in range {
+                buf[(area.x + offset as u16, y)].set_style(style);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker(words: &[&str]) -> SpellChecker {
+        SpellChecker {
+            words: words.iter().map(|w| w.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unknown_words_skip_code_acronyms_and_custom_words() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut checker = checker(&["fix", "the", "login", "page", "user", "in"]);
+        checker.set_project(dir.path());
+
+        let text = "Fix teh login page for user's API in src/main.rs 修复 v2beta helixKanban";
+        let unknown: Vec<String> = checker
+            .unknown_words(text)
+            .into_iter()
+            .map(|r| text.chars().skip(r.start).take(r.len()).collect())
+            .collect();
+        assert_eq!(unknown, vec!["teh", "for"]);
+
+        add_to_dictionary(dir.path(), "teh").unwrap();
+        add_to_dictionary(dir.path(), "teh").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dictionary_path(dir.path())).unwrap(),
+            "teh\n"
+        );
+        checker.reload_dictionary();
+        assert!(checker.is_known("Teh"));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 1));
+        buf.set_string(0, 0, "fix for", Style::default());
+        let area = buf.area;
+        underline_unknown(&mut buf, area, &checker);
+        assert!(!buf[(0, 0)].modifier.contains(Modifier::UNDERLINED));
+        assert!(buf[(4, 0)].modifier.contains(Modifier::UNDERLINED));
+        assert!(!buf[(7, 0)].modifier.contains(Modifier::UNDERLINED));
+    }
+}
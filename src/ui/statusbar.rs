@@ -12,7 +12,7 @@ use ratatui::{
 pub fn render(f: &mut Frame, area: Rect, app: &App) {
     let mode_text = match app.mode {
         Mode::Normal => ("NORMAL", Color::Green),
-        // Mode::Command => ("COMMAND", Color::Yellow), // 已注释
+        Mode::Command => ("COMMAND", Color::Yellow),
         Mode::TaskSelect => ("SELECT", Color::Cyan),
         Mode::Dialog => ("DIALOG", Color::Magenta),
         Mode::Help => ("HELP", Color::Blue),
@@ -21,6 +21,10 @@ pub fn render(f: &mut Frame, area: Rect, app: &App) {
         Mode::Search => ("SEARCH", Color::Yellow),
         Mode::StatusSelect => ("MOVE", Color::Magenta),
         Mode::MarkSelect => ("MARK", Color::Yellow),
+        Mode::QuickAdd => ("ADD", Color::Green),
+        Mode::TagOverview => ("TAGS", Color::Cyan),
+        Mode::SendToPane => ("SEND", Color::Magenta),
+        Mode::StatusRename => ("RENAME", Color::Green),
     };
 
     // 显示键序列
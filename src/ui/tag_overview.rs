@@ -0,0 +1,177 @@
+/// 标签概览（`:tags`）：列出当前项目的全部标签、任务数和条形图，Enter 按标签筛选面板
+use crate::models::Project;
+use crate::ui::tags::tag_color;
+use crate::ui::width::{display_width, pad_to_width, truncate_to_width};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState};
+use std::collections::HashMap;
+
+/// 标签名列的最大宽度
+const MAX_TAG_WIDTH: usize = 24;
+/// 条形图的最大长度
+const MAX_BAR_WIDTH: usize = 30;
+
+/// 标签概览的内容和选中项
+#[derive(Debug, Clone, Default)]
+pub struct TagOverview {
+    pub project_name: String,
+    /// (标签, 任务数)，按任务数从多到少排列
+    pub tags: Vec<(String, usize)>,
+    pub selected: usize,
+}
+
+impl TagOverview {
+    pub fn new(project: &Project) -> Self {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for tag in project.tasks.iter().flat_map(|task| &task.tags) {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+        let mut tags: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(tag, count)| (tag.to_string(), count))
+            .collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Self {
+            project_name: project.name.clone(),
+            tags,
+            selected: 0,
+        }
+    }
+
+    pub fn selected_tag(&self) -> Option<&str> {
+        self.tags.get(self.selected).map(|(tag, _)| tag.as_str())
+    }
+
+    /// 上下移动选中项，到头后停住
+    pub fn move_selection(&mut self, down: bool) {
+        self.selected = if down {
+            (self.selected + 1).min(self.tags.len().saturating_sub(1))
+        } else {
+            self.selected.saturating_sub(1)
+        };
+    }
+}
+
+/// 按任务数缩放的条形长度，至少一格
+fn bar_width(count: usize, max: usize, width: usize) -> usize {
+    if max == 0 {
+        return 0;
+    }
+    (count * width).div_ceil(max).max(1)
+}
+
+/// 居中渲染标签概览
+pub fn render(f: &mut Frame, area: Rect, overview: &TagOverview) {
+    let tag_width = overview
+        .tags
+        .iter()
+        .map(|(tag, _)| display_width(tag) + 1)
+        .max()
+        .unwrap_or(0)
+        .min(MAX_TAG_WIDTH);
+    let max_count = overview.tags.first().map(|(_, count)| *count).unwrap_or(0);
+    let count_width = max_count.to_string().len();
+
+    let width = ((tag_width + MAX_BAR_WIDTH + count_width + 8) as u16)
+        .max(36)
+        .min(area.width);
+    let height = (overview.tags.len().max(1) as u16 + 2).min(area.height.saturating_sub(4));
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+    let bar_space = (width as usize).saturating_sub(tag_width + count_width + 8);
+
+    let items: Vec<ListItem> = if overview.tags.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            " 当前项目没有标签",
+            Style::default().fg(Color::DarkGray),
+        )))]
+    } else {
+        overview
+            .tags
+            .iter()
+            .map(|(tag, count)| {
+                let color = tag_color(tag);
+                let label = truncate_to_width(&format!("#{}", tag), tag_width);
+                ListItem::new(Line::from(vec![
+                    Span::raw(" "),
+                    Span::styled(
+                        pad_to_width(&label, tag_width),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" "),
+                    Span::styled(
+                        "█".repeat(bar_width(*count, max_count, bar_space)),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(format!(" {}", count), Style::default().fg(Color::Gray)),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(
+                    " 标签 · {} ({}) ",
+                    overview.project_name,
+                    overview.tags.len()
+                ))
+                .title_bottom(Line::from(" j/k 选择 · Enter 筛选 · Esc 关闭 ").right_aligned())
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Rgb(136, 192, 208)))
+                .style(Style::default().bg(Color::Rgb(40, 40, 40))),
+        )
+        .highlight_style(Style::default().bg(Color::Rgb(41, 98, 218)));
+    let mut state = ListState::default()
+        .with_selected((!overview.tags.is_empty()).then_some(overview.selected));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProjectType, Task};
+    use std::path::PathBuf;
+
+    #[test]
+    fn tags_are_counted_and_sorted_by_frequency() {
+        let mut project = Project::new(
+            "web".to_string(),
+            PathBuf::from("/tmp/web"),
+            ProjectType::Global,
+        );
+        for (id, tags) in [(1, vec!["ui", "api"]), (2, vec!["api"]), (3, vec!["bug"])] {
+            let mut task = Task::new(id, format!("Task {}", id), "todo".to_string());
+            task.tags = tags.into_iter().map(str::to_string).collect();
+            project.tasks.push(task);
+        }
+
+        let mut overview = TagOverview::new(&project);
+        assert_eq!(
+            overview.tags,
+            vec![
+                ("api".to_string(), 2),
+                ("bug".to_string(), 1),
+                ("ui".to_string(), 1)
+            ]
+        );
+        overview.move_selection(true);
+        overview.move_selection(true);
+        overview.move_selection(true);
+        assert_eq!(overview.selected_tag(), Some("ui"));
+
+        assert_eq!(bar_width(2, 2, 30), 30);
+        assert_eq!(bar_width(1, 40, 30), 1);
+    }
+}
@@ -0,0 +1,273 @@
+/// 单行文本输入框（命令输入、项目搜索框等共用）
+///
+/// 光标按字符计位，支持中文等宽字符；内容超出宽度时水平滚动，保证光标可见。
+use crate::ui::width::display_width;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use unicode_width::UnicodeWidthChar;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextField {
+    value: String,
+    /// 光标位置（字符索引）
+    cursor: usize,
+}
+
+impl TextField {
+    /// 以给定内容创建，光标位于末尾
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            cursor: value.chars().count(),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    #[cfg(test)]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// 处理编辑按键，返回按键是否被输入框消费
+    ///
+    /// - 字符输入、Backspace、Delete
+    /// - ←/→、Home/End、Ctrl+A/E：移动光标
+    /// - Alt+b/f、Ctrl+←/→：按单词移动
+    /// - Ctrl+W：删除前一个单词；Ctrl+U：删除到行首；Ctrl+K：删除到行尾
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Char('w') if ctrl => self.delete_word_before(),
+            KeyCode::Char('u') if ctrl => self.delete_to_start(),
+            KeyCode::Char('k') if ctrl => self.delete_to_end(),
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.len(),
+            KeyCode::Char('b') if alt => self.cursor = self.word_start_before(),
+            KeyCode::Char('f') if alt => self.cursor = self.word_end_after(),
+            KeyCode::Left if ctrl || alt => self.cursor = self.word_start_before(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.word_end_after(),
+            KeyCode::Char(c) if !ctrl && !alt => self.insert(c),
+            KeyCode::Backspace => {
+                self.backspace();
+            }
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len(),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let idx = self.byte_index(self.cursor);
+        self.value.insert(idx, c);
+        self.cursor += 1;
+    }
+
+    /// 删除光标前的字符，输入框为空时返回 false
+    pub fn backspace(&mut self) -> bool {
+        if self.cursor == 0 {
+            return !self.value.is_empty();
+        }
+        self.cursor -= 1;
+        let idx = self.byte_index(self.cursor);
+        self.value.remove(idx);
+        true
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.len() {
+            let idx = self.byte_index(self.cursor);
+            self.value.remove(idx);
+        }
+    }
+
+    fn delete_word_before(&mut self) {
+        let start = self.word_start_before();
+        self.remove_range(start, self.cursor);
+        self.cursor = start;
+    }
+
+    fn delete_to_start(&mut self) {
+        self.remove_range(0, self.cursor);
+        self.cursor = 0;
+    }
+
+    fn delete_to_end(&mut self) {
+        self.remove_range(self.cursor, self.len());
+    }
+
+    fn remove_range(&mut self, start: usize, end: usize) {
+        let (start, end) = (self.byte_index(start), self.byte_index(end));
+        self.value.replace_range(start..end, "");
+    }
+
+    fn word_start_before(&self) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut pos = self.cursor;
+        while pos > 0 && !is_word_char(chars[pos - 1]) {
+            pos -= 1;
+        }
+        while pos > 0 && is_word_char(chars[pos - 1]) {
+            pos -= 1;
+        }
+        pos
+    }
+
+    fn word_end_after(&self) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut pos = self.cursor;
+        while pos < chars.len() && !is_word_char(chars[pos]) {
+            pos += 1;
+        }
+        while pos < chars.len() && is_word_char(chars[pos]) {
+            pos += 1;
+        }
+        pos
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+
+    /// 计算在给定宽度内可见的文本（水平滚动），返回 (光标前, 光标处字符, 光标后)
+    fn visible_parts(&self, width: usize) -> (String, Option<char>, String) {
+        let chars: Vec<char> = self.value.chars().collect();
+        let char_width = |c: char| c.width().unwrap_or(0);
+
+        // 光标本身至少占一列；从光标向左回退，直到填满宽度
+        let cursor_width = chars.get(self.cursor).map(|c| char_width(*c)).unwrap_or(1);
+        let mut start = self.cursor;
+        let mut used = cursor_width;
+        while start > 0 && used + char_width(chars[start - 1]) <= width {
+            start -= 1;
+            used += char_width(chars[start]);
+        }
+
+        let before: String = chars[start..self.cursor].iter().collect();
+        let at = chars.get(self.cursor).copied();
+
+        let mut after = String::new();
+        let mut used = display_width(&before) + cursor_width;
+        for c in chars.iter().skip(self.cursor + 1) {
+            let w = char_width(*c);
+            if used + w > width {
+                break;
+            }
+            used += w;
+            after.push(*c);
+        }
+
+        (before, at, after)
+    }
+
+    /// 渲染为一行文本，光标处使用反色块
+    pub fn line(
+        &self,
+        prefix: &str,
+        width: usize,
+        style: Style,
+        show_cursor: bool,
+    ) -> Line<'static> {
+        let available = width.saturating_sub(display_width(prefix)).max(1);
+        let (before, at, after) = self.visible_parts(available);
+
+        let mut spans = vec![
+            Span::styled(prefix.to_string(), style),
+            Span::styled(before, style),
+        ];
+        let cursor_text = at.map(|c| c.to_string()).unwrap_or_else(|| " ".to_string());
+        if show_cursor {
+            spans.push(Span::styled(
+                cursor_text,
+                Style::default()
+                    .bg(Color::Rgb(136, 192, 208))
+                    .fg(Color::Rgb(46, 52, 64)),
+            ));
+        } else {
+            spans.push(Span::styled(cursor_text, style));
+        }
+        spans.push(Span::styled(after, style));
+
+        Line::from(spans)
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn inserts_and_deletes_at_cursor() {
+        let mut field = TextField::new("helo");
+        field.handle_key(key(KeyCode::Left, KeyModifiers::NONE));
+        field.handle_key(key(KeyCode::Char('l'), KeyModifiers::NONE));
+        assert_eq!(field.value(), "hello");
+
+        field.handle_key(key(KeyCode::Home, KeyModifiers::NONE));
+        field.handle_key(key(KeyCode::Delete, KeyModifiers::NONE));
+        assert_eq!(field.value(), "ello");
+        assert!(field.backspace());
+        assert_eq!(field.value(), "ello");
+    }
+
+    #[test]
+    fn word_motions_and_deletions() {
+        let mut field = TextField::new("task-move todo done");
+        field.handle_key(key(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!(field.value(), "task-move todo ");
+
+        field.handle_key(key(KeyCode::Char('b'), KeyModifiers::ALT));
+        assert_eq!(field.cursor(), 10);
+        field.handle_key(key(KeyCode::Char('f'), KeyModifiers::ALT));
+        assert_eq!(field.cursor(), 14);
+
+        field.handle_key(key(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        assert_eq!(field.value(), " ");
+        assert_eq!(field.cursor(), 0);
+    }
+
+    #[test]
+    fn handles_wide_characters_and_scrolls_to_cursor() {
+        let mut field = TextField::new("中文");
+        field.handle_key(key(KeyCode::Backspace, KeyModifiers::NONE));
+        assert_eq!(field.value(), "中");
+
+        let field = TextField::new("abcdefghij");
+        let (before, at, after) = field.visible_parts(5);
+        assert_eq!((before.as_str(), at, after.as_str()), ("ghij", None, ""));
+    }
+}
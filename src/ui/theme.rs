@@ -0,0 +1,300 @@
+/// 配色方案
+///
+/// 优先级、通知级别等区分不能只依赖颜色：色盲友好方案使用 Okabe-Ito 配色，
+/// 并用 ▲ ■ ● 区分高/中/低优先级；无颜色方案（`NO_COLOR` / `hxk --no-color`）
+/// 在每帧绘制完成后去掉所有颜色，高亮背景改为反色显示。
+use crate::app::NotificationLevel;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Palette {
+    #[default]
+    Default,
+    ColorBlind,
+    NoColor,
+}
+
+/// 当前使用的配色方案（启动时由配置和环境决定）
+static PALETTE: AtomicU8 = AtomicU8::new(0);
+/// 命令行指定了 `--no-color`
+static NO_COLOR_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// 卡片颜色可选值：(frontmatter 中的名称, 菜单按键, 显示名)
+pub const CARD_COLORS: [(&str, &str, &str); 7] = [
+    ("red", "r", "红色"),
+    ("orange", "o", "橙色"),
+    ("yellow", "y", "黄色"),
+    ("green", "g", "绿色"),
+    ("cyan", "c", "青色"),
+    ("blue", "b", "蓝色"),
+    ("purple", "p", "紫色"),
+];
+
+/// 卡片背景色向看板背景混合的比例（百分比），保证文字仍然清晰
+const CARD_TINT_PERCENT: u16 = 14;
+/// 看板背景色（Nord），卡片底色在它的基础上混合
+const BOARD_BACKGROUND: (u8, u8, u8) = (46, 52, 64);
+
+/// 亮度高于此值的背景色视为高亮（选中、搜索匹配），无颜色方案下改为反色
+const HIGHLIGHT_LUMA: u32 = 70;
+
+impl Palette {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Palette::Default),
+            "color-blind" | "colorblind" => Some(Palette::ColorBlind),
+            "no-color" | "mono" => Some(Palette::NoColor),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Palette::Default => "default",
+            Palette::ColorBlind => "color-blind",
+            Palette::NoColor => "no-color",
+        }
+    }
+
+    /// 优先级标记（含尾随空格），无优先级时为两个空格
+    pub fn priority_marker(self, priority: Option<&str>) -> &'static str {
+        match (self, priority) {
+            (_, None) => "  ",
+            (Palette::Default, Some(_)) => "● ",
+            (_, Some("high")) => "▲ ",
+            (_, Some("medium")) => "■ ",
+            (_, Some(_)) => "● ",
+        }
+    }
+
+    pub fn priority_color(self, priority: Option<&str>) -> Color {
+        match (self, priority) {
+            (Palette::NoColor, _) => Color::Reset,
+            (Palette::Default, Some("high")) => Color::Red,
+            (Palette::Default, Some("medium")) => Color::Yellow,
+            (Palette::Default, Some("low")) => Color::Green,
+            (Palette::ColorBlind, Some("high")) => Color::Rgb(213, 94, 0), // vermillion
+            (Palette::ColorBlind, Some("medium")) => Color::Rgb(230, 159, 0), // orange
+            (Palette::ColorBlind, Some("low")) => Color::Rgb(86, 180, 233), // sky blue
+            _ => Color::Gray,
+        }
+    }
+
+    /// 任务卡片颜色（frontmatter 的 `color`），未知名称返回 None
+    ///
+    /// 色盲友好方案换成 Okabe-Ito 中对应的颜色，无颜色方案不着色
+    pub fn card_color(self, name: &str) -> Option<Color> {
+        let name = card_color_name(name)?;
+        let (r, g, b) = match (self, name) {
+            (Palette::NoColor, _) => return None,
+            (Palette::Default, "red") => (191, 97, 106),
+            (Palette::Default, "orange") => (208, 135, 112),
+            (Palette::Default, "yellow") => (235, 203, 139),
+            (Palette::Default, "green") => (163, 190, 140),
+            (Palette::Default, "cyan") => (136, 192, 208),
+            (Palette::Default, "blue") => (94, 129, 172),
+            (Palette::Default, "purple") => (180, 142, 173),
+            (_, "red") => (213, 94, 0),
+            (_, "orange") => (230, 159, 0),
+            (_, "yellow") => (240, 228, 66),
+            (_, "green") => (0, 158, 115),
+            (_, "cyan") => (86, 180, 233),
+            (_, "blue") => (0, 114, 178),
+            (_, _) => (204, 121, 167),
+        };
+        Some(Color::Rgb(r, g, b))
+    }
+
+    /// 任务卡片的底色：卡片颜色与看板背景混合
+    pub fn card_tint(self, name: &str) -> Option<Color> {
+        let Color::Rgb(r, g, b) = self.card_color(name)? else {
+            return None;
+        };
+        let mix = |c: u8, bg: u8| {
+            ((c as u16 * CARD_TINT_PERCENT + bg as u16 * (100 - CARD_TINT_PERCENT)) / 100) as u8
+        };
+        let (br, bg, bb) = BOARD_BACKGROUND;
+        Some(Color::Rgb(mix(r, br), mix(g, bg), mix(b, bb)))
+    }
+
+    /// 通知栏的 (背景色, 前景色)
+    pub fn notification_colors(self, level: NotificationLevel) -> (Color, Color) {
+        match (self, level) {
+            (Palette::NoColor, _) => (Color::Reset, Color::Reset),
+            (Palette::Default, NotificationLevel::Info) => (Color::Blue, Color::White),
+            (Palette::Default, NotificationLevel::Success) => (Color::Green, Color::White),
+            (Palette::Default, NotificationLevel::Warning) => (Color::Yellow, Color::Black),
+            (Palette::Default, NotificationLevel::Error) => (Color::Red, Color::White),
+            (Palette::ColorBlind, NotificationLevel::Info) => {
+                (Color::Rgb(0, 114, 178), Color::White)
+            }
+            (Palette::ColorBlind, NotificationLevel::Success) => {
+                (Color::Rgb(0, 158, 115), Color::Black)
+            }
+            (Palette::ColorBlind, NotificationLevel::Warning) => {
+                (Color::Rgb(240, 228, 66), Color::Black)
+            }
+            (Palette::ColorBlind, NotificationLevel::Error) => {
+                (Color::Rgb(213, 94, 0), Color::White)
+            }
+        }
+    }
+}
+
+/// 命令行 `--no-color`：强制使用无颜色方案
+pub fn request_no_color() {
+    NO_COLOR_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// 启动时确定配色方案：`--no-color` 或非空的 `NO_COLOR` 环境变量优先于配置
+pub fn init(configured: Palette) -> Palette {
+    let no_color = NO_COLOR_REQUESTED.load(Ordering::Relaxed)
+        || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let palette = if no_color {
+        Palette::NoColor
+    } else {
+        configured
+    };
+    PALETTE.store(palette as u8, Ordering::Relaxed);
+    palette
+}
+
+pub fn palette() -> Palette {
+    match PALETTE.load(Ordering::Relaxed) {
+        1 => Palette::ColorBlind,
+        2 => Palette::NoColor,
+        _ => Palette::Default,
+    }
+}
+
+/// 卡片上的优先级标记
+pub fn priority_span(priority: Option<&str>) -> Span<'static> {
+    let palette = palette();
+    Span::styled(
+        palette.priority_marker(priority),
+        Style::default().fg(palette.priority_color(priority)),
+    )
+}
+
+pub fn priority_color(priority: Option<&str>) -> Color {
+    palette().priority_color(priority)
+}
+
+/// 规范化卡片颜色名称（不区分大小写），不在可选范围内时返回 None
+pub fn card_color_name(name: &str) -> Option<&'static str> {
+    let name = name.trim();
+    CARD_COLORS
+        .iter()
+        .find(|(known, _, _)| known.eq_ignore_ascii_case(name))
+        .map(|(known, _, _)| *known)
+}
+
+pub fn card_color(name: &str) -> Option<Color> {
+    palette().card_color(name)
+}
+
+pub fn card_tint(name: &str) -> Option<Color> {
+    palette().card_tint(name)
+}
+
+pub fn notification_colors(level: NotificationLevel) -> (Color, Color) {
+    palette().notification_colors(level)
+}
+
+/// 无颜色方案：去掉缓冲区中的所有颜色，高亮背景改为反色
+pub fn strip_colors(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if is_highlight(cell.bg) {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+fn is_highlight(color: Color) -> bool {
+    match color {
+        Color::Reset | Color::Black => false,
+        Color::Rgb(r, g, b) => luma(r, g, b) > HIGHLIGHT_LUMA,
+        _ => true,
+    }
+}
+
+fn luma(r: u8, g: u8, b: u8) -> u32 {
+    (299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn color_blind_palettes_distinguish_priority_by_shape() {
+        for palette in [Palette::ColorBlind, Palette::NoColor] {
+            let markers: Vec<&str> = ["high", "medium", "low"]
+                .iter()
+                .map(|p| palette.priority_marker(Some(p)))
+                .collect();
+            assert_eq!(markers, vec!["▲ ", "■ ", "● "]);
+        }
+        assert_eq!(Palette::Default.priority_marker(Some("high")), "● ");
+        assert_eq!(Palette::NoColor.priority_color(Some("high")), Color::Reset);
+        assert_eq!(Palette::parse("colorblind"), Some(Palette::ColorBlind));
+    }
+
+    #[test]
+    fn card_colors_follow_palette() {
+        assert_eq!(
+            Palette::Default.card_color("Red"),
+            Some(Color::Rgb(191, 97, 106))
+        );
+        assert_eq!(
+            Palette::ColorBlind.card_color("red"),
+            Some(Color::Rgb(213, 94, 0))
+        );
+        assert_eq!(Palette::NoColor.card_color("red"), None);
+        assert_eq!(Palette::NoColor.card_tint("red"), None);
+        assert_eq!(Palette::Default.card_color("magenta"), None);
+        // 底色比搜索匹配的高亮背景暗，选中和匹配的卡片仍然醒目
+        for (name, _, _) in CARD_COLORS {
+            for palette in [Palette::Default, Palette::ColorBlind] {
+                let Some(Color::Rgb(r, g, b)) = palette.card_tint(name) else {
+                    panic!("{} 没有底色", name);
+                };
+                assert!(
+                    luma(r, g, b) < luma(60, 80, 100),
+                    "{} {:?}",
+                    name,
+                    (r, g, b)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn strip_colors_keeps_highlights_as_reverse_video() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer[(0, 0)]
+            .set_bg(Color::Rgb(41, 98, 218))
+            .set_fg(Color::White);
+        buffer[(1, 0)].set_bg(Color::Rgb(46, 52, 64));
+        buffer[(2, 0)].set_fg(Color::Red);
+
+        strip_colors(&mut buffer);
+
+        assert!(buffer[(0, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(
+            buffer
+                .content
+                .iter()
+                .all(|c| c.fg == Color::Reset && c.bg == Color::Reset)
+        );
+    }
+}
@@ -0,0 +1,266 @@
+/// 交互式教程（`:tutor`）：仿照 vimtutor，在临时生成的练习项目中逐步练习导航、新建、移动和分屏
+///
+/// 每次按键后检查看板的实际状态，当前步骤完成后自动进入下一步。
+use crate::app::{App, Mode};
+use crate::fs::project::Workflow;
+use crate::models::{Project, Task};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use std::path::{Path, PathBuf};
+
+/// 练习项目的名称
+pub const PROJECT_NAME: &str = "hxk-tutor";
+
+/// 练习项目中预置的任务 (标题, 状态)
+const SEED_TASKS: &[(&str, &str)] = &[
+    ("欢迎使用 hxk 教程", "todo"),
+    ("用 j/k 在任务之间上下移动", "todo"),
+    ("用 h/l 在列之间左右移动", "todo"),
+    ("这一列是进行中的任务", "doing"),
+];
+
+/// 教程的一个步骤：说明文字和完成条件
+pub struct TutorStep {
+    pub title: &'static str,
+    pub lines: &'static [&'static str],
+    done: fn(&App, &Tutor) -> bool,
+}
+
+const STEPS: &[TutorStep] = &[
+    TutorStep {
+        title: "上下移动",
+        lines: &[
+            "j 向下、k 向上选择任务",
+            "试试：按 j 选中 Todo 列的第二个任务",
+        ],
+        done: |app, tutor| app.focused_pane == tutor.pane_id && selection(app, tutor).1 >= 1,
+    },
+    TutorStep {
+        title: "左右移动",
+        lines: &["l 移到右边一列、h 移到左边一列", "试试：按 l 移到 Doing 列"],
+        done: |app, tutor| app.focused_pane == tutor.pane_id && selection(app, tutor).0 == 1,
+    },
+    TutorStep {
+        title: "新建任务",
+        lines: &["a 打开新建对话框，输入任务标题", "Ctrl+S 保存，Esc 取消"],
+        done: |app, tutor| project(app, tutor).is_some_and(|p| p.tasks.len() > SEED_TASKS.len()),
+    },
+    TutorStep {
+        title: "移动任务",
+        lines: &[
+            "L 把选中的任务移到右边一列，H 移回左边",
+            "试试：把任意一个任务移到 Done 列",
+        ],
+        done: |app, tutor| {
+            project(app, tutor).is_some_and(|p| p.tasks.iter().any(|t| t.status == "done"))
+        },
+    },
+    TutorStep {
+        title: "分屏",
+        lines: &[
+            "Space w v 垂直分屏，Space w s 水平分屏",
+            "新面板可以打开另一个项目",
+        ],
+        done: |app, tutor| pane_count(app) > tutor.panes_at_start,
+    },
+    TutorStep {
+        title: "关闭面板",
+        lines: &[
+            "Space w w 在面板之间切换",
+            "试试：按 Space w q 关闭刚才的新面板",
+        ],
+        done: |app, tutor| pane_count(app) <= tutor.panes_at_start,
+    },
+    TutorStep {
+        title: "查看帮助",
+        lines: &["? 打开完整的快捷键帮助", "试试：按 ? 打开帮助，Esc 关闭"],
+        done: |app, _| app.mode == Mode::Help,
+    },
+];
+
+/// 教程进度
+#[derive(Debug, Clone)]
+pub struct Tutor {
+    pub project_id: String,
+    /// 练习项目所在的临时目录，退出教程时删除
+    pub project_path: PathBuf,
+    /// 开始教程时聚焦的面板（打开练习项目的面板）
+    pub pane_id: usize,
+    /// 该面板原来显示的项目，退出教程时恢复
+    pub previous_project: Option<String>,
+    pub panes_at_start: usize,
+    pub step: usize,
+}
+
+impl Tutor {
+    pub fn current(&self) -> Option<&'static TutorStep> {
+        STEPS.get(self.step)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.step >= STEPS.len()
+    }
+}
+
+/// 从当前步骤开始跳过已完成的步骤，返回新的步骤序号
+pub fn next_step(app: &App, tutor: &Tutor) -> usize {
+    let mut step = tutor.step;
+    while STEPS.get(step).is_some_and(|s| (s.done)(app, tutor)) {
+        step += 1;
+    }
+    step
+}
+
+fn project<'a>(app: &'a App, tutor: &Tutor) -> Option<&'a Project> {
+    app.projects.iter().find(|p| p.id == tutor.project_id)
+}
+
+/// 练习面板选中的 (列, 任务)
+fn selection(app: &App, tutor: &Tutor) -> (usize, usize) {
+    (
+        app.selected_column
+            .get(&tutor.pane_id)
+            .copied()
+            .unwrap_or(0),
+        app.selected_task_index
+            .get(&tutor.pane_id)
+            .copied()
+            .unwrap_or(0),
+    )
+}
+
+fn pane_count(app: &App) -> usize {
+    app.split_tree.collect_pane_ids().len()
+}
+
+/// 练习项目的临时目录（按进程区分，同时运行多个 hxk 互不影响）
+pub fn practice_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("{}-{}", PROJECT_NAME, std::process::id()))
+}
+
+/// 在指定目录生成练习项目（todo / doing / done 和几个预置任务）
+pub fn create_practice_project(dir: &Path) -> Result<(), String> {
+    crate::fs::project::create_project_in(dir, PROJECT_NAME, Workflow::Basic)?;
+    for (i, (title, status)) in SEED_TASKS.iter().enumerate() {
+        let mut task = Task::new(i as u32 + 1, title.to_string(), status.to_string());
+        task.order = (i as i32 + 1) * 1000;
+        crate::fs::save_task(dir, &task)?;
+    }
+    Ok(())
+}
+
+/// 在右下角渲染教程浮层
+pub fn render(f: &mut Frame, area: Rect, tutor: &Tutor) {
+    let accent_color = Color::Rgb(136, 192, 208);
+    let (title, lines): (String, &[&str]) = match tutor.current() {
+        Some(step) => (
+            format!(" 教程 {}/{} · {} ", tutor.step + 1, STEPS.len(), step.title),
+            step.lines,
+        ),
+        None => (
+            " 教程 · 全部完成 ".to_string(),
+            &[
+                "你已经掌握了基本操作！",
+                "再次输入 :tutor 退出，练习项目会被删除",
+            ],
+        ),
+    };
+
+    let mut text: Vec<Line> = lines
+        .iter()
+        .map(|line| Line::from(format!(" {}", line)))
+        .collect();
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::raw(" "),
+        Span::styled(
+            "●".repeat(tutor.step.min(STEPS.len())),
+            Style::default().fg(Color::Rgb(163, 190, 140)),
+        ),
+        Span::styled(
+            "○".repeat(STEPS.len().saturating_sub(tutor.step)),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]));
+
+    let width = 46.min(area.width);
+    let height = (text.len() as u16 + 2).min(area.height);
+    // 留出底部状态栏的位置
+    let popup = Rect {
+        x: area.x + area.width - width,
+        y: area.y + area.height.saturating_sub(height + 1),
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(accent_color)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Line::from(" :tutor 退出 ").right_aligned())
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(accent_color))
+        .style(Style::default().bg(Color::Rgb(40, 40, 40)));
+
+    f.render_widget(Clear, popup);
+    f.render_widget(Paragraph::new(text).block(block), popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectType;
+    use crate::ui::layout::SplitNode;
+
+    #[test]
+    fn steps_advance_as_the_board_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        create_practice_project(dir.path()).unwrap();
+        let project = crate::fs::load_project_with_type(dir.path(), ProjectType::Global).unwrap();
+        assert_eq!(project.tasks.len(), SEED_TASKS.len());
+
+        let mut app = crate::app::tests::test_app();
+        let tutor = Tutor {
+            project_id: project.id.clone(),
+            project_path: dir.path().to_path_buf(),
+            pane_id: 0,
+            previous_project: Some("alpha".to_string()),
+            panes_at_start: 1,
+            step: 0,
+        };
+        app.projects.push(project);
+        assert_eq!(next_step(&app, &tutor), 0);
+
+        app.selected_task_index.insert(0, 1);
+        app.selected_column.insert(0, 1);
+        assert_eq!(next_step(&app, &tutor), 2);
+
+        let tutor = Tutor { step: 2, ..tutor };
+        let project = app.projects.last_mut().unwrap();
+        project
+            .tasks
+            .push(Task::new(5, "我的任务".to_string(), "todo".to_string()));
+        project.tasks[0].status = "done".to_string();
+        assert_eq!(next_step(&app, &tutor), 4);
+
+        let tutor = Tutor { step: 4, ..tutor };
+        app.split_tree = crate::app::tests::three_pane_layout();
+        assert_eq!(next_step(&app, &tutor), 5);
+
+        let tutor = Tutor { step: 5, ..tutor };
+        app.split_tree = SplitNode::Leaf {
+            id: 0,
+            project_id: None,
+        };
+        app.mode = Mode::Help;
+        let step = next_step(&app, &tutor);
+        assert!(Tutor { step, ..tutor }.is_finished());
+    }
+}
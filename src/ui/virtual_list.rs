@@ -0,0 +1,167 @@
+/// 任务列表虚拟化
+///
+/// 几百个任务的列每帧只构建可见的行：根据上一帧的滚动位置和选中项算出可见范围，
+/// 只把这些行交给 `List` 渲染。未展开的卡片按内容缓存，任务没有变化时直接复用上一帧的 `ListItem`。
+use ratatui::widgets::ListItem;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// 可见范围：`start` 是新的滚动位置
+///
+/// 与 `List` 自身的滚动规则一致：选中项在滚动位置之前时滚到选中项，
+/// 在可见区域之后时滚到刚好能显示选中项的位置。`row_height` 只会对可见范围附近的行调用。
+pub fn visible_range(
+    len: usize,
+    selected: Option<usize>,
+    offset: usize,
+    height: usize,
+    mut row_height: impl FnMut(usize) -> usize,
+) -> Range<usize> {
+    if len == 0 {
+        return 0..0;
+    }
+    let mut start = offset.min(len - 1);
+    if let Some(selected) = selected.map(|s| s.min(len - 1)) {
+        if selected < start {
+            start = selected;
+        } else {
+            // 从选中项向上累加行高，找到能同时显示它的最小滚动位置
+            let mut first = selected;
+            let mut used = row_height(selected);
+            while first > start {
+                let above = row_height(first - 1);
+                if used + above > height {
+                    break;
+                }
+                used += above;
+                first -= 1;
+            }
+            start = first;
+        }
+    }
+
+    let mut end = start;
+    let mut used = 0;
+    while end < len && used < height {
+        used += row_height(end);
+        end += 1;
+    }
+    // 列表变短后不留空白：末尾不足一屏时向上补满
+    while end == len && start > 0 {
+        let above = row_height(start - 1);
+        if used + above > height {
+            break;
+        }
+        used += above;
+        start -= 1;
+    }
+    start..end
+}
+
+/// 决定卡片外观的任务内容，全部相同时复用缓存
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CardKey {
+    title: String,
+    priority: Option<String>,
+    tags: Vec<String>,
+    color: Option<String>,
+    marked: bool,
+    width: usize,
+}
+
+/// 超过此数量时清空项目的缓存（删除、改号的任务不会一直留在缓存中）
+const CARD_CACHE_LIMIT: usize = 2048;
+
+/// 未展开卡片的缓存：项目 ID -> 任务 ID -> 上次构建的卡片
+#[derive(Debug, Default)]
+pub struct CardCache {
+    cards: HashMap<String, HashMap<u32, (CardKey, ListItem<'static>)>>,
+}
+
+impl CardCache {
+    /// 取出缓存的卡片，任务内容或列宽变化时重新构建
+    pub fn get_or_build(
+        &mut self,
+        project_id: &str,
+        task: &crate::models::Task,
+        marked: bool,
+        width: usize,
+        build: impl FnOnce() -> ListItem<'static>,
+    ) -> ListItem<'static> {
+        let cards = match self.cards.get_mut(project_id) {
+            Some(cards) => cards,
+            None => self.cards.entry(project_id.to_string()).or_default(),
+        };
+        if let Some((key, item)) = cards.get(&task.id)
+            && key.title == task.title
+            && key.priority == task.priority
+            && key.tags == task.tags
+            && key.color.as_deref() == task.color()
+            && key.marked == marked
+            && key.width == width
+        {
+            return item.clone();
+        }
+
+        if cards.len() >= CARD_CACHE_LIMIT {
+            cards.clear();
+        }
+        let item = build();
+        let key = CardKey {
+            title: task.title.clone(),
+            priority: task.priority.clone(),
+            tags: task.tags.clone(),
+            color: task.color().map(str::to_string),
+            marked,
+            width,
+        };
+        cards.insert(task.id, (key, item.clone()));
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Task;
+
+    #[test]
+    fn visible_range_follows_selection_like_list() {
+        let ones = |_| 1;
+        assert_eq!(visible_range(0, Some(0), 0, 10, ones), 0..0);
+        assert_eq!(visible_range(500, None, 0, 10, ones), 0..10);
+        // 选中项在可见区域之后：滚到刚好显示它
+        assert_eq!(visible_range(500, Some(42), 0, 10, ones), 33..43);
+        // 选中项仍可见时保持滚动位置
+        assert_eq!(visible_range(500, Some(35), 33, 10, ones), 33..43);
+        // 选中项在滚动位置之前
+        assert_eq!(visible_range(500, Some(5), 33, 10, ones), 5..15);
+        // 列表变短后显示最后一屏
+        assert_eq!(visible_range(12, None, 40, 10, ones), 2..12);
+        // 展开的卡片占多行
+        let tall = |i| if i == 3 { 5 } else { 1 };
+        assert_eq!(visible_range(20, Some(4), 0, 6, tall), 3..5);
+    }
+
+    #[test]
+    fn cards_are_rebuilt_only_when_the_task_changes() {
+        let mut cache = CardCache::default();
+        let mut task = Task::new(1, "first".to_string(), "todo".to_string());
+        let builds = std::cell::Cell::new(0);
+        let get = |cache: &mut CardCache, task: &Task, width| {
+            cache.get_or_build("p", task, false, width, || {
+                builds.set(builds.get() + 1);
+                ListItem::new(task.title.clone())
+            });
+        };
+
+        get(&mut cache, &task, 30);
+        get(&mut cache, &task, 30);
+        task.tags.push("api".to_string());
+        get(&mut cache, &task, 30);
+        get(&mut cache, &task, 40);
+        task.set_color(Some("red"));
+        get(&mut cache, &task, 40);
+        assert_eq!(builds.get(), 4);
+    }
+}
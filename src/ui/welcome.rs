@@ -54,7 +54,7 @@ pub fn render(f: &mut Frame, area: Rect, config: &crate::config::Config) {
         Line::from(vec![
             Span::styled("  Markdown 预览: ", Style::default().fg(Color::DarkGray)),
             Span::styled(
-                &config.markdown_viewer,
+                crate::config::viewer_label(&config.markdown_viewer),
                 Style::default()
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
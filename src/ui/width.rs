@@ -0,0 +1,77 @@
+/// 文本显示宽度工具（按终端列数计算，中文/emoji 占两列）
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 计算文本显示宽度
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// 按显示宽度截断文本，超出时以省略号结尾（不会切断多字节字符）
+pub fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    // 预留省略号的一列
+    let budget = max_width - 1;
+    let mut result = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        result.push(c);
+    }
+    result.push('…');
+    result
+}
+
+/// 用空格将文本补齐到指定显示宽度（用于表格对齐）
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    let current = display_width(text);
+    if current >= width {
+        return text.to_string();
+    }
+    format!("{}{}", text, " ".repeat(width - current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_characters_count_as_two_columns() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("中文"), 4);
+    }
+
+    #[test]
+    fn short_titles_are_not_truncated() {
+        assert_eq!(truncate_to_width("Fix login", 20), "Fix login");
+    }
+
+    #[test]
+    fn long_titles_end_with_ellipsis_within_width() {
+        let truncated = truncate_to_width("Implement the checkout flow", 10);
+        assert_eq!(truncated, "Implement…");
+        assert_eq!(display_width(&truncated), 10);
+    }
+
+    #[test]
+    fn truncation_never_splits_or_overflows_wide_characters() {
+        let truncated = truncate_to_width("实现结账流程", 6);
+        assert_eq!(truncated, "实现…");
+        assert!(display_width(&truncated) <= 6);
+    }
+
+    #[test]
+    fn padding_uses_display_width() {
+        assert_eq!(pad_to_width("中文", 6), "中文  ");
+        assert_eq!(pad_to_width("toolong", 3), "toolong");
+    }
+}
@@ -0,0 +1,230 @@
+/// 磁盘 IO 工作线程
+///
+/// 启动时加载项目任务、重新加载项目（`:reload`、外部编辑器或 shell 返回后）和保存界面状态
+/// 在工作线程中执行，主循环每帧取回已完成的结果。任务的增删改仍同步写入，以便立即提示错误。网络文件系统或大项目上读写较慢时，按键和重绘不会卡住。
+/// 请求按发送顺序处理；同一项目在结果返回前又被同步重新加载过时，旧结果会被丢弃。
+use crate::models::{Project, ProjectType};
+use crate::state::AppState;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+
+enum IoRequest {
+    LoadProject {
+        id: String,
+        seq: u64,
+        path: PathBuf,
+        project_type: ProjectType,
+        initial: bool,
+    },
+    SaveState(Box<AppState>),
+}
+
+/// IO 线程加载完成的项目
+pub struct ProjectLoaded {
+    pub id: String,
+    seq: u64,
+    pub result: Result<Project, String>,
+    /// 启动时的首次加载（之前只有项目配置、没有任务，不需要对比外部修改）
+    pub initial: bool,
+}
+
+pub struct IoWorker {
+    sender: Option<Sender<IoRequest>>,
+    receiver: Receiver<ProjectLoaded>,
+    /// 后台保存失败的错误信息
+    errors: Receiver<String>,
+    thread: Option<JoinHandle<()>>,
+    /// 等待中的项目加载：项目 ID → (最新请求的序号, 是否仍是首次加载)
+    loading: HashMap<String, (u64, bool)>,
+    next_seq: u64,
+}
+
+impl IoWorker {
+    pub fn spawn() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<IoRequest>();
+        let (response_tx, response_rx) = mpsc::channel();
+        let (error_tx, error_rx) = mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            for request in request_rx {
+                match request {
+                    IoRequest::LoadProject {
+                        id,
+                        seq,
+                        path,
+                        project_type,
+                        initial,
+                    } => {
+                        let result = crate::fs::load_project_with_type(&path, project_type);
+                        let loaded = ProjectLoaded {
+                            id,
+                            seq,
+                            result,
+                            initial,
+                        };
+                        if response_tx.send(loaded).is_err() {
+                            break;
+                        }
+                    }
+                    IoRequest::SaveState(state) => {
+                        if let Err(e) = crate::state::save_state(&state) {
+                            let _ = error_tx.send(format!("保存界面状态失败: {}", e));
+                        }
+                    }
+                }
+            }
+        });
+        Self {
+            sender: Some(request_tx),
+            receiver: response_rx,
+            errors: error_rx,
+            thread: Some(thread),
+            loading: HashMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// 在后台重新加载项目，结果由 `poll` 取回
+    pub fn load_project(&mut self, project: &Project) {
+        self.request_load(project, false);
+    }
+
+    /// 启动时在后台加载只读取了配置的项目的任务
+    pub fn load_initial(&mut self, project: &Project) {
+        self.request_load(project, true);
+    }
+
+    fn request_load(&mut self, project: &Project, initial: bool) {
+        self.next_seq += 1;
+        // 首次加载完成前又请求重新加载时，内存中仍只有项目配置，结果同样按首次加载处理
+        let initial = initial || self.loading.get(&project.id).is_some_and(|&(_, i)| i);
+        self.loading
+            .insert(project.id.clone(), (self.next_seq, initial));
+        self.send(IoRequest::LoadProject {
+            id: project.id.clone(),
+            seq: self.next_seq,
+            path: project.path.clone(),
+            project_type: project.project_type,
+            initial,
+        });
+    }
+
+    /// 在后台保存界面状态
+    pub fn save_state(&self, state: AppState) {
+        self.send(IoRequest::SaveState(Box::new(state)));
+    }
+
+    pub fn is_loading(&self, project_id: &str) -> bool {
+        self.loading.contains_key(project_id)
+    }
+
+    /// 项目已同步重新加载，放弃等待中的后台加载结果
+    pub fn forget(&mut self, project_id: &str) {
+        self.loading.remove(project_id);
+    }
+
+    /// 取出一个已完成、且仍是该项目最新请求的加载结果
+    pub fn poll(&mut self) -> Option<ProjectLoaded> {
+        while let Ok(loaded) = self.receiver.try_recv() {
+            if self.loading.get(&loaded.id).map(|&(seq, _)| seq) == Some(loaded.seq) {
+                self.loading.remove(&loaded.id);
+                return Some(loaded);
+            }
+        }
+        None
+    }
+
+    /// 取出一条后台保存失败的错误
+    pub fn poll_error(&mut self) -> Option<String> {
+        self.errors.try_recv().ok()
+    }
+
+    /// 等待已排队的请求处理完（退出前调用，避免后台保存覆盖最后一次同步保存）
+    pub fn shutdown(&mut self) {
+        self.sender = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    fn send(&self, request: IoRequest) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(request);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    fn project(dir: &TempDir) -> Project {
+        std::fs::write(
+            dir.path().join(".kanban.toml"),
+            "name = \"web\"\ncreated = \"0\"\n\n[statuses]\norder = [\"todo\"]\n\n[statuses.todo]\ndisplay = \"Todo\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("todo")).unwrap();
+        crate::fs::load_project(dir.path()).unwrap()
+    }
+
+    fn wait(worker: &mut IoWorker) -> Option<ProjectLoaded> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !worker.loading.is_empty() && Instant::now() < deadline {
+            if let Some(loaded) = worker.poll() {
+                return Some(loaded);
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        None
+    }
+
+    #[test]
+    fn loads_projects_in_the_background() {
+        let dir = TempDir::new().unwrap();
+        let project = project(&dir);
+        let mut worker = IoWorker::spawn();
+
+        worker.load_project(&project);
+        assert!(worker.is_loading(&project.id));
+        let loaded = wait(&mut worker).unwrap();
+        assert_eq!(loaded.id, project.id);
+        assert!(!loaded.initial);
+        assert_eq!(loaded.result.unwrap().name, "web");
+        assert!(!worker.is_loading(&project.id));
+    }
+
+    #[test]
+    fn initial_loads_fill_in_tasks_of_header_only_projects() {
+        let dir = TempDir::new().unwrap();
+        project(&dir);
+        let task = crate::models::Task::new(1, "x".to_string(), "todo".to_string());
+        crate::fs::save_task(dir.path(), &task).unwrap();
+        let header =
+            crate::fs::project::load_project_header_with_type(dir.path(), ProjectType::Global)
+                .unwrap();
+        assert!(header.tasks.is_empty());
+        let mut worker = IoWorker::spawn();
+
+        worker.load_initial(&header);
+        worker.load_project(&header);
+        let loaded = wait(&mut worker).unwrap();
+        assert!(loaded.initial);
+        assert_eq!(loaded.result.unwrap().tasks.len(), 1);
+    }
+
+    #[test]
+    fn forgotten_loads_are_dropped() {
+        let dir = TempDir::new().unwrap();
+        let project = project(&dir);
+        let mut worker = IoWorker::spawn();
+
+        worker.load_project(&project);
+        worker.forget(&project.id);
+        worker.shutdown();
+        assert!(worker.poll().is_none());
+    }
+}